@@ -0,0 +1,49 @@
+/**
+ * Large upload benchmark: queue.writeBuffer vs queue.uploadBuffer
+ *
+ * Usage: node benchmarks/large-upload.js [sizeMiB] [iterations]
+ */
+const { Gpu, GPUBufferUsage } = require('../webgpu.js')
+
+async function measure(device, label, upload, bytes, iterations) {
+    // Warm up allocations and pipelines
+    upload()
+    await device.waitIdle()
+
+    const start = process.hrtime.bigint()
+    for (let i = 0; i < iterations; i++) {
+        upload()
+        await device.waitIdle()
+    }
+    const seconds = Number(process.hrtime.bigint() - start) / 1e9
+    const throughput = (bytes * iterations) / seconds / (1024 * 1024 * 1024)
+
+    console.log(`  ${label.padEnd(14)} ${(seconds * 1000 / iterations).toFixed(1).padStart(8)} ms/upload  ${throughput.toFixed(2)} GiB/s`)
+}
+
+async function main() {
+    const sizeMiB = Number(process.argv[2] || 256)
+    const iterations = Number(process.argv[3] || 5)
+    const bytes = sizeMiB * 1024 * 1024
+
+    const adapter = await Gpu().requestAdapter({ powerPreference: 'high-performance' })
+    const device = await adapter.requestDevice({
+        requiredLimits: { maxBufferSize: bytes }
+    })
+    console.log(`Uploading ${sizeMiB} MiB x ${iterations} on ${adapter.info.name} (${adapter.info.backend})\n`)
+
+    const data = new Uint8Array(bytes)
+    for (let i = 0; i < bytes; i += 4096) data[i] = i & 0xff
+    const buffer = device.createBuffer({ size: bytes, usage: GPUBufferUsage.COPY_DST | GPUBufferUsage.STORAGE })
+
+    await measure(device, 'writeBuffer', () => device.queue.writeBuffer(buffer, 0, data), bytes, iterations)
+    await measure(device, 'uploadBuffer', () => device.queue.uploadBuffer(buffer, 0, data), bytes, iterations)
+
+    buffer.destroy()
+    device.destroy()
+}
+
+main().catch((error) => {
+    console.error(error)
+    process.exit(1)
+})
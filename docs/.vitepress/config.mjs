@@ -67,6 +67,7 @@ export default defineConfig({
             { text: 'Texture', link: '/api/texture' },
             { text: 'Pipeline', link: '/api/pipeline' },
             { text: 'Command Encoder', link: '/api/command-encoder' },
+            { text: 'Surface', link: '/api/surface' },
           ],
         },
       ],
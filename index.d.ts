@@ -3,19 +3,89 @@
 
 /* auto-generated by NAPI-RS */
 
+/** GPU instance options */
+export interface GpuOptions {
+  /**
+   * Backends to use: "vulkan", "metal", "dx12" and "gl" (OpenGL / GLES,
+   * including ANGLE's EGL). Defaults to `WGPU_BACKEND` or all backends.
+   * `Gpu({ backends: ['null'] })` in webgpu.js gives a driverless adapter
+   * for tests instead; "null" can't be combined with other backends
+   */
+  backends?: Array<string>
+  /**
+   * DX12 shader compiler: "fxc" or "dxc". DXC is needed for Shader Model 6
+   * features such as f16. Defaults to `WGPU_DX12_COMPILER` or "fxc"
+   */
+  dx12ShaderCompiler?: string
+  /** Path to `dxil.dll` or its directory, for "dxc" */
+  dxilPath?: string
+  /** Path to `dxcompiler.dll` or its directory, for "dxc" */
+  dxcPath?: string
+  /**
+   * Instance flags: "debug", "validation", "discard-hal-labels" and
+   * "allow-noncompliant-adapters". Defaults to debug and validation in
+   * debug builds; `WGPU_VALIDATION`, `WGPU_DEBUG` etc. still apply
+   */
+  instanceFlags?: Array<string>
+  /**
+   * OpenGL ES 3.x minor version the GL backend requests: 0, 1 or 2.
+   * Defaults to `WGPU_GLES_MINOR_VERSION` or the highest available
+   */
+  glesMinorVersion?: number
+}
+/**
+ * Adapter selection policy for `requestAdapter({ selector })` (extension)
+ *
+ * `vendor` and `device` must match; among the adapters that do, one of the
+ * preferred device type, then one on the preferred backend, is picked.
+ * Remaining ties keep the `enumerateAdapters()` order.
+ */
+export interface AdapterSelector {
+  /**
+   * Preferred device type: "discrete", "integrated", "virtual", "cpu" or "other".
+   * Defaults to "discrete" for the "high-performance" power preference and
+   * "integrated" for "low-power"
+   */
+  deviceType?: string
+  /** Preferred backend: "vulkan", "metal", "dx12" or "gl" */
+  backend?: string
+  /** PCI vendor ID the adapter must have, e.g. 0x10de for NVIDIA */
+  vendor?: number
+  /** PCI device ID the adapter must have */
+  device?: number
+}
 export interface AdapterInfo {
   name: string
   vendor: number
   device: number
   deviceType: string
   backend: string
+  /** Driver name, e.g. "NVIDIA" or "llvmpipe" (empty if the backend doesn't report it) */
+  driver: string
+  /** Driver version and build details (empty if the backend doesn't report it) */
+  driverInfo: string
+  /**
+   * API version the driver implements, e.g. "1.3.255" for Vulkan
+   *
+   * Only reported by the Vulkan backend.
+   */
+  backendVersion?: string
 }
-export interface AdapterLimits {
-  maxTextureDimension1D: number
-  maxTextureDimension2D: number
-  maxTextureDimension3D: number
-  maxBindGroups: number
-  maxBufferSize: number
+/** What an adapter supports below full WebGPU */
+export interface DownlevelCapabilities {
+  /** Whether the adapter supports all of WebGPU */
+  compliant: boolean
+  /** Whether compute pipelines can be used */
+  computeShaders: boolean
+  /** Supported downlevel flags, e.g. "compute-shaders", "indirect-execution" */
+  flags: Array<string>
+  /** "sm2", "sm4" or "sm5" */
+  shaderModel: string
+}
+/** Contents of one `getMappedRange()` range, written back by `unmap()` */
+export interface MappedRangeData {
+  offset: number
+  data: Buffer
 }
 /** Texture descriptor */
 export interface TextureDescriptor {
@@ -23,11 +93,39 @@ export interface TextureDescriptor {
   width: number
   height: number
   depth?: number
-  format: string
-  usage: number
-  dimension?: string
+  format: string | number
+  /** GPUTextureUsage bit mask or flag names, e.g. ["TEXTURE_BINDING", "COPY_DST"] */
+  usage: number | Array<string>
+  dimension?: string | number
   mipLevelCount?: number
   sampleCount?: number
+  /**
+   * Initial contents, uploaded right after creation (extension). Without
+   * `dataLayout` they are tightly packed and fill mip level 0 or every mip
+   * level, layer by layer; COPY_DST usage is added implicitly
+   */
+  data?: Buffer
+  /** Layout of `data`, which then fills mip level 0 (extension) */
+  dataLayout?: TextureDataLayout
+}
+/** Texture view descriptor */
+export interface TextureViewDescriptor {
+  label?: string
+  format?: string | number
+  dimension?: string | number
+  /** "all" (default), "depth-only" or "stencil-only" */
+  aspect?: string | number
+  baseMipLevel?: number
+  mipLevelCount?: number
+  baseArrayLayer?: number
+  arrayLayerCount?: number
+}
+/** Options for `createLayerView` */
+export interface LayerViewOptions {
+  label?: string
+  mipLevel?: number
+  /** "all" (default), "depth-only" or "stencil-only" */
+  aspect?: string | number
 }
 /** Buffer usage flags object */
 export interface BufferUsage {
@@ -61,6 +159,38 @@ export interface TextureUsage {
 }
 /** Get texture usage constants */
 export declare function textureUsage(): TextureUsage
+/** Shader stage visibility flags object */
+export interface ShaderStage {
+  vertex: number
+  fragment: number
+  compute: number
+}
+/** Get shader stage constants */
+export declare function shaderStage(): ShaderStage
+/** Command stream opcodes object */
+export interface CommandOpcode {
+  setPipeline: number
+  setBindGroup: number
+  setVertexBuffer: number
+  setIndexBuffer: number
+  draw: number
+  drawIndexed: number
+  drawIndirect: number
+  drawIndexedIndirect: number
+  dispatch: number
+  dispatchIndirect: number
+  /** Buffer size argument for the rest of the buffer */
+  wholeSize: number
+}
+/** Get command stream opcodes for `executeCommandStream()` */
+export declare function commandOpcode(): CommandOpcode
+/**
+ * Get numeric descriptor enum constants, keyed by constant object name
+ *
+ * Descriptor fields accept these in place of the WebGPU strings, e.g.
+ * `GPUTextureFormat.RGBA8UNORM` for 'rgba8unorm', and skip string matching.
+ */
+export declare function descriptorEnums(): Record<string, Record<string, number>>
 /**
  * WebGPU-compliant bind group entry descriptor (without resource references)
  * Resources are passed separately to avoid napi-rs External serialization issues
@@ -70,6 +200,14 @@ export interface BindGroupEntry {
   resourceType: string
   offset?: number
   size?: number
+  /** Number of resources an array entry takes from its resource list */
+  count?: number
+  /**
+   * Offset and size of each buffer of a "buffer-array" entry; `null`
+   * sizes bind the rest of the buffer
+   */
+  offsets?: Array<number>
+  sizes?: Array<number | undefined | null>
 }
 /** Compute pass descriptor (simplified) */
 export interface ComputePassDescriptor {
@@ -78,7 +216,8 @@ export interface ComputePassDescriptor {
 /** Render pass descriptor (simplified - views passed separately) */
 export interface RenderPassDescriptor {
   label?: string
-  colorAttachments: Array<RenderPassColorAttachment>
+  /** `null` entries skip that color location */
+  colorAttachments: Array<RenderPassColorAttachment | undefined | null>
   depthStencilAttachment?: RenderPassDepthStencilAttachment
 }
 /**
@@ -87,8 +226,13 @@ export interface RenderPassDescriptor {
  */
 export interface RenderPassColorAttachment {
   clearValue?: Color
-  loadOp: string
-  storeOp: string
+  loadOp: string | number
+  storeOp: string | number
+  /**
+   * Render into a multisampled target with this many samples, cached per
+   * device, and resolve it into the view (extension)
+   */
+  sampleCount?: number
 }
 export interface Color {
   r: number
@@ -102,37 +246,98 @@ export interface Color {
  */
 export interface RenderPassDepthStencilAttachment {
   depthClearValue?: number
-  depthLoadOp?: string
-  depthStoreOp?: string
+  depthLoadOp?: string | number
+  depthStoreOp?: string | number
   stencilClearValue?: number
-  stencilLoadOp?: string
-  stencilStoreOp?: string
+  stencilLoadOp?: string | number
+  stencilStoreOp?: string | number
+  /** Depth is only tested, never written, so the pass can also sample it */
+  depthReadOnly?: boolean
+  stencilReadOnly?: boolean
 }
 /** Sampler descriptor */
 export interface SamplerDescriptor {
   label?: string
-  addressModeU?: string
-  addressModeV?: string
-  addressModeW?: string
-  magFilter?: string
-  minFilter?: string
-  mipmapFilter?: string
+  addressModeU?: string | number
+  addressModeV?: string | number
+  addressModeW?: string | number
+  magFilter?: string | number
+  minFilter?: string | number
+  mipmapFilter?: string | number
   lodMinClamp?: number
   lodMaxClamp?: number
-  compare?: string
+  compare?: string | number
   maxAnisotropy?: number
 }
+/**
+ * Render bundle encoder descriptor following WebGPU spec
+ *
+ * Must match the attachments of the render passes the bundle is executed in.
+ */
+export interface RenderBundleEncoderDescriptor {
+  label?: string
+  /** Color attachment formats; `null` skips a color location */
+  colorFormats: Array<string | number | undefined | null>
+  depthStencilFormat?: string | number
+  /** The bundle doesn't write depth, so it can run in passes with a read-only depth aspect */
+  depthReadOnly?: boolean
+  /** The bundle doesn't write stencil, so it can run in passes with a read-only stencil aspect */
+  stencilReadOnly?: boolean
+  /** Sample count of the pass attachments (default 1) */
+  sampleCount?: number
+}
+/** Render bundle descriptor following WebGPU spec */
+export interface RenderBundleDescriptor {
+  label?: string
+}
+/** Device descriptor following WebGPU spec */
+export interface DeviceDescriptor {
+  label?: string
+  /** Feature names as reported by `adapter.features` */
+  requiredFeatures?: Array<string>
+  /**
+   * Limit values by WebGPU name, or a preset name: "default" (the WebGPU
+   * defaults), "downlevel" or "adapterMaximum"
+   */
+  requiredLimits?: Record<string, number> | 'default' | 'downlevel' | 'adapterMaximum'
+  /** Preset that object `requiredLimits` apply on top of (extension) */
+  limitsPreset?: 'default' | 'downlevel' | 'adapterMaximum'
+}
+/** Options for `device.enableFaultInjection()` (extension) */
+export interface FaultInjectionOptions {
+  /** Fail every nth buffer, texture or query set creation as out of memory */
+  oomEveryN?: number
+  /** Fail the next create call with a validation error */
+  failNextCreate?: boolean
+}
 /** Buffer descriptor following WebGPU spec */
 export interface BufferDescriptor {
   label?: string
   size: number
-  usage: number
+  /** GPUBufferUsage bit mask or flag names, e.g. ["COPY_DST", "STORAGE"] */
+  usage: number | Array<string>
   mappedAtCreation?: boolean
 }
-/** Shader module descriptor following WebGPU spec */
+/**
+ * Descriptor for creating a buffer from initial contents
+ * Note: the data is passed as a separate parameter
+ */
+export interface BufferInitDescriptor {
+  label?: string
+  /** GPUBufferUsage bit mask or flag names */
+  usage: number | Array<string>
+}
+/**
+ * Shader module descriptor following WebGPU spec
+ * Note: the include resolver callback is passed as separate parameter due to napi-rs limitations
+ */
 export interface ShaderModuleDescriptor {
   label?: string
   code: string
+  /** Preprocessor defines (extension) */
+  defines?: Record<string, string>
+  /** Directories searched for `#include` files (extension) */
+  includePaths?: Array<string>
 }
 /**
  * Pipeline layout descriptor following WebGPU spec
@@ -156,7 +361,7 @@ export interface CommandEncoderDescriptor {
 /** Query set descriptor following WebGPU spec */
 export interface QuerySetDescriptor {
   label?: string
-  type: string
+  type: string | number
   count: number
 }
 /**
@@ -178,24 +383,30 @@ export interface BindGroupLayoutEntry {
   sampler?: SamplerBindingLayout
   texture?: TextureBindingLayout
   storageTexture?: StorageTextureBindingLayout
+  /**
+   * Makes the binding an array of this many resources (extension); buffer
+   * arrays need the "wgpu-buffer-binding-array" feature, texture and
+   * sampler arrays "wgpu-texture-binding-array"
+   */
+  count?: number
 }
 export interface BufferBindingLayout {
-  type?: string
+  type?: string | number
   hasDynamicOffset?: boolean
   minBindingSize?: number
 }
 export interface SamplerBindingLayout {
-  type?: string
+  type?: string | number
 }
 export interface TextureBindingLayout {
-  sampleType?: string
-  viewDimension?: string
+  sampleType?: string | number
+  viewDimension?: string | number
   multisampled?: boolean
 }
 export interface StorageTextureBindingLayout {
-  access?: string
-  format: string
-  viewDimension?: string
+  access?: string | number
+  format: string | number
+  viewDimension?: string | number
 }
 /**
  * Render pipeline descriptor following WebGPU spec
@@ -215,24 +426,24 @@ export interface VertexState {
 }
 export interface VertexBufferLayout {
   arrayStride: number
-  stepMode?: string
+  stepMode?: string | number
   attributes: Array<VertexAttribute>
 }
 export interface VertexAttribute {
-  format: string
+  format: string | number
   offset: number
   shaderLocation: number
 }
 export interface PrimitiveState {
-  topology?: string
-  stripIndexFormat?: string
-  frontFace?: string
-  cullMode?: string
+  topology?: string | number
+  stripIndexFormat?: string | number
+  frontFace?: string | number
+  cullMode?: string | number
 }
 export interface DepthStencilState {
-  format: string
+  format: string | number
   depthWriteEnabled?: boolean
-  depthCompare?: string
+  depthCompare?: string | number
   stencilFront?: StencilFaceState
   stencilBack?: StencilFaceState
   stencilReadMask?: number
@@ -242,10 +453,10 @@ export interface DepthStencilState {
   depthBiasClamp?: number
 }
 export interface StencilFaceState {
-  compare?: string
-  failOp?: string
-  depthFailOp?: string
-  passOp?: string
+  compare?: string | number
+  failOp?: string | number
+  depthFailOp?: string | number
+  passOp?: string | number
 }
 export interface MultisampleState {
   count?: number
@@ -254,10 +465,11 @@ export interface MultisampleState {
 }
 export interface FragmentState {
   entryPoint: string
-  targets: Array<ColorTargetState>
+  /** `null` entries leave that color location unused */
+  targets: Array<ColorTargetState | undefined | null>
 }
 export interface ColorTargetState {
-  format: string
+  format: string | number
   blend?: BlendState
   writeMask?: number
 }
@@ -266,9 +478,53 @@ export interface BlendState {
   alpha: BlendComponent
 }
 export interface BlendComponent {
-  srcFactor: string
-  dstFactor: string
-  operation: string
+  srcFactor: string | number
+  dstFactor: string | number
+  operation: string | number
+}
+/** Mip level and origin of a texture copy (texture passed separately) */
+export interface TextureCopyLocation {
+  mipLevel?: number
+  origin?: Origin3D
+  /** "all" (default), "depth-only" or "stencil-only" */
+  aspect?: string | number
+}
+/** Mips, layers and aspect of a texture cleared by `clearTexture` */
+export interface ImageSubresourceRange {
+  /** "all" (default), "depth-only" or "stencil-only" */
+  aspect?: string | number
+  baseMipLevel?: number
+  /** Defaults to the remaining mip levels */
+  mipLevelCount?: number
+  baseArrayLayer?: number
+  /** Defaults to the remaining array layers */
+  arrayLayerCount?: number
+}
+/** Layout of texel data in a `writeTexture` source */
+export interface TextureDataLayout {
+  offset?: number
+  bytesPerRow?: number
+  rowsPerImage?: number
+}
+export interface Origin3D {
+  x?: number
+  y?: number
+  z?: number
+}
+export interface Origin2D {
+  x?: number
+  y?: number
+}
+export interface Extent3D {
+  width: number
+  height?: number
+  depthOrArrayLayers?: number
+}
+/** Options for `writeCubeFaces` */
+export interface CubeFacesOptions {
+  mipLevel?: number
+  /** First layer to write; a multiple of 6 selects a cube in a cube array */
+  baseArrayLayer?: number
 }
 /** GPU supported limits following WebGPU spec */
 export interface GpuSupportedLimits {
@@ -302,6 +558,565 @@ export interface GpuSupportedLimits {
   maxComputeWorkgroupSizeZ: number
   maxComputeWorkgroupsPerDimension: number
 }
+/**
+ * Limits of a device requested from the null adapter (extension)
+ *
+ * The null adapter has no driver to ask, so it supports exactly the WebGPU
+ * default limits. `descriptor.requiredLimits` is checked against them like
+ * in `requestDevice()`; without a descriptor, these are the adapter's limits.
+ */
+export declare function nullDeviceLimits(descriptor?: DeviceDescriptor | undefined | null): GpuSupportedLimits
+/** Shader module cache statistics */
+export interface ShaderModuleCacheStats {
+  /** Number of distinct shader sources currently cached */
+  entries: number
+  /** Number of createShaderModule calls served from the cache */
+  hits: number
+  /** Number of createShaderModule calls that compiled a new module */
+  misses: number
+}
+/**
+ * Convert a SPIR-V binary to WGSL source
+ *
+ * The input is the raw SPIR-V byte stream (little-endian words, e.g. the
+ * contents of a `.spv` file). Throws if the module can't be parsed, fails
+ * validation, or uses features WGSL can't express.
+ */
+export declare function spirvToWgsl(spirv: Uint8Array): string
+/** Options for translateWgsl */
+export interface TranslateWgslOptions {
+  /** Entry point to translate (GLSL only; defaults to the first entry point) */
+  entryPoint?: string
+  /** GLSL version, e.g. "450" (core) or "310 es" (default) */
+  glslVersion?: string
+}
+/**
+ * Translate WGSL to another shading language
+ *
+ * `target` is one of "msl", "hlsl", "glsl" or "spirv". Text targets return
+ * source code; "spirv" returns the binary as a Buffer. GLSL output covers a
+ * single entry point, selected with `options.entryPoint`.
+ */
+export declare function translateWgsl(code: string, target: string, options?: TranslateWgslOptions | undefined | null): string | Buffer
+/** Options for preprocessWgsl */
+export interface WgslPreprocessOptions {
+  /** Name used for the root source in error messages */
+  label?: string
+  /** Compile-time defines, substituted wherever the identifier appears */
+  defines?: Record<string, string>
+  /** Directories searched for `#include` files */
+  includePaths?: Array<string>
+}
+/**
+ * Expand `#include`, `#define` and `#ifdef` directives in WGSL source
+ *
+ * `resolveInclude(path, fromFile)` may return the source for an include;
+ * returning null falls back to the file system (relative to the including
+ * file, then `includePaths`).
+ */
+export declare function preprocessWgsl(code: string, options?: WgslPreprocessOptions | undefined | null, resolveInclude?: (...args: any[]) => any | undefined | null): string
+/** An error captured by an error scope, returned by `popErrorScope()` */
+export interface CapturedError {
+  /** "validation" or "out-of-memory" */
+  type: string
+  message: string
+}
+/** Prefix sum descriptor */
+export interface PrefixSumDescriptor {
+  label?: string
+  /** Element type: "u32" (default) or "f32" */
+  dataType?: string
+}
+/** Radix sort descriptor */
+export interface RadixSortDescriptor {
+  label?: string
+}
+/** Matrix multiply descriptor */
+export interface MatmulDescriptor {
+  label?: string
+  /** Element type: "f32" (default) or "f16" (requires the "shader-f16" feature) */
+  dataType?: string
+}
+/** Matrix dimensions: A is m x k, B is k x n, the result is m x n */
+export interface MatmulDimensions {
+  m: number
+  n: number
+  k: number
+}
+/** Cross-device buffer copy options */
+export interface CrossDeviceBufferCopyOptions {
+  sourceOffset?: number
+  destinationOffset?: number
+  /** Bytes to copy (default: the rest of the source buffer) */
+  size?: number
+}
+/** Cross-device texture copy options */
+export interface CrossDeviceTextureCopyOptions {
+  mipLevel?: number
+}
+/**
+ * Copy a buffer range from one device to another
+ *
+ * Works across devices created from different adapters (e.g. two GPUs in one
+ * process). The data is staged through host memory in 64 MiB chunks; when both
+ * buffers live on the same device a plain GPU copy is recorded instead.
+ * The source needs COPY_SRC usage and the destination COPY_DST usage.
+ *
+ * Example:
+ * ```js
+ * await copyBufferBetweenDevices(resultsOnGpu0, inputsOnGpu1, { size: 4096 })
+ * ```
+ */
+export declare function copyBufferBetweenDevices(source: GpuBuffer, destination: GpuBuffer, options?: CrossDeviceBufferCopyOptions | undefined | null): Promise<void>
+/**
+ * Copy a texture mip level (all layers) from one device to another
+ *
+ * Both textures must share format and size. The source needs COPY_SRC usage and
+ * the destination COPY_DST usage; depth/stencil and multisampled textures are not
+ * supported.
+ *
+ * Example:
+ * ```js
+ * await copyTextureBetweenDevices(frameOnGpu0, frameOnGpu1)
+ * ```
+ */
+export declare function copyTextureBetweenDevices(source: GpuTexture, destination: GpuTexture, options?: CrossDeviceTextureCopyOptions | undefined | null): Promise<void>
+/** A log record passed to the `setLogCallback` callback */
+export interface LogRecord {
+  /** "error", "warn", "info", "debug" or "trace" */
+  level: string
+  /** Module that emitted the record, e.g. "wgpu_core::device::resource" */
+  target: string
+  message: string
+}
+/**
+ * Forward wgpu and naga log records at `level` or more severe to `callback`
+ *
+ * `level` is "off", "error", "warn", "info", "debug" or "trace". Passing
+ * "off" or no callback stops forwarding. Records are delivered
+ * asynchronously, and the callback doesn't keep the process alive.
+ */
+export declare function setLogCallback(level: 'off' | 'error' | 'warn' | 'info' | 'debug' | 'trace', callback?: ((record: LogRecord) => void) | null): void
+/** Native window to create a surface for */
+export interface SurfaceDescriptor {
+  /** "win32", "appkit", "xlib", "xcb" or "wayland" */
+  platform: string
+  /** HWND, NSView*, X11 Window, xcb_window_t or wl_surface* */
+  window: bigint
+  /** X11 Display*, xcb_connection_t* or wl_display* (required for "wayland") */
+  display?: bigint
+  /** X11 screen number (default 0) */
+  screen?: number
+  /** HINSTANCE of the module that created the window ("win32", optional) */
+  hinstance?: bigint
+}
+/** Surface configuration */
+export interface SurfaceConfiguration {
+  format: string | number
+  width: number
+  height: number
+  /** Texture usage bit mask or flag names (default RENDER_ATTACHMENT) */
+  usage?: number | Array<string>
+  /**
+   * "fifo" (default, vsync), "fifo-relaxed", "immediate", "mailbox",
+   * "auto-vsync" or "auto-no-vsync"
+   */
+  presentMode?: string | number
+  /** "auto" (default), "opaque", "premultiplied", "postmultiplied" or "inherit" */
+  alphaMode?: string | number
+  /**
+   * Extra formats views of the frame may use: the sRGB or non-sRGB
+   * variant of `format`
+   */
+  viewFormats?: Array<string | number>
+}
+/** What a surface supports on a given adapter */
+export interface SurfaceCapabilities {
+  /** Supported texture formats, preferred first */
+  formats: Array<string>
+  /** Supported present modes; "fifo" is always included */
+  presentModes: Array<string>
+  /** Supported alpha compositing modes */
+  alphaModes: Array<string>
+}
+/** Properties of a texture format, as returned by `getTextureFormatInfo` */
+export interface TextureFormatInfo {
+  /** Canonical WebGPU name of the format */
+  format: string
+  /** Texel block size in texels (4x4 for BC/ETC2, varies for ASTC, 1x1 otherwise) */
+  blockWidth: number
+  blockHeight: number
+  /** Bytes per texel block in copies; absent for depth24plus and combined depth-stencil formats */
+  bytesPerBlock?: number
+  /** Bytes per texel; only present for uncompressed formats with a copyable size */
+  bytesPerTexel?: number
+  /** "color", "depth" and/or "stencil" */
+  aspects: Array<string>
+  compressed: boolean
+  srgb: boolean
+  /** "float", "unfilterable-float", "depth", "sint" or "uint" (depth aspect for depth-stencil formats) */
+  sampleType?: string
+  /** Whether the format can be sampled with a filtering sampler */
+  filterable: boolean
+  /** Whether the format is guaranteed to support RENDER_ATTACHMENT usage */
+  renderable: boolean
+  /** Whether the format is guaranteed to support STORAGE_BINDING usage */
+  storage: boolean
+  /** Device feature needed to create textures of this format, if any */
+  requiredFeature?: string
+}
+/**
+ * Describe a texture format
+ *
+ * Usage capabilities are the ones guaranteed without optional device
+ * features; an adapter may support more. Compute a tightly packed row size as
+ * `Math.ceil(width / blockWidth) * bytesPerBlock`.
+ */
+export declare function getTextureFormatInfo(format: string): TextureFormatInfo
+/** What an adapter supports for a texture format, as returned by `adapter.getTextureFormatFeatures` */
+export interface TextureFormatFeatures {
+  /** Canonical WebGPU name of the format */
+  format: string
+  /**
+   * Whether the adapter has the feature the format requires, if any; a
+   * device must still request it
+   */
+  supported: boolean
+  /** Allowed `GPUTextureUsage` flags */
+  usage: number
+  /** Whether the format can be a render attachment */
+  renderable: boolean
+  /** Whether the format can be a storage texture */
+  storage: boolean
+  /** Whether storage textures of the format support "read-write" access */
+  storageReadWrite: boolean
+  /** Whether the format can be sampled with a filtering sampler */
+  filterable: boolean
+  /** Whether render attachments of the format support blending */
+  blendable: boolean
+  /** Supported sample counts, always including 1 */
+  sampleCounts: Array<number>
+  /** Whether multisampled attachments of the format can be resolved */
+  multisampleResolve: boolean
+}
+/** Timer descriptor */
+export interface TimerDescriptor {
+  label?: string
+  /** Maximum number of spans measured between `results()` calls (default 32) */
+  capacity?: number
+}
+/** GPU duration of one labeled span */
+export interface TimerResult {
+  label: string
+  /** Duration in milliseconds */
+  ms: number
+}
+/** Get the device registered under `handle` (from `device.toHandle()`) */
+export declare function deviceFromHandle(handle: string): GpuDevice
+/** Get the buffer registered under `handle` (from `buffer.toHandle()`) */
+export declare function bufferFromHandle(handle: string): GpuBuffer
+/** Get the texture registered under `handle` (from `texture.toHandle()`) */
+export declare function textureFromHandle(handle: string): GpuTexture
+/**
+ * Drop the registry's reference to a handle
+ *
+ * Objects already created from the handle keep working. Returns false if
+ * the handle was unknown or already released.
+ */
+export declare function releaseHandle(handle: string): boolean
+/** Render loop options */
+export interface RenderLoopOptions {
+  /**
+   * Target frames per second. By default frames are paced by the present
+   * mode alone, e.g. to the display refresh rate with "fifo"
+   */
+  fps?: number
+}
+/** Timing of a render loop frame */
+export interface RenderLoopFrame {
+  /** Frame number, counting from 0 */
+  frame: number
+  /** Milliseconds since the loop started */
+  time: number
+  /** Milliseconds since the previous frame */
+  delta: number
+}
+/** An event passed to the `setEventCallback` callback */
+export interface DeviceEvent {
+  /** "deviceLost", "uncapturedError" or "oomWarning" */
+  type: string
+  /** "unknown" or "destroyed", for "deviceLost" */
+  reason?: string
+  /** Description of the error or loss */
+  message?: string
+}
+/** Why a device was lost, as `device.lost` resolves with */
+export interface DeviceLostInfo {
+  /** "destroyed" after `device.destroy()`, otherwise "unknown" */
+  reason: string
+  message: string
+}
+/** Options for `blitTexture` */
+export interface BlitOptions {
+  /** "linear" (default) or "nearest" */
+  filter?: string
+  /** Mip level of the source to read (default 0) */
+  sourceMipLevel?: number
+  /** Mip level of the destination to write (default 0) */
+  destinationMipLevel?: number
+  /** Source channel of each destination channel, e.g. "bgra" (default "rgba") */
+  swizzle?: string
+  /** "srgb-to-linear" or "linear-to-srgb"; applied to RGB after the swizzle */
+  colorSpace?: string
+}
+/** Options for `convertPixels` */
+export interface ColorConversionOptions {
+  /** Source channel of each output channel, e.g. "bgra" (default "rgba") */
+  swizzle?: string
+  /** "srgb-to-linear" or "linear-to-srgb"; applied to RGB after the swizzle */
+  colorSpace?: string
+}
+/** Decoded image for `copyExternalImageToTexture` */
+export interface ExternalImageSource {
+  /** Tightly packed, unpremultiplied RGBA8 pixels with the top row first */
+  data: Buffer
+  width: number
+  height: number
+  /** Top-left pixel of the copy, counted in the flipped image with `flipY` */
+  origin?: Origin2D
+  /** Flip the image vertically, e.g. for bottom-up decoders (default false) */
+  flipY?: boolean
+}
+/** Conversions of `copyExternalImageToTexture` */
+export interface ExternalImageDestination {
+  /** Multiply RGB by alpha after the color space conversion (default false) */
+  premultipliedAlpha?: boolean
+  /** "srgb" (default) keeps the encoded values, "srgb-linear" decodes them */
+  colorSpace?: string
+}
+/** Gaussian blur descriptor */
+export interface BlurDescriptor {
+  label?: string
+  /** Taps on each side of the center, up to 63. Defaults to 3 sigma, or 4 */
+  radius?: number
+  /** Standard deviation in texels. Defaults to half the radius */
+  sigma?: number
+}
+/** Histogram descriptor */
+export interface HistogramDescriptor {
+  label?: string
+  /** Bins per channel, 1 to 256 (default 256) */
+  bins?: number
+}
+/** Per-channel bin counts */
+export interface HistogramResult {
+  red: Array<number>
+  green: Array<number>
+  blue: Array<number>
+  alpha: Array<number>
+}
+/** Tensor descriptor */
+export interface TensorDescriptor {
+  label?: string
+  /**
+   * Size of each dimension. Required by `createTensor`; `fromArray`
+   * defaults to one dimension holding all the data
+   */
+  shape?: Array<number>
+  /** "f32" (default), "f16", "i32" or "u8" */
+  dtype?: string
+  /** Usage added to STORAGE | COPY_SRC | COPY_DST, e.g. VERTEX or ["VERTEX"] */
+  usage?: number | Array<string>
+}
+/**
+ * Pack floats into half-precision bytes
+ *
+ * Returns two little-endian bytes per value, rounded to nearest even, for
+ * uploading to f16 storage buffers or textures.
+ */
+export declare function packF16(values: Float32Array): Buffer
+/** Unpack half-precision bytes into floats */
+export declare function unpackF16(data: Buffer): Float32Array
+/** Scale and zero point of `quantizeInt8` / `dequantizeInt8` */
+export interface QuantizationParams {
+  /** One scale for all values, or one per channel */
+  scale: number | Array<number>
+  /** Zero point(s) from -128 to 127, one per channel if `scale` is (default 0) */
+  zeroPoint?: number | Array<number>
+  /**
+   * Whether channels alternate value by value (e.g. the last dimension of
+   * NHWC data) rather than each filling a contiguous block (e.g. the rows
+   * of a weight matrix). Default false
+   */
+  interleaved?: boolean
+}
+/**
+ * Quantize floats to int8
+ *
+ * Returns one signed byte per value. Values round to nearest even and
+ * saturate at -128 and 127; NaN becomes the zero point.
+ */
+export declare function quantizeInt8(values: Float32Array, params: QuantizationParams): Buffer
+/** Dequantize int8 bytes to floats */
+export declare function dequantizeInt8(data: Buffer, params: QuantizationParams): Float32Array
+/** 2D renderer descriptor */
+export interface Draw2DDescriptor {
+  label?: string
+}
+/** Options for `sprite` */
+export interface SpriteOptions {
+  /** Region of the texture to draw as [x, y, width, height] in texels (default: all of it) */
+  source?: Array<number>
+  /** Tint multiplied with the texels as [r, g, b, a?] (default white) */
+  color?: Array<number>
+  /** "linear" (default) or "nearest" */
+  filter?: string
+}
+/** Options for `encode` and `run` */
+export interface Draw2DEncodeOptions {
+  /** Clear the target to this [r, g, b, a?] color first instead of drawing over it */
+  clear?: Array<number>
+}
+/** Font descriptor */
+export interface FontDescriptor {
+  label?: string
+  /** Default text size in pixels per em (default 16) */
+  size?: number
+}
+/** Options for `drawText` and `measureText` */
+export interface TextOptions {
+  /** Size in pixels per em (default: the font's size) */
+  size?: number
+  /** Color as [r, g, b, a?] from 0 to 1 (default white) */
+  color?: Array<number>
+}
+/** Size of laid out text in pixels */
+export interface TextMetrics {
+  /** Advance of the widest line */
+  width: number
+  /** Line height times the number of lines */
+  height: number
+}
+/** Compute runner descriptor */
+export interface ComputeRunnerDescriptor {
+  label?: string
+  /** Byte offset of the parameters in the parameter buffer (default 0) */
+  paramsOffset?: number
+}
+/** Arguments of `drawIndirect()` */
+export interface DrawIndirectArgs {
+  vertexCount: number
+  /** Default 1 */
+  instanceCount?: number
+  firstVertex?: number
+  firstInstance?: number
+}
+/** Arguments of `drawIndexedIndirect()` */
+export interface DrawIndexedIndirectArgs {
+  indexCount: number
+  /** Default 1 */
+  instanceCount?: number
+  firstIndex?: number
+  baseVertex?: number
+  firstInstance?: number
+}
+/** Workgroup counts of `dispatchWorkgroupsIndirect()` */
+export interface DispatchIndirectArgs {
+  x: number
+  /** Default 1 */
+  y?: number
+  /** Default 1 */
+  z?: number
+}
+/** Where the structs are in a buffer */
+export interface IndirectArgsLayout {
+  /** Byte offset of the first struct (default 0) */
+  offset?: number
+  /** Bytes from one struct to the next (default the struct size) */
+  stride?: number
+  /** Number of structs to unpack (default as many as fit) */
+  count?: number
+}
+/**
+ * Pack `drawIndirect()` arguments, 16 bytes each
+ *
+ * Writes into `destination` when given, and returns it; otherwise into a
+ * new buffer that ends with the last struct.
+ */
+export declare function packDrawIndirectArgs(args: Array<DrawIndirectArgs>, layout?: IndirectArgsLayout | undefined | null, destination?: Buffer | undefined | null): Buffer
+/** Pack `drawIndexedIndirect()` arguments, 20 bytes each */
+export declare function packDrawIndexedIndirectArgs(args: Array<DrawIndexedIndirectArgs>, layout?: IndirectArgsLayout | undefined | null, destination?: Buffer | undefined | null): Buffer
+/** Pack `dispatchWorkgroupsIndirect()` arguments, 12 bytes each */
+export declare function packDispatchIndirectArgs(args: Array<DispatchIndirectArgs>, layout?: IndirectArgsLayout | undefined | null, destination?: Buffer | undefined | null): Buffer
+/** Unpack `drawIndirect()` arguments */
+export declare function unpackDrawIndirectArgs(data: Buffer, layout?: IndirectArgsLayout | undefined | null): Array<DrawIndirectArgs>
+/** Unpack `drawIndexedIndirect()` arguments */
+export declare function unpackDrawIndexedIndirectArgs(data: Buffer, layout?: IndirectArgsLayout | undefined | null): Array<DrawIndexedIndirectArgs>
+/** Unpack `dispatchWorkgroupsIndirect()` arguments */
+export declare function unpackDispatchIndirectArgs(data: Buffer, layout?: IndirectArgsLayout | undefined | null): Array<DispatchIndirectArgs>
+/** Frame capture descriptor */
+export interface FrameCaptureDescriptor {
+  label?: string
+  /** "rgba" (default) or "yuv420p" */
+  pixelFormat?: string
+  /** Staging buffers frames are copied into, in rotation (default 2) */
+  bufferCount?: number
+}
+/** One captured frame */
+export interface CapturedFrame {
+  /** Tightly packed RGBA8 pixels, or the Y, U and V planes of I420 */
+  data: Buffer
+  width: number
+  height: number
+  /** Number of frames captured before this one */
+  index: number
+  /**
+   * Milliseconds since the capture was created when the frame was
+   * captured, or the timestamp passed to `capture()`
+   */
+  timestamp: number
+}
+/** Chunked buffer descriptor */
+export interface ChunkedBufferDescriptor {
+  label?: string
+  /** Size of the whole dataset in bytes, a multiple of `elementSize` */
+  size: number
+  /**
+   * Bytes per element, a multiple of 4 (default 4). Chunks hold whole
+   * elements
+   */
+  elementSize?: number
+  /**
+   * Largest chunk in bytes (default and maximum: the smaller of
+   * `maxStorageBufferBindingSize` and `maxBufferSize`)
+   */
+  maxChunkSize?: number
+  /** Usage added to STORAGE | COPY_SRC | COPY_DST, e.g. VERTEX or ["VERTEX"] */
+  usage?: number | Array<string>
+}
+/** Where one chunk lies in the dataset */
+export interface BufferChunk {
+  index: number
+  /** Offset of the chunk in the dataset, in bytes */
+  byteOffset: number
+  byteSize: number
+  /** Offset of the chunk's first element in the dataset */
+  elementOffset: number
+  elementCount: number
+}
+/** Options of `dispatch()` */
+export interface ChunkDispatchDescriptor {
+  label?: string
+  /** Bind group index the per-chunk bind groups are set at (default 0) */
+  group?: number
+  /** Indices the shared bind groups are set at, one per group */
+  sharedGroups?: Array<number>
+  /**
+   * Invocations per workgroup (default: the pipeline's `@workgroup_size`,
+   * all dimensions multiplied)
+   */
+  workgroupSize?: number
+}
 /**
  * GPU instance - entry point for WebGPU API
  *
@@ -312,28 +1127,81 @@ export declare class Gpu {
   /**
    * Create a new GPU instance
    *
+   * Restrict `backends` to e.g. `['gl']` to run on machines with only
+   * EGL / software OpenGL, such as CI containers. On Windows, select DXC
+   * with `dx12_shader_compiler` for Shader Model 6 features. `instance_flags`
+   * and `gles_minor_version` tune the backends for platform-specific issues.
+   *
    * Example:
    * ```js
    * const gpu = Gpu.create()
+   * const glGpu = Gpu.create({ backends: ['gl'] })
+   * const dxcGpu = Gpu.create({ dx12ShaderCompiler: 'dxc', dxcPath: 'C:\\dxc\in' })
    * ```
    */
-  static create(): Gpu
+  static create(options?: GpuOptions | undefined | null): Gpu
   /**
    * Request a GPU adapter
    *
+   * Set `force_fallback_adapter` to only consider software (CPU) adapters such as
+   * lavapipe, WARP or SwiftShader, e.g. for deterministic CI runs without a GPU.
+   * Pass `compatible_surface` to only consider adapters that can present to it.
+   * A `selector` picks from `enumerateAdapters()` by device type, backend and
+   * PCI IDs instead of leaving the choice to wgpu.
+   *
    * Example:
    * ```js
    * const adapter = await gpu.requestAdapter()
+   * const cpuAdapter = await gpu.requestAdapter(null, true)
+   * const windowAdapter = await gpu.requestAdapter(null, false, surface)
+   * const nvidiaAdapter = await gpu.requestAdapter(null, false, null, { vendor: 0x10de, backend: 'vulkan' })
    * ```
    */
-  requestAdapter(powerPreference?: string | undefined | null): Promise<GpuAdapter>
+  requestAdapter(powerPreference?: string | undefined | null, forceFallbackAdapter?: boolean | undefined | null, compatibleSurface?: GpuSurface | undefined | null, selector?: AdapterSelector | undefined | null): Promise<GpuAdapter>
   /**
    * Enumerate all available adapters
    *
-   * Returns a list of all available GPU adapters with their backend (Metal, Vulkan, DX12).
-   * Useful for debugging and displaying available hardware to users.
+   * Returns structured info (name, vendor, device, type, backend, driver) for every adapter
+   * on the instance's backends. The position in this list is the index accepted by
+   * `requestAdapterByIndex`, so multi-GPU machines can pick an exact device.
+   */
+  enumerateAdapters(): Array<AdapterInfo>
+  /**
+   * Request the adapter at `index` in the `enumerateAdapters()` list
+   *
+   * Example:
+   * ```js
+   * const index = gpu.enumerateAdapters().findIndex(info => info.name.includes('NVIDIA'))
+   * const adapter = gpu.requestAdapterByIndex(index)
+   * ```
+   */
+  requestAdapterByIndex(index: number): GpuAdapter
+  /**
+   * Create a surface for presenting to a native window
+   *
+   * The handles are not checked: they must identify a live window (and
+   * display connection) that stays open until the surface is released.
+   *
+   * Example:
+   * ```js
+   * const surface = gpu.createSurface({ platform: 'win32', window: hwnd })
+   * ```
+   */
+  createSurface(descriptor: SurfaceDescriptor): GpuSurface
+  /**
+   * Create a surface from a native window handle Buffer
+   *
+   * Accepts what Electron's `BrowserWindow.getNativeWindowHandle()` returns:
+   * an `HWND` on Windows, an `NSView*` on macOS and an X11 window id on
+   * Linux, where a connection to the X server in `DISPLAY` is opened for
+   * the surface. The window must stay open until the surface is released.
+   *
+   * Example:
+   * ```js
+   * const surface = gpu.createSurfaceFromNativeWindowHandle(win.getNativeWindowHandle())
+   * ```
    */
-  enumerateAdapters(): Array<string>
+  createSurfaceFromNativeWindowHandle(handle: Buffer): GpuSurface
 }
 /**
  * GPU adapter - represents a physical GPU or software renderer
@@ -345,9 +1213,11 @@ export declare class GpuAdapter {
   /**
    * Get adapter information
    *
-   * Returns name, vendor ID, device ID, type, and backend.
+   * Returns name, vendor ID, device ID, type, backend, and driver details.
    */
   getInfo(): AdapterInfo
+  /** Whether this is a fallback (software/CPU) adapter */
+  get isFallbackAdapter(): boolean
   /**
    * Get adapter features
    *
@@ -358,16 +1228,33 @@ export declare class GpuAdapter {
   /**
    * Get adapter limits
    *
-   * Returns maximum resource sizes and counts supported by this adapter.
+   * Returns the same limits a device reports, with the adapter's maximum values.
    */
-  getLimits(): AdapterLimits
+  getLimits(): GpuSupportedLimits
+  /**
+   * Get the adapter's downlevel capabilities
+   *
+   * Reports which parts of WebGPU the adapter can't fully support, e.g.
+   * on the GL backend. `compliant` is true when nothing is missing.
+   */
+  getDownlevelCapabilities(): DownlevelCapabilities
   /**
    * Request a device from this adapter
    *
    * Creates a logical device for executing GPU operations.
-   * Currently requests timestamp query feature by default.
+   * Requests timestamp queries and adapter-specific format features when the adapter supports them,
+   * in addition to `requiredFeatures`.
+   * Without `requiredLimits`, adapters below the WebGPU default limits (e.g. GLES)
+   * get the downlevel defaults they support.
+   */
+  requestDevice(descriptor?: DeviceDescriptor | undefined | null): Promise<GpuDevice>
+  /**
+   * Get what this adapter supports for a texture format (extension)
+   *
+   * Unlike `getTextureFormatInfo`, reports the adapter's actual
+   * capabilities, e.g. to pick fallback formats at startup.
    */
-  requestDevice(): Promise<GpuDevice>
+  getTextureFormatFeatures(format: string): TextureFormatFeatures
 }
 export declare class GpuDevice {
   /** Get the queue for this device (WebGPU standard property) */
@@ -379,26 +1266,122 @@ export declare class GpuDevice {
   /** Get the label of this device (WebGPU standard property) */
   get label(): string | null
   /**
-   * Push an error scope for error handling (WebGPU standard method)
-   * NOTE: wgpu only supports "validation" and "out-of-memory" filters
+   * Why the device was lost, or null while it's usable
+   *
+   * The `GpuDevice` wrapper resolves `device.lost` with this.
+   */
+  get lostInfo(): DeviceLostInfo | null
+  /**
+   * Push an error scope for error handling (WebGPU standard method)
+   * NOTE: wgpu only supports "validation" and "out-of-memory" filters
    */
   pushErrorScope(filter: string): void
-  /** Pop an error scope and return any error (WebGPU standard method) */
-  popErrorScope(): Promise<string | null>
+  /**
+   * Pop an error scope and return any error (WebGPU standard method)
+   *
+   * Pops on the calling thread, so it can't interleave with the private
+   * scopes that `check()` pushes around API calls.
+   */
+  popErrorScope(): CapturedError | null
+  /**
+   * Make create calls fail on demand, for testing error handling (extension)
+   *
+   * Injected failures are thrown like real ones without creating
+   * anything, and out-of-memory ones also emit 'oomWarning'. While an
+   * error scope is open, the call goes ahead and the failure is left to
+   * the next `popErrorScope()` instead. Each call replaces the previous
+   * options and restarts the allocation count.
+   */
+  enableFaultInjection(options: FaultInjectionOptions): void
+  /** Stop injecting failures (extension) */
+  disableFaultInjection(): void
   /** Create a GPU buffer */
   createBuffer(descriptor: BufferDescriptor): GpuBuffer
-  /** Create a shader module */
-  createShaderModule(descriptor: ShaderModuleDescriptor): GpuShaderModule
+  /**
+   * Create a GPU buffer holding `data`
+   *
+   * The buffer is created mapped, filled and unmapped in one call. Its size
+   * is the data length rounded up to a multiple of 4 bytes.
+   */
+  createBufferInit(descriptor: BufferInitDescriptor, data: Buffer): GpuBuffer
+  /**
+   * Create a shader module
+   *
+   * Source using `#include`/`#define`/`#ifdef` directives, or given
+   * `defines`, `includePaths` or an include resolver, is preprocessed first
+   * and checked with naga so errors point at the original files.
+   *
+   * When the shader module cache is enabled, identical WGSL source returns
+   * the previously compiled module.
+   */
+  createShaderModule(descriptor: ShaderModuleDescriptor, resolveInclude?: (...args: any[]) => any | undefined | null): GpuShaderModule
+  /**
+   * Create a bind group layout matching a shader's bindings in `group`
+   *
+   * Binding types, texture dimensions/formats, minimum buffer sizes and
+   * stage visibility are derived from the WGSL source via reflection.
+   */
+  createBindGroupLayoutFromShader(module: GpuShaderModule, group: number, label?: string | undefined | null): GpuBindGroupLayout
+  /**
+   * Create a writer for the WGSL struct `struct_name` declared in `module`
+   *
+   * Field values passed to the writer are type-checked and packed using the
+   * struct's reflected offsets and padding.
+   */
+  createUniformWriter(module: GpuShaderModule, structName: string): GpuUniformWriter
+  /**
+   * Enable or disable the shader module de-duplication cache
+   *
+   * Off by default. Useful for generated-shader systems that recreate the
+   * same module many times. Disabling the cache drops all cached modules.
+   */
+  setShaderModuleCacheEnabled(enabled: boolean): void
+  /** Drop all cached shader modules and reset the cache statistics */
+  clearShaderModuleCache(): void
+  /** Get shader module cache statistics */
+  getShaderModuleCacheStats(): ShaderModuleCacheStats
   /** Create a command encoder */
   createCommandEncoder(descriptor?: CommandEncoderDescriptor | undefined | null): GpuCommandEncoder
-  /** Poll the device */
-  poll(forceWait?: boolean | undefined | null): void
+  /**
+   * Poll the device
+   *
+   * Returns `true` if the queue is empty, i.e. all submitted work has completed.
+   */
+  poll(forceWait?: boolean | undefined | null): boolean
+  /**
+   * Enable or disable automatic device maintenance
+   *
+   * When enabled, every `queue.submit()` keeps the device poll thread running until
+   * that submission completes, so wgpu callbacks and resource cleanup happen without
+   * explicit `device.poll()` calls. Disabled by default.
+   */
+  setAutoMaintenance(enabled: boolean): void
+  /**
+   * Wait until all work submitted to this device has completed
+   *
+   * Flushes pending `queue.writeBuffer()` data first. The wait happens on the device
+   * poll thread, so the JS thread stays free.
+   *
+   * Example:
+   * ```js
+   * device.queue.submit([encoder.finish()])
+   * await device.waitIdle()
+   * ```
+   */
+  waitIdle(): Promise<void>
   /** Create a texture */
   createTexture(descriptor: TextureDescriptor): GpuTexture
   /** Create a sampler */
   createSampler(descriptor: SamplerDescriptor): GpuSampler
   /** Create a query set for timestamp or occlusion queries */
   createQuerySet(descriptor: QuerySetDescriptor): GpuQuerySet
+  /**
+   * Create a render bundle encoder following WebGPU standard
+   *
+   * Color formats, depth-stencil format and sample count must match the
+   * render passes the bundle will be executed in.
+   */
+  createRenderBundleEncoder(descriptor: RenderBundleEncoderDescriptor): GpuRenderBundleEncoder
   /** Create a bind group layout */
   createBindGroupLayout(descriptor: BindGroupLayoutDescriptor): GpuBindGroupLayout
   /**
@@ -413,8 +1396,104 @@ export declare class GpuDevice {
   createComputePipeline(descriptor: ComputePipelineDescriptor, layout: GpuPipelineLayout | undefined | null, module: GpuShaderModule): GpuComputePipeline
   /** Create a render pipeline following WebGPU spec */
   createRenderPipeline(descriptor: RenderPipelineDescriptor, layout: GpuPipelineLayout | undefined | null, vertexModule: GpuShaderModule, fragmentModule?: GpuShaderModule | undefined | null): GpuRenderPipeline
-  /** Destroy the device */
+  /**
+   * Create a prefix sum (scan) utility
+   *
+   * The returned object owns its compute pipelines and can be reused for any number of scans.
+   */
+  createPrefixSum(descriptor?: PrefixSumDescriptor | undefined | null): GpuPrefixSum
+  /** Create a radix sort utility for u32 keys with optional u32 values */
+  createRadixSort(descriptor?: RadixSortDescriptor | undefined | null): GpuRadixSort
+  /** Create a tiled matrix multiply utility for f32/f16 matrices */
+  createMatmul(descriptor?: MatmulDescriptor | undefined | null): GpuMatmul
+  /** Create a separable Gaussian blur utility for textures */
+  createBlur(descriptor?: BlurDescriptor | undefined | null): GpuBlur
+  /** Create a per-channel histogram utility for textures and RGBA8 pixel buffers */
+  createHistogram(descriptor?: HistogramDescriptor | undefined | null): GpuHistogram
+  /** Create an immediate-mode 2D renderer for shapes and sprites */
+  createDraw2D(descriptor?: Draw2DDescriptor | undefined | null): GpuDraw2D
+  /** Load a TrueType font for drawing text into render passes */
+  createFont(data: Buffer, descriptor?: FontDescriptor | undefined | null): GpuFont
+  /**
+   * Create a timestamp timer for measuring GPU durations of encoded work
+   *
+   * Requires the "timestamp-query" feature.
+   */
+  createTimer(descriptor?: TimerDescriptor | undefined | null): GpuTimer
+  /**
+   * Create a runner that dispatches `pipeline` repeatedly
+   *
+   * `bindGroups` are set at indices 0, 1, ... and `params`, if given, is
+   * the buffer each `run()` writes its parameters into.
+   */
+  createComputeRunner(descriptor: ComputeRunnerDescriptor | undefined | null, pipeline: GpuComputePipeline, bindGroups: Array<GpuBindGroup>, params?: GpuBuffer | undefined | null): GpuComputeRunner
+  /**
+   * Create a capture that reads rendered frames back for video encoding
+   *
+   * Frames rotate through `bufferCount` staging buffers and come out of
+   * `next()` in order, as RGBA or yuv420p bytes.
+   */
+  createFrameCapture(descriptor?: FrameCaptureDescriptor | undefined | null): GpuFrameCapture
+  /**
+   * Create a dataset split across storage buffers no larger than a binding
+   *
+   * For data beyond `maxStorageBufferBindingSize`; `dispatch()` runs a
+   * pipeline over each chunk in turn.
+   */
+  createChunkedBuffer(descriptor: ChunkedBufferDescriptor): GpuChunkedBuffer
+  /**
+   * Destroy the device
+   *
+   * The device is lost with reason "destroyed": its "deviceLost" event fires
+   * and later calls on it, its queue and its encoders throw. Resources are
+   * freed once submitted work has finished. Destroying again does nothing.
+   */
   destroy(): void
+  /**
+   * Register the device for use in worker threads
+   *
+   * Returns a token for `deviceFromHandle()`. The device stays alive until
+   * the token is passed to `releaseHandle()`.
+   */
+  toHandle(): string
+  /**
+   * Receive this device's lifecycle events (extension)
+   *
+   * The `GpuDevice` wrapper emits them as EventEmitter events; use that
+   * instead of calling this directly. Passing no callback stops delivery,
+   * and uncaptured errors go to the log again. The callback doesn't keep
+   * the process alive.
+   */
+  setEventCallback(callback?: ((event: DeviceEvent) => void) | null): void
+  /**
+   * Draw `source` into `destination`, rescaling and converting the format (extension)
+   *
+   * The source is stretched over the whole destination, so this covers
+   * downscaling render targets and conversions such as rgba16float to
+   * rgba8unorm. Both textures must hold floating-point or normalized
+   * colors; sRGB formats are decoded and encoded. `swizzle` and
+   * `color_space` convert the colors in between. The source needs
+   * TEXTURE_BINDING usage, the destination RENDER_ATTACHMENT.
+   */
+  blitTexture(encoder: GpuCommandEncoder, source: GpuTexture, destination: GpuTexture, options?: BlitOptions | undefined | null): void
+  /**
+   * Swizzle channels and convert sRGB of packed RGBA8 pixels (extension)
+   *
+   * Converts `count` 4-byte pixels from `input` into `output`, e.g. BGRA
+   * screen captures into RGBA with `swizzle: "bgra"`. Both buffers need
+   * STORAGE usage and must be different buffers.
+   */
+  convertPixels(encoder: GpuCommandEncoder, input: GpuBuffer, output: GpuBuffer, count: number, options?: ColorConversionOptions | undefined | null): void
+  /** Create a zero-filled tensor (extension) */
+  createTensor(descriptor: TensorDescriptor): GpuTensor
+  /**
+   * Create a multisampled texture matching `view` in size and format (extension)
+   *
+   * Render into it and resolve into `view` with a color attachment's
+   * `resolveTarget`. `sampleCount` defaults to 4. The texture only has
+   * RENDER_ATTACHMENT usage.
+   */
+  createMultisampleTexture(view: GpuTextureView, sampleCount?: number | undefined | null, label?: string | undefined | null): GpuTexture
 }
 export declare class GpuShaderModule { }
 export declare class GpuCommandEncoder {
@@ -436,9 +1515,16 @@ export declare class GpuCommandEncoder {
   /** Copy data from one buffer to another (WebGPU standard method) */
   copyBufferToBuffer(source: GpuBuffer, sourceOffset: number, destination: GpuBuffer, destinationOffset: number, size: number): void
   /** Copy data from buffer to texture (WebGPU standard method) */
-  copyBufferToTexture(source: GpuBuffer, sourceOffset: number, bytesPerRow: number, rowsPerImage: number | undefined | null, destination: GpuTexture, mipLevel: number | undefined | null, originX: number | undefined | null, originY: number | undefined | null, originZ: number | undefined | null, width: number, height: number, depth?: number | undefined | null): void
+  copyBufferToTexture(source: GpuBuffer, sourceOffset: number, bytesPerRow: number, rowsPerImage: number | undefined | null, destination: GpuTexture, mipLevel: number | undefined | null, originX: number | undefined | null, originY: number | undefined | null, originZ: number | undefined | null, width: number, height: number, depth?: number | undefined | null, aspect?: string | number | undefined | null): void
   /** Copy data from texture to buffer (WebGPU standard method) */
-  copyTextureToBuffer(source: GpuTexture, mipLevel: number | undefined | null, originX: number | undefined | null, originY: number | undefined | null, originZ: number | undefined | null, destination: GpuBuffer, destinationOffset: number, bytesPerRow: number, rowsPerImage: number | undefined | null, width: number, height: number, depth?: number | undefined | null): void
+  copyTextureToBuffer(source: GpuTexture, mipLevel: number | undefined | null, originX: number | undefined | null, originY: number | undefined | null, originZ: number | undefined | null, destination: GpuBuffer, destinationOffset: number, bytesPerRow: number, rowsPerImage: number | undefined | null, width: number, height: number, depth?: number | undefined | null, aspect?: string | number | undefined | null): void
+  /** Copy data between textures (WebGPU standard method) */
+  copyTextureToTexture(source: GpuTexture, sourceLocation: TextureCopyLocation, destination: GpuTexture, destinationLocation: TextureCopyLocation, size: Extent3D): void
+  /**
+   * Clear a range of texture subresources to zero
+   * Needs the 'wgpu-clear-texture' feature; the whole texture is cleared by default
+   */
+  clearTexture(texture: GpuTexture, range?: ImageSubresourceRange | undefined | null): void
   /**
    * Begin a compute pass following WebGPU standard
    * Returns a compute pass encoder for recording compute commands
@@ -449,9 +1535,20 @@ export declare class GpuCommandEncoder {
    * Texture views are passed separately to avoid napi-rs External serialization issues
    * Returns a render pass encoder for recording render commands
    */
-  beginRenderPass(descriptor: RenderPassDescriptor, colorViews: Array<GpuTextureView>, colorResolveViews?: Array<GpuTextureView | undefined | null> | undefined | null, depthStencilView?: GpuTextureView | undefined | null): GpuRenderPassEncoder
-  /** Finish encoding and return a command buffer */
+  beginRenderPass(descriptor: RenderPassDescriptor, colorViews: Array<GpuTextureView | undefined | null>, colorResolveViews?: Array<GpuTextureView | undefined | null> | undefined | null, depthStencilView?: GpuTextureView | undefined | null): GpuRenderPassEncoder
+  /**
+   * Finish encoding and return a command buffer
+   *
+   * Throws if a pass begun on this encoder hasn't ended.
+   */
   finish(): GpuCommandBuffer
+  /**
+   * Drop the encoder and everything recorded in it without finishing (extension)
+   *
+   * Afterwards the encoder behaves as if finished. Does nothing if it
+   * already was.
+   */
+  discard(): void
 }
 export declare class GpuCommandBuffer { }
 /**
@@ -461,24 +1558,30 @@ export declare class GpuCommandBuffer { }
  * They can be written from CPU (via queueWriteBuffer) and read back (via mapRead).
  */
 export declare class GpuBuffer {
-  /** Get the size of the buffer */
-  size(): number
-  /** Get the usage flags of the buffer */
-  usage(): number
-  /**
-   * Get the current map state of the buffer
-   *
-   * Returns one of: "unmapped", "pending", "mapped"
-   */
-  mapState(): string
+  /** Size of the buffer in bytes */
+  get size(): number
+  /** Usage flags of the buffer */
+  get usage(): number
+  /** Names of the usage flags, e.g. ["COPY_DST", "STORAGE"] (extension) */
+  usageNames(): Array<string>
+  /** Current map state of the buffer: "unmapped", "pending" or "mapped" */
+  get mapState(): string
+  /** Descriptor label, or "" without one */
+  get label(): string
   /**
    * Map the buffer asynchronously for reading or writing
    *
    * Asynchronously maps the buffer for CPU access.
-   * mode: "READ" or "WRITE"
-   * Buffer must have MAP_READ or MAP_WRITE usage flag.
+   * mode: "READ" or "WRITE", or GPUMapMode.READ or GPUMapMode.WRITE
+   * Buffer must have the matching MAP_READ or MAP_WRITE usage flag.
+   *
+   * Maps `size` bytes from `offset` (default 0 and the rest of the buffer);
+   * getMappedRange() ranges must lie within them. Rejects while the buffer
+   * is already pending or mapped, and with code "Cancelled" when unmap()
+   * or destroy() aborts the map before it completes. The state changes to
+   * "pending" before this returns, as the spec requires.
    */
-  mapAsync(mode: string): Promise<void>
+  mapAsync(mode: string | number, offset?: number | undefined | null, size?: number | undefined | null): Promise<void>
   /**
    * Get the mapped range as a buffer
    *
@@ -488,6 +1591,9 @@ export declare class GpuBuffer {
    * The returned buffer is a COPY of GPU memory. Modifications to this buffer in JavaScript
    * will be automatically flushed back to GPU when unmap() is called.
    *
+   * Each call returns a new copy and ranges may not overlap, even identical ones; the
+   * JavaScript wrapper returns the same ArrayBuffer for repeated calls over a range.
+   *
    * This implements the standard WebGPU getMappedRange() behavior.
    *
    * # Parameters
@@ -495,6 +1601,42 @@ export declare class GpuBuffer {
    * * `size` - Number of bytes to return (optional, default remaining bytes). Must be multiple of 4.
    */
   getMappedRange(offset?: number | undefined | null, size?: number | undefined | null): Buffer
+  /**
+   * Copy a mapped range into `destination` without creating a mapped view
+   *
+   * `destination` may be backed by a SharedArrayBuffer, so worker threads
+   * can read GPU results without another copy. The range follows the same
+   * rules as getMappedRange() but isn't tracked, so it may overlap other
+   * ranges. Returns the number of bytes copied.
+   */
+  readMappedRange(destination: Uint8Array, offset?: number | undefined | null, size?: number | undefined | null): number
+  /**
+   * Write data straight into the mapped memory (extension)
+   *
+   * Valid while the buffer is mapped for writing, by mapAsync(WRITE) or
+   * mappedAtCreation. Unlike changes to a getMappedRange() copy, the data
+   * isn't buffered and written again on unmap(). The range follows the
+   * getMappedRange() rules and may not overlap a range returned by it.
+   *
+   * # Parameters
+   * * `offset` - Byte offset into the buffer. Must be multiple of 8.
+   * * `data` - Bytes to write. The length must be a multiple of 4.
+   */
+  writeMapped(offset: number, data: Uint8Array): void
+  /**
+   * Read a range synchronously (extension)
+   *
+   * Maps, waits, copies and unmaps in one blocking call, for small debug
+   * readbacks and scripts. Buffers without MAP_READ usage are copied into a
+   * staging buffer first, which needs COPY_SRC usage. The calling thread is
+   * blocked until all submitted GPU work is done, so ranges are limited to
+   * 4 MiB; use mapAsync() in anything latency sensitive.
+   *
+   * # Parameters
+   * * `offset` - Byte offset into the buffer (optional, default 0). Must be multiple of 8.
+   * * `size` - Number of bytes to read (optional, default remaining bytes). Must be multiple of 4.
+   */
+  readSync(offset?: number | undefined | null, size?: number | undefined | null): Buffer
   /**
    * Unmap the buffer
    *
@@ -502,9 +1644,10 @@ export declare class GpuBuffer {
    * Must be called after mapping operations before using buffer in GPU operations.
    *
    * # Parameters
-   * * `modified_buffer` - Optional. If provided, writes this data to GPU before unmapping.
-   *                       Use this when you've modified the buffer from getMappedRange().
-   *                       Note: In JavaScript, this is handled automatically by the wrapper.
+   * * `modified` - Optional. Data to write to the GPU before unmapping: either one
+   *                buffer written at offset 0, or the `{ offset, data }` of every
+   *                range returned by getMappedRange().
+   *                Note: In JavaScript, this is handled automatically by the wrapper.
    *
    * # WebGPU Standard Usage (JavaScript)
    * ```js
@@ -521,13 +1664,20 @@ export declare class GpuBuffer {
    * buffer.unmap()
    * ```
    */
-  unmap(modifiedBuffer?: Buffer | undefined | null): void
+  unmap(modified?: Buffer | Array<MappedRangeData> | undefined | null): void
   /**
    * Destroy the buffer
    *
    * Explicitly releases GPU resources. Buffers are automatically destroyed when dropped.
    */
   destroy(): void
+  /**
+   * Register the buffer for use in worker threads
+   *
+   * Returns a token for `bufferFromHandle()`. The buffer stays alive until
+   * the token is passed to `releaseHandle()`.
+   */
+  toHandle(): string
 }
 /**
  * GPU texture - multi-dimensional image data on the GPU
@@ -536,14 +1686,57 @@ export declare class GpuBuffer {
  * Create views to bind textures to shaders.
  */
 export declare class GpuTexture {
-  /** Create a view of this texture */
-  createView(label?: string | undefined | null): GpuTextureView
-  /** Get texture width */
-  width(): number
-  /** Get texture height */
-  height(): number
+  /**
+   * Create a view of this texture
+   *
+   * Accepts a view descriptor, or just a label for a default view.
+   */
+  createView(descriptor?: TextureViewDescriptor | string): GpuTextureView
+  /**
+   * Create a 2D view of one array layer (extension)
+   *
+   * Covers a single mip level (`mipLevel`, default 0), as needed to render
+   * into one layer of a texture array or cube map.
+   */
+  createLayerView(layer: number, options?: LayerViewOptions | undefined | null): GpuTextureView
+  /** Create a view of one mip level, covering all array layers (extension) */
+  createMipView(level: number, label?: string | undefined | null): GpuTextureView
+  /** Width of the base mip level in texels */
+  get width(): number
+  /** Height of the base mip level in texels */
+  get height(): number
+  /** Depth of 3D textures, or the number of array layers */
+  get depthOrArrayLayers(): number
+  get mipLevelCount(): number
+  get sampleCount(): number
+  /** "1d", "2d" or "3d" */
+  get dimension(): string
+  /** Texture format name, e.g. "rgba8unorm" */
+  get format(): string
+  /** Usage flags of the texture */
+  get usage(): number
+  /** Names of the usage flags, e.g. ["COPY_DST", "TEXTURE_BINDING"] (extension) */
+  usageNames(): Array<string>
+  /** Descriptor label, or "" without one */
+  get label(): string
+  /**
+   * Read one mip level back as tightly packed RGBA8 pixels (extension)
+   *
+   * For 2D rgba8unorm, rgba8unorm-srgb, bgra8unorm and bgra8unorm-srgb
+   * textures with COPY_SRC usage; BGRA pixels are swizzled to RGBA. Reads
+   * array layer 0 of `mipLevel` (default 0). Submits the copy immediately
+   * and resolves once the GPU is done.
+   */
+  readPixels(mipLevel?: number | undefined | null): Promise<Buffer>
   /** Destroy the texture */
   destroy(): void
+  /**
+   * Register the texture for use in worker threads
+   *
+   * Returns a token for `textureFromHandle()`. The texture stays alive until
+   * the token is passed to `releaseHandle()`.
+   */
+  toHandle(): string
 }
 /**
  * Texture view - a view into a texture for binding to shaders
@@ -584,6 +1777,12 @@ export declare class GpuComputePipeline {
    * Used for automatic layout inference when pipeline is created without explicit layout
    */
   getBindGroupLayout(index: number): GpuBindGroupLayout
+  /**
+   * The entry point's `@workgroup_size` as `[x, y, z]` (extension)
+   *
+   * Unset dimensions are 1. Suitable as the `workgroupSize` of `dispatchThreads()`.
+   */
+  getWorkgroupSize(): Array<number>
 }
 /**
  * Render pipeline - configured graphics pipeline
@@ -624,14 +1823,101 @@ export declare class GpuRenderBundle {
   /** Destroy the render bundle (automatic when dropped) */
   destroy(): void
 }
+/**
+ * Render bundle encoder following WebGPU spec
+ *
+ * Commands are recorded here and encoded into a wgpu bundle on `finish()`,
+ * the same way render passes are replayed on `end()`.
+ */
+export declare class GpuRenderBundleEncoder {
+  /** Set the pipeline for subsequent draws (WebGPU standard method) */
+  setPipeline(pipeline: GpuRenderPipeline): void
+  /** Set a bind group (WebGPU standard method) */
+  setBindGroup(index: number, bindGroup: GpuBindGroup, dynamicOffsets?: Array<number> | undefined | null): void
+  /** Set a vertex buffer (WebGPU standard method) */
+  setVertexBuffer(slot: number, buffer: GpuBuffer, offset?: number | undefined | null, size?: number | undefined | null): void
+  /** Set the index buffer (WebGPU standard method) */
+  setIndexBuffer(buffer: GpuBuffer, indexFormat: string | number, offset?: number | undefined | null, size?: number | undefined | null): void
+  /** Draw primitives (WebGPU standard method) */
+  draw(vertexCount: number, instanceCount?: number | undefined | null, firstVertex?: number | undefined | null, firstInstance?: number | undefined | null): void
+  /** Draw indexed primitives (WebGPU standard method) */
+  drawIndexed(indexCount: number, instanceCount?: number | undefined | null, firstIndex?: number | undefined | null, baseVertex?: number | undefined | null, firstInstance?: number | undefined | null): void
+  /** Draw primitives using parameters from a buffer (WebGPU standard method) */
+  drawIndirect(indirectBuffer: GpuBuffer, indirectOffset: number): void
+  /** Draw indexed primitives using parameters from a buffer (WebGPU standard method) */
+  drawIndexedIndirect(indirectBuffer: GpuBuffer, indirectOffset: number): void
+  /**
+   * Finish recording and create the render bundle (WebGPU standard method)
+   * After calling this, the encoder can no longer be used
+   */
+  finish(descriptor?: RenderBundleDescriptor | undefined | null): GpuRenderBundle
+  /**
+   * Record one draw per `[vertexCount, instanceCount, firstVertex, firstInstance]`
+   * in `draws` (extension)
+   */
+  drawBatch(draws: Uint32Array, bindGroupIndex?: number | undefined | null, bindGroup?: GpuBindGroup | undefined | null, dynamicOffsets?: Uint32Array | undefined | null): void
+  /**
+   * Record one indexed draw per
+   * `[indexCount, instanceCount, firstIndex, baseVertex, firstInstance]` in
+   * `draws` (extension)
+   */
+  drawIndexedBatch(draws: Uint32Array, bindGroupIndex?: number | undefined | null, bindGroup?: GpuBindGroup | undefined | null, dynamicOffsets?: Uint32Array | undefined | null): void
+  /**
+   * Record a binary command stream (extension)
+   *
+   * Takes the same commands as render passes.
+   */
+  executeCommandStream(stream: Uint32Array, pipelines: Array<GpuRenderPipeline>, bindGroups?: Array<GpuBindGroup> | undefined | null, buffers?: Array<GpuBuffer> | undefined | null): void
+}
 /** GPU queue for submitting commands following WebGPU spec */
 export declare class GpuQueue {
-  /** Submit command buffers to the queue (WebGPU standard - accepts array) */
-  submit(commandBuffers: Array<GpuCommandBuffer>): void
+  /**
+   * Submit command buffers to the queue (WebGPU standard - accepts array)
+   *
+   * A single command buffer is accepted too. Buffers that were already
+   * submitted are skipped with a warning instead of failing the submission.
+   */
+  submit(commandBuffers: Array<GpuCommandBuffer> | GpuCommandBuffer): void
   /** Write data to a buffer using the queue */
   writeBuffer(buffer: GpuBuffer, offset: number, data: Buffer): void
+  /**
+   * Write data to a texture using the queue
+   *
+   * The texture is passed separately from its location, as in
+   * `copyTextureToTexture`; the JavaScript wrapper accepts the standard form.
+   */
+  writeTexture(destination: GpuTexture, location: TextureCopyLocation, data: Buffer, layout: TextureDataLayout, size: Extent3D): void
+  /**
+   * Upload large data to a buffer with a single copy submission (extension)
+   *
+   * Unlike `writeBuffer`, which goes through wgpu's internal staging belt on every call,
+   * the data is written straight into `mappedAtCreation` staging buffers (in 64 MiB
+   * chunks) and copied into `buffer` by one submitted command buffer. Intended for
+   * multi-hundred-MB uploads; `offset` and the data length must be multiples of 4.
+   */
+  uploadBuffer(buffer: GpuBuffer, offset: number, data: Buffer): void
+  /**
+   * Write the six faces of a cube map in one call (extension)
+   *
+   * `faces` are tightly packed images in layer order +X, -X, +Y, -Y, +Z, -Z,
+   * written to layers `baseArrayLayer..baseArrayLayer + 6` (default 0, use a
+   * multiple of 6 to fill one cube of a cube array) at `mipLevel`.
+   */
+  writeCubeFaces(texture: GpuTexture, faces: Array<Buffer>, options?: CubeFacesOptions | undefined | null): void
+  /** Resolve once all work submitted so far has finished (WebGPU standard method) */
+  onSubmittedWorkDone(): Promise<void>
   /** Get the label of this queue */
   get label(): string | null
+  /**
+   * Copy a decoded RGBA8 image into a texture (WebGPU standard method)
+   *
+   * Flipping, premultiplying and the color space conversion run in a
+   * compute pass before the copy, which is submitted immediately. The
+   * destination must be an rgba8unorm or bgra8unorm texture (or their
+   * -srgb variants) with COPY_DST usage; the JavaScript wrapper accepts
+   * the standard form.
+   */
+  copyExternalImageToTexture(source: ExternalImageSource, destination: GpuTexture, location: TextureCopyLocation, conversion: ExternalImageDestination | undefined | null, size: Extent3D): void
 }
 /** GPU supported features following WebGPU spec */
 export declare class GpuSupportedFeatures {
@@ -639,10 +1925,20 @@ export declare class GpuSupportedFeatures {
   has(feature: string): boolean
   /** Get the number of features supported */
   get size(): number
+  /**
+   * Names of the supported features, as accepted by `has`
+   *
+   * The JavaScript wrapper builds the rest of the set-like interface
+   * (iteration, `keys`, `entries`, `forEach`) on this.
+   */
+  values(): Array<string>
 }
 /**
  * Compute pass encoder following WebGPU spec
  * Records commands for compute shader execution
+ *
+ * Commands are recorded here and encoded into the parent command encoder on
+ * `end()`, so the pass never outlives or aliases the encoder.
  */
 export declare class GpuComputePassEncoder {
   /** Set the pipeline for this compute pass (WebGPU standard method) */
@@ -651,6 +1947,14 @@ export declare class GpuComputePassEncoder {
   setBindGroup(index: number, bindGroup: GpuBindGroup, dynamicOffsets?: Array<number> | undefined | null): void
   /** Dispatch work to the compute shader (WebGPU standard method) */
   dispatchWorkgroups(workgroupCountX: number, workgroupCountY?: number | undefined | null, workgroupCountZ?: number | undefined | null): void
+  /**
+   * Dispatch enough workgroups to cover `x * y * z` invocations (extension)
+   *
+   * `workgroup_size` is the shader's `@workgroup_size`, as a number or an
+   * `[x, y, z]` array (missing dimensions are 1). Each workgroup count is
+   * rounded up, so the shader must bounds-check invocations past the end.
+   */
+  dispatchThreads(x: number, y: number | undefined | null, z: number | undefined | null, workgroupSize: number | number[]): void
   /** Dispatch work using parameters from a buffer (WebGPU standard method) */
   dispatchWorkgroupsIndirect(indirectBuffer: GpuBuffer, indirectOffset: number): void
   /**
@@ -664,10 +1968,28 @@ export declare class GpuComputePassEncoder {
   popDebugGroup(): void
   /** Insert a debug marker (WebGPU standard method) */
   insertDebugMarker(label: string): void
+  /**
+   * Record one dispatch per `[x, y, z]` triple in `counts` (extension)
+   *
+   * With `bindGroup`, the bind group is set before every dispatch using that
+   * dispatch's share of `dynamicOffsets`, or once if there are no offsets.
+   */
+  dispatchWorkgroupsBatch(counts: Uint32Array, bindGroupIndex?: number | undefined | null, bindGroup?: GpuBindGroup | undefined | null, dynamicOffsets?: Uint32Array | undefined | null): void
+  /**
+   * Record a binary command stream (extension)
+   *
+   * `stream` holds `GPUCommandOpcode` commands whose pipeline, bind group and
+   * buffer arguments index into the given tables. The whole stream is
+   * checked before anything is recorded.
+   */
+  executeCommandStream(stream: Uint32Array, pipelines: Array<GpuComputePipeline>, bindGroups?: Array<GpuBindGroup> | undefined | null, buffers?: Array<GpuBuffer> | undefined | null): void
 }
 /**
  * Render pass encoder following WebGPU spec
  * Records commands for rendering operations
+ *
+ * Commands are recorded here and encoded into the parent command encoder on
+ * `end()`, so the pass never outlives or aliases the encoder.
  */
 export declare class GpuRenderPassEncoder {
   /** Set the pipeline for this render pass (WebGPU standard method) */
@@ -677,7 +1999,7 @@ export declare class GpuRenderPassEncoder {
   /** Set the vertex buffer for this render pass (WebGPU standard method) */
   setVertexBuffer(slot: number, buffer: GpuBuffer, offset?: number | undefined | null, size?: number | undefined | null): void
   /** Set the index buffer for this render pass (WebGPU standard method) */
-  setIndexBuffer(buffer: GpuBuffer, indexFormat: string, offset?: number | undefined | null, size?: number | undefined | null): void
+  setIndexBuffer(buffer: GpuBuffer, indexFormat: string | number, offset?: number | undefined | null, size?: number | undefined | null): void
   /** Draw primitives (WebGPU standard method) */
   draw(vertexCount: number, instanceCount?: number | undefined | null, firstVertex?: number | undefined | null, firstInstance?: number | undefined | null): void
   /** Draw indexed primitives (WebGPU standard method) */
@@ -707,4 +2029,465 @@ export declare class GpuRenderPassEncoder {
   popDebugGroup(): void
   /** Insert a debug marker (WebGPU standard method) */
   insertDebugMarker(label: string): void
+  /**
+   * Record one draw per `[vertexCount, instanceCount, firstVertex, firstInstance]`
+   * in `draws` (extension)
+   */
+  drawBatch(draws: Uint32Array, bindGroupIndex?: number | undefined | null, bindGroup?: GpuBindGroup | undefined | null, dynamicOffsets?: Uint32Array | undefined | null): void
+  /**
+   * Record one indexed draw per
+   * `[indexCount, instanceCount, firstIndex, baseVertex, firstInstance]` in
+   * `draws` (extension)
+   */
+  drawIndexedBatch(draws: Uint32Array, bindGroupIndex?: number | undefined | null, bindGroup?: GpuBindGroup | undefined | null, dynamicOffsets?: Uint32Array | undefined | null): void
+  /**
+   * Record a binary command stream (extension)
+   *
+   * `stream` holds `GPUCommandOpcode` commands whose pipeline, bind group and
+   * buffer arguments index into the given tables. The whole stream is
+   * checked before anything is recorded.
+   */
+  executeCommandStream(stream: Uint32Array, pipelines: Array<GpuRenderPipeline>, bindGroups?: Array<GpuBindGroup> | undefined | null, buffers?: Array<GpuBuffer> | undefined | null): void
+}
+/**
+ * Prefix sum (scan) utility
+ *
+ * Computes exclusive or inclusive prefix sums over u32/f32 storage buffers.
+ * Large inputs are scanned in 256-element blocks; the block totals are scanned
+ * recursively and added back, so any element count is supported.
+ * Building block for stream compaction, GPU culling, and radix sort.
+ */
+export declare class GpuPrefixSum {
+  /**
+   * Record the prefix sum into a command encoder
+   *
+   * Scans `count` elements of `input` into `output`. The scan is exclusive
+   * unless `inclusive` is true. Both buffers need STORAGE usage.
+   */
+  encode(encoder: GpuCommandEncoder, input: GpuBuffer, output: GpuBuffer, count: number, inclusive?: boolean | undefined | null): void
+  /**
+   * Run the prefix sum immediately
+   *
+   * Convenience wrapper that encodes the scan into a fresh command buffer and submits it.
+   */
+  run(input: GpuBuffer, output: GpuBuffer, count: number, inclusive?: boolean | undefined | null): void
+}
+/**
+ * GPU radix sort utility
+ *
+ * Sorts u32 keys in ascending order, optionally carrying a u32 value per key.
+ * Each pass handles 4 key bits: a per-block digit histogram, a prefix sum over
+ * the histograms, and a stable scatter into a scratch buffer. Sorting happens
+ * in place; scratch buffers are allocated per call.
+ */
+export declare class GpuRadixSort {
+  /**
+   * Record an in-place sort into a command encoder
+   *
+   * Sorts the first `count` u32 keys ascending. If `values` is provided, each
+   * value moves with its key (stable). `keyBits` (default 32) limits the sort
+   * to the low bits of each key, saving passes for small key ranges.
+   */
+  encode(encoder: GpuCommandEncoder, keys: GpuBuffer, count: number, values?: GpuBuffer | undefined | null, keyBits?: number | undefined | null): void
+  /**
+   * Sort immediately
+   *
+   * Convenience wrapper that encodes the sort into a fresh command buffer and submits it.
+   */
+  run(keys: GpuBuffer, count: number, values?: GpuBuffer | undefined | null, keyBits?: number | undefined | null): void
+}
+/**
+ * Tiled matrix multiply (GEMM) utility
+ *
+ * Multiplies dense row-major f32/f16 matrices stored in storage buffers.
+ * Tiles are staged in workgroup memory and each invocation accumulates a 4x4
+ * block of the result in f32, so f16 inputs keep reasonable precision.
+ */
+export declare class GpuMatmul {
+  /** Record `output = a * b` into a command encoder */
+  encode(encoder: GpuCommandEncoder, a: GpuBuffer, b: GpuBuffer, output: GpuBuffer, dims: MatmulDimensions): void
+  /**
+   * Compute `output = a * b` immediately
+   *
+   * Convenience wrapper that encodes the multiply into a fresh command buffer and submits it.
+   */
+  run(a: GpuBuffer, b: GpuBuffer, output: GpuBuffer, dims: MatmulDimensions): void
+  /**
+   * Multiply two matrices into a newly allocated buffer
+   *
+   * The result buffer has STORAGE | COPY_SRC | COPY_DST usage and is
+   * m * n elements long. The work is submitted immediately.
+   */
+  matmul(a: GpuBuffer, b: GpuBuffer, dims: MatmulDimensions): GpuBuffer
+}
+/** CPU-side copy of a WGSL struct, packed to match the shader's memory layout */
+export declare class GpuUniformWriter {
+  /**
+   * Set struct fields from a `{ field: value }` object
+   *
+   * Fields that are not mentioned keep their previous value (initially zero).
+   * Vectors and matrices take arrays or TypedArrays (matrices column-major);
+   * nested structs take objects.
+   */
+  set(values: object): void
+  /** Write the packed struct to `buffer` at `offset` (default 0) */
+  upload(queue: GpuQueue, buffer: GpuBuffer, offset?: number | undefined | null): void
+  /** Packed struct bytes */
+  getData(): Buffer
+  /** Struct size in bytes, including trailing padding */
+  get size(): number
+}
+/** Presentable surface of a native window */
+export declare class GpuSurface {
+  /** Formats, present modes and alpha modes this surface supports on `adapter` */
+  getCapabilities(adapter: GpuAdapter): SurfaceCapabilities
+  /**
+   * Configure the surface for presenting frames rendered by `device`
+   *
+   * Throws if the present or alpha mode isn't supported by the surface on
+   * the device's adapter; the "auto" modes always succeed.
+   */
+  configure(device: GpuDevice, configuration: SurfaceConfiguration): void
+  /**
+   * Reconfigure the surface for a new window size, keeping the other settings
+   *
+   * The new size applies from the next acquired frame. A zero width or
+   * height (a minimized window) is ignored; keep rendering paused until the
+   * window has a size again.
+   */
+  resize(width: number, height: number): void
+  /**
+   * Acquire the next frame to render into
+   *
+   * An outdated or lost surface (e.g. after the window was resized) is
+   * reconfigured and acquired again. Returns `null` if no frame became
+   * available in time; skip rendering that frame. Call `present()` on the
+   * returned texture once the frame's commands have been submitted.
+   */
+  getCurrentTexture(): GpuSurfaceTexture | null
+  /**
+   * Call `callback` with a texture for every frame until `stop()` (extension)
+   *
+   * Present the texture at the end of the callback; the next frame is
+   * acquired once it has been presented or dropped. The loop keeps the
+   * process alive while it runs.
+   */
+  runRenderLoop(callback: (texture: GpuSurfaceTexture, frame: RenderLoopFrame) => void, options?: RenderLoopOptions | undefined | null): GpuRenderLoop
+}
+/** Frame acquired from a surface */
+export declare class GpuSurfaceTexture {
+  /** Create a view of this frame, e.g. for a render pass color attachment */
+  createView(descriptor?: TextureViewDescriptor | string): GpuTextureView
+  /** Get texture width */
+  width(): number
+  /** Get texture height */
+  height(): number
+  /** Whether the surface should be reconfigured to present efficiently */
+  get suboptimal(): boolean
+  /** Whether `present()` was called */
+  get presented(): boolean
+  /** Present the frame to the window */
+  present(): void
+}
+/**
+ * Timestamp query timer
+ *
+ * Measures labeled spans of encoded work and reports their GPU durations in
+ * milliseconds. Requires the "timestamp-query" feature.
+ */
+export declare class GpuTimer {
+  /**
+   * Start a labeled span by writing a timestamp into `encoder`
+   *
+   * Returns the span index to pass to `end()`.
+   */
+  begin(encoder: GpuCommandEncoder, label: string): number
+  /** End the span returned by `begin()` by writing a timestamp into `encoder` */
+  end(encoder: GpuCommandEncoder, span: number): void
+  /**
+   * Read back the durations of all spans recorded since the last call
+   *
+   * Call after submitting the encoders the spans were written to. The spans
+   * are cleared, so the timer can be reused for the next measurement.
+   */
+  results(): Promise<Array<TimerResult>>
+  /** Maximum number of spans between `results()` calls */
+  get capacity(): number
+}
+/** Handle of a running render loop */
+export declare class GpuRenderLoop {
+  /** Stop the loop; a frame already passed to the callback still completes */
+  stop(): void
+  /** False once stopped, or after the surface failed to provide a frame */
+  get running(): boolean
+}
+/**
+ * Separable Gaussian blur utility
+ *
+ * Blurs a texture in two render passes, horizontally into an rgba16float
+ * intermediate and then vertically into the destination. Texels are read
+ * without filtering, so any float or normalized format can be blurred.
+ */
+export declare class GpuBlur {
+  /** Blur radius in texels */
+  get radius(): number
+  /** Standard deviation in texels */
+  get sigma(): number
+  /**
+   * Record the blur into a command encoder
+   *
+   * Blurs mip level 0 of `source` into `destination`, which must be a
+   * different texture of the same size. The source needs TEXTURE_BINDING
+   * usage, the destination RENDER_ATTACHMENT.
+   */
+  encode(encoder: GpuCommandEncoder, source: GpuTexture, destination: GpuTexture): void
+  /**
+   * Run the blur immediately
+   *
+   * Convenience wrapper that encodes the blur into a fresh command buffer and submits it.
+   */
+  run(source: GpuTexture, destination: GpuTexture): void
+}
+/**
+ * Per-channel histogram utility
+ *
+ * Counts the red, green, blue and alpha values of a texture or of packed
+ * RGBA8 pixels into `bins` equal ranges of [0, 1]. Each workgroup counts
+ * into workgroup memory before adding its bins to the output with atomics,
+ * so few invocations contend for the same counter.
+ */
+export declare class GpuHistogram {
+  /** Bins per channel */
+  get bins(): number
+  /** Bytes `encode()` writes: the red, green, blue and alpha bins as u32 */
+  get outputSize(): number
+  /**
+   * Record the histogram of a texture into a command encoder
+   *
+   * Counts mip level 0 of `source`, which needs TEXTURE_BINDING usage and a
+   * float or normalized format. `output` is overwritten with the bins and
+   * needs STORAGE and COPY_DST usage.
+   */
+  encode(encoder: GpuCommandEncoder, source: GpuTexture, output: GpuBuffer): void
+  /**
+   * Record the histogram of packed RGBA8 pixels into a command encoder
+   *
+   * Counts `count` 4-byte pixels of `pixels`, which needs STORAGE usage.
+   * `output` is overwritten as in `encode()`.
+   */
+  encodeBuffer(encoder: GpuCommandEncoder, pixels: GpuBuffer, count: number, output: GpuBuffer): void
+  /**
+   * Compute the histogram of a texture and read the bins back
+   *
+   * Submits the work immediately and resolves once the GPU is done.
+   */
+  compute(source: GpuTexture): Promise<HistogramResult>
+  /** Compute the histogram of packed RGBA8 pixels and read the bins back */
+  computeBuffer(pixels: GpuBuffer, count: number): Promise<HistogramResult>
+}
+/**
+ * Shaped, typed view of a storage buffer
+ *
+ * Elements are laid out by `strides` (in elements) starting at `offset`.
+ * Tensors created by `createTensor` and `fromArray` are row-major and
+ * contiguous; `slice()` returns views into the same buffer.
+ */
+export declare class GpuTensor {
+  /**
+   * Create a tensor holding `data` (extension)
+   *
+   * `data` is either raw little-endian elements of the dtype or numbers,
+   * which are converted to it. The `GpuTensor` wrapper accepts typed
+   * arrays and picks between the two.
+   */
+  static fromArray(device: GpuDevice, data: Buffer | Array<number>, descriptor?: TensorDescriptor | undefined | null): GpuTensor
+  /** Size of each dimension */
+  get shape(): Array<number>
+  /** Elements between neighbors along each dimension */
+  get strides(): Array<number>
+  /** "f32", "f16", "i32" or "u8" */
+  get dtype(): string
+  /** Number of elements */
+  get size(): number
+  /** Offset of the first element in the buffer, in bytes */
+  get byteOffset(): number
+  /** Whether the elements are packed in row-major order */
+  get isContiguous(): boolean
+  /** The storage buffer holding the elements, shared with other views */
+  get buffer(): GpuBuffer
+  /**
+   * View of `start..end` along `dimension`, sharing the buffer
+   *
+   * `end` defaults to the size of the dimension. Slicing any but the first
+   * dimension gives a non-contiguous view.
+   */
+  slice(dimension: number, start: number, end?: number | undefined | null): GpuTensor
+  /**
+   * View with a new shape of the same number of elements, sharing the buffer
+   *
+   * One dimension may be -1 to infer its size. Non-contiguous slices can't
+   * be reshaped.
+   */
+  reshape(shape: Array<number>): GpuTensor
+  /**
+   * Read the elements back in row-major order
+   *
+   * Resolves to a Float32Array for f32 and f16, an Int32Array for i32 and
+   * a Uint8Array for u8 tensors.
+   */
+  toArray(): Promise<Float32Array | Int32Array | Uint8Array>
+}
+/**
+ * Immediate-mode 2D renderer
+ *
+ * Draw calls queue colored rectangles, lines, quads and sprites in pixel
+ * coordinates, with the origin at the top left of the target. `encode()`
+ * draws everything queued since the last call into a texture view in one
+ * render pass. Shapes are alpha blended in call order.
+ */
+export declare class GpuDraw2D {
+  /**
+   * Fill an axis-aligned rectangle
+   *
+   * `color` is [r, g, b, a?] from 0 to 1 (default white).
+   */
+  rect(x: number, y: number, width: number, height: number, color?: Array<number> | undefined | null): void
+  /** Draw a line segment `width` pixels wide (default 1) with square ends */
+  line(x0: number, y0: number, x1: number, y1: number, color?: Array<number> | undefined | null, width?: number | undefined | null): void
+  /** Fill a quadrilateral given as [x0, y0, x1, y1, x2, y2, x3, y3] in drawing order */
+  quad(points: Array<number>, color?: Array<number> | undefined | null): void
+  /**
+   * Draw a texture, or a region of it, stretched over a rectangle
+   *
+   * The texture needs TEXTURE_BINDING usage and a filterable float format;
+   * its first array layer and all mip levels are sampled.
+   */
+  sprite(texture: GpuTexture, x: number, y: number, width: number, height: number, options?: SpriteOptions | undefined | null): void
+  /** Move the origin of later draws */
+  translate(x: number, y: number): void
+  /** Scale later draws about the origin */
+  scale(x: number, y?: number | undefined | null): void
+  /** Rotate later draws about the origin, clockwise on screen */
+  rotate(radians: number): void
+  /** Replace the transform with [a, b, c, d, e, f], as in the canvas API */
+  setTransform(a: number, b: number, c: number, d: number, e: number, f: number): void
+  /** Go back to drawing in target pixels */
+  resetTransform(): void
+  /** Push the current transform onto a stack */
+  save(): void
+  /** Pop the transform pushed by the last `save()` */
+  restore(): void
+  /**
+   * Record the queued draws into a render pass on `target`
+   *
+   * Draws over the current contents unless `clear` is given. The queue is
+   * emptied; the transform is kept.
+   */
+  encode(encoder: GpuCommandEncoder, target: GpuTextureView, options?: Draw2DEncodeOptions | undefined | null): void
+  /**
+   * Draw the queued shapes immediately
+   *
+   * Convenience wrapper that encodes them into a fresh command buffer and submits it.
+   */
+  run(target: GpuTextureView, options?: Draw2DEncodeOptions | undefined | null): void
+}
+/**
+ * TrueType font drawn from a glyph atlas
+ *
+ * Glyphs are rasterized on first use at each size and packed into a 1024x1024
+ * `r8unorm` atlas texture.
+ */
+export declare class GpuFont {
+  /** Default text size in pixels per em */
+  get size(): number
+  /** Distance between baselines at the default size, in pixels */
+  get lineHeight(): number
+  /** Measure `text` without drawing it */
+  measureText(text: string, options?: TextOptions | undefined | null): TextMetrics
+  /**
+   * Record drawing `text` into a render pass
+   *
+   * (x, y) is the top left of the first line in pixels of the pass's
+   * attachments, and `
+  ` starts a new line. Text is alpha blended into
+   * the first color attachment, ignoring depth. This sets the pass's
+   * pipeline, bind group 0 and vertex buffer 0, so set them again before
+   * drawing anything else.
+   */
+  drawText(pass: GpuRenderPassEncoder, text: string, x: number, y: number, options?: TextOptions | undefined | null): void
+}
+/** Compute pipeline with its bind groups, dispatched by `run()` */
+export declare class GpuComputeRunner {
+  /**
+   * Write `params` into the parameter buffer and dispatch `workgroups`
+   *
+   * `workgroups` is a count or `[x, y?, z?]`. The write and the dispatch
+   * are submitted immediately, in this order, so each run sees its own
+   * parameters. Without `params` the buffer is left as it is.
+   */
+  run(params: Buffer | undefined | null, workgroups: number | number[]): void
+}
+/** Reads rendered frames back through rotating staging buffers */
+export declare class GpuFrameCapture {
+  /**
+   * Copy mip level 0 of `texture` into the next free staging buffer
+   *
+   * For the texture formats `readPixels()` supports. The copy is
+   * submitted immediately; when every staging buffer holds a frame, this
+   * first waits for the oldest to be read back. Resolves with the frame's
+   * index.
+   */
+  capture(texture: GpuTexture, timestamp?: number | undefined | null): Promise<number>
+  /**
+   * The oldest frame not yet taken, in capture order
+   *
+   * Waits for the next `capture()` when there is none, and resolves with
+   * null once the capture was ended and every frame was taken.
+   */
+  next(): Promise<CapturedFrame | null>
+  /** Stop capturing; `next()` still returns the frames captured so far */
+  end(): void
+  /** Number of frames captured and not yet taken by `next()` */
+  get queuedFrames(): number
+  /** Descriptor label, or "" without one */
+  get label(): string
+}
+/** One logical dataset stored in several storage buffers */
+export declare class GpuChunkedBuffer {
+  /** Size of the dataset in bytes */
+  get size(): number
+  get elementSize(): number
+  get chunkCount(): number
+  /** Where each chunk lies in the dataset, in order */
+  get chunks(): Array<BufferChunk>
+  /** The storage buffer holding chunk `index` */
+  buffer(index: number): GpuBuffer
+  /**
+   * Uniform buffer holding a `ChunkInfo` per chunk, `infoStride` bytes apart
+   *
+   * `struct ChunkInfo { index: u32, elementCount: u32, elementOffset: vec2<u32> }`,
+   * with the low 32 bits of the element offset first.
+   */
+  get infoBuffer(): GpuBuffer
+  get infoStride(): number
+  /**
+   * Write `data` at byte `offset` of the dataset, across chunks as needed
+   *
+   * Like `queue.writeBuffer()`, the offset and length must be multiples of 4.
+   */
+  write(offset: number, data: Buffer): void
+  /** Read `size` bytes at byte `offset` of the dataset (default: all of it) */
+  read(offset?: number | undefined | null, size?: number | undefined | null): Promise<Buffer>
+  /**
+   * Dispatch `pipeline` once per chunk
+   *
+   * `bind_groups[i]` is set at `group` for chunk `i`, and the shared bind
+   * groups at their `sharedGroups` indices for every chunk. Each chunk
+   * gets one invocation per element; counts beyond
+   * `maxComputeWorkgroupsPerDimension` workgroups spill into y. All
+   * dispatches are recorded in one pass and submitted immediately.
+   */
+  dispatch(pipeline: GpuComputePipeline, bindGroups: Array<GpuBindGroup>, shared: Array<GpuBindGroup>, descriptor?: ChunkDispatchDescriptor | undefined | null): void
+  /** Descriptor label, or "" without one */
+  get label(): string
 }
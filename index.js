@@ -310,7 +310,7 @@ if (!nativeBinding) {
   throw new Error(`Failed to load native binding`)
 }
 
-const { Gpu, GpuAdapter, GpuDevice, GpuShaderModule, GpuCommandEncoder, GpuCommandBuffer, GpuBuffer, GpuTexture, GpuTextureView, bufferUsage, mapMode, textureUsage, GpuBindGroupLayout, GpuBindGroup, GpuPipelineLayout, GpuComputePipeline, GpuRenderPipeline, GpuSampler, GpuQuerySet, GpuRenderBundle, GpuQueue, GpuSupportedFeatures, GpuComputePassEncoder, GpuRenderPassEncoder } = nativeBinding
+const { Gpu, GpuAdapter, GpuDevice, GpuShaderModule, GpuCommandEncoder, GpuCommandBuffer, GpuBuffer, GpuTexture, GpuTextureView, bufferUsage, mapMode, textureUsage, GpuBindGroupLayout, GpuBindGroup, GpuPipelineLayout, GpuComputePipeline, GpuRenderPipeline, GpuSampler, GpuQuerySet, GpuRenderBundle, GpuQueue, GpuSupportedFeatures, GpuComputePassEncoder, GpuRenderPassEncoder, spirvToWgsl, translateWgsl, preprocessWgsl, GpuPrefixSum, GpuRadixSort, GpuMatmul, copyBufferBetweenDevices, copyTextureBetweenDevices, setLogCallback, GpuSurface, GpuSurfaceTexture, getTextureFormatInfo, GpuTimer, GpuRenderBundleEncoder, shaderStage, deviceFromHandle, bufferFromHandle, textureFromHandle, releaseHandle, descriptorEnums, commandOpcode, GpuTensor, packF16, unpackF16, quantizeInt8, dequantizeInt8, nullDeviceLimits, GpuComputeRunner, packDrawIndirectArgs, packDrawIndexedIndirectArgs, packDispatchIndirectArgs, unpackDrawIndirectArgs, unpackDrawIndexedIndirectArgs, unpackDispatchIndirectArgs, GpuFrameCapture, GpuChunkedBuffer } = nativeBinding
 
 module.exports.Gpu = Gpu
 module.exports.GpuAdapter = GpuAdapter
@@ -336,3 +336,39 @@ module.exports.GpuQueue = GpuQueue
 module.exports.GpuSupportedFeatures = GpuSupportedFeatures
 module.exports.GpuComputePassEncoder = GpuComputePassEncoder
 module.exports.GpuRenderPassEncoder = GpuRenderPassEncoder
+module.exports.spirvToWgsl = spirvToWgsl
+module.exports.translateWgsl = translateWgsl
+module.exports.preprocessWgsl = preprocessWgsl
+module.exports.GpuPrefixSum = GpuPrefixSum
+module.exports.GpuRadixSort = GpuRadixSort
+module.exports.GpuMatmul = GpuMatmul
+module.exports.copyBufferBetweenDevices = copyBufferBetweenDevices
+module.exports.copyTextureBetweenDevices = copyTextureBetweenDevices
+module.exports.setLogCallback = setLogCallback
+module.exports.GpuSurface = GpuSurface
+module.exports.GpuSurfaceTexture = GpuSurfaceTexture
+module.exports.getTextureFormatInfo = getTextureFormatInfo
+module.exports.GpuTimer = GpuTimer
+module.exports.GpuRenderBundleEncoder = GpuRenderBundleEncoder
+module.exports.shaderStage = shaderStage
+module.exports.deviceFromHandle = deviceFromHandle
+module.exports.bufferFromHandle = bufferFromHandle
+module.exports.textureFromHandle = textureFromHandle
+module.exports.releaseHandle = releaseHandle
+module.exports.descriptorEnums = descriptorEnums
+module.exports.commandOpcode = commandOpcode
+module.exports.GpuTensor = GpuTensor
+module.exports.packF16 = packF16
+module.exports.unpackF16 = unpackF16
+module.exports.quantizeInt8 = quantizeInt8
+module.exports.dequantizeInt8 = dequantizeInt8
+module.exports.nullDeviceLimits = nullDeviceLimits
+module.exports.GpuComputeRunner = GpuComputeRunner
+module.exports.packDrawIndirectArgs = packDrawIndirectArgs
+module.exports.packDrawIndexedIndirectArgs = packDrawIndexedIndirectArgs
+module.exports.packDispatchIndirectArgs = packDispatchIndirectArgs
+module.exports.unpackDrawIndirectArgs = unpackDrawIndirectArgs
+module.exports.unpackDrawIndexedIndirectArgs = unpackDrawIndexedIndirectArgs
+module.exports.unpackDispatchIndirectArgs = unpackDispatchIndirectArgs
+module.exports.GpuFrameCapture = GpuFrameCapture
+module.exports.GpuChunkedBuffer = GpuChunkedBuffer
@@ -0,0 +1,7 @@
+/**
+ * Installs navigator.gpu and the GPU* constants as globals:
+ *
+ *   require('@sylphx/webgpu/polyfill')
+ *   const adapter = await navigator.gpu.requestAdapter()
+ */
+require('./webgpu.js').installNavigatorGpu()
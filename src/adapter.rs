@@ -1,5 +1,6 @@
 use napi::bindgen_prelude::*;
 use napi_derive::napi;
+use std::sync::Arc;
 
 /// GPU adapter - represents a physical GPU or software renderer
 ///
@@ -7,12 +8,14 @@ use napi_derive::napi;
 /// and are used to request logical devices for GPU operations.
 #[napi]
 pub struct GpuAdapter {
-    pub(crate) adapter: wgpu::Adapter,
+    pub(crate) adapter: Arc<wgpu::Adapter>,
 }
 
 impl GpuAdapter {
     pub(crate) fn new(adapter: wgpu::Adapter) -> Self {
-        Self { adapter }
+        Self {
+            adapter: Arc::new(adapter),
+        }
     }
 }
 
@@ -20,78 +23,129 @@ impl GpuAdapter {
 impl GpuAdapter {
     /// Get adapter information
     ///
-    /// Returns name, vendor ID, device ID, type, and backend.
-    #[napi]
+    /// Returns name, vendor ID, device ID, type, backend, and driver details.
+    #[napi(catch_unwind)]
     pub fn get_info(&self) -> AdapterInfo {
-        let info = self.adapter.get_info();
-        AdapterInfo {
-            name: info.name,
-            vendor: info.vendor,
-            device: info.device,
-            device_type: format!("{:?}", info.device_type),
-            backend: format!("{:?}", info.backend),
-        }
+        AdapterInfo::from_adapter(&self.adapter)
+    }
+
+    /// Whether this is a fallback (software/CPU) adapter
+    #[napi(catch_unwind, getter)]
+    pub fn is_fallback_adapter(&self) -> bool {
+        self.adapter.get_info().device_type == wgpu::DeviceType::Cpu
     }
 
     /// Get adapter features
     ///
     /// Returns a list of optional features supported by this adapter.
     /// Features must be explicitly requested when creating a device.
-    #[napi]
+    #[napi(catch_unwind)]
     pub fn get_features(&self) -> Vec<String> {
-        let features = self.adapter.features();
-
-        let mut result = Vec::new();
-        if features.contains(wgpu::Features::DEPTH_CLIP_CONTROL) {
-            result.push("depth-clip-control".to_string());
-        }
-        if features.contains(wgpu::Features::TIMESTAMP_QUERY) {
-            result.push("timestamp-query".to_string());
-        }
-        if features.contains(wgpu::Features::TEXTURE_COMPRESSION_BC) {
-            result.push("texture-compression-bc".to_string());
+        let mut features = crate::features::feature_names(self.adapter.features());
+        if supports_read_write_storage_textures(&self.adapter) {
+            features.push(crate::features::READ_WRITE_STORAGE_TEXTURES.to_string());
         }
-        // Add more features as needed
-        result
+        features
     }
 
     /// Get adapter limits
     ///
-    /// Returns maximum resource sizes and counts supported by this adapter.
-    #[napi]
-    pub fn get_limits(&self) -> AdapterLimits {
-        let limits = self.adapter.limits();
-        AdapterLimits {
-            max_texture_dimension_1d: limits.max_texture_dimension_1d,
-            max_texture_dimension_2d: limits.max_texture_dimension_2d,
-            max_texture_dimension_3d: limits.max_texture_dimension_3d,
-            max_bind_groups: limits.max_bind_groups,
-            max_buffer_size: limits.max_buffer_size as i64, // Convert to i64
+    /// Returns the same limits a device reports, with the adapter's maximum values.
+    #[napi(catch_unwind)]
+    pub fn get_limits(&self) -> crate::GpuSupportedLimits {
+        crate::GpuSupportedLimits::from_wgpu(&self.adapter.limits())
+    }
+
+    /// Get the adapter's downlevel capabilities
+    ///
+    /// Reports which parts of WebGPU the adapter can't fully support, e.g.
+    /// on the GL backend. `compliant` is true when nothing is missing.
+    #[napi(catch_unwind, js_name = "getDownlevelCapabilities")]
+    pub fn get_downlevel_capabilities(&self) -> DownlevelCapabilities {
+        let capabilities = self.adapter.get_downlevel_capabilities();
+        DownlevelCapabilities {
+            compliant: capabilities.is_webgpu_compliant(),
+            compute_shaders: capabilities.flags.contains(wgpu::DownlevelFlags::COMPUTE_SHADERS),
+            flags: capabilities
+                .flags
+                .iter_names()
+                .map(|(name, _)| name.to_lowercase().replace('_', "-"))
+                .collect(),
+            shader_model: format!("{:?}", capabilities.shader_model).to_lowercase(),
         }
     }
 
     /// Request a device from this adapter
     ///
     /// Creates a logical device for executing GPU operations.
-    /// Currently requests timestamp query feature by default.
-    #[napi]
-    pub async fn request_device(&self) -> Result<crate::GpuDevice> {
+    /// Requests timestamp queries and adapter-specific format features when the adapter supports them,
+    /// in addition to `requiredFeatures`.
+    /// Without `requiredLimits`, adapters below the WebGPU default limits (e.g. GLES)
+    /// get the downlevel defaults they support.
+    #[napi(catch_unwind)]
+    pub async fn request_device(&self, descriptor: Option<crate::DeviceDescriptor>) -> Result<crate::GpuDevice> {
+        let descriptor = descriptor.unwrap_or_default();
+        let (preset, overrides) = crate::limits::limits_request(&descriptor);
+        let required_limits = crate::limits::required_limits(&self.adapter, preset.as_deref(), &overrides)?;
+        let required_features = crate::features::required_features(
+            &self.adapter,
+            descriptor.required_features.as_deref().unwrap_or_default(),
+        )?;
+
         let (device, queue) = self.adapter
             .request_device(
                 &wgpu::DeviceDescriptor {
-                    label: None,
-                    required_features: wgpu::Features::TIMESTAMP_QUERY,
-                    required_limits: wgpu::Limits::default(),
+                    label: descriptor.label.as_deref(),
+                    required_features,
+                    required_limits,
                 },
                 None,
             )
             .await
             .map_err(|e| Error::from_reason(format!("Failed to request device: {}", e)))?;
 
-        Ok(crate::GpuDevice::new(device, queue))
+        Ok(crate::GpuDevice::new(self.adapter.clone(), device, queue))
     }
 }
 
+/// The best standard limit tier `adapter` supports
+///
+/// WebGPU defaults, then wgpu's downlevel and WebGL2 tiers; the adapter's own
+/// limits if it falls below all of them.
+pub(crate) fn default_limits(adapter: &wgpu::Adapter) -> wgpu::Limits {
+    let supported = adapter.limits();
+    [
+        wgpu::Limits::default(),
+        wgpu::Limits::downlevel_defaults(),
+        wgpu::Limits::downlevel_webgl2_defaults(),
+    ]
+    .into_iter()
+    .find(|limits| limits.check_limits(&supported))
+    .unwrap_or(supported)
+}
+
+/// Whether `access: "read-only"` / `"read-write"` storage textures work on this adapter
+///
+/// Mirrors the WGSL `readonly_and_readwrite_storage_textures` language feature,
+/// which guarantees read-write access for r32float, r32uint and r32sint.
+pub(crate) fn supports_read_write_storage_textures(adapter: &wgpu::Adapter) -> bool {
+    adapter
+        .features()
+        .contains(wgpu::Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES)
+        && [
+            wgpu::TextureFormat::R32Float,
+            wgpu::TextureFormat::R32Uint,
+            wgpu::TextureFormat::R32Sint,
+        ]
+        .iter()
+        .all(|format| {
+            adapter
+                .get_texture_format_features(*format)
+                .flags
+                .contains(wgpu::TextureFormatFeatureFlags::STORAGE_READ_WRITE)
+        })
+}
+
 #[napi(object)]
 pub struct AdapterInfo {
     pub name: String,
@@ -99,13 +153,63 @@ pub struct AdapterInfo {
     pub device: u32,
     pub device_type: String,
     pub backend: String,
+    /// Driver name, e.g. "NVIDIA" or "llvmpipe" (empty if the backend doesn't report it)
+    pub driver: String,
+    /// Driver version and build details (empty if the backend doesn't report it)
+    pub driver_info: String,
+    /// API version the driver implements, e.g. "1.3.255" for Vulkan
+    ///
+    /// Only reported by the Vulkan backend.
+    pub backend_version: Option<String>,
+}
+
+impl AdapterInfo {
+    pub(crate) fn from_adapter(adapter: &wgpu::Adapter) -> Self {
+        let info = adapter.get_info();
+        Self {
+            name: info.name,
+            vendor: info.vendor,
+            device: info.device,
+            device_type: format!("{:?}", info.device_type),
+            backend: format!("{:?}", info.backend),
+            driver: info.driver,
+            driver_info: info.driver_info,
+            backend_version: backend_version(adapter),
+        }
+    }
+}
+
+/// The Vulkan API version of `adapter`, if it's a Vulkan adapter
+#[cfg(not(any(target_os = "macos", target_os = "ios")))]
+fn backend_version(adapter: &wgpu::Adapter) -> Option<String> {
+    // SAFETY: the raw adapter is only read inside the callback
+    let version = unsafe {
+        adapter.as_hal::<wgpu::hal::api::Vulkan, _, _>(|adapter| {
+            adapter.map(|adapter| adapter.physical_device_capabilities().properties().api_version)
+        })
+    }?;
+    Some(format!(
+        "{}.{}.{}",
+        (version >> 22) & 0x7f,
+        (version >> 12) & 0x3ff,
+        version & 0xfff
+    ))
+}
+
+#[cfg(any(target_os = "macos", target_os = "ios"))]
+fn backend_version(_adapter: &wgpu::Adapter) -> Option<String> {
+    None
 }
 
+/// What an adapter supports below full WebGPU
 #[napi(object)]
-pub struct AdapterLimits {
-    pub max_texture_dimension_1d: u32,
-    pub max_texture_dimension_2d: u32,
-    pub max_texture_dimension_3d: u32,
-    pub max_bind_groups: u32,
-    pub max_buffer_size: i64, // u64 not supported by napi, use i64
+pub struct DownlevelCapabilities {
+    /// Whether the adapter supports all of WebGPU
+    pub compliant: bool,
+    /// Whether compute pipelines can be used
+    pub compute_shaders: bool,
+    /// Supported downlevel flags, e.g. "compute-shaders", "indirect-execution"
+    pub flags: Vec<String>,
+    /// "sm2", "sm4" or "sm5"
+    pub shader_model: String,
 }
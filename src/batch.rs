@@ -0,0 +1,232 @@
+//! Recording many dispatches or draws in one call
+//!
+//! Each pass method is an N-API crossing, which dominates recording time when
+//! a frame issues thousands of small draws or dispatches. The batch methods
+//! take the parameters of every call packed into a `Uint32Array`, laid out
+//! like the matching indirect buffer arguments, and record them all at once.
+//! An optional bind group is set before each item with that item's slice of
+//! `dynamicOffsets`, or once up front when there are no offsets.
+
+use crate::compute_pass::ComputeCommand;
+use crate::render_pass::RenderCommand;
+use crate::{GpuBindGroup, GpuComputePassEncoder, GpuRenderBundleEncoder, GpuRenderPassEncoder};
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use std::sync::Arc;
+
+/// `x, y, z` workgroup counts
+const DISPATCH_STRIDE: usize = 3;
+/// `vertexCount, instanceCount, firstVertex, firstInstance`
+const DRAW_STRIDE: usize = 4;
+/// `indexCount, instanceCount, firstIndex, baseVertex, firstInstance`
+const DRAW_INDEXED_STRIDE: usize = 5;
+
+/// Bind group arguments shared by the batch methods
+struct BatchBindGroup<'a> {
+    index: Option<u32>,
+    bind_group: Option<&'a GpuBindGroup>,
+    dynamic_offsets: Option<&'a [u32]>,
+}
+
+/// Build the commands for a batch, with the bind group set before each item
+/// when dynamic offsets are given and once up front otherwise
+fn batch_commands<C>(
+    params: &[u32],
+    stride: usize,
+    name: &str,
+    bind: BatchBindGroup,
+    set_bind_group: impl Fn(u32, Arc<wgpu::BindGroup>, Vec<u32>) -> C,
+    command: impl Fn(&[u32]) -> C,
+) -> Result<Vec<C>> {
+    if params.len() % stride != 0 {
+        return Err(Error::from_reason(format!(
+            "{} length must be a multiple of {}, got {}",
+            name,
+            stride,
+            params.len()
+        )));
+    }
+    let count = params.len() / stride;
+    let offsets = bind.dynamic_offsets.unwrap_or_default();
+
+    let (index, bind_group) = match (bind.index, bind.bind_group) {
+        (Some(index), Some(bind_group)) => (index, &bind_group.bind_group),
+        (None, None) if offsets.is_empty() => return Ok(params.chunks_exact(stride).map(command).collect()),
+        (None, None) => return Err(Error::from_reason("dynamicOffsets require a bindGroup")),
+        _ => return Err(Error::from_reason("bindGroupIndex and bindGroup must be given together")),
+    };
+    if offsets.is_empty() {
+        let mut commands = Vec::with_capacity(count + 1);
+        commands.push(set_bind_group(index, bind_group.clone(), Vec::new()));
+        commands.extend(params.chunks_exact(stride).map(command));
+        return Ok(commands);
+    }
+    if count == 0 || offsets.len() % count != 0 {
+        return Err(Error::from_reason(format!(
+            "dynamicOffsets length must be a multiple of the batch size {}, got {}",
+            count,
+            offsets.len()
+        )));
+    }
+
+    let mut commands = Vec::with_capacity(count * 2);
+    for (item, offsets) in params.chunks_exact(stride).zip(offsets.chunks_exact(offsets.len() / count)) {
+        commands.push(set_bind_group(index, bind_group.clone(), offsets.to_vec()));
+        commands.push(command(item));
+    }
+    Ok(commands)
+}
+
+pub(crate) fn draw_command(item: &[u32]) -> RenderCommand {
+    RenderCommand::Draw {
+        vertices: item[2]..item[2] + item[0],
+        instances: item[3]..item[3] + item[1],
+    }
+}
+
+pub(crate) fn draw_indexed_command(item: &[u32]) -> RenderCommand {
+    RenderCommand::DrawIndexed {
+        indices: item[2]..item[2] + item[0],
+        // Signed, as in indirect buffers
+        base_vertex: item[3] as i32,
+        instances: item[4]..item[4] + item[1],
+    }
+}
+
+#[napi]
+impl GpuComputePassEncoder {
+    /// Record one dispatch per `[x, y, z]` triple in `counts` (extension)
+    ///
+    /// With `bindGroup`, the bind group is set before every dispatch using that
+    /// dispatch's share of `dynamicOffsets`, or once if there are no offsets.
+    #[napi(catch_unwind, js_name = "dispatchWorkgroupsBatch")]
+    pub fn dispatch_workgroups_batch(
+        &mut self,
+        counts: Uint32Array,
+        bind_group_index: Option<u32>,
+        bind_group: Option<&GpuBindGroup>,
+        dynamic_offsets: Option<Uint32Array>,
+    ) -> Result<()> {
+        let commands = batch_commands(
+            &counts,
+            DISPATCH_STRIDE,
+            "counts",
+            BatchBindGroup {
+                index: bind_group_index,
+                bind_group,
+                dynamic_offsets: dynamic_offsets.as_deref(),
+            },
+            ComputeCommand::SetBindGroup,
+            |item| ComputeCommand::Dispatch(item[0], item[1], item[2]),
+        )?;
+        self.record_all(commands)
+    }
+}
+
+#[napi]
+impl GpuRenderPassEncoder {
+    /// Record one draw per `[vertexCount, instanceCount, firstVertex, firstInstance]`
+    /// in `draws` (extension)
+    #[napi(catch_unwind, js_name = "drawBatch")]
+    pub fn draw_batch(
+        &mut self,
+        draws: Uint32Array,
+        bind_group_index: Option<u32>,
+        bind_group: Option<&GpuBindGroup>,
+        dynamic_offsets: Option<Uint32Array>,
+    ) -> Result<()> {
+        let commands = batch_commands(
+            &draws,
+            DRAW_STRIDE,
+            "draws",
+            BatchBindGroup {
+                index: bind_group_index,
+                bind_group,
+                dynamic_offsets: dynamic_offsets.as_deref(),
+            },
+            RenderCommand::SetBindGroup,
+            draw_command,
+        )?;
+        self.record_all(commands)
+    }
+
+    /// Record one indexed draw per
+    /// `[indexCount, instanceCount, firstIndex, baseVertex, firstInstance]` in
+    /// `draws` (extension)
+    #[napi(catch_unwind, js_name = "drawIndexedBatch")]
+    pub fn draw_indexed_batch(
+        &mut self,
+        draws: Uint32Array,
+        bind_group_index: Option<u32>,
+        bind_group: Option<&GpuBindGroup>,
+        dynamic_offsets: Option<Uint32Array>,
+    ) -> Result<()> {
+        let commands = batch_commands(
+            &draws,
+            DRAW_INDEXED_STRIDE,
+            "draws",
+            BatchBindGroup {
+                index: bind_group_index,
+                bind_group,
+                dynamic_offsets: dynamic_offsets.as_deref(),
+            },
+            RenderCommand::SetBindGroup,
+            draw_indexed_command,
+        )?;
+        self.record_all(commands)
+    }
+}
+
+#[napi]
+impl GpuRenderBundleEncoder {
+    /// Record one draw per `[vertexCount, instanceCount, firstVertex, firstInstance]`
+    /// in `draws` (extension)
+    #[napi(catch_unwind, js_name = "drawBatch")]
+    pub fn draw_batch(
+        &mut self,
+        draws: Uint32Array,
+        bind_group_index: Option<u32>,
+        bind_group: Option<&GpuBindGroup>,
+        dynamic_offsets: Option<Uint32Array>,
+    ) -> Result<()> {
+        let commands = batch_commands(
+            &draws,
+            DRAW_STRIDE,
+            "draws",
+            BatchBindGroup {
+                index: bind_group_index,
+                bind_group,
+                dynamic_offsets: dynamic_offsets.as_deref(),
+            },
+            RenderCommand::SetBindGroup,
+            draw_command,
+        )?;
+        self.record_all(commands)
+    }
+
+    /// Record one indexed draw per
+    /// `[indexCount, instanceCount, firstIndex, baseVertex, firstInstance]` in
+    /// `draws` (extension)
+    #[napi(catch_unwind, js_name = "drawIndexedBatch")]
+    pub fn draw_indexed_batch(
+        &mut self,
+        draws: Uint32Array,
+        bind_group_index: Option<u32>,
+        bind_group: Option<&GpuBindGroup>,
+        dynamic_offsets: Option<Uint32Array>,
+    ) -> Result<()> {
+        let commands = batch_commands(
+            &draws,
+            DRAW_INDEXED_STRIDE,
+            "draws",
+            BatchBindGroup {
+                index: bind_group_index,
+                bind_group,
+                dynamic_offsets: dynamic_offsets.as_deref(),
+            },
+            RenderCommand::SetBindGroup,
+            draw_indexed_command,
+        )?;
+        self.record_all(commands)
+    }
+}
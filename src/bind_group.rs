@@ -10,12 +10,16 @@ use std::sync::Arc;
 #[napi]
 pub struct GpuBindGroupLayout {
     pub(crate) layout: Arc<wgpu::BindGroupLayout>,
+    /// Entries the layout was created with, for mismatch diagnostics;
+    /// `None` for layouts derived by wgpu
+    pub(crate) entries: Option<crate::layout_check::LayoutEntries>,
 }
 
 impl GpuBindGroupLayout {
-    pub(crate) fn new(layout: wgpu::BindGroupLayout) -> Self {
+    pub(crate) fn new(layout: wgpu::BindGroupLayout, entries: Option<crate::layout_check::LayoutEntries>) -> Self {
         Self {
             layout: Arc::new(layout),
+            entries,
         }
     }
 }
@@ -25,9 +29,136 @@ impl GpuBindGroupLayout {
 #[napi(object)]
 pub struct BindGroupEntry {
     pub binding: u32,
-    pub resource_type: String,    // "buffer" | "texture" | "sampler"
+    pub resource_type: String,    // "buffer" | "buffer-array" | "texture" | "texture-array" | "sampler" | "sampler-array"
     pub offset: Option<i64>,      // For buffer bindings only
     pub size: Option<i64>,        // For buffer bindings only
+    /// Number of resources an array entry takes from its resource list
+    pub count: Option<u32>,
+    /// Offset and size of each buffer of a "buffer-array" entry; `null`
+    /// sizes bind the rest of the buffer
+    pub offsets: Option<Vec<i64>>,
+    pub sizes: Option<Vec<Option<i64>>>,
+}
+
+/// Resource of a bind group entry, owning the elements of an array
+pub(crate) enum EntryResource<'a> {
+    Single(wgpu::BindingResource<'a>),
+    BufferArray(Vec<wgpu::BufferBinding<'a>>),
+    TextureViewArray(Vec<&'a wgpu::TextureView>),
+    SamplerArray(Vec<&'a wgpu::Sampler>),
+}
+
+impl EntryResource<'_> {
+    pub(crate) fn to_wgpu(&self) -> wgpu::BindingResource<'_> {
+        match self {
+            EntryResource::Single(resource) => resource.clone(),
+            EntryResource::BufferArray(bindings) => wgpu::BindingResource::BufferArray(bindings),
+            EntryResource::TextureViewArray(views) => wgpu::BindingResource::TextureViewArray(views),
+            EntryResource::SamplerArray(samplers) => wgpu::BindingResource::SamplerArray(samplers),
+        }
+    }
+}
+
+/// Whether shaders can write through bindings of type `ty`, which vertex
+/// shaders can only do with the "wgpu-vertex-writable-storage" feature
+pub(crate) fn is_writable_storage(ty: &wgpu::BindingType) -> bool {
+    match ty {
+        wgpu::BindingType::Buffer {
+            ty: wgpu::BufferBindingType::Storage { read_only },
+            ..
+        } => !read_only,
+        wgpu::BindingType::StorageTexture { access, .. } => *access != wgpu::StorageTextureAccess::ReadOnly,
+        _ => false,
+    }
+}
+
+/// Check that array entries bind every element of their layout entry,
+/// unless the device has the "wgpu-partially-bound-binding-array" feature
+///
+/// wgpu reports a bare length mismatch otherwise.
+pub(crate) fn check_array_lengths(
+    entries: &[BindGroupEntry],
+    layout: Option<&crate::layout_check::LayoutEntries>,
+    features: wgpu::Features,
+) -> napi::Result<()> {
+    let Some(layout) = layout else {
+        return Ok(());
+    };
+    if features.contains(wgpu::Features::PARTIALLY_BOUND_BINDING_ARRAY) {
+        return Ok(());
+    }
+    for entry in entries.iter().filter(|entry| entry.resource_type.ends_with("-array")) {
+        let provided = entry.count.unwrap_or(0);
+        let Some(count) = layout
+            .iter()
+            .find(|layout_entry| layout_entry.binding == entry.binding)
+            .and_then(|layout_entry| layout_entry.count)
+        else {
+            continue;
+        };
+        if provided > 0 && provided < count.get() {
+            return Err(napi::Error::from_reason(format!(
+                "createBindGroup: binding {} binds {} of the {} elements of its array; leaving elements unbound needs the 'wgpu-partially-bound-binding-array' feature",
+                entry.binding,
+                provided,
+                count
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Describe the resources of `createBindGroup()` entries, for diagnostics
+///
+/// Takes resources from the lists in entry order, like `createBindGroup()`.
+pub(crate) fn provided_resources(
+    entries: &[BindGroupEntry],
+    buffers: Option<&[&crate::GpuBuffer]>,
+    textures: Option<&[&crate::GpuTextureView]>,
+) -> Vec<(u32, crate::layout_check::ProvidedResource)> {
+    let mut buffers = buffers.unwrap_or_default().iter();
+    let mut textures = textures.unwrap_or_default().iter();
+    entries
+        .iter()
+        .filter_map(|entry| {
+            let resource = match entry.resource_type.as_str() {
+                "buffer" => {
+                    let buffer = buffers.next()?;
+                    let offset = entry.offset.unwrap_or(0) as u64;
+                    crate::layout_check::ProvidedResource::Buffer {
+                        label: buffer.label.clone(),
+                        size: entry.size.map_or(buffer.buffer.size().saturating_sub(offset), |size| size as u64),
+                        usage: buffer.buffer.usage(),
+                    }
+                }
+                "buffer-array" => {
+                    let count = entry.count.unwrap_or(0) as usize;
+                    if count > 0 {
+                        buffers.nth(count - 1)?;
+                    }
+                    crate::layout_check::ProvidedResource::BufferArray(count)
+                }
+                "texture" => {
+                    let view = textures.next()?;
+                    crate::layout_check::ProvidedResource::TextureView {
+                        format: view.format,
+                        sample_count: view.sample_count,
+                    }
+                }
+                "texture-array" => {
+                    let count = entry.count.unwrap_or(0) as usize;
+                    if count > 0 {
+                        textures.nth(count - 1)?;
+                    }
+                    crate::layout_check::ProvidedResource::TextureViewArray(count)
+                }
+                "sampler" => crate::layout_check::ProvidedResource::Sampler,
+                "sampler-array" => crate::layout_check::ProvidedResource::SamplerArray(entry.count.unwrap_or(0) as usize),
+                _ => return None,
+            };
+            Some((entry.binding, resource))
+        })
+        .collect()
 }
 
 /// Bind group - collection of resources bound to shaders
@@ -37,12 +168,15 @@ pub struct BindGroupEntry {
 #[napi]
 pub struct GpuBindGroup {
     pub(crate) bind_group: Arc<wgpu::BindGroup>,
+    /// Entries of the bind group's layout, when known
+    pub(crate) layout_entries: Option<crate::layout_check::LayoutEntries>,
 }
 
 impl GpuBindGroup {
-    pub(crate) fn new(bind_group: wgpu::BindGroup) -> Self {
+    pub(crate) fn new(bind_group: wgpu::BindGroup, layout_entries: Option<crate::layout_check::LayoutEntries>) -> Self {
         Self {
             bind_group: Arc::new(bind_group),
+            layout_entries,
         }
     }
 }
@@ -62,16 +196,6 @@ fn parse_visibility(visibility: u32) -> wgpu::ShaderStages {
     stages
 }
 
-/// Convert buffer type string to wgpu type
-fn parse_buffer_binding_type(ty: &str) -> wgpu::BufferBindingType {
-    match ty {
-        "uniform" => wgpu::BufferBindingType::Uniform,
-        "storage" => wgpu::BufferBindingType::Storage { read_only: false },
-        "read-only-storage" => wgpu::BufferBindingType::Storage { read_only: true },
-        _ => wgpu::BufferBindingType::Uniform,
-    }
-}
-
 pub(crate) fn convert_bind_group_layout_entry(
     entry: &crate::BindGroupLayoutEntry,
 ) -> wgpu::BindGroupLayoutEntry {
@@ -79,25 +203,26 @@ pub(crate) fn convert_bind_group_layout_entry(
 
     // Determine binding type - WebGPU standard uses buffer/sampler/texture/storageTexture fields
     let ty = if let Some(ref buffer) = entry.buffer {
-        let buffer_ty = buffer.ty.as_deref().unwrap_or("uniform");
         wgpu::BindingType::Buffer {
-            ty: parse_buffer_binding_type(buffer_ty),
+            ty: crate::parse::parse_buffer_binding_type(buffer.ty.as_ref()),
             has_dynamic_offset: buffer.has_dynamic_offset.unwrap_or(false),
             min_binding_size: buffer.min_binding_size.map(|s| std::num::NonZeroU64::new(s as u64)).flatten(),
         }
     } else if entry.sampler.is_some() {
         wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering)
-    } else if entry.texture.is_some() {
+    } else if let Some(ref texture) = entry.texture {
         wgpu::BindingType::Texture {
             sample_type: wgpu::TextureSampleType::Float { filterable: true },
-            view_dimension: wgpu::TextureViewDimension::D2,
+            view_dimension: crate::parse::parse_texture_view_dimension(texture.view_dimension.as_ref())
+                .unwrap_or(wgpu::TextureViewDimension::D2),
             multisampled: false,
         }
-    } else if entry.storage_texture.is_some() {
+    } else if let Some(ref storage_texture) = entry.storage_texture {
         wgpu::BindingType::StorageTexture {
-            access: wgpu::StorageTextureAccess::WriteOnly,
-            format: wgpu::TextureFormat::Rgba8Unorm,
-            view_dimension: wgpu::TextureViewDimension::D2,
+            access: crate::parse::parse_storage_texture_access(storage_texture.access.as_ref()),
+            format: crate::parse::parse_texture_format(&storage_texture.format),
+            view_dimension: crate::parse::parse_texture_view_dimension(storage_texture.view_dimension.as_ref())
+                .unwrap_or(wgpu::TextureViewDimension::D2),
         }
     } else {
         // Default to uniform buffer
@@ -112,6 +237,6 @@ pub(crate) fn convert_bind_group_layout_entry(
         binding: entry.binding,
         visibility,
         ty,
-        count: None,
+        count: entry.count.and_then(std::num::NonZeroU32::new),
     }
 }
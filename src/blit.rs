@@ -0,0 +1,275 @@
+//! Texture blits
+//!
+//! `blitTexture` draws one texture into another with a fullscreen triangle, so
+//! unlike copies it can change size and format, and convert colors on the way.
+//! The render pipelines depend on the destination format and are cached per
+//! device.
+
+use crate::GpuDevice;
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Vertex shader `vs_main` drawing a fullscreen triangle
+pub(crate) const FULLSCREEN_WGSL: &str = include_str!("shaders/fullscreen.wgsl");
+
+/// Options for `blitTexture`
+#[napi(object)]
+pub struct BlitOptions {
+    /// "linear" (default) or "nearest"
+    pub filter: Option<String>,
+    /// Mip level of the source to read (default 0)
+    pub source_mip_level: Option<u32>,
+    /// Mip level of the destination to write (default 0)
+    pub destination_mip_level: Option<u32>,
+    /// Source channel of each destination channel, e.g. "bgra" (default "rgba")
+    pub swizzle: Option<String>,
+    /// "srgb-to-linear" or "linear-to-srgb"; applied to RGB after the swizzle
+    pub color_space: Option<String>,
+}
+
+/// Blit render pipelines of one device
+#[derive(Default)]
+pub(crate) struct BlitPipelines {
+    /// Keyed by destination format and whether the source is filterable
+    pipelines: Mutex<HashMap<(wgpu::TextureFormat, bool), Arc<wgpu::RenderPipeline>>>,
+}
+
+impl BlitPipelines {
+    fn get(&self, device: &wgpu::Device, format: wgpu::TextureFormat, filterable: bool) -> Arc<wgpu::RenderPipeline> {
+        let mut pipelines = self.pipelines.lock().unwrap();
+        pipelines
+            .entry((format, filterable))
+            .or_insert_with(|| Arc::new(create_pipeline(device, format, filterable)))
+            .clone()
+    }
+}
+
+fn create_pipeline(device: &wgpu::Device, format: wgpu::TextureFormat, filterable: bool) -> wgpu::RenderPipeline {
+    let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: Some("blit"),
+        source: wgpu::ShaderSource::Wgsl(
+            [FULLSCREEN_WGSL, crate::color::COLOR_WGSL, include_str!("shaders/blit.wgsl")]
+                .concat()
+                .into(),
+        ),
+    });
+    let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+        label: Some("blit"),
+        entries: &[
+            wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Texture {
+                    sample_type: wgpu::TextureSampleType::Float { filterable },
+                    view_dimension: wgpu::TextureViewDimension::D2,
+                    multisampled: false,
+                },
+                count: None,
+            },
+            wgpu::BindGroupLayoutEntry {
+                binding: 1,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Sampler(if filterable {
+                    wgpu::SamplerBindingType::Filtering
+                } else {
+                    wgpu::SamplerBindingType::NonFiltering
+                }),
+                count: None,
+            },
+            wgpu::BindGroupLayoutEntry {
+                binding: 2,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            },
+        ],
+    });
+    let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+        label: Some("blit"),
+        bind_group_layouts: &[&bind_group_layout],
+        push_constant_ranges: &[],
+    });
+    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some("blit"),
+        layout: Some(&layout),
+        vertex: wgpu::VertexState {
+            module: &module,
+            entry_point: "vs_main",
+            buffers: &[],
+        },
+        fragment: Some(wgpu::FragmentState {
+            module: &module,
+            entry_point: "fs_main",
+            targets: &[Some(format.into())],
+        }),
+        primitive: wgpu::PrimitiveState::default(),
+        depth_stencil: None,
+        multisample: wgpu::MultisampleState::default(),
+        multiview: None,
+    })
+}
+
+/// Check that `level` is a mip level of a single-sampled 2D `texture`
+fn validate_texture(texture: &wgpu::Texture, level: u32, usage: wgpu::TextureUsages, name: &str) -> Result<()> {
+    if texture.dimension() != wgpu::TextureDimension::D2 || texture.sample_count() != 1 {
+        return Err(Error::from_reason(format!(
+            "blitTexture: {} must be a single-sampled 2D texture",
+            name
+        )));
+    }
+    if !texture.usage().contains(usage) {
+        return Err(Error::from_reason(format!(
+            "blitTexture: {} needs {} usage",
+            name,
+            if usage == wgpu::TextureUsages::TEXTURE_BINDING { "TEXTURE_BINDING" } else { "RENDER_ATTACHMENT" }
+        )));
+    }
+    if level >= texture.mip_level_count() {
+        return Err(Error::from_reason(format!(
+            "blitTexture: {} mip level {} is out of range ({} levels)",
+            name,
+            level,
+            texture.mip_level_count()
+        )));
+    }
+    Ok(())
+}
+
+fn mip_view(texture: &wgpu::Texture, level: u32) -> wgpu::TextureView {
+    texture.create_view(&wgpu::TextureViewDescriptor {
+        dimension: Some(wgpu::TextureViewDimension::D2),
+        base_mip_level: level,
+        mip_level_count: Some(1),
+        base_array_layer: 0,
+        array_layer_count: Some(1),
+        ..Default::default()
+    })
+}
+
+#[napi]
+impl GpuDevice {
+    /// Draw `source` into `destination`, rescaling and converting the format (extension)
+    ///
+    /// The source is stretched over the whole destination, so this covers
+    /// downscaling render targets and conversions such as rgba16float to
+    /// rgba8unorm. Both textures must hold floating-point or normalized
+    /// colors; sRGB formats are decoded and encoded. `swizzle` and
+    /// `color_space` convert the colors in between. The source needs
+    /// TEXTURE_BINDING usage, the destination RENDER_ATTACHMENT.
+    #[napi(catch_unwind, js_name = "blitTexture")]
+    pub fn blit_texture(
+        &self,
+        encoder: &crate::GpuCommandEncoder,
+        source: &crate::GpuTexture,
+        destination: &crate::GpuTexture,
+        options: Option<BlitOptions>,
+    ) -> Result<()> {
+        let filter = match options.as_ref().and_then(|o| o.filter.as_deref()) {
+            None | Some("linear") => wgpu::FilterMode::Linear,
+            Some("nearest") => wgpu::FilterMode::Nearest,
+            Some(other) => {
+                return Err(Error::from_reason(format!(
+                    "blitTexture: unknown filter {}. Use 'linear' or 'nearest'",
+                    other
+                )))
+            }
+        };
+        let [r, g, b, a, color_space] = crate::color::color_params(
+            options.as_ref().and_then(|o| o.swizzle.as_deref()),
+            options.as_ref().and_then(|o| o.color_space.as_deref()),
+        )?;
+        let source_level = options.as_ref().and_then(|o| o.source_mip_level).unwrap_or(0);
+        let destination_level = options.as_ref().and_then(|o| o.destination_mip_level).unwrap_or(0);
+
+        validate_texture(&source.texture, source_level, wgpu::TextureUsages::TEXTURE_BINDING, "source")?;
+        validate_texture(&destination.texture, destination_level, wgpu::TextureUsages::RENDER_ATTACHMENT, "destination")?;
+        if Arc::ptr_eq(&source.texture, &destination.texture) && source_level == destination_level {
+            return Err(Error::from_reason(
+                "blitTexture: source and destination must be different textures or mip levels",
+            ));
+        }
+
+        let source_format = source.texture.format();
+        let filterable = match source_format.sample_type(None, Some(self.device.features())) {
+            Some(wgpu::TextureSampleType::Float { filterable }) => filterable,
+            _ => {
+                return Err(Error::from_reason(format!(
+                    "blitTexture: source format {} can't be sampled as float",
+                    crate::parse::texture_format_name(source_format)
+                )))
+            }
+        };
+        if !filterable && filter == wgpu::FilterMode::Linear {
+            return Err(Error::from_reason(format!(
+                "blitTexture: source format {} isn't filterable; use filter: 'nearest'",
+                crate::parse::texture_format_name(source_format)
+            )));
+        }
+        let destination_format = destination.texture.format();
+        if !matches!(
+            destination_format.sample_type(None, Some(self.device.features())),
+            Some(wgpu::TextureSampleType::Float { .. })
+        ) {
+            return Err(Error::from_reason(format!(
+                "blitTexture: destination format {} isn't a float color format",
+                crate::parse::texture_format_name(destination_format)
+            )));
+        }
+
+        let pipeline = self.blit.get(&self.device, destination_format, filterable);
+        let sampler = self.device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("blit"),
+            mag_filter: filter,
+            min_filter: filter,
+            ..Default::default()
+        });
+        let params = crate::util::create_params_buffer(&self.device, "blit params", &[r, g, b, a, color_space]);
+        let source_view = mip_view(&source.texture, source_level);
+        let destination_view = mip_view(&destination.texture, destination_level);
+        let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("blit"),
+            layout: &pipeline.get_bind_group_layout(0),
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(&source_view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Sampler(&sampler),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: params.as_entire_binding(),
+                },
+            ],
+        });
+
+        encoder.with_encoder(|enc| {
+            let mut pass = enc.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("blit"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &destination_view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+            pass.set_pipeline(&pipeline);
+            pass.set_bind_group(0, &bind_group, &[]);
+            pass.draw(0..3, 0..1);
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,343 @@
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use crate::util;
+
+/// Largest radius the weight table in blur.wgsl holds
+const MAX_RADIUS: u32 = 63;
+
+/// Format of the texture between the two passes
+const INTERMEDIATE_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba16Float;
+
+/// Gaussian blur descriptor
+#[napi(object)]
+pub struct BlurDescriptor {
+    pub label: Option<String>,
+    /// Taps on each side of the center, up to 63. Defaults to 3 sigma, or 4
+    pub radius: Option<u32>,
+    /// Standard deviation in texels. Defaults to half the radius
+    pub sigma: Option<f64>,
+}
+
+/// Separable Gaussian blur utility
+///
+/// Blurs a texture in two render passes, horizontally into an rgba16float
+/// intermediate and then vertically into the destination. Texels are read
+/// without filtering, so any float or normalized format can be blurred.
+#[napi]
+pub struct GpuBlur {
+    device: Arc<wgpu::Device>,
+    queue: Arc<wgpu::Queue>,
+    label: String,
+    radius: u32,
+    sigma: f64,
+    module: wgpu::ShaderModule,
+    bind_group_layout: wgpu::BindGroupLayout,
+    layout: wgpu::PipelineLayout,
+    horizontal_params: wgpu::Buffer,
+    vertical_params: wgpu::Buffer,
+    /// Render pipelines by target format
+    pipelines: Mutex<HashMap<wgpu::TextureFormat, Arc<wgpu::RenderPipeline>>>,
+    /// Intermediate texture of the last size blurred
+    intermediate: Mutex<Option<Arc<wgpu::Texture>>>,
+}
+
+/// Normalized weights of the center and each distance up to `radius`
+fn gaussian_weights(radius: u32, sigma: f64) -> Vec<f32> {
+    let weights: Vec<f64> = (0..=radius)
+        .map(|distance| (-((distance * distance) as f64) / (2.0 * sigma * sigma)).exp())
+        .collect();
+    let total = weights[0] + 2.0 * weights[1..].iter().sum::<f64>();
+    weights.iter().map(|weight| (weight / total) as f32).collect()
+}
+
+impl GpuBlur {
+    pub(crate) fn new(device: Arc<wgpu::Device>, queue: Arc<wgpu::Queue>, descriptor: Option<BlurDescriptor>) -> Result<Self> {
+        let (radius, sigma) = match (
+            descriptor.as_ref().and_then(|d| d.radius),
+            descriptor.as_ref().and_then(|d| d.sigma),
+        ) {
+            (None, None) => (4, 2.0),
+            (Some(radius), None) => (radius, (radius as f64 / 2.0).max(0.5)),
+            (None, Some(sigma)) => ((sigma * 3.0).ceil().min(MAX_RADIUS as f64) as u32, sigma),
+            (Some(radius), Some(sigma)) => (radius, sigma),
+        };
+        if radius > MAX_RADIUS {
+            return Err(Error::from_reason(format!(
+                "Blur radius {} is too large (at most {})",
+                radius, MAX_RADIUS
+            )));
+        }
+        if !(sigma > 0.0 && sigma.is_finite()) {
+            return Err(Error::from_reason(format!("Blur sigma must be positive, got {}", sigma)));
+        }
+        let label = descriptor
+            .as_ref()
+            .and_then(|d| d.label.clone())
+            .unwrap_or_else(|| "blur".to_string());
+
+        let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some(&label),
+            source: wgpu::ShaderSource::Wgsl(
+                [crate::blit::FULLSCREEN_WGSL, include_str!("shaders/blur.wgsl")].concat().into(),
+            ),
+        });
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some(&label),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: false },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+            ],
+        });
+        let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some(&label),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+
+        let weights: Vec<u32> = gaussian_weights(radius, sigma).iter().map(|w| w.to_bits()).collect();
+        let params = |x: u32, y: u32| {
+            let mut params = vec![x, y, radius, 0];
+            params.extend(&weights);
+            params.resize(4 + 64, 0);
+            util::create_params_buffer(&device, &format!("{} params", label), &params)
+        };
+        let horizontal_params = params(1, 0);
+        let vertical_params = params(0, 1);
+
+        Ok(Self {
+            device,
+            queue,
+            label,
+            radius,
+            sigma,
+            module,
+            bind_group_layout,
+            layout,
+            horizontal_params,
+            vertical_params,
+            pipelines: Mutex::default(),
+            intermediate: Mutex::default(),
+        })
+    }
+
+    fn pipeline(&self, format: wgpu::TextureFormat) -> Arc<wgpu::RenderPipeline> {
+        let mut pipelines = self.pipelines.lock().unwrap();
+        pipelines
+            .entry(format)
+            .or_insert_with(|| {
+                Arc::new(self.device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                    label: Some(&self.label),
+                    layout: Some(&self.layout),
+                    vertex: wgpu::VertexState {
+                        module: &self.module,
+                        entry_point: "vs_main",
+                        buffers: &[],
+                    },
+                    fragment: Some(wgpu::FragmentState {
+                        module: &self.module,
+                        entry_point: "fs_main",
+                        targets: &[Some(format.into())],
+                    }),
+                    primitive: wgpu::PrimitiveState::default(),
+                    depth_stencil: None,
+                    multisample: wgpu::MultisampleState::default(),
+                    multiview: None,
+                }))
+            })
+            .clone()
+    }
+
+    fn intermediate(&self, size: wgpu::Extent3d) -> Arc<wgpu::Texture> {
+        let mut intermediate = self.intermediate.lock().unwrap();
+        match &*intermediate {
+            Some(texture) if texture.width() == size.width && texture.height() == size.height => texture.clone(),
+            _ => {
+                let texture = Arc::new(self.device.create_texture(&wgpu::TextureDescriptor {
+                    label: Some(&format!("{} intermediate", self.label)),
+                    size: wgpu::Extent3d {
+                        depth_or_array_layers: 1,
+                        ..size
+                    },
+                    mip_level_count: 1,
+                    sample_count: 1,
+                    dimension: wgpu::TextureDimension::D2,
+                    format: INTERMEDIATE_FORMAT,
+                    usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+                    view_formats: &[],
+                }));
+                *intermediate = Some(texture.clone());
+                texture
+            }
+        }
+    }
+
+    fn validate(&self, source: &crate::GpuTexture, destination: &crate::GpuTexture) -> Result<()> {
+        for (texture, name, usage, usage_name) in [
+            (source, "source", wgpu::TextureUsages::TEXTURE_BINDING, "TEXTURE_BINDING"),
+            (destination, "destination", wgpu::TextureUsages::RENDER_ATTACHMENT, "RENDER_ATTACHMENT"),
+        ] {
+            let texture = &texture.texture;
+            if texture.dimension() != wgpu::TextureDimension::D2 || texture.sample_count() != 1 {
+                return Err(Error::from_reason(format!(
+                    "Blur {} must be a single-sampled 2D texture",
+                    name
+                )));
+            }
+            if !texture.usage().contains(usage) {
+                return Err(Error::from_reason(format!(
+                    "Blur {} needs {} usage",
+                    name, usage_name
+                )));
+            }
+            if !matches!(
+                texture.format().sample_type(None, Some(self.device.features())),
+                Some(wgpu::TextureSampleType::Float { .. })
+            ) {
+                return Err(Error::from_reason(format!(
+                    "Blur {} format {} isn't a float color format",
+                    name,
+                    crate::parse::texture_format_name(texture.format())
+                )));
+            }
+        }
+        if Arc::ptr_eq(&source.texture, &destination.texture) {
+            return Err(Error::from_reason("Blur source and destination must be different textures"));
+        }
+        let (source_size, destination_size) = (source.texture.size(), destination.texture.size());
+        if (source_size.width, source_size.height) != (destination_size.width, destination_size.height) {
+            return Err(Error::from_reason(format!(
+                "Blur source and destination must be the same size, got {}x{} and {}x{}",
+                source_size.width, source_size.height, destination_size.width, destination_size.height
+            )));
+        }
+        Ok(())
+    }
+
+    /// Record one blur pass reading `source` into `target`
+    fn record_pass(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        source: &wgpu::Texture,
+        target: &wgpu::Texture,
+        params: &wgpu::Buffer,
+    ) {
+        let pipeline = self.pipeline(target.format());
+        let source_view = mip0_view(source);
+        let target_view = mip0_view(target);
+        let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some(&self.label),
+            layout: &self.bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(&source_view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: params.as_entire_binding(),
+                },
+            ],
+        });
+        let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some(&self.label),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: &target_view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+        pass.set_pipeline(&pipeline);
+        pass.set_bind_group(0, &bind_group, &[]);
+        pass.draw(0..3, 0..1);
+    }
+
+    fn record(&self, encoder: &mut wgpu::CommandEncoder, source: &wgpu::Texture, destination: &wgpu::Texture) {
+        let intermediate = self.intermediate(source.size());
+        self.record_pass(encoder, source, &intermediate, &self.horizontal_params);
+        self.record_pass(encoder, &intermediate, destination, &self.vertical_params);
+    }
+}
+
+fn mip0_view(texture: &wgpu::Texture) -> wgpu::TextureView {
+    texture.create_view(&wgpu::TextureViewDescriptor {
+        dimension: Some(wgpu::TextureViewDimension::D2),
+        mip_level_count: Some(1),
+        array_layer_count: Some(1),
+        ..Default::default()
+    })
+}
+
+#[napi]
+impl GpuBlur {
+    /// Blur radius in texels
+    #[napi(catch_unwind, getter)]
+    pub fn radius(&self) -> u32 {
+        self.radius
+    }
+
+    /// Standard deviation in texels
+    #[napi(catch_unwind, getter)]
+    pub fn sigma(&self) -> f64 {
+        self.sigma
+    }
+
+    /// Record the blur into a command encoder
+    ///
+    /// Blurs mip level 0 of `source` into `destination`, which must be a
+    /// different texture of the same size. The source needs TEXTURE_BINDING
+    /// usage, the destination RENDER_ATTACHMENT.
+    #[napi(catch_unwind)]
+    pub fn encode(
+        &self,
+        encoder: &crate::GpuCommandEncoder,
+        source: &crate::GpuTexture,
+        destination: &crate::GpuTexture,
+    ) -> Result<()> {
+        self.validate(source, destination)?;
+        encoder.with_encoder(|enc| {
+            self.record(enc, &source.texture, &destination.texture);
+            Ok(())
+        })
+    }
+
+    /// Run the blur immediately
+    ///
+    /// Convenience wrapper that encodes the blur into a fresh command buffer and submits it.
+    #[napi(catch_unwind)]
+    pub fn run(&self, source: &crate::GpuTexture, destination: &crate::GpuTexture) -> Result<()> {
+        self.validate(source, destination)?;
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: Some(&self.label) });
+        self.record(&mut encoder, &source.texture, &destination.texture);
+        self.queue.submit(Some(encoder.finish()));
+        Ok(())
+    }
+}
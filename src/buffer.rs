@@ -1,156 +1,219 @@
 use napi::bindgen_prelude::*;
 use napi_derive::napi;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
 
+/// Largest range `readSync()` reads, since it blocks the calling thread
+const READ_SYNC_LIMIT: u64 = 4 * 1024 * 1024;
+
+/// Numbers `mapAsync()` calls, so a call can tell whether it is still the
+/// pending one when the GPU is done
+static NEXT_MAP_REQUEST: AtomicU64 = AtomicU64::new(0);
+
+/// Where a buffer is in the WebGPU mapping state machine
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub(crate) enum MapState {
+    Unmapped,
+    /// `mapAsync()` call `request` is waiting for the GPU
+    Pending { request: u64 },
+    /// Mapped for `mode`, `size` bytes from `offset`
+    Mapped { mode: wgpu::MapMode, offset: u64, size: u64 },
+}
+
+impl MapState {
+    /// The GPUBuffer.mapState name of the state
+    fn name(&self) -> &'static str {
+        match self {
+            MapState::Unmapped => "unmapped",
+            MapState::Pending { .. } => "pending",
+            MapState::Mapped { .. } => "mapped",
+        }
+    }
+}
+
+/// Contents of one `getMappedRange()` range, written back by `unmap()`
+#[napi(object)]
+pub struct MappedRangeData {
+    pub offset: u32,
+    pub data: Buffer,
+}
+
+/// Why `usage` can't be created on a device with `features`, naming the
+/// conflicting flags, or None when its map usages are valid
+///
+/// MAP_READ may only be combined with COPY_DST, and MAP_WRITE with
+/// COPY_SRC, unless the device has the "wgpu-mappable-primary-buffers"
+/// feature.
+pub(crate) fn map_usage_error(usage: wgpu::BufferUsages, features: wgpu::Features) -> Option<String> {
+    if features.contains(wgpu::Features::MAPPABLE_PRIMARY_BUFFERS) {
+        return None;
+    }
+    let read_write = wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::MAP_WRITE;
+    if usage.contains(read_write) {
+        return Some("MAP_READ and MAP_WRITE can't be combined".to_string());
+    }
+    let (allowed, name, copy) = if usage.contains(wgpu::BufferUsages::MAP_READ) {
+        (wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST, "MAP_READ", "COPY_DST")
+    } else if usage.contains(wgpu::BufferUsages::MAP_WRITE) {
+        (wgpu::BufferUsages::MAP_WRITE | wgpu::BufferUsages::COPY_SRC, "MAP_WRITE", "COPY_SRC")
+    } else {
+        return None;
+    };
+    let conflicting = usage - allowed;
+    if conflicting.is_empty() {
+        return None;
+    }
+    Some(format!(
+        "{} can only be combined with {}, not {} (without the 'wgpu-mappable-primary-buffers' feature)",
+        name,
+        copy,
+        crate::parse::buffer_usage_names(conflicting).join(" | ")
+    ))
+}
+
 /// GPU buffer - contiguous memory allocation on the GPU
 ///
 /// Buffers store data for shaders (vertices, indices, uniforms, storage).
 /// They can be written from CPU (via queueWriteBuffer) and read back (via mapRead).
-#[napi]
+#[napi(custom_finalize)]
+#[derive(Clone)]
 pub struct GpuBuffer {
-    pub(crate) buffer: wgpu::Buffer,
+    pub(crate) buffer: Arc<wgpu::Buffer>,
+    /// Descriptor label, quoted in validation errors
+    pub(crate) label: Option<String>,
     pub(crate) device: Arc<wgpu::Device>,
     pub(crate) queue: Arc<wgpu::Queue>,
+    pub(crate) poller: crate::poller::DevicePoller,
     /// Tracks pending writes to the mapped buffer
     /// Writes are accumulated and applied via queue.write_buffer() in unmap()
     pub(crate) pending_writes: Arc<Mutex<Vec<(u64, Vec<u8>)>>>,
     /// Stores the mapped range data returned from getMappedRange()
     /// When user modifies this data in JavaScript, we need to flush it back to GPU on unmap()
     pub(crate) mapped_data: Arc<Mutex<Option<Vec<u8>>>>,
-    /// Tracks the current map state of the buffer
-    /// Values: "unmapped", "pending", "mapped"
-    pub(crate) map_state: Arc<Mutex<String>>,
+    /// Tracks the current map state of the buffer, with the mode and range
+    /// of a mapping; mappedAtCreation maps the whole buffer for writing
+    pub(crate) map_state: Arc<Mutex<MapState>>,
     /// Tracks active getMappedRange() calls to prevent overlapping ranges
     /// Each entry is (offset, size) of an active range
     pub(crate) active_ranges: Arc<Mutex<Vec<(u64, u64)>>>,
+    /// Set by destroy(); wgpu panics submitting copies from destroyed buffers,
+    /// so readbacks check it first
+    pub(crate) destroyed: Arc<AtomicBool>,
+    /// Buffer size reported to the garbage collector
+    pub(crate) external_memory: crate::memory::ExternalMemory,
 }
 
 impl GpuBuffer {
-    pub(crate) fn new(buffer: wgpu::Buffer, device: Arc<wgpu::Device>, queue: Arc<wgpu::Queue>) -> Self {
+    pub(crate) fn new(buffer: wgpu::Buffer, label: Option<String>, device: Arc<wgpu::Device>, queue: Arc<wgpu::Queue>, poller: crate::poller::DevicePoller) -> Self {
         Self {
-            buffer,
+            buffer: Arc::new(buffer),
+            label,
             device,
             queue,
+            poller,
             pending_writes: Arc::new(Mutex::new(Vec::new())),
             mapped_data: Arc::new(Mutex::new(None)),
-            map_state: Arc::new(Mutex::new("unmapped".to_string())),
+            map_state: Arc::new(Mutex::new(MapState::Unmapped)),
             active_ranges: Arc::new(Mutex::new(Vec::new())),
+            destroyed: Arc::new(AtomicBool::new(false)),
+            external_memory: Default::default(),
         }
     }
 
-    pub(crate) fn new_mapped(buffer: wgpu::Buffer, device: Arc<wgpu::Device>, queue: Arc<wgpu::Queue>) -> Self {
+    pub(crate) fn new_mapped(buffer: wgpu::Buffer, label: Option<String>, device: Arc<wgpu::Device>, queue: Arc<wgpu::Queue>, poller: crate::poller::DevicePoller) -> Self {
+        let size = buffer.size();
         Self {
-            buffer,
+            buffer: Arc::new(buffer),
+            label,
             device,
             queue,
+            poller,
             pending_writes: Arc::new(Mutex::new(Vec::new())),
             mapped_data: Arc::new(Mutex::new(None)),
-            map_state: Arc::new(Mutex::new("mapped".to_string())),
+            map_state: Arc::new(Mutex::new(MapState::Mapped {
+                mode: wgpu::MapMode::Write,
+                offset: 0,
+                size,
+            })),
             active_ranges: Arc::new(Mutex::new(Vec::new())),
+            destroyed: Arc::new(AtomicBool::new(false)),
+            external_memory: Default::default(),
         }
     }
-}
 
-#[napi]
-impl GpuBuffer {
-    /// Get the size of the buffer
-    #[napi]
-    pub fn size(&self) -> u32 {
-        self.buffer.size() as u32
-    }
-
-    /// Get the usage flags of the buffer
-    #[napi]
-    pub fn usage(&self) -> u32 {
-        self.buffer.usage().bits()
-    }
-
-    /// Get the current map state of the buffer
-    ///
-    /// Returns one of: "unmapped", "pending", "mapped"
-    #[napi(js_name = "mapState")]
-    pub fn map_state(&self) -> Result<String> {
-        let state = self.map_state.lock()
-            .map_err(|_| Error::from_reason("Failed to lock map state"))?;
-        Ok(state.clone())
+    /// Report the buffer's size to the garbage collector of `env`
+    pub(crate) fn track_memory(self, env: &mut Env) -> Result<Self> {
+        self.external_memory.report(env, self.buffer.size())?;
+        Ok(self)
     }
 
-    /// Map the buffer asynchronously for reading or writing
-    ///
-    /// Asynchronously maps the buffer for CPU access.
-    /// mode: "READ" or "WRITE"
-    /// Buffer must have MAP_READ or MAP_WRITE usage flag.
-    #[napi(js_name = "mapAsync")]
-    pub async fn map_async(&self, mode: String) -> Result<()> {
-        // Set state to pending
-        {
-            let mut state = self.map_state.lock()
-                .map_err(|_| Error::from_reason("Failed to lock map state"))?;
-            *state = "pending".to_string();
-        }
-
-        let slice = self.buffer.slice(..);
-
-        let map_mode = match mode.as_str() {
-            "READ" => wgpu::MapMode::Read,
-            "WRITE" => wgpu::MapMode::Write,
-            _ => return Err(Error::from_reason(format!("Invalid map mode: {}. Use 'READ' or 'WRITE'", mode))),
-        };
-
-        let (sender, receiver) = futures::channel::oneshot::channel();
-        slice.map_async(map_mode, move |result| {
+    /// Map a range of `buffer` for reading, wait for it and copy it out
+    fn read_blocking(&self, buffer: &wgpu::Buffer, offset: u64, size: u64) -> Result<Vec<u8>> {
+        let slice = buffer.slice(offset..offset + size);
+        let (sender, receiver) = std::sync::mpsc::channel();
+        slice.map_async(wgpu::MapMode::Read, move |result| {
             let _ = sender.send(result);
         });
-
         self.device.poll(wgpu::Maintain::Wait);
-
-        let result = receiver.await
+        receiver
+            .recv()
             .map_err(|_| Error::from_reason("Failed to receive map result"))?
-            .map_err(|e| Error::from_reason(format!("Failed to map buffer: {:?}", e)));
+            .map_err(|e| Error::from_reason(format!("Failed to map buffer: {:?}", e)))?;
 
-        // Update state based on result
-        if result.is_ok() {
-            let mut state = self.map_state.lock()
-                .map_err(|_| Error::from_reason("Failed to lock map state"))?;
-            *state = "mapped".to_string();
-        } else {
-            let mut state = self.map_state.lock()
-                .map_err(|_| Error::from_reason("Failed to lock map state"))?;
-            *state = "unmapped".to_string();
+        let data = slice.get_mapped_range().to_vec();
+        buffer.unmap();
+        Ok(data)
+    }
+
+    /// Throw if destroy() was called
+    pub(crate) fn ensure_not_destroyed(&self, method: &str) -> Result<()> {
+        if self.destroyed.load(Ordering::Relaxed) {
+            return Err(Error::from_reason(format!(
+                "{}: buffer {} is destroyed",
+                method,
+                crate::error::describe_label(self.label.as_deref())
+            )));
         }
+        Ok(())
+    }
 
-        result
+    /// Throw if the buffer is destroyed, mapped or waiting on mapAsync(),
+    /// since wgpu panics submitting work that uses it
+    pub(crate) fn ensure_submittable(&self, method: &str) -> Result<()> {
+        self.ensure_not_destroyed(method)?;
+        let state = *self.lock_map_state()?;
+        if state != MapState::Unmapped {
+            return Err(Error::from_reason(format!(
+                "{}: buffer {} is {}; unmap it first",
+                method,
+                crate::error::describe_label(self.label.as_deref()),
+                state.name()
+            )));
+        }
+        Ok(())
     }
 
-    /// Get the mapped range as a buffer
-    ///
-    /// Returns the mapped data as a Node.js Buffer.
-    /// Must be called after mapAsync() succeeds or if buffer created with mappedAtCreation: true.
-    ///
-    /// The returned buffer is a COPY of GPU memory. Modifications to this buffer in JavaScript
-    /// will be automatically flushed back to GPU when unmap() is called.
-    ///
-    /// This implements the standard WebGPU getMappedRange() behavior.
+    fn lock_map_state(&self) -> Result<std::sync::MutexGuard<'_, MapState>> {
+        self.map_state.lock().map_err(|_| Error::from_reason("Failed to lock map state"))
+    }
+
+    /// Check that the buffer is mapped and resolve a `getMappedRange()`-style byte range
     ///
-    /// # Parameters
-    /// * `offset` - Byte offset into the buffer (optional, default 0). Must be multiple of 8.
-    /// * `size` - Number of bytes to return (optional, default remaining bytes). Must be multiple of 4.
-    #[napi(js_name = "getMappedRange")]
-    pub fn get_mapped_range(&self, offset: Option<u32>, size: Option<u32>) -> Result<Buffer> {
+    /// The range must lie within the range `mapAsync()` mapped.
+    fn mapped_range(&self, method: &str, offset: Option<u32>, size: Option<u32>) -> Result<(u64, u64)> {
         // Validate map state (WebGPU spec requirement)
-        let state = self.map_state.lock()
-            .map_err(|_| Error::from_reason("Failed to lock map state"))?;
-        if state.as_str() != "mapped" {
+        let state = *self.lock_map_state()?;
+        let MapState::Mapped { offset: mapped_offset, size: mapped_size, .. } = state else {
             return Err(Error::from_reason(format!(
-                "Buffer must be mapped before calling getMappedRange(). Current state: {}",
-                state
+                "Buffer must be mapped before calling {}(). Current state: {}",
+                method,
+                state.name()
             )));
-        }
-        drop(state);
+        };
 
         let buffer_size = self.buffer.size();
         let offset = offset.unwrap_or(0) as u64;
-        let size = size.map(|s| s as u64).unwrap_or(buffer_size - offset);
+        let size = size.map(|s| s as u64).unwrap_or(buffer_size.saturating_sub(offset));
 
         // Validate alignment (WebGPU spec requirements)
         if offset % 8 != 0 {
@@ -173,6 +236,204 @@ impl GpuBuffer {
                 offset, size, buffer_size
             )));
         }
+        if offset < mapped_offset || offset + size > mapped_offset + mapped_size {
+            return Err(Error::from_reason(format!(
+                "{}() range [{}, {}) is outside the mapped range [{}, {})",
+                method,
+                offset,
+                offset + size,
+                mapped_offset,
+                mapped_offset + mapped_size
+            )));
+        }
+
+        Ok((offset, size))
+    }
+
+    /// Check a `setVertexBuffer()`/`setIndexBuffer()` range, see `render_pass::buffer_range`
+    pub(crate) fn binding_range(&self, method: &str, offset: Option<f64>, size: Option<f64>) -> Result<(u64, Option<u64>)> {
+        crate::render_pass::buffer_range(&self.buffer, offset.unwrap_or(0.0) as u64, size.map(|size| size as u64))
+            .map_err(|reason| {
+                Error::from_reason(format!(
+                    "{}(buffer: {}): {}",
+                    method,
+                    crate::error::describe_label(self.label.as_deref()),
+                    reason
+                ))
+            })
+    }
+}
+
+#[napi]
+impl GpuBuffer {
+    /// Size of the buffer in bytes
+    #[napi(catch_unwind, getter)]
+    pub fn size(&self) -> u32 {
+        self.buffer.size() as u32
+    }
+
+    /// Usage flags of the buffer
+    #[napi(catch_unwind, getter)]
+    pub fn usage(&self) -> u32 {
+        self.buffer.usage().bits()
+    }
+
+    /// Names of the usage flags, e.g. ["COPY_DST", "STORAGE"] (extension)
+    #[napi(catch_unwind, js_name = "usageNames")]
+    pub fn usage_names(&self) -> Vec<String> {
+        crate::parse::buffer_usage_names(self.buffer.usage())
+    }
+
+    /// Current map state of the buffer: "unmapped", "pending" or "mapped"
+    #[napi(catch_unwind, getter, js_name = "mapState")]
+    pub fn map_state(&self) -> Result<String> {
+        Ok(self.lock_map_state()?.name().to_string())
+    }
+
+    /// Descriptor label, or "" without one
+    #[napi(catch_unwind, getter)]
+    pub fn label(&self) -> String {
+        self.label.clone().unwrap_or_default()
+    }
+
+    /// Map the buffer asynchronously for reading or writing
+    ///
+    /// Asynchronously maps the buffer for CPU access.
+    /// mode: "READ" or "WRITE", or GPUMapMode.READ or GPUMapMode.WRITE
+    /// Buffer must have the matching MAP_READ or MAP_WRITE usage flag.
+    ///
+    /// Maps `size` bytes from `offset` (default 0 and the rest of the buffer);
+    /// getMappedRange() ranges must lie within them. Rejects while the buffer
+    /// is already pending or mapped, and with code "Cancelled" when unmap()
+    /// or destroy() aborts the map before it completes. The state changes to
+    /// "pending" before this returns, as the spec requires.
+    #[napi(catch_unwind, js_name = "mapAsync", ts_return_type = "Promise<void>")]
+    pub fn map_async(
+        &self,
+        env: Env,
+        mode: Either<String, u32>,
+        offset: Option<u32>,
+        size: Option<u32>,
+    ) -> Result<napi::JsObject> {
+        let map_mode = match &mode {
+            Either::A(name) if name == "READ" => wgpu::MapMode::Read,
+            Either::A(name) if name == "WRITE" => wgpu::MapMode::Write,
+            Either::B(1) => wgpu::MapMode::Read,
+            Either::B(2) => wgpu::MapMode::Write,
+            Either::A(name) => {
+                return Err(Error::from_reason(format!("Invalid map mode: {}. Use 'READ' or 'WRITE'", name)))
+            }
+            Either::B(bits) => {
+                return Err(Error::from_reason(format!(
+                    "Invalid map mode: {}. Use GPUMapMode.READ or GPUMapMode.WRITE",
+                    bits
+                )))
+            }
+        };
+        let (mode_name, flag, flag_name) = match map_mode {
+            wgpu::MapMode::Read => ("READ", wgpu::BufferUsages::MAP_READ, "MAP_READ"),
+            wgpu::MapMode::Write => ("WRITE", wgpu::BufferUsages::MAP_WRITE, "MAP_WRITE"),
+        };
+        if !self.buffer.usage().contains(flag) {
+            return Err(Error::from_reason(format!(
+                "mapAsync: buffer {} can't be mapped for {}, its usage ({}) is missing {}",
+                crate::error::describe_label(self.label.as_deref()),
+                mode_name,
+                crate::parse::buffer_usage_names(self.buffer.usage()).join(" | "),
+                flag_name
+            )));
+        }
+
+
+        let buffer_size = self.buffer.size();
+        let offset = offset.unwrap_or(0) as u64;
+        let size = size.map(|s| s as u64).unwrap_or(buffer_size.saturating_sub(offset));
+        if offset % wgpu::MAP_ALIGNMENT != 0 || size % wgpu::COPY_BUFFER_ALIGNMENT != 0 {
+            return Err(Error::from_reason(format!(
+                "mapAsync offset ({}) must be a multiple of 8 and size ({}) a multiple of 4",
+                offset, size
+            )));
+        }
+        if offset + size > buffer_size {
+            return Err(Error::from_reason(format!(
+                "mapAsync range (offset {} + size {}) exceeds buffer size ({})",
+                offset, size, buffer_size
+            )));
+        }
+
+        let request = NEXT_MAP_REQUEST.fetch_add(1, Ordering::Relaxed);
+        {
+            let mut state = self.lock_map_state()?;
+            if *state != MapState::Unmapped {
+                return Err(Error::from_reason(format!(
+                    "mapAsync: buffer {} is already {}",
+                    crate::error::describe_label(self.label.as_deref()),
+                    state.name()
+                )));
+            }
+            self.ensure_not_destroyed("mapAsync")?;
+            // wgpu can't map an empty range, and there is nothing to wait for
+            *state = if size == 0 {
+                MapState::Mapped { mode: map_mode, offset, size }
+            } else {
+                MapState::Pending { request }
+            };
+        }
+        let (deferred, promise) = env.create_deferred::<(), Box<dyn FnOnce(Env) -> Result<()> + Send>>()?;
+        if size == 0 {
+            deferred.resolve(Box::new(|_| Ok(())));
+            return Ok(promise);
+        }
+
+        // The device poll thread fires the callback once the GPU is done with the buffer
+        let polling = self.poller.track();
+        let map_state = self.map_state.clone();
+        self.buffer.slice(offset..offset + size).map_async(map_mode, move |result| {
+            drop(polling);
+            // Settled on the JS thread, so an unmap() or destroy() before
+            // the promise settles still aborts the map
+            deferred.resolve(Box::new(move |_| {
+                let mut state = map_state.lock().map_err(|_| Error::from_reason("Failed to lock map state"))?;
+                if *state != (MapState::Pending { request }) {
+                    return Err(Error::new(
+                        Status::Cancelled,
+                        "mapAsync: the map was aborted by unmap() or destroy() before it completed".to_string(),
+                    ));
+                }
+                match result {
+                    Ok(()) => {
+                        *state = MapState::Mapped { mode: map_mode, offset, size };
+                        Ok(())
+                    }
+                    Err(e) => {
+                        *state = MapState::Unmapped;
+                        Err(Error::from_reason(format!("Failed to map buffer: {:?}", e)))
+                    }
+                }
+            }));
+        });
+        Ok(promise)
+    }
+
+    /// Get the mapped range as a buffer
+    ///
+    /// Returns the mapped data as a Node.js Buffer.
+    /// Must be called after mapAsync() succeeds or if buffer created with mappedAtCreation: true.
+    ///
+    /// The returned buffer is a COPY of GPU memory. Modifications to this buffer in JavaScript
+    /// will be automatically flushed back to GPU when unmap() is called.
+    ///
+    /// Each call returns a new copy and ranges may not overlap, even identical ones; the
+    /// JavaScript wrapper returns the same ArrayBuffer for repeated calls over a range.
+    ///
+    /// This implements the standard WebGPU getMappedRange() behavior.
+    ///
+    /// # Parameters
+    /// * `offset` - Byte offset into the buffer (optional, default 0). Must be multiple of 8.
+    /// * `size` - Number of bytes to return (optional, default remaining bytes). Must be multiple of 4.
+    #[napi(catch_unwind, js_name = "getMappedRange")]
+    pub fn get_mapped_range(&self, offset: Option<u32>, size: Option<u32>) -> Result<Buffer> {
+        let (offset, size) = self.mapped_range("getMappedRange", offset, size)?;
 
         // Check for overlapping ranges (WebGPU spec requirement)
         let mut ranges = self.active_ranges.lock()
@@ -212,15 +473,150 @@ impl GpuBuffer {
     }
 
 
+    /// Copy a mapped range into `destination` without creating a mapped view
+    ///
+    /// `destination` may be backed by a SharedArrayBuffer, so worker threads
+    /// can read GPU results without another copy. The range follows the same
+    /// rules as getMappedRange() but isn't tracked, so it may overlap other
+    /// ranges. Returns the number of bytes copied.
+    #[napi(catch_unwind, js_name = "readMappedRange")]
+    pub fn read_mapped_range(&self, mut destination: Uint8Array, offset: Option<u32>, size: Option<u32>) -> Result<u32> {
+        let (offset, size) = self.mapped_range("readMappedRange", offset, size)?;
+        if (destination.len() as u64) < size {
+            return Err(Error::from_reason(format!(
+                "Destination ({} bytes) is smaller than the range ({} bytes)",
+                destination.len(),
+                size
+            )));
+        }
+
+        let data = self.buffer.slice(offset..offset + size).get_mapped_range();
+        destination[..size as usize].copy_from_slice(&data);
+        Ok(size as u32)
+    }
+
+    /// Write data straight into the mapped memory (extension)
+    ///
+    /// Valid while the buffer is mapped for writing, by mapAsync(WRITE) or
+    /// mappedAtCreation. Unlike changes to a getMappedRange() copy, the data
+    /// isn't buffered and written again on unmap(). The range follows the
+    /// getMappedRange() rules and may not overlap a range returned by it.
+    ///
+    /// # Parameters
+    /// * `offset` - Byte offset into the buffer. Must be multiple of 8.
+    /// * `data` - Bytes to write. The length must be a multiple of 4.
+    #[napi(catch_unwind, js_name = "writeMapped")]
+    pub fn write_mapped(&self, offset: u32, data: Uint8Array) -> Result<()> {
+        let (offset, size) = self.mapped_range("writeMapped", Some(offset), Some(data.len() as u32))?;
+        let state = *self.lock_map_state()?;
+        if !matches!(state, MapState::Mapped { mode: wgpu::MapMode::Write, .. }) {
+            return Err(Error::from_reason(
+                "writeMapped() needs a buffer mapped for writing (mapAsync(WRITE) or mappedAtCreation)",
+            ));
+        }
+
+        // Writes under a getMappedRange() copy would be overwritten on unmap()
+        let ranges = self.active_ranges.lock()
+            .map_err(|_| Error::from_reason("Failed to lock active ranges"))?;
+        if let Some((active_offset, active_size)) = ranges
+            .iter()
+            .find(|(active_offset, active_size)| offset < active_offset + active_size && offset + size > *active_offset)
+        {
+            return Err(Error::from_reason(format!(
+                "writeMapped() range [{}, {}) overlaps with getMappedRange() range [{}, {})",
+                offset,
+                offset + size,
+                active_offset,
+                active_offset + active_size
+            )));
+        }
+        drop(ranges);
+
+        if size > 0 {
+            self.buffer.slice(offset..offset + size).get_mapped_range_mut().copy_from_slice(&data);
+        }
+        Ok(())
+    }
+
+    /// Read a range synchronously (extension)
+    ///
+    /// Maps, waits, copies and unmaps in one blocking call, for small debug
+    /// readbacks and scripts. Buffers without MAP_READ usage are copied into a
+    /// staging buffer first, which needs COPY_SRC usage. The calling thread is
+    /// blocked until all submitted GPU work is done, so ranges are limited to
+    /// 4 MiB; use mapAsync() in anything latency sensitive.
+    ///
+    /// # Parameters
+    /// * `offset` - Byte offset into the buffer (optional, default 0). Must be multiple of 8.
+    /// * `size` - Number of bytes to read (optional, default remaining bytes). Must be multiple of 4.
+    #[napi(catch_unwind, js_name = "readSync")]
+    pub fn read_sync(&self, offset: Option<u32>, size: Option<u32>) -> Result<Buffer> {
+        let state = *self.lock_map_state()?;
+        if state != MapState::Unmapped {
+            return Err(Error::from_reason(format!(
+                "Buffer must be unmapped before calling readSync(). Current state: {}",
+                state.name()
+            )));
+        }
+
+        self.ensure_not_destroyed("readSync")?;
+
+        let buffer_size = self.buffer.size();
+        let offset = offset.unwrap_or(0) as u64;
+        let size = size.map(|s| s as u64).unwrap_or(buffer_size.saturating_sub(offset));
+        if offset % 8 != 0 || size % 4 != 0 {
+            return Err(Error::from_reason(format!(
+                "readSync() offset ({}) must be a multiple of 8 and size ({}) a multiple of 4",
+                offset, size
+            )));
+        }
+        if offset + size > buffer_size {
+            return Err(Error::from_reason(format!(
+                "Range (offset {} + size {}) exceeds buffer size ({})",
+                offset, size, buffer_size
+            )));
+        }
+        if size > READ_SYNC_LIMIT {
+            return Err(Error::from_reason(format!(
+                "readSync() reads at most {} bytes, got {}; use mapAsync() for large buffers",
+                READ_SYNC_LIMIT, size
+            )));
+        }
+        if size == 0 {
+            return Ok(Buffer::from(Vec::new()));
+        }
+
+        let usage = self.buffer.usage();
+        if usage.contains(wgpu::BufferUsages::MAP_READ) {
+            return self.read_blocking(&self.buffer, offset, size).map(Buffer::from);
+        }
+        if !usage.contains(wgpu::BufferUsages::COPY_SRC) {
+            return Err(Error::from_reason("readSync() needs a buffer with MAP_READ or COPY_SRC usage"));
+        }
+        let staging = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("readSync staging"),
+            size,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let mut encoder = self.device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("readSync"),
+        });
+        encoder.copy_buffer_to_buffer(&self.buffer, offset, &staging, 0, size);
+        self.queue.submit(Some(encoder.finish()));
+        self.read_blocking(&staging, 0, size).map(Buffer::from)
+    }
+
     /// Unmap the buffer
     ///
     /// Releases the mapped memory and flushes changes to GPU.
     /// Must be called after mapping operations before using buffer in GPU operations.
     ///
     /// # Parameters
-    /// * `modified_buffer` - Optional. If provided, writes this data to GPU before unmapping.
-    ///                       Use this when you've modified the buffer from getMappedRange().
-    ///                       Note: In JavaScript, this is handled automatically by the wrapper.
+    /// * `modified` - Optional. Data to write to the GPU before unmapping: either one
+    ///                buffer written at offset 0, or the `{ offset, data }` of every
+    ///                range returned by getMappedRange().
+    ///                Note: In JavaScript, this is handled automatically by the wrapper.
     ///
     /// # WebGPU Standard Usage (JavaScript)
     /// ```js
@@ -236,16 +632,40 @@ impl GpuBuffer {
     /// console.log(view[0])
     /// buffer.unmap()
     /// ```
-    #[napi]
-    pub fn unmap(&self, modified_buffer: Option<Buffer>) -> Result<()> {
+    #[napi(catch_unwind)]
+    pub fn unmap(&self, modified: Option<Either<Buffer, Vec<MappedRangeData>>>) -> Result<()> {
+        let mut state = self.lock_map_state()?;
+        let (mode, mapped_offset, mapped_size) = match *state {
+            // Unmapping an unmapped buffer does nothing (WebGPU spec)
+            MapState::Unmapped => return Ok(()),
+            // Aborts the pending map; its mapAsync() call rejects
+            MapState::Pending { .. } => {
+                *state = MapState::Unmapped;
+                drop(state);
+                self.buffer.unmap();
+                return Ok(());
+            }
+            MapState::Mapped { mode, offset, size } => (mode, offset, size),
+        };
+
         // Get pending writes before unmapping
         let mut pending = self.pending_writes.lock()
             .map_err(|_| Error::from_reason("Failed to lock pending writes"))?;
 
+        // Ranges of a buffer mapped for reading aren't written back
+        let modified: Vec<(u64, Buffer)> = match modified {
+            _ if mode == wgpu::MapMode::Read => Vec::new(),
+            None => Vec::new(),
+            Some(Either::A(data)) => vec![(0, data)],
+            Some(Either::B(ranges)) => ranges.into_iter().map(|range| (range.offset as u64, range.data)).collect(),
+        };
+
         // Check if buffer has COPY_DST usage (required for queue.write_buffer())
         let has_copy_dst = self.buffer.usage().contains(wgpu::BufferUsages::COPY_DST);
 
-        if !pending.is_empty() || modified_buffer.is_some() {
+        if mapped_size == 0 {
+            // Nothing was mapped in wgpu
+        } else if !pending.is_empty() || !modified.is_empty() {
             if has_copy_dst {
                 // Buffer has COPY_DST: unmap first, then use queue.write_buffer()
                 self.buffer.unmap();
@@ -255,9 +675,9 @@ impl GpuBuffer {
                     self.queue.write_buffer(&self.buffer, *offset, data);
                 }
 
-                // If a modified buffer was provided, write it too
-                if let Some(data) = modified_buffer {
-                    self.queue.write_buffer(&self.buffer, 0, data.as_ref());
+                // Write the modified ranges too
+                for (offset, data) in &modified {
+                    self.queue.write_buffer(&self.buffer, *offset, data.as_ref());
                 }
 
                 // Submit and poll to ensure writes complete
@@ -265,22 +685,17 @@ impl GpuBuffer {
                 self.device.poll(wgpu::Maintain::Wait);
             } else {
                 // Buffer doesn't have COPY_DST: use mapped memory writes
-                let slice = self.buffer.slice(..);
+                let slice = self.buffer.slice(mapped_offset..mapped_offset + mapped_size);
                 let mut mapped = slice.get_mapped_range_mut();
 
-                // Write all pending writes directly to mapped memory
-                for (offset, data) in pending.iter() {
-                    let offset_usize = *offset as usize;
-                    if offset_usize + data.len() <= mapped.len() {
-                        mapped[offset_usize..offset_usize + data.len()].copy_from_slice(data);
-                    }
-                }
-
-                // If a modified buffer was provided, write it too
-                if let Some(data) = modified_buffer {
-                    let data_slice = data.as_ref();
-                    if data_slice.len() <= mapped.len() {
-                        mapped[..data_slice.len()].copy_from_slice(data_slice);
+                // Write all pending writes and modified ranges directly to mapped memory
+                let writes = pending.iter().map(|(offset, data)| (*offset, data.as_slice()));
+                for (offset, data) in writes.chain(modified.iter().map(|(offset, data)| (*offset, data.as_ref()))) {
+                    let Some(start) = offset.checked_sub(mapped_offset).map(|start| start as usize) else {
+                        continue;
+                    };
+                    if start + data.len() <= mapped.len() {
+                        mapped[start..start + data.len()].copy_from_slice(data);
                     }
                 }
 
@@ -301,19 +716,22 @@ impl GpuBuffer {
             .map_err(|_| Error::from_reason("Failed to lock active ranges"))?;
         ranges.clear();
 
-        // Update map state to unmapped
-        let mut state = self.map_state.lock()
-            .map_err(|_| Error::from_reason("Failed to lock map state"))?;
-        *state = "unmapped".to_string();
-
+        *state = MapState::Unmapped;
         Ok(())
     }
 
     /// Destroy the buffer
     ///
     /// Explicitly releases GPU resources. Buffers are automatically destroyed when dropped.
-    #[napi]
-    pub fn destroy(&self) {
+    #[napi(catch_unwind)]
+    pub fn destroy(&self, mut env: Env) -> Result<()> {
+        // Destroying unmaps the buffer and aborts a pending map
+        *self.lock_map_state()? = MapState::Unmapped;
+        self.destroyed.store(true, Ordering::Relaxed);
+        self.active_ranges.lock()
+            .map_err(|_| Error::from_reason("Failed to lock active ranges"))?
+            .clear();
         self.buffer.destroy();
+        self.external_memory.release(&mut env)
     }
 }
@@ -0,0 +1,444 @@
+//! Datasets split across storage buffer bindings
+//!
+//! A storage binding can't exceed `maxStorageBufferBindingSize`, often
+//! 128 MiB to 2 GiB, and no buffer can exceed `maxBufferSize`, so larger
+//! inputs have to be processed in pieces. A chunked buffer stores one
+//! logical dataset in as many buffers as it takes, each bindable whole,
+//! with chunk boundaries on whole elements. Reads and writes address the
+//! dataset as one byte range, and `dispatch()` runs a pipeline over every
+//! chunk in turn.
+
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use std::sync::Arc;
+use wgpu::util::DeviceExt;
+
+/// Chunked buffer descriptor
+#[napi(object)]
+pub struct ChunkedBufferDescriptor {
+    pub label: Option<String>,
+    /// Size of the whole dataset in bytes, a multiple of `elementSize`
+    pub size: i64,
+    /// Bytes per element, a multiple of 4 (default 4). Chunks hold whole
+    /// elements
+    pub element_size: Option<u32>,
+    /// Largest chunk in bytes (default and maximum: the smaller of
+    /// `maxStorageBufferBindingSize` and `maxBufferSize`)
+    pub max_chunk_size: Option<i64>,
+    /// Usage added to STORAGE | COPY_SRC | COPY_DST, e.g. VERTEX or ["VERTEX"]
+    pub usage: Option<Either<u32, Vec<String>>>,
+}
+
+/// Where one chunk lies in the dataset
+#[napi(object)]
+pub struct BufferChunk {
+    pub index: u32,
+    /// Offset of the chunk in the dataset, in bytes
+    pub byte_offset: i64,
+    pub byte_size: i64,
+    /// Offset of the chunk's first element in the dataset
+    pub element_offset: i64,
+    pub element_count: u32,
+}
+
+/// Options of `dispatch()`
+#[napi(object)]
+pub struct ChunkDispatchDescriptor {
+    pub label: Option<String>,
+    /// Bind group index the per-chunk bind groups are set at (default 0)
+    pub group: Option<u32>,
+    /// Indices the shared bind groups are set at, one per group
+    pub shared_groups: Option<Vec<u32>>,
+    /// Invocations per workgroup (default: the pipeline's `@workgroup_size`,
+    /// all dimensions multiplied)
+    pub workgroup_size: Option<u32>,
+}
+
+/// Size of the `ChunkInfo` struct in the info buffer
+const CHUNK_INFO_SIZE: u64 = 16;
+
+/// One logical dataset stored in several storage buffers
+#[napi]
+pub struct GpuChunkedBuffer {
+    device: Arc<wgpu::Device>,
+    queue: Arc<wgpu::Queue>,
+    poller: crate::poller::DevicePoller,
+    errors: crate::error::ErrorSink,
+    label: Option<String>,
+    size: u64,
+    element_size: u64,
+    /// Bytes in every chunk but the last
+    chunk_size: u64,
+    buffers: Vec<crate::GpuBuffer>,
+    /// A `ChunkInfo` per chunk, `info_stride` bytes apart
+    info: crate::GpuBuffer,
+    info_stride: u64,
+}
+
+impl GpuChunkedBuffer {
+    pub(crate) fn new(env: &mut Env, device: &crate::GpuDevice, descriptor: ChunkedBufferDescriptor) -> Result<Self> {
+        let limits = device.device.limits();
+        let limit = (limits.max_storage_buffer_binding_size as u64).min(limits.max_buffer_size);
+        let element_size = descriptor.element_size.unwrap_or(4) as u64;
+        if element_size == 0 || element_size % 4 != 0 {
+            return Err(Error::from_reason(format!(
+                "createChunkedBuffer elementSize must be a non-zero multiple of 4, got {}",
+                element_size
+            )));
+        }
+        if descriptor.size <= 0 || descriptor.size as u64 % element_size != 0 {
+            return Err(Error::from_reason(format!(
+                "createChunkedBuffer size must be a positive multiple of elementSize ({}), got {}",
+                element_size, descriptor.size
+            )));
+        }
+        let max_chunk_size = match descriptor.max_chunk_size {
+            None => limit,
+            Some(size) if size > 0 && size as u64 <= limit => size as u64,
+            Some(size) => {
+                return Err(Error::from_reason(format!(
+                    "createChunkedBuffer maxChunkSize must be between 1 and {} (the binding and buffer size limits), got {}",
+                    limit, size
+                )))
+            }
+        };
+        let chunk_size = max_chunk_size / element_size * element_size;
+        if chunk_size == 0 {
+            return Err(Error::from_reason(format!(
+                "createChunkedBuffer maxChunkSize ({}) is smaller than one element ({} bytes)",
+                max_chunk_size, element_size
+            )));
+        }
+
+        let size = descriptor.size as u64;
+        let usage = wgpu::BufferUsages::STORAGE
+            | wgpu::BufferUsages::COPY_SRC
+            | wgpu::BufferUsages::COPY_DST
+            | descriptor
+                .usage
+                .as_ref()
+                .map(crate::parse::parse_buffer_usage)
+                .transpose()?
+                .unwrap_or(wgpu::BufferUsages::empty());
+        let label = descriptor.label.as_deref();
+        let chunk_count = size.div_ceil(chunk_size);
+        let mut buffers = Vec::with_capacity(chunk_count as usize);
+        for index in 0..chunk_count {
+            let chunk = (size - index * chunk_size).min(chunk_size);
+            let buffer = device.errors.check(
+                "createChunkedBuffer",
+                || {
+                    format!(
+                        "label: {}, chunk: {} of {}, size: {}",
+                        crate::error::describe_label(label),
+                        index,
+                        chunk_count,
+                        chunk
+                    )
+                },
+                || {
+                    device.device.create_buffer(&wgpu::BufferDescriptor {
+                        label,
+                        size: chunk,
+                        usage,
+                        mapped_at_creation: false,
+                    })
+                },
+            )?;
+            let buffer = crate::GpuBuffer::new(
+                buffer,
+                descriptor.label.clone(),
+                device.device.clone(),
+                device.queue_internal.clone(),
+                device.poller.clone(),
+            )
+            .track_memory(env)?;
+            buffers.push(buffer);
+        }
+
+        // struct ChunkInfo { index: u32, elementCount: u32, elementOffset: vec2<u32> }
+        let info_stride = CHUNK_INFO_SIZE.next_multiple_of(limits.min_uniform_buffer_offset_alignment as u64);
+        let mut contents = vec![0u8; (info_stride * chunk_count) as usize];
+        for index in 0..chunk_count {
+            let element_offset = index * chunk_size / element_size;
+            let element_count = (size - index * chunk_size).min(chunk_size) / element_size;
+            let at = (index * info_stride) as usize;
+            contents[at..at + 4].copy_from_slice(&(index as u32).to_le_bytes());
+            contents[at + 4..at + 8].copy_from_slice(&(element_count as u32).to_le_bytes());
+            contents[at + 8..at + 16].copy_from_slice(&element_offset.to_le_bytes());
+        }
+        let info = device.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("chunk info"),
+            contents: &contents,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_SRC,
+        });
+        let info = crate::GpuBuffer::new(
+            info,
+            Some("chunk info".to_string()),
+            device.device.clone(),
+            device.queue_internal.clone(),
+            device.poller.clone(),
+        );
+
+        Ok(Self {
+            device: device.device.clone(),
+            queue: device.queue_internal.clone(),
+            poller: device.poller.clone(),
+            errors: device.errors.clone(),
+            label: descriptor.label,
+            size,
+            element_size,
+            chunk_size,
+            buffers,
+            info,
+            info_stride,
+        })
+    }
+
+    /// Check that `offset..offset + size` is a 4-byte aligned range of the
+    /// dataset, returning its end
+    fn check_range(&self, method: &str, offset: i64, size: i64) -> Result<u64> {
+        if offset < 0 || size < 0 || offset % 4 != 0 || size % 4 != 0 || (offset + size) as u64 > self.size {
+            return Err(Error::from_reason(format!(
+                "{}: range [{}, {}) must be 4-byte aligned and within the dataset ({} bytes)",
+                method,
+                offset,
+                offset.saturating_add(size),
+                self.size
+            )));
+        }
+        Ok((offset + size) as u64)
+    }
+
+    /// The pieces of `start..end` in each chunk: chunk index, offset in the
+    /// chunk and length
+    fn pieces(&self, start: u64, end: u64) -> Vec<(usize, u64, u64)> {
+        let mut pieces = Vec::new();
+        let mut at = start;
+        while at < end {
+            let index = at / self.chunk_size;
+            let within = at - index * self.chunk_size;
+            let length = (self.chunk_size - within).min(end - at);
+            pieces.push((index as usize, within, length));
+            at += length;
+        }
+        pieces
+    }
+}
+
+#[napi]
+impl GpuChunkedBuffer {
+    /// Size of the dataset in bytes
+    #[napi(catch_unwind, getter)]
+    pub fn size(&self) -> i64 {
+        self.size as i64
+    }
+
+    #[napi(catch_unwind, getter, js_name = "elementSize")]
+    pub fn element_size(&self) -> u32 {
+        self.element_size as u32
+    }
+
+    #[napi(catch_unwind, getter, js_name = "chunkCount")]
+    pub fn chunk_count(&self) -> u32 {
+        self.buffers.len() as u32
+    }
+
+    /// Where each chunk lies in the dataset, in order
+    #[napi(catch_unwind, getter)]
+    pub fn chunks(&self) -> Vec<BufferChunk> {
+        self.buffers
+            .iter()
+            .enumerate()
+            .map(|(index, buffer)| {
+                let byte_offset = index as u64 * self.chunk_size;
+                BufferChunk {
+                    index: index as u32,
+                    byte_offset: byte_offset as i64,
+                    byte_size: buffer.buffer.size() as i64,
+                    element_offset: (byte_offset / self.element_size) as i64,
+                    element_count: (buffer.buffer.size() / self.element_size) as u32,
+                }
+            })
+            .collect()
+    }
+
+    /// The storage buffer holding chunk `index`
+    #[napi(catch_unwind)]
+    pub fn buffer(&self, index: u32) -> Result<crate::GpuBuffer> {
+        self.buffers.get(index as usize).cloned().ok_or_else(|| {
+            Error::from_reason(format!(
+                "buffer: chunk {} doesn't exist, the dataset has {} chunks",
+                index,
+                self.buffers.len()
+            ))
+        })
+    }
+
+    /// Uniform buffer holding a `ChunkInfo` per chunk, `infoStride` bytes apart
+    ///
+    /// `struct ChunkInfo { index: u32, elementCount: u32, elementOffset: vec2<u32> }`,
+    /// with the low 32 bits of the element offset first.
+    #[napi(catch_unwind, getter, js_name = "infoBuffer")]
+    pub fn info_buffer(&self) -> crate::GpuBuffer {
+        self.info.clone()
+    }
+
+    #[napi(catch_unwind, getter, js_name = "infoStride")]
+    pub fn info_stride(&self) -> u32 {
+        self.info_stride as u32
+    }
+
+    /// Write `data` at byte `offset` of the dataset, across chunks as needed
+    ///
+    /// Like `queue.writeBuffer()`, the offset and length must be multiples of 4.
+    #[napi(catch_unwind)]
+    pub fn write(&self, offset: i64, data: Buffer) -> Result<()> {
+        self.errors.ensure_usable("write")?;
+        let end = self.check_range("write", offset, data.len() as i64)?;
+        let mut at = 0;
+        for (index, within, length) in self.pieces(offset as u64, end) {
+            self.queue
+                .write_buffer(&self.buffers[index].buffer, within, &data[at..at + length as usize]);
+            at += length as usize;
+        }
+        Ok(())
+    }
+
+    /// Read `size` bytes at byte `offset` of the dataset (default: all of it)
+    #[napi(catch_unwind)]
+    pub async fn read(&self, offset: Option<i64>, size: Option<i64>) -> Result<Buffer> {
+        self.errors.ensure_usable("read")?;
+        let offset = offset.unwrap_or(0);
+        let size = size.unwrap_or(self.size as i64 - offset);
+        let end = self.check_range("read", offset, size)?;
+
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: Some("chunked readback") });
+        let mut readbacks = Vec::new();
+        for (index, within, length) in self.pieces(offset as u64, end) {
+            let readback = self.device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some("chunked readback"),
+                size: length,
+                usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            });
+            encoder.copy_buffer_to_buffer(&self.buffers[index].buffer, within, &readback, 0, length);
+            readbacks.push(readback);
+        }
+        self.queue.submit(Some(encoder.finish()));
+
+        let _polling = self.poller.track();
+        let mut data = Vec::with_capacity(size as usize);
+        for readback in readbacks {
+            let slice = readback.slice(..);
+            let (sender, receiver) = futures::channel::oneshot::channel();
+            slice.map_async(wgpu::MapMode::Read, move |result| {
+                let _ = sender.send(result);
+            });
+            receiver
+                .await
+                .map_err(|_| Error::from_reason("Failed to receive map result"))?
+                .map_err(|e| Error::from_reason(format!("Failed to read chunked buffer: {:?}", e)))?;
+            data.extend_from_slice(&slice.get_mapped_range());
+            readback.unmap();
+        }
+        Ok(data.into())
+    }
+
+    /// Dispatch `pipeline` once per chunk
+    ///
+    /// `bind_groups[i]` is set at `group` for chunk `i`, and the shared bind
+    /// groups at their `sharedGroups` indices for every chunk. Each chunk
+    /// gets one invocation per element; counts beyond
+    /// `maxComputeWorkgroupsPerDimension` workgroups spill into y. All
+    /// dispatches are recorded in one pass and submitted immediately.
+    #[napi(catch_unwind)]
+    pub fn dispatch(
+        &self,
+        pipeline: &crate::GpuComputePipeline,
+        bind_groups: Vec<&crate::GpuBindGroup>,
+        shared: Vec<&crate::GpuBindGroup>,
+        descriptor: Option<ChunkDispatchDescriptor>,
+    ) -> Result<()> {
+        self.errors.ensure_usable("dispatch")?;
+        let descriptor = descriptor.unwrap_or(ChunkDispatchDescriptor {
+            label: None,
+            group: None,
+            shared_groups: None,
+            workgroup_size: None,
+        });
+        if bind_groups.len() != self.buffers.len() {
+            return Err(Error::from_reason(format!(
+                "dispatch: needs one bind group per chunk ({}), got {}",
+                self.buffers.len(),
+                bind_groups.len()
+            )));
+        }
+        let shared_groups = descriptor.shared_groups.unwrap_or_default();
+        if shared_groups.len() != shared.len() {
+            return Err(Error::from_reason(format!(
+                "dispatch: sharedGroups has {} indices for {} shared bind groups",
+                shared_groups.len(),
+                shared.len()
+            )));
+        }
+        let workgroup_size = match descriptor.workgroup_size {
+            Some(0) => return Err(Error::from_reason("dispatch: workgroupSize must be non-zero")),
+            Some(size) => size,
+            None => pipeline.reflection.workgroup_size(&pipeline.entry_point)?.iter().product(),
+        };
+        let group = descriptor.group.unwrap_or(0);
+        let label = descriptor.label.as_deref().or(self.label.as_deref());
+        let max_workgroups = self.device.limits().max_compute_workgroups_per_dimension;
+
+        self.errors.check(
+            "dispatch",
+            || {
+                format!(
+                    "label: {}, chunks: {}, workgroupSize: {}",
+                    crate::error::describe_label(label),
+                    self.buffers.len(),
+                    workgroup_size
+                )
+            },
+            || {
+                let mut encoder = self
+                    .device
+                    .create_command_encoder(&wgpu::CommandEncoderDescriptor { label });
+                {
+                    let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                        label,
+                        timestamp_writes: None,
+                    });
+                    pass.set_pipeline(&pipeline.pipeline);
+                    for (index, bind_group) in shared_groups.iter().zip(&shared) {
+                        pass.set_bind_group(*index, &bind_group.bind_group, &[]);
+                    }
+                    for (buffer, bind_group) in self.buffers.iter().zip(&bind_groups) {
+                        let elements = (buffer.buffer.size() / self.element_size) as u32;
+                        let workgroups = elements.div_ceil(workgroup_size);
+                        let x = workgroups.min(max_workgroups);
+                        pass.set_bind_group(group, &bind_group.bind_group, &[]);
+                        pass.dispatch_workgroups(x, workgroups.div_ceil(x), 1);
+                    }
+                }
+                self.queue.submit(Some(encoder.finish()));
+            },
+        )?;
+
+        if self.poller.auto_maintain() {
+            // Released by the poll thread itself once this submission has completed
+            let polling = self.poller.track();
+            self.queue.on_submitted_work_done(move || drop(polling));
+        }
+        Ok(())
+    }
+
+    /// Descriptor label, or "" without one
+    #[napi(catch_unwind, getter)]
+    pub fn label(&self) -> String {
+        self.label.clone().unwrap_or_default()
+    }
+}
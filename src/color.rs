@@ -0,0 +1,309 @@
+//! Color conversions
+//!
+//! Channel swizzles (e.g. RGBA to BGRA) and sRGB transfer functions, applied
+//! by `blitTexture` between textures, by `convertPixels` to packed RGBA8
+//! pixels in storage buffers and by `copyExternalImageToTexture` to decoded
+//! images on their way into a texture.
+
+use crate::util;
+use crate::{GpuDevice, GpuQueue};
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use std::sync::OnceLock;
+use wgpu::util::DeviceExt;
+
+const WORKGROUP_SIZE: u32 = 256;
+
+/// WGSL shared by the conversion shaders
+pub(crate) const COLOR_WGSL: &str = include_str!("shaders/color.wgsl");
+
+/// Options for `convertPixels`
+#[napi(object)]
+pub struct ColorConversionOptions {
+    /// Source channel of each output channel, e.g. "bgra" (default "rgba")
+    pub swizzle: Option<String>,
+    /// "srgb-to-linear" or "linear-to-srgb"; applied to RGB after the swizzle
+    pub color_space: Option<String>,
+}
+
+/// Decoded image for `copyExternalImageToTexture`
+#[napi(object)]
+pub struct ExternalImageSource {
+    /// Tightly packed, unpremultiplied RGBA8 pixels with the top row first
+    pub data: Buffer,
+    pub width: u32,
+    pub height: u32,
+    /// Top-left pixel of the copy, counted in the flipped image with `flipY`
+    pub origin: Option<crate::Origin2D>,
+    /// Flip the image vertically, e.g. for bottom-up decoders (default false)
+    pub flip_y: Option<bool>,
+}
+
+/// Conversions of `copyExternalImageToTexture`
+#[napi(object)]
+pub struct ExternalImageDestination {
+    /// Multiply RGB by alpha after the color space conversion (default false)
+    pub premultiplied_alpha: Option<bool>,
+    /// "srgb" (default) keeps the encoded values, "srgb-linear" decodes them
+    pub color_space: Option<String>,
+}
+
+/// Parse a conversion into the `ColorParams` fields before `count`
+pub(crate) fn color_params(swizzle: Option<&str>, color_space: Option<&str>) -> Result<[u32; 5]> {
+    let swizzle = swizzle.unwrap_or("rgba");
+    let mut params = [0; 5];
+    if swizzle.len() != 4 {
+        return Err(invalid_swizzle(swizzle));
+    }
+    for (param, channel) in params.iter_mut().zip(swizzle.chars()) {
+        *param = match channel {
+            'r' => 0,
+            'g' => 1,
+            'b' => 2,
+            'a' => 3,
+            _ => return Err(invalid_swizzle(swizzle)),
+        };
+    }
+    params[4] = match color_space {
+        None => 0,
+        Some("srgb-to-linear") => 1,
+        Some("linear-to-srgb") => 2,
+        Some(other) => {
+            return Err(Error::from_reason(format!(
+                "Unknown color space conversion: {}. Use 'srgb-to-linear' or 'linear-to-srgb'",
+                other
+            )))
+        }
+    };
+    Ok(params)
+}
+
+fn invalid_swizzle(swizzle: &str) -> Error {
+    Error::from_reason(format!(
+        "Invalid swizzle: {}. Use four of r, g, b and a, e.g. 'bgra'",
+        swizzle
+    ))
+}
+
+/// Pixel conversion pipeline of one device, created on first use
+#[derive(Default)]
+pub(crate) struct ColorPipelines {
+    convert: OnceLock<wgpu::ComputePipeline>,
+    external_image: OnceLock<wgpu::ComputePipeline>,
+}
+
+impl ColorPipelines {
+    fn convert(&self, device: &wgpu::Device) -> &wgpu::ComputePipeline {
+        self.convert.get_or_init(|| {
+            util::create_compute_pipeline(
+                device,
+                "convert-pixels",
+                &(COLOR_WGSL.to_string() + include_str!("shaders/convert_pixels.wgsl")),
+                "main",
+            )
+        })
+    }
+
+    fn external_image(&self, device: &wgpu::Device) -> &wgpu::ComputePipeline {
+        self.external_image.get_or_init(|| {
+            util::create_compute_pipeline(
+                device,
+                "external-image",
+                &(COLOR_WGSL.to_string() + include_str!("shaders/external_image.wgsl")),
+                "main",
+            )
+        })
+    }
+}
+
+#[napi]
+impl GpuDevice {
+    /// Swizzle channels and convert sRGB of packed RGBA8 pixels (extension)
+    ///
+    /// Converts `count` 4-byte pixels from `input` into `output`, e.g. BGRA
+    /// screen captures into RGBA with `swizzle: "bgra"`. Both buffers need
+    /// STORAGE usage and must be different buffers.
+    #[napi(catch_unwind, js_name = "convertPixels")]
+    pub fn convert_pixels(
+        &self,
+        encoder: &crate::GpuCommandEncoder,
+        input: &crate::GpuBuffer,
+        output: &crate::GpuBuffer,
+        count: u32,
+        options: Option<ColorConversionOptions>,
+    ) -> Result<()> {
+        let [r, g, b, a, color_space] = color_params(
+            options.as_ref().and_then(|o| o.swizzle.as_deref()),
+            options.as_ref().and_then(|o| o.color_space.as_deref()),
+        )?;
+        if std::ptr::eq(input, output) {
+            return Err(Error::from_reason("convertPixels: input and output must be different buffers"));
+        }
+        let size = count as u64 * 4;
+        util::validate_storage_buffer(&input.buffer, size, "convertPixels input")?;
+        util::validate_storage_buffer(&output.buffer, size, "convertPixels output")?;
+        if count == 0 {
+            return Ok(());
+        }
+
+        let pipeline = self.color.convert(&self.device);
+        let params = util::create_params_buffer(&self.device, "convert-pixels params", &[r, g, b, a, color_space, count]);
+        let bind_group = util::bind_buffers(&self.device, pipeline, &[&params, &input.buffer, &output.buffer]);
+        encoder.with_encoder(|enc| {
+            util::dispatch(enc, pipeline, &bind_group, count.div_ceil(WORKGROUP_SIZE));
+            Ok(())
+        })
+    }
+}
+
+#[napi]
+impl GpuQueue {
+    /// Copy a decoded RGBA8 image into a texture (WebGPU standard method)
+    ///
+    /// Flipping, premultiplying and the color space conversion run in a
+    /// compute pass before the copy, which is submitted immediately. The
+    /// destination must be an rgba8unorm or bgra8unorm texture (or their
+    /// -srgb variants) with COPY_DST usage; the JavaScript wrapper accepts
+    /// the standard form.
+    #[napi(catch_unwind, js_name = "copyExternalImageToTexture")]
+    pub fn copy_external_image_to_texture(
+        &self,
+        source: ExternalImageSource,
+        destination: &crate::GpuTexture,
+        location: crate::TextureCopyLocation,
+        conversion: Option<ExternalImageDestination>,
+        size: crate::Extent3D,
+    ) -> Result<()> {
+        let size: wgpu::Extent3d = (&size).into();
+        let origin_x = source.origin.as_ref().and_then(|o| o.x).unwrap_or(0);
+        let origin_y = source.origin.as_ref().and_then(|o| o.y).unwrap_or(0);
+        let expected = source.width as u64 * source.height as u64 * 4;
+        if source.data.len() as u64 != expected {
+            return Err(Error::from_reason(format!(
+                "copyExternalImageToTexture: source data is {} bytes, expected {} for a {}x{} RGBA8 image",
+                source.data.len(),
+                expected,
+                source.width,
+                source.height
+            )));
+        }
+        if size.depth_or_array_layers != 1 {
+            return Err(Error::from_reason(format!(
+                "copyExternalImageToTexture: copy depth must be 1, got {}",
+                size.depth_or_array_layers
+            )));
+        }
+        if origin_x as u64 + size.width as u64 > source.width as u64
+            || origin_y as u64 + size.height as u64 > source.height as u64
+        {
+            return Err(Error::from_reason(format!(
+                "copyExternalImageToTexture: {}x{} at ({}, {}) exceeds the {}x{} source",
+                size.width, size.height, origin_x, origin_y, source.width, source.height
+            )));
+        }
+
+        let format = destination.texture.format();
+        let swizzle = match format.remove_srgb_suffix() {
+            wgpu::TextureFormat::Rgba8Unorm => "rgba",
+            wgpu::TextureFormat::Bgra8Unorm => "bgra",
+            _ => {
+                return Err(Error::from_reason(format!(
+                    "copyExternalImageToTexture doesn't support {:?} destinations, use rgba8unorm or bgra8unorm",
+                    format
+                )))
+            }
+        };
+        let premultiply = conversion.as_ref().and_then(|c| c.premultiplied_alpha).unwrap_or(false);
+        let color_space = match conversion.as_ref().and_then(|c| c.color_space.as_deref()) {
+            None | Some("srgb") => None,
+            Some("srgb-linear") if format.is_srgb() => {
+                return Err(Error::from_reason(format!(
+                    "copyExternalImageToTexture: colorSpace 'srgb-linear' needs a non-sRGB destination, {:?} already decodes when sampled",
+                    format
+                )))
+            }
+            Some("srgb-linear") => Some("srgb-to-linear"),
+            Some(other) => {
+                return Err(Error::from_reason(format!(
+                    "Unknown color space: {}. Use 'srgb' or 'srgb-linear'",
+                    other
+                )))
+            }
+        };
+        let [r, g, b, a, color_space] = color_params(Some(swizzle), color_space)?;
+        let count = size.width * size.height;
+        if count == 0 {
+            return Ok(());
+        }
+
+        // Buffer-to-texture copies need rows padded to 256 bytes
+        let row_pixels = (size.width * 4).next_multiple_of(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT) / 4;
+        let commands = self.errors.check(
+            "copyExternalImageToTexture",
+            || {
+                format!(
+                    "destination: {}, size: {}x{}",
+                    crate::error::describe_label(destination.label.as_deref()),
+                    size.width,
+                    size.height
+                )
+            },
+            || {
+                let pipeline = self.color.external_image(&self.device);
+                let params = util::create_params_buffer(
+                    &self.device,
+                    "external-image params",
+                    &[
+                        r,
+                        g,
+                        b,
+                        a,
+                        color_space,
+                        count,
+                        0,
+                        0,
+                        source.width,
+                        source.height,
+                        origin_x,
+                        origin_y,
+                        size.width,
+                        row_pixels,
+                        source.flip_y.unwrap_or(false) as u32,
+                        premultiply as u32,
+                    ],
+                );
+                let input = self.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                    label: Some("external-image source"),
+                    contents: &source.data,
+                    usage: wgpu::BufferUsages::STORAGE,
+                });
+                let output = util::create_storage_buffer(
+                    &self.device,
+                    "external-image pixels",
+                    row_pixels as u64 * size.height as u64 * 4,
+                );
+                let bind_group = util::bind_buffers(&self.device, pipeline, &[&params, &input, &output]);
+
+                let mut encoder = self.device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                    label: Some("copyExternalImageToTexture"),
+                });
+                util::dispatch(&mut encoder, pipeline, &bind_group, count.div_ceil(WORKGROUP_SIZE));
+                encoder.copy_buffer_to_texture(
+                    wgpu::ImageCopyBuffer {
+                        buffer: &output,
+                        layout: wgpu::ImageDataLayout {
+                            offset: 0,
+                            bytes_per_row: Some(row_pixels * 4),
+                            rows_per_image: None,
+                        },
+                    },
+                    location.to_wgpu(&destination.texture),
+                    size,
+                );
+                encoder.finish()
+            },
+        )?;
+        self.queue.submit(Some(commands));
+        Ok(())
+    }
+}
@@ -0,0 +1,264 @@
+//! Replaying binary command streams
+//!
+//! Engines with very high command counts can encode a whole pass as a
+//! `Uint32Array` of opcodes and arguments, with resources referenced by their
+//! index in tables passed alongside, and record it with one native call. Each
+//! command is an opcode word followed by a fixed number of argument words,
+//! except `SET_BIND_GROUP`, whose dynamic offsets follow an offset count.
+
+use crate::batch::{draw_command, draw_indexed_command};
+use crate::compute_pass::ComputeCommand;
+use crate::render_pass::RenderCommand;
+use crate::{
+    GpuBindGroup, GpuBuffer, GpuComputePassEncoder, GpuComputePipeline, GpuRenderBundleEncoder,
+    GpuRenderPassEncoder, GpuRenderPipeline,
+};
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use std::sync::Arc;
+
+/// `pipeline`
+pub(crate) const SET_PIPELINE: u32 = 1;
+/// `index, bindGroup, offsetCount, ...offsets`
+pub(crate) const SET_BIND_GROUP: u32 = 2;
+/// `slot, buffer, offset, size`
+pub(crate) const SET_VERTEX_BUFFER: u32 = 3;
+/// `buffer, format, offset, size`
+pub(crate) const SET_INDEX_BUFFER: u32 = 4;
+/// `vertexCount, instanceCount, firstVertex, firstInstance`
+pub(crate) const DRAW: u32 = 5;
+/// `indexCount, instanceCount, firstIndex, baseVertex, firstInstance`
+pub(crate) const DRAW_INDEXED: u32 = 6;
+/// `buffer, offset`
+pub(crate) const DRAW_INDIRECT: u32 = 7;
+/// `buffer, offset`
+pub(crate) const DRAW_INDEXED_INDIRECT: u32 = 8;
+/// `x, y, z`
+pub(crate) const DISPATCH: u32 = 9;
+/// `buffer, offset`
+pub(crate) const DISPATCH_INDIRECT: u32 = 10;
+
+/// Buffer size argument meaning "the rest of the buffer"
+pub(crate) const WHOLE_SIZE: u32 = u32::MAX;
+
+/// Resources referenced by index from a command stream
+struct Resources<'a, P> {
+    pipelines: &'a [&'a P],
+    bind_groups: &'a [&'a GpuBindGroup],
+    buffers: &'a [&'a GpuBuffer],
+}
+
+fn resource<'a, T>(table: &[&'a T], index: u32, kind: &str, at: usize) -> Result<&'a T> {
+    table.get(index as usize).copied().ok_or_else(|| {
+        Error::from_reason(format!(
+            "{} index {} at word {} is out of range ({} given)",
+            kind,
+            index,
+            at,
+            table.len()
+        ))
+    })
+}
+
+fn buffer_at(table: &[&GpuBuffer], index: u32, at: usize) -> Result<Arc<wgpu::Buffer>> {
+    Ok(resource(table, index, "Buffer", at)?.buffer.clone())
+}
+
+struct Reader<'a> {
+    words: &'a [u32],
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    /// The next opcode and the word it starts at
+    fn opcode(&mut self) -> Option<(u32, usize)> {
+        let at = self.position;
+        let opcode = *self.words.get(at)?;
+        self.position += 1;
+        Some((opcode, at))
+    }
+
+    fn take(&mut self, count: usize, at: usize) -> Result<&'a [u32]> {
+        let args = self
+            .words
+            .get(self.position..self.position + count)
+            .ok_or_else(|| Error::from_reason(format!("Command stream ends inside the command at word {}", at)))?;
+        self.position += count;
+        Ok(args)
+    }
+
+    fn bind_group<P>(&mut self, resources: &Resources<P>, at: usize) -> Result<(u32, Arc<wgpu::BindGroup>, Vec<u32>)> {
+        let args = self.take(3, at)?;
+        let bind_group = resource(resources.bind_groups, args[1], "Bind group", at)?;
+        let offsets = self.take(args[2] as usize, at)?;
+        Ok((args[0], bind_group.bind_group.clone(), offsets.to_vec()))
+    }
+}
+
+fn unsupported(opcode: u32, at: usize, target: &str) -> Error {
+    match opcode {
+        SET_PIPELINE..=DISPATCH_INDIRECT => Error::from_reason(format!(
+            "Command opcode {} at word {} is not supported in a {}",
+            opcode, at, target
+        )),
+        _ => Error::from_reason(format!("Unknown command opcode {} at word {}", opcode, at)),
+    }
+}
+
+fn buffer_range(buffer: &wgpu::Buffer, offset: u32, size: u32, at: usize) -> Result<(u64, Option<u64>)> {
+    let size = (size != WHOLE_SIZE).then_some(size as u64);
+    crate::render_pass::buffer_range(buffer, offset as u64, size)
+        .map_err(|reason| Error::from_reason(format!("Invalid buffer range at word {}: {}", at, reason)))
+}
+
+fn parse_render_stream(
+    words: &[u32],
+    resources: &Resources<GpuRenderPipeline>,
+    target: &str,
+) -> Result<Vec<RenderCommand>> {
+    let mut reader = Reader { words, position: 0 };
+    let mut commands = Vec::new();
+    while let Some((opcode, at)) = reader.opcode() {
+        let command = match opcode {
+            SET_PIPELINE => {
+                let args = reader.take(1, at)?;
+                RenderCommand::SetPipeline(resource(resources.pipelines, args[0], "Pipeline", at)?.pipeline.clone())
+            }
+            SET_BIND_GROUP => {
+                let (index, bind_group, offsets) = reader.bind_group(resources, at)?;
+                RenderCommand::SetBindGroup(index, bind_group, offsets)
+            }
+            SET_VERTEX_BUFFER => {
+                let args = reader.take(4, at)?;
+                let buffer = buffer_at(resources.buffers, args[1], at)?;
+                let (offset, size) = buffer_range(&buffer, args[2], args[3], at)?;
+                RenderCommand::SetVertexBuffer(args[0], buffer, offset, size)
+            }
+            SET_INDEX_BUFFER => {
+                let args = reader.take(4, at)?;
+                let buffer = buffer_at(resources.buffers, args[0], at)?;
+                let format = crate::parse::parse_index_format(&Either::B(args[1])).ok_or_else(|| {
+                    Error::from_reason(format!("Invalid index format: {} at word {}", args[1], at))
+                })?;
+                let (offset, size) = buffer_range(&buffer, args[2], args[3], at)?;
+                RenderCommand::SetIndexBuffer(buffer, format, offset, size)
+            }
+            DRAW => draw_command(reader.take(4, at)?),
+            DRAW_INDEXED => draw_indexed_command(reader.take(5, at)?),
+            DRAW_INDIRECT => {
+                let args = reader.take(2, at)?;
+                RenderCommand::DrawIndirect(buffer_at(resources.buffers, args[0], at)?, args[1] as u64)
+            }
+            DRAW_INDEXED_INDIRECT => {
+                let args = reader.take(2, at)?;
+                RenderCommand::DrawIndexedIndirect(buffer_at(resources.buffers, args[0], at)?, args[1] as u64)
+            }
+            _ => return Err(unsupported(opcode, at, target)),
+        };
+        commands.push(command);
+    }
+    Ok(commands)
+}
+
+fn parse_compute_stream(words: &[u32], resources: &Resources<GpuComputePipeline>) -> Result<Vec<ComputeCommand>> {
+    let mut reader = Reader { words, position: 0 };
+    let mut commands = Vec::new();
+    while let Some((opcode, at)) = reader.opcode() {
+        let command = match opcode {
+            SET_PIPELINE => {
+                let args = reader.take(1, at)?;
+                ComputeCommand::SetPipeline(resource(resources.pipelines, args[0], "Pipeline", at)?.pipeline.clone())
+            }
+            SET_BIND_GROUP => {
+                let (index, bind_group, offsets) = reader.bind_group(resources, at)?;
+                ComputeCommand::SetBindGroup(index, bind_group, offsets)
+            }
+            DISPATCH => {
+                let args = reader.take(3, at)?;
+                ComputeCommand::Dispatch(args[0], args[1], args[2])
+            }
+            DISPATCH_INDIRECT => {
+                let args = reader.take(2, at)?;
+                ComputeCommand::DispatchIndirect(buffer_at(resources.buffers, args[0], at)?, args[1] as u64)
+            }
+            _ => return Err(unsupported(opcode, at, "compute pass")),
+        };
+        commands.push(command);
+    }
+    Ok(commands)
+}
+
+#[napi]
+impl GpuComputePassEncoder {
+    /// Record a binary command stream (extension)
+    ///
+    /// `stream` holds `GPUCommandOpcode` commands whose pipeline, bind group and
+    /// buffer arguments index into the given tables. The whole stream is
+    /// checked before anything is recorded.
+    #[napi(catch_unwind, js_name = "executeCommandStream")]
+    pub fn execute_command_stream(
+        &mut self,
+        stream: Uint32Array,
+        pipelines: Vec<&GpuComputePipeline>,
+        bind_groups: Option<Vec<&GpuBindGroup>>,
+        buffers: Option<Vec<&GpuBuffer>>,
+    ) -> Result<()> {
+        let bind_groups = bind_groups.unwrap_or_default();
+        let buffers = buffers.unwrap_or_default();
+        let commands = parse_compute_stream(
+            &stream,
+            &Resources { pipelines: &pipelines, bind_groups: &bind_groups, buffers: &buffers },
+        )?;
+        self.record_all(commands)
+    }
+}
+
+#[napi]
+impl GpuRenderPassEncoder {
+    /// Record a binary command stream (extension)
+    ///
+    /// `stream` holds `GPUCommandOpcode` commands whose pipeline, bind group and
+    /// buffer arguments index into the given tables. The whole stream is
+    /// checked before anything is recorded.
+    #[napi(catch_unwind, js_name = "executeCommandStream")]
+    pub fn execute_command_stream(
+        &mut self,
+        stream: Uint32Array,
+        pipelines: Vec<&GpuRenderPipeline>,
+        bind_groups: Option<Vec<&GpuBindGroup>>,
+        buffers: Option<Vec<&GpuBuffer>>,
+    ) -> Result<()> {
+        let bind_groups = bind_groups.unwrap_or_default();
+        let buffers = buffers.unwrap_or_default();
+        let commands = parse_render_stream(
+            &stream,
+            &Resources { pipelines: &pipelines, bind_groups: &bind_groups, buffers: &buffers },
+            "render pass",
+        )?;
+        self.record_all(commands)
+    }
+}
+
+#[napi]
+impl GpuRenderBundleEncoder {
+    /// Record a binary command stream (extension)
+    ///
+    /// Takes the same commands as render passes.
+    #[napi(catch_unwind, js_name = "executeCommandStream")]
+    pub fn execute_command_stream(
+        &mut self,
+        stream: Uint32Array,
+        pipelines: Vec<&GpuRenderPipeline>,
+        bind_groups: Option<Vec<&GpuBindGroup>>,
+        buffers: Option<Vec<&GpuBuffer>>,
+    ) -> Result<()> {
+        let bind_groups = bind_groups.unwrap_or_default();
+        let buffers = buffers.unwrap_or_default();
+        let commands = parse_render_stream(
+            &stream,
+            &Resources { pipelines: &pipelines, bind_groups: &bind_groups, buffers: &buffers },
+            "render bundle",
+        )?;
+        self.record_all(commands)
+    }
+}
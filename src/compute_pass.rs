@@ -1,154 +1,213 @@
 use napi::bindgen_prelude::*;
 use napi_derive::napi;
+use std::sync::{Arc, Mutex};
+
+/// A compute pass command, holding the resources it uses until replay
+pub(crate) enum ComputeCommand {
+    SetPipeline(Arc<wgpu::ComputePipeline>),
+    SetBindGroup(u32, Arc<wgpu::BindGroup>, Vec<u32>),
+    Dispatch(u32, u32, u32),
+    DispatchIndirect(Arc<wgpu::Buffer>, u64),
+    PushDebugGroup(String),
+    PopDebugGroup,
+    InsertDebugMarker(String),
+}
 
 /// Compute pass encoder following WebGPU spec
 /// Records commands for compute shader execution
+///
+/// Commands are recorded here and encoded into the parent command encoder on
+/// `end()`, so the pass never outlives or aliases the encoder.
 #[napi]
 pub struct GpuComputePassEncoder {
-    // Store as erased pointer to avoid lifetime issues
-    pub(crate) pass: Option<*mut ()>,
+    pub(crate) encoder: Arc<Mutex<crate::device::EncoderState>>,
+    pub(crate) label: Option<String>,
+    /// `None` once ended
+    pub(crate) commands: Option<Vec<ComputeCommand>>,
+    /// Layouts of the pipeline and bind groups set, to explain a failed `end()`
+    pub(crate) bindings: crate::layout_check::BindingTracker,
+    pub(crate) errors: crate::error::ErrorSink,
+}
+
+impl GpuComputePassEncoder {
+    fn record(&mut self, command: ComputeCommand) -> Result<()> {
+        match self.commands.as_mut() {
+            Some(commands) => {
+                commands.push(command);
+                Ok(())
+            }
+            None => Err(Error::from_reason("Compute pass already ended")),
+        }
+    }
+
+    pub(crate) fn record_all(&mut self, commands: Vec<ComputeCommand>) -> Result<()> {
+        match self.commands.as_mut() {
+            Some(recorded) => {
+                recorded.extend(commands);
+                Ok(())
+            }
+            None => Err(Error::from_reason("Compute pass already ended")),
+        }
+    }
 }
 
 #[napi]
 impl GpuComputePassEncoder {
     /// Set the pipeline for this compute pass (WebGPU standard method)
-    #[napi(js_name = "setPipeline")]
+    #[napi(catch_unwind, js_name = "setPipeline")]
     pub fn set_pipeline(&mut self, pipeline: &crate::GpuComputePipeline) -> Result<()> {
-        if let Some(pass_ptr) = self.pass {
-            unsafe {
-                let pass = &mut *(pass_ptr as *mut wgpu::ComputePass<'_>);
-                pass.set_pipeline(&pipeline.pipeline);
-            }
-            Ok(())
-        } else {
-            Err(Error::from_reason("Compute pass already ended"))
-        }
+        self.record(ComputeCommand::SetPipeline(pipeline.pipeline.clone()))?;
+        self.bindings.set_pipeline(pipeline.bind_group_layouts.clone());
+        Ok(())
     }
 
     /// Set a bind group for this compute pass (WebGPU standard method)
-    #[napi(js_name = "setBindGroup")]
+    #[napi(catch_unwind, js_name = "setBindGroup")]
     pub fn set_bind_group(
         &mut self,
         index: u32,
         bind_group: &crate::GpuBindGroup,
         dynamic_offsets: Option<Vec<u32>>,
     ) -> Result<()> {
-        if let Some(pass_ptr) = self.pass {
-            unsafe {
-                let pass = &mut *(pass_ptr as *mut wgpu::ComputePass<'_>);
-                let offsets = dynamic_offsets.unwrap_or_default();
-                pass.set_bind_group(index, &bind_group.bind_group, &offsets);
-            }
-            Ok(())
-        } else {
-            Err(Error::from_reason("Compute pass already ended"))
-        }
+        self.record(ComputeCommand::SetBindGroup(
+            index,
+            bind_group.bind_group.clone(),
+            dynamic_offsets.unwrap_or_default(),
+        ))?;
+        self.bindings.set_bind_group(index, bind_group.layout_entries.clone());
+        Ok(())
     }
 
     /// Dispatch work to the compute shader (WebGPU standard method)
-    #[napi(js_name = "dispatchWorkgroups")]
+    #[napi(catch_unwind, js_name = "dispatchWorkgroups")]
     pub fn dispatch_workgroups(
         &mut self,
         workgroup_count_x: u32,
         workgroup_count_y: Option<u32>,
         workgroup_count_z: Option<u32>,
     ) -> Result<()> {
-        if let Some(pass_ptr) = self.pass {
-            unsafe {
-                let pass = &mut *(pass_ptr as *mut wgpu::ComputePass<'_>);
-                pass.dispatch_workgroups(
-                    workgroup_count_x,
-                    workgroup_count_y.unwrap_or(1),
-                    workgroup_count_z.unwrap_or(1),
-                );
+        self.record(ComputeCommand::Dispatch(
+            workgroup_count_x,
+            workgroup_count_y.unwrap_or(1),
+            workgroup_count_z.unwrap_or(1),
+        ))?;
+        self.bindings.check("dispatch");
+        Ok(())
+    }
+
+    /// Dispatch enough workgroups to cover `x * y * z` invocations (extension)
+    ///
+    /// `workgroup_size` is the shader's `@workgroup_size`, as a number or an
+    /// `[x, y, z]` array (missing dimensions are 1). Each workgroup count is
+    /// rounded up, so the shader must bounds-check invocations past the end.
+    #[napi(
+        catch_unwind,
+        js_name = "dispatchThreads",
+        ts_args_type = "x: number, y: number | undefined | null, z: number | undefined | null, workgroupSize: number | number[]"
+    )]
+    pub fn dispatch_threads(
+        &mut self,
+        x: u32,
+        y: Option<u32>,
+        z: Option<u32>,
+        workgroup_size: Either<u32, Vec<u32>>,
+    ) -> Result<()> {
+        let size = match workgroup_size {
+            Either::A(size) => [size, 1, 1],
+            Either::B(size) if (1..=3).contains(&size.len()) => [
+                size[0],
+                size.get(1).copied().unwrap_or(1),
+                size.get(2).copied().unwrap_or(1),
+            ],
+            Either::B(size) => {
+                return Err(Error::from_reason(format!(
+                    "workgroupSize must have 1 to 3 dimensions, got {}",
+                    size.len()
+                )))
             }
-            Ok(())
-        } else {
-            Err(Error::from_reason("Compute pass already ended"))
+        };
+        if size.contains(&0) {
+            return Err(Error::from_reason(format!(
+                "workgroupSize dimensions must be non-zero, got [{}, {}, {}]",
+                size[0], size[1], size[2]
+            )));
         }
+
+        self.dispatch_workgroups(
+            x.div_ceil(size[0]),
+            Some(y.unwrap_or(1).div_ceil(size[1])),
+            Some(z.unwrap_or(1).div_ceil(size[2])),
+        )
     }
 
     /// Dispatch work using parameters from a buffer (WebGPU standard method)
-    #[napi(js_name = "dispatchWorkgroupsIndirect")]
+    #[napi(catch_unwind, js_name = "dispatchWorkgroupsIndirect")]
     pub fn dispatch_workgroups_indirect(
         &mut self,
         indirect_buffer: &crate::GpuBuffer,
         indirect_offset: f64,
     ) -> Result<()> {
-        if let Some(pass_ptr) = self.pass {
-            unsafe {
-                let pass = &mut *(pass_ptr as *mut wgpu::ComputePass<'_>);
-                pass.dispatch_workgroups_indirect(&indirect_buffer.buffer, indirect_offset as u64);
-            }
-            Ok(())
-        } else {
-            Err(Error::from_reason("Compute pass already ended"))
-        }
+        self.record(ComputeCommand::DispatchIndirect(
+            indirect_buffer.buffer.clone(),
+            indirect_offset as u64,
+        ))?;
+        self.bindings.check("dispatch");
+        Ok(())
     }
 
     /// End the compute pass (WebGPU standard method)
     /// After calling this, the pass encoder can no longer be used
-    #[napi]
-    pub fn end(&mut self) {
-        // Take the pass pointer and drop it properly
-        if let Some(pass_ptr) = self.pass.take() {
-            unsafe {
-                // Cast back to the correct type and reconstruct the Box to drop it
-                let _ = Box::from_raw(pass_ptr as *mut wgpu::ComputePass<'static>);
+    #[napi(catch_unwind)]
+    pub fn end(&mut self) -> Result<()> {
+        let Some(commands) = self.commands.take() else {
+            return Ok(());
+        };
+        let mut state = crate::device::lock_encoder(&self.encoder);
+        state.pass_open = false;
+        let Some(encoder) = state.encoder.as_mut() else {
+            return Err(Error::from_reason("Command encoder already finished"));
+        };
+
+        // Recorded commands are validated when the pass is dropped
+        let bindings = &self.bindings;
+        self.errors.check_diagnosed("end", String::new, || bindings.diagnosis(), || {
+            let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: self.label.as_deref(),
+                timestamp_writes: None,
+            });
+            for command in &commands {
+                match command {
+                    ComputeCommand::SetPipeline(pipeline) => pass.set_pipeline(pipeline),
+                    ComputeCommand::SetBindGroup(index, bind_group, offsets) => {
+                        pass.set_bind_group(*index, bind_group, offsets)
+                    }
+                    ComputeCommand::Dispatch(x, y, z) => pass.dispatch_workgroups(*x, *y, *z),
+                    ComputeCommand::DispatchIndirect(buffer, offset) => {
+                        pass.dispatch_workgroups_indirect(buffer, *offset)
+                    }
+                    ComputeCommand::PushDebugGroup(label) => pass.push_debug_group(label),
+                    ComputeCommand::PopDebugGroup => pass.pop_debug_group(),
+                    ComputeCommand::InsertDebugMarker(label) => pass.insert_debug_marker(label),
+                }
             }
-        }
+        })
     }
 
     /// Push a debug group (WebGPU standard method)
-    #[napi(js_name = "pushDebugGroup")]
+    #[napi(catch_unwind, js_name = "pushDebugGroup")]
     pub fn push_debug_group(&mut self, label: String) -> Result<()> {
-        if let Some(pass_ptr) = self.pass {
-            unsafe {
-                let pass = &mut *(pass_ptr as *mut wgpu::ComputePass<'_>);
-                pass.push_debug_group(&label);
-            }
-            Ok(())
-        } else {
-            Err(Error::from_reason("Compute pass already ended"))
-        }
+        self.record(ComputeCommand::PushDebugGroup(label))
     }
 
     /// Pop a debug group (WebGPU standard method)
-    #[napi(js_name = "popDebugGroup")]
+    #[napi(catch_unwind, js_name = "popDebugGroup")]
     pub fn pop_debug_group(&mut self) -> Result<()> {
-        if let Some(pass_ptr) = self.pass {
-            unsafe {
-                let pass = &mut *(pass_ptr as *mut wgpu::ComputePass<'_>);
-                pass.pop_debug_group();
-            }
-            Ok(())
-        } else {
-            Err(Error::from_reason("Compute pass already ended"))
-        }
+        self.record(ComputeCommand::PopDebugGroup)
     }
 
     /// Insert a debug marker (WebGPU standard method)
-    #[napi(js_name = "insertDebugMarker")]
+    #[napi(catch_unwind, js_name = "insertDebugMarker")]
     pub fn insert_debug_marker(&mut self, label: String) -> Result<()> {
-        if let Some(pass_ptr) = self.pass {
-            unsafe {
-                let pass = &mut *(pass_ptr as *mut wgpu::ComputePass<'_>);
-                pass.insert_debug_marker(&label);
-            }
-            Ok(())
-        } else {
-            Err(Error::from_reason("Compute pass already ended"))
-        }
-    }
-}
-
-// Implement Drop to ensure the pass is properly cleaned up if not manually ended
-impl Drop for GpuComputePassEncoder {
-    fn drop(&mut self) {
-        if let Some(pass_ptr) = self.pass.take() {
-            unsafe {
-                let _ = Box::from_raw(pass_ptr as *mut wgpu::ComputePass<'static>);
-            }
-        }
+        self.record(ComputeCommand::InsertDebugMarker(label))
     }
 }
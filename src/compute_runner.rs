@@ -0,0 +1,157 @@
+//! Repeated dispatches of one compute pipeline
+//!
+//! Tight simulation and optimization loops write a few parameters and
+//! dispatch the same pipeline with the same bind groups every iteration.
+//! Through the standard API that is a writeBuffer, an encoder, a pass with
+//! its pipeline, bind groups and dispatch, and a submit, each an N-API
+//! crossing. A runner keeps the pipeline, bind groups and parameter buffer
+//! and does all of it in one `run()` call.
+
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use std::sync::Arc;
+
+/// Compute runner descriptor
+#[napi(object)]
+pub struct ComputeRunnerDescriptor {
+    pub label: Option<String>,
+    /// Byte offset of the parameters in the parameter buffer (default 0)
+    pub params_offset: Option<i64>,
+}
+
+/// Compute pipeline with its bind groups, dispatched by `run()`
+#[napi]
+pub struct GpuComputeRunner {
+    device: Arc<wgpu::Device>,
+    queue: Arc<wgpu::Queue>,
+    poller: crate::poller::DevicePoller,
+    errors: crate::error::ErrorSink,
+    label: Option<String>,
+    pipeline: Arc<wgpu::ComputePipeline>,
+    /// Set at indices 0, 1, ... before each dispatch
+    bind_groups: Vec<Arc<wgpu::BindGroup>>,
+    /// Buffer `run()` writes the parameters into, with its label
+    params: Option<(Arc<wgpu::Buffer>, Option<String>)>,
+    params_offset: u64,
+}
+
+impl GpuComputeRunner {
+    pub(crate) fn new(
+        device: &crate::GpuDevice,
+        descriptor: Option<ComputeRunnerDescriptor>,
+        pipeline: &crate::GpuComputePipeline,
+        bind_groups: Vec<&crate::GpuBindGroup>,
+        params: Option<&crate::GpuBuffer>,
+    ) -> Result<Self> {
+        let (label, params_offset) = descriptor.map_or((None, 0), |d| (d.label, d.params_offset.unwrap_or(0)));
+        if params_offset < 0 || params_offset % 4 != 0 {
+            return Err(Error::from_reason(format!(
+                "createComputeRunner paramsOffset must be a non-negative multiple of 4, got {}",
+                params_offset
+            )));
+        }
+        if let Some(params) = params {
+            if !params.buffer.usage().contains(wgpu::BufferUsages::COPY_DST) {
+                return Err(Error::from_reason(format!(
+                    "createComputeRunner params buffer {} needs COPY_DST usage",
+                    crate::error::describe_label(params.label.as_deref())
+                )));
+            }
+        }
+
+        Ok(Self {
+            device: device.device.clone(),
+            queue: device.queue_internal.clone(),
+            poller: device.poller.clone(),
+            errors: device.errors.clone(),
+            label,
+            pipeline: pipeline.pipeline.clone(),
+            bind_groups: bind_groups.into_iter().map(|group| group.bind_group.clone()).collect(),
+            params: params.map(|params| (params.buffer.clone(), params.label.clone())),
+            params_offset: params_offset as u64,
+        })
+    }
+
+    fn write_params(&self, data: &[u8]) -> Result<()> {
+        let Some((buffer, label)) = &self.params else {
+            return Err(Error::from_reason("run: the runner was created without a params buffer"));
+        };
+        let end = self.params_offset + data.len() as u64;
+        if data.len() % 4 != 0 || end > buffer.size() {
+            return Err(Error::from_reason(format!(
+                "run: {} bytes of params at offset {} don't fit buffer {} ({} bytes) in multiples of 4",
+                data.len(),
+                self.params_offset,
+                crate::error::describe_label(label.as_deref()),
+                buffer.size()
+            )));
+        }
+        self.queue.write_buffer(buffer, self.params_offset, data);
+        Ok(())
+    }
+}
+
+#[napi]
+impl GpuComputeRunner {
+    /// Write `params` into the parameter buffer and dispatch `workgroups`
+    ///
+    /// `workgroups` is a count or `[x, y?, z?]`. The write and the dispatch
+    /// are submitted immediately, in this order, so each run sees its own
+    /// parameters. Without `params` the buffer is left as it is.
+    #[napi(catch_unwind, ts_args_type = "params: Buffer | undefined | null, workgroups: number | number[]")]
+    pub fn run(&self, params: Option<Buffer>, workgroups: Either<u32, Vec<u32>>) -> Result<()> {
+        self.errors.ensure_usable("run")?;
+        let [x, y, z] = match workgroups {
+            Either::A(x) => [x, 1, 1],
+            Either::B(counts) if (1..=3).contains(&counts.len()) => {
+                [counts[0], counts.get(1).copied().unwrap_or(1), counts.get(2).copied().unwrap_or(1)]
+            }
+            Either::B(counts) => {
+                return Err(Error::from_reason(format!(
+                    "run workgroups must have 1 to 3 counts, got {}",
+                    counts.len()
+                )))
+            }
+        };
+        if let Some(params) = &params {
+            self.write_params(params)?;
+        }
+
+        self.errors.check(
+            "run",
+            || {
+                format!(
+                    "label: {}, workgroups: [{}, {}, {}]",
+                    crate::error::describe_label(self.label.as_deref()),
+                    x,
+                    y,
+                    z
+                )
+            },
+            || {
+                let mut encoder = self.device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                    label: self.label.as_deref(),
+                });
+                {
+                    let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                        label: self.label.as_deref(),
+                        timestamp_writes: None,
+                    });
+                    pass.set_pipeline(&self.pipeline);
+                    for (index, bind_group) in self.bind_groups.iter().enumerate() {
+                        pass.set_bind_group(index as u32, bind_group, &[]);
+                    }
+                    pass.dispatch_workgroups(x, y, z);
+                }
+                self.queue.submit(Some(encoder.finish()));
+            },
+        )?;
+
+        if self.poller.auto_maintain() {
+            // Released by the poll thread itself once this submission has completed
+            let polling = self.poller.track();
+            self.queue.on_submitted_work_done(move || drop(polling));
+        }
+        Ok(())
+    }
+}
@@ -1,4 +1,5 @@
 use napi_derive::napi;
+use std::collections::HashMap;
 
 /// Buffer usage flags object
 #[napi(object)]
@@ -16,7 +17,7 @@ pub struct BufferUsage {
 }
 
 /// Get buffer usage constants
-#[napi]
+#[napi(catch_unwind)]
 pub fn buffer_usage() -> BufferUsage {
     BufferUsage {
         copy_src: 0x0004,
@@ -40,7 +41,7 @@ pub struct MapMode {
 }
 
 /// Get map mode constants
-#[napi]
+#[napi(catch_unwind)]
 pub fn map_mode() -> MapMode {
     MapMode {
         read: 1,
@@ -59,7 +60,7 @@ pub struct TextureUsage {
 }
 
 /// Get texture usage constants
-#[napi]
+#[napi(catch_unwind)]
 pub fn texture_usage() -> TextureUsage {
     TextureUsage {
         copy_src: 0x01,
@@ -69,3 +70,87 @@ pub fn texture_usage() -> TextureUsage {
         render_attachment: 0x10,
     }
 }
+
+/// Shader stage visibility flags object
+#[napi(object)]
+pub struct ShaderStage {
+    pub vertex: u32,
+    pub fragment: u32,
+    pub compute: u32,
+}
+
+/// Get shader stage constants
+#[napi(catch_unwind)]
+pub fn shader_stage() -> ShaderStage {
+    ShaderStage {
+        vertex: 0x1,
+        fragment: 0x2,
+        compute: 0x4,
+    }
+}
+
+/// Command stream opcodes object
+#[napi(object)]
+pub struct CommandOpcode {
+    pub set_pipeline: u32,
+    pub set_bind_group: u32,
+    pub set_vertex_buffer: u32,
+    pub set_index_buffer: u32,
+    pub draw: u32,
+    pub draw_indexed: u32,
+    pub draw_indirect: u32,
+    pub draw_indexed_indirect: u32,
+    pub dispatch: u32,
+    pub dispatch_indirect: u32,
+    /// Buffer size argument for the rest of the buffer
+    pub whole_size: u32,
+}
+
+/// Get command stream opcodes for `executeCommandStream()`
+#[napi(catch_unwind)]
+pub fn command_opcode() -> CommandOpcode {
+    use crate::command_stream::*;
+    CommandOpcode {
+        set_pipeline: SET_PIPELINE,
+        set_bind_group: SET_BIND_GROUP,
+        set_vertex_buffer: SET_VERTEX_BUFFER,
+        set_index_buffer: SET_INDEX_BUFFER,
+        draw: DRAW,
+        draw_indexed: DRAW_INDEXED,
+        draw_indirect: DRAW_INDIRECT,
+        draw_indexed_indirect: DRAW_INDEXED_INDIRECT,
+        dispatch: DISPATCH,
+        dispatch_indirect: DISPATCH_INDIRECT,
+        whole_size: WHOLE_SIZE,
+    }
+}
+
+/// Constant name of an enum value: "rgba8unorm-srgb" -> "RGBA8UNORM_SRGB",
+/// and dimensions like "2d-array" -> "D2_ARRAY"
+fn constant_name(name: &str) -> String {
+    let name = if name.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("D{}", name.replacen('d', "", 1))
+    } else {
+        name.to_string()
+    };
+    name.to_uppercase().replace('-', "_")
+}
+
+/// Get numeric descriptor enum constants, keyed by constant object name
+///
+/// Descriptor fields accept these in place of the WebGPU strings, e.g.
+/// `GPUTextureFormat.RGBA8UNORM` for 'rgba8unorm', and skip string matching.
+#[napi(catch_unwind)]
+pub fn descriptor_enums() -> HashMap<String, HashMap<String, u32>> {
+    crate::parse::enum_tables()
+        .into_iter()
+        .map(|(object, names)| {
+            let values = names
+                .iter()
+                .enumerate()
+                .map(|(index, name)| (constant_name(name), index as u32))
+                .collect();
+            (object.to_string(), values)
+        })
+        .collect()
+}
@@ -1,20 +1,67 @@
+use napi::bindgen_prelude::Either;
 use napi_derive::napi;
 
+/// Device descriptor following WebGPU spec
+#[napi(object)]
+#[derive(Default)]
+pub struct DeviceDescriptor {
+    pub label: Option<String>,
+    /// Feature names as reported by `adapter.features`
+    #[napi(js_name = "requiredFeatures")]
+    pub required_features: Option<Vec<String>>,
+    /// Limit values by WebGPU name, or a preset name: "default" (the WebGPU
+    /// defaults), "downlevel" or "adapterMaximum"
+    #[napi(
+        js_name = "requiredLimits",
+        ts_type = "Record<string, number> | 'default' | 'downlevel' | 'adapterMaximum'"
+    )]
+    pub required_limits: Option<Either<std::collections::HashMap<String, f64>, String>>,
+    /// Preset that object `requiredLimits` apply on top of (extension)
+    #[napi(js_name = "limitsPreset", ts_type = "'default' | 'downlevel' | 'adapterMaximum'")]
+    pub limits_preset: Option<String>,
+}
+
+/// Options for `device.enableFaultInjection()` (extension)
+#[napi(object)]
+pub struct FaultInjectionOptions {
+    /// Fail every nth buffer, texture or query set creation as out of memory
+    #[napi(js_name = "oomEveryN")]
+    pub oom_every_n: Option<u32>,
+    /// Fail the next create call with a validation error
+    pub fail_next_create: Option<bool>,
+}
+
 /// Buffer descriptor following WebGPU spec
 #[napi(object)]
 pub struct BufferDescriptor {
     pub label: Option<String>,
     pub size: i64,
-    pub usage: u32,
+    /// GPUBufferUsage bit mask or flag names, e.g. ["COPY_DST", "STORAGE"]
+    pub usage: Either<u32, Vec<String>>,
     #[napi(js_name = "mappedAtCreation")]
     pub mapped_at_creation: Option<bool>,
 }
 
+/// Descriptor for creating a buffer from initial contents
+/// Note: the data is passed as a separate parameter
+#[napi(object)]
+pub struct BufferInitDescriptor {
+    pub label: Option<String>,
+    /// GPUBufferUsage bit mask or flag names
+    pub usage: Either<u32, Vec<String>>,
+}
+
 /// Shader module descriptor following WebGPU spec
+/// Note: the include resolver callback is passed as separate parameter due to napi-rs limitations
 #[napi(object)]
 pub struct ShaderModuleDescriptor {
     pub label: Option<String>,
     pub code: String,
+    /// Preprocessor defines (extension)
+    pub defines: Option<std::collections::HashMap<String, String>>,
+    /// Directories searched for `#include` files (extension)
+    #[napi(js_name = "includePaths")]
+    pub include_paths: Option<Vec<String>>,
 }
 
 /// Pipeline layout descriptor following WebGPU spec
@@ -44,7 +91,7 @@ pub struct CommandEncoderDescriptor {
 pub struct QuerySetDescriptor {
     pub label: Option<String>,
     #[napi(js_name = "type")]
-    pub query_type: String, // "timestamp" or "occlusion"
+    pub query_type: Either<String, u32>, // "timestamp" or "occlusion"
     pub count: u32,
 }
 
@@ -73,12 +120,16 @@ pub struct BindGroupLayoutEntry {
     pub texture: Option<TextureBindingLayout>,
     #[napi(js_name = "storageTexture")]
     pub storage_texture: Option<StorageTextureBindingLayout>,
+    /// Makes the binding an array of this many resources (extension); buffer
+    /// arrays need the "wgpu-buffer-binding-array" feature, texture and
+    /// sampler arrays "wgpu-texture-binding-array"
+    pub count: Option<u32>,
 }
 
 #[napi(object)]
 pub struct BufferBindingLayout {
     #[napi(js_name = "type")]
-    pub ty: Option<String>, // "uniform", "storage", "read-only-storage"
+    pub ty: Option<Either<String, u32>>, // "uniform", "storage", "read-only-storage"
     #[napi(js_name = "hasDynamicOffset")]
     pub has_dynamic_offset: Option<bool>,
     #[napi(js_name = "minBindingSize")]
@@ -88,24 +139,24 @@ pub struct BufferBindingLayout {
 #[napi(object)]
 pub struct SamplerBindingLayout {
     #[napi(js_name = "type")]
-    pub ty: Option<String>, // "filtering", "non-filtering", "comparison"
+    pub ty: Option<Either<String, u32>>, // "filtering", "non-filtering", "comparison"
 }
 
 #[napi(object)]
 pub struct TextureBindingLayout {
     #[napi(js_name = "sampleType")]
-    pub sample_type: Option<String>, // "float", "unfilterable-float", "depth", "sint", "uint"
+    pub sample_type: Option<Either<String, u32>>, // "float", "unfilterable-float", "depth", "sint", "uint"
     #[napi(js_name = "viewDimension")]
-    pub view_dimension: Option<String>, // "1d", "2d", "2d-array", "cube", "cube-array", "3d"
+    pub view_dimension: Option<Either<String, u32>>, // "1d", "2d", "2d-array", "cube", "cube-array", "3d"
     pub multisampled: Option<bool>,
 }
 
 #[napi(object)]
 pub struct StorageTextureBindingLayout {
-    pub access: Option<String>, // "write-only", "read-only", "read-write"
-    pub format: String,
+    pub access: Option<Either<String, u32>>, // "write-only", "read-only", "read-write"
+    pub format: Either<String, u32>,
     #[napi(js_name = "viewDimension")]
-    pub view_dimension: Option<String>,
+    pub view_dimension: Option<Either<String, u32>>,
 }
 
 /// Render pipeline descriptor following WebGPU spec
@@ -135,13 +186,13 @@ pub struct VertexBufferLayout {
     #[napi(js_name = "arrayStride")]
     pub array_stride: i64,
     #[napi(js_name = "stepMode")]
-    pub step_mode: Option<String>, // "vertex" or "instance"
+    pub step_mode: Option<Either<String, u32>>, // "vertex" or "instance"
     pub attributes: Vec<VertexAttribute>,
 }
 
 #[napi(object)]
 pub struct VertexAttribute {
-    pub format: String,
+    pub format: Either<String, u32>,
     pub offset: i64,
     #[napi(js_name = "shaderLocation")]
     pub shader_location: u32,
@@ -149,22 +200,22 @@ pub struct VertexAttribute {
 
 #[napi(object)]
 pub struct PrimitiveState {
-    pub topology: Option<String>, // "point-list", "line-list", "line-strip", "triangle-list", "triangle-strip"
+    pub topology: Option<Either<String, u32>>, // "point-list", "line-list", "line-strip", "triangle-list", "triangle-strip"
     #[napi(js_name = "stripIndexFormat")]
-    pub strip_index_format: Option<String>, // "uint16" or "uint32"
+    pub strip_index_format: Option<Either<String, u32>>, // "uint16" or "uint32"
     #[napi(js_name = "frontFace")]
-    pub front_face: Option<String>, // "ccw" or "cw"
+    pub front_face: Option<Either<String, u32>>, // "ccw" or "cw"
     #[napi(js_name = "cullMode")]
-    pub cull_mode: Option<String>, // "none", "front", "back"
+    pub cull_mode: Option<Either<String, u32>>, // "none", "front", "back"
 }
 
 #[napi(object)]
 pub struct DepthStencilState {
-    pub format: String,
+    pub format: Either<String, u32>,
     #[napi(js_name = "depthWriteEnabled")]
     pub depth_write_enabled: Option<bool>,
     #[napi(js_name = "depthCompare")]
-    pub depth_compare: Option<String>, // "never", "less", "equal", "less-equal", "greater", "not-equal", "greater-equal", "always"
+    pub depth_compare: Option<Either<String, u32>>, // "never", "less", "equal", "less-equal", "greater", "not-equal", "greater-equal", "always"
     #[napi(js_name = "stencilFront")]
     pub stencil_front: Option<StencilFaceState>,
     #[napi(js_name = "stencilBack")]
@@ -183,13 +234,13 @@ pub struct DepthStencilState {
 
 #[napi(object)]
 pub struct StencilFaceState {
-    pub compare: Option<String>,
+    pub compare: Option<Either<String, u32>>,
     #[napi(js_name = "failOp")]
-    pub fail_op: Option<String>,
+    pub fail_op: Option<Either<String, u32>>,
     #[napi(js_name = "depthFailOp")]
-    pub depth_fail_op: Option<String>,
+    pub depth_fail_op: Option<Either<String, u32>>,
     #[napi(js_name = "passOp")]
-    pub pass_op: Option<String>,
+    pub pass_op: Option<Either<String, u32>>,
 }
 
 #[napi(object)]
@@ -205,12 +256,13 @@ pub struct FragmentState {
     // module removed - passed as separate parameter
     #[napi(js_name = "entryPoint")]
     pub entry_point: String,
-    pub targets: Vec<ColorTargetState>,
+    /// `null` entries leave that color location unused
+    pub targets: Vec<Option<ColorTargetState>>,
 }
 
 #[napi(object)]
 pub struct ColorTargetState {
-    pub format: String,
+    pub format: Either<String, u32>,
     pub blend: Option<BlendState>,
     #[napi(js_name = "writeMask")]
     pub write_mask: Option<u32>,
@@ -225,8 +277,100 @@ pub struct BlendState {
 #[napi(object)]
 pub struct BlendComponent {
     #[napi(js_name = "srcFactor")]
-    pub src_factor: String,
+    pub src_factor: Either<String, u32>,
     #[napi(js_name = "dstFactor")]
-    pub dst_factor: String,
-    pub operation: String,
+    pub dst_factor: Either<String, u32>,
+    pub operation: Either<String, u32>,
+}
+
+/// Mip level and origin of a texture copy (texture passed separately)
+#[napi(object)]
+pub struct TextureCopyLocation {
+    #[napi(js_name = "mipLevel")]
+    pub mip_level: Option<u32>,
+    pub origin: Option<Origin3D>,
+    /// "all" (default), "depth-only" or "stencil-only"
+    pub aspect: Option<Either<String, u32>>,
+}
+
+/// Mips, layers and aspect of a texture cleared by `clearTexture`
+#[napi(object)]
+pub struct ImageSubresourceRange {
+    /// "all" (default), "depth-only" or "stencil-only"
+    pub aspect: Option<Either<String, u32>>,
+    pub base_mip_level: Option<u32>,
+    /// Defaults to the remaining mip levels
+    pub mip_level_count: Option<u32>,
+    pub base_array_layer: Option<u32>,
+    /// Defaults to the remaining array layers
+    pub array_layer_count: Option<u32>,
+}
+
+impl ImageSubresourceRange {
+    pub(crate) fn to_wgpu(&self) -> wgpu::ImageSubresourceRange {
+        wgpu::ImageSubresourceRange {
+            aspect: crate::parse::parse_texture_aspect(self.aspect.as_ref()),
+            base_mip_level: self.base_mip_level.unwrap_or(0),
+            mip_level_count: self.mip_level_count,
+            base_array_layer: self.base_array_layer.unwrap_or(0),
+            array_layer_count: self.array_layer_count,
+        }
+    }
+}
+
+/// Layout of texel data in a `writeTexture` source
+#[napi(object)]
+pub struct TextureDataLayout {
+    pub offset: Option<i64>,
+    #[napi(js_name = "bytesPerRow")]
+    pub bytes_per_row: Option<u32>,
+    #[napi(js_name = "rowsPerImage")]
+    pub rows_per_image: Option<u32>,
+}
+
+#[napi(object)]
+pub struct Origin3D {
+    pub x: Option<u32>,
+    pub y: Option<u32>,
+    pub z: Option<u32>,
+}
+
+#[napi(object)]
+pub struct Origin2D {
+    pub x: Option<u32>,
+    pub y: Option<u32>,
+}
+
+#[napi(object)]
+pub struct Extent3D {
+    pub width: u32,
+    pub height: Option<u32>,
+    #[napi(js_name = "depthOrArrayLayers")]
+    pub depth_or_array_layers: Option<u32>,
+}
+
+impl TextureCopyLocation {
+    pub(crate) fn to_wgpu<'a>(&self, texture: &'a wgpu::Texture) -> wgpu::ImageCopyTexture<'a> {
+        let origin = self.origin.as_ref();
+        wgpu::ImageCopyTexture {
+            texture,
+            mip_level: self.mip_level.unwrap_or(0),
+            origin: wgpu::Origin3d {
+                x: origin.and_then(|o| o.x).unwrap_or(0),
+                y: origin.and_then(|o| o.y).unwrap_or(0),
+                z: origin.and_then(|o| o.z).unwrap_or(0),
+            },
+            aspect: crate::parse::parse_texture_aspect(self.aspect.as_ref()),
+        }
+    }
+}
+
+impl From<&Extent3D> for wgpu::Extent3d {
+    fn from(size: &Extent3D) -> Self {
+        wgpu::Extent3d {
+            width: size.width,
+            height: size.height.unwrap_or(1),
+            depth_or_array_layers: size.depth_or_array_layers.unwrap_or(1),
+        }
+    }
 }
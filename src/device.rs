@@ -1,27 +1,53 @@
 use napi::bindgen_prelude::*;
+use napi::JsFunction;
 use napi_derive::napi;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
+use wgpu::util::DeviceExt;
 
 #[napi]
+#[derive(Clone)]
 pub struct GpuDevice {
+    /// Adapter the device was requested from, for surface capability checks
+    pub(crate) adapter: Arc<wgpu::Adapter>,
     pub(crate) device: Arc<wgpu::Device>,
     pub(crate) queue_internal: Arc<wgpu::Queue>,
     features: crate::GpuSupportedFeatures,
     limits: crate::GpuSupportedLimits,
+    shader_cache: Arc<crate::shader_cache::ShaderModuleCache>,
+    pub(crate) blit: Arc<crate::blit::BlitPipelines>,
+    pub(crate) color: Arc<crate::color::ColorPipelines>,
+    msaa: Arc<crate::msaa::MultisampleTargets>,
+    pub(crate) poller: crate::poller::DevicePoller,
+    pub(crate) errors: crate::error::ErrorSink,
+    pub(crate) events: crate::events::DeviceEvents,
 }
 
 impl GpuDevice {
-    pub(crate) fn new(device: wgpu::Device, queue: wgpu::Queue) -> Self {
+    pub(crate) fn new(adapter: Arc<wgpu::Adapter>, device: wgpu::Device, queue: wgpu::Queue) -> Self {
         let features = crate::GpuSupportedFeatures {
             features: device.features(),
         };
         let limits = crate::GpuSupportedLimits::from_wgpu(&device.limits());
 
+        let device = Arc::new(device);
+        let poller = crate::poller::DevicePoller::spawn(&device);
+        let events = crate::events::DeviceEvents::default();
+        events.watch_device_lost(&device);
+        let errors = crate::error::ErrorSink::new(device.clone(), events.clone());
+
         Self {
-            device: Arc::new(device),
+            adapter,
+            device,
             queue_internal: Arc::new(queue),
             features,
             limits,
+            shader_cache: Default::default(),
+            blit: Default::default(),
+            color: Default::default(),
+            msaa: Default::default(),
+            poller,
+            errors,
+            events,
         }
     }
 }
@@ -29,13 +55,19 @@ impl GpuDevice {
 #[napi]
 impl GpuDevice {
     /// Get the queue for this device (WebGPU standard property)
-    #[napi(getter)]
+    #[napi(catch_unwind, getter)]
     pub fn queue(&self) -> crate::GpuQueue {
-        crate::GpuQueue::new(self.queue_internal.clone())
+        crate::GpuQueue::new(
+            self.queue_internal.clone(),
+            self.device.clone(),
+            self.color.clone(),
+            self.poller.clone(),
+            self.errors.clone(),
+        )
     }
 
     /// Get the supported features for this device (WebGPU standard property)
-    #[napi(getter)]
+    #[napi(catch_unwind, getter)]
     pub fn features(&self) -> crate::GpuSupportedFeatures {
         crate::GpuSupportedFeatures {
             features: self.features.features,
@@ -43,20 +75,28 @@ impl GpuDevice {
     }
 
     /// Get the supported limits for this device (WebGPU standard property)
-    #[napi(getter)]
+    #[napi(catch_unwind, getter)]
     pub fn limits(&self) -> crate::GpuSupportedLimits {
         self.limits.clone()
     }
 
     /// Get the label of this device (WebGPU standard property)
-    #[napi(getter)]
+    #[napi(catch_unwind, getter)]
     pub fn label(&self) -> Option<String> {
         None // wgpu doesn't expose device labels after creation
     }
 
+    /// Why the device was lost, or null while it's usable
+    ///
+    /// The `GpuDevice` wrapper resolves `device.lost` with this.
+    #[napi(catch_unwind, getter, js_name = "lostInfo")]
+    pub fn lost_info(&self) -> Option<crate::DeviceLostInfo> {
+        self.events.lost_info()
+    }
+
     /// Push an error scope for error handling (WebGPU standard method)
     /// NOTE: wgpu only supports "validation" and "out-of-memory" filters
-    #[napi(js_name = "pushErrorScope")]
+    #[napi(catch_unwind, js_name = "pushErrorScope")]
     pub fn push_error_scope(&self, filter: String) -> Result<()> {
         let filter = match filter.as_str() {
             "validation" => wgpu::ErrorFilter::Validation,
@@ -64,81 +104,323 @@ impl GpuDevice {
             "internal" => return Err(Error::from_reason("wgpu does not support 'internal' error filter")),
             _ => return Err(Error::from_reason(format!("Invalid error filter: {}", filter))),
         };
-        self.device.push_error_scope(filter);
+        self.errors.push_user_scope(filter);
         Ok(())
     }
 
     /// Pop an error scope and return any error (WebGPU standard method)
-    #[napi(js_name = "popErrorScope")]
-    pub async fn pop_error_scope(&self) -> Result<Option<String>> {
-        match self.device.pop_error_scope().await {
-            Some(error) => Ok(Some(error.to_string())),
-            None => Ok(None),
-        }
+    ///
+    /// Pops on the calling thread, so it can't interleave with the private
+    /// scopes that `check()` pushes around API calls.
+    #[napi(catch_unwind, js_name = "popErrorScope")]
+    pub fn pop_error_scope(&self) -> Result<Option<crate::error::CapturedError>> {
+        self.errors.pop_user_scope()
+    }
+
+    /// Make create calls fail on demand, for testing error handling (extension)
+    ///
+    /// Injected failures are thrown like real ones without creating
+    /// anything, and out-of-memory ones also emit 'oomWarning'. While an
+    /// error scope is open, the call goes ahead and the failure is left to
+    /// the next `popErrorScope()` instead. Each call replaces the previous
+    /// options and restarts the allocation count.
+    #[napi(catch_unwind, js_name = "enableFaultInjection")]
+    pub fn enable_fault_injection(&self, options: crate::FaultInjectionOptions) {
+        self.errors
+            .inject_faults(options.oom_every_n.unwrap_or(0), options.fail_next_create.unwrap_or(false));
+    }
+
+    /// Stop injecting failures (extension)
+    #[napi(catch_unwind, js_name = "disableFaultInjection")]
+    pub fn disable_fault_injection(&self) {
+        self.errors.inject_faults(0, false);
     }
 
     /// Create a GPU buffer
-    #[napi(js_name = "createBuffer")]
-    pub fn create_buffer(&self, descriptor: crate::BufferDescriptor) -> crate::GpuBuffer {
+    #[napi(catch_unwind, js_name = "createBuffer")]
+    pub fn create_buffer(&self, mut env: Env, descriptor: crate::BufferDescriptor) -> Result<crate::GpuBuffer> {
         let mapped_at_creation = descriptor.mapped_at_creation.unwrap_or(false);
-        let buffer = self.device.create_buffer(&wgpu::BufferDescriptor {
-            label: descriptor.label.as_deref(),
-            size: descriptor.size as u64,
-            usage: wgpu::BufferUsages::from_bits_truncate(descriptor.usage),
-            mapped_at_creation,
-        });
+        let usage = crate::parse::parse_buffer_usage(&descriptor.usage)?;
+        let buffer = self.errors.check_diagnosed(
+            "createBuffer",
+            || {
+                format!(
+                    "label: {}, size: {}, usage: {:#x}, mappedAtCreation: {}",
+                    crate::error::describe_label(descriptor.label.as_deref()),
+                    descriptor.size,
+                    usage.bits(),
+                    mapped_at_creation
+                )
+            },
+            || crate::buffer::map_usage_error(usage, self.device.features()),
+            || {
+                self.device.create_buffer(&wgpu::BufferDescriptor {
+                    label: descriptor.label.as_deref(),
+                    size: descriptor.size as u64,
+                    usage,
+                    mapped_at_creation,
+                })
+            },
+        )?;
 
-        if mapped_at_creation {
-            crate::GpuBuffer::new_mapped(buffer, self.device.clone(), self.queue_internal.clone())
+        let buffer = if mapped_at_creation {
+            crate::GpuBuffer::new_mapped(buffer, descriptor.label, self.device.clone(), self.queue_internal.clone(), self.poller.clone())
         } else {
-            crate::GpuBuffer::new(buffer, self.device.clone(), self.queue_internal.clone())
-        }
+            crate::GpuBuffer::new(buffer, descriptor.label, self.device.clone(), self.queue_internal.clone(), self.poller.clone())
+        };
+        buffer.track_memory(&mut env)
+    }
+
+    /// Create a GPU buffer holding `data`
+    ///
+    /// The buffer is created mapped, filled and unmapped in one call. Its size
+    /// is the data length rounded up to a multiple of 4 bytes.
+    #[napi(catch_unwind, js_name = "createBufferInit")]
+    pub fn create_buffer_init(
+        &self,
+        mut env: Env,
+        descriptor: crate::BufferInitDescriptor,
+        data: Buffer,
+    ) -> Result<crate::GpuBuffer> {
+        let usage = crate::parse::parse_buffer_usage(&descriptor.usage)?;
+        let max_buffer_size = self.device.limits().max_buffer_size;
+        let invalid_reason = if data.len() as u64 > max_buffer_size {
+            Some(format!(
+                "Buffer size {} is greater than the maximum buffer size ({})",
+                data.len(),
+                max_buffer_size
+            ))
+        } else {
+            crate::buffer::map_usage_error(usage, self.device.features())
+        };
+        let invalid = invalid_reason.is_some();
+        let buffer = self.errors.check_diagnosed(
+            "createBufferInit",
+            || {
+                format!(
+                    "label: {}, size: {}, usage: {:#x}",
+                    crate::error::describe_label(descriptor.label.as_deref()),
+                    data.len(),
+                    usage.bits()
+                )
+            },
+            || invalid_reason,
+            || {
+                if invalid {
+                    // wgpu panics filling a buffer that failed validation
+                    // (bad usage, or over maxBufferSize), so create it
+                    // unmapped to report the same error
+                    self.device.create_buffer(&wgpu::BufferDescriptor {
+                        label: descriptor.label.as_deref(),
+                        size: (data.len() as u64).next_multiple_of(wgpu::COPY_BUFFER_ALIGNMENT),
+                        usage,
+                        mapped_at_creation: false,
+                    })
+                } else {
+                    self.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                        label: descriptor.label.as_deref(),
+                        contents: &data,
+                        usage,
+                    })
+                }
+            },
+        )?;
+
+        crate::GpuBuffer::new(buffer, descriptor.label, self.device.clone(), self.queue_internal.clone(), self.poller.clone())
+            .track_memory(&mut env)
     }
 
     /// Create a shader module
-    #[napi(js_name = "createShaderModule")]
-    pub fn create_shader_module(&self, descriptor: crate::ShaderModuleDescriptor) -> Result<GpuShaderModule> {
-        let shader = self.device.create_shader_module(wgpu::ShaderModuleDescriptor {
-            label: descriptor.label.as_deref(),
-            source: wgpu::ShaderSource::Wgsl(descriptor.code.into()),
+    ///
+    /// Source using `#include`/`#define`/`#ifdef` directives, or given
+    /// `defines`, `includePaths` or an include resolver, is preprocessed first
+    /// and checked with naga so errors point at the original files.
+    ///
+    /// When the shader module cache is enabled, identical WGSL source returns
+    /// the previously compiled module.
+    #[napi(catch_unwind, js_name = "createShaderModule")]
+    pub fn create_shader_module(
+        &self,
+        env: Env,
+        descriptor: crate::ShaderModuleDescriptor,
+        resolve_include: Option<JsFunction>,
+    ) -> Result<GpuShaderModule> {
+        let label = descriptor.label;
+        let (code, parsed) = if descriptor.defines.is_some()
+            || descriptor.include_paths.is_some()
+            || resolve_include.is_some()
+            || crate::preprocess::has_directives(&descriptor.code)
+        {
+            let resolver = resolve_include.map(|callback| crate::preprocess::js_include_resolver(env, callback));
+            let source = crate::preprocess::Preprocessor::new(
+                descriptor.defines,
+                descriptor.include_paths,
+                resolver.as_ref().map(|r| r as &crate::preprocess::IncludeResolver),
+            )
+            .run(label.as_deref().unwrap_or("<shader>"), &descriptor.code)?;
+            let parsed = source.check()?;
+            (source.code, Some(parsed))
+        } else {
+            (descriptor.code, None)
+        };
+
+        self.shader_cache.get_or_create(code, |code| {
+            let shader = self.errors.check(
+                "createShaderModule",
+                || format!("label: {}", crate::error::describe_label(label.as_deref())),
+                || {
+                    self.device.create_shader_module(wgpu::ShaderModuleDescriptor {
+                        label: label.as_deref(),
+                        source: wgpu::ShaderSource::Wgsl(code.into()),
+                    })
+                },
+            )?;
+            let reflection = match parsed {
+                Some(parsed) => crate::reflect::ShaderReflection::with_parsed(code.to_string(), parsed),
+                None => crate::reflect::ShaderReflection::new(code.to_string()),
+            };
+            Ok(GpuShaderModule {
+                shader: Arc::new(shader),
+                reflection: Arc::new(reflection),
+            })
+        })
+    }
+
+    /// Create a bind group layout matching a shader's bindings in `group`
+    ///
+    /// Binding types, texture dimensions/formats, minimum buffer sizes and
+    /// stage visibility are derived from the WGSL source via reflection.
+    #[napi(catch_unwind, js_name = "createBindGroupLayoutFromShader")]
+    pub fn create_bind_group_layout_from_shader(
+        &self,
+        module: &GpuShaderModule,
+        group: u32,
+        label: Option<String>,
+    ) -> Result<crate::GpuBindGroupLayout> {
+        self.errors.ensure_usable("createBindGroupLayoutFromShader")?;
+        let entries = module.reflection.bind_group_layout_entries(group, self.device.features())?;
+        let layout = self.device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: label.as_deref(),
+            entries: &entries,
         });
 
-        Ok(GpuShaderModule { shader })
+        Ok(crate::GpuBindGroupLayout::new(layout, Some(Arc::new(entries))))
+    }
+
+    /// Create a writer for the WGSL struct `struct_name` declared in `module`
+    ///
+    /// Field values passed to the writer are type-checked and packed using the
+    /// struct's reflected offsets and padding.
+    #[napi(catch_unwind, js_name = "createUniformWriter")]
+    pub fn create_uniform_writer(&self, module: &GpuShaderModule, struct_name: String) -> Result<crate::GpuUniformWriter> {
+        self.errors.ensure_usable("createUniformWriter")?;
+        let layout = module.reflection.struct_layout(&struct_name)?;
+        Ok(crate::GpuUniformWriter::new(layout))
+    }
+
+    /// Enable or disable the shader module de-duplication cache
+    ///
+    /// Off by default. Useful for generated-shader systems that recreate the
+    /// same module many times. Disabling the cache drops all cached modules.
+    #[napi(catch_unwind, js_name = "setShaderModuleCacheEnabled")]
+    pub fn set_shader_module_cache_enabled(&self, enabled: bool) {
+        self.shader_cache.set_enabled(enabled);
+    }
+
+    /// Drop all cached shader modules and reset the cache statistics
+    #[napi(catch_unwind, js_name = "clearShaderModuleCache")]
+    pub fn clear_shader_module_cache(&self) {
+        self.shader_cache.clear();
+    }
+
+    /// Get shader module cache statistics
+    #[napi(catch_unwind, js_name = "getShaderModuleCacheStats")]
+    pub fn get_shader_module_cache_stats(&self) -> crate::ShaderModuleCacheStats {
+        self.shader_cache.stats()
     }
 
     /// Create a command encoder
-    #[napi(js_name = "createCommandEncoder")]
-    pub fn create_command_encoder(&self, descriptor: Option<crate::CommandEncoderDescriptor>) -> GpuCommandEncoder {
+    #[napi(catch_unwind, js_name = "createCommandEncoder")]
+    pub fn create_command_encoder(&self, descriptor: Option<crate::CommandEncoderDescriptor>) -> Result<GpuCommandEncoder> {
+        self.errors.ensure_usable("createCommandEncoder")?;
         let encoder = self.device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
             label: descriptor.as_ref().and_then(|d| d.label.as_deref()),
         });
 
-        GpuCommandEncoder {
-            encoder: Some(encoder),
-        }
+        Ok(GpuCommandEncoder {
+            state: Arc::new(Mutex::new(EncoderState {
+                encoder: Some(encoder),
+                pass_open: false,
+            })),
+            device: self.device.clone(),
+            msaa: self.msaa.clone(),
+            errors: self.errors.clone(),
+        })
     }
 
     /// Poll the device
-    #[napi]
-    pub fn poll(&self, force_wait: Option<bool>) {
-        self.device.poll(if force_wait.unwrap_or(false) {
-            wgpu::Maintain::Wait
-        } else {
-            wgpu::Maintain::Poll
+    ///
+    /// Returns `true` if the queue is empty, i.e. all submitted work has completed.
+    #[napi(catch_unwind)]
+    pub fn poll(&self, force_wait: Option<bool>) -> bool {
+        self.device
+            .poll(if force_wait.unwrap_or(false) {
+                wgpu::Maintain::Wait
+            } else {
+                wgpu::Maintain::Poll
+            })
+            .is_queue_empty()
+    }
+
+    /// Enable or disable automatic device maintenance
+    ///
+    /// When enabled, every `queue.submit()` keeps the device poll thread running until
+    /// that submission completes, so wgpu callbacks and resource cleanup happen without
+    /// explicit `device.poll()` calls. Disabled by default.
+    #[napi(catch_unwind, js_name = "setAutoMaintenance")]
+    pub fn set_auto_maintenance(&self, enabled: bool) {
+        self.poller.set_auto_maintain(enabled);
+    }
+
+    /// Wait until all work submitted to this device has completed
+    ///
+    /// Flushes pending `queue.writeBuffer()` data first. The wait happens on the device
+    /// poll thread, so the JS thread stays free.
+    ///
+    /// Example:
+    /// ```js
+    /// device.queue.submit([encoder.finish()])
+    /// await device.waitIdle()
+    /// ```
+    #[napi(catch_unwind, js_name = "waitIdle")]
+    pub async fn wait_idle(&self) -> Result<()> {
+        let _polling = self.poller.track();
+        let (sender, receiver) = futures::channel::oneshot::channel();
+        self.queue_internal.submit(std::iter::empty());
+        self.queue_internal.on_submitted_work_done(move || {
+            let _ = sender.send(());
         });
+
+        receiver
+            .await
+            .map_err(|_| Error::from_reason("Device was lost while waiting for idle"))
     }
 
     /// Create a texture
-    #[napi(js_name = "createTexture")]
-    pub fn create_texture(&self, descriptor: crate::TextureDescriptor) -> crate::GpuTexture {
-        let format = crate::parse::parse_texture_format(&descriptor.format);
-        let dimension = match descriptor.dimension.as_deref() {
-            Some("1d") => wgpu::TextureDimension::D1,
-            Some("3d") => wgpu::TextureDimension::D3,
-            _ => wgpu::TextureDimension::D2,
-        };
-
-        let texture = self.device.create_texture(&wgpu::TextureDescriptor {
+    #[napi(catch_unwind, js_name = "createTexture")]
+    pub fn create_texture(&self, mut env: Env, descriptor: crate::TextureDescriptor) -> Result<crate::GpuTexture> {
+        let format = crate::parse::texture_format(&descriptor.format).ok_or_else(|| {
+            Error::from_reason(format!(
+                "Unknown texture format: {}",
+                crate::parse::describe_enum(&descriptor.format)
+            ))
+        })?;
+        let dimension = crate::parse::parse_texture_dimension(descriptor.dimension.as_ref());
+        let mut usage = crate::parse::parse_texture_usage(&descriptor.usage)?;
+        if descriptor.data.is_some() {
+            usage |= wgpu::TextureUsages::COPY_DST;
+        }
+        let wgpu_descriptor = wgpu::TextureDescriptor {
             label: descriptor.label.as_deref(),
             size: wgpu::Extent3d {
                 width: descriptor.width,
@@ -149,58 +431,178 @@ impl GpuDevice {
             sample_count: descriptor.sample_count.unwrap_or(1),
             dimension,
             format,
-            usage: wgpu::TextureUsages::from_bits_truncate(descriptor.usage),
+            usage,
             view_formats: &[],
-        });
+        };
+        let initial_mip_levels = descriptor
+            .data
+            .as_ref()
+            .map(|data| crate::texture::initial_data_mip_levels(&wgpu_descriptor, data, descriptor.data_layout.as_ref()))
+            .transpose()?;
+
+        let texture = self.errors.check_diagnosed(
+            "createTexture",
+            || {
+                format!(
+                    "label: {}, size: {}x{}x{}, format: {:?}, usage: {:#x}, mipLevelCount: {}, sampleCount: {}",
+                    crate::error::describe_label(descriptor.label.as_deref()),
+                    descriptor.width,
+                    descriptor.height,
+                    descriptor.depth.unwrap_or(1),
+                    format,
+                    usage.bits(),
+                    descriptor.mip_level_count.unwrap_or(1),
+                    descriptor.sample_count.unwrap_or(1)
+                )
+            },
+            || {
+                let features = self.device.features();
+                let allowed_usages = if features.contains(wgpu::Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES) {
+                    self.adapter.get_texture_format_features(format).allowed_usages
+                } else {
+                    format.guaranteed_format_features(features).allowed_usages
+                };
+                crate::texture::format_feature_error(format, usage, allowed_usages, features)
+            },
+            || {
+                let texture = self.device.create_texture(&wgpu_descriptor);
+                if let (Some(data), Some(mip_levels)) = (&descriptor.data, initial_mip_levels) {
+                    crate::texture::write_initial_data(
+                        &self.queue_internal,
+                        &texture,
+                        &wgpu_descriptor,
+                        data,
+                        descriptor.data_layout.as_ref(),
+                        mip_levels,
+                    );
+                }
+                texture
+            },
+        )?;
 
-        crate::GpuTexture::new(texture)
+        crate::GpuTexture::new(texture, descriptor.label, self.device.clone(), self.queue_internal.clone(), self.poller.clone())
+            .track_memory(&mut env)
     }
 
     /// Create a sampler
-    #[napi(js_name = "createSampler")]
-    pub fn create_sampler(&self, descriptor: crate::SamplerDescriptor) -> crate::GpuSampler {
-        let sampler = crate::sampler::create_sampler(&self.device, &descriptor);
-        crate::GpuSampler::new(sampler)
+    #[napi(catch_unwind, js_name = "createSampler")]
+    pub fn create_sampler(&self, descriptor: crate::SamplerDescriptor) -> Result<crate::GpuSampler> {
+        let sampler_descriptor = crate::sampler::sampler_descriptor(&descriptor)?;
+        let sampler = self.errors.check(
+            "createSampler",
+            || format!("label: {}", crate::error::describe_label(descriptor.label.as_deref())),
+            || self.device.create_sampler(&sampler_descriptor),
+        )?;
+        Ok(crate::GpuSampler::new(sampler))
     }
 
     /// Create a query set for timestamp or occlusion queries
-    #[napi(js_name = "createQuerySet")]
+    #[napi(catch_unwind, js_name = "createQuerySet")]
     pub fn create_query_set(&self, descriptor: crate::QuerySetDescriptor) -> Result<crate::GpuQuerySet> {
-        let ty = match descriptor.query_type.as_str() {
-            "timestamp" => wgpu::QueryType::Timestamp,
-            "occlusion" => wgpu::QueryType::Occlusion,
-            _ => return Err(Error::from_reason(format!("Invalid query type: {}", descriptor.query_type))),
-        };
-
-        let query_set = self.device.create_query_set(&wgpu::QuerySetDescriptor {
-            label: descriptor.label.as_deref(),
-            ty,
-            count: descriptor.count,
-        });
+        let ty = crate::parse::parse_query_type(&descriptor.query_type).ok_or_else(|| {
+            Error::from_reason(format!(
+                "Invalid query type: {}",
+                crate::parse::describe_enum(&descriptor.query_type)
+            ))
+        })?;
+
+        let query_set = self.errors.check(
+            "createQuerySet",
+            || {
+                format!(
+                    "label: {}, type: {:?}, count: {}",
+                    crate::error::describe_label(descriptor.label.as_deref()),
+                    ty,
+                    descriptor.count
+                )
+            },
+            || {
+                self.device.create_query_set(&wgpu::QuerySetDescriptor {
+                    label: descriptor.label.as_deref(),
+                    ty,
+                    count: descriptor.count,
+                })
+            },
+        )?;
 
         Ok(crate::GpuQuerySet::new(query_set))
     }
 
+    /// Create a render bundle encoder following WebGPU standard
+    ///
+    /// Color formats, depth-stencil format and sample count must match the
+    /// render passes the bundle will be executed in.
+    #[napi(catch_unwind, js_name = "createRenderBundleEncoder")]
+    pub fn create_render_bundle_encoder(
+        &self,
+        descriptor: crate::RenderBundleEncoderDescriptor,
+    ) -> Result<crate::GpuRenderBundleEncoder> {
+        self.errors.ensure_usable("createRenderBundleEncoder")?;
+        crate::GpuRenderBundleEncoder::new(self.device.clone(), descriptor, self.errors.clone())
+    }
+
     /// Create a bind group layout
-    #[napi(js_name = "createBindGroupLayout")]
+    #[napi(catch_unwind, js_name = "createBindGroupLayout")]
     pub fn create_bind_group_layout(&self, descriptor: crate::BindGroupLayoutDescriptor) -> Result<crate::GpuBindGroupLayout> {
         let entries: Vec<_> = descriptor.entries
             .iter()
             .map(|e| crate::bind_group::convert_bind_group_layout_entry(e))
             .collect();
 
-        let layout = self.device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
-            label: descriptor.label.as_deref(),
-            entries: &entries,
-        });
+        for entry in &entries {
+            // wgpu names its flag, not the feature to request
+            if entry.visibility.contains(wgpu::ShaderStages::VERTEX)
+                && crate::bind_group::is_writable_storage(&entry.ty)
+                && !self.device.features().contains(wgpu::Features::VERTEX_WRITABLE_STORAGE)
+            {
+                return Err(Error::from_reason(format!(
+                    "createBindGroupLayout: binding {} is writable storage visible to VERTEX, which needs the 'wgpu-vertex-writable-storage' feature",
+                    entry.binding
+                )));
+            }
+            // wgpu only rejects unsupported storage texture reads when the bind group is created
+            if let wgpu::BindingType::StorageTexture { access, format, .. } = entry.ty {
+                if access != wgpu::StorageTextureAccess::WriteOnly
+                    && !self
+                        .adapter
+                        .get_texture_format_features(format)
+                        .flags
+                        .contains(wgpu::TextureFormatFeatureFlags::STORAGE_READ_WRITE)
+                {
+                    return Err(Error::from_reason(format!(
+                        "createBindGroupLayout: binding {} uses {} access, but {} storage textures can only be written on this adapter",
+                        entry.binding,
+                        if access == wgpu::StorageTextureAccess::ReadOnly { "read-only" } else { "read-write" },
+                        crate::parse::texture_format_name(format)
+                    )));
+                }
+            }
+        }
+
+        let layout = self.errors.check(
+            "createBindGroupLayout",
+            || {
+                format!(
+                    "label: {}, bindings: {:?}",
+                    crate::error::describe_label(descriptor.label.as_deref()),
+                    entries.iter().map(|e| e.binding).collect::<Vec<_>>()
+                )
+            },
+            || {
+                self.device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                    label: descriptor.label.as_deref(),
+                    entries: &entries,
+                })
+            },
+        )?;
 
-        Ok(crate::GpuBindGroupLayout::new(layout))
+        Ok(crate::GpuBindGroupLayout::new(layout, Some(Arc::new(entries))))
     }
 
     /// Create a bind group following WebGPU spec
     /// Resources are passed separately to avoid napi-rs External serialization issues
     /// Each entry specifies resource_type ("buffer" | "texture" | "sampler")
-    #[napi(js_name = "createBindGroup")]
+    #[napi(catch_unwind, js_name = "createBindGroup")]
     pub fn create_bind_group(
         &self,
         descriptor: crate::BindGroupDescriptor,
@@ -215,7 +617,7 @@ impl GpuDevice {
         let mut texture_idx = 0;
         let mut sampler_idx = 0;
 
-        let wgpu_entries: Result<Vec<_>> = entries
+        let resources: Result<Vec<_>> = entries
             .iter()
             .map(|entry| {
                 // Use resource_type to determine which resource array to pull from
@@ -232,6 +634,32 @@ impl GpuDevice {
                             size: entry.size.map(|s| std::num::NonZeroU64::new(s as u64)).flatten(),
                         })
                     }
+                    "buffer-array" => {
+                        // wgpu panics on buffer arrays without the feature
+                        if !self.device.features().contains(wgpu::Features::BUFFER_BINDING_ARRAY) {
+                            return Err(Error::from_reason(format!(
+                                "createBindGroup: binding {} is a buffer array, which needs the 'wgpu-buffer-binding-array' feature",
+                                entry.binding
+                            )));
+                        }
+                        let count = entry.count.unwrap_or(0) as usize;
+                        let bufs = buffers.as_ref()
+                            .and_then(|bufs| bufs.get(buffer_idx..buffer_idx + count))
+                            .ok_or_else(|| Error::from_reason("Not enough buffers provided"))?;
+                        let bindings = bufs
+                            .iter()
+                            .enumerate()
+                            .map(|(i, buf)| wgpu::BufferBinding {
+                                buffer: &buf.buffer,
+                                offset: entry.offsets.as_ref().and_then(|o| o.get(i)).copied().unwrap_or(0) as u64,
+                                size: entry.sizes.as_ref()
+                                    .and_then(|s| s.get(i).copied().flatten())
+                                    .and_then(|s| std::num::NonZeroU64::new(s as u64)),
+                            })
+                            .collect();
+                        buffer_idx += count;
+                        return Ok(crate::bind_group::EntryResource::BufferArray(bindings));
+                    }
                     "texture" => {
                         let texs = textures.as_ref()
                             .ok_or_else(|| Error::from_reason("No textures provided for texture binding"))?;
@@ -248,76 +676,158 @@ impl GpuDevice {
                         sampler_idx += 1;
                         wgpu::BindingResource::Sampler(&samp.sampler)
                     }
+                    "texture-array" | "sampler-array" => {
+                        // wgpu panics on texture and sampler arrays without the feature
+                        if !self.device.features().contains(wgpu::Features::TEXTURE_BINDING_ARRAY) {
+                            return Err(Error::from_reason(format!(
+                                "createBindGroup: binding {} is a {}, which needs the 'wgpu-texture-binding-array' feature",
+                                entry.binding,
+                                entry.resource_type.replace('-', " ")
+                            )));
+                        }
+                        let count = entry.count.unwrap_or(0) as usize;
+                        if entry.resource_type == "texture-array" {
+                            let views = textures.as_ref()
+                                .and_then(|texs| texs.get(texture_idx..texture_idx + count))
+                                .ok_or_else(|| Error::from_reason("Not enough textures provided"))?;
+                            texture_idx += count;
+                            return Ok(crate::bind_group::EntryResource::TextureViewArray(
+                                views.iter().map(|view| view.view.as_ref()).collect(),
+                            ));
+                        }
+                        let samps = samplers.as_ref()
+                            .and_then(|samps| samps.get(sampler_idx..sampler_idx + count))
+                            .ok_or_else(|| Error::from_reason("Not enough samplers provided"))?;
+                        sampler_idx += count;
+                        return Ok(crate::bind_group::EntryResource::SamplerArray(
+                            samps.iter().map(|samp| samp.sampler.as_ref()).collect(),
+                        ));
+                    }
                     _ => {
                         return Err(Error::from_reason(format!("Invalid resource_type: {}", entry.resource_type)));
                     }
                 };
 
-                Ok(wgpu::BindGroupEntry {
-                    binding: entry.binding,
-                    resource,
-                })
+                Ok(crate::bind_group::EntryResource::Single(resource))
             })
             .collect();
 
-        let wgpu_entries = wgpu_entries?;
+        let resources = resources?;
+        crate::bind_group::check_array_lengths(&entries, layout.entries.as_ref(), self.device.features())?;
+        let wgpu_entries: Vec<_> = entries
+            .iter()
+            .zip(&resources)
+            .map(|(entry, resource)| wgpu::BindGroupEntry {
+                binding: entry.binding,
+                resource: resource.to_wgpu(),
+            })
+            .collect();
 
-        let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
-            label: descriptor.label.as_deref(),
-            layout: &layout.layout,
-            entries: &wgpu_entries,
-        });
+        let bind_group = self.errors.check_diagnosed(
+            "createBindGroup",
+            || {
+                format!(
+                    "label: {}, entries: [{}]",
+                    crate::error::describe_label(descriptor.label.as_deref()),
+                    entries
+                        .iter()
+                        .map(|e| format!("{} ({})", e.binding, e.resource_type))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+            },
+            || {
+                let provided = crate::bind_group::provided_resources(&entries, buffers.as_deref(), textures.as_deref());
+                crate::layout_check::bind_group_diff(layout.entries.as_ref()?, &provided)
+            },
+            || {
+                self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+                    label: descriptor.label.as_deref(),
+                    layout: &layout.layout,
+                    entries: &wgpu_entries,
+                })
+            },
+        )?;
 
-        Ok(crate::GpuBindGroup::new(bind_group))
+        Ok(crate::GpuBindGroup::new(bind_group, layout.entries.clone()))
     }
 
     /// Create a pipeline layout
-    #[napi(js_name = "createPipelineLayout")]
+    #[napi(catch_unwind, js_name = "createPipelineLayout")]
     pub fn create_pipeline_layout(
         &self,
         descriptor: crate::PipelineLayoutDescriptor,
         bind_group_layouts: Vec<&crate::GpuBindGroupLayout>,
-    ) -> crate::GpuPipelineLayout {
+    ) -> Result<crate::GpuPipelineLayout> {
         let bind_group_layouts_refs: Vec<_> = bind_group_layouts
             .iter()
             .map(|l| l.layout.as_ref())
             .collect();
 
-        let layout = self.device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
-            label: descriptor.label.as_deref(),
-            bind_group_layouts: &bind_group_layouts_refs,
-            push_constant_ranges: &[],
-        });
+        let layout = self.errors.check(
+            "createPipelineLayout",
+            || {
+                format!(
+                    "label: {}, bindGroupLayouts: {}",
+                    crate::error::describe_label(descriptor.label.as_deref()),
+                    bind_group_layouts_refs.len()
+                )
+            },
+            || {
+                self.device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                    label: descriptor.label.as_deref(),
+                    bind_group_layouts: &bind_group_layouts_refs,
+                    push_constant_ranges: &[],
+                })
+            },
+        )?;
 
-        crate::GpuPipelineLayout {
+        Ok(crate::GpuPipelineLayout {
             layout: std::sync::Arc::new(layout),
-        }
+            bind_group_layouts: Arc::new(bind_group_layouts.iter().map(|l| l.entries.clone()).collect()),
+        })
     }
 
     /// Create a compute pipeline following WebGPU spec
-    #[napi(js_name = "createComputePipeline")]
+    #[napi(catch_unwind, js_name = "createComputePipeline")]
     pub fn create_compute_pipeline(
         &self,
         descriptor: crate::ComputePipelineDescriptor,
         layout: Option<&crate::GpuPipelineLayout>,
         module: &crate::GpuShaderModule,
-    ) -> crate::GpuComputePipeline {
+    ) -> Result<crate::GpuComputePipeline> {
         let layout_ref = layout.map(|l| l.layout.as_ref());
 
-        let pipeline = self.device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
-            label: descriptor.label.as_deref(),
-            layout: layout_ref,
-            module: &module.shader,
-            entry_point: &descriptor.entry_point,
-        });
+        let pipeline = self.errors.check(
+            "createComputePipeline",
+            || {
+                format!(
+                    "label: {}, entryPoint: {:?}, layout: {}",
+                    crate::error::describe_label(descriptor.label.as_deref()),
+                    descriptor.entry_point,
+                    if layout_ref.is_some() { "explicit" } else { "auto" }
+                )
+            },
+            || {
+                self.device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+                    label: descriptor.label.as_deref(),
+                    layout: layout_ref,
+                    module: &module.shader,
+                    entry_point: &descriptor.entry_point,
+                })
+            },
+        )?;
 
-        crate::GpuComputePipeline {
+        Ok(crate::GpuComputePipeline {
             pipeline: std::sync::Arc::new(pipeline),
-        }
+            reflection: module.reflection.clone(),
+            entry_point: descriptor.entry_point,
+            bind_group_layouts: layout.map(|l| l.bind_group_layouts.clone()),
+        })
     }
 
     /// Create a render pipeline following WebGPU spec
-    #[napi(js_name = "createRenderPipeline")]
+    #[napi(catch_unwind, js_name = "createRenderPipeline")]
     pub fn create_render_pipeline(
         &self,
         descriptor: crate::RenderPipelineDescriptor,
@@ -344,10 +854,7 @@ impl GpuDevice {
         // Build vertex buffer layouts
         let vertex_buffers: Vec<wgpu::VertexBufferLayout> = if let Some(ref buffers) = descriptor.vertex.buffers {
             buffers.iter().enumerate().map(|(i, buf)| {
-                let step_mode = match buf.step_mode.as_deref() {
-                    Some("instance") => wgpu::VertexStepMode::Instance,
-                    _ => wgpu::VertexStepMode::Vertex,
-                };
+                let step_mode = crate::parse::parse_vertex_step_mode(buf.step_mode.as_ref());
 
                 wgpu::VertexBufferLayout {
                     array_stride: buf.array_stride as u64,
@@ -361,30 +868,11 @@ impl GpuDevice {
 
         // Build primitive state
         let primitive = if let Some(ref prim) = descriptor.primitive {
-            let topology = match prim.topology.as_deref() {
-                Some("point-list") => wgpu::PrimitiveTopology::PointList,
-                Some("line-list") => wgpu::PrimitiveTopology::LineList,
-                Some("line-strip") => wgpu::PrimitiveTopology::LineStrip,
-                Some("triangle-strip") => wgpu::PrimitiveTopology::TriangleStrip,
-                _ => wgpu::PrimitiveTopology::TriangleList,
-            };
-
-            let front_face = match prim.front_face.as_deref() {
-                Some("cw") => wgpu::FrontFace::Cw,
-                _ => wgpu::FrontFace::Ccw,
-            };
-
-            let cull_mode = match prim.cull_mode.as_deref() {
-                Some("front") => Some(wgpu::Face::Front),
-                Some("back") => Some(wgpu::Face::Back),
-                _ => None,
-            };
-
             wgpu::PrimitiveState {
-                topology,
+                topology: crate::parse::parse_primitive_topology(prim.topology.as_ref()),
                 strip_index_format: None,
-                front_face,
-                cull_mode,
+                front_face: crate::parse::parse_front_face(prim.front_face.as_ref()),
+                cull_mode: crate::parse::parse_cull_mode(prim.cull_mode.as_ref()),
                 ..Default::default()
             }
         } else {
@@ -393,24 +881,25 @@ impl GpuDevice {
 
         // Build depth/stencil state
         let depth_stencil = descriptor.depth_stencil.as_ref().map(|ds| {
-            let compare = match ds.depth_compare.as_deref() {
-                Some("never") => wgpu::CompareFunction::Never,
-                Some("less") => wgpu::CompareFunction::Less,
-                Some("equal") => wgpu::CompareFunction::Equal,
-                Some("less-equal") => wgpu::CompareFunction::LessEqual,
-                Some("greater") => wgpu::CompareFunction::Greater,
-                Some("not-equal") => wgpu::CompareFunction::NotEqual,
-                Some("greater-equal") => wgpu::CompareFunction::GreaterEqual,
-                Some("always") => wgpu::CompareFunction::Always,
-                _ => wgpu::CompareFunction::Less,
-            };
+            let compare = crate::parse::parse_compare_function(ds.depth_compare.as_ref())
+                .unwrap_or(wgpu::CompareFunction::Less);
 
             wgpu::DepthStencilState {
                 format: crate::parse::parse_texture_format(&ds.format),
                 depth_write_enabled: ds.depth_write_enabled.unwrap_or(true),
                 depth_compare: compare,
-                stencil: wgpu::StencilState::default(),
-                bias: wgpu::DepthBiasState::default(),
+                stencil: wgpu::StencilState {
+                    front: crate::parse::parse_stencil_face(ds.stencil_front.as_ref()),
+                    back: crate::parse::parse_stencil_face(ds.stencil_back.as_ref()),
+                    read_mask: ds.stencil_read_mask.unwrap_or(0xFFFF_FFFF),
+                    write_mask: ds.stencil_write_mask.unwrap_or(0xFFFF_FFFF),
+                },
+                // Slope-scaled bias keeps shadow maps free of acne
+                bias: wgpu::DepthBiasState {
+                    constant: ds.depth_bias.unwrap_or(0),
+                    slope_scale: ds.depth_bias_slope_scale.unwrap_or(0.0) as f32,
+                    clamp: ds.depth_bias_clamp.unwrap_or(0.0) as f32,
+                },
             }
         });
 
@@ -428,6 +917,7 @@ impl GpuDevice {
         // Build fragment targets - need to own them
         let frag_targets: Vec<Option<wgpu::ColorTargetState>> = if let Some(ref frag_desc) = descriptor.fragment {
             frag_desc.targets.iter().map(|target| {
+                let target = target.as_ref()?;
                 let blend = target.blend.as_ref().map(|b| {
                     wgpu::BlendState {
                         color: wgpu::BlendComponent {
@@ -466,41 +956,210 @@ impl GpuDevice {
             None
         };
 
-        let pipeline = self.device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-            label: descriptor.label.as_deref(),
-            layout: layout_ref,
-            vertex: wgpu::VertexState {
-                module: &vertex_module.shader,
-                entry_point: &descriptor.vertex.entry_point,
-                buffers: &vertex_buffers,
+        let pipeline = self.errors.check(
+            "createRenderPipeline",
+            || {
+                format!(
+                    "label: {}, vertex: {:?}, fragment: {:?}, targets: {:?}, depthStencil: {:?}",
+                    crate::error::describe_label(descriptor.label.as_deref()),
+                    descriptor.vertex.entry_point,
+                    descriptor.fragment.as_ref().map(|f| f.entry_point.as_str()),
+                    frag_targets.iter().map(|t| t.as_ref().map(|t| t.format)).collect::<Vec<_>>(),
+                    depth_stencil.as_ref().map(|ds| ds.format)
+                )
             },
-            fragment,
-            primitive,
-            depth_stencil,
-            multisample,
-            multiview: None,
-        });
+            || {
+                self.device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                    label: descriptor.label.as_deref(),
+                    layout: layout_ref,
+                    vertex: wgpu::VertexState {
+                        module: &vertex_module.shader,
+                        entry_point: &descriptor.vertex.entry_point,
+                        buffers: &vertex_buffers,
+                    },
+                    fragment,
+                    primitive,
+                    depth_stencil: depth_stencil.clone(),
+                    multisample,
+                    multiview: None,
+                })
+            },
+        )?;
 
         Ok(crate::GpuRenderPipeline {
             pipeline: std::sync::Arc::new(pipeline),
+            bind_group_layouts: layout.map(|l| l.bind_group_layouts.clone()),
         })
     }
 
+    /// Create a prefix sum (scan) utility
+    ///
+    /// The returned object owns its compute pipelines and can be reused for any number of scans.
+    #[napi(catch_unwind, js_name = "createPrefixSum")]
+    pub fn create_prefix_sum(&self, descriptor: Option<crate::PrefixSumDescriptor>) -> Result<crate::GpuPrefixSum> {
+        self.errors.ensure_usable("createPrefixSum")?;
+        crate::GpuPrefixSum::new(self.device.clone(), self.queue_internal.clone(), descriptor)
+    }
+
+    /// Create a radix sort utility for u32 keys with optional u32 values
+    #[napi(catch_unwind, js_name = "createRadixSort")]
+    pub fn create_radix_sort(&self, descriptor: Option<crate::RadixSortDescriptor>) -> Result<crate::GpuRadixSort> {
+        self.errors.ensure_usable("createRadixSort")?;
+        crate::GpuRadixSort::new(self.device.clone(), self.queue_internal.clone(), descriptor)
+    }
+
+    /// Create a tiled matrix multiply utility for f32/f16 matrices
+    #[napi(catch_unwind, js_name = "createMatmul")]
+    pub fn create_matmul(&self, descriptor: Option<crate::MatmulDescriptor>) -> Result<crate::GpuMatmul> {
+        self.errors.ensure_usable("createMatmul")?;
+        crate::GpuMatmul::new(self.device.clone(), self.queue_internal.clone(), self.poller.clone(), descriptor)
+    }
+
+    /// Create a separable Gaussian blur utility for textures
+    #[napi(catch_unwind, js_name = "createBlur")]
+    pub fn create_blur(&self, descriptor: Option<crate::BlurDescriptor>) -> Result<crate::GpuBlur> {
+        self.errors.ensure_usable("createBlur")?;
+        crate::GpuBlur::new(self.device.clone(), self.queue_internal.clone(), descriptor)
+    }
+
+    /// Create a per-channel histogram utility for textures and RGBA8 pixel buffers
+    #[napi(catch_unwind, js_name = "createHistogram")]
+    pub fn create_histogram(&self, descriptor: Option<crate::HistogramDescriptor>) -> Result<crate::GpuHistogram> {
+        self.errors.ensure_usable("createHistogram")?;
+        crate::GpuHistogram::new(self.device.clone(), self.queue_internal.clone(), self.poller.clone(), descriptor)
+    }
+
+    /// Create an immediate-mode 2D renderer for shapes and sprites
+    #[napi(catch_unwind, js_name = "createDraw2D")]
+    pub fn create_draw_2d(&self, descriptor: Option<crate::Draw2DDescriptor>) -> Result<crate::GpuDraw2D> {
+        self.errors.ensure_usable("createDraw2D")?;
+        Ok(crate::GpuDraw2D::new(self.device.clone(), self.queue_internal.clone(), descriptor))
+    }
+
+    /// Load a TrueType font for drawing text into render passes
+    #[napi(catch_unwind, js_name = "createFont")]
+    pub fn create_font(&self, data: Buffer, descriptor: Option<crate::FontDescriptor>) -> Result<crate::GpuFont> {
+        self.errors.ensure_usable("createFont")?;
+        crate::GpuFont::new(self.device.clone(), self.queue_internal.clone(), data.to_vec(), descriptor)
+    }
+
+    /// Create a timestamp timer for measuring GPU durations of encoded work
+    ///
+    /// Requires the "timestamp-query" feature.
+    #[napi(catch_unwind, js_name = "createTimer")]
+    pub fn create_timer(&self, descriptor: Option<crate::TimerDescriptor>) -> Result<crate::GpuTimer> {
+        self.errors.ensure_usable("createTimer")?;
+        crate::GpuTimer::new(self.device.clone(), self.queue_internal.clone(), self.poller.clone(), descriptor)
+    }
+
+    /// Create a runner that dispatches `pipeline` repeatedly
+    ///
+    /// `bindGroups` are set at indices 0, 1, ... and `params`, if given, is
+    /// the buffer each `run()` writes its parameters into.
+    #[napi(catch_unwind, js_name = "createComputeRunner")]
+    pub fn create_compute_runner(
+        &self,
+        descriptor: Option<crate::ComputeRunnerDescriptor>,
+        pipeline: &crate::GpuComputePipeline,
+        bind_groups: Vec<&crate::GpuBindGroup>,
+        params: Option<&crate::GpuBuffer>,
+    ) -> Result<crate::GpuComputeRunner> {
+        self.errors.ensure_usable("createComputeRunner")?;
+        crate::GpuComputeRunner::new(self, descriptor, pipeline, bind_groups, params)
+    }
+
+    /// Create a capture that reads rendered frames back for video encoding
+    ///
+    /// Frames rotate through `bufferCount` staging buffers and come out of
+    /// `next()` in order, as RGBA or yuv420p bytes.
+    #[napi(catch_unwind, js_name = "createFrameCapture")]
+    pub fn create_frame_capture(
+        &self,
+        descriptor: Option<crate::FrameCaptureDescriptor>,
+    ) -> Result<crate::GpuFrameCapture> {
+        self.errors.ensure_usable("createFrameCapture")?;
+        crate::GpuFrameCapture::new(self, descriptor)
+    }
+
+    /// Create a dataset split across storage buffers no larger than a binding
+    ///
+    /// For data beyond `maxStorageBufferBindingSize`; `dispatch()` runs a
+    /// pipeline over each chunk in turn.
+    #[napi(catch_unwind, js_name = "createChunkedBuffer")]
+    pub fn create_chunked_buffer(
+        &self,
+        mut env: Env,
+        descriptor: crate::ChunkedBufferDescriptor,
+    ) -> Result<crate::GpuChunkedBuffer> {
+        self.errors.ensure_usable("createChunkedBuffer")?;
+        crate::GpuChunkedBuffer::new(&mut env, self, descriptor)
+    }
+
     /// Destroy the device
-    #[napi]
+    ///
+    /// The device is lost with reason "destroyed": its "deviceLost" event fires
+    /// and later calls on it, its queue and its encoders throw. Resources are
+    /// freed once submitted work has finished. Destroying again does nothing.
+    #[napi(catch_unwind)]
     pub fn destroy(&self) {
-        // wgpu devices are automatically cleaned up
+        self.events.lose("destroyed", "Device was destroyed".to_string());
+        self.device.destroy();
     }
 }
 
 #[napi]
+#[derive(Clone)]
 pub struct GpuShaderModule {
-    pub(crate) shader: wgpu::ShaderModule,
+    pub(crate) shader: Arc<wgpu::ShaderModule>,
+    pub(crate) reflection: Arc<crate::reflect::ShaderReflection>,
+}
+
+/// Command encoder state shared with the pass encoders it begins
+///
+/// Passes don't borrow the encoder: they record their commands and replay them
+/// into it on `end()`. While a pass is open the encoder is locked, so commands
+/// keep their order and misordered calls fail instead of touching a dead pass.
+pub(crate) struct EncoderState {
+    /// `None` once finished
+    pub(crate) encoder: Option<wgpu::CommandEncoder>,
+    pub(crate) pass_open: bool,
+}
+
+/// Lock encoder state, recovering it if a panic was caught while it was held
+pub(crate) fn lock_encoder(state: &Mutex<EncoderState>) -> std::sync::MutexGuard<'_, EncoderState> {
+    state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
 }
 
 #[napi]
 pub struct GpuCommandEncoder {
-    pub(crate) encoder: Option<wgpu::CommandEncoder>,
+    pub(crate) state: Arc<Mutex<EncoderState>>,
+    device: Arc<wgpu::Device>,
+    /// Targets of color attachments with `sampleCount`
+    msaa: Arc<crate::msaa::MultisampleTargets>,
+    errors: crate::error::ErrorSink,
+}
+
+impl GpuCommandEncoder {
+    /// Run `f` on the encoder if it is neither finished nor locked by an open pass
+    pub(crate) fn with_encoder<T>(&self, f: impl FnOnce(&mut wgpu::CommandEncoder) -> Result<T>) -> Result<T> {
+        let mut state = lock_encoder(&self.state);
+        if state.pass_open {
+            return Err(Error::from_reason(
+                "Command encoder is locked by an open pass; call end() on the pass first",
+            ));
+        }
+        match state.encoder.as_mut() {
+            Some(enc) => f(enc),
+            None => Err(Error::from_reason("Command encoder already finished")),
+        }
+    }
+
+    /// Lock the encoder for a new pass
+    fn open_pass(&self) -> Result<()> {
+        self.with_encoder(|_| Ok(()))?;
+        lock_encoder(&self.state).pass_open = true;
+        Ok(())
+    }
 }
 
 #[napi]
@@ -508,18 +1167,19 @@ impl GpuCommandEncoder {
     /// Write a timestamp to a query set
     /// query_set: the query set to write to
     /// query_index: the index of the query to write (0 to count-1)
-    #[napi]
+    #[napi(catch_unwind)]
     pub fn write_timestamp(
         &mut self,
         query_set: &crate::GpuQuerySet,
         query_index: u32,
     ) -> Result<()> {
-        if let Some(ref mut enc) = self.encoder {
-            enc.write_timestamp(&query_set.query_set, query_index);
-            Ok(())
-        } else {
-            Err(Error::from_reason("Command encoder already finished"))
-        }
+        self.with_encoder(|enc| {
+            self.errors.check(
+                "writeTimestamp",
+                || format!("queryIndex: {}", query_index),
+                || enc.write_timestamp(&query_set.query_set, query_index),
+            )
+        })
     }
 
     /// Resolve query results to a buffer
@@ -528,7 +1188,7 @@ impl GpuCommandEncoder {
     /// query_count: the number of queries to resolve
     /// destination: the buffer to write results to
     /// destination_offset: the byte offset in the destination buffer
-    #[napi]
+    #[napi(catch_unwind)]
     pub fn resolve_query_set(
         &mut self,
         query_set: &crate::GpuQuerySet,
@@ -537,21 +1197,29 @@ impl GpuCommandEncoder {
         destination: &crate::GpuBuffer,
         destination_offset: u32,
     ) -> Result<()> {
-        if let Some(ref mut enc) = self.encoder {
-            enc.resolve_query_set(
-                &query_set.query_set,
-                first_query..first_query + query_count,
-                &destination.buffer,
-                destination_offset as u64,
-            );
-            Ok(())
-        } else {
-            Err(Error::from_reason("Command encoder already finished"))
-        }
+        self.with_encoder(|enc| {
+            self.errors.check(
+                "resolveQuerySet",
+                || {
+                    format!(
+                        "firstQuery: {}, queryCount: {}, destinationOffset: {}",
+                        first_query, query_count, destination_offset
+                    )
+                },
+                || {
+                    enc.resolve_query_set(
+                        &query_set.query_set,
+                        first_query..first_query + query_count,
+                        &destination.buffer,
+                        destination_offset as u64,
+                    )
+                },
+            )
+        })
     }
 
     /// Copy data from one buffer to another (WebGPU standard method)
-    #[napi(js_name = "copyBufferToBuffer")]
+    #[napi(catch_unwind, js_name = "copyBufferToBuffer")]
     pub fn copy_buffer_to_buffer_standard(
         &mut self,
         source: &crate::GpuBuffer,
@@ -560,22 +1228,34 @@ impl GpuCommandEncoder {
         destination_offset: i64,
         size: i64,
     ) -> Result<()> {
-        if let Some(ref mut enc) = self.encoder {
-            enc.copy_buffer_to_buffer(
-                &source.buffer,
-                source_offset as u64,
-                &destination.buffer,
-                destination_offset as u64,
-                size as u64,
-            );
-            Ok(())
-        } else {
-            Err(Error::from_reason("Command encoder already finished"))
-        }
+        self.with_encoder(|enc| {
+            self.errors.check(
+                "copyBufferToBuffer",
+                || {
+                    format!(
+                        "source: {}, sourceOffset: {}, destination: {}, destinationOffset: {}, size: {}",
+                        crate::error::describe_label(source.label.as_deref()),
+                        source_offset,
+                        crate::error::describe_label(destination.label.as_deref()),
+                        destination_offset,
+                        size
+                    )
+                },
+                || {
+                    enc.copy_buffer_to_buffer(
+                        &source.buffer,
+                        source_offset as u64,
+                        &destination.buffer,
+                        destination_offset as u64,
+                        size as u64,
+                    )
+                },
+            )
+        })
     }
 
     /// Copy data from buffer to texture (WebGPU standard method)
-    #[napi(js_name = "copyBufferToTexture")]
+    #[napi(catch_unwind, js_name = "copyBufferToTexture")]
     pub fn copy_buffer_to_texture_standard(
         &mut self,
         source: &crate::GpuBuffer,
@@ -590,41 +1270,55 @@ impl GpuCommandEncoder {
         width: u32,
         height: u32,
         depth: Option<u32>,
+        aspect: Option<Either<String, u32>>,
     ) -> Result<()> {
-        if let Some(ref mut enc) = self.encoder {
-            enc.copy_buffer_to_texture(
-                wgpu::ImageCopyBuffer {
-                    buffer: &source.buffer,
-                    layout: wgpu::ImageDataLayout {
-                        offset: source_offset as u64,
-                        bytes_per_row: Some(bytes_per_row),
-                        rows_per_image,
-                    },
-                },
-                wgpu::ImageCopyTexture {
-                    texture: &destination.texture,
-                    mip_level: mip_level.unwrap_or(0),
-                    origin: wgpu::Origin3d {
-                        x: origin_x.unwrap_or(0),
-                        y: origin_y.unwrap_or(0),
-                        z: origin_z.unwrap_or(0),
-                    },
-                    aspect: wgpu::TextureAspect::All,
+        self.with_encoder(|enc| {
+            self.errors.check(
+                "copyBufferToTexture",
+                || {
+                    format!(
+                        "source: {}, destination: {}, bytesPerRow: {}, size: {}x{}x{}",
+                        crate::error::describe_label(source.label.as_deref()),
+                        crate::error::describe_label(destination.label.as_deref()),
+                        bytes_per_row,
+                        width,
+                        height,
+                        depth.unwrap_or(1)
+                    )
                 },
-                wgpu::Extent3d {
-                    width,
-                    height,
-                    depth_or_array_layers: depth.unwrap_or(1),
+                || {
+                    enc.copy_buffer_to_texture(
+                        wgpu::ImageCopyBuffer {
+                            buffer: &source.buffer,
+                            layout: wgpu::ImageDataLayout {
+                                offset: source_offset as u64,
+                                bytes_per_row: Some(bytes_per_row),
+                                rows_per_image,
+                            },
+                        },
+                        wgpu::ImageCopyTexture {
+                            texture: &destination.texture,
+                            mip_level: mip_level.unwrap_or(0),
+                            origin: wgpu::Origin3d {
+                                x: origin_x.unwrap_or(0),
+                                y: origin_y.unwrap_or(0),
+                                z: origin_z.unwrap_or(0),
+                            },
+                            aspect: crate::parse::parse_texture_aspect(aspect.as_ref()),
+                        },
+                        wgpu::Extent3d {
+                            width,
+                            height,
+                            depth_or_array_layers: depth.unwrap_or(1),
+                        },
+                    )
                 },
-            );
-            Ok(())
-        } else {
-            Err(Error::from_reason("Command encoder already finished"))
-        }
+            )
+        })
     }
 
     /// Copy data from texture to buffer (WebGPU standard method)
-    #[napi(js_name = "copyTextureToBuffer")]
+    #[napi(catch_unwind, js_name = "copyTextureToBuffer")]
     pub fn copy_texture_to_buffer_standard(
         &mut self,
         source: &crate::GpuTexture,
@@ -639,188 +1333,316 @@ impl GpuCommandEncoder {
         width: u32,
         height: u32,
         depth: Option<u32>,
+        aspect: Option<Either<String, u32>>,
     ) -> Result<()> {
-        if let Some(ref mut enc) = self.encoder {
-            enc.copy_texture_to_buffer(
-                wgpu::ImageCopyTexture {
-                    texture: &source.texture,
-                    mip_level: mip_level.unwrap_or(0),
-                    origin: wgpu::Origin3d {
-                        x: origin_x.unwrap_or(0),
-                        y: origin_y.unwrap_or(0),
-                        z: origin_z.unwrap_or(0),
-                    },
-                    aspect: wgpu::TextureAspect::All,
+        self.with_encoder(|enc| {
+            self.errors.check(
+                "copyTextureToBuffer",
+                || {
+                    format!(
+                        "source: {}, destination: {}, bytesPerRow: {}, size: {}x{}x{}",
+                        crate::error::describe_label(source.label.as_deref()),
+                        crate::error::describe_label(destination.label.as_deref()),
+                        bytes_per_row,
+                        width,
+                        height,
+                        depth.unwrap_or(1)
+                    )
                 },
-                wgpu::ImageCopyBuffer {
-                    buffer: &destination.buffer,
-                    layout: wgpu::ImageDataLayout {
-                        offset: destination_offset as u64,
-                        bytes_per_row: Some(bytes_per_row),
-                        rows_per_image,
-                    },
+                || {
+                    enc.copy_texture_to_buffer(
+                        wgpu::ImageCopyTexture {
+                            texture: &source.texture,
+                            mip_level: mip_level.unwrap_or(0),
+                            origin: wgpu::Origin3d {
+                                x: origin_x.unwrap_or(0),
+                                y: origin_y.unwrap_or(0),
+                                z: origin_z.unwrap_or(0),
+                            },
+                            aspect: crate::parse::parse_texture_aspect(aspect.as_ref()),
+                        },
+                        wgpu::ImageCopyBuffer {
+                            buffer: &destination.buffer,
+                            layout: wgpu::ImageDataLayout {
+                                offset: destination_offset as u64,
+                                bytes_per_row: Some(bytes_per_row),
+                                rows_per_image,
+                            },
+                        },
+                        wgpu::Extent3d {
+                            width,
+                            height,
+                            depth_or_array_layers: depth.unwrap_or(1),
+                        },
+                    )
                 },
-                wgpu::Extent3d {
-                    width,
-                    height,
-                    depth_or_array_layers: depth.unwrap_or(1),
+            )
+        })
+    }
+
+    /// Copy data between textures (WebGPU standard method)
+    #[napi(catch_unwind, js_name = "copyTextureToTexture")]
+    pub fn copy_texture_to_texture_standard(
+        &mut self,
+        source: &crate::GpuTexture,
+        source_location: crate::TextureCopyLocation,
+        destination: &crate::GpuTexture,
+        destination_location: crate::TextureCopyLocation,
+        size: crate::Extent3D,
+    ) -> Result<()> {
+        self.with_encoder(|enc| {
+            self.errors.check(
+                "copyTextureToTexture",
+                || {
+                    format!(
+                        "source: {}, destination: {}, size: {}x{}x{}",
+                        crate::error::describe_label(source.label.as_deref()),
+                        crate::error::describe_label(destination.label.as_deref()),
+                        size.width,
+                        size.height.unwrap_or(1),
+                        size.depth_or_array_layers.unwrap_or(1)
+                    )
                 },
-            );
-            Ok(())
-        } else {
-            Err(Error::from_reason("Command encoder already finished"))
+                || {
+                    enc.copy_texture_to_texture(
+                        source_location.to_wgpu(&source.texture),
+                        destination_location.to_wgpu(&destination.texture),
+                        (&size).into(),
+                    )
+                },
+            )
+        })
+    }
+
+    /// Clear a range of texture subresources to zero
+    /// Needs the 'wgpu-clear-texture' feature; the whole texture is cleared by default
+    #[napi(catch_unwind, js_name = "clearTexture")]
+    pub fn clear_texture(
+        &mut self,
+        texture: &crate::GpuTexture,
+        range: Option<crate::ImageSubresourceRange>,
+    ) -> Result<()> {
+        if !self.device.features().contains(wgpu::Features::CLEAR_TEXTURE) {
+            return Err(Error::from_reason(
+                "clearTexture: needs the 'wgpu-clear-texture' feature",
+            ));
+        }
+        let range = range.map(|r| r.to_wgpu()).unwrap_or_default();
+        let texture_label = crate::error::describe_label(texture.label.as_deref());
+        // wgpu-core doesn't reject a base past the end when the count is left
+        // to default, and then panics clearing the reversed range
+        let layers = match texture.texture.dimension() {
+            wgpu::TextureDimension::D3 => 1,
+            _ => texture.texture.depth_or_array_layers(),
+        };
+        for (what, base, count, total) in [
+            ("mip level", range.base_mip_level, range.mip_level_count, texture.texture.mip_level_count()),
+            ("array layer", range.base_array_layer, range.array_layer_count, layers),
+        ] {
+            if base >= total {
+                return Err(Error::from_reason(format!(
+                    "clearTexture: base {} {} is outside texture {}, which has {} {}s",
+                    what, base, texture_label, total, what
+                )));
+            }
+            if let Some(end) = count.map(|count| base.saturating_add(count)).filter(|&end| end > total) {
+                return Err(Error::from_reason(format!(
+                    "clearTexture: {}s {}..{} are outside texture {}, which has {}",
+                    what, base, end, texture_label, total
+                )));
+            }
         }
+        self.with_encoder(|enc| {
+            self.errors.check(
+                "clearTexture",
+                || format!("texture: {}", texture_label),
+                || enc.clear_texture(&texture.texture, &range),
+            )
+        })
     }
 
     /// Begin a compute pass following WebGPU standard
     /// Returns a compute pass encoder for recording compute commands
-    #[napi(js_name = "beginComputePass")]
+    #[napi(catch_unwind, js_name = "beginComputePass")]
     pub fn begin_compute_pass(&mut self, descriptor: Option<crate::pipeline::ComputePassDescriptor>) -> Result<crate::GpuComputePassEncoder> {
-        if let Some(ref mut enc) = self.encoder {
-            let pass = enc.begin_compute_pass(&wgpu::ComputePassDescriptor {
-                label: descriptor.as_ref().and_then(|d| d.label.as_deref()),
-                timestamp_writes: None,
-            });
-            // SAFETY: This is unsafe because we're extending the lifetime of the ComputePass
-            // from being tied to the encoder to 'static. This works because:
-            // 1. The pass must be ended (dropped) before encoder.finish() is called
-            // 2. We use transmute to force the lifetime to 'static
-            // 3. We erase the type to *mut () to avoid lifetime issues in the struct
-            // 4. The JavaScript API enforces correct usage order
-            let pass_static: wgpu::ComputePass<'static> = unsafe { std::mem::transmute(pass) };
-            let pass_ptr = Box::into_raw(Box::new(pass_static)) as *mut ();
-            Ok(crate::GpuComputePassEncoder {
-                pass: Some(pass_ptr),
-            })
-        } else {
-            Err(Error::from_reason("Command encoder already finished"))
-        }
+        self.open_pass()?;
+        Ok(crate::GpuComputePassEncoder {
+            encoder: self.state.clone(),
+            label: descriptor.and_then(|d| d.label),
+            commands: Some(Vec::new()),
+            bindings: Default::default(),
+            errors: self.errors.clone(),
+        })
     }
 
     /// Begin a render pass following WebGPU standard
     /// Texture views are passed separately to avoid napi-rs External serialization issues
     /// Returns a render pass encoder for recording render commands
-    #[napi(js_name = "beginRenderPass")]
+    #[napi(catch_unwind, js_name = "beginRenderPass")]
     pub fn begin_render_pass(
         &mut self,
         descriptor: crate::pipeline::RenderPassDescriptor,
-        color_views: Vec<&crate::GpuTextureView>,
+        color_views: Vec<Option<&crate::GpuTextureView>>,
         color_resolve_views: Option<Vec<Option<&crate::GpuTextureView>>>,
         depth_stencil_view: Option<&crate::GpuTextureView>,
     ) -> Result<crate::GpuRenderPassEncoder> {
-        if let Some(ref mut enc) = self.encoder {
-            // Convert color attachments
-            let color_attachments: Vec<Option<wgpu::RenderPassColorAttachment>> = descriptor.color_attachments
-                .iter()
-                .enumerate()
-                .map(|(i, attachment)| {
-                    let load_op = match attachment.load_op.as_str() {
-                        "clear" => {
-                            let clear_value = attachment.clear_value.as_ref().map(|c| wgpu::Color {
-                                r: c.r,
-                                g: c.g,
-                                b: c.b,
-                                a: c.a,
-                            }).unwrap_or(wgpu::Color::BLACK);
-                            wgpu::LoadOp::Clear(clear_value)
-                        },
-                        _ => wgpu::LoadOp::Load,
-                    };
-                    let store_op = match attachment.store_op.as_str() {
-                        "discard" => wgpu::StoreOp::Discard,
-                        _ => wgpu::StoreOp::Store,
-                    };
+        if let Some(ref attachment) = descriptor.depth_stencil_attachment {
+            if attachment.depth_read_only.unwrap_or(false)
+                && (attachment.depth_load_op.is_some() || attachment.depth_store_op.is_some())
+            {
+                return Err(Error::from_reason(
+                    "depthLoadOp and depthStoreOp must not be set when depthReadOnly is true",
+                ));
+            }
+            if attachment.stencil_read_only.unwrap_or(false)
+                && (attachment.stencil_load_op.is_some() || attachment.stencil_store_op.is_some())
+            {
+                return Err(Error::from_reason(
+                    "stencilLoadOp and stencilStoreOp must not be set when stencilReadOnly is true",
+                ));
+            }
+        }
 
-                    // Get view from separate array
-                    let view = color_views.get(i)?;
-
-                    // Get resolve target if provided
-                    let resolve_target = color_resolve_views.as_ref()
-                        .and_then(|resolve_views| resolve_views.get(i))
-                        .and_then(|opt_view| opt_view.as_ref())
-                        .map(|v| v.view.as_ref());
-
-                    Some(wgpu::RenderPassColorAttachment {
-                        view: &view.view,
-                        resolve_target,
-                        ops: wgpu::Operations {
-                            load: load_op,
-                            store: store_op,
-                        },
-                    })
-                })
-                .collect();
-
-            // Convert depth/stencil attachment
-            let depth_stencil_attachment = if let Some(ref attachment) = descriptor.depth_stencil_attachment {
-                depth_stencil_view.map(|view| {
-                    let depth_ops = if attachment.depth_load_op.is_some() || attachment.depth_store_op.is_some() {
-                        let load = match attachment.depth_load_op.as_deref() {
-                            Some("clear") => wgpu::LoadOp::Clear(attachment.depth_clear_value.unwrap_or(1.0) as f32),
-                            _ => wgpu::LoadOp::Load,
-                        };
-                        let store = match attachment.depth_store_op.as_deref() {
-                            Some("discard") => wgpu::StoreOp::Discard,
-                            _ => wgpu::StoreOp::Store,
-                        };
-                        Some(wgpu::Operations { load, store })
+        // Convert color attachments
+        let mut color_attachments: Vec<Option<crate::render_pass::ColorTarget>> =
+            Vec::with_capacity(descriptor.color_attachments.len());
+        for (i, attachment) in descriptor.color_attachments.iter().enumerate() {
+            // Get view from separate array
+            let (Some(attachment), Some(view)) = (attachment, color_views.get(i).copied().flatten()) else {
+                color_attachments.push(None);
+                continue;
+            };
+            let load_op = if crate::parse::is_clear_load_op(Some(&attachment.load_op)) {
+                let clear_value = attachment.clear_value.as_ref().map(|c| wgpu::Color {
+                    r: c.r,
+                    g: c.g,
+                    b: c.b,
+                    a: c.a,
+                }).unwrap_or(wgpu::Color::BLACK);
+                wgpu::LoadOp::Clear(clear_value)
+            } else {
+                wgpu::LoadOp::Load
+            };
+            let store_op = crate::parse::parse_store_op(Some(&attachment.store_op));
+
+            // Get resolve target if provided
+            let resolve_target = color_resolve_views.as_ref()
+                .and_then(|resolve_views| resolve_views.get(i))
+                .and_then(|opt_view| opt_view.as_ref())
+                .map(|v| v.view.clone());
+
+            // Render into a multisampled target and resolve into the view
+            let (view_handle, resolve_target, sample_count) = match attachment.sample_count {
+                Some(sample_count) if sample_count > 1 => {
+                    if resolve_target.is_some() {
+                        return Err(Error::from_reason(format!(
+                            "colorAttachments[{}]: resolveTarget can't be combined with sampleCount",
+                            i
+                        )));
+                    }
+                    if view.sample_count > 1 {
+                        return Err(Error::from_reason(format!(
+                            "colorAttachments[{}]: sampleCount needs a single-sampled view to resolve into",
+                            i
+                        )));
+                    }
+                    let target = self.msaa.get(&self.device, &self.errors, view.format, view.size, sample_count)?;
+                    (target, Some(view.view.clone()), sample_count)
+                }
+                _ => (view.view.clone(), resolve_target, view.sample_count),
+            };
+
+            color_attachments.push(Some(crate::render_pass::ColorTarget {
+                view: view_handle,
+                resolve_target,
+                ops: wgpu::Operations {
+                    load: load_op,
+                    store: store_op,
+                },
+                format: view.format,
+                size: view.size,
+                sample_count,
+            }));
+        }
+
+        self.open_pass()?;
+
+        // Convert depth/stencil attachment
+        let depth_stencil_attachment = if let Some(ref attachment) = descriptor.depth_stencil_attachment {
+            depth_stencil_view.map(|view| {
+                // wgpu expresses a read-only aspect as having no ops
+                let depth_ops = if attachment.depth_read_only.unwrap_or(false) {
+                    None
+                } else if attachment.depth_load_op.is_some() || attachment.depth_store_op.is_some() {
+                    let load = if crate::parse::is_clear_load_op(attachment.depth_load_op.as_ref()) {
+                        wgpu::LoadOp::Clear(attachment.depth_clear_value.unwrap_or(1.0) as f32)
                     } else {
-                        None
+                        wgpu::LoadOp::Load
                     };
+                    let store = crate::parse::parse_store_op(attachment.depth_store_op.as_ref());
+                    Some(wgpu::Operations { load, store })
+                } else {
+                    None
+                };
 
-                    let stencil_ops = if attachment.stencil_load_op.is_some() || attachment.stencil_store_op.is_some() {
-                        let load = match attachment.stencil_load_op.as_deref() {
-                            Some("clear") => wgpu::LoadOp::Clear(attachment.stencil_clear_value.unwrap_or(0)),
-                            _ => wgpu::LoadOp::Load,
-                        };
-                        let store = match attachment.stencil_store_op.as_deref() {
-                            Some("discard") => wgpu::StoreOp::Discard,
-                            _ => wgpu::StoreOp::Store,
-                        };
-                        Some(wgpu::Operations { load, store })
+                let stencil_ops = if attachment.stencil_read_only.unwrap_or(false) {
+                    None
+                } else if attachment.stencil_load_op.is_some() || attachment.stencil_store_op.is_some() {
+                    let load = if crate::parse::is_clear_load_op(attachment.stencil_load_op.as_ref()) {
+                        wgpu::LoadOp::Clear(attachment.stencil_clear_value.unwrap_or(0))
                     } else {
-                        None
+                        wgpu::LoadOp::Load
                     };
+                    let store = crate::parse::parse_store_op(attachment.stencil_store_op.as_ref());
+                    Some(wgpu::Operations { load, store })
+                } else {
+                    None
+                };
 
-                    wgpu::RenderPassDepthStencilAttachment {
-                        view: &view.view,
-                        depth_ops,
-                        stencil_ops,
-                    }
-                })
-            } else {
-                None
-            };
-
-            let pass = enc.begin_render_pass(&wgpu::RenderPassDescriptor {
-                label: descriptor.label.as_deref(),
-                color_attachments: &color_attachments,
-                depth_stencil_attachment,
-                timestamp_writes: None,
-                occlusion_query_set: None,
-            });
-
-            // SAFETY: This is unsafe because we're extending the lifetime of the RenderPass
-            // from being tied to the encoder to 'static. This works because:
-            // 1. The pass must be ended (dropped) before encoder.finish() is called
-            // 2. We use transmute to force the lifetime to 'static
-            // 3. We erase the type to *mut () to avoid lifetime issues in the struct
-            // 4. The JavaScript API enforces correct usage order
-            let pass_static: wgpu::RenderPass<'static> = unsafe { std::mem::transmute(pass) };
-            let pass_ptr = Box::into_raw(Box::new(pass_static)) as *mut ();
-            Ok(crate::GpuRenderPassEncoder {
-                pass: Some(pass_ptr),
+                crate::render_pass::DepthStencilTarget {
+                    view: view.view.clone(),
+                    format: view.format,
+                    depth_ops,
+                    stencil_ops,
+                }
             })
         } else {
-            Err(Error::from_reason("Command encoder already finished"))
-        }
+            None
+        };
+
+        Ok(crate::GpuRenderPassEncoder {
+            encoder: self.state.clone(),
+            label: descriptor.label,
+            color_attachments,
+            depth_stencil_attachment,
+            commands: Some(Vec::new()),
+            bindings: Default::default(),
+            errors: self.errors.clone(),
+        })
     }
 
     /// Finish encoding and return a command buffer
-    #[napi]
-    pub fn finish(&mut self) -> GpuCommandBuffer {
-        let buffer = self.encoder.take().map(|e| e.finish());
-        GpuCommandBuffer { buffer }
+    ///
+    /// Throws if a pass begun on this encoder hasn't ended.
+    #[napi(catch_unwind)]
+    pub fn finish(&mut self) -> Result<GpuCommandBuffer> {
+        let mut state = lock_encoder(&self.state);
+        if state.pass_open {
+            return Err(Error::from_reason(
+                "Cannot finish a command encoder while a pass is open; call end() on the pass first",
+            ));
+        }
+        let encoder = state.encoder.take();
+        let buffer = self.errors.check("finish", String::new, || encoder.map(|e| e.finish()))?;
+        Ok(GpuCommandBuffer { buffer })
+    }
+    /// Drop the encoder and everything recorded in it without finishing (extension)
+    ///
+    /// Afterwards the encoder behaves as if finished. Does nothing if it
+    /// already was.
+    #[napi(catch_unwind)]
+    pub fn discard(&self) {
+        lock_encoder(&self.state).encoder.take();
     }
 }
 
@@ -0,0 +1,541 @@
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use std::collections::HashMap;
+use std::ops::Range;
+use std::sync::Arc;
+use wgpu::util::DeviceExt;
+
+use crate::util;
+
+/// Floats per vertex: position, texture coordinates and color
+const VERTEX_FLOATS: usize = 8;
+
+/// 2D renderer descriptor
+#[napi(object)]
+pub struct Draw2DDescriptor {
+    pub label: Option<String>,
+}
+
+/// Options for `sprite`
+#[napi(object)]
+pub struct SpriteOptions {
+    /// Region of the texture to draw as [x, y, width, height] in texels (default: all of it)
+    pub source: Option<Vec<f64>>,
+    /// Tint multiplied with the texels as [r, g, b, a?] (default white)
+    pub color: Option<Vec<f64>>,
+    /// "linear" (default) or "nearest"
+    pub filter: Option<String>,
+}
+
+/// Options for `encode` and `run`
+#[napi(object)]
+pub struct Draw2DEncodeOptions {
+    /// Clear the target to this [r, g, b, a?] color first instead of drawing over it
+    pub clear: Option<Vec<f64>>,
+}
+
+/// Consecutive triangles drawn with the same texture
+struct Batch {
+    /// Sprite texture and whether to filter it linearly; None for shapes
+    image: Option<(Arc<wgpu::Texture>, bool)>,
+    vertices: Range<u32>,
+}
+
+/// 2x3 affine transform [a, b, c, d, e, f], mapping (x, y) to
+/// (a * x + c * y + e, b * x + d * y + f) like the canvas API
+type Transform = [f32; 6];
+
+const IDENTITY: Transform = [1.0, 0.0, 0.0, 1.0, 0.0, 0.0];
+
+pub(crate) fn parse_color(color: Option<&[f64]>, name: &str) -> Result<[f32; 4]> {
+    match color {
+        None => Ok([1.0; 4]),
+        Some(&[r, g, b]) => Ok([r as f32, g as f32, b as f32, 1.0]),
+        Some(&[r, g, b, a]) => Ok([r as f32, g as f32, b as f32, a as f32]),
+        Some(other) => Err(Error::from_reason(format!(
+            "{} must have 3 or 4 components, got {}",
+            name,
+            other.len()
+        ))),
+    }
+}
+
+/// Immediate-mode 2D renderer
+///
+/// Draw calls queue colored rectangles, lines, quads and sprites in pixel
+/// coordinates, with the origin at the top left of the target. `encode()`
+/// draws everything queued since the last call into a texture view in one
+/// render pass. Shapes are alpha blended in call order.
+#[napi]
+pub struct GpuDraw2D {
+    device: Arc<wgpu::Device>,
+    queue: Arc<wgpu::Queue>,
+    label: String,
+    module: wgpu::ShaderModule,
+    bind_group_layout: wgpu::BindGroupLayout,
+    layout: wgpu::PipelineLayout,
+    /// Render pipelines by target format and sample count
+    pipelines: HashMap<(wgpu::TextureFormat, u32), Arc<wgpu::RenderPipeline>>,
+    linear_sampler: wgpu::Sampler,
+    nearest_sampler: wgpu::Sampler,
+    /// 1x1 white texture sampled by untextured shapes
+    white: wgpu::TextureView,
+    vertices: Vec<f32>,
+    batches: Vec<Batch>,
+    transform: Transform,
+    saved: Vec<Transform>,
+}
+
+impl GpuDraw2D {
+    pub(crate) fn new(device: Arc<wgpu::Device>, queue: Arc<wgpu::Queue>, descriptor: Option<Draw2DDescriptor>) -> Self {
+        let label = descriptor
+            .and_then(|d| d.label)
+            .unwrap_or_else(|| "draw2d".to_string());
+        let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some(&label),
+            source: wgpu::ShaderSource::Wgsl(include_str!("shaders/draw2d.wgsl").into()),
+        });
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some(&label),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::VERTEX,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+            ],
+        });
+        let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some(&label),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+        let sampler = |filter| {
+            device.create_sampler(&wgpu::SamplerDescriptor {
+                label: Some(&label),
+                mag_filter: filter,
+                min_filter: filter,
+                ..Default::default()
+            })
+        };
+        let linear_sampler = sampler(wgpu::FilterMode::Linear);
+        let nearest_sampler = sampler(wgpu::FilterMode::Nearest);
+        let white = device
+            .create_texture_with_data(
+                &queue,
+                &wgpu::TextureDescriptor {
+                    label: Some(&format!("{} white", label)),
+                    size: wgpu::Extent3d {
+                        width: 1,
+                        height: 1,
+                        depth_or_array_layers: 1,
+                    },
+                    mip_level_count: 1,
+                    sample_count: 1,
+                    dimension: wgpu::TextureDimension::D2,
+                    format: wgpu::TextureFormat::Rgba8Unorm,
+                    usage: wgpu::TextureUsages::TEXTURE_BINDING,
+                    view_formats: &[],
+                },
+                wgpu::util::TextureDataOrder::LayerMajor,
+                &[255; 4],
+            )
+            .create_view(&Default::default());
+
+        Self {
+            device,
+            queue,
+            label,
+            module,
+            bind_group_layout,
+            layout,
+            pipelines: HashMap::new(),
+            linear_sampler,
+            nearest_sampler,
+            white,
+            vertices: Vec::new(),
+            batches: Vec::new(),
+            transform: IDENTITY,
+            saved: Vec::new(),
+        }
+    }
+
+    fn pipeline(&mut self, format: wgpu::TextureFormat, sample_count: u32) -> Arc<wgpu::RenderPipeline> {
+        let (device, module, layout, label) = (&self.device, &self.module, &self.layout, &self.label);
+        self.pipelines
+            .entry((format, sample_count))
+            .or_insert_with(|| {
+                Arc::new(device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                    label: Some(label),
+                    layout: Some(layout),
+                    vertex: wgpu::VertexState {
+                        module,
+                        entry_point: "vs_main",
+                        buffers: &[wgpu::VertexBufferLayout {
+                            array_stride: (VERTEX_FLOATS * 4) as u64,
+                            step_mode: wgpu::VertexStepMode::Vertex,
+                            attributes: &wgpu::vertex_attr_array![0 => Float32x2, 1 => Float32x2, 2 => Float32x4],
+                        }],
+                    },
+                    fragment: Some(wgpu::FragmentState {
+                        module,
+                        entry_point: "fs_main",
+                        targets: &[Some(wgpu::ColorTargetState {
+                            format,
+                            blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                            write_mask: wgpu::ColorWrites::ALL,
+                        })],
+                    }),
+                    primitive: wgpu::PrimitiveState::default(),
+                    depth_stencil: None,
+                    multisample: wgpu::MultisampleState {
+                        count: sample_count,
+                        ..Default::default()
+                    },
+                    multiview: None,
+                }))
+            })
+            .clone()
+    }
+
+    /// Queue a quad from its corners in drawing order, transformed by the current transform
+    fn push_quad(&mut self, corners: [[f32; 2]; 4], uvs: [[f32; 2]; 4], color: [f32; 4], image: Option<(Arc<wgpu::Texture>, bool)>) {
+        let start = (self.vertices.len() / VERTEX_FLOATS) as u32;
+        let [a, b, c, d, e, f] = self.transform;
+        for corner in [0, 1, 2, 0, 2, 3] {
+            let [x, y] = corners[corner];
+            self.vertices.extend([a * x + c * y + e, b * x + d * y + f]);
+            self.vertices.extend(uvs[corner]);
+            self.vertices.extend(color);
+        }
+        let end = start + 6;
+        let same_image = |batch: &Batch| match (&batch.image, &image) {
+            (None, None) => true,
+            (Some((current, current_linear)), Some((next, next_linear))) => {
+                Arc::ptr_eq(current, next) && current_linear == next_linear
+            }
+            _ => false,
+        };
+        match self.batches.last_mut() {
+            Some(batch) if same_image(batch) => batch.vertices.end = end,
+            _ => self.batches.push(Batch {
+                image,
+                vertices: start..end,
+            }),
+        }
+    }
+
+    fn record(
+        &mut self,
+        encoder: &mut wgpu::CommandEncoder,
+        target: &crate::GpuTextureView,
+        options: Option<Draw2DEncodeOptions>,
+    ) -> Result<()> {
+        let clear = match options.and_then(|o| o.clear) {
+            Some(color) => {
+                let [r, g, b, a] = parse_color(Some(&color), "Draw2D clear color")?;
+                Some(wgpu::Color {
+                    r: r as f64,
+                    g: g as f64,
+                    b: b as f64,
+                    a: a as f64,
+                })
+            }
+            None => None,
+        };
+        let vertices = std::mem::take(&mut self.vertices);
+        let batches = std::mem::take(&mut self.batches);
+        if batches.is_empty() && clear.is_none() {
+            return Ok(());
+        }
+
+        let pipeline = self.pipeline(target.format, target.sample_count);
+        let (width, height) = target.size;
+        let viewport = util::create_params_buffer(
+            &self.device,
+            &format!("{} viewport", self.label),
+            &[(width as f32).to_bits(), (height as f32).to_bits()],
+        );
+        let vertex_buffer = self.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some(&format!("{} vertices", self.label)),
+            contents: &vertices.iter().flat_map(|v| v.to_le_bytes()).collect::<Vec<u8>>(),
+            usage: wgpu::BufferUsages::VERTEX,
+        });
+        let bind_groups: Vec<wgpu::BindGroup> = batches
+            .iter()
+            .map(|batch| {
+                let sprite_view = batch.image.as_ref().map(|(texture, _)| {
+                    texture.create_view(&wgpu::TextureViewDescriptor {
+                        dimension: Some(wgpu::TextureViewDimension::D2),
+                        array_layer_count: Some(1),
+                        ..Default::default()
+                    })
+                });
+                let sampler = match batch.image {
+                    Some((_, false)) => &self.nearest_sampler,
+                    _ => &self.linear_sampler,
+                };
+                self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+                    label: Some(&self.label),
+                    layout: &self.bind_group_layout,
+                    entries: &[
+                        wgpu::BindGroupEntry {
+                            binding: 0,
+                            resource: viewport.as_entire_binding(),
+                        },
+                        wgpu::BindGroupEntry {
+                            binding: 1,
+                            resource: wgpu::BindingResource::Sampler(sampler),
+                        },
+                        wgpu::BindGroupEntry {
+                            binding: 2,
+                            resource: wgpu::BindingResource::TextureView(sprite_view.as_ref().unwrap_or(&self.white)),
+                        },
+                    ],
+                })
+            })
+            .collect();
+
+        let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some(&self.label),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: &target.view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: clear.map_or(wgpu::LoadOp::Load, wgpu::LoadOp::Clear),
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+        pass.set_pipeline(&pipeline);
+        pass.set_vertex_buffer(0, vertex_buffer.slice(..));
+        for (batch, bind_group) in batches.iter().zip(&bind_groups) {
+            pass.set_bind_group(0, bind_group, &[]);
+            pass.draw(batch.vertices.clone(), 0..1);
+        }
+        Ok(())
+    }
+}
+
+#[napi]
+impl GpuDraw2D {
+    /// Fill an axis-aligned rectangle
+    ///
+    /// `color` is [r, g, b, a?] from 0 to 1 (default white).
+    #[napi(catch_unwind)]
+    pub fn rect(&mut self, x: f64, y: f64, width: f64, height: f64, color: Option<Vec<f64>>) -> Result<()> {
+        let color = parse_color(color.as_deref(), "Draw2D color")?;
+        let (x0, y0, x1, y1) = (x as f32, y as f32, (x + width) as f32, (y + height) as f32);
+        self.push_quad([[x0, y0], [x1, y0], [x1, y1], [x0, y1]], [[0.0; 2]; 4], color, None);
+        Ok(())
+    }
+
+    /// Draw a line segment `width` pixels wide (default 1) with square ends
+    #[napi(catch_unwind)]
+    pub fn line(
+        &mut self,
+        x0: f64,
+        y0: f64,
+        x1: f64,
+        y1: f64,
+        color: Option<Vec<f64>>,
+        width: Option<f64>,
+    ) -> Result<()> {
+        let color = parse_color(color.as_deref(), "Draw2D color")?;
+        let (dx, dy) = ((x1 - x0) as f32, (y1 - y0) as f32);
+        let length = (dx * dx + dy * dy).sqrt();
+        if length == 0.0 {
+            return Ok(());
+        }
+        // Half the width along the normal of the segment
+        let half = width.unwrap_or(1.0) as f32 / 2.0;
+        let (nx, ny) = (-dy / length * half, dx / length * half);
+        let (x0, y0, x1, y1) = (x0 as f32, y0 as f32, x1 as f32, y1 as f32);
+        self.push_quad(
+            [[x0 + nx, y0 + ny], [x1 + nx, y1 + ny], [x1 - nx, y1 - ny], [x0 - nx, y0 - ny]],
+            [[0.0; 2]; 4],
+            color,
+            None,
+        );
+        Ok(())
+    }
+
+    /// Fill a quadrilateral given as [x0, y0, x1, y1, x2, y2, x3, y3] in drawing order
+    #[napi(catch_unwind)]
+    pub fn quad(&mut self, points: Vec<f64>, color: Option<Vec<f64>>) -> Result<()> {
+        let color = parse_color(color.as_deref(), "Draw2D color")?;
+        let &[x0, y0, x1, y1, x2, y2, x3, y3] = points.as_slice() else {
+            return Err(Error::from_reason(format!(
+                "Draw2D quad takes 8 coordinates, got {}",
+                points.len()
+            )));
+        };
+        let corners = [[x0, y0], [x1, y1], [x2, y2], [x3, y3]].map(|[x, y]| [x as f32, y as f32]);
+        self.push_quad(corners, [[0.0; 2]; 4], color, None);
+        Ok(())
+    }
+
+    /// Draw a texture, or a region of it, stretched over a rectangle
+    ///
+    /// The texture needs TEXTURE_BINDING usage and a filterable float format;
+    /// its first array layer and all mip levels are sampled.
+    #[napi(catch_unwind)]
+    pub fn sprite(
+        &mut self,
+        texture: &crate::GpuTexture,
+        x: f64,
+        y: f64,
+        width: f64,
+        height: f64,
+        options: Option<SpriteOptions>,
+    ) -> Result<()> {
+        let image = &texture.texture;
+        if image.dimension() != wgpu::TextureDimension::D2 || image.sample_count() != 1 {
+            return Err(Error::from_reason("Draw2D sprite must be a single-sampled 2D texture"));
+        }
+        if !image.usage().contains(wgpu::TextureUsages::TEXTURE_BINDING) {
+            return Err(Error::from_reason("Draw2D sprite needs TEXTURE_BINDING usage"));
+        }
+        if image.format().sample_type(None, Some(self.device.features()))
+            != Some(wgpu::TextureSampleType::Float { filterable: true })
+        {
+            return Err(Error::from_reason(format!(
+                "Draw2D sprite format {} isn't a filterable float format",
+                crate::parse::texture_format_name(image.format())
+            )));
+        }
+        let linear = match options.as_ref().and_then(|o| o.filter.as_deref()) {
+            None | Some("linear") => true,
+            Some("nearest") => false,
+            Some(other) => {
+                return Err(Error::from_reason(format!(
+                    "Draw2D sprite: unknown filter {}. Use 'linear' or 'nearest'",
+                    other
+                )))
+            }
+        };
+        let color = parse_color(options.as_ref().and_then(|o| o.color.as_deref()), "Draw2D sprite color")?;
+        let (u0, v0, u1, v1) = match options.as_ref().and_then(|o| o.source.as_deref()) {
+            None => (0.0, 0.0, 1.0, 1.0),
+            Some(&[sx, sy, sw, sh]) => {
+                let (w, h) = (image.width() as f64, image.height() as f64);
+                ((sx / w) as f32, (sy / h) as f32, ((sx + sw) / w) as f32, ((sy + sh) / h) as f32)
+            }
+            Some(other) => {
+                return Err(Error::from_reason(format!(
+                    "Draw2D sprite source must be [x, y, width, height], got {} numbers",
+                    other.len()
+                )))
+            }
+        };
+        let (x0, y0, x1, y1) = (x as f32, y as f32, (x + width) as f32, (y + height) as f32);
+        self.push_quad(
+            [[x0, y0], [x1, y0], [x1, y1], [x0, y1]],
+            [[u0, v0], [u1, v0], [u1, v1], [u0, v1]],
+            color,
+            Some((image.clone(), linear)),
+        );
+        Ok(())
+    }
+
+    /// Move the origin of later draws
+    #[napi(catch_unwind)]
+    pub fn translate(&mut self, x: f64, y: f64) {
+        let [a, b, c, d, e, f] = self.transform;
+        let (x, y) = (x as f32, y as f32);
+        self.transform = [a, b, c, d, a * x + c * y + e, b * x + d * y + f];
+    }
+
+    /// Scale later draws about the origin
+    #[napi(catch_unwind)]
+    pub fn scale(&mut self, x: f64, y: Option<f64>) {
+        let [a, b, c, d, e, f] = self.transform;
+        let (x, y) = (x as f32, y.unwrap_or(x) as f32);
+        self.transform = [a * x, b * x, c * y, d * y, e, f];
+    }
+
+    /// Rotate later draws about the origin, clockwise on screen
+    #[napi(catch_unwind)]
+    pub fn rotate(&mut self, radians: f64) {
+        let [a, b, c, d, e, f] = self.transform;
+        let (sin, cos) = (radians as f32).sin_cos();
+        self.transform = [a * cos + c * sin, b * cos + d * sin, c * cos - a * sin, d * cos - b * sin, e, f];
+    }
+
+    /// Replace the transform with [a, b, c, d, e, f], as in the canvas API
+    #[napi(catch_unwind, js_name = "setTransform")]
+    pub fn set_transform(&mut self, a: f64, b: f64, c: f64, d: f64, e: f64, f: f64) {
+        self.transform = [a, b, c, d, e, f].map(|v| v as f32);
+    }
+
+    /// Go back to drawing in target pixels
+    #[napi(catch_unwind, js_name = "resetTransform")]
+    pub fn reset_transform(&mut self) {
+        self.transform = IDENTITY;
+    }
+
+    /// Push the current transform onto a stack
+    #[napi(catch_unwind)]
+    pub fn save(&mut self) {
+        self.saved.push(self.transform);
+    }
+
+    /// Pop the transform pushed by the last `save()`
+    #[napi(catch_unwind)]
+    pub fn restore(&mut self) {
+        if let Some(transform) = self.saved.pop() {
+            self.transform = transform;
+        }
+    }
+
+    /// Record the queued draws into a render pass on `target`
+    ///
+    /// Draws over the current contents unless `clear` is given. The queue is
+    /// emptied; the transform is kept.
+    #[napi(catch_unwind)]
+    pub fn encode(
+        &mut self,
+        encoder: &crate::GpuCommandEncoder,
+        target: &crate::GpuTextureView,
+        options: Option<Draw2DEncodeOptions>,
+    ) -> Result<()> {
+        encoder.with_encoder(|enc| self.record(enc, target, options))
+    }
+
+    /// Draw the queued shapes immediately
+    ///
+    /// Convenience wrapper that encodes them into a fresh command buffer and submits it.
+    #[napi(catch_unwind)]
+    pub fn run(&mut self, target: &crate::GpuTextureView, options: Option<Draw2DEncodeOptions>) -> Result<()> {
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: Some(&self.label) });
+        self.record(&mut encoder, target, options)?;
+        self.queue.submit(Some(encoder.finish()));
+        Ok(())
+    }
+}
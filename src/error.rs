@@ -0,0 +1,342 @@
+//! Validation error reporting
+//!
+//! wgpu reports validation failures to the device's error scopes, or prints
+//! them through its uncaptured error handler. API calls run inside a private
+//! error scope instead, so a failure is thrown from the JavaScript call that
+//! caused it, with the method name and the descriptor values involved.
+
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use std::sync::{Arc, Mutex};
+
+/// Methods whose calls count as allocations for `oomEveryN`
+const ALLOCATING_METHODS: &[&str] = &[
+    "createBuffer",
+    "createBufferInit",
+    "createTexture",
+    "createMultisampleTexture",
+    "createQuerySet",
+];
+
+/// Faults set up with `device.enableFaultInjection()`
+#[derive(Default)]
+struct FaultInjection {
+    /// Every nth allocation fails as out of memory; 0 disables this
+    oom_every_n: u32,
+    allocations: u32,
+    fail_next_create: bool,
+}
+
+impl FaultInjection {
+    /// Error to raise instead of running `method`, if one is due
+    fn next_fault(&mut self, method: &str) -> Option<wgpu::Error> {
+        if !method.starts_with("create") {
+            return None;
+        }
+        if self.fail_next_create {
+            self.fail_next_create = false;
+            return Some(wgpu::Error::Validation {
+                source: injected_source(),
+                description: INJECTED_FAULT.to_string(),
+            });
+        }
+        if self.oom_every_n > 0 && ALLOCATING_METHODS.contains(&method) {
+            self.allocations += 1;
+            if self.allocations % self.oom_every_n == 0 {
+                return Some(wgpu::Error::OutOfMemory {
+                    source: injected_source(),
+                });
+            }
+        }
+        None
+    }
+}
+
+const INJECTED_FAULT: &str = "Injected by enableFaultInjection()";
+
+fn injected_source() -> Box<dyn std::error::Error + Send + 'static> {
+    Box::<dyn std::error::Error + Send + Sync>::from(INJECTED_FAULT)
+}
+
+/// An error captured by an error scope, returned by `popErrorScope()`
+#[napi(object)]
+pub struct CapturedError {
+    /// "validation" or "out-of-memory"
+    #[napi(js_name = "type")]
+    pub kind: String,
+    pub message: String,
+}
+
+impl From<wgpu::Error> for CapturedError {
+    fn from(error: wgpu::Error) -> Self {
+        let kind = match error {
+            wgpu::Error::OutOfMemory { .. } => "out-of-memory",
+            wgpu::Error::Validation { .. } => "validation",
+        };
+        Self {
+            kind: kind.to_string(),
+            message: error.to_string(),
+        }
+    }
+}
+
+/// An error scope pushed from JavaScript with `device.pushErrorScope()`
+struct UserScope {
+    filter: wgpu::ErrorFilter,
+    /// The first injected fault the scope captured; wgpu's own scope holds
+    /// any real error
+    injected: Option<CapturedError>,
+}
+
+impl UserScope {
+    fn captures(&self, error: &wgpu::Error) -> bool {
+        matches!(
+            (self.filter, error),
+            (wgpu::ErrorFilter::Validation, wgpu::Error::Validation { .. })
+                | (wgpu::ErrorFilter::OutOfMemory, wgpu::Error::OutOfMemory { .. })
+        )
+    }
+}
+
+/// Per-device error capture shared by the device, its queue and its encoders
+#[derive(Clone)]
+pub(crate) struct ErrorSink {
+    device: Arc<wgpu::Device>,
+    /// Error scopes pushed from JavaScript, innermost last
+    user_scopes: Arc<Mutex<Vec<UserScope>>>,
+    events: crate::events::DeviceEvents,
+    faults: Arc<Mutex<FaultInjection>>,
+}
+
+impl ErrorSink {
+    pub(crate) fn new(device: Arc<wgpu::Device>, events: crate::events::DeviceEvents) -> Self {
+        // wgpu's default handler panics, which would take down the process
+        let uncaptured_events = events.clone();
+        device.on_uncaptured_error(Box::new(move |error| report_uncaptured(&uncaptured_events, error)));
+        Self {
+            device,
+            user_scopes: Default::default(),
+            events,
+            faults: Default::default(),
+        }
+    }
+
+    /// Open an error scope for `device.pushErrorScope()`
+    pub(crate) fn push_user_scope(&self, filter: wgpu::ErrorFilter) {
+        let mut scopes = self.user_scopes.lock().unwrap();
+        self.device.push_error_scope(filter);
+        scopes.push(UserScope { filter, injected: None });
+    }
+
+    /// Close the innermost scope pushed from JavaScript and return the
+    /// error it captured, preferring an injected fault to a real error
+    pub(crate) fn pop_user_scope(&self) -> Result<Option<CapturedError>> {
+        let mut scopes = self.user_scopes.lock().unwrap();
+        // wgpu panics when popping an empty scope stack
+        let scope = scopes
+            .pop()
+            .ok_or_else(|| Error::from_reason("popErrorScope: no error scope to pop"))?;
+        // wgpu-core resolves error scopes immediately
+        let error = futures::executor::block_on(self.device.pop_error_scope());
+        Ok(scope.injected.or(error.map(Into::into)))
+    }
+
+    /// Make every `oom_every_n`th allocation (0 for none) and, with
+    /// `fail_next_create`, the next create call fail
+    ///
+    /// Resets the allocation count.
+    pub(crate) fn inject_faults(&self, oom_every_n: u32, fail_next_create: bool) {
+        *self.faults.lock().unwrap() = FaultInjection {
+            oom_every_n,
+            allocations: 0,
+            fail_next_create,
+        };
+    }
+
+    /// Throw if the device was destroyed or lost
+    ///
+    /// wgpu turns calls on an invalid device into errors that don't say why;
+    /// this names the method and the loss instead.
+    pub(crate) fn ensure_usable(&self, method: &str) -> Result<()> {
+        match self.events.lost_info() {
+            Some(lost) if lost.reason == "destroyed" => {
+                Err(Error::from_reason(format!("{}: device was destroyed", method)))
+            }
+            Some(lost) => Err(Error::from_reason(format!("{}: device was lost: {}", method, lost.message))),
+            None => Ok(()),
+        }
+    }
+
+    /// Run `f`, throwing any validation or out-of-memory error it raises
+    ///
+    /// `method` is the JavaScript method name and `details` describes the
+    /// arguments (labels, sizes, formats) to include in the message. While a
+    /// scope pushed from JavaScript is open, errors are left to that scope.
+    /// Injected faults are thrown without running `f`, or captured by the
+    /// innermost open scope with a matching filter.
+    pub(crate) fn check<T>(&self, method: &str, details: impl FnOnce() -> String, f: impl FnOnce() -> T) -> Result<T> {
+        self.check_diagnosed(method, details, || None, f)
+    }
+
+    /// Like `check()`, replacing the summary and causes of a validation
+    /// error with what `diagnose` returns, when it returns anything
+    ///
+    /// For checks that can explain a failure better than wgpu's first failed
+    /// rule, from state wgpu doesn't report.
+    pub(crate) fn check_diagnosed<T>(
+        &self,
+        method: &str,
+        details: impl FnOnce() -> String,
+        diagnose: impl FnOnce() -> Option<String>,
+        f: impl FnOnce() -> T,
+    ) -> Result<T> {
+        self.ensure_usable(method)?;
+        let fault = self.faults.lock().unwrap().next_fault(method);
+        {
+            let mut scopes = self.user_scopes.lock().unwrap();
+            if !scopes.is_empty() {
+                if let Some(error) = fault {
+                    match scopes.iter_mut().rev().find(|scope| scope.captures(&error)) {
+                        Some(scope) => {
+                            let message = format_error(method, &details(), &error);
+                            let mut captured = CapturedError::from(error);
+                            captured.message = message;
+                            scope.injected.get_or_insert(captured);
+                        }
+                        None => report_uncaptured(&self.events, error),
+                    }
+                }
+                drop(scopes);
+                return Ok(f());
+            }
+        }
+        if let Some(error) = fault {
+            return Err(self.throw(method, &details(), error));
+        }
+
+        let scopes = PrivateScopes::push(&self.device);
+        let value = f();
+        let (validation, out_of_memory) = scopes.pop();
+
+        match (validation, out_of_memory) {
+            (Some(error), _) => match diagnose() {
+                Some(diagnosis) => Err(Error::from_reason(with_method(method, &details(), &diagnosis))),
+                None => Err(self.throw(method, &details(), error)),
+            },
+            (None, Some(error)) => Err(self.throw(method, &details(), error)),
+            (None, None) => Ok(value),
+        }
+    }
+
+    /// Turn an error into the one to throw, warning listeners about running out of memory
+    fn throw(&self, method: &str, details: &str, error: wgpu::Error) -> Error {
+        let message = format_error(method, details, &error);
+        if matches!(error, wgpu::Error::OutOfMemory { .. }) {
+            self.events
+                .emit(crate::events::DeviceEvent::with_message("oomWarning", message.clone()));
+        }
+        Error::from_reason(message)
+    }
+}
+
+/// The out-of-memory and validation scopes `check()` wraps around a call
+///
+/// Dropping them unpopped, when the call panics, pops them anyway so the
+/// scope stack stays balanced for the scopes JavaScript pushes later.
+struct PrivateScopes<'a> {
+    device: &'a wgpu::Device,
+    open: bool,
+}
+
+impl<'a> PrivateScopes<'a> {
+    fn push(device: &'a wgpu::Device) -> Self {
+        device.push_error_scope(wgpu::ErrorFilter::OutOfMemory);
+        device.push_error_scope(wgpu::ErrorFilter::Validation);
+        Self { device, open: true }
+    }
+
+    /// Pop both scopes, returning the validation and out-of-memory errors
+    fn pop(mut self) -> (Option<wgpu::Error>, Option<wgpu::Error>) {
+        self.open = false;
+        // wgpu-core resolves error scopes immediately
+        let validation = futures::executor::block_on(self.device.pop_error_scope());
+        let out_of_memory = futures::executor::block_on(self.device.pop_error_scope());
+        (validation, out_of_memory)
+    }
+}
+
+impl Drop for PrivateScopes<'_> {
+    fn drop(&mut self) {
+        if self.open {
+            let _ = futures::executor::block_on(self.device.pop_error_scope());
+            let _ = futures::executor::block_on(self.device.pop_error_scope());
+        }
+    }
+}
+
+/// Report an error that no error scope captured
+///
+/// Goes to the device's event callback when one is set, otherwise to the
+/// `setLogCallback` callback when one is installed, stderr otherwise.
+fn report_uncaptured(events: &crate::events::DeviceEvents, error: wgpu::Error) {
+    let message = format_error("Uncaptured error", "", &error);
+    if events.emit_error(message.clone(), matches!(error, wgpu::Error::OutOfMemory { .. })) {
+        return;
+    }
+    if log::log_enabled!(target: "webgpu", log::Level::Error) {
+        log::error!(target: "webgpu", "{}", message);
+    } else {
+        eprintln!("{}", message);
+    }
+}
+
+/// The message a panic was raised with
+pub(crate) fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+/// Format a wgpu error as `method(details): summary` followed by the indented cause chain
+pub(crate) fn format_error(method: &str, details: &str, error: &wgpu::Error) -> String {
+    let (summary, causes) = match error {
+        wgpu::Error::Validation { description, .. } => {
+            // The description reads "Validation Error\n\nCaused by:\n    In Device::..., label = '...'\n      ..."
+            let causes = description
+                .split_once("Caused by:")
+                .map(|(_, causes)| causes)
+                .unwrap_or(description);
+            ("validation failed", causes.to_string())
+        }
+        wgpu::Error::OutOfMemory { source } => ("out of memory", source.to_string()),
+    };
+
+    let mut message = with_method(method, details, summary);
+    for line in causes.lines().map(str::trim_end).filter(|line| !line.trim().is_empty()) {
+        message.push('\n');
+        message.push_str("  ");
+        message.push_str(line.strip_prefix("    ").unwrap_or(line));
+    }
+    message
+}
+
+/// Prefix `text` with `method(details): `, or `method: ` without details
+fn with_method(method: &str, details: &str, text: &str) -> String {
+    if details.is_empty() {
+        format!("{}: {}", method, text)
+    } else {
+        format!("{}({}): {}", method, details, text)
+    }
+}
+
+/// Quote a resource label for error details
+pub(crate) fn describe_label(label: Option<&str>) -> String {
+    match label {
+        Some(label) => format!("'{}'", label),
+        None => "(none)".to_string(),
+    }
+}
@@ -0,0 +1,152 @@
+//! Device lifecycle events
+//!
+//! Device loss and errors are reported to one callback per device, which the
+//! JavaScript `GpuDevice` wrapper re-emits as EventEmitter events alongside
+//! the submission events it raises itself. They can be raised on the poll
+//! thread and in wgpu callbacks, so they are queued through a threadsafe
+//! function.
+
+use crate::GpuDevice;
+use napi::bindgen_prelude::*;
+use napi::threadsafe_function::{ErrorStrategy, ThreadsafeFunction, ThreadsafeFunctionCallMode};
+use napi::{Env, JsFunction};
+use napi_derive::napi;
+use std::sync::{Arc, Mutex};
+
+/// An event passed to the `setEventCallback` callback
+#[napi(object)]
+pub struct DeviceEvent {
+    /// "deviceLost", "uncapturedError" or "oomWarning"
+    #[napi(js_name = "type")]
+    pub kind: String,
+    /// "unknown" or "destroyed", for "deviceLost"
+    pub reason: Option<String>,
+    /// Description of the error or loss
+    pub message: Option<String>,
+}
+
+impl DeviceEvent {
+    fn new(kind: &str) -> Self {
+        Self {
+            kind: kind.to_string(),
+            reason: None,
+            message: None,
+        }
+    }
+
+    pub(crate) fn with_message(kind: &str, message: String) -> Self {
+        Self {
+            message: Some(message),
+            ..Self::new(kind)
+        }
+    }
+}
+
+/// Why a device was lost, as `device.lost` resolves with
+#[napi(object)]
+#[derive(Clone)]
+pub struct DeviceLostInfo {
+    /// "destroyed" after `device.destroy()`, otherwise "unknown"
+    pub reason: String,
+    pub message: String,
+}
+
+type EventCallback = ThreadsafeFunction<DeviceEvent, ErrorStrategy::Fatal>;
+
+/// Event callback and loss state shared by a device and its error sink
+#[derive(Clone, Default)]
+pub(crate) struct DeviceEvents {
+    callback: Arc<Mutex<Option<EventCallback>>>,
+    lost: Arc<Mutex<Option<DeviceLostInfo>>>,
+}
+
+impl DeviceEvents {
+    /// Queue `event` to the callback; returns false if there is none
+    pub(crate) fn emit(&self, event: DeviceEvent) -> bool {
+        match self.callback.lock().as_deref() {
+            Ok(Some(callback)) => {
+                callback.call(event, ThreadsafeFunctionCallMode::NonBlocking);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Report an error no error scope captured
+    pub(crate) fn emit_error(&self, message: String, out_of_memory: bool) -> bool {
+        if out_of_memory {
+            self.emit(DeviceEvent::with_message("oomWarning", message.clone()));
+        }
+        self.emit(DeviceEvent::with_message("uncapturedError", message))
+    }
+
+    /// Mark the device lost and emit "deviceLost"; only the first loss counts
+    pub(crate) fn lose(&self, reason: &str, message: String) {
+        match self.lost.lock() {
+            Ok(mut lost) if lost.is_none() => {
+                *lost = Some(DeviceLostInfo {
+                    reason: reason.to_string(),
+                    message: message.clone(),
+                })
+            }
+            _ => return,
+        }
+        self.emit(DeviceEvent {
+            reason: Some(reason.to_string()),
+            message: Some(message),
+            ..DeviceEvent::new("deviceLost")
+        });
+    }
+
+    /// How the device was lost, or `None` while it's usable
+    pub(crate) fn lost_info(&self) -> Option<DeviceLostInfo> {
+        self.lost.lock().ok().and_then(|lost| lost.clone())
+    }
+
+    /// Forward wgpu's device lost callback
+    pub(crate) fn watch_device_lost(&self, device: &wgpu::Device) {
+        let events = self.clone();
+        device.set_device_lost_callback(move |reason, message| {
+            let reason = match reason {
+                wgpu::DeviceLostReason::Unknown => "unknown",
+                // Already recorded by `destroy()`, which is what triggers it
+                wgpu::DeviceLostReason::Destroyed => "destroyed",
+                // wgpu-specific: the device was dropped or the callback replaced
+                wgpu::DeviceLostReason::Dropped | wgpu::DeviceLostReason::ReplacedCallback => return,
+            };
+            events.lose(reason, message);
+        });
+    }
+}
+
+#[napi]
+impl GpuDevice {
+    /// Receive this device's lifecycle events (extension)
+    ///
+    /// The `GpuDevice` wrapper emits them as EventEmitter events; use that
+    /// instead of calling this directly. Passing no callback stops delivery,
+    /// and uncaptured errors go to the log again. The callback doesn't keep
+    /// the process alive.
+    #[napi(
+        catch_unwind,
+        js_name = "setEventCallback",
+        ts_args_type = "callback?: ((event: DeviceEvent) => void) | null"
+    )]
+    pub fn set_event_callback(&self, env: Env, callback: Option<JsFunction>) -> Result<()> {
+        let callback = match callback {
+            Some(callback) => {
+                let mut callback: EventCallback = callback.create_threadsafe_function(0, |ctx| Ok(vec![ctx.value]))?;
+                callback.unref(&env)?;
+                Some(callback)
+            }
+            None => None,
+        };
+        let previous = match self.events.callback.lock() {
+            Ok(mut current) => std::mem::replace(&mut *current, callback),
+            Err(_) => return Err(Error::from_reason("Device event state is poisoned")),
+        };
+        // Released outside the lock
+        drop(previous);
+        Ok(())
+    }
+}
@@ -1,7 +1,88 @@
+use napi::bindgen_prelude::*;
 use napi_derive::napi;
 
+/// wgpu features that WebGPU defines, whose names match the flag names
+const WEBGPU_FEATURES: wgpu::Features = wgpu::Features::DEPTH_CLIP_CONTROL
+    .union(wgpu::Features::DEPTH32FLOAT_STENCIL8)
+    .union(wgpu::Features::TEXTURE_COMPRESSION_BC)
+    .union(wgpu::Features::TEXTURE_COMPRESSION_ETC2)
+    .union(wgpu::Features::TEXTURE_COMPRESSION_ASTC)
+    .union(wgpu::Features::TIMESTAMP_QUERY)
+    .union(wgpu::Features::INDIRECT_FIRST_INSTANCE)
+    .union(wgpu::Features::SHADER_F16)
+    .union(wgpu::Features::RG11B10UFLOAT_RENDERABLE)
+    .union(wgpu::Features::BGRA8UNORM_STORAGE)
+    .union(wgpu::Features::FLOAT32_FILTERABLE);
+
+/// WGSL language feature reported with the features, see
+/// `supports_read_write_storage_textures`
+pub(crate) const READ_WRITE_STORAGE_TEXTURES: &str = "readonly-and-readwrite-storage-textures";
+
+/// Name of a wgpu feature flag
+///
+/// The WebGPU name for standard features, e.g. "shader-f16"; native-only
+/// features get a "wgpu-" prefix, e.g. "wgpu-push-constants".
+fn feature_name(flag_name: &str, flag: wgpu::Features) -> String {
+    let name = flag_name.to_lowercase().replace('_', "-");
+    if WEBGPU_FEATURES.contains(flag) {
+        name
+    } else {
+        format!("wgpu-{}", name)
+    }
+}
+
+/// Names of all flags in `features`, in flag order
+pub(crate) fn feature_names(features: wgpu::Features) -> Vec<String> {
+    features
+        .iter_names()
+        .map(|(flag_name, flag)| feature_name(flag_name, flag))
+        .collect()
+}
+
+/// The flag named `name`, as returned by `feature_names`
+pub(crate) fn parse_feature(name: &str) -> Option<wgpu::Features> {
+    wgpu::Features::all()
+        .iter_names()
+        .find(|(flag_name, flag)| feature_name(flag_name, *flag) == name)
+        .map(|(_, flag)| flag)
+}
+
+/// The features a device is requested with
+///
+/// Timestamp queries and adapter-specific format features when available,
+/// plus `requested`; fails listing unknown names and unsupported features.
+pub(crate) fn required_features(adapter: &wgpu::Adapter, requested: &[String]) -> Result<wgpu::Features> {
+    let supported = adapter.features();
+    // Adapter-specific format features enable read-only and read-write storage textures
+    let mut features = (wgpu::Features::TIMESTAMP_QUERY | wgpu::Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES)
+        & supported;
+    let mut unsupported = Vec::new();
+    for name in requested {
+        if name == READ_WRITE_STORAGE_TEXTURES {
+            if !crate::adapter::supports_read_write_storage_textures(adapter) {
+                unsupported.push(name.as_str());
+            }
+            continue;
+        }
+        let flag = parse_feature(name).ok_or_else(|| Error::from_reason(format!("Unknown feature: {}", name)))?;
+        if supported.contains(flag) {
+            features |= flag;
+        } else {
+            unsupported.push(name.as_str());
+        }
+    }
+    if !unsupported.is_empty() {
+        return Err(Error::from_reason(format!(
+            "Adapter doesn't support the required features: {}",
+            unsupported.join(", ")
+        )));
+    }
+    Ok(features)
+}
+
 /// GPU supported features following WebGPU spec
 #[napi]
+#[derive(Clone)]
 pub struct GpuSupportedFeatures {
     pub(crate) features: wgpu::Features,
 }
@@ -9,27 +90,23 @@ pub struct GpuSupportedFeatures {
 #[napi]
 impl GpuSupportedFeatures {
     /// Check if a feature is supported
-    #[napi]
+    #[napi(catch_unwind)]
     pub fn has(&self, feature: String) -> bool {
-        match feature.as_str() {
-            "depth-clip-control" => self.features.contains(wgpu::Features::DEPTH_CLIP_CONTROL),
-            "depth32float-stencil8" => self.features.contains(wgpu::Features::DEPTH32FLOAT_STENCIL8),
-            "texture-compression-bc" => self.features.contains(wgpu::Features::TEXTURE_COMPRESSION_BC),
-            "texture-compression-etc2" => self.features.contains(wgpu::Features::TEXTURE_COMPRESSION_ETC2),
-            "texture-compression-astc" => self.features.contains(wgpu::Features::TEXTURE_COMPRESSION_ASTC),
-            "timestamp-query" => self.features.contains(wgpu::Features::TIMESTAMP_QUERY),
-            "indirect-first-instance" => self.features.contains(wgpu::Features::INDIRECT_FIRST_INSTANCE),
-            "shader-f16" => self.features.contains(wgpu::Features::SHADER_F16),
-            "rg11b10ufloat-renderable" => self.features.contains(wgpu::Features::RG11B10UFLOAT_RENDERABLE),
-            "bgra8unorm-storage" => self.features.contains(wgpu::Features::BGRA8UNORM_STORAGE),
-            "float32-filterable" => self.features.contains(wgpu::Features::FLOAT32_FILTERABLE),
-            _ => false,
-        }
+        parse_feature(&feature).is_some_and(|flag| self.features.contains(flag))
     }
 
     /// Get the number of features supported
-    #[napi(getter)]
+    #[napi(catch_unwind, getter)]
     pub fn size(&self) -> u32 {
         self.features.bits().count_ones()
     }
+
+    /// Names of the supported features, as accepted by `has`
+    ///
+    /// The JavaScript wrapper builds the rest of the set-like interface
+    /// (iteration, `keys`, `entries`, `forEach`) on this.
+    #[napi(catch_unwind)]
+    pub fn values(&self) -> Vec<String> {
+        feature_names(self.features)
+    }
 }
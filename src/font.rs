@@ -0,0 +1,515 @@
+//! Minimal TrueType reader and glyph rasterizer
+//!
+//! Reads what horizontal text layout needs — character mapping, advances and
+//! `glyf` outlines — and rasterizes outlines to coverage by accumulating the
+//! exact area each edge covers in every pixel. CFF-flavored OpenType fonts,
+//! hinting and kerning aren't supported.
+
+use napi::bindgen_prelude::*;
+
+/// Composite glyphs nested deeper than this are ignored
+const MAX_COMPONENT_DEPTH: u32 = 8;
+
+fn read_u16(data: &[u8], offset: usize) -> Option<u16> {
+    Some(u16::from_be_bytes(data.get(offset..offset + 2)?.try_into().ok()?))
+}
+
+fn read_i16(data: &[u8], offset: usize) -> Option<i16> {
+    read_u16(data, offset).map(|value| value as i16)
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+    Some(u32::from_be_bytes(data.get(offset..offset + 4)?.try_into().ok()?))
+}
+
+/// 2x3 affine transform [a, b, c, d, e, f] of font units
+type Affine = [f32; 6];
+
+fn apply(transform: &Affine, [x, y]: [f32; 2]) -> [f32; 2] {
+    let [a, b, c, d, e, f] = *transform;
+    [a * x + c * y + e, b * x + d * y + f]
+}
+
+/// Character mapping subtable formats we read
+#[derive(Clone, Copy)]
+enum Cmap {
+    /// Segment mapping to delta values (BMP only)
+    Format4(usize),
+    /// Segmented coverage (full Unicode)
+    Format12(usize),
+}
+
+/// Coverage of one glyph, positioned relative to the pen on the baseline
+pub(crate) struct GlyphBitmap {
+    /// Offset of the bitmap's top left corner from the pen, in pixels with y down
+    pub(crate) left: i32,
+    pub(crate) top: i32,
+    pub(crate) width: u32,
+    pub(crate) height: u32,
+    pub(crate) coverage: Vec<u8>,
+}
+
+/// A parsed TrueType font
+pub(crate) struct Font {
+    data: Vec<u8>,
+    pub(crate) units_per_em: f32,
+    pub(crate) ascent: f32,
+    pub(crate) descent: f32,
+    pub(crate) line_gap: f32,
+    num_glyphs: u16,
+    num_h_metrics: u16,
+    long_loca: bool,
+    cmap: Cmap,
+    hmtx: usize,
+    loca: usize,
+    glyf: usize,
+}
+
+impl Font {
+    /// Parse a .ttf file, or the first font of a .ttc collection
+    pub(crate) fn parse(data: Vec<u8>) -> Result<Self> {
+        Self::parse_tables(data).ok_or_else(|| Error::from_reason("Font data is truncated or malformed"))?
+    }
+
+    fn parse_tables(data: Vec<u8>) -> Option<Result<Self>> {
+        let mut start = 0;
+        let mut version = read_u32(&data, 0)?;
+        if version == u32::from_be_bytes(*b"ttcf") {
+            start = read_u32(&data, 12)? as usize;
+            version = read_u32(&data, start)?;
+        }
+        if version == u32::from_be_bytes(*b"OTTO") {
+            return Some(Err(Error::from_reason(
+                "Font has CFF outlines; only TrueType (glyf) outlines are supported",
+            )));
+        }
+        if version != 0x0001_0000 && version != u32::from_be_bytes(*b"true") {
+            return Some(Err(Error::from_reason("Data isn't a TrueType font")));
+        }
+
+        let num_tables = read_u16(&data, start + 4)? as usize;
+        let table = |tag: &[u8; 4]| -> Option<usize> {
+            (0..num_tables).find_map(|i| {
+                let record = start + 12 + i * 16;
+                (data.get(record..record + 4)? == tag).then(|| read_u32(&data, record + 8).map(|o| o as usize))?
+            })
+        };
+        let missing = |name: &str| Some(Err(Error::from_reason(format!("Font has no {} table", name))));
+        let Some(head) = table(b"head") else { return missing("head") };
+        let Some(hhea) = table(b"hhea") else { return missing("hhea") };
+        let Some(maxp) = table(b"maxp") else { return missing("maxp") };
+        let Some(hmtx) = table(b"hmtx") else { return missing("hmtx") };
+        let Some(cmap) = table(b"cmap") else { return missing("cmap") };
+        let (Some(loca), Some(glyf)) = (table(b"loca"), table(b"glyf")) else {
+            return missing("glyf");
+        };
+
+        // Prefer full Unicode (format 12) over BMP-only (format 4) subtables
+        let mut subtable = None;
+        for i in 0..read_u16(&data, cmap + 2)? as usize {
+            let record = cmap + 4 + i * 8;
+            let (platform, encoding) = (read_u16(&data, record)?, read_u16(&data, record + 2)?);
+            let unicode = platform == 0 || (platform == 3 && (encoding == 1 || encoding == 10));
+            if !unicode {
+                continue;
+            }
+            let offset = cmap + read_u32(&data, record + 4)? as usize;
+            match read_u16(&data, offset)? {
+                12 => subtable = Some(Cmap::Format12(offset)),
+                4 if subtable.is_none() => subtable = Some(Cmap::Format4(offset)),
+                _ => {}
+            }
+        }
+        let Some(cmap) = subtable else {
+            return Some(Err(Error::from_reason("Font has no Unicode character map")));
+        };
+
+        let units_per_em = read_u16(&data, head + 18)?;
+        if units_per_em == 0 {
+            return None;
+        }
+        Some(Ok(Self {
+            units_per_em: units_per_em as f32,
+            long_loca: read_i16(&data, head + 50)? != 0,
+            ascent: read_i16(&data, hhea + 4)? as f32,
+            descent: read_i16(&data, hhea + 6)? as f32,
+            line_gap: read_i16(&data, hhea + 8)? as f32,
+            num_h_metrics: read_u16(&data, hhea + 34)?,
+            num_glyphs: read_u16(&data, maxp + 4)?,
+            cmap,
+            hmtx,
+            loca,
+            glyf,
+            data,
+        }))
+    }
+
+    /// Glyph for a character; 0 (the missing glyph) if the font lacks it
+    pub(crate) fn glyph_index(&self, character: char) -> u16 {
+        let code = character as u32;
+        let data = &self.data;
+        let glyph = match self.cmap {
+            Cmap::Format4(offset) => (|| {
+                if code > 0xffff {
+                    return None;
+                }
+                let segments = read_u16(data, offset + 6)? as usize / 2;
+                let ends = offset + 14;
+                let starts = ends + segments * 2 + 2;
+                let deltas = starts + segments * 2;
+                let range_offsets = deltas + segments * 2;
+                for i in 0..segments {
+                    if code > read_u16(data, ends + i * 2)? as u32 {
+                        continue;
+                    }
+                    let start = read_u16(data, starts + i * 2)? as u32;
+                    if code < start {
+                        return None;
+                    }
+                    let delta = read_u16(data, deltas + i * 2)?;
+                    let range_offset = read_u16(data, range_offsets + i * 2)? as usize;
+                    if range_offset == 0 {
+                        return Some((code as u16).wrapping_add(delta));
+                    }
+                    let address = range_offsets + i * 2 + range_offset + (code - start) as usize * 2;
+                    let glyph = read_u16(data, address)?;
+                    return (glyph != 0).then(|| glyph.wrapping_add(delta));
+                }
+                None
+            })(),
+            Cmap::Format12(offset) => (|| {
+                let groups = read_u32(data, offset + 12)? as usize;
+                for i in 0..groups {
+                    let group = offset + 16 + i * 12;
+                    let start = read_u32(data, group)?;
+                    if code >= start && code <= read_u32(data, group + 4)? {
+                        return Some((read_u32(data, group + 8)? + code - start) as u16);
+                    }
+                }
+                None
+            })(),
+        };
+        glyph.filter(|&glyph| glyph < self.num_glyphs).unwrap_or(0)
+    }
+
+    /// Horizontal advance in font units
+    pub(crate) fn advance(&self, glyph: u16) -> f32 {
+        let metric = glyph.min(self.num_h_metrics.saturating_sub(1)) as usize;
+        read_u16(&self.data, self.hmtx + metric * 4).unwrap_or(0) as f32
+    }
+
+    fn glyph_data(&self, glyph: u16) -> Option<&[u8]> {
+        if glyph >= self.num_glyphs {
+            return None;
+        }
+        let glyph = glyph as usize;
+        let (start, end) = if self.long_loca {
+            let entry = self.loca + glyph * 4;
+            (read_u32(&self.data, entry)? as usize, read_u32(&self.data, entry + 4)? as usize)
+        } else {
+            let entry = self.loca + glyph * 2;
+            (read_u16(&self.data, entry)? as usize * 2, read_u16(&self.data, entry + 2)? as usize * 2)
+        };
+        if start >= end {
+            return None;
+        }
+        self.data.get(self.glyf + start..self.glyf + end)
+    }
+
+    /// Flatten a glyph outline into line segments [x0, y0, x1, y1] in font units
+    ///
+    /// Curves are split finely enough for drawing at `scale` pixels per unit.
+    fn outline(&self, glyph: u16, transform: &Affine, scale: f32, depth: u32, segments: &mut Vec<[f32; 4]>) -> Option<()> {
+        let data = self.glyph_data(glyph)?;
+        let contours = read_i16(data, 0)?;
+        if contours < 0 {
+            return self.composite_outline(data, transform, scale, depth, segments);
+        }
+
+        let contours = contours as usize;
+        let mut ends = Vec::with_capacity(contours);
+        for i in 0..contours {
+            ends.push(read_u16(data, 10 + i * 2)? as usize);
+        }
+        let num_points = ends.last().map_or(0, |end| end + 1);
+        let instructions = read_u16(data, 10 + contours * 2)? as usize;
+        let mut cursor = 12 + contours * 2 + instructions;
+
+        let mut flags = Vec::with_capacity(num_points);
+        while flags.len() < num_points {
+            let flag = *data.get(cursor)?;
+            cursor += 1;
+            flags.push(flag);
+            if flag & 0x08 != 0 {
+                let repeat = *data.get(cursor)?;
+                cursor += 1;
+                flags.extend(std::iter::repeat(flag).take(repeat as usize));
+            }
+        }
+        flags.truncate(num_points);
+
+        // Coordinates are deltas, either one unsigned byte with a sign flag
+        // or an i16, or repeated when the "same" flag is set
+        let mut read_coordinates = |short: u8, same: u8| -> Option<Vec<f32>> {
+            let mut value = 0i32;
+            let mut coordinates = Vec::with_capacity(num_points);
+            for &flag in &flags {
+                if flag & short != 0 {
+                    let delta = *data.get(cursor)? as i32;
+                    cursor += 1;
+                    value += if flag & same != 0 { delta } else { -delta };
+                } else if flag & same == 0 {
+                    value += read_i16(data, cursor)? as i32;
+                    cursor += 2;
+                }
+                coordinates.push(value as f32);
+            }
+            Some(coordinates)
+        };
+        let xs = read_coordinates(0x02, 0x10)?;
+        let ys = read_coordinates(0x04, 0x20)?;
+
+        let mut first = 0;
+        for end in ends {
+            if end < first || end >= num_points {
+                return None;
+            }
+            let points: Vec<([f32; 2], bool)> = (first..=end)
+                .map(|i| (apply(transform, [xs[i], ys[i]]), flags[i] & 0x01 != 0))
+                .collect();
+            flatten_contour(&points, scale, segments);
+            first = end + 1;
+        }
+        Some(())
+    }
+
+    fn composite_outline(&self, data: &[u8], transform: &Affine, scale: f32, depth: u32, segments: &mut Vec<[f32; 4]>) -> Option<()> {
+        if depth >= MAX_COMPONENT_DEPTH {
+            return None;
+        }
+        let f2dot14 = |offset| read_i16(data, offset).map(|value| value as f32 / 16384.0);
+        let mut cursor = 10;
+        loop {
+            let flags = read_u16(data, cursor)?;
+            let glyph = read_u16(data, cursor + 2)?;
+            cursor += 4;
+            let (arg1, arg2) = if flags & 0x0001 != 0 {
+                cursor += 4;
+                (read_i16(data, cursor - 4)? as f32, read_i16(data, cursor - 2)? as f32)
+            } else {
+                cursor += 2;
+                (*data.get(cursor - 2)? as i8 as f32, *data.get(cursor - 1)? as i8 as f32)
+            };
+            // Point-matched placement (arguments aren't offsets) is treated as no offset
+            let (dx, dy) = if flags & 0x0002 != 0 { (arg1, arg2) } else { (0.0, 0.0) };
+            let (a, b, c, d) = if flags & 0x0008 != 0 {
+                cursor += 2;
+                let s = f2dot14(cursor - 2)?;
+                (s, 0.0, 0.0, s)
+            } else if flags & 0x0040 != 0 {
+                cursor += 4;
+                (f2dot14(cursor - 4)?, 0.0, 0.0, f2dot14(cursor - 2)?)
+            } else if flags & 0x0080 != 0 {
+                cursor += 8;
+                (f2dot14(cursor - 8)?, f2dot14(cursor - 6)?, f2dot14(cursor - 4)?, f2dot14(cursor - 2)?)
+            } else {
+                (1.0, 0.0, 0.0, 1.0)
+            };
+
+            // Component transform, then ours
+            let [pa, pb, pc, pd, ..] = *transform;
+            let [e, f] = apply(transform, [dx, dy]);
+            let component = [
+                pa * a + pc * b,
+                pb * a + pd * b,
+                pa * c + pc * d,
+                pb * c + pd * d,
+                e,
+                f,
+            ];
+            // Missing or malformed components leave the rest of the glyph intact
+            let _ = self.outline(glyph, &component, scale, depth + 1, segments);
+
+            if flags & 0x0020 == 0 {
+                return Some(());
+            }
+        }
+    }
+
+    /// Rasterize a glyph at `size` pixels per em; None for glyphs without ink
+    pub(crate) fn rasterize(&self, glyph: u16, size: f32) -> Option<GlyphBitmap> {
+        let scale = size / self.units_per_em;
+        let mut segments = Vec::new();
+        self.outline(glyph, &[1.0, 0.0, 0.0, 1.0, 0.0, 0.0], scale, 0, &mut segments);
+        if segments.is_empty() {
+            return None;
+        }
+
+        // Pixel space has y pointing down
+        let to_pixels = |x: f32, y: f32| [x * scale, -y * scale];
+        let (mut min, mut max) = ([f32::MAX; 2], [f32::MIN; 2]);
+        for &[x0, y0, x1, y1] in &segments {
+            for [x, y] in [to_pixels(x0, y0), to_pixels(x1, y1)] {
+                min = [min[0].min(x), min[1].min(y)];
+                max = [max[0].max(x), max[1].max(y)];
+            }
+        }
+        let (left, top) = (min[0].floor(), min[1].floor());
+        let width = (max[0].ceil() - left) as usize;
+        let height = (max[1].ceil() - top) as usize;
+        if width == 0 || height == 0 {
+            return None;
+        }
+
+        let mut raster = Raster::new(width, height);
+        for &[x0, y0, x1, y1] in &segments {
+            let [px0, py0] = to_pixels(x0, y0);
+            let [px1, py1] = to_pixels(x1, y1);
+            raster.line([px0 - left, py0 - top], [px1 - left, py1 - top]);
+        }
+        Some(GlyphBitmap {
+            left: left as i32,
+            top: top as i32,
+            width: width as u32,
+            height: height as u32,
+            coverage: raster.coverage(),
+        })
+    }
+}
+
+/// Split a TrueType contour of on- and off-curve points into line segments
+fn flatten_contour(points: &[([f32; 2], bool)], scale: f32, segments: &mut Vec<[f32; 4]>) {
+    let Some(&(last, last_on)) = points.last() else {
+        return;
+    };
+    let midpoint = |a: [f32; 2], b: [f32; 2]| [(a[0] + b[0]) / 2.0, (a[1] + b[1]) / 2.0];
+    // Start on an on-curve point; between two off-curve points one is implied
+    let (start, skip) = match points.iter().position(|&(_, on)| on) {
+        Some(first) => (points[first].0, first + 1),
+        None if last_on => (last, 0),
+        None => (midpoint(last, points[0].0), 0),
+    };
+    let mut line = |from: [f32; 2], to: [f32; 2]| segments.push([from[0], from[1], to[0], to[1]]);
+    let quad = |from: [f32; 2], control: [f32; 2], to: [f32; 2], line: &mut dyn FnMut([f32; 2], [f32; 2])| {
+        // Subdivide so the chords stay within a fraction of a pixel of the curve
+        let deviation = ((from[0] - 2.0 * control[0] + to[0]).hypot(from[1] - 2.0 * control[1] + to[1])) * scale;
+        let steps = (deviation * 4.0).sqrt().ceil().clamp(1.0, 32.0) as usize;
+        let mut previous = from;
+        for step in 1..=steps {
+            let t = step as f32 / steps as f32;
+            let u = 1.0 - t;
+            let point = [
+                u * u * from[0] + 2.0 * u * t * control[0] + t * t * to[0],
+                u * u * from[1] + 2.0 * u * t * control[1] + t * t * to[1],
+            ];
+            line(previous, point);
+            previous = point;
+        }
+    };
+
+    let mut pen = start;
+    let mut control: Option<[f32; 2]> = None;
+    for k in 0..points.len() {
+        let (point, on_curve) = points[(skip + k) % points.len()];
+        if on_curve {
+            match control.take() {
+                Some(c) => quad(pen, c, point, &mut line),
+                None => line(pen, point),
+            }
+            pen = point;
+        } else {
+            if let Some(c) = control {
+                let implied = midpoint(c, point);
+                quad(pen, c, implied, &mut line);
+                pen = implied;
+            }
+            control = Some(point);
+        }
+    }
+    match control {
+        Some(c) => quad(pen, c, start, &mut line),
+        None if pen != start => line(pen, start),
+        None => {}
+    }
+}
+
+/// Signed area accumulation buffer
+///
+/// Each edge adds the area it covers to the left of itself in every pixel it
+/// crosses, signed by direction; a running sum along each row then gives the
+/// coverage of every pixel under the nonzero rule for non-overlapping contours.
+struct Raster {
+    width: usize,
+    height: usize,
+    /// One extra cell per row end for contributions right of the last pixel
+    areas: Vec<f32>,
+}
+
+impl Raster {
+    fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            areas: vec![0.0; width * height + 2],
+        }
+    }
+
+    fn line(&mut self, from: [f32; 2], to: [f32; 2]) {
+        let clamp = |[x, y]: [f32; 2]| [x.clamp(0.0, self.width as f32), y.clamp(0.0, self.height as f32)];
+        let (from, to) = (clamp(from), clamp(to));
+        if from[1] == to[1] {
+            return;
+        }
+        let (direction, top, bottom) = if from[1] < to[1] { (1.0, from, to) } else { (-1.0, to, from) };
+        let dxdy = (bottom[0] - top[0]) / (bottom[1] - top[1]);
+        let mut x = top[0];
+        for row in top[1] as usize..self.height.min(bottom[1].ceil() as usize) {
+            let row_start = row * self.width;
+            let dy = ((row + 1) as f32).min(bottom[1]) - (row as f32).max(top[1]);
+            let x_next = x + dxdy * dy;
+            let area = dy * direction;
+            let (x0, x1) = if x < x_next { (x, x_next) } else { (x_next, x) };
+            let x0_floor = x0.floor();
+            let x1_ceil = x1.ceil();
+            let (i0, i1) = (x0_floor as usize, x1_ceil as usize);
+            if i1 <= i0 + 1 {
+                // Within one pixel: split by the mean x
+                let fraction = 0.5 * (x + x_next) - x0_floor;
+                self.areas[row_start + i0] += area - area * fraction;
+                self.areas[row_start + i0 + 1] += area * fraction;
+            } else {
+                // Across pixels: trapezoids in the end pixels, even steps between
+                let slope = (x1 - x0).recip();
+                let x0_fraction = x0 - x0_floor;
+                let first = 0.5 * slope * (1.0 - x0_fraction) * (1.0 - x0_fraction);
+                let x1_fraction = x1 - x1_ceil + 1.0;
+                let last = 0.5 * slope * x1_fraction * x1_fraction;
+                self.areas[row_start + i0] += area * first;
+                if i1 == i0 + 2 {
+                    self.areas[row_start + i0 + 1] += area * (1.0 - first - last);
+                } else {
+                    let second = slope * (1.5 - x0_fraction);
+                    self.areas[row_start + i0 + 1] += area * (second - first);
+                    for i in i0 + 2..i1 - 1 {
+                        self.areas[row_start + i] += area * slope;
+                    }
+                    let before_last = second + (i1 - i0 - 3) as f32 * slope;
+                    self.areas[row_start + i1 - 1] += area * (1.0 - before_last - last);
+                }
+                self.areas[row_start + i1] += area * last;
+            }
+            x = x_next;
+        }
+    }
+
+    fn coverage(&self) -> Vec<u8> {
+        let mut sum = 0.0f32;
+        self.areas[..self.width * self.height]
+            .iter()
+            .map(|area| {
+                sum += area;
+                (sum.abs().min(1.0) * 255.0 + 0.5) as u8
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,150 @@
+//! Texture format properties for upload and layout code
+
+use crate::GpuAdapter;
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+
+/// Properties of a texture format, as returned by `getTextureFormatInfo`
+#[napi(object)]
+pub struct TextureFormatInfo {
+    /// Canonical WebGPU name of the format
+    pub format: String,
+    /// Texel block size in texels (4x4 for BC/ETC2, varies for ASTC, 1x1 otherwise)
+    pub block_width: u32,
+    pub block_height: u32,
+    /// Bytes per texel block in copies; absent for depth24plus and combined depth-stencil formats
+    pub bytes_per_block: Option<u32>,
+    /// Bytes per texel; only present for uncompressed formats with a copyable size
+    pub bytes_per_texel: Option<u32>,
+    /// "color", "depth" and/or "stencil"
+    pub aspects: Vec<String>,
+    pub compressed: bool,
+    pub srgb: bool,
+    /// "float", "unfilterable-float", "depth", "sint" or "uint" (depth aspect for depth-stencil formats)
+    pub sample_type: Option<String>,
+    /// Whether the format can be sampled with a filtering sampler
+    pub filterable: bool,
+    /// Whether the format is guaranteed to support RENDER_ATTACHMENT usage
+    pub renderable: bool,
+    /// Whether the format is guaranteed to support STORAGE_BINDING usage
+    pub storage: bool,
+    /// Device feature needed to create textures of this format, if any
+    pub required_feature: Option<String>,
+}
+
+/// Describe a texture format
+///
+/// Usage capabilities are the ones guaranteed without optional device
+/// features; an adapter may support more. Compute a tightly packed row size as
+/// `Math.ceil(width / blockWidth) * bytesPerBlock`.
+#[napi(catch_unwind, js_name = "getTextureFormatInfo")]
+pub fn get_texture_format_info(format: String) -> Result<TextureFormatInfo> {
+    let texture_format = crate::parse::lookup_texture_format(&format)
+        .ok_or_else(|| Error::from_reason(format!("Unknown texture format: {}", format)))?;
+
+    let (block_width, block_height) = texture_format.block_dimensions();
+    let bytes_per_block = texture_format.block_copy_size(None);
+    let compressed = texture_format.is_compressed();
+
+    let mut aspects = Vec::new();
+    if texture_format.has_color_aspect() {
+        aspects.push("color".to_string());
+    }
+    if texture_format.has_depth_aspect() {
+        aspects.push("depth".to_string());
+    }
+    if texture_format.has_stencil_aspect() {
+        aspects.push("stencil".to_string());
+    }
+
+    let sample_type = texture_format
+        .sample_type(None, None)
+        .or_else(|| texture_format.sample_type(Some(wgpu::TextureAspect::DepthOnly), None));
+    let usages = texture_format
+        .guaranteed_format_features(wgpu::Features::empty())
+        .allowed_usages;
+
+    let required_feature = crate::features::feature_names(texture_format.required_features())
+        .into_iter()
+        .next();
+
+    Ok(TextureFormatInfo {
+        format: crate::parse::texture_format_name(texture_format),
+        block_width,
+        block_height,
+        bytes_per_block,
+        bytes_per_texel: if compressed { None } else { bytes_per_block },
+        aspects,
+        compressed,
+        srgb: texture_format.is_srgb(),
+        sample_type: sample_type.map(|ty| {
+            match ty {
+                wgpu::TextureSampleType::Float { filterable: true } => "float",
+                wgpu::TextureSampleType::Float { filterable: false } => "unfilterable-float",
+                wgpu::TextureSampleType::Depth => "depth",
+                wgpu::TextureSampleType::Sint => "sint",
+                wgpu::TextureSampleType::Uint => "uint",
+            }
+            .to_string()
+        }),
+        filterable: matches!(sample_type, Some(wgpu::TextureSampleType::Float { filterable: true })),
+        renderable: usages.contains(wgpu::TextureUsages::RENDER_ATTACHMENT),
+        storage: usages.contains(wgpu::TextureUsages::STORAGE_BINDING),
+        required_feature,
+    })
+}
+
+/// What an adapter supports for a texture format, as returned by `adapter.getTextureFormatFeatures`
+#[napi(object)]
+pub struct TextureFormatFeatures {
+    /// Canonical WebGPU name of the format
+    pub format: String,
+    /// Whether the adapter has the feature the format requires, if any; a
+    /// device must still request it
+    pub supported: bool,
+    /// Allowed `GPUTextureUsage` flags
+    pub usage: u32,
+    /// Whether the format can be a render attachment
+    pub renderable: bool,
+    /// Whether the format can be a storage texture
+    pub storage: bool,
+    /// Whether storage textures of the format support "read-write" access
+    pub storage_read_write: bool,
+    /// Whether the format can be sampled with a filtering sampler
+    pub filterable: bool,
+    /// Whether render attachments of the format support blending
+    pub blendable: bool,
+    /// Supported sample counts, always including 1
+    pub sample_counts: Vec<u32>,
+    /// Whether multisampled attachments of the format can be resolved
+    pub multisample_resolve: bool,
+}
+
+#[napi]
+impl GpuAdapter {
+    /// Get what this adapter supports for a texture format (extension)
+    ///
+    /// Unlike `getTextureFormatInfo`, reports the adapter's actual
+    /// capabilities, e.g. to pick fallback formats at startup.
+    #[napi(catch_unwind, js_name = "getTextureFormatFeatures")]
+    pub fn get_texture_format_features(&self, format: String) -> Result<TextureFormatFeatures> {
+        let texture_format = crate::parse::lookup_texture_format(&format)
+            .ok_or_else(|| Error::from_reason(format!("Unknown texture format: {}", format)))?;
+        let features = self.adapter.get_texture_format_features(texture_format);
+        let usages = features.allowed_usages;
+        let flags = features.flags;
+
+        Ok(TextureFormatFeatures {
+            format: crate::parse::texture_format_name(texture_format),
+            supported: self.adapter.features().contains(texture_format.required_features()),
+            usage: usages.bits(),
+            renderable: usages.contains(wgpu::TextureUsages::RENDER_ATTACHMENT),
+            storage: usages.contains(wgpu::TextureUsages::STORAGE_BINDING),
+            storage_read_write: flags.contains(wgpu::TextureFormatFeatureFlags::STORAGE_READ_WRITE),
+            filterable: flags.contains(wgpu::TextureFormatFeatureFlags::FILTERABLE),
+            blendable: flags.contains(wgpu::TextureFormatFeatureFlags::BLENDABLE),
+            sample_counts: flags.supported_sample_counts(),
+            multisample_resolve: flags.contains(wgpu::TextureFormatFeatureFlags::MULTISAMPLE_RESOLVE),
+        })
+    }
+}
@@ -0,0 +1,312 @@
+//! Frame readback for video encoding
+//!
+//! Rendering video on a server means reading every frame back. A new
+//! readback buffer and a wait for the GPU per frame stalls rendering on the
+//! copy; a capture instead rotates through a fixed set of staging buffers,
+//! so the next frame renders while earlier ones are still being copied,
+//! and hands the frames out in order as raw RGBA or I420 (yuv420p) bytes,
+//! the layouts ffmpeg reads from a pipe with `-f rawvideo`.
+
+use futures::channel::oneshot;
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+/// Frame capture descriptor
+#[napi(object)]
+pub struct FrameCaptureDescriptor {
+    pub label: Option<String>,
+    /// "rgba" (default) or "yuv420p"
+    pub pixel_format: Option<String>,
+    /// Staging buffers frames are copied into, in rotation (default 2)
+    pub buffer_count: Option<u32>,
+}
+
+/// One captured frame
+#[napi(object)]
+pub struct CapturedFrame {
+    /// Tightly packed RGBA8 pixels, or the Y, U and V planes of I420
+    pub data: Buffer,
+    pub width: u32,
+    pub height: u32,
+    /// Number of frames captured before this one
+    pub index: u32,
+    /// Milliseconds since the capture was created when the frame was
+    /// captured, or the timestamp passed to `capture()`
+    pub timestamp: f64,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum PixelFormat {
+    Rgba,
+    Yuv420p,
+}
+
+/// A frame whose copy was submitted, waiting for its staging buffer to map
+struct PendingFrame {
+    buffer: Arc<wgpu::Buffer>,
+    mapped: oneshot::Receiver<std::result::Result<(), wgpu::BufferAsyncError>>,
+    width: u32,
+    height: u32,
+    bgra: bool,
+    index: u32,
+    timestamp: f64,
+}
+
+#[derive(Default)]
+struct CaptureState {
+    /// Staging buffers not holding a frame
+    free: Vec<Arc<wgpu::Buffer>>,
+    /// Submitted frames, oldest first
+    pending: VecDeque<PendingFrame>,
+    /// Frames read back but not yet taken by `next()`, oldest first
+    ready: VecDeque<CapturedFrame>,
+    captured: u32,
+}
+
+/// `next()` calls waiting for a capture, and whether `end()` was called
+#[derive(Default)]
+struct Waiting {
+    senders: Vec<oneshot::Sender<()>>,
+    ended: bool,
+}
+
+/// Reads rendered frames back through rotating staging buffers
+#[napi]
+pub struct GpuFrameCapture {
+    device: Arc<wgpu::Device>,
+    queue: Arc<wgpu::Queue>,
+    poller: crate::poller::DevicePoller,
+    label: Option<String>,
+    pixel_format: PixelFormat,
+    buffer_count: usize,
+    created: Instant,
+    state: futures::lock::Mutex<CaptureState>,
+    waiting: Mutex<Waiting>,
+    /// Frames captured and not yet taken by `next()`
+    queued: AtomicU32,
+}
+
+impl GpuFrameCapture {
+    pub(crate) fn new(device: &crate::GpuDevice, descriptor: Option<FrameCaptureDescriptor>) -> Result<Self> {
+        let descriptor = descriptor.unwrap_or(FrameCaptureDescriptor {
+            label: None,
+            pixel_format: None,
+            buffer_count: None,
+        });
+        let pixel_format = match descriptor.pixel_format.as_deref() {
+            None | Some("rgba") => PixelFormat::Rgba,
+            Some("yuv420p") => PixelFormat::Yuv420p,
+            Some(other) => {
+                return Err(Error::from_reason(format!(
+                    "createFrameCapture pixelFormat must be 'rgba' or 'yuv420p', got '{}'",
+                    other
+                )))
+            }
+        };
+        let buffer_count = descriptor.buffer_count.unwrap_or(2);
+        if buffer_count == 0 {
+            return Err(Error::from_reason("createFrameCapture bufferCount must be at least 1"));
+        }
+
+        Ok(Self {
+            device: device.device.clone(),
+            queue: device.queue_internal.clone(),
+            poller: device.poller.clone(),
+            label: descriptor.label,
+            pixel_format,
+            buffer_count: buffer_count as usize,
+            created: Instant::now(),
+            state: Default::default(),
+            waiting: Default::default(),
+            queued: AtomicU32::new(0),
+        })
+    }
+
+    /// Wait for the copy of `frame` and convert it to the output format
+    async fn read_back(&self, frame: PendingFrame) -> Result<(CapturedFrame, Arc<wgpu::Buffer>)> {
+        let _polling = self.poller.track();
+        frame
+            .mapped
+            .await
+            .map_err(|_| Error::from_reason("Failed to receive map result"))?
+            .map_err(|e| Error::from_reason(format!("Failed to read frame {}: {:?}", frame.index, e)))?;
+
+        let slice = frame.buffer.slice(..crate::texture::rgba8_readback_size(frame.width, frame.height));
+        let pixels = crate::texture::unpad_rgba8_rows(&slice.get_mapped_range(), frame.width, frame.height, frame.bgra);
+        frame.buffer.unmap();
+        let data = match self.pixel_format {
+            PixelFormat::Rgba => pixels,
+            PixelFormat::Yuv420p => rgba_to_i420(&pixels, frame.width as usize, frame.height as usize),
+        };
+
+        let captured = CapturedFrame {
+            data: data.into(),
+            width: frame.width,
+            height: frame.height,
+            index: frame.index,
+            timestamp: frame.timestamp,
+        };
+        Ok((captured, frame.buffer))
+    }
+}
+
+#[napi]
+impl GpuFrameCapture {
+    /// Copy mip level 0 of `texture` into the next free staging buffer
+    ///
+    /// For the texture formats `readPixels()` supports. The copy is
+    /// submitted immediately; when every staging buffer holds a frame, this
+    /// first waits for the oldest to be read back. Resolves with the frame's
+    /// index.
+    #[napi(catch_unwind)]
+    pub async fn capture(&self, texture: &crate::GpuTexture, timestamp: Option<f64>) -> Result<u32> {
+        let bgra = texture.rgba8_readback_order("capture")?;
+        let timestamp = timestamp.unwrap_or_else(|| self.created.elapsed().as_secs_f64() * 1000.0);
+        let size = texture.texture.size();
+        let needed = crate::texture::rgba8_readback_size(size.width, size.height);
+
+        let mut state = self.state.lock().await;
+        if self.waiting.lock().unwrap().ended {
+            return Err(Error::from_reason("capture: the capture was ended"));
+        }
+        let buffer = loop {
+            if let Some(buffer) = state.free.pop() {
+                break Some(buffer);
+            }
+            if state.pending.len() < self.buffer_count {
+                break None;
+            }
+            let oldest = state.pending.pop_front().expect("a pending frame");
+            let (frame, buffer) = self.read_back(oldest).await?;
+            state.ready.push_back(frame);
+            state.free.push(buffer);
+        };
+        // Frames larger than the last ones need a larger staging buffer
+        let buffer = match buffer {
+            Some(buffer) if buffer.size() >= needed => buffer,
+            _ => Arc::new(self.device.create_buffer(&wgpu::BufferDescriptor {
+                label: self.label.as_deref(),
+                size: needed,
+                usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            })),
+        };
+
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: self.label.as_deref() });
+        texture.encode_rgba8_readback(&mut encoder, &buffer, 0);
+        self.queue.submit(Some(encoder.finish()));
+        let (sender, mapped) = oneshot::channel();
+        buffer.slice(..needed).map_async(wgpu::MapMode::Read, move |result| {
+            let _ = sender.send(result);
+        });
+
+        let index = state.captured;
+        state.captured += 1;
+        state.pending.push_back(PendingFrame {
+            buffer,
+            mapped,
+            width: size.width,
+            height: size.height,
+            bgra,
+            index,
+            timestamp,
+        });
+        self.queued.fetch_add(1, Ordering::Relaxed);
+        for sender in self.waiting.lock().unwrap().senders.drain(..) {
+            let _ = sender.send(());
+        }
+        Ok(index)
+    }
+
+    /// The oldest frame not yet taken, in capture order
+    ///
+    /// Waits for the next `capture()` when there is none, and resolves with
+    /// null once the capture was ended and every frame was taken.
+    #[napi(catch_unwind)]
+    pub async fn next(&self) -> Result<Option<CapturedFrame>> {
+        loop {
+            let mut state = self.state.lock().await;
+            if let Some(frame) = state.ready.pop_front() {
+                self.queued.fetch_sub(1, Ordering::Relaxed);
+                return Ok(Some(frame));
+            }
+            if let Some(oldest) = state.pending.pop_front() {
+                let (frame, buffer) = self.read_back(oldest).await?;
+                state.free.push(buffer);
+                self.queued.fetch_sub(1, Ordering::Relaxed);
+                return Ok(Some(frame));
+            }
+            let captured = {
+                let mut waiting = self.waiting.lock().unwrap();
+                if waiting.ended {
+                    return Ok(None);
+                }
+                let (sender, captured) = oneshot::channel();
+                waiting.senders.push(sender);
+                captured
+            };
+            drop(state);
+            // end() drops the sender, which wakes this up as well
+            let _ = captured.await;
+        }
+    }
+
+    /// Stop capturing; `next()` still returns the frames captured so far
+    #[napi(catch_unwind)]
+    pub fn end(&self) {
+        let mut waiting = self.waiting.lock().unwrap();
+        waiting.ended = true;
+        waiting.senders.clear();
+    }
+
+    /// Number of frames captured and not yet taken by `next()`
+    #[napi(catch_unwind, getter, js_name = "queuedFrames")]
+    pub fn queued_frames(&self) -> u32 {
+        self.queued.load(Ordering::Relaxed)
+    }
+
+    /// Descriptor label, or "" without one
+    #[napi(catch_unwind, getter)]
+    pub fn label(&self) -> String {
+        self.label.clone().unwrap_or_default()
+    }
+}
+
+/// I420 planes of tightly packed RGBA8 pixels: full size Y, then U and V at
+/// half the width and height, rounded up; BT.709 limited range
+fn rgba_to_i420(pixels: &[u8], width: usize, height: usize) -> Vec<u8> {
+    let (chroma_width, chroma_height) = (width.div_ceil(2), height.div_ceil(2));
+    let mut planes = vec![0u8; width * height + 2 * chroma_width * chroma_height];
+    let (luma, chroma) = planes.split_at_mut(width * height);
+    let (u_plane, v_plane) = chroma.split_at_mut(chroma_width * chroma_height);
+
+    for (y, pixel) in luma.iter_mut().zip(pixels.chunks_exact(4)) {
+        let (r, g, b) = (pixel[0] as i32, pixel[1] as i32, pixel[2] as i32);
+        *y = (((47 * r + 157 * g + 16 * b + 128) >> 8) + 16) as u8;
+    }
+    for row in 0..chroma_height {
+        for column in 0..chroma_width {
+            // Average the 2x2 block, or what of it lies inside the frame
+            let (mut r, mut g, mut b, mut count) = (0, 0, 0, 0);
+            for y in row * 2..(row * 2 + 2).min(height) {
+                for x in column * 2..(column * 2 + 2).min(width) {
+                    let pixel = &pixels[(y * width + x) * 4..];
+                    r += pixel[0] as i32;
+                    g += pixel[1] as i32;
+                    b += pixel[2] as i32;
+                    count += 1;
+                }
+            }
+            let (r, g, b) = (r / count, g / count, b / count);
+            u_plane[row * chroma_width + column] = (((-26 * r - 86 * g + 112 * b + 128) >> 8) + 128) as u8;
+            v_plane[row * chroma_width + column] = (((112 * r - 102 * g - 10 * b + 128) >> 8) + 128) as u8;
+        }
+    }
+    planes
+}
@@ -1,6 +1,142 @@
 use napi::bindgen_prelude::*;
 use napi_derive::napi;
 
+/// GPU instance options
+#[napi(object)]
+#[derive(Default)]
+pub struct GpuOptions {
+    /// Backends to use: "vulkan", "metal", "dx12" and "gl" (OpenGL / GLES,
+    /// including ANGLE's EGL). Defaults to `WGPU_BACKEND` or all backends.
+    /// `Gpu({ backends: ['null'] })` in webgpu.js gives a driverless adapter
+    /// for tests instead; "null" can't be combined with other backends
+    pub backends: Option<Vec<String>>,
+    /// DX12 shader compiler: "fxc" or "dxc". DXC is needed for Shader Model 6
+    /// features such as f16. Defaults to `WGPU_DX12_COMPILER` or "fxc"
+    pub dx12_shader_compiler: Option<String>,
+    /// Path to `dxil.dll` or its directory, for "dxc"
+    pub dxil_path: Option<String>,
+    /// Path to `dxcompiler.dll` or its directory, for "dxc"
+    pub dxc_path: Option<String>,
+    /// Instance flags: "debug", "validation", "discard-hal-labels" and
+    /// "allow-noncompliant-adapters". Defaults to debug and validation in
+    /// debug builds; `WGPU_VALIDATION`, `WGPU_DEBUG` etc. still apply
+    pub instance_flags: Option<Vec<String>>,
+    /// OpenGL ES 3.x minor version the GL backend requests: 0, 1 or 2.
+    /// Defaults to `WGPU_GLES_MINOR_VERSION` or the highest available
+    pub gles_minor_version: Option<u32>,
+}
+
+/// Adapter selection policy for `requestAdapter({ selector })` (extension)
+///
+/// `vendor` and `device` must match; among the adapters that do, one of the
+/// preferred device type, then one on the preferred backend, is picked.
+/// Remaining ties keep the `enumerateAdapters()` order.
+#[napi(object)]
+pub struct AdapterSelector {
+    /// Preferred device type: "discrete", "integrated", "virtual", "cpu" or "other".
+    /// Defaults to "discrete" for the "high-performance" power preference and
+    /// "integrated" for "low-power"
+    pub device_type: Option<String>,
+    /// Preferred backend: "vulkan", "metal", "dx12" or "gl"
+    pub backend: Option<String>,
+    /// PCI vendor ID the adapter must have, e.g. 0x10de for NVIDIA
+    pub vendor: Option<u32>,
+    /// PCI device ID the adapter must have
+    pub device: Option<u32>,
+}
+
+/// Parse a selector device type
+fn parse_device_type(name: &str) -> Result<wgpu::DeviceType> {
+    match name {
+        "discrete" => Ok(wgpu::DeviceType::DiscreteGpu),
+        "integrated" => Ok(wgpu::DeviceType::IntegratedGpu),
+        "virtual" => Ok(wgpu::DeviceType::VirtualGpu),
+        "cpu" => Ok(wgpu::DeviceType::Cpu),
+        "other" => Ok(wgpu::DeviceType::Other),
+        other => Err(Error::from_reason(format!(
+            "Unknown device type: {}. Use 'discrete', 'integrated', 'virtual', 'cpu' or 'other'",
+            other
+        ))),
+    }
+}
+
+/// Parse backend names into wgpu backend bits
+fn parse_backends(names: &[String]) -> Result<wgpu::Backends> {
+    if names.is_empty() {
+        return Err(Error::from_reason("backends must list at least one backend"));
+    }
+    names.iter().try_fold(wgpu::Backends::empty(), |backends, name| {
+        let backend = match name.as_str() {
+            "vulkan" => wgpu::Backends::VULKAN,
+            "metal" => wgpu::Backends::METAL,
+            "dx12" => wgpu::Backends::DX12,
+            "gl" => wgpu::Backends::GL,
+            other => {
+                return Err(Error::from_reason(format!(
+                    "Unknown backend: {}. Use 'vulkan', 'metal', 'dx12' or 'gl'",
+                    other
+                )))
+            }
+        };
+        Ok(backends | backend)
+    })
+}
+
+/// Parse the DX12 shader compiler options
+fn parse_dx12_compiler(options: &GpuOptions) -> Result<Option<wgpu::Dx12Compiler>> {
+    let dll_paths = options.dxil_path.is_some() || options.dxc_path.is_some();
+    match options.dx12_shader_compiler.as_deref() {
+        Some("dxc") => Ok(Some(wgpu::Dx12Compiler::Dxc {
+            dxil_path: options.dxil_path.as_ref().map(Into::into),
+            dxc_path: options.dxc_path.as_ref().map(Into::into),
+        })),
+        Some("fxc") if dll_paths => Err(Error::from_reason(
+            "dxilPath and dxcPath require dx12ShaderCompiler: 'dxc'",
+        )),
+        Some("fxc") => Ok(Some(wgpu::Dx12Compiler::Fxc)),
+        Some(other) => Err(Error::from_reason(format!(
+            "Unknown DX12 shader compiler: {}. Use 'fxc' or 'dxc'",
+            other
+        ))),
+        None if dll_paths => Err(Error::from_reason(
+            "dxilPath and dxcPath require dx12ShaderCompiler: 'dxc'",
+        )),
+        None => Ok(None),
+    }
+}
+
+/// Parse instance flag names into wgpu instance flags
+fn parse_instance_flags(names: &[String]) -> Result<wgpu::InstanceFlags> {
+    names.iter().try_fold(wgpu::InstanceFlags::empty(), |flags, name| {
+        let flag = match name.as_str() {
+            "debug" => wgpu::InstanceFlags::DEBUG,
+            "validation" => wgpu::InstanceFlags::VALIDATION,
+            "discard-hal-labels" => wgpu::InstanceFlags::DISCARD_HAL_LABELS,
+            "allow-noncompliant-adapters" => wgpu::InstanceFlags::ALLOW_UNDERLYING_NONCOMPLIANT_ADAPTER,
+            other => {
+                return Err(Error::from_reason(format!(
+                    "Unknown instance flag: {}. Use 'debug', 'validation', 'discard-hal-labels' or 'allow-noncompliant-adapters'",
+                    other
+                )))
+            }
+        };
+        Ok(flags | flag)
+    })
+}
+
+/// Parse the requested OpenGL ES 3.x minor version
+fn parse_gles_minor_version(version: u32) -> Result<wgpu::Gles3MinorVersion> {
+    match version {
+        0 => Ok(wgpu::Gles3MinorVersion::Version0),
+        1 => Ok(wgpu::Gles3MinorVersion::Version1),
+        2 => Ok(wgpu::Gles3MinorVersion::Version2),
+        other => Err(Error::from_reason(format!(
+            "Unsupported GLES minor version: {}. Use 0, 1 or 2",
+            other
+        ))),
+    }
+}
+
 /// GPU instance - entry point for WebGPU API
 ///
 /// This is the starting point for all WebGPU operations. Create an instance to
@@ -8,62 +144,279 @@ use napi_derive::napi;
 #[napi]
 pub struct Gpu {
     instance: wgpu::Instance,
+    backends: wgpu::Backends,
 }
 
 #[napi]
 impl Gpu {
     /// Create a new GPU instance
     ///
+    /// Restrict `backends` to e.g. `['gl']` to run on machines with only
+    /// EGL / software OpenGL, such as CI containers. On Windows, select DXC
+    /// with `dx12_shader_compiler` for Shader Model 6 features. `instance_flags`
+    /// and `gles_minor_version` tune the backends for platform-specific issues.
+    ///
     /// Example:
     /// ```js
     /// const gpu = Gpu.create()
+    /// const glGpu = Gpu.create({ backends: ['gl'] })
+    /// const dxcGpu = Gpu.create({ dx12ShaderCompiler: 'dxc', dxcPath: 'C:\\dxc\\bin' })
     /// ```
-    #[napi(factory)]
-    pub fn create() -> Self {
-        Self {
-            instance: wgpu::Instance::default(),
-        }
+    #[napi(catch_unwind, factory)]
+    pub fn create(options: Option<GpuOptions>) -> Result<Self> {
+        let options = options.unwrap_or_default();
+        let dx12_compiler = parse_dx12_compiler(&options)?;
+        let backends = match &options.backends {
+            Some(names) => parse_backends(names)?,
+            None => wgpu::util::backend_bits_from_env().unwrap_or_else(wgpu::Backends::all),
+        };
+        let flags = match &options.instance_flags {
+            Some(names) => parse_instance_flags(names)?,
+            None => wgpu::InstanceFlags::from_build_config(),
+        };
+        let gles_minor_version = match options.gles_minor_version {
+            Some(version) => Some(parse_gles_minor_version(version)?),
+            None => wgpu::util::gles_minor_version_from_env(),
+        };
+        let instance = wgpu::Instance::new(wgpu::InstanceDescriptor {
+            backends,
+            flags: flags.with_env(),
+            dx12_shader_compiler: dx12_compiler
+                .or_else(wgpu::util::dx12_shader_compiler_from_env)
+                .unwrap_or_default(),
+            gles_minor_version: gles_minor_version.unwrap_or_default(),
+        });
+        Ok(Self { instance, backends })
     }
 
     /// Request a GPU adapter
     ///
+    /// Set `force_fallback_adapter` to only consider software (CPU) adapters such as
+    /// lavapipe, WARP or SwiftShader, e.g. for deterministic CI runs without a GPU.
+    /// Pass `compatible_surface` to only consider adapters that can present to it.
+    /// A `selector` picks from `enumerateAdapters()` by device type, backend and
+    /// PCI IDs instead of leaving the choice to wgpu.
+    ///
     /// Example:
     /// ```js
     /// const adapter = await gpu.requestAdapter()
+    /// const cpuAdapter = await gpu.requestAdapter(null, true)
+    /// const windowAdapter = await gpu.requestAdapter(null, false, surface)
+    /// const nvidiaAdapter = await gpu.requestAdapter(null, false, null, { vendor: 0x10de, backend: 'vulkan' })
     /// ```
-    #[napi]
-    pub async fn request_adapter(&self, power_preference: Option<String>) -> Result<crate::GpuAdapter> {
+    #[napi(catch_unwind)]
+    pub async fn request_adapter(
+        &self,
+        power_preference: Option<String>,
+        force_fallback_adapter: Option<bool>,
+        compatible_surface: Option<&crate::GpuSurface>,
+        selector: Option<AdapterSelector>,
+    ) -> Result<crate::GpuAdapter> {
         let power_pref = match power_preference.as_deref() {
             Some("low-power") => wgpu::PowerPreference::LowPower,
             Some("high-performance") => wgpu::PowerPreference::HighPerformance,
             _ => wgpu::PowerPreference::default(),
         };
 
+        if let Some(selector) = selector {
+            let adapter = self.select_adapter(
+                &selector,
+                power_pref,
+                force_fallback_adapter.unwrap_or(false),
+                compatible_surface,
+            )?;
+            return Ok(crate::GpuAdapter::new(adapter));
+        }
+
         let adapter = self.instance
             .request_adapter(&wgpu::RequestAdapterOptions {
                 power_preference: power_pref,
-                compatible_surface: None,
-                force_fallback_adapter: false,
+                compatible_surface: compatible_surface.map(|surface| surface.surface()),
+                force_fallback_adapter: force_fallback_adapter.unwrap_or(false),
             })
             .await
-            .ok_or_else(|| Error::from_reason("No suitable GPU adapter found"))?;
+            .ok_or_else(|| {
+                if compatible_surface.is_some() {
+                    Error::from_reason("No adapter found that can present to the surface")
+                } else if force_fallback_adapter.unwrap_or(false) {
+                    Error::from_reason("No fallback (software) adapter found")
+                } else {
+                    Error::from_reason("No suitable GPU adapter found")
+                }
+            })?;
 
         Ok(crate::GpuAdapter::new(adapter))
     }
 
-    /// Enumerate all available adapters
-    ///
-    /// Returns a list of all available GPU adapters with their backend (Metal, Vulkan, DX12).
-    /// Useful for debugging and displaying available hardware to users.
-    #[napi]
-    pub fn enumerate_adapters(&self) -> Vec<String> {
-        self.instance
-            .enumerate_adapters(wgpu::Backends::all())
+    /// Pick the adapter `selector` prefers among those matching its IDs and the other options
+    fn select_adapter(
+        &self,
+        selector: &AdapterSelector,
+        power_preference: wgpu::PowerPreference,
+        force_fallback_adapter: bool,
+        compatible_surface: Option<&crate::GpuSurface>,
+    ) -> Result<wgpu::Adapter> {
+        let device_type = match selector.device_type.as_deref() {
+            Some(name) => Some(parse_device_type(name)?),
+            None => match power_preference {
+                wgpu::PowerPreference::HighPerformance => Some(wgpu::DeviceType::DiscreteGpu),
+                wgpu::PowerPreference::LowPower => Some(wgpu::DeviceType::IntegratedGpu),
+                wgpu::PowerPreference::None => None,
+            },
+        };
+        let backend = match &selector.backend {
+            Some(name) => Some(parse_backends(std::slice::from_ref(name))?),
+            None => None,
+        };
+
+        let mut candidates: Vec<(wgpu::Adapter, wgpu::AdapterInfo)> = self
+            .instance
+            .enumerate_adapters(self.backends)
             .into_iter()
             .map(|adapter| {
                 let info = adapter.get_info();
-                format!("{} ({:?})", info.name, info.backend)
+                (adapter, info)
             })
+            .filter(|(adapter, info)| {
+                selector.vendor.map_or(true, |vendor| info.vendor == vendor)
+                    && selector.device.map_or(true, |device| info.device == device)
+                    && (!force_fallback_adapter || info.device_type == wgpu::DeviceType::Cpu)
+                    && compatible_surface.map_or(true, |surface| adapter.is_surface_supported(surface.surface()))
+            })
+            .collect();
+        // Stable, so ties keep the enumeration order
+        candidates.sort_by_key(|(_, info)| {
+            (
+                device_type.is_some_and(|device_type| info.device_type != device_type),
+                backend.is_some_and(|backend| !backend.contains(info.backend.into())),
+            )
+        });
+
+        candidates.into_iter().next().map(|(adapter, _)| adapter).ok_or_else(|| {
+            let mut required = Vec::new();
+            if let Some(vendor) = selector.vendor {
+                required.push(format!("vendor 0x{:04x}", vendor));
+            }
+            if let Some(device) = selector.device {
+                required.push(format!("device 0x{:04x}", device));
+            }
+            if force_fallback_adapter {
+                required.push("fallback (software)".to_string());
+            }
+            if compatible_surface.is_some() {
+                required.push("presents to the surface".to_string());
+            }
+            if required.is_empty() {
+                Error::from_reason("No suitable GPU adapter found")
+            } else {
+                Error::from_reason(format!("No adapter matches the selector ({})", required.join(", ")))
+            }
+        })
+    }
+
+    /// Enumerate all available adapters
+    ///
+    /// Returns structured info (name, vendor, device, type, backend, driver) for every adapter
+    /// on the instance's backends. The position in this list is the index accepted by
+    /// `requestAdapterByIndex`, so multi-GPU machines can pick an exact device.
+    #[napi(catch_unwind)]
+    pub fn enumerate_adapters(&self) -> Vec<crate::AdapterInfo> {
+        self.instance
+            .enumerate_adapters(self.backends)
+            .into_iter()
+            .map(|adapter| crate::AdapterInfo::from_adapter(&adapter))
             .collect()
     }
+
+    /// Request the adapter at `index` in the `enumerateAdapters()` list
+    ///
+    /// Example:
+    /// ```js
+    /// const index = gpu.enumerateAdapters().findIndex(info => info.name.includes('NVIDIA'))
+    /// const adapter = gpu.requestAdapterByIndex(index)
+    /// ```
+    #[napi(catch_unwind)]
+    pub fn request_adapter_by_index(&self, index: u32) -> Result<crate::GpuAdapter> {
+        let mut adapters = self.instance.enumerate_adapters(self.backends);
+        let count = adapters.len();
+        if index as usize >= count {
+            return Err(Error::from_reason(format!(
+                "Adapter index {} out of range ({} adapters available)",
+                index, count
+            )));
+        }
+        Ok(crate::GpuAdapter::new(adapters.swap_remove(index as usize)))
+    }
+
+    /// Create a surface for presenting to a native window
+    ///
+    /// The handles are not checked: they must identify a live window (and
+    /// display connection) that stays open until the surface is released.
+    ///
+    /// Example:
+    /// ```js
+    /// const surface = gpu.createSurface({ platform: 'win32', window: hwnd })
+    /// ```
+    #[napi(catch_unwind)]
+    pub fn create_surface(&self, descriptor: crate::SurfaceDescriptor) -> Result<crate::GpuSurface> {
+        let target = crate::surface::surface_target(&descriptor)?;
+        // SAFETY: the caller guarantees the handles stay valid for the surface's lifetime
+        let surface = unsafe { self.instance.create_surface_unsafe(target) }
+            .map_err(|e| Error::from_reason(format!("Failed to create surface: {}", e)))?;
+        Ok(crate::GpuSurface::new(surface))
+    }
+
+    /// Create a surface from a native window handle Buffer
+    ///
+    /// Accepts what Electron's `BrowserWindow.getNativeWindowHandle()` returns:
+    /// an `HWND` on Windows, an `NSView*` on macOS and an X11 window id on
+    /// Linux, where a connection to the X server in `DISPLAY` is opened for
+    /// the surface. The window must stay open until the surface is released.
+    ///
+    /// Example:
+    /// ```js
+    /// const surface = gpu.createSurfaceFromNativeWindowHandle(win.getNativeWindowHandle())
+    /// ```
+    #[napi(catch_unwind, js_name = "createSurfaceFromNativeWindowHandle")]
+    pub fn create_surface_from_native_window_handle(&self, handle: Buffer) -> Result<crate::GpuSurface> {
+        let window = crate::surface::native_window_handle(&handle)?;
+
+        #[cfg(target_os = "linux")]
+        {
+            let display = crate::surface::XlibDisplay::open()?;
+            let target = crate::surface::surface_target(&crate::SurfaceDescriptor {
+                platform: "xlib".to_string(),
+                window: BigInt::from(window),
+                display: Some(BigInt::from(display.as_ptr().as_ptr() as u64)),
+                screen: None,
+                hinstance: None,
+            })?;
+            // SAFETY: the caller guarantees the window stays open; the display
+            // is owned by the surface
+            let surface = unsafe { self.instance.create_surface_unsafe(target) }
+                .map_err(|e| Error::from_reason(format!("Failed to create surface: {}", e)))?;
+            Ok(crate::GpuSurface::with_display(surface, display))
+        }
+
+        #[cfg(not(target_os = "linux"))]
+        {
+            let platform = match std::env::consts::OS {
+                "windows" => "win32",
+                "macos" => "appkit",
+                other => {
+                    return Err(Error::from_reason(format!(
+                        "Native window handles are not supported on {}",
+                        other
+                    )))
+                }
+            };
+            self.create_surface(crate::SurfaceDescriptor {
+                platform: platform.to_string(),
+                window: BigInt::from(window),
+                display: None,
+                screen: None,
+                hinstance: None,
+            })
+        }
+    }
 }
@@ -0,0 +1,139 @@
+//! Half-precision float conversion
+//!
+//! JavaScript has no f16 array type, so half-precision data is packed into
+//! bytes natively. x86_64 CPUs with F16C convert eight values per
+//! instruction; elsewhere a scalar conversion rounds the same way.
+
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+
+/// Convert to IEEE half-precision bits, rounding to nearest even
+pub(crate) fn f32_to_f16(value: f32) -> u16 {
+    let bits = value.to_bits();
+    let sign = ((bits >> 16) & 0x8000) as u16;
+    let exponent = ((bits >> 23) & 0xff) as i32;
+    let mantissa = bits & 0x7f_ffff;
+    if exponent == 0xff {
+        // Infinity, or a quiet NaN
+        return sign | 0x7c00 | if mantissa != 0 { 0x200 } else { 0 };
+    }
+    let exponent = exponent - 127 + 15;
+    if exponent >= 0x1f {
+        return sign | 0x7c00;
+    }
+    let (half, rest, halfway) = if exponent <= 0 {
+        if exponent < -10 {
+            return sign;
+        }
+        // Subnormal: shift the mantissa and its implicit bit into place
+        let shift = (14 - exponent) as u32;
+        let mantissa = mantissa | 0x80_0000;
+        (mantissa >> shift, mantissa & ((1 << shift) - 1), 1 << (shift - 1))
+    } else {
+        (((exponent as u32) << 10) | (mantissa >> 13), mantissa & 0x1fff, 0x1000)
+    };
+    // A carry out of the mantissa correctly bumps the exponent, up to infinity
+    let round_up = rest > halfway || (rest == halfway && half & 1 == 1);
+    sign | (half + round_up as u32) as u16
+}
+
+/// Convert IEEE half-precision bits to f32
+pub(crate) fn f16_to_f32(bits: u16) -> f32 {
+    let sign = if bits & 0x8000 != 0 { -1.0 } else { 1.0 };
+    let exponent = (bits >> 10) & 0x1f;
+    let mantissa = (bits & 0x3ff) as f32;
+    sign * match exponent {
+        0 => mantissa * 2f32.powi(-24),
+        0x1f if mantissa == 0.0 => f32::INFINITY,
+        0x1f => f32::NAN,
+        _ => (1.0 + mantissa / 1024.0) * 2f32.powi(exponent as i32 - 15),
+    }
+}
+
+/// Pack `values` as little-endian f16
+pub(crate) fn pack(values: &[f32]) -> Vec<u8> {
+    let mut bytes = vec![0; values.len() * 2];
+    #[cfg(target_arch = "x86_64")]
+    // SAFETY: the CPU supports the instructions
+    let done = if has_f16c() { unsafe { pack_f16c(values, &mut bytes) } } else { 0 };
+    #[cfg(not(target_arch = "x86_64"))]
+    let done = 0;
+    for (value, out) in values[done..].iter().zip(bytes[done * 2..].chunks_exact_mut(2)) {
+        out.copy_from_slice(&f32_to_f16(*value).to_le_bytes());
+    }
+    bytes
+}
+
+/// Unpack little-endian f16 `bytes`, whose length must be even
+pub(crate) fn unpack(bytes: &[u8]) -> Vec<f32> {
+    let mut values = vec![0.0; bytes.len() / 2];
+    #[cfg(target_arch = "x86_64")]
+    // SAFETY: the CPU supports the instructions
+    let done = if has_f16c() { unsafe { unpack_f16c(bytes, &mut values) } } else { 0 };
+    #[cfg(not(target_arch = "x86_64"))]
+    let done = 0;
+    for (value, half) in values[done..].iter_mut().zip(bytes[done * 2..].chunks_exact(2)) {
+        *value = f16_to_f32(u16::from_le_bytes([half[0], half[1]]));
+    }
+    values
+}
+
+#[cfg(target_arch = "x86_64")]
+fn has_f16c() -> bool {
+    std::arch::is_x86_feature_detected!("avx") && std::arch::is_x86_feature_detected!("f16c")
+}
+
+/// Convert whole groups of eight values; returns how many were converted
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx,f16c")]
+unsafe fn pack_f16c(values: &[f32], bytes: &mut [u8]) -> usize {
+    use std::arch::x86_64::*;
+    let groups = values.len() / 8;
+    for i in 0..groups {
+        // SAFETY: group `i` is in bounds of both slices, and the loads and
+        // stores are unaligned
+        unsafe {
+            let floats = _mm256_loadu_ps(values.as_ptr().add(i * 8));
+            let halves = _mm256_cvtps_ph::<_MM_FROUND_TO_NEAREST_INT>(floats);
+            _mm_storeu_si128(bytes.as_mut_ptr().add(i * 16).cast(), halves);
+        }
+    }
+    groups * 8
+}
+
+/// Convert whole groups of eight values; returns how many were converted
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx,f16c")]
+unsafe fn unpack_f16c(bytes: &[u8], values: &mut [f32]) -> usize {
+    use std::arch::x86_64::*;
+    let groups = values.len() / 8;
+    for i in 0..groups {
+        // SAFETY: as in `pack_f16c`
+        unsafe {
+            let halves = _mm_loadu_si128(bytes.as_ptr().add(i * 16).cast());
+            _mm256_storeu_ps(values.as_mut_ptr().add(i * 8), _mm256_cvtph_ps(halves));
+        }
+    }
+    groups * 8
+}
+
+/// Pack floats into half-precision bytes
+///
+/// Returns two little-endian bytes per value, rounded to nearest even, for
+/// uploading to f16 storage buffers or textures.
+#[napi(catch_unwind, js_name = "packF16")]
+pub fn pack_f16(values: Float32Array) -> Buffer {
+    pack(&values).into()
+}
+
+/// Unpack half-precision bytes into floats
+#[napi(catch_unwind, js_name = "unpackF16")]
+pub fn unpack_f16(data: Buffer) -> Result<Float32Array> {
+    if data.len() % 2 != 0 {
+        return Err(Error::from_reason(format!(
+            "unpackF16: data length {} isn't a multiple of 2",
+            data.len()
+        )));
+    }
+    Ok(Float32Array::new(unpack(&data)))
+}
@@ -0,0 +1,133 @@
+//! Sharing devices and resources with worker threads
+//!
+//! JavaScript objects can't cross `worker_threads` boundaries, but the wgpu
+//! objects behind them are reference counted and thread safe. `toHandle()`
+//! parks a clone of the native object in a process-wide registry under a
+//! string token that can be posted to a worker, where the matching
+//! `*FromHandle()` function wraps the same device or resource again.
+
+use crate::{GpuBuffer, GpuDevice, GpuTexture};
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock};
+
+enum Shared {
+    Device(GpuDevice),
+    Buffer(GpuBuffer),
+    Texture(GpuTexture),
+}
+
+impl Shared {
+    fn kind(&self) -> &'static str {
+        match self {
+            Shared::Device(_) => "device",
+            Shared::Buffer(_) => "buffer",
+            Shared::Texture(_) => "texture",
+        }
+    }
+}
+
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+fn registry() -> &'static Mutex<HashMap<String, Shared>> {
+    static REGISTRY: OnceLock<Mutex<HashMap<String, Shared>>> = OnceLock::new();
+    REGISTRY.get_or_init(Default::default)
+}
+
+fn lock_registry() -> Result<std::sync::MutexGuard<'static, HashMap<String, Shared>>> {
+    registry()
+        .lock()
+        .map_err(|_| Error::from_reason("Handle registry is poisoned"))
+}
+
+fn register(shared: Shared) -> Result<String> {
+    let handle = format!("webgpu-{}:{}", shared.kind(), NEXT_ID.fetch_add(1, Ordering::Relaxed));
+    lock_registry()?.insert(handle.clone(), shared);
+    Ok(handle)
+}
+
+fn lookup<T>(handle: &str, kind: &str, f: impl FnOnce(&Shared) -> Option<T>) -> Result<T> {
+    let registry = lock_registry()?;
+    let shared = registry
+        .get(handle)
+        .ok_or_else(|| Error::from_reason(format!("Unknown or released handle: {}", handle)))?;
+    f(shared).ok_or_else(|| {
+        Error::from_reason(format!("Handle {} refers to a {}, not a {}", handle, shared.kind(), kind))
+    })
+}
+
+#[napi]
+impl GpuDevice {
+    /// Register the device for use in worker threads
+    ///
+    /// Returns a token for `deviceFromHandle()`. The device stays alive until
+    /// the token is passed to `releaseHandle()`.
+    #[napi(catch_unwind, js_name = "toHandle")]
+    pub fn to_handle(&self) -> Result<String> {
+        register(Shared::Device(self.clone()))
+    }
+}
+
+#[napi]
+impl GpuBuffer {
+    /// Register the buffer for use in worker threads
+    ///
+    /// Returns a token for `bufferFromHandle()`. The buffer stays alive until
+    /// the token is passed to `releaseHandle()`.
+    #[napi(catch_unwind, js_name = "toHandle")]
+    pub fn to_handle(&self) -> Result<String> {
+        register(Shared::Buffer(self.clone()))
+    }
+}
+
+#[napi]
+impl GpuTexture {
+    /// Register the texture for use in worker threads
+    ///
+    /// Returns a token for `textureFromHandle()`. The texture stays alive until
+    /// the token is passed to `releaseHandle()`.
+    #[napi(catch_unwind, js_name = "toHandle")]
+    pub fn to_handle(&self) -> Result<String> {
+        register(Shared::Texture(self.clone()))
+    }
+}
+
+/// Get the device registered under `handle` (from `device.toHandle()`)
+#[napi(catch_unwind, js_name = "deviceFromHandle")]
+pub fn device_from_handle(handle: String) -> Result<GpuDevice> {
+    lookup(&handle, "device", |shared| match shared {
+        Shared::Device(device) => Some(device.clone()),
+        _ => None,
+    })
+}
+
+/// Get the buffer registered under `handle` (from `buffer.toHandle()`)
+#[napi(catch_unwind, js_name = "bufferFromHandle")]
+pub fn buffer_from_handle(handle: String) -> Result<GpuBuffer> {
+    lookup(&handle, "buffer", |shared| match shared {
+        Shared::Buffer(buffer) => Some(buffer.clone()),
+        _ => None,
+    })
+}
+
+/// Get the texture registered under `handle` (from `texture.toHandle()`)
+#[napi(catch_unwind, js_name = "textureFromHandle")]
+pub fn texture_from_handle(handle: String) -> Result<GpuTexture> {
+    lookup(&handle, "texture", |shared| match shared {
+        Shared::Texture(texture) => Some(texture.clone()),
+        _ => None,
+    })
+}
+
+/// Drop the registry's reference to a handle
+///
+/// Objects already created from the handle keep working. Returns false if
+/// the handle was unknown or already released.
+#[napi(catch_unwind, js_name = "releaseHandle")]
+pub fn release_handle(handle: String) -> Result<bool> {
+    // Released outside the lock
+    let shared = lock_registry()?.remove(&handle);
+    Ok(shared.is_some())
+}
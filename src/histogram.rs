@@ -0,0 +1,339 @@
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use std::sync::Arc;
+
+use crate::util;
+
+/// Largest bin count the workgroup bins in histogram.wgsl hold
+const MAX_BINS: u32 = 256;
+
+/// Texels per side of the tile each workgroup counts
+const TILE: u32 = 16;
+
+/// Packed pixels each workgroup counts
+const PIXELS_PER_WORKGROUP: u32 = 256 * 4;
+
+/// Histogram descriptor
+#[napi(object)]
+pub struct HistogramDescriptor {
+    pub label: Option<String>,
+    /// Bins per channel, 1 to 256 (default 256)
+    pub bins: Option<u32>,
+}
+
+/// Per-channel bin counts
+#[napi(object)]
+pub struct HistogramResult {
+    pub red: Vec<u32>,
+    pub green: Vec<u32>,
+    pub blue: Vec<u32>,
+    pub alpha: Vec<u32>,
+}
+
+/// Per-channel histogram utility
+///
+/// Counts the red, green, blue and alpha values of a texture or of packed
+/// RGBA8 pixels into `bins` equal ranges of [0, 1]. Each workgroup counts
+/// into workgroup memory before adding its bins to the output with atomics,
+/// so few invocations contend for the same counter.
+#[napi]
+pub struct GpuHistogram {
+    device: Arc<wgpu::Device>,
+    queue: Arc<wgpu::Queue>,
+    poller: crate::poller::DevicePoller,
+    label: String,
+    bins: u32,
+    texture_pipeline: wgpu::ComputePipeline,
+    buffer_pipeline: wgpu::ComputePipeline,
+}
+
+impl GpuHistogram {
+    pub(crate) fn new(
+        device: Arc<wgpu::Device>,
+        queue: Arc<wgpu::Queue>,
+        poller: crate::poller::DevicePoller,
+        descriptor: Option<HistogramDescriptor>,
+    ) -> Result<Self> {
+        let bins = descriptor.as_ref().and_then(|d| d.bins).unwrap_or(MAX_BINS);
+        if bins == 0 || bins > MAX_BINS {
+            return Err(Error::from_reason(format!(
+                "Histogram bins must be between 1 and {}, got {}",
+                MAX_BINS, bins
+            )));
+        }
+        let label = descriptor
+            .as_ref()
+            .and_then(|d| d.label.clone())
+            .unwrap_or_else(|| "histogram".to_string());
+
+        // The automatic layout would require a filterable texture, so the
+        // texture pipeline spells its layout out
+        let buffer_entry = |binding, ty| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStages::COMPUTE,
+            ty: wgpu::BindingType::Buffer {
+                ty,
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        };
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some(&label),
+            entries: &[
+                buffer_entry(0, wgpu::BufferBindingType::Uniform),
+                buffer_entry(1, wgpu::BufferBindingType::Storage { read_only: false }),
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: false },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+            ],
+        });
+        let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some(&label),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+        let texture_module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some(&label),
+            source: wgpu::ShaderSource::Wgsl(
+                [include_str!("shaders/histogram.wgsl"), include_str!("shaders/histogram_texture.wgsl")]
+                    .concat()
+                    .into(),
+            ),
+        });
+        let texture_pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some(&label),
+            layout: Some(&layout),
+            module: &texture_module,
+            entry_point: "main",
+        });
+        let buffer_pipeline = util::create_compute_pipeline(
+            &device,
+            &label,
+            &[include_str!("shaders/histogram.wgsl"), include_str!("shaders/histogram_buffer.wgsl")].concat(),
+            "main",
+        );
+
+        Ok(Self {
+            device,
+            queue,
+            poller,
+            label,
+            bins,
+            texture_pipeline,
+            buffer_pipeline,
+        })
+    }
+
+    /// Bytes of the output: `bins` u32 counters per channel
+    fn output_size(&self) -> u64 {
+        self.bins as u64 * 4 * 4
+    }
+
+    fn validate_texture(&self, source: &crate::GpuTexture) -> Result<()> {
+        let texture = &source.texture;
+        if texture.dimension() != wgpu::TextureDimension::D2 || texture.sample_count() != 1 {
+            return Err(Error::from_reason("Histogram source must be a single-sampled 2D texture"));
+        }
+        if !texture.usage().contains(wgpu::TextureUsages::TEXTURE_BINDING) {
+            return Err(Error::from_reason("Histogram source needs TEXTURE_BINDING usage"));
+        }
+        if !matches!(
+            texture.format().sample_type(None, Some(self.device.features())),
+            Some(wgpu::TextureSampleType::Float { .. })
+        ) {
+            return Err(Error::from_reason(format!(
+                "Histogram source format {} isn't a float color format",
+                crate::parse::texture_format_name(texture.format())
+            )));
+        }
+        Ok(())
+    }
+
+    fn validate_pixels(&self, pixels: &crate::GpuBuffer, count: u32) -> Result<()> {
+        util::validate_storage_buffer(&pixels.buffer, count as u64 * 4, "Histogram pixels")
+    }
+
+    fn validate_output(&self, output: &crate::GpuBuffer) -> Result<()> {
+        util::validate_storage_buffer(&output.buffer, self.output_size(), "Histogram output")?;
+        if !output.buffer.usage().contains(wgpu::BufferUsages::COPY_DST) {
+            return Err(Error::from_reason("Histogram output buffer must have COPY_DST usage"));
+        }
+        Ok(())
+    }
+
+    /// Record the histogram of mip level 0 of `source` into `output`
+    fn record_texture(&self, encoder: &mut wgpu::CommandEncoder, source: &wgpu::Texture, output: &wgpu::Buffer) {
+        encoder.clear_buffer(output, 0, Some(self.output_size()));
+        let params = util::create_params_buffer(&self.device, &format!("{} params", self.label), &[self.bins, 0]);
+        let view = source.create_view(&wgpu::TextureViewDescriptor {
+            dimension: Some(wgpu::TextureViewDimension::D2),
+            mip_level_count: Some(1),
+            array_layer_count: Some(1),
+            ..Default::default()
+        });
+        let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some(&self.label),
+            layout: &self.texture_pipeline.get_bind_group_layout(0),
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: params.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: output.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: wgpu::BindingResource::TextureView(&view),
+                },
+            ],
+        });
+        let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+            label: Some(&self.label),
+            timestamp_writes: None,
+        });
+        pass.set_pipeline(&self.texture_pipeline);
+        pass.set_bind_group(0, &bind_group, &[]);
+        pass.dispatch_workgroups(source.width().div_ceil(TILE), source.height().div_ceil(TILE), 1);
+    }
+
+    /// Record the histogram of `count` packed RGBA8 pixels into `output`
+    fn record_buffer(&self, encoder: &mut wgpu::CommandEncoder, pixels: &wgpu::Buffer, count: u32, output: &wgpu::Buffer) {
+        encoder.clear_buffer(output, 0, Some(self.output_size()));
+        if count == 0 {
+            return;
+        }
+        let params = util::create_params_buffer(&self.device, &format!("{} params", self.label), &[self.bins, count]);
+        let bind_group = util::bind_buffers(&self.device, &self.buffer_pipeline, &[&params, output, pixels]);
+        util::dispatch(encoder, &self.buffer_pipeline, &bind_group, count.div_ceil(PIXELS_PER_WORKGROUP));
+    }
+
+    /// Submit the work `record` writes into a scratch output and read the bins back
+    async fn read(&self, record: impl FnOnce(&mut wgpu::CommandEncoder, &wgpu::Buffer)) -> Result<HistogramResult> {
+        let size = self.output_size();
+        let output = util::create_storage_buffer(&self.device, &format!("{} output", self.label), size);
+        let readback = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some(&format!("{} readback", self.label)),
+            size,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: Some(&self.label) });
+        record(&mut encoder, &output);
+        encoder.copy_buffer_to_buffer(&output, 0, &readback, 0, size);
+        self.queue.submit(Some(encoder.finish()));
+
+        let slice = readback.slice(..);
+        let _polling = self.poller.track();
+        let (sender, receiver) = futures::channel::oneshot::channel();
+        slice.map_async(wgpu::MapMode::Read, move |result| {
+            let _ = sender.send(result);
+        });
+        receiver
+            .await
+            .map_err(|_| Error::from_reason("Failed to receive map result"))?
+            .map_err(|e| Error::from_reason(format!("Failed to read histogram: {:?}", e)))?;
+
+        let counts: Vec<u32> = slice
+            .get_mapped_range()
+            .chunks_exact(4)
+            .map(|bytes| u32::from_le_bytes(bytes.try_into().unwrap()))
+            .collect();
+        readback.unmap();
+
+        let mut channels = counts.chunks_exact(self.bins as usize).map(<[u32]>::to_vec);
+        Ok(HistogramResult {
+            red: channels.next().unwrap_or_default(),
+            green: channels.next().unwrap_or_default(),
+            blue: channels.next().unwrap_or_default(),
+            alpha: channels.next().unwrap_or_default(),
+        })
+    }
+}
+
+#[napi]
+impl GpuHistogram {
+    /// Bins per channel
+    #[napi(catch_unwind, getter)]
+    pub fn bins(&self) -> u32 {
+        self.bins
+    }
+
+    /// Bytes `encode()` writes: the red, green, blue and alpha bins as u32
+    #[napi(catch_unwind, getter, js_name = "outputSize")]
+    pub fn output_size_bytes(&self) -> u32 {
+        self.output_size() as u32
+    }
+
+    /// Record the histogram of a texture into a command encoder
+    ///
+    /// Counts mip level 0 of `source`, which needs TEXTURE_BINDING usage and a
+    /// float or normalized format. `output` is overwritten with the bins and
+    /// needs STORAGE and COPY_DST usage.
+    #[napi(catch_unwind)]
+    pub fn encode(
+        &self,
+        encoder: &crate::GpuCommandEncoder,
+        source: &crate::GpuTexture,
+        output: &crate::GpuBuffer,
+    ) -> Result<()> {
+        self.validate_texture(source)?;
+        self.validate_output(output)?;
+        encoder.with_encoder(|enc| {
+            self.record_texture(enc, &source.texture, &output.buffer);
+            Ok(())
+        })
+    }
+
+    /// Record the histogram of packed RGBA8 pixels into a command encoder
+    ///
+    /// Counts `count` 4-byte pixels of `pixels`, which needs STORAGE usage.
+    /// `output` is overwritten as in `encode()`.
+    #[napi(catch_unwind, js_name = "encodeBuffer")]
+    pub fn encode_buffer(
+        &self,
+        encoder: &crate::GpuCommandEncoder,
+        pixels: &crate::GpuBuffer,
+        count: u32,
+        output: &crate::GpuBuffer,
+    ) -> Result<()> {
+        if std::ptr::eq(pixels, output) {
+            return Err(Error::from_reason("Histogram output must not alias the pixel buffer"));
+        }
+        self.validate_pixels(pixels, count)?;
+        self.validate_output(output)?;
+        encoder.with_encoder(|enc| {
+            self.record_buffer(enc, &pixels.buffer, count, &output.buffer);
+            Ok(())
+        })
+    }
+
+    /// Compute the histogram of a texture and read the bins back
+    ///
+    /// Submits the work immediately and resolves once the GPU is done.
+    #[napi(catch_unwind)]
+    pub async fn compute(&self, source: &crate::GpuTexture) -> Result<HistogramResult> {
+        self.validate_texture(source)?;
+        let texture = source.texture.clone();
+        self.read(|enc, output| self.record_texture(enc, &texture, output)).await
+    }
+
+    /// Compute the histogram of packed RGBA8 pixels and read the bins back
+    #[napi(catch_unwind, js_name = "computeBuffer")]
+    pub async fn compute_buffer(&self, pixels: &crate::GpuBuffer, count: u32) -> Result<HistogramResult> {
+        self.validate_pixels(pixels, count)?;
+        let buffer = pixels.buffer.clone();
+        self.read(|enc, output| self.record_buffer(enc, &buffer, count, output)).await
+    }
+}
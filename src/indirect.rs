@@ -0,0 +1,257 @@
+//! Indirect argument structs
+//!
+//! `drawIndirect`, `drawIndexedIndirect` and `dispatchWorkgroupsIndirect`
+//! read 16, 20 and 12 byte structs of little-endian 32-bit fields from a
+//! buffer. These helpers pack them from objects, many at once at a stride,
+//! and unpack them again, e.g. to check what a culling shader wrote.
+
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+
+/// Arguments of `drawIndirect()`
+#[napi(object)]
+pub struct DrawIndirectArgs {
+    pub vertex_count: u32,
+    /// Default 1
+    pub instance_count: Option<u32>,
+    pub first_vertex: Option<u32>,
+    pub first_instance: Option<u32>,
+}
+
+/// Arguments of `drawIndexedIndirect()`
+#[napi(object)]
+pub struct DrawIndexedIndirectArgs {
+    pub index_count: u32,
+    /// Default 1
+    pub instance_count: Option<u32>,
+    pub first_index: Option<u32>,
+    pub base_vertex: Option<i32>,
+    pub first_instance: Option<u32>,
+}
+
+/// Workgroup counts of `dispatchWorkgroupsIndirect()`
+#[napi(object)]
+pub struct DispatchIndirectArgs {
+    pub x: u32,
+    /// Default 1
+    pub y: Option<u32>,
+    /// Default 1
+    pub z: Option<u32>,
+}
+
+/// Where the structs are in a buffer
+#[napi(object)]
+pub struct IndirectArgsLayout {
+    /// Byte offset of the first struct (default 0)
+    pub offset: Option<u32>,
+    /// Bytes from one struct to the next (default the struct size)
+    pub stride: Option<u32>,
+    /// Number of structs to unpack (default as many as fit)
+    pub count: Option<u32>,
+}
+
+trait IndirectArgs: Sized {
+    const NAME: &'static str;
+    const WORDS: usize;
+    fn to_words(&self) -> Vec<u32>;
+    fn from_words(words: &[u32]) -> Self;
+}
+
+impl IndirectArgs for DrawIndirectArgs {
+    const NAME: &'static str = "DrawIndirectArgs";
+    const WORDS: usize = 4;
+
+    fn to_words(&self) -> Vec<u32> {
+        vec![
+            self.vertex_count,
+            self.instance_count.unwrap_or(1),
+            self.first_vertex.unwrap_or(0),
+            self.first_instance.unwrap_or(0),
+        ]
+    }
+
+    fn from_words(words: &[u32]) -> Self {
+        Self {
+            vertex_count: words[0],
+            instance_count: Some(words[1]),
+            first_vertex: Some(words[2]),
+            first_instance: Some(words[3]),
+        }
+    }
+}
+
+impl IndirectArgs for DrawIndexedIndirectArgs {
+    const NAME: &'static str = "DrawIndexedIndirectArgs";
+    const WORDS: usize = 5;
+
+    fn to_words(&self) -> Vec<u32> {
+        vec![
+            self.index_count,
+            self.instance_count.unwrap_or(1),
+            self.first_index.unwrap_or(0),
+            self.base_vertex.unwrap_or(0) as u32,
+            self.first_instance.unwrap_or(0),
+        ]
+    }
+
+    fn from_words(words: &[u32]) -> Self {
+        Self {
+            index_count: words[0],
+            instance_count: Some(words[1]),
+            first_index: Some(words[2]),
+            base_vertex: Some(words[3] as i32),
+            first_instance: Some(words[4]),
+        }
+    }
+}
+
+impl IndirectArgs for DispatchIndirectArgs {
+    const NAME: &'static str = "DispatchIndirectArgs";
+    const WORDS: usize = 3;
+
+    fn to_words(&self) -> Vec<u32> {
+        vec![self.x, self.y.unwrap_or(1), self.z.unwrap_or(1)]
+    }
+
+    fn from_words(words: &[u32]) -> Self {
+        Self {
+            x: words[0],
+            y: Some(words[1]),
+            z: Some(words[2]),
+        }
+    }
+}
+
+/// Offset and stride of `layout`, checked for structs of type `T`
+fn offset_and_stride<T: IndirectArgs>(layout: Option<&IndirectArgsLayout>) -> Result<(usize, usize)> {
+    let size = T::WORDS * 4;
+    let offset = layout.and_then(|l| l.offset).unwrap_or(0) as usize;
+    let stride = layout.and_then(|l| l.stride).map_or(size, |stride| stride as usize);
+    if offset % 4 != 0 || stride % 4 != 0 || stride < size {
+        return Err(Error::from_reason(format!(
+            "{} offset ({}) and stride ({}) must be multiples of 4, and the stride at least {}",
+            T::NAME,
+            offset,
+            stride,
+            size
+        )));
+    }
+    Ok((offset, stride))
+}
+
+/// Write `args` into `destination`, or a new zeroed buffer just large enough
+fn pack<T: IndirectArgs>(args: Vec<T>, layout: Option<IndirectArgsLayout>, destination: Option<Buffer>) -> Result<Buffer> {
+    let (offset, stride) = offset_and_stride::<T>(layout.as_ref())?;
+    let end = match args.len() {
+        0 => offset,
+        count => offset + (count - 1) * stride + T::WORDS * 4,
+    };
+    let mut destination = match destination {
+        Some(destination) if destination.len() < end => {
+            return Err(Error::from_reason(format!(
+                "{} {} structs need {} bytes, the destination has {}",
+                args.len(),
+                T::NAME,
+                end,
+                destination.len()
+            )))
+        }
+        Some(destination) => destination,
+        None => Buffer::from(vec![0u8; end]),
+    };
+    for (index, item) in args.iter().enumerate() {
+        let at = offset + index * stride;
+        for (word, value) in item.to_words().into_iter().enumerate() {
+            destination[at + word * 4..at + word * 4 + 4].copy_from_slice(&value.to_le_bytes());
+        }
+    }
+    Ok(destination)
+}
+
+fn unpack<T: IndirectArgs>(data: Buffer, layout: Option<IndirectArgsLayout>) -> Result<Vec<T>> {
+    let (offset, stride) = offset_and_stride::<T>(layout.as_ref())?;
+    let size = T::WORDS * 4;
+    let fitting = if data.len() < offset + size { 0 } else { (data.len() - offset - size) / stride + 1 };
+    let count = match layout.and_then(|l| l.count) {
+        Some(count) if count as usize > fitting => {
+            return Err(Error::from_reason(format!(
+                "{} bytes hold {} {} structs at offset {} and stride {}, not {}",
+                data.len(),
+                fitting,
+                T::NAME,
+                offset,
+                stride,
+                count
+            )))
+        }
+        Some(count) => count as usize,
+        None => fitting,
+    };
+    Ok((0..count)
+        .map(|index| {
+            let at = offset + index * stride;
+            let words: Vec<u32> = data[at..at + size]
+                .chunks_exact(4)
+                .map(|word| u32::from_le_bytes([word[0], word[1], word[2], word[3]]))
+                .collect();
+            T::from_words(&words)
+        })
+        .collect())
+}
+
+/// Pack `drawIndirect()` arguments, 16 bytes each
+///
+/// Writes into `destination` when given, and returns it; otherwise into a
+/// new buffer that ends with the last struct.
+#[napi(catch_unwind, js_name = "packDrawIndirectArgs")]
+pub fn pack_draw_indirect_args(
+    args: Vec<DrawIndirectArgs>,
+    layout: Option<IndirectArgsLayout>,
+    destination: Option<Buffer>,
+) -> Result<Buffer> {
+    pack(args, layout, destination)
+}
+
+/// Pack `drawIndexedIndirect()` arguments, 20 bytes each
+#[napi(catch_unwind, js_name = "packDrawIndexedIndirectArgs")]
+pub fn pack_draw_indexed_indirect_args(
+    args: Vec<DrawIndexedIndirectArgs>,
+    layout: Option<IndirectArgsLayout>,
+    destination: Option<Buffer>,
+) -> Result<Buffer> {
+    pack(args, layout, destination)
+}
+
+/// Pack `dispatchWorkgroupsIndirect()` arguments, 12 bytes each
+#[napi(catch_unwind, js_name = "packDispatchIndirectArgs")]
+pub fn pack_dispatch_indirect_args(
+    args: Vec<DispatchIndirectArgs>,
+    layout: Option<IndirectArgsLayout>,
+    destination: Option<Buffer>,
+) -> Result<Buffer> {
+    pack(args, layout, destination)
+}
+
+/// Unpack `drawIndirect()` arguments
+#[napi(catch_unwind, js_name = "unpackDrawIndirectArgs")]
+pub fn unpack_draw_indirect_args(data: Buffer, layout: Option<IndirectArgsLayout>) -> Result<Vec<DrawIndirectArgs>> {
+    unpack(data, layout)
+}
+
+/// Unpack `drawIndexedIndirect()` arguments
+#[napi(catch_unwind, js_name = "unpackDrawIndexedIndirectArgs")]
+pub fn unpack_draw_indexed_indirect_args(
+    data: Buffer,
+    layout: Option<IndirectArgsLayout>,
+) -> Result<Vec<DrawIndexedIndirectArgs>> {
+    unpack(data, layout)
+}
+
+/// Unpack `dispatchWorkgroupsIndirect()` arguments
+#[napi(catch_unwind, js_name = "unpackDispatchIndirectArgs")]
+pub fn unpack_dispatch_indirect_args(
+    data: Buffer,
+    layout: Option<IndirectArgsLayout>,
+) -> Result<Vec<DispatchIndirectArgs>> {
+    unpack(data, layout)
+}
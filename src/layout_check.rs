@@ -0,0 +1,286 @@
+//! Bind group layout mismatch diagnostics
+//!
+//! wgpu reports a bind group that doesn't fit its layout, or a bind group
+//! whose layout doesn't fit the pipeline, with the first check that failed.
+//! When the layout entries are known, the error lists every binding that
+//! doesn't match instead, with what the layout expects (`-`) against what
+//! was provided (`+`).
+//!
+//! Entries are known for layouts created with `createBindGroupLayout()` or
+//! `createBindGroupLayoutFromShader()`, and for pipelines created with an
+//! explicit layout. Layouts wgpu derives for `layout: 'auto'` aren't.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+/// Entries of a bind group layout, kept for diagnostics
+pub(crate) type LayoutEntries = Arc<Vec<wgpu::BindGroupLayoutEntry>>;
+
+/// Layout entries of each bind group of a pipeline layout
+pub(crate) type PipelineLayoutEntries = Arc<Vec<Option<LayoutEntries>>>;
+
+/// A resource passed to `createBindGroup()`
+pub(crate) enum ProvidedResource {
+    Buffer {
+        label: Option<String>,
+        /// Size of the bound range
+        size: u64,
+        usage: wgpu::BufferUsages,
+    },
+    BufferArray(usize),
+    TextureView {
+        format: wgpu::TextureFormat,
+        sample_count: u32,
+    },
+    Sampler,
+    TextureViewArray(usize),
+    SamplerArray(usize),
+}
+
+impl ProvidedResource {
+    fn describe(&self) -> String {
+        match self {
+            ProvidedResource::Buffer { label, size, usage } => format!(
+                "buffer {}, {} bytes, usage {}",
+                crate::error::describe_label(label.as_deref()),
+                size,
+                crate::parse::buffer_usage_names(*usage).join(" | ")
+            ),
+            ProvidedResource::BufferArray(count) => format!("array of {} buffers", count),
+            ProvidedResource::TextureView { format, sample_count: 1 } => {
+                format!("texture view, {}", crate::parse::texture_format_name(*format))
+            }
+            ProvidedResource::TextureView { format, sample_count } => format!(
+                "texture view, {}, {} samples",
+                crate::parse::texture_format_name(*format),
+                sample_count
+            ),
+            ProvidedResource::Sampler => "sampler".to_string(),
+            ProvidedResource::TextureViewArray(count) => format!("array of {} texture views", count),
+            ProvidedResource::SamplerArray(count) => format!("array of {} samplers", count),
+        }
+    }
+
+    /// Whether the resource can be bound to `entry`
+    ///
+    /// Only checks that are cheap to repeat here; anything else is left to
+    /// wgpu, whose message is kept when every binding fits.
+    fn fits(&self, entry: &wgpu::BindGroupLayoutEntry) -> bool {
+        match (&entry.ty, self) {
+            (wgpu::BindingType::Buffer { ty, min_binding_size, .. }, ProvidedResource::Buffer { size, usage, .. }) => {
+                let needed = match ty {
+                    wgpu::BufferBindingType::Uniform => wgpu::BufferUsages::UNIFORM,
+                    wgpu::BufferBindingType::Storage { .. } => wgpu::BufferUsages::STORAGE,
+                };
+                entry.count.is_none()
+                    && usage.contains(needed)
+                    && min_binding_size.map_or(true, |min| *size >= min.get())
+            }
+            (wgpu::BindingType::Buffer { .. }, ProvidedResource::BufferArray(count)) => {
+                entry.count.is_some_and(|max| *count <= max.get() as usize)
+            }
+            (
+                wgpu::BindingType::Texture { sample_type, multisampled, .. },
+                ProvidedResource::TextureView { format, sample_count },
+            ) => {
+                *multisampled == (*sample_count > 1) && sample_type_fits(*sample_type, format.sample_type(None, None))
+            }
+            (wgpu::BindingType::StorageTexture { format, .. }, ProvidedResource::TextureView { format: view_format, .. }) => {
+                format == view_format
+            }
+            (wgpu::BindingType::Sampler(_), ProvidedResource::Sampler) => true,
+            (
+                wgpu::BindingType::Texture { .. } | wgpu::BindingType::StorageTexture { .. },
+                ProvidedResource::TextureViewArray(count),
+            )
+            | (wgpu::BindingType::Sampler(_), ProvidedResource::SamplerArray(count)) => {
+                entry.count.is_some_and(|max| *count <= max.get() as usize)
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Whether a view whose format samples as `view` can be bound as `layout`
+fn sample_type_fits(layout: wgpu::TextureSampleType, view: Option<wgpu::TextureSampleType>) -> bool {
+    use wgpu::TextureSampleType::*;
+    match (layout, view) {
+        // Combined depth-stencil formats depend on the view's aspect
+        (_, None) => true,
+        (Float { filterable: true }, Some(Float { filterable: true })) => true,
+        (Float { filterable: false }, Some(Float { .. } | Depth)) => true,
+        (Depth, Some(Depth)) | (Sint, Some(Sint)) | (Uint, Some(Uint)) => true,
+        _ => false,
+    }
+}
+
+/// Describe a layout entry, e.g. "storage buffer, at least 64 bytes, visible to COMPUTE"
+fn describe_entry(entry: &wgpu::BindGroupLayoutEntry) -> String {
+    let mut text = match entry.ty {
+        wgpu::BindingType::Buffer { ty, has_dynamic_offset, min_binding_size } => {
+            let mut text = format!("{} buffer", crate::parse::buffer_binding_type_name(ty));
+            if let Some(size) = min_binding_size {
+                text.push_str(&format!(", at least {} bytes", size));
+            }
+            if has_dynamic_offset {
+                text.push_str(", dynamic offset");
+            }
+            text
+        }
+        wgpu::BindingType::Sampler(ty) => format!("{} sampler", crate::parse::sampler_binding_type_name(ty)),
+        wgpu::BindingType::Texture { sample_type, view_dimension, multisampled } => format!(
+            "{} texture, {}{}",
+            crate::parse::texture_sample_type_name(sample_type),
+            crate::parse::texture_view_dimension_name(view_dimension),
+            if multisampled { ", multisampled" } else { "" }
+        ),
+        wgpu::BindingType::StorageTexture { access, format, view_dimension } => format!(
+            "{} storage texture, {}, {}",
+            crate::parse::storage_texture_access_name(access),
+            crate::parse::texture_format_name(format),
+            crate::parse::texture_view_dimension_name(view_dimension)
+        ),
+        wgpu::BindingType::AccelerationStructure => "acceleration structure".to_string(),
+    };
+    if let Some(count) = entry.count {
+        text = format!("array of {} × {}", count, text);
+    }
+    format!("{}, visible to {}", text, describe_stages(entry.visibility))
+}
+
+fn describe_stages(stages: wgpu::ShaderStages) -> String {
+    let names: Vec<&str> = [
+        (wgpu::ShaderStages::VERTEX, "VERTEX"),
+        (wgpu::ShaderStages::FRAGMENT, "FRAGMENT"),
+        (wgpu::ShaderStages::COMPUTE, "COMPUTE"),
+    ]
+    .iter()
+    .filter(|(stage, _)| stages.contains(*stage))
+    .map(|(_, name)| *name)
+    .collect();
+    if names.is_empty() {
+        "no stage".to_string()
+    } else {
+        names.join(" | ")
+    }
+}
+
+/// Diff lines for one binding, each indented for an error message
+fn binding_diff(binding: u32, expected: (&str, Option<String>), provided: (&str, Option<String>)) -> Vec<String> {
+    let width = expected.0.len().max(provided.0.len()) + 1;
+    vec![
+        format!("  binding {}:", binding),
+        format!("  - {:<width$} {}", format!("{}:", expected.0), expected.1.as_deref().unwrap_or("nothing")),
+        format!("  + {:<width$} {}", format!("{}:", provided.0), provided.1.as_deref().unwrap_or("nothing")),
+    ]
+}
+
+/// Describe the bindings of a `createBindGroup()` call that don't match `layout`
+///
+/// `None` when every binding fits, so wgpu's own message is kept.
+pub(crate) fn bind_group_diff(layout: &[wgpu::BindGroupLayoutEntry], provided: &[(u32, ProvidedResource)]) -> Option<String> {
+    let mut bindings: BTreeMap<u32, (Option<&wgpu::BindGroupLayoutEntry>, Option<&ProvidedResource>)> = BTreeMap::new();
+    for entry in layout {
+        bindings.entry(entry.binding).or_default().0 = Some(entry);
+    }
+    for (binding, resource) in provided {
+        bindings.entry(*binding).or_default().1 = Some(resource);
+    }
+
+    let lines: Vec<String> = bindings
+        .into_iter()
+        .filter(|(_, (entry, resource))| match (entry, resource) {
+            (Some(entry), Some(resource)) => !resource.fits(entry),
+            _ => true,
+        })
+        .flat_map(|(binding, (entry, resource))| {
+            binding_diff(
+                binding,
+                ("layout", entry.map(describe_entry)),
+                ("provided", resource.map(ProvidedResource::describe)),
+            )
+        })
+        .collect();
+    if lines.is_empty() {
+        return None;
+    }
+    Some(format!("entries don't match the bind group layout\n{}", lines.join("\n")))
+}
+
+/// Describe the bindings where a bind group's layout differs from the pipeline's
+fn layout_diff(pipeline: &[wgpu::BindGroupLayoutEntry], bind_group: &[wgpu::BindGroupLayoutEntry]) -> Vec<String> {
+    let mut bindings: BTreeMap<u32, (Option<&wgpu::BindGroupLayoutEntry>, Option<&wgpu::BindGroupLayoutEntry>)> = BTreeMap::new();
+    for entry in pipeline {
+        bindings.entry(entry.binding).or_default().0 = Some(entry);
+    }
+    for entry in bind_group {
+        bindings.entry(entry.binding).or_default().1 = Some(entry);
+    }
+
+    bindings
+        .into_iter()
+        .filter(|(_, (expected, bound))| expected != bound)
+        .flat_map(|(binding, (expected, bound))| {
+            binding_diff(
+                binding,
+                ("pipeline", expected.map(describe_entry)),
+                ("bind group", bound.map(describe_entry)),
+            )
+        })
+        .collect()
+}
+
+/// Tracks the pipeline and bind groups set on a pass, to explain a failed `end()`
+///
+/// Each draw or dispatch compares the layout of every bound group with the
+/// pipeline's; the first mismatch is kept.
+#[derive(Default)]
+pub(crate) struct BindingTracker {
+    /// `None` while no pipeline with an explicit layout is set
+    pipeline: Option<PipelineLayoutEntries>,
+    bind_groups: BTreeMap<u32, Option<LayoutEntries>>,
+    calls: u32,
+    mismatch: Option<String>,
+}
+
+impl BindingTracker {
+    pub(crate) fn set_pipeline(&mut self, layouts: Option<PipelineLayoutEntries>) {
+        self.pipeline = layouts;
+    }
+
+    pub(crate) fn set_bind_group(&mut self, index: u32, layout: Option<LayoutEntries>) {
+        self.bind_groups.insert(index, layout);
+    }
+
+    /// Check the bound groups for a draw or dispatch, named `call` in the message
+    pub(crate) fn check(&mut self, call: &str) {
+        self.calls += 1;
+        let (None, Some(pipeline)) = (&self.mismatch, &self.pipeline) else {
+            return;
+        };
+        for (group, expected) in pipeline.iter().enumerate() {
+            let (Some(expected), Some(Some(bound))) = (expected, self.bind_groups.get(&(group as u32))) else {
+                continue;
+            };
+            if Arc::ptr_eq(expected, bound) {
+                continue;
+            }
+            let lines = layout_diff(expected, bound);
+            if !lines.is_empty() {
+                self.mismatch = Some(format!(
+                    "bind group {} doesn't match the pipeline layout at {} {} of the pass\n{}",
+                    group,
+                    call,
+                    self.calls,
+                    lines.join("\n")
+                ));
+                return;
+            }
+        }
+    }
+
+    /// The first mismatch found, if any
+    pub(crate) fn diagnosis(&self) -> Option<String> {
+        self.mismatch.clone()
+    }
+}
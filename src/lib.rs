@@ -19,6 +19,44 @@ mod features;
 mod limits;
 mod compute_pass;
 mod render_pass;
+mod shader_cache;
+mod shader_tools;
+mod preprocess;
+mod reflect;
+mod util;
+mod poller;
+mod error;
+mod scan;
+mod radix_sort;
+mod matmul;
+mod transfer;
+mod uniform;
+mod logging;
+mod surface;
+mod format_info;
+mod timer;
+mod handles;
+mod batch;
+mod command_stream;
+mod render_loop;
+mod memory;
+mod events;
+mod blit;
+mod color;
+mod blur;
+mod histogram;
+mod tensor;
+mod half;
+mod quantize;
+mod draw2d;
+mod font;
+mod text;
+mod msaa;
+mod layout_check;
+mod compute_runner;
+mod indirect;
+mod frame_capture;
+mod chunked;
 
 pub use gpu::*;
 pub use adapter::*;
@@ -37,3 +75,31 @@ pub use features::*;
 pub use limits::*;
 pub use compute_pass::*;
 pub use render_pass::*;
+pub use shader_cache::*;
+pub use shader_tools::*;
+pub use preprocess::*;
+pub use scan::*;
+pub use radix_sort::*;
+pub use matmul::*;
+pub use transfer::*;
+pub use uniform::*;
+pub use logging::*;
+pub use surface::*;
+pub use format_info::*;
+pub use timer::*;
+pub use handles::*;
+pub use render_loop::*;
+pub use events::*;
+pub use blit::*;
+pub use color::*;
+pub use blur::*;
+pub use histogram::*;
+pub use tensor::*;
+pub use half::*;
+pub use quantize::*;
+pub use draw2d::*;
+pub use text::*;
+pub use compute_runner::*;
+pub use indirect::*;
+pub use frame_capture::*;
+pub use chunked::*;
@@ -1,4 +1,6 @@
+use napi::bindgen_prelude::*;
 use napi_derive::napi;
+use std::collections::HashMap;
 
 /// GPU supported limits following WebGPU spec
 #[napi(object)]
@@ -99,3 +101,150 @@ impl GpuSupportedLimits {
         }
     }
 }
+
+/// The limits a device is requested with
+///
+/// Starts from `preset` ("default", "downlevel" or "adapterMaximum"; the best
+/// standard tier the adapter supports if omitted) and applies `overrides`,
+/// keyed by WebGPU limit names. Fails listing every limit the adapter can't
+/// provide.
+pub(crate) fn required_limits(
+    adapter: &wgpu::Adapter,
+    preset: Option<&str>,
+    overrides: &HashMap<String, f64>,
+) -> Result<wgpu::Limits> {
+    let tier = if preset.is_none() { crate::adapter::default_limits(adapter) } else { adapter.limits() };
+    resolve_limits(&adapter.limits(), tier, preset, overrides)
+}
+
+/// `required_limits()` for an adapter supporting `supported`, whose best
+/// standard tier is `tier`
+fn resolve_limits(
+    supported: &wgpu::Limits,
+    tier: wgpu::Limits,
+    preset: Option<&str>,
+    overrides: &HashMap<String, f64>,
+) -> Result<wgpu::Limits> {
+    let mut limits = match preset {
+        None => tier,
+        Some("default") => wgpu::Limits::default(),
+        Some("downlevel") => wgpu::Limits::downlevel_defaults(),
+        Some("adapterMaximum") => supported.clone(),
+        Some(other) => {
+            return Err(Error::from_reason(format!(
+                "Unknown limits preset: {} (expected \"default\", \"downlevel\" or \"adapterMaximum\")",
+                other
+            )))
+        }
+    };
+
+    // Sorted so errors don't depend on the map's order
+    let mut names: Vec<_> = overrides.keys().collect();
+    names.sort();
+    for name in names {
+        set_limit(&mut limits, name, overrides[name])?;
+    }
+
+    let mut exceeded = Vec::new();
+    limits.check_limits_with_fail_fn(supported, false, |field, requested, allowed| {
+        exceeded.push(format!("{} (requested {}, adapter supports {})", limit_name(field), requested, allowed));
+    });
+    if !exceeded.is_empty() {
+        return Err(Error::from_reason(format!(
+            "Required limits exceed what the adapter supports: {}",
+            exceeded.join(", ")
+        )));
+    }
+    Ok(limits)
+}
+
+/// The preset and overrides of a device descriptor's `requiredLimits`
+pub(crate) fn limits_request(descriptor: &crate::DeviceDescriptor) -> (Option<String>, HashMap<String, f64>) {
+    match &descriptor.required_limits {
+        Some(Either::B(preset)) => (Some(preset.clone()), HashMap::new()),
+        Some(Either::A(overrides)) => (descriptor.limits_preset.clone(), overrides.clone()),
+        None => (descriptor.limits_preset.clone(), HashMap::new()),
+    }
+}
+
+/// Limits of a device requested from the null adapter (extension)
+///
+/// The null adapter has no driver to ask, so it supports exactly the WebGPU
+/// default limits. `descriptor.requiredLimits` is checked against them like
+/// in `requestDevice()`; without a descriptor, these are the adapter's limits.
+#[napi(catch_unwind, js_name = "nullDeviceLimits")]
+pub fn null_device_limits(descriptor: Option<crate::DeviceDescriptor>) -> Result<GpuSupportedLimits> {
+    let descriptor = descriptor.unwrap_or_default();
+    let (preset, overrides) = limits_request(&descriptor);
+    let supported = wgpu::Limits::default();
+    let limits = resolve_limits(&supported, supported.clone(), preset.as_deref(), &overrides)?;
+    Ok(GpuSupportedLimits::from_wgpu(&limits))
+}
+
+fn set_limit(limits: &mut wgpu::Limits, name: &str, value: f64) -> Result<()> {
+    if value.fract() != 0.0 || !(0.0..=MAX_SAFE_INTEGER).contains(&value) {
+        return Err(Error::from_reason(format!(
+            "Limit {} must be a non-negative integer, got {}",
+            name, value
+        )));
+    }
+    let field = match name {
+        "maxBufferSize" => {
+            limits.max_buffer_size = value as u64;
+            return Ok(());
+        }
+        // Derived from maxBindGroups and maxVertexBuffers, which are checked instead
+        "maxBindGroupsPlusVertexBuffers" => return Ok(()),
+        "maxTextureDimension1D" => &mut limits.max_texture_dimension_1d,
+        "maxTextureDimension2D" => &mut limits.max_texture_dimension_2d,
+        "maxTextureDimension3D" => &mut limits.max_texture_dimension_3d,
+        "maxTextureArrayLayers" => &mut limits.max_texture_array_layers,
+        "maxBindGroups" => &mut limits.max_bind_groups,
+        "maxBindingsPerBindGroup" => &mut limits.max_bindings_per_bind_group,
+        "maxDynamicUniformBuffersPerPipelineLayout" => &mut limits.max_dynamic_uniform_buffers_per_pipeline_layout,
+        "maxDynamicStorageBuffersPerPipelineLayout" => &mut limits.max_dynamic_storage_buffers_per_pipeline_layout,
+        "maxSampledTexturesPerShaderStage" => &mut limits.max_sampled_textures_per_shader_stage,
+        "maxSamplersPerShaderStage" => &mut limits.max_samplers_per_shader_stage,
+        "maxStorageBuffersPerShaderStage" => &mut limits.max_storage_buffers_per_shader_stage,
+        "maxStorageTexturesPerShaderStage" => &mut limits.max_storage_textures_per_shader_stage,
+        "maxUniformBuffersPerShaderStage" => &mut limits.max_uniform_buffers_per_shader_stage,
+        "maxUniformBufferBindingSize" => &mut limits.max_uniform_buffer_binding_size,
+        "maxStorageBufferBindingSize" => &mut limits.max_storage_buffer_binding_size,
+        "minUniformBufferOffsetAlignment" => &mut limits.min_uniform_buffer_offset_alignment,
+        "minStorageBufferOffsetAlignment" => &mut limits.min_storage_buffer_offset_alignment,
+        "maxVertexBuffers" => &mut limits.max_vertex_buffers,
+        "maxVertexAttributes" => &mut limits.max_vertex_attributes,
+        "maxVertexBufferArrayStride" => &mut limits.max_vertex_buffer_array_stride,
+        "maxInterStageShaderComponents" => &mut limits.max_inter_stage_shader_components,
+        "maxComputeWorkgroupStorageSize" => &mut limits.max_compute_workgroup_storage_size,
+        "maxComputeInvocationsPerWorkgroup" => &mut limits.max_compute_invocations_per_workgroup,
+        "maxComputeWorkgroupSizeX" => &mut limits.max_compute_workgroup_size_x,
+        "maxComputeWorkgroupSizeY" => &mut limits.max_compute_workgroup_size_y,
+        "maxComputeWorkgroupSizeZ" => &mut limits.max_compute_workgroup_size_z,
+        "maxComputeWorkgroupsPerDimension" => &mut limits.max_compute_workgroups_per_dimension,
+        _ => return Err(Error::from_reason(format!("Unknown limit: {}", name))),
+    };
+    // Larger values than any adapter reports are caught by the adapter check
+    *field = value.min(u32::MAX as f64) as u32;
+    Ok(())
+}
+
+/// 2^53 - 1, the largest integer a JavaScript number holds exactly
+const MAX_SAFE_INTEGER: f64 = 9_007_199_254_740_991.0;
+
+/// WebGPU name of a wgpu limit field, e.g. "maxTextureDimension2D"
+fn limit_name(field: &str) -> String {
+    let mut parts = field.split('_');
+    let mut name = parts.next().unwrap_or_default().to_string();
+    for part in parts {
+        if part.starts_with(|c: char| c.is_ascii_digit()) {
+            // "1d" -> "1D"
+            name.push_str(&part.to_uppercase());
+        } else {
+            let mut chars = part.chars();
+            name.extend(chars.next().map(|c| c.to_ascii_uppercase()));
+            name.push_str(chars.as_str());
+        }
+    }
+    name
+}
@@ -0,0 +1,110 @@
+//! Forwarding of wgpu and naga log records to JavaScript
+//!
+//! A `log` logger is installed on the first `setLogCallback` call. Records can
+//! be emitted from any thread (the poll thread, wgpu's internal threads), so
+//! they are queued to the JavaScript callback through a threadsafe function.
+
+use napi::bindgen_prelude::*;
+use napi::threadsafe_function::{ErrorStrategy, ThreadsafeFunction, ThreadsafeFunctionCallMode};
+use napi::{Env, JsFunction};
+use napi_derive::napi;
+use std::sync::{Mutex, OnceLock};
+
+/// A log record passed to the `setLogCallback` callback
+#[napi(object)]
+pub struct LogRecord {
+    /// "error", "warn", "info", "debug" or "trace"
+    pub level: String,
+    /// Module that emitted the record, e.g. "wgpu_core::device::resource"
+    pub target: String,
+    pub message: String,
+}
+
+type LogCallback = ThreadsafeFunction<LogRecord, ErrorStrategy::Fatal>;
+
+static CALLBACK: Mutex<Option<LogCallback>> = Mutex::new(None);
+/// Whether our logger won `log::set_logger`
+static INSTALLED: OnceLock<bool> = OnceLock::new();
+
+struct JsLogger;
+
+impl log::Log for JsLogger {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &log::Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        if let Ok(callback) = CALLBACK.lock() {
+            if let Some(callback) = callback.as_ref() {
+                callback.call(
+                    LogRecord {
+                        level: record.level().as_str().to_lowercase(),
+                        target: record.target().to_string(),
+                        message: record.args().to_string(),
+                    },
+                    ThreadsafeFunctionCallMode::NonBlocking,
+                );
+            }
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+static LOGGER: JsLogger = JsLogger;
+
+/// Forward wgpu and naga log records at `level` or more severe to `callback`
+///
+/// `level` is "off", "error", "warn", "info", "debug" or "trace". Passing
+/// "off" or no callback stops forwarding. Records are delivered
+/// asynchronously, and the callback doesn't keep the process alive.
+#[napi(
+    catch_unwind,
+    js_name = "setLogCallback",
+    ts_args_type = "level: 'off' | 'error' | 'warn' | 'info' | 'debug' | 'trace', callback?: ((record: LogRecord) => void) | null"
+)]
+pub fn set_log_callback(env: Env, level: String, callback: Option<JsFunction>) -> Result<()> {
+    let filter: log::LevelFilter = level.parse().map_err(|_| {
+        Error::from_reason(format!(
+            "Invalid log level: {}. Use 'off', 'error', 'warn', 'info', 'debug' or 'trace'",
+            level
+        ))
+    })?;
+
+    if !*INSTALLED.get_or_init(|| log::set_logger(&LOGGER).is_ok()) {
+        return Err(Error::from_reason("Another logger is already installed in this process"));
+    }
+
+    let callback = match callback {
+        Some(callback) if filter != log::LevelFilter::Off => {
+            let mut callback: LogCallback = callback.create_threadsafe_function(0, |ctx| Ok(vec![ctx.value]))?;
+            callback.unref(&env)?;
+            Some(callback)
+        }
+        _ => None,
+    };
+    log::set_max_level(if callback.is_some() { filter } else { log::LevelFilter::Off });
+
+    let previous = match CALLBACK.lock() {
+        Ok(mut current) => std::mem::replace(&mut *current, callback),
+        Err(_) => return Err(Error::from_reason("Log callback state is poisoned")),
+    };
+    // Released outside the lock
+    drop(previous);
+    Ok(())
+}
+
+/// Report a misuse that doesn't fail the call
+///
+/// Goes to the `setLogCallback` callback as a "warn" record of the "webgpu"
+/// target when one is listening, to stderr otherwise.
+pub(crate) fn warn(message: &str) {
+    if log::log_enabled!(target: "webgpu", log::Level::Warn) {
+        log::warn!(target: "webgpu", "{}", message);
+    } else {
+        eprintln!("Warning: {}", message);
+    }
+}
@@ -0,0 +1,197 @@
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use std::sync::Arc;
+
+use crate::util;
+
+const TILE: u32 = 64;
+
+/// Matrix multiply descriptor
+#[napi(object)]
+pub struct MatmulDescriptor {
+    pub label: Option<String>,
+    /// Element type: "f32" (default) or "f16" (requires the "shader-f16" feature)
+    #[napi(js_name = "dataType")]
+    pub data_type: Option<String>,
+}
+
+/// Matrix dimensions: A is m x k, B is k x n, the result is m x n
+#[napi(object)]
+pub struct MatmulDimensions {
+    pub m: u32,
+    pub n: u32,
+    pub k: u32,
+}
+
+/// Tiled matrix multiply (GEMM) utility
+///
+/// Multiplies dense row-major f32/f16 matrices stored in storage buffers.
+/// Tiles are staged in workgroup memory and each invocation accumulates a 4x4
+/// block of the result in f32, so f16 inputs keep reasonable precision.
+#[napi]
+pub struct GpuMatmul {
+    device: Arc<wgpu::Device>,
+    queue: Arc<wgpu::Queue>,
+    poller: crate::poller::DevicePoller,
+    pipeline: wgpu::ComputePipeline,
+    element_size: u64,
+}
+
+impl GpuMatmul {
+    pub(crate) fn new(
+        device: Arc<wgpu::Device>,
+        queue: Arc<wgpu::Queue>,
+        poller: crate::poller::DevicePoller,
+        descriptor: Option<MatmulDescriptor>,
+    ) -> Result<Self> {
+        let (prelude, element_size) = match descriptor.as_ref().and_then(|d| d.data_type.as_deref()) {
+            None | Some("f32") => ("alias Element = f32;\n", 4),
+            Some("f16") => {
+                if !device.features().contains(wgpu::Features::SHADER_F16) {
+                    return Err(Error::from_reason(
+                        "f16 matrix multiply requires the 'shader-f16' feature",
+                    ));
+                }
+                ("enable f16;\nalias Element = f16;\n", 2)
+            }
+            Some(other) => {
+                return Err(Error::from_reason(format!(
+                    "Invalid matmul data type: {}. Use 'f32' or 'f16'",
+                    other
+                )))
+            }
+        };
+        let label = descriptor
+            .as_ref()
+            .and_then(|d| d.label.clone())
+            .unwrap_or_else(|| "matmul".to_string());
+
+        let pipeline = util::create_compute_pipeline(
+            &device,
+            &label,
+            &(prelude.to_string() + include_str!("shaders/matmul.wgsl")),
+            "main",
+        );
+
+        Ok(Self {
+            device,
+            queue,
+            poller,
+            pipeline,
+            element_size,
+        })
+    }
+
+    /// Record `output = a * b` into an encoder
+    pub(crate) fn record(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        a: &wgpu::Buffer,
+        b: &wgpu::Buffer,
+        output: &wgpu::Buffer,
+        dims: &MatmulDimensions,
+    ) {
+        if dims.m == 0 || dims.n == 0 {
+            return;
+        }
+
+        let params = util::create_params_buffer(&self.device, "matmul params", &[dims.m, dims.n, dims.k]);
+        let bind_group = util::bind_buffers(&self.device, &self.pipeline, &[&params, a, b, output]);
+
+        let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+            label: Some("matmul"),
+            timestamp_writes: None,
+        });
+        pass.set_pipeline(&self.pipeline);
+        pass.set_bind_group(0, &bind_group, &[]);
+        pass.dispatch_workgroups(dims.n.div_ceil(TILE), dims.m.div_ceil(TILE), 1);
+    }
+
+    fn validate(
+        &self,
+        a: &crate::GpuBuffer,
+        b: &crate::GpuBuffer,
+        output: &crate::GpuBuffer,
+        dims: &MatmulDimensions,
+    ) -> Result<()> {
+        if std::ptr::eq(a, output) || std::ptr::eq(b, output) {
+            return Err(Error::from_reason("Matmul output must not alias an input buffer"));
+        }
+        if dims.m.div_ceil(TILE) > util::MAX_WORKGROUPS_PER_DIMENSION
+            || dims.n.div_ceil(TILE) > util::MAX_WORKGROUPS_PER_DIMENSION
+        {
+            return Err(Error::from_reason(format!(
+                "Matmul dimensions too large: m and n must be at most {}",
+                util::MAX_WORKGROUPS_PER_DIMENSION as u64 * TILE as u64
+            )));
+        }
+        let (m, n, k) = (dims.m as u64, dims.n as u64, dims.k as u64);
+        util::validate_storage_buffer(&a.buffer, m * k * self.element_size, "Matmul A")?;
+        util::validate_storage_buffer(&b.buffer, k * n * self.element_size, "Matmul B")?;
+        util::validate_storage_buffer(&output.buffer, m * n * self.element_size, "Matmul output")?;
+        Ok(())
+    }
+}
+
+#[napi]
+impl GpuMatmul {
+    /// Record `output = a * b` into a command encoder
+    #[napi(catch_unwind)]
+    pub fn encode(
+        &self,
+        encoder: &mut crate::GpuCommandEncoder,
+        a: &crate::GpuBuffer,
+        b: &crate::GpuBuffer,
+        output: &crate::GpuBuffer,
+        dims: MatmulDimensions,
+    ) -> Result<()> {
+        self.validate(a, b, output, &dims)?;
+        encoder.with_encoder(|enc| {
+            self.record(enc, &a.buffer, &b.buffer, &output.buffer, &dims);
+            Ok(())
+        })
+    }
+
+    /// Compute `output = a * b` immediately
+    ///
+    /// Convenience wrapper that encodes the multiply into a fresh command buffer and submits it.
+    #[napi(catch_unwind)]
+    pub fn run(
+        &self,
+        a: &crate::GpuBuffer,
+        b: &crate::GpuBuffer,
+        output: &crate::GpuBuffer,
+        dims: MatmulDimensions,
+    ) -> Result<()> {
+        self.validate(a, b, output, &dims)?;
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: Some("matmul") });
+        self.record(&mut encoder, &a.buffer, &b.buffer, &output.buffer, &dims);
+        self.queue.submit(Some(encoder.finish()));
+        Ok(())
+    }
+
+    /// Multiply two matrices into a newly allocated buffer
+    ///
+    /// The result buffer has STORAGE | COPY_SRC | COPY_DST usage and is
+    /// m * n elements long. The work is submitted immediately.
+    #[napi(catch_unwind)]
+    pub fn matmul(
+        &self,
+        mut env: Env,
+        a: &crate::GpuBuffer,
+        b: &crate::GpuBuffer,
+        dims: MatmulDimensions,
+    ) -> Result<crate::GpuBuffer> {
+        let output = util::create_storage_buffer(
+            &self.device,
+            "matmul output",
+            dims.m as u64 * dims.n as u64 * self.element_size,
+        );
+        let output = crate::GpuBuffer::new(output, Some("matmul output".to_string()), self.device.clone(), self.queue.clone(), self.poller.clone())
+            .track_memory(&mut env)?;
+        self.run(a, b, &output, dims)?;
+        Ok(output)
+    }
+}
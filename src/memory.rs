@@ -0,0 +1,81 @@
+//! GPU memory accounting for the JavaScript garbage collector
+//!
+//! V8 only sees the small wrapper objects of buffers and textures, so a loop
+//! that drops them without `destroy()` would rarely trigger a collection while
+//! GPU memory fills up. Their allocation size is reported as external memory,
+//! which makes the collector run in proportion to GPU allocations. The
+//! finalizer gives the memory back; dropping the last reference then releases
+//! the wgpu resource.
+
+use crate::{GpuBuffer, GpuTexture};
+use napi::bindgen_prelude::*;
+use napi::Env;
+use std::sync::atomic::{AtomicI64, Ordering};
+
+/// Bytes reported to V8 by the JavaScript object owning this value
+///
+/// Clones (e.g. for worker thread handles) report nothing, so only the
+/// original object gives the memory back.
+#[derive(Default)]
+pub(crate) struct ExternalMemory(AtomicI64);
+
+impl Clone for ExternalMemory {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+impl ExternalMemory {
+    pub(crate) fn report(&self, env: &mut Env, bytes: u64) -> Result<()> {
+        let bytes = bytes.min(i64::MAX as u64) as i64;
+        env.adjust_external_memory(bytes)?;
+        self.0.fetch_add(bytes, Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// Give back everything reported so far, e.g. once the resource is destroyed
+    pub(crate) fn release(&self, env: &mut Env) -> Result<()> {
+        let bytes = self.0.swap(0, Ordering::Relaxed);
+        if bytes > 0 {
+            env.adjust_external_memory(-bytes)?;
+        }
+        Ok(())
+    }
+}
+
+/// Approximate size of a texture's allocation, including all mip levels
+pub(crate) fn texture_size(texture: &wgpu::Texture) -> u64 {
+    let format = texture.format();
+    let (block_width, block_height) = format.block_dimensions();
+    // Depth-stencil formats have no single copy size; count 4 bytes per aspect
+    let block_size = format
+        .block_copy_size(None)
+        .unwrap_or(if format.has_depth_aspect() && format.has_stencil_aspect() { 8 } else { 4 });
+    let is_3d = texture.dimension() == wgpu::TextureDimension::D3;
+
+    (0..texture.mip_level_count())
+        .map(|level| {
+            let width = (texture.width() >> level).max(1).div_ceil(block_width) as u64;
+            let height = (texture.height() >> level).max(1).div_ceil(block_height) as u64;
+            let layers = if is_3d {
+                (texture.depth_or_array_layers() >> level).max(1)
+            } else {
+                texture.depth_or_array_layers()
+            } as u64;
+            width * height * layers * block_size as u64
+        })
+        .sum::<u64>()
+        * texture.sample_count() as u64
+}
+
+impl ObjectFinalize for GpuBuffer {
+    fn finalize(self, mut env: Env) -> Result<()> {
+        self.external_memory.release(&mut env)
+    }
+}
+
+impl ObjectFinalize for GpuTexture {
+    fn finalize(self, mut env: Env) -> Result<()> {
+        self.external_memory.release(&mut env)
+    }
+}
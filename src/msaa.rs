@@ -0,0 +1,120 @@
+//! Multisampled color targets
+//!
+//! `createMultisampleTexture` creates a multisampled texture matching the
+//! size and format of a view. A color attachment with `sampleCount` renders
+//! into such a texture, cached per device, and resolves into its view, so
+//! enabling MSAA takes the attachment option and a pipeline with the same
+//! `multisample.count`.
+
+use crate::GpuDevice;
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+struct CachedTarget {
+    size: (u32, u32),
+    view: Arc<wgpu::TextureView>,
+}
+
+/// Multisampled render targets of one device, reused by render passes
+#[derive(Default)]
+pub(crate) struct MultisampleTargets {
+    /// Keyed by format and sample count; a request for another size
+    /// replaces the target, as when a window is resized
+    targets: Mutex<HashMap<(wgpu::TextureFormat, u32), CachedTarget>>,
+}
+
+impl MultisampleTargets {
+    /// View of the cached target for `format`, `size` and `sample_count`
+    pub(crate) fn get(
+        &self,
+        device: &wgpu::Device,
+        errors: &crate::error::ErrorSink,
+        format: wgpu::TextureFormat,
+        size: (u32, u32),
+        sample_count: u32,
+    ) -> Result<Arc<wgpu::TextureView>> {
+        let mut targets = self.targets.lock().unwrap();
+        if let Some(target) = targets.get(&(format, sample_count)) {
+            if target.size == size {
+                return Ok(target.view.clone());
+            }
+        }
+
+        let texture = errors.check(
+            "beginRenderPass",
+            || format!("multisampled target: {}x{}, format: {:?}, sampleCount: {}", size.0, size.1, format, sample_count),
+            || create_texture(device, Some("multisampled target"), format, size, sample_count),
+        )?;
+        let view = Arc::new(texture.create_view(&wgpu::TextureViewDescriptor::default()));
+        targets.insert((format, sample_count), CachedTarget { size, view: view.clone() });
+        Ok(view)
+    }
+}
+
+/// Create a multisampled texture usable only as a render attachment
+fn create_texture(
+    device: &wgpu::Device,
+    label: Option<&str>,
+    format: wgpu::TextureFormat,
+    (width, height): (u32, u32),
+    sample_count: u32,
+) -> wgpu::Texture {
+    device.create_texture(&wgpu::TextureDescriptor {
+        label,
+        size: wgpu::Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count,
+        dimension: wgpu::TextureDimension::D2,
+        format,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+        view_formats: &[],
+    })
+}
+
+#[napi]
+impl GpuDevice {
+    /// Create a multisampled texture matching `view` in size and format (extension)
+    ///
+    /// Render into it and resolve into `view` with a color attachment's
+    /// `resolveTarget`. `sampleCount` defaults to 4. The texture only has
+    /// RENDER_ATTACHMENT usage.
+    #[napi(catch_unwind, js_name = "createMultisampleTexture")]
+    pub fn create_multisample_texture(
+        &self,
+        mut env: Env,
+        view: &crate::GpuTextureView,
+        sample_count: Option<u32>,
+        label: Option<String>,
+    ) -> Result<crate::GpuTexture> {
+        let sample_count = sample_count.unwrap_or(4);
+        if view.sample_count > 1 {
+            return Err(Error::from_reason(
+                "createMultisampleTexture: the view is already multisampled",
+            ));
+        }
+
+        let texture = self.errors.check(
+            "createMultisampleTexture",
+            || {
+                format!(
+                    "label: {}, size: {}x{}, format: {:?}, sampleCount: {}",
+                    crate::error::describe_label(label.as_deref()),
+                    view.size.0,
+                    view.size.1,
+                    view.format,
+                    sample_count
+                )
+            },
+            || create_texture(&self.device, label.as_deref(), view.format, view.size, sample_count),
+        )?;
+
+        crate::GpuTexture::new(texture, label, self.device.clone(), self.queue_internal.clone(), self.poller.clone())
+            .track_memory(&mut env)
+    }
+}
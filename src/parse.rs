@@ -2,31 +2,392 @@
 ///
 /// These functions convert JavaScript-friendly string formats into
 /// strongly-typed wgpu enums and structures.
+///
+/// Enums are kept in name tables. Descriptors may pass either the WebGPU name
+/// or a numeric constant, which is the value's index in its table and is
+/// looked up without any string matching.
+
+use napi::bindgen_prelude::Either;
+
+/// A descriptor enum given by its WebGPU name or its numeric constant
+pub(crate) type EnumValue = Either<String, u32>;
+
+/// The name or number of an enum value, for error messages
+pub(crate) fn describe_enum(value: &EnumValue) -> String {
+    match value {
+        Either::A(name) => name.clone(),
+        Either::B(index) => index.to_string(),
+    }
+}
+
+/// Look up an enum value by name, or by numeric constant (its index in `table`)
+pub(crate) fn lookup<T: Copy>(table: &[(&str, T)], value: &EnumValue) -> Option<T> {
+    match value {
+        Either::A(name) => table.iter().find(|(n, _)| n == name).map(|(_, v)| *v),
+        Either::B(index) => table.get(*index as usize).map(|(_, v)| *v),
+    }
+}
+
+/// WebGPU names of all non-ASTC texture formats
+const TEXTURE_FORMATS: &[(&str, wgpu::TextureFormat)] = &[
+    ("r8unorm", wgpu::TextureFormat::R8Unorm),
+    ("r8snorm", wgpu::TextureFormat::R8Snorm),
+    ("r8uint", wgpu::TextureFormat::R8Uint),
+    ("r8sint", wgpu::TextureFormat::R8Sint),
+    ("r16uint", wgpu::TextureFormat::R16Uint),
+    ("r16sint", wgpu::TextureFormat::R16Sint),
+    ("r16unorm", wgpu::TextureFormat::R16Unorm),
+    ("r16snorm", wgpu::TextureFormat::R16Snorm),
+    ("r16float", wgpu::TextureFormat::R16Float),
+    ("rg8unorm", wgpu::TextureFormat::Rg8Unorm),
+    ("rg8snorm", wgpu::TextureFormat::Rg8Snorm),
+    ("rg8uint", wgpu::TextureFormat::Rg8Uint),
+    ("rg8sint", wgpu::TextureFormat::Rg8Sint),
+    ("r32uint", wgpu::TextureFormat::R32Uint),
+    ("r32sint", wgpu::TextureFormat::R32Sint),
+    ("r32float", wgpu::TextureFormat::R32Float),
+    ("rg16uint", wgpu::TextureFormat::Rg16Uint),
+    ("rg16sint", wgpu::TextureFormat::Rg16Sint),
+    ("rg16unorm", wgpu::TextureFormat::Rg16Unorm),
+    ("rg16snorm", wgpu::TextureFormat::Rg16Snorm),
+    ("rg16float", wgpu::TextureFormat::Rg16Float),
+    ("rgba8unorm", wgpu::TextureFormat::Rgba8Unorm),
+    ("rgba8unorm-srgb", wgpu::TextureFormat::Rgba8UnormSrgb),
+    ("rgba8snorm", wgpu::TextureFormat::Rgba8Snorm),
+    ("rgba8uint", wgpu::TextureFormat::Rgba8Uint),
+    ("rgba8sint", wgpu::TextureFormat::Rgba8Sint),
+    ("bgra8unorm", wgpu::TextureFormat::Bgra8Unorm),
+    ("bgra8unorm-srgb", wgpu::TextureFormat::Bgra8UnormSrgb),
+    ("rgb9e5ufloat", wgpu::TextureFormat::Rgb9e5Ufloat),
+    ("rgb10a2uint", wgpu::TextureFormat::Rgb10a2Uint),
+    ("rgb10a2unorm", wgpu::TextureFormat::Rgb10a2Unorm),
+    ("rg11b10ufloat", wgpu::TextureFormat::Rg11b10Float),
+    ("rg32uint", wgpu::TextureFormat::Rg32Uint),
+    ("rg32sint", wgpu::TextureFormat::Rg32Sint),
+    ("rg32float", wgpu::TextureFormat::Rg32Float),
+    ("rgba16uint", wgpu::TextureFormat::Rgba16Uint),
+    ("rgba16sint", wgpu::TextureFormat::Rgba16Sint),
+    ("rgba16unorm", wgpu::TextureFormat::Rgba16Unorm),
+    ("rgba16snorm", wgpu::TextureFormat::Rgba16Snorm),
+    ("rgba16float", wgpu::TextureFormat::Rgba16Float),
+    ("rgba32uint", wgpu::TextureFormat::Rgba32Uint),
+    ("rgba32sint", wgpu::TextureFormat::Rgba32Sint),
+    ("rgba32float", wgpu::TextureFormat::Rgba32Float),
+    ("stencil8", wgpu::TextureFormat::Stencil8),
+    ("depth16unorm", wgpu::TextureFormat::Depth16Unorm),
+    ("depth24plus", wgpu::TextureFormat::Depth24Plus),
+    ("depth24plus-stencil8", wgpu::TextureFormat::Depth24PlusStencil8),
+    ("depth32float", wgpu::TextureFormat::Depth32Float),
+    ("depth32float-stencil8", wgpu::TextureFormat::Depth32FloatStencil8),
+    ("bc1-rgba-unorm", wgpu::TextureFormat::Bc1RgbaUnorm),
+    ("bc1-rgba-unorm-srgb", wgpu::TextureFormat::Bc1RgbaUnormSrgb),
+    ("bc2-rgba-unorm", wgpu::TextureFormat::Bc2RgbaUnorm),
+    ("bc2-rgba-unorm-srgb", wgpu::TextureFormat::Bc2RgbaUnormSrgb),
+    ("bc3-rgba-unorm", wgpu::TextureFormat::Bc3RgbaUnorm),
+    ("bc3-rgba-unorm-srgb", wgpu::TextureFormat::Bc3RgbaUnormSrgb),
+    ("bc4-r-unorm", wgpu::TextureFormat::Bc4RUnorm),
+    ("bc4-r-snorm", wgpu::TextureFormat::Bc4RSnorm),
+    ("bc5-rg-unorm", wgpu::TextureFormat::Bc5RgUnorm),
+    ("bc5-rg-snorm", wgpu::TextureFormat::Bc5RgSnorm),
+    ("bc6h-rgb-ufloat", wgpu::TextureFormat::Bc6hRgbUfloat),
+    ("bc6h-rgb-float", wgpu::TextureFormat::Bc6hRgbFloat),
+    ("bc7-rgba-unorm", wgpu::TextureFormat::Bc7RgbaUnorm),
+    ("bc7-rgba-unorm-srgb", wgpu::TextureFormat::Bc7RgbaUnormSrgb),
+    ("etc2-rgb8unorm", wgpu::TextureFormat::Etc2Rgb8Unorm),
+    ("etc2-rgb8unorm-srgb", wgpu::TextureFormat::Etc2Rgb8UnormSrgb),
+    ("etc2-rgb8a1unorm", wgpu::TextureFormat::Etc2Rgb8A1Unorm),
+    ("etc2-rgb8a1unorm-srgb", wgpu::TextureFormat::Etc2Rgb8A1UnormSrgb),
+    ("etc2-rgba8unorm", wgpu::TextureFormat::Etc2Rgba8Unorm),
+    ("etc2-rgba8unorm-srgb", wgpu::TextureFormat::Etc2Rgba8UnormSrgb),
+    ("eac-r11unorm", wgpu::TextureFormat::EacR11Unorm),
+    ("eac-r11snorm", wgpu::TextureFormat::EacR11Snorm),
+    ("eac-rg11unorm", wgpu::TextureFormat::EacRg11Unorm),
+    ("eac-rg11snorm", wgpu::TextureFormat::EacRg11Snorm),
+];
+
+/// ASTC block sizes, as they appear in "astc-<block>-<channel>" format names
+const ASTC_BLOCKS: &[(&str, wgpu::AstcBlock)] = &[
+    ("4x4", wgpu::AstcBlock::B4x4),
+    ("5x4", wgpu::AstcBlock::B5x4),
+    ("5x5", wgpu::AstcBlock::B5x5),
+    ("6x5", wgpu::AstcBlock::B6x5),
+    ("6x6", wgpu::AstcBlock::B6x6),
+    ("8x5", wgpu::AstcBlock::B8x5),
+    ("8x6", wgpu::AstcBlock::B8x6),
+    ("8x8", wgpu::AstcBlock::B8x8),
+    ("10x5", wgpu::AstcBlock::B10x5),
+    ("10x6", wgpu::AstcBlock::B10x6),
+    ("10x8", wgpu::AstcBlock::B10x8),
+    ("10x10", wgpu::AstcBlock::B10x10),
+    ("12x10", wgpu::AstcBlock::B12x10),
+    ("12x12", wgpu::AstcBlock::B12x12),
+];
+
+const ASTC_CHANNELS: &[(&str, wgpu::AstcChannel)] = &[
+    ("unorm", wgpu::AstcChannel::Unorm),
+    ("unorm-srgb", wgpu::AstcChannel::UnormSrgb),
+    ("hdr", wgpu::AstcChannel::Hdr),
+];
+
+/// Look up a texture format by its WebGPU name
+pub(crate) fn lookup_texture_format(format: &str) -> Option<wgpu::TextureFormat> {
+    if let Some(astc) = format.strip_prefix("astc-") {
+        let (block, channel) = astc.split_once('-')?;
+        let block = ASTC_BLOCKS.iter().find(|(name, _)| *name == block)?.1;
+        let channel = ASTC_CHANNELS.iter().find(|(name, _)| *name == channel)?.1;
+        return Some(wgpu::TextureFormat::Astc { block, channel });
+    }
+    TEXTURE_FORMATS
+        .iter()
+        .find(|(name, _)| *name == format)
+        .map(|(_, format)| *format)
+}
 
-/// Parse texture format string
-pub(crate) fn parse_texture_format(format: &str) -> wgpu::TextureFormat {
-    match format {
-        "rgba8unorm" => wgpu::TextureFormat::Rgba8Unorm,
-        "bgra8unorm" => wgpu::TextureFormat::Bgra8Unorm,
-        "rgba16float" => wgpu::TextureFormat::Rgba16Float,
-        "rgba32float" => wgpu::TextureFormat::Rgba32Float,
-        "depth24plus" => wgpu::TextureFormat::Depth24Plus,
-        "depth32float" => wgpu::TextureFormat::Depth32Float,
-        _ => wgpu::TextureFormat::Rgba8Unorm,
+/// Look up a texture format by name or numeric constant
+///
+/// ASTC formats are numbered after `TEXTURE_FORMATS`, one block size at a time.
+pub(crate) fn texture_format(value: &EnumValue) -> Option<wgpu::TextureFormat> {
+    match value {
+        Either::A(name) => lookup_texture_format(name),
+        Either::B(index) => {
+            let index = *index as usize;
+            if let Some((_, format)) = TEXTURE_FORMATS.get(index) {
+                return Some(*format);
+            }
+            let astc = index - TEXTURE_FORMATS.len();
+            let block = ASTC_BLOCKS.get(astc / ASTC_CHANNELS.len())?.1;
+            let channel = ASTC_CHANNELS[astc % ASTC_CHANNELS.len()].1;
+            Some(wgpu::TextureFormat::Astc { block, channel })
+        }
     }
 }
 
-/// Parse vertex format string
-pub(crate) fn parse_vertex_format(format: &str) -> wgpu::VertexFormat {
-    match format {
-        "float32" => wgpu::VertexFormat::Float32,
-        "float32x2" => wgpu::VertexFormat::Float32x2,
-        "float32x3" => wgpu::VertexFormat::Float32x3,
-        "float32x4" => wgpu::VertexFormat::Float32x4,
-        "uint32" => wgpu::VertexFormat::Uint32,
-        "sint32" => wgpu::VertexFormat::Sint32,
-        _ => wgpu::VertexFormat::Float32x3,
+/// Parse texture format (unknown formats fall back to rgba8unorm)
+pub(crate) fn parse_texture_format(format: &EnumValue) -> wgpu::TextureFormat {
+    texture_format(format).unwrap_or(wgpu::TextureFormat::Rgba8Unorm)
+}
+
+/// Names of all texture formats, in numeric constant order
+pub(crate) fn texture_format_names() -> Vec<String> {
+    let astc = ASTC_BLOCKS.iter().flat_map(|(block, _)| {
+        ASTC_CHANNELS
+            .iter()
+            .map(move |(channel, _)| format!("astc-{}-{}", block, channel))
+    });
+    TEXTURE_FORMATS
+        .iter()
+        .map(|(name, _)| name.to_string())
+        .chain(astc)
+        .collect()
+}
+
+/// WebGPU name of a texture format, e.g. for reporting surface formats
+pub(crate) fn texture_format_name(format: wgpu::TextureFormat) -> String {
+    if let wgpu::TextureFormat::Astc { block, channel } = format {
+        let block = ASTC_BLOCKS.iter().find(|(_, b)| *b == block).map_or("", |(name, _)| name);
+        let channel = ASTC_CHANNELS.iter().find(|(_, c)| *c == channel).map_or("", |(name, _)| name);
+        return format!("astc-{}-{}", block, channel);
     }
+    TEXTURE_FORMATS
+        .iter()
+        .find(|(_, f)| *f == format)
+        .map(|(name, _)| name.to_string())
+        .unwrap_or_else(|| format!("{:?}", format).to_lowercase())
+}
+
+const PRESENT_MODES: &[(&str, wgpu::PresentMode)] = &[
+    ("fifo", wgpu::PresentMode::Fifo),
+    ("fifo-relaxed", wgpu::PresentMode::FifoRelaxed),
+    ("immediate", wgpu::PresentMode::Immediate),
+    ("mailbox", wgpu::PresentMode::Mailbox),
+    ("auto-vsync", wgpu::PresentMode::AutoVsync),
+    ("auto-no-vsync", wgpu::PresentMode::AutoNoVsync),
+];
+
+/// Parse surface present mode
+pub(crate) fn parse_present_mode(mode: &EnumValue) -> Option<wgpu::PresentMode> {
+    lookup(PRESENT_MODES, mode)
+}
+
+/// WebGPU-style name of a present mode
+pub(crate) fn present_mode_name(mode: wgpu::PresentMode) -> &'static str {
+    PRESENT_MODES.iter().find(|(_, m)| *m == mode).map_or("fifo", |(name, _)| name)
+}
+
+const ALPHA_MODES: &[(&str, wgpu::CompositeAlphaMode)] = &[
+    ("auto", wgpu::CompositeAlphaMode::Auto),
+    ("opaque", wgpu::CompositeAlphaMode::Opaque),
+    ("premultiplied", wgpu::CompositeAlphaMode::PreMultiplied),
+    ("postmultiplied", wgpu::CompositeAlphaMode::PostMultiplied),
+    ("inherit", wgpu::CompositeAlphaMode::Inherit),
+];
+
+/// Parse surface alpha mode
+pub(crate) fn parse_alpha_mode(mode: &EnumValue) -> Option<wgpu::CompositeAlphaMode> {
+    lookup(ALPHA_MODES, mode)
+}
+
+/// WebGPU-style name of a surface alpha mode
+pub(crate) fn alpha_mode_name(mode: wgpu::CompositeAlphaMode) -> &'static str {
+    ALPHA_MODES.iter().find(|(_, m)| *m == mode).map_or("auto", |(name, _)| name)
+}
+
+const VERTEX_FORMATS: &[(&str, wgpu::VertexFormat)] = &[
+    ("uint8x2", wgpu::VertexFormat::Uint8x2),
+    ("uint8x4", wgpu::VertexFormat::Uint8x4),
+    ("sint8x2", wgpu::VertexFormat::Sint8x2),
+    ("sint8x4", wgpu::VertexFormat::Sint8x4),
+    ("unorm8x2", wgpu::VertexFormat::Unorm8x2),
+    ("unorm8x4", wgpu::VertexFormat::Unorm8x4),
+    ("snorm8x2", wgpu::VertexFormat::Snorm8x2),
+    ("snorm8x4", wgpu::VertexFormat::Snorm8x4),
+    ("uint16x2", wgpu::VertexFormat::Uint16x2),
+    ("uint16x4", wgpu::VertexFormat::Uint16x4),
+    ("sint16x2", wgpu::VertexFormat::Sint16x2),
+    ("sint16x4", wgpu::VertexFormat::Sint16x4),
+    ("unorm16x2", wgpu::VertexFormat::Unorm16x2),
+    ("unorm16x4", wgpu::VertexFormat::Unorm16x4),
+    ("snorm16x2", wgpu::VertexFormat::Snorm16x2),
+    ("snorm16x4", wgpu::VertexFormat::Snorm16x4),
+    ("float16x2", wgpu::VertexFormat::Float16x2),
+    ("float16x4", wgpu::VertexFormat::Float16x4),
+    ("float32", wgpu::VertexFormat::Float32),
+    ("float32x2", wgpu::VertexFormat::Float32x2),
+    ("float32x3", wgpu::VertexFormat::Float32x3),
+    ("float32x4", wgpu::VertexFormat::Float32x4),
+    ("uint32", wgpu::VertexFormat::Uint32),
+    ("uint32x2", wgpu::VertexFormat::Uint32x2),
+    ("uint32x3", wgpu::VertexFormat::Uint32x3),
+    ("uint32x4", wgpu::VertexFormat::Uint32x4),
+    ("sint32", wgpu::VertexFormat::Sint32),
+    ("sint32x2", wgpu::VertexFormat::Sint32x2),
+    ("sint32x3", wgpu::VertexFormat::Sint32x3),
+    ("sint32x4", wgpu::VertexFormat::Sint32x4),
+];
+
+/// Parse vertex format
+pub(crate) fn parse_vertex_format(format: &EnumValue) -> wgpu::VertexFormat {
+    lookup(VERTEX_FORMATS, format).unwrap_or(wgpu::VertexFormat::Float32x3)
+}
+
+const VERTEX_STEP_MODES: &[(&str, wgpu::VertexStepMode)] = &[
+    ("vertex", wgpu::VertexStepMode::Vertex),
+    ("instance", wgpu::VertexStepMode::Instance),
+];
+
+/// Parse vertex buffer step mode
+pub(crate) fn parse_vertex_step_mode(mode: Option<&EnumValue>) -> wgpu::VertexStepMode {
+    mode.and_then(|mode| lookup(VERTEX_STEP_MODES, mode))
+        .unwrap_or(wgpu::VertexStepMode::Vertex)
+}
+
+const PRIMITIVE_TOPOLOGIES: &[(&str, wgpu::PrimitiveTopology)] = &[
+    ("point-list", wgpu::PrimitiveTopology::PointList),
+    ("line-list", wgpu::PrimitiveTopology::LineList),
+    ("line-strip", wgpu::PrimitiveTopology::LineStrip),
+    ("triangle-list", wgpu::PrimitiveTopology::TriangleList),
+    ("triangle-strip", wgpu::PrimitiveTopology::TriangleStrip),
+];
+
+/// Parse primitive topology
+pub(crate) fn parse_primitive_topology(topology: Option<&EnumValue>) -> wgpu::PrimitiveTopology {
+    topology
+        .and_then(|topology| lookup(PRIMITIVE_TOPOLOGIES, topology))
+        .unwrap_or(wgpu::PrimitiveTopology::TriangleList)
+}
+
+const INDEX_FORMATS: &[(&str, wgpu::IndexFormat)] = &[
+    ("uint16", wgpu::IndexFormat::Uint16),
+    ("uint32", wgpu::IndexFormat::Uint32),
+];
+
+/// Parse index format
+pub(crate) fn parse_index_format(format: &EnumValue) -> Option<wgpu::IndexFormat> {
+    lookup(INDEX_FORMATS, format)
+}
+
+const FRONT_FACES: &[(&str, wgpu::FrontFace)] = &[
+    ("ccw", wgpu::FrontFace::Ccw),
+    ("cw", wgpu::FrontFace::Cw),
+];
+
+/// Parse front face winding
+pub(crate) fn parse_front_face(face: Option<&EnumValue>) -> wgpu::FrontFace {
+    face.and_then(|face| lookup(FRONT_FACES, face))
+        .unwrap_or(wgpu::FrontFace::Ccw)
+}
+
+const CULL_MODES: &[(&str, Option<wgpu::Face>)] = &[
+    ("none", None),
+    ("front", Some(wgpu::Face::Front)),
+    ("back", Some(wgpu::Face::Back)),
+];
+
+/// Parse cull mode (None culls nothing)
+pub(crate) fn parse_cull_mode(mode: Option<&EnumValue>) -> Option<wgpu::Face> {
+    mode.and_then(|mode| lookup(CULL_MODES, mode)).flatten()
+}
+
+/// Attachment load ops; true clears the attachment
+const LOAD_OPS: &[(&str, bool)] = &[("load", false), ("clear", true)];
+
+/// Whether an attachment load op clears the attachment
+pub(crate) fn is_clear_load_op(op: Option<&EnumValue>) -> bool {
+    op.and_then(|op| lookup(LOAD_OPS, op)).unwrap_or(false)
+}
+
+const STORE_OPS: &[(&str, wgpu::StoreOp)] = &[
+    ("store", wgpu::StoreOp::Store),
+    ("discard", wgpu::StoreOp::Discard),
+];
+
+/// Parse attachment store op
+///
+/// "discard" lets tilers skip writing transient attachments (MSAA color, depth) back to memory.
+pub(crate) fn parse_store_op(op: Option<&EnumValue>) -> wgpu::StoreOp {
+    op.and_then(|op| lookup(STORE_OPS, op)).unwrap_or(wgpu::StoreOp::Store)
+}
+
+const TEXTURE_ASPECTS: &[(&str, wgpu::TextureAspect)] = &[
+    ("all", wgpu::TextureAspect::All),
+    ("stencil-only", wgpu::TextureAspect::StencilOnly),
+    ("depth-only", wgpu::TextureAspect::DepthOnly),
+];
+
+/// Parse texture aspect ("all", "depth-only", "stencil-only")
+///
+/// Selects one aspect of a combined depth/stencil texture for copies and views.
+pub(crate) fn parse_texture_aspect(aspect: Option<&EnumValue>) -> wgpu::TextureAspect {
+    aspect
+        .and_then(|aspect| lookup(TEXTURE_ASPECTS, aspect))
+        .unwrap_or(wgpu::TextureAspect::All)
+}
+
+const TEXTURE_DIMENSIONS: &[(&str, wgpu::TextureDimension)] = &[
+    ("1d", wgpu::TextureDimension::D1),
+    ("2d", wgpu::TextureDimension::D2),
+    ("3d", wgpu::TextureDimension::D3),
+];
+
+/// Parse texture dimension (default 2d)
+pub(crate) fn parse_texture_dimension(dimension: Option<&EnumValue>) -> wgpu::TextureDimension {
+    dimension
+        .and_then(|dimension| lookup(TEXTURE_DIMENSIONS, dimension))
+        .unwrap_or(wgpu::TextureDimension::D2)
+}
+
+const TEXTURE_VIEW_DIMENSIONS: &[(&str, wgpu::TextureViewDimension)] = &[
+    ("1d", wgpu::TextureViewDimension::D1),
+    ("2d", wgpu::TextureViewDimension::D2),
+    ("2d-array", wgpu::TextureViewDimension::D2Array),
+    ("cube", wgpu::TextureViewDimension::Cube),
+    ("cube-array", wgpu::TextureViewDimension::CubeArray),
+    ("3d", wgpu::TextureViewDimension::D3),
+];
+
+/// Parse texture view dimension (None keeps the texture's own dimension)
+pub(crate) fn parse_texture_view_dimension(dimension: Option<&EnumValue>) -> Option<wgpu::TextureViewDimension> {
+    dimension.and_then(|dimension| lookup(TEXTURE_VIEW_DIMENSIONS, dimension))
 }
 
 /// Parse blend mode string into blend state
@@ -51,68 +412,293 @@ pub(crate) fn parse_blend_mode(mode: &str) -> wgpu::BlendState {
     }
 }
 
+const ADDRESS_MODES: &[(&str, wgpu::AddressMode)] = &[
+    ("clamp-to-edge", wgpu::AddressMode::ClampToEdge),
+    ("repeat", wgpu::AddressMode::Repeat),
+    ("mirror-repeat", wgpu::AddressMode::MirrorRepeat),
+];
+
 /// Parse address mode for samplers
-pub(crate) fn parse_address_mode(mode: Option<&String>) -> wgpu::AddressMode {
-    match mode.map(|s| s.as_str()) {
-        Some("clamp-to-edge") => wgpu::AddressMode::ClampToEdge,
-        Some("repeat") => wgpu::AddressMode::Repeat,
-        Some("mirror-repeat") => wgpu::AddressMode::MirrorRepeat,
-        _ => wgpu::AddressMode::ClampToEdge,
-    }
+pub(crate) fn parse_address_mode(mode: Option<&EnumValue>) -> wgpu::AddressMode {
+    mode.and_then(|mode| lookup(ADDRESS_MODES, mode))
+        .unwrap_or(wgpu::AddressMode::ClampToEdge)
 }
 
+const FILTER_MODES: &[(&str, wgpu::FilterMode)] = &[
+    ("nearest", wgpu::FilterMode::Nearest),
+    ("linear", wgpu::FilterMode::Linear),
+];
+
 /// Parse filter mode for samplers
-pub(crate) fn parse_filter_mode(mode: Option<&String>) -> wgpu::FilterMode {
-    match mode.map(|s| s.as_str()) {
-        Some("linear") => wgpu::FilterMode::Linear,
-        Some("nearest") => wgpu::FilterMode::Nearest,
-        _ => wgpu::FilterMode::Nearest,
-    }
+pub(crate) fn parse_filter_mode(mode: Option<&EnumValue>) -> wgpu::FilterMode {
+    mode.and_then(|mode| lookup(FILTER_MODES, mode))
+        .unwrap_or(wgpu::FilterMode::Nearest)
 }
 
-/// Parse compare function for samplers
-pub(crate) fn parse_compare_function(func: Option<&String>) -> Option<wgpu::CompareFunction> {
-    match func.map(|s| s.as_str()) {
-        Some("never") => Some(wgpu::CompareFunction::Never),
-        Some("less") => Some(wgpu::CompareFunction::Less),
-        Some("equal") => Some(wgpu::CompareFunction::Equal),
-        Some("less-equal") => Some(wgpu::CompareFunction::LessEqual),
-        Some("greater") => Some(wgpu::CompareFunction::Greater),
-        Some("not-equal") => Some(wgpu::CompareFunction::NotEqual),
-        Some("greater-equal") => Some(wgpu::CompareFunction::GreaterEqual),
-        Some("always") => Some(wgpu::CompareFunction::Always),
-        _ => None,
+const COMPARE_FUNCTIONS: &[(&str, wgpu::CompareFunction)] = &[
+    ("never", wgpu::CompareFunction::Never),
+    ("less", wgpu::CompareFunction::Less),
+    ("equal", wgpu::CompareFunction::Equal),
+    ("less-equal", wgpu::CompareFunction::LessEqual),
+    ("greater", wgpu::CompareFunction::Greater),
+    ("not-equal", wgpu::CompareFunction::NotEqual),
+    ("greater-equal", wgpu::CompareFunction::GreaterEqual),
+    ("always", wgpu::CompareFunction::Always),
+];
+
+/// Parse compare function for samplers and depth/stencil states
+pub(crate) fn parse_compare_function(func: Option<&EnumValue>) -> Option<wgpu::CompareFunction> {
+    func.and_then(|func| lookup(COMPARE_FUNCTIONS, func))
+}
+
+const STENCIL_OPERATIONS: &[(&str, wgpu::StencilOperation)] = &[
+    ("keep", wgpu::StencilOperation::Keep),
+    ("zero", wgpu::StencilOperation::Zero),
+    ("replace", wgpu::StencilOperation::Replace),
+    ("invert", wgpu::StencilOperation::Invert),
+    ("increment-clamp", wgpu::StencilOperation::IncrementClamp),
+    ("decrement-clamp", wgpu::StencilOperation::DecrementClamp),
+    ("increment-wrap", wgpu::StencilOperation::IncrementWrap),
+    ("decrement-wrap", wgpu::StencilOperation::DecrementWrap),
+];
+
+/// Parse stencil operation for depth/stencil states
+pub(crate) fn parse_stencil_operation(operation: Option<&EnumValue>) -> wgpu::StencilOperation {
+    operation
+        .and_then(|operation| lookup(STENCIL_OPERATIONS, operation))
+        .unwrap_or(wgpu::StencilOperation::Keep)
+}
+
+/// Parse one face of a stencil state (defaults match WebGPU: always pass, keep)
+pub(crate) fn parse_stencil_face(face: Option<&crate::StencilFaceState>) -> wgpu::StencilFaceState {
+    match face {
+        Some(face) => wgpu::StencilFaceState {
+            compare: parse_compare_function(face.compare.as_ref()).unwrap_or(wgpu::CompareFunction::Always),
+            fail_op: parse_stencil_operation(face.fail_op.as_ref()),
+            depth_fail_op: parse_stencil_operation(face.depth_fail_op.as_ref()),
+            pass_op: parse_stencil_operation(face.pass_op.as_ref()),
+        },
+        None => wgpu::StencilFaceState::IGNORE,
     }
 }
 
+const BLEND_FACTORS: &[(&str, wgpu::BlendFactor)] = &[
+    ("zero", wgpu::BlendFactor::Zero),
+    ("one", wgpu::BlendFactor::One),
+    ("src", wgpu::BlendFactor::Src),
+    ("one-minus-src", wgpu::BlendFactor::OneMinusSrc),
+    ("src-alpha", wgpu::BlendFactor::SrcAlpha),
+    ("one-minus-src-alpha", wgpu::BlendFactor::OneMinusSrcAlpha),
+    ("dst", wgpu::BlendFactor::Dst),
+    ("one-minus-dst", wgpu::BlendFactor::OneMinusDst),
+    ("dst-alpha", wgpu::BlendFactor::DstAlpha),
+    ("one-minus-dst-alpha", wgpu::BlendFactor::OneMinusDstAlpha),
+    ("src-alpha-saturated", wgpu::BlendFactor::SrcAlphaSaturated),
+    ("constant", wgpu::BlendFactor::Constant),
+    ("one-minus-constant", wgpu::BlendFactor::OneMinusConstant),
+];
+
 /// Parse blend factor for blend states
-pub(crate) fn parse_blend_factor(factor: &str) -> wgpu::BlendFactor {
-    match factor {
-        "zero" => wgpu::BlendFactor::Zero,
-        "one" => wgpu::BlendFactor::One,
-        "src" => wgpu::BlendFactor::Src,
-        "one-minus-src" => wgpu::BlendFactor::OneMinusSrc,
-        "src-alpha" => wgpu::BlendFactor::SrcAlpha,
-        "one-minus-src-alpha" => wgpu::BlendFactor::OneMinusSrcAlpha,
-        "dst" => wgpu::BlendFactor::Dst,
-        "one-minus-dst" => wgpu::BlendFactor::OneMinusDst,
-        "dst-alpha" => wgpu::BlendFactor::DstAlpha,
-        "one-minus-dst-alpha" => wgpu::BlendFactor::OneMinusDstAlpha,
-        "src-alpha-saturated" => wgpu::BlendFactor::SrcAlphaSaturated,
-        "constant" => wgpu::BlendFactor::Constant,
-        "one-minus-constant" => wgpu::BlendFactor::OneMinusConstant,
-        _ => wgpu::BlendFactor::One,
-    }
+pub(crate) fn parse_blend_factor(factor: &EnumValue) -> wgpu::BlendFactor {
+    lookup(BLEND_FACTORS, factor).unwrap_or(wgpu::BlendFactor::One)
 }
 
+const BLEND_OPERATIONS: &[(&str, wgpu::BlendOperation)] = &[
+    ("add", wgpu::BlendOperation::Add),
+    ("subtract", wgpu::BlendOperation::Subtract),
+    ("reverse-subtract", wgpu::BlendOperation::ReverseSubtract),
+    ("min", wgpu::BlendOperation::Min),
+    ("max", wgpu::BlendOperation::Max),
+];
+
 /// Parse blend operation for blend states
-pub(crate) fn parse_blend_operation(operation: &str) -> wgpu::BlendOperation {
-    match operation {
-        "add" => wgpu::BlendOperation::Add,
-        "subtract" => wgpu::BlendOperation::Subtract,
-        "reverse-subtract" => wgpu::BlendOperation::ReverseSubtract,
-        "min" => wgpu::BlendOperation::Min,
-        "max" => wgpu::BlendOperation::Max,
-        _ => wgpu::BlendOperation::Add,
+pub(crate) fn parse_blend_operation(operation: &EnumValue) -> wgpu::BlendOperation {
+    lookup(BLEND_OPERATIONS, operation).unwrap_or(wgpu::BlendOperation::Add)
+}
+
+const BUFFER_BINDING_TYPES: &[(&str, wgpu::BufferBindingType)] = &[
+    ("uniform", wgpu::BufferBindingType::Uniform),
+    ("storage", wgpu::BufferBindingType::Storage { read_only: false }),
+    ("read-only-storage", wgpu::BufferBindingType::Storage { read_only: true }),
+];
+
+/// Parse buffer binding type (default uniform)
+pub(crate) fn parse_buffer_binding_type(ty: Option<&EnumValue>) -> wgpu::BufferBindingType {
+    ty.and_then(|ty| lookup(BUFFER_BINDING_TYPES, ty))
+        .unwrap_or(wgpu::BufferBindingType::Uniform)
+}
+
+const SAMPLER_BINDING_TYPES: &[(&str, wgpu::SamplerBindingType)] = &[
+    ("filtering", wgpu::SamplerBindingType::Filtering),
+    ("non-filtering", wgpu::SamplerBindingType::NonFiltering),
+    ("comparison", wgpu::SamplerBindingType::Comparison),
+];
+
+const TEXTURE_SAMPLE_TYPES: &[(&str, wgpu::TextureSampleType)] = &[
+    ("float", wgpu::TextureSampleType::Float { filterable: true }),
+    ("unfilterable-float", wgpu::TextureSampleType::Float { filterable: false }),
+    ("depth", wgpu::TextureSampleType::Depth),
+    ("sint", wgpu::TextureSampleType::Sint),
+    ("uint", wgpu::TextureSampleType::Uint),
+];
+
+const STORAGE_TEXTURE_ACCESSES: &[(&str, wgpu::StorageTextureAccess)] = &[
+    ("write-only", wgpu::StorageTextureAccess::WriteOnly),
+    ("read-only", wgpu::StorageTextureAccess::ReadOnly),
+    ("read-write", wgpu::StorageTextureAccess::ReadWrite),
+];
+
+/// Parse storage texture access (default write-only)
+pub(crate) fn parse_storage_texture_access(access: Option<&EnumValue>) -> wgpu::StorageTextureAccess {
+    access
+        .and_then(|access| lookup(STORAGE_TEXTURE_ACCESSES, access))
+        .unwrap_or(wgpu::StorageTextureAccess::WriteOnly)
+}
+
+/// WebGPU name of a buffer binding type
+pub(crate) fn buffer_binding_type_name(ty: wgpu::BufferBindingType) -> &'static str {
+    BUFFER_BINDING_TYPES.iter().find(|(_, t)| *t == ty).map_or("uniform", |(name, _)| name)
+}
+
+/// WebGPU name of a sampler binding type
+pub(crate) fn sampler_binding_type_name(ty: wgpu::SamplerBindingType) -> &'static str {
+    SAMPLER_BINDING_TYPES.iter().find(|(_, t)| *t == ty).map_or("filtering", |(name, _)| name)
+}
+
+/// WebGPU name of a texture sample type
+pub(crate) fn texture_sample_type_name(ty: wgpu::TextureSampleType) -> &'static str {
+    TEXTURE_SAMPLE_TYPES.iter().find(|(_, t)| *t == ty).map_or("float", |(name, _)| name)
+}
+
+/// WebGPU name of a storage texture access mode
+pub(crate) fn storage_texture_access_name(access: wgpu::StorageTextureAccess) -> &'static str {
+    STORAGE_TEXTURE_ACCESSES.iter().find(|(_, a)| *a == access).map_or("write-only", |(name, _)| name)
+}
+
+/// WebGPU name of a texture view dimension
+pub(crate) fn texture_view_dimension_name(dimension: wgpu::TextureViewDimension) -> &'static str {
+    TEXTURE_VIEW_DIMENSIONS.iter().find(|(_, d)| *d == dimension).map_or("2d", |(name, _)| name)
+}
+
+const QUERY_TYPES: &[(&str, wgpu::QueryType)] = &[
+    ("occlusion", wgpu::QueryType::Occlusion),
+    ("timestamp", wgpu::QueryType::Timestamp),
+];
+
+/// Parse query set type
+pub(crate) fn parse_query_type(ty: &EnumValue) -> Option<wgpu::QueryType> {
+    lookup(QUERY_TYPES, ty)
+}
+
+/// Usage flags given as a bit mask or as an array of flag names, e.g.
+/// `["COPY_DST", "STORAGE"]`
+pub(crate) type FlagsValue = Either<u32, Vec<String>>;
+
+/// GPUBufferUsage flag names in bit order
+const BUFFER_USAGES: &[(&str, wgpu::BufferUsages)] = &[
+    ("MAP_READ", wgpu::BufferUsages::MAP_READ),
+    ("MAP_WRITE", wgpu::BufferUsages::MAP_WRITE),
+    ("COPY_SRC", wgpu::BufferUsages::COPY_SRC),
+    ("COPY_DST", wgpu::BufferUsages::COPY_DST),
+    ("INDEX", wgpu::BufferUsages::INDEX),
+    ("VERTEX", wgpu::BufferUsages::VERTEX),
+    ("UNIFORM", wgpu::BufferUsages::UNIFORM),
+    ("STORAGE", wgpu::BufferUsages::STORAGE),
+    ("INDIRECT", wgpu::BufferUsages::INDIRECT),
+    ("QUERY_RESOLVE", wgpu::BufferUsages::QUERY_RESOLVE),
+];
+
+/// GPUTextureUsage flag names in bit order
+const TEXTURE_USAGES: &[(&str, wgpu::TextureUsages)] = &[
+    ("COPY_SRC", wgpu::TextureUsages::COPY_SRC),
+    ("COPY_DST", wgpu::TextureUsages::COPY_DST),
+    ("TEXTURE_BINDING", wgpu::TextureUsages::TEXTURE_BINDING),
+    ("STORAGE_BINDING", wgpu::TextureUsages::STORAGE_BINDING),
+    ("RENDER_ATTACHMENT", wgpu::TextureUsages::RENDER_ATTACHMENT),
+];
+
+/// Combine the flags named in `value`, or take its bit mask as is
+fn parse_flags<T: Copy + std::ops::BitOr<Output = T>>(
+    table: &[(&str, T)],
+    value: &FlagsValue,
+    empty: T,
+    from_bits: fn(u32) -> T,
+    kind: &str,
+) -> napi::Result<T> {
+    match value {
+        Either::A(bits) => Ok(from_bits(*bits)),
+        Either::B(names) => names.iter().try_fold(empty, |flags, name| {
+            table
+                .iter()
+                .find(|(n, _)| n == name)
+                .map(|(_, flag)| flags | *flag)
+                .ok_or_else(|| {
+                    let valid: Vec<&str> = table.iter().map(|(n, _)| *n).collect();
+                    napi::Error::from_reason(format!("Unknown {} usage: {}. Use {}", kind, name, valid.join(", ")))
+                })
+        }),
+    }
+}
+
+/// Parse buffer usage flags
+pub(crate) fn parse_buffer_usage(usage: &FlagsValue) -> napi::Result<wgpu::BufferUsages> {
+    parse_flags(BUFFER_USAGES, usage, wgpu::BufferUsages::empty(), wgpu::BufferUsages::from_bits_truncate, "buffer")
+}
+
+/// Parse texture usage flags
+pub(crate) fn parse_texture_usage(usage: &FlagsValue) -> napi::Result<wgpu::TextureUsages> {
+    parse_flags(TEXTURE_USAGES, usage, wgpu::TextureUsages::empty(), wgpu::TextureUsages::from_bits_truncate, "texture")
+}
+
+/// Names of the buffer usage flags set in `usage`, in bit order
+pub(crate) fn buffer_usage_names(usage: wgpu::BufferUsages) -> Vec<String> {
+    BUFFER_USAGES
+        .iter()
+        .filter(|(_, flag)| usage.contains(*flag))
+        .map(|(name, _)| name.to_string())
+        .collect()
+}
+
+/// Names of the texture usage flags set in `usage`, in bit order
+pub(crate) fn texture_usage_names(usage: wgpu::TextureUsages) -> Vec<String> {
+    TEXTURE_USAGES
+        .iter()
+        .filter(|(_, flag)| usage.contains(*flag))
+        .map(|(name, _)| name.to_string())
+        .collect()
+}
+
+/// Names of each numeric enum, keyed by the JS constant object exposing them
+pub(crate) fn enum_tables() -> Vec<(&'static str, Vec<String>)> {
+    fn names<T>(table: &[(&str, T)]) -> Vec<String> {
+        table.iter().map(|(name, _)| name.to_string()).collect()
     }
+
+    vec![
+        ("GPUTextureFormat", texture_format_names()),
+        ("GPUTextureDimension", names(TEXTURE_DIMENSIONS)),
+        ("GPUTextureViewDimension", names(TEXTURE_VIEW_DIMENSIONS)),
+        ("GPUTextureAspect", names(TEXTURE_ASPECTS)),
+        ("GPUVertexFormat", names(VERTEX_FORMATS)),
+        ("GPUVertexStepMode", names(VERTEX_STEP_MODES)),
+        ("GPUPrimitiveTopology", names(PRIMITIVE_TOPOLOGIES)),
+        ("GPUIndexFormat", names(INDEX_FORMATS)),
+        ("GPUFrontFace", names(FRONT_FACES)),
+        ("GPUCullMode", names(CULL_MODES)),
+        ("GPUCompareFunction", names(COMPARE_FUNCTIONS)),
+        ("GPUStencilOperation", names(STENCIL_OPERATIONS)),
+        ("GPUBlendFactor", names(BLEND_FACTORS)),
+        ("GPUBlendOperation", names(BLEND_OPERATIONS)),
+        ("GPULoadOp", names(LOAD_OPS)),
+        ("GPUStoreOp", names(STORE_OPS)),
+        ("GPUAddressMode", names(ADDRESS_MODES)),
+        ("GPUFilterMode", names(FILTER_MODES)),
+        ("GPUBufferBindingType", names(BUFFER_BINDING_TYPES)),
+        ("GPUSamplerBindingType", names(SAMPLER_BINDING_TYPES)),
+        ("GPUTextureSampleType", names(TEXTURE_SAMPLE_TYPES)),
+        ("GPUStorageTextureAccess", names(STORAGE_TEXTURE_ACCESSES)),
+        ("GPUQueryType", names(QUERY_TYPES)),
+        ("GPUPresentMode", names(PRESENT_MODES)),
+        ("GPUCanvasAlphaMode", names(ALPHA_MODES)),
+    ]
 }
@@ -1,3 +1,4 @@
+use napi::bindgen_prelude::Either;
 use napi_derive::napi;
 use std::sync::Arc;
 
@@ -10,6 +11,7 @@ use std::sync::Arc;
 #[napi]
 pub struct GpuPipelineLayout {
     pub(crate) layout: Arc<wgpu::PipelineLayout>,
+    pub(crate) bind_group_layouts: crate::layout_check::PipelineLayoutEntries,
 }
 
 // Note: ComputePipelineDescriptor and ComputeStage are now in descriptors.rs
@@ -21,16 +23,33 @@ pub struct GpuPipelineLayout {
 #[napi]
 pub struct GpuComputePipeline {
     pub(crate) pipeline: Arc<wgpu::ComputePipeline>,
+    /// Reflection of the pipeline's shader module, for `getWorkgroupSize()`
+    pub(crate) reflection: Arc<crate::reflect::ShaderReflection>,
+    pub(crate) entry_point: String,
+    /// Entries of the explicit layout's bind group layouts; `None` for auto layouts
+    pub(crate) bind_group_layouts: Option<crate::layout_check::PipelineLayoutEntries>,
 }
 
 #[napi]
 impl GpuComputePipeline {
     /// Get a bind group layout at a specific index (WebGPU standard method)
     /// Used for automatic layout inference when pipeline is created without explicit layout
-    #[napi(js_name = "getBindGroupLayout")]
+    #[napi(catch_unwind, js_name = "getBindGroupLayout")]
     pub fn get_bind_group_layout(&self, index: u32) -> crate::GpuBindGroupLayout {
         let layout = self.pipeline.get_bind_group_layout(index);
-        crate::GpuBindGroupLayout::new(layout)
+        let entries = self
+            .bind_group_layouts
+            .as_ref()
+            .and_then(|layouts| layouts.get(index as usize).cloned().flatten());
+        crate::GpuBindGroupLayout::new(layout, entries)
+    }
+
+    /// The entry point's `@workgroup_size` as `[x, y, z]` (extension)
+    ///
+    /// Unset dimensions are 1. Suitable as the `workgroupSize` of `dispatchThreads()`.
+    #[napi(catch_unwind, js_name = "getWorkgroupSize")]
+    pub fn get_workgroup_size(&self) -> napi::Result<Vec<u32>> {
+        Ok(self.reflection.workgroup_size(&self.entry_point)?.to_vec())
     }
 }
 
@@ -49,16 +68,22 @@ pub struct ComputePassDescriptor {
 #[napi]
 pub struct GpuRenderPipeline {
     pub(crate) pipeline: Arc<wgpu::RenderPipeline>,
+    /// Entries of the explicit layout's bind group layouts; `None` for auto layouts
+    pub(crate) bind_group_layouts: Option<crate::layout_check::PipelineLayoutEntries>,
 }
 
 #[napi]
 impl GpuRenderPipeline {
     /// Get a bind group layout at a specific index (WebGPU standard method)
     /// Used for automatic layout inference when pipeline is created without explicit layout
-    #[napi(js_name = "getBindGroupLayout")]
+    #[napi(catch_unwind, js_name = "getBindGroupLayout")]
     pub fn get_bind_group_layout(&self, index: u32) -> crate::GpuBindGroupLayout {
         let layout = self.pipeline.get_bind_group_layout(index);
-        crate::GpuBindGroupLayout::new(layout)
+        let entries = self
+            .bind_group_layouts
+            .as_ref()
+            .and_then(|layouts| layouts.get(index as usize).cloned().flatten());
+        crate::GpuBindGroupLayout::new(layout, entries)
     }
 }
 
@@ -66,7 +91,8 @@ impl GpuRenderPipeline {
 #[napi(object)]
 pub struct RenderPassDescriptor {
     pub label: Option<String>,
-    pub color_attachments: Vec<RenderPassColorAttachment>,
+    /// `null` entries skip that color location
+    pub color_attachments: Vec<Option<RenderPassColorAttachment>>,
     pub depth_stencil_attachment: Option<RenderPassDepthStencilAttachment>,
 }
 
@@ -75,8 +101,11 @@ pub struct RenderPassDescriptor {
 #[napi(object)]
 pub struct RenderPassColorAttachment {
     pub clear_value: Option<Color>,
-    pub load_op: String,
-    pub store_op: String,
+    pub load_op: Either<String, u32>,
+    pub store_op: Either<String, u32>,
+    /// Render into a multisampled target with this many samples, cached per
+    /// device, and resolve it into the view (extension)
+    pub sample_count: Option<u32>,
 }
 
 #[napi(object)]
@@ -92,10 +121,13 @@ pub struct Color {
 #[napi(object)]
 pub struct RenderPassDepthStencilAttachment {
     pub depth_clear_value: Option<f64>,
-    pub depth_load_op: Option<String>,
-    pub depth_store_op: Option<String>,
+    pub depth_load_op: Option<Either<String, u32>>,
+    pub depth_store_op: Option<Either<String, u32>>,
     pub stencil_clear_value: Option<u32>,
-    pub stencil_load_op: Option<String>,
-    pub stencil_store_op: Option<String>,
+    pub stencil_load_op: Option<Either<String, u32>>,
+    pub stencil_store_op: Option<Either<String, u32>>,
+    /// Depth is only tested, never written, so the pass can also sample it
+    pub depth_read_only: Option<bool>,
+    pub stencil_read_only: Option<bool>,
 }
 
@@ -0,0 +1,111 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Condvar, Mutex, Weak};
+
+/// Background poll thread for one device
+///
+/// wgpu only runs mapping and submitted-work-done callbacks while the device is
+/// polled. Instead of calling `device.poll(Wait)` inline on the calling thread, async
+/// APIs hold a [`PollGuard`] while they wait; the thread polls for as long as any
+/// guard is alive and sleeps otherwise. The thread exits once every handle is dropped.
+#[derive(Clone)]
+pub(crate) struct DevicePoller {
+    handle: Arc<PollerHandle>,
+}
+
+struct PollerHandle {
+    shared: Arc<Shared>,
+    auto_maintain: AtomicBool,
+}
+
+struct Shared {
+    state: Mutex<PollState>,
+    wake: Condvar,
+}
+
+#[derive(Default)]
+struct PollState {
+    waiters: usize,
+    shutdown: bool,
+}
+
+impl DevicePoller {
+    pub(crate) fn spawn(device: &Arc<wgpu::Device>) -> Self {
+        let shared = Arc::new(Shared {
+            state: Mutex::new(PollState::default()),
+            wake: Condvar::new(),
+        });
+
+        let thread_shared = shared.clone();
+        let device = Arc::downgrade(device);
+        std::thread::Builder::new()
+            .name("webgpu-device-poll".to_string())
+            .spawn(move || poll_loop(&thread_shared, &device))
+            .expect("Failed to spawn device poll thread");
+
+        Self {
+            handle: Arc::new(PollerHandle {
+                shared,
+                auto_maintain: AtomicBool::new(false),
+            }),
+        }
+    }
+
+    /// Keep the device polled until the returned guard is dropped
+    pub(crate) fn track(&self) -> PollGuard {
+        let shared = &self.handle.shared;
+        shared.state.lock().unwrap().waiters += 1;
+        shared.wake.notify_one();
+        PollGuard {
+            shared: shared.clone(),
+        }
+    }
+
+    /// Keep polling after every `queue.submit()` until that submission completes
+    pub(crate) fn set_auto_maintain(&self, enabled: bool) {
+        self.handle.auto_maintain.store(enabled, Ordering::Relaxed);
+    }
+
+    pub(crate) fn auto_maintain(&self) -> bool {
+        self.handle.auto_maintain.load(Ordering::Relaxed)
+    }
+}
+
+impl Drop for PollerHandle {
+    fn drop(&mut self) {
+        self.shared.state.lock().unwrap().shutdown = true;
+        self.shared.wake.notify_one();
+    }
+}
+
+/// Outstanding async operation; see [`DevicePoller::track`]
+pub(crate) struct PollGuard {
+    shared: Arc<Shared>,
+}
+
+impl Drop for PollGuard {
+    fn drop(&mut self) {
+        self.shared.state.lock().unwrap().waiters -= 1;
+    }
+}
+
+fn poll_loop(shared: &Shared, device: &Weak<wgpu::Device>) {
+    loop {
+        {
+            let mut state = shared.state.lock().unwrap();
+            while state.waiters == 0 && !state.shutdown {
+                state = shared.wake.wait(state).unwrap();
+            }
+            if state.shutdown {
+                return;
+            }
+        }
+
+        let Some(device) = device.upgrade() else {
+            return;
+        };
+        // Blocks until submitted work finishes, then fires the pending callbacks
+        device.poll(wgpu::Maintain::Wait);
+        drop(device);
+        std::thread::yield_now();
+    }
+}
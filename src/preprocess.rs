@@ -0,0 +1,318 @@
+//! WGSL preprocessor
+//!
+//! WGSL has no module system, so shader code is usually composed by string
+//! concatenation. This adds a small C-like preprocessing stage:
+//!
+//! - `#include "path"` (or `<path>`) splices in another file, at most once
+//! - `#define NAME value` / `#undef NAME` substitute identifiers
+//! - `#ifdef NAME` / `#ifndef NAME` / `#else` / `#endif` conditionally drop lines
+//!
+//! Every output line remembers where it came from, so parse and validation
+//! errors can be reported against the original files.
+
+use napi::bindgen_prelude::*;
+use napi::{JsFunction, JsUnknown, ValueType};
+use napi_derive::napi;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// Options for preprocessWgsl
+#[napi(object)]
+pub struct WgslPreprocessOptions {
+    /// Name used for the root source in error messages
+    pub label: Option<String>,
+    /// Compile-time defines, substituted wherever the identifier appears
+    pub defines: Option<HashMap<String, String>>,
+    /// Directories searched for `#include` files
+    #[napi(js_name = "includePaths")]
+    pub include_paths: Option<Vec<String>>,
+}
+
+/// Resolves an include path requested from a file to its source, if known
+pub(crate) type IncludeResolver<'a> = dyn Fn(&str, &str) -> Result<Option<String>> + 'a;
+
+/// Expanded source plus a map from each output line to its origin
+pub(crate) struct PreprocessedSource {
+    pub(crate) code: String,
+    files: Vec<String>,
+    /// (file index, 1-based line) for every output line
+    lines: Vec<(usize, u32)>,
+}
+
+impl PreprocessedSource {
+    /// Map a 1-based line of the expanded source to `file:line`
+    fn origin(&self, line_number: u32) -> Option<(&str, u32)> {
+        let (file, line) = *self.lines.get(line_number.checked_sub(1)? as usize)?;
+        Some((&self.files[file], line))
+    }
+
+    fn format_location(&self, location: naga::SourceLocation) -> String {
+        match self.origin(location.line_number) {
+            Some((file, line)) => format!("{}:{}:{}", file, line, location.line_position),
+            None => format!("<expanded>:{}:{}", location.line_number, location.line_position),
+        }
+    }
+
+    /// Parse and validate the expanded source with naga
+    ///
+    /// Errors are reported against the original files rather than the
+    /// expanded source the driver sees.
+    pub(crate) fn check(&self) -> Result<crate::reflect::ParsedShader> {
+        let module = naga::front::wgsl::parse_str(&self.code).map_err(|e| {
+            let mut message = format!("Failed to parse WGSL: {}", e.message());
+            for (span, label) in e.labels() {
+                if span.is_defined() {
+                    let location = self.format_location(span.location(&self.code));
+                    message.push_str(&format!("\n  at {}: {}", location, label));
+                }
+            }
+            Error::from_reason(message)
+        })?;
+
+        let info = naga::valid::Validator::new(
+            naga::valid::ValidationFlags::all(),
+            naga::valid::Capabilities::all(),
+        )
+        .validate(&module)
+        .map_err(|e| {
+            let mut message = format!("Shader validation failed: {}", e.as_inner());
+            if let Some(location) = e.location(&self.code) {
+                message.push_str(&format!("\n  at {}", self.format_location(location)));
+            }
+            Error::from_reason(message)
+        })?;
+
+        Ok(crate::reflect::ParsedShader { module, info })
+    }
+}
+
+/// Whether the source uses any preprocessor directive
+pub(crate) fn has_directives(code: &str) -> bool {
+    code.lines().any(|line| line.trim_start().starts_with('#'))
+}
+
+struct Condition {
+    /// Whether lines in the current branch are emitted
+    active: bool,
+    /// Whether the enclosing block is emitted
+    parent_active: bool,
+    seen_else: bool,
+}
+
+pub(crate) struct Preprocessor<'a> {
+    defines: HashMap<String, String>,
+    include_paths: Vec<PathBuf>,
+    resolver: Option<&'a IncludeResolver<'a>>,
+    included: HashSet<String>,
+    output: PreprocessedSource,
+}
+
+impl<'a> Preprocessor<'a> {
+    pub(crate) fn new(
+        defines: Option<HashMap<String, String>>,
+        include_paths: Option<Vec<String>>,
+        resolver: Option<&'a IncludeResolver<'a>>,
+    ) -> Self {
+        Self {
+            defines: defines.unwrap_or_default(),
+            include_paths: include_paths.unwrap_or_default().into_iter().map(PathBuf::from).collect(),
+            resolver,
+            included: HashSet::new(),
+            output: PreprocessedSource {
+                code: String::new(),
+                files: Vec::new(),
+                lines: Vec::new(),
+            },
+        }
+    }
+
+    /// Expand `code`, naming it `name` in error messages
+    pub(crate) fn run(mut self, name: &str, code: &str) -> Result<PreprocessedSource> {
+        self.included.insert(name.to_string());
+        self.process(name, code)?;
+        Ok(self.output)
+    }
+
+    fn process(&mut self, name: &str, code: &str) -> Result<()> {
+        let file = self.output.files.len();
+        self.output.files.push(name.to_string());
+        let mut conditions: Vec<Condition> = Vec::new();
+
+        for (index, line) in code.lines().enumerate() {
+            let line_number = index as u32 + 1;
+            let active = conditions.last().map_or(true, |c| c.active);
+            let error = |message: String| Error::from_reason(format!("{} at {}:{}", message, name, line_number));
+
+            let Some(directive) = line.trim_start().strip_prefix('#') else {
+                if active {
+                    let expanded = self.substitute(line);
+                    self.output.code.push_str(&expanded);
+                    self.output.code.push('\n');
+                    self.output.lines.push((file, line_number));
+                }
+                continue;
+            };
+
+            let directive = directive.trim();
+            let (keyword, argument) = directive
+                .split_once(char::is_whitespace)
+                .map(|(k, a)| (k, a.trim()))
+                .unwrap_or((directive, ""));
+
+            match keyword {
+                "ifdef" | "ifndef" => {
+                    if argument.is_empty() {
+                        return Err(error(format!("#{} requires a name", keyword)));
+                    }
+                    let defined = self.defines.contains_key(argument);
+                    conditions.push(Condition {
+                        active: active && (defined == (keyword == "ifdef")),
+                        parent_active: active,
+                        seen_else: false,
+                    });
+                }
+                "else" => {
+                    let condition = conditions
+                        .last_mut()
+                        .filter(|c| !c.seen_else)
+                        .ok_or_else(|| error("Unexpected #else".to_string()))?;
+                    condition.active = condition.parent_active && !condition.active;
+                    condition.seen_else = true;
+                }
+                "endif" => {
+                    conditions.pop().ok_or_else(|| error("Unexpected #endif".to_string()))?;
+                }
+                _ if !active => {}
+                "define" => {
+                    let (define, value) = argument
+                        .split_once(char::is_whitespace)
+                        .map(|(n, v)| (n, v.trim()))
+                        .unwrap_or((argument, ""));
+                    if !is_identifier(define) {
+                        return Err(error(format!("Invalid #define name '{}'", define)));
+                    }
+                    let value = self.substitute(value);
+                    self.defines.insert(define.to_string(), value);
+                }
+                "undef" => {
+                    self.defines.remove(argument);
+                }
+                "include" => {
+                    let path = argument
+                        .strip_prefix('"')
+                        .and_then(|a| a.strip_suffix('"'))
+                        .or_else(|| argument.strip_prefix('<').and_then(|a| a.strip_suffix('>')))
+                        .ok_or_else(|| error(format!("Malformed #include {}", argument)))?;
+                    let (key, source) = self
+                        .resolve(path, name)?
+                        .ok_or_else(|| error(format!("Cannot resolve #include \"{}\"", path)))?;
+                    // Includes are implicitly guarded: WGSL forbids redeclaration anyway
+                    if self.included.insert(key.clone()) {
+                        self.process(&key, &source)?;
+                    }
+                }
+                _ => return Err(error(format!("Unknown preprocessor directive #{}", keyword))),
+            }
+        }
+
+        if !conditions.is_empty() {
+            return Err(Error::from_reason(format!("Unterminated #ifdef/#ifndef in {}", name)));
+        }
+        Ok(())
+    }
+
+    /// Find an include, first via the resolver, then relative to the including
+    /// file, then in the include paths. Returns the file's name and source.
+    fn resolve(&self, path: &str, from: &str) -> Result<Option<(String, String)>> {
+        if let Some(resolver) = self.resolver {
+            if let Some(source) = resolver(path, from)? {
+                return Ok(Some((path.to_string(), source)));
+            }
+        }
+
+        let relative = Path::new(from).parent().map(|dir| dir.join(path));
+        let candidates = relative
+            .into_iter()
+            .chain(self.include_paths.iter().map(|dir| dir.join(path)));
+        for candidate in candidates {
+            if candidate.is_file() {
+                let source = std::fs::read_to_string(&candidate).map_err(|e| {
+                    Error::from_reason(format!("Failed to read {}: {}", candidate.display(), e))
+                })?;
+                let key = candidate.canonicalize().unwrap_or(candidate);
+                return Ok(Some((key.display().to_string(), source)));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Replace defined identifiers in a line
+    fn substitute(&self, line: &str) -> String {
+        if self.defines.is_empty() {
+            return line.to_string();
+        }
+        let mut result = String::with_capacity(line.len());
+        let mut rest = line;
+        while let Some(start) = rest.find(|c: char| c.is_alphabetic() || c == '_') {
+            let (before, from) = rest.split_at(start);
+            result.push_str(before);
+            let end = from
+                .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+                .unwrap_or(from.len());
+            let (word, after) = from.split_at(end);
+            // Digits before an identifier belong to a literal (e.g. 1u, 0x1f)
+            let in_literal = result.chars().last().is_some_and(|c| c.is_ascii_alphanumeric());
+            match self.defines.get(word) {
+                Some(value) if !in_literal => result.push_str(value),
+                _ => result.push_str(word),
+            }
+            rest = after;
+        }
+        result.push_str(rest);
+        result
+    }
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_alphabetic() || c == '_')
+        && chars.all(|c| c.is_alphanumeric() || c == '_')
+}
+
+/// Wrap a JS `(path, fromFile) => string | null | undefined` callback as a resolver
+pub(crate) fn js_include_resolver(env: Env, callback: JsFunction) -> impl Fn(&str, &str) -> Result<Option<String>> {
+    move |path, from| {
+        let args = [env.create_string(path)?, env.create_string(from)?];
+        let result: JsUnknown = callback.call(None, &args)?;
+        match result.get_type()? {
+            ValueType::String => Ok(Some(result.coerce_to_string()?.into_utf8()?.into_owned()?)),
+            ValueType::Null | ValueType::Undefined => Ok(None),
+            _ => Err(Error::from_reason(format!(
+                "Include resolver must return a string, null or undefined for \"{}\"",
+                path
+            ))),
+        }
+    }
+}
+
+/// Expand `#include`, `#define` and `#ifdef` directives in WGSL source
+///
+/// `resolveInclude(path, fromFile)` may return the source for an include;
+/// returning null falls back to the file system (relative to the including
+/// file, then `includePaths`).
+#[napi(catch_unwind, js_name = "preprocessWgsl")]
+pub fn preprocess_wgsl(
+    env: Env,
+    code: String,
+    options: Option<WgslPreprocessOptions>,
+    resolve_include: Option<JsFunction>,
+) -> Result<String> {
+    let (label, defines, include_paths) = match options {
+        Some(o) => (o.label, o.defines, o.include_paths),
+        None => (None, None, None),
+    };
+    let resolver = resolve_include.map(|callback| js_include_resolver(env, callback));
+    let source = Preprocessor::new(defines, include_paths, resolver.as_ref().map(|r| r as &IncludeResolver))
+        .run(label.as_deref().unwrap_or("<shader>"), &code)?;
+    Ok(source.code)
+}
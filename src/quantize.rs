@@ -0,0 +1,126 @@
+//! Int8 quantization
+//!
+//! Affine quantization maps a float `x` to `round(x / scale) + zeroPoint`,
+//! clamped to an int8, and back with `(q - zeroPoint) * scale`. Parameters
+//! apply to the whole tensor or to each channel.
+
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+
+/// Scale and zero point of `quantizeInt8` / `dequantizeInt8`
+#[napi(object)]
+pub struct QuantizationParams {
+    /// One scale for all values, or one per channel
+    pub scale: Either<f64, Vec<f64>>,
+    /// Zero point(s) from -128 to 127, one per channel if `scale` is (default 0)
+    pub zero_point: Option<Either<i32, Vec<i32>>>,
+    /// Whether channels alternate value by value (e.g. the last dimension of
+    /// NHWC data) rather than each filling a contiguous block (e.g. the rows
+    /// of a weight matrix). Default false
+    pub interleaved: Option<bool>,
+}
+
+/// Parameters of each channel, validated for `len` values
+struct Channels {
+    scales: Vec<f32>,
+    zero_points: Vec<i32>,
+    interleaved: bool,
+    /// Values per channel block when not interleaved
+    block: usize,
+}
+
+impl Channels {
+    fn new(method: &str, params: &QuantizationParams, len: usize) -> Result<Self> {
+        let scales: Vec<f32> = match &params.scale {
+            Either::A(scale) => vec![*scale as f32],
+            Either::B(scales) => scales.iter().map(|&scale| scale as f32).collect(),
+        };
+        if scales.is_empty() {
+            return Err(Error::from_reason(format!("{}: scale must not be empty", method)));
+        }
+        if let Some(scale) = scales.iter().find(|scale| !(scale.is_finite() && **scale > 0.0)) {
+            return Err(Error::from_reason(format!(
+                "{}: scale must be positive and finite, got {}",
+                method, scale
+            )));
+        }
+        let zero_points = match &params.zero_point {
+            None => vec![0; scales.len()],
+            Some(Either::A(zero_point)) => vec![*zero_point; scales.len()],
+            Some(Either::B(zero_points)) => zero_points.clone(),
+        };
+        if zero_points.len() != scales.len() {
+            return Err(Error::from_reason(format!(
+                "{}: {} zero points given for {} scales",
+                method,
+                zero_points.len(),
+                scales.len()
+            )));
+        }
+        if let Some(zero_point) = zero_points.iter().find(|zero_point| !(-128..=127).contains(*zero_point)) {
+            return Err(Error::from_reason(format!(
+                "{}: zero point {} is outside the int8 range",
+                method, zero_point
+            )));
+        }
+        if len % scales.len() != 0 {
+            return Err(Error::from_reason(format!(
+                "{}: {} values can't be split into {} channels",
+                method,
+                len,
+                scales.len()
+            )));
+        }
+        Ok(Self {
+            block: (len / scales.len()).max(1),
+            scales,
+            zero_points,
+            interleaved: params.interleaved.unwrap_or(false),
+        })
+    }
+
+    /// Scale and zero point of value `index`
+    fn get(&self, index: usize) -> (f32, i32) {
+        let channel = if self.interleaved {
+            index % self.scales.len()
+        } else {
+            index / self.block
+        };
+        (self.scales[channel], self.zero_points[channel])
+    }
+}
+
+/// Quantize floats to int8
+///
+/// Returns one signed byte per value. Values round to nearest even and
+/// saturate at -128 and 127; NaN becomes the zero point.
+#[napi(catch_unwind, js_name = "quantizeInt8")]
+pub fn quantize_int8(values: Float32Array, params: QuantizationParams) -> Result<Buffer> {
+    let channels = Channels::new("quantizeInt8", &params, values.len())?;
+    let bytes: Vec<u8> = values
+        .iter()
+        .enumerate()
+        .map(|(index, &value)| {
+            let (scale, zero_point) = channels.get(index);
+            // Float to int casts saturate, and NaN casts to 0
+            let quantized = ((value / scale).round_ties_even() as i32).saturating_add(zero_point);
+            quantized.clamp(-128, 127) as i8 as u8
+        })
+        .collect();
+    Ok(bytes.into())
+}
+
+/// Dequantize int8 bytes to floats
+#[napi(catch_unwind, js_name = "dequantizeInt8")]
+pub fn dequantize_int8(data: Buffer, params: QuantizationParams) -> Result<Float32Array> {
+    let channels = Channels::new("dequantizeInt8", &params, data.len())?;
+    let values: Vec<f32> = data
+        .iter()
+        .enumerate()
+        .map(|(index, &byte)| {
+            let (scale, zero_point) = channels.get(index);
+            (byte as i8 as i32 - zero_point) as f32 * scale
+        })
+        .collect();
+    Ok(Float32Array::new(values))
+}
@@ -17,7 +17,7 @@ impl GpuQuerySet {
 #[napi]
 impl GpuQuerySet {
     /// Destroy the query set
-    #[napi]
+    #[napi(catch_unwind)]
     pub fn destroy(&self) {
         // wgpu doesn't have explicit destroy for query sets
         // They're automatically cleaned up when dropped
@@ -2,38 +2,317 @@ use napi::bindgen_prelude::*;
 use napi_derive::napi;
 use std::sync::Arc;
 
+/// Largest staging allocation used per chunk of an `uploadBuffer` call
+const UPLOAD_CHUNK_SIZE: usize = 64 * 1024 * 1024;
+
+/// Options for `writeCubeFaces`
+#[napi(object)]
+pub struct CubeFacesOptions {
+    pub mip_level: Option<u32>,
+    /// First layer to write; a multiple of 6 selects a cube in a cube array
+    pub base_array_layer: Option<u32>,
+}
+
 /// GPU queue for submitting commands following WebGPU spec
 #[napi]
 pub struct GpuQueue {
     pub(crate) queue: Arc<wgpu::Queue>,
+    pub(crate) device: Arc<wgpu::Device>,
+    pub(crate) color: Arc<crate::color::ColorPipelines>,
+    poller: crate::poller::DevicePoller,
+    pub(crate) errors: crate::error::ErrorSink,
 }
 
 impl GpuQueue {
-    pub(crate) fn new(queue: Arc<wgpu::Queue>) -> Self {
-        Self { queue }
+    pub(crate) fn new(
+        queue: Arc<wgpu::Queue>,
+        device: Arc<wgpu::Device>,
+        color: Arc<crate::color::ColorPipelines>,
+        poller: crate::poller::DevicePoller,
+        errors: crate::error::ErrorSink,
+    ) -> Self {
+        Self {
+            queue,
+            device,
+            color,
+            poller,
+            errors,
+        }
     }
 }
 
 #[napi]
 impl GpuQueue {
     /// Submit command buffers to the queue (WebGPU standard - accepts array)
-    #[napi]
-    pub fn submit(&self, command_buffers: Vec<&mut crate::GpuCommandBuffer>) {
+    ///
+    /// A single command buffer is accepted too. Buffers that were already
+    /// submitted are skipped with a warning instead of failing the submission.
+    #[napi(catch_unwind)]
+    pub fn submit(
+        &self,
+        command_buffers: Either<Vec<&mut crate::GpuCommandBuffer>, &mut crate::GpuCommandBuffer>,
+    ) -> Result<()> {
+        self.errors.ensure_usable("submit")?;
+        let command_buffers = match command_buffers {
+            Either::A(command_buffers) => command_buffers,
+            Either::B(command_buffer) => vec![command_buffer],
+        };
+        let count = command_buffers.len();
         let buffers: Vec<wgpu::CommandBuffer> = command_buffers
             .into_iter()
             .filter_map(|cb| cb.buffer.take())
             .collect();
+        if buffers.len() < count {
+            crate::logging::warn(&format!(
+                "submit: skipped {} of {} command buffers that were already submitted",
+                count - buffers.len(),
+                count
+            ));
+        }
         self.queue.submit(buffers);
+
+        if self.poller.auto_maintain() {
+            // Released by the poll thread itself once this submission has completed
+            let polling = self.poller.track();
+            self.queue.on_submitted_work_done(move || drop(polling));
+        }
+        Ok(())
     }
 
     /// Write data to a buffer using the queue
-    #[napi(js_name = "writeBuffer")]
-    pub fn write_buffer(&self, buffer: &crate::GpuBuffer, offset: i64, data: Buffer) {
-        self.queue.write_buffer(&buffer.buffer, offset as u64, &data);
+    #[napi(catch_unwind, js_name = "writeBuffer")]
+    pub fn write_buffer(&self, buffer: &crate::GpuBuffer, offset: i64, data: Buffer) -> Result<()> {
+        self.errors.check(
+            "writeBuffer",
+            || {
+                format!(
+                    "buffer: {}, bufferOffset: {}, size: {}",
+                    crate::error::describe_label(buffer.label.as_deref()),
+                    offset,
+                    data.len()
+                )
+            },
+            || self.queue.write_buffer(&buffer.buffer, offset as u64, &data),
+        )
+    }
+
+    /// Write data to a texture using the queue
+    ///
+    /// The texture is passed separately from its location, as in
+    /// `copyTextureToTexture`; the JavaScript wrapper accepts the standard form.
+    #[napi(catch_unwind, js_name = "writeTexture")]
+    pub fn write_texture(
+        &self,
+        destination: &crate::GpuTexture,
+        location: crate::TextureCopyLocation,
+        data: Buffer,
+        layout: crate::TextureDataLayout,
+        size: crate::Extent3D,
+    ) -> Result<()> {
+        self.errors.check(
+            "writeTexture",
+            || {
+                format!(
+                    "destination: {}, size: {}x{}x{}",
+                    crate::error::describe_label(destination.label.as_deref()),
+                    size.width,
+                    size.height.unwrap_or(1),
+                    size.depth_or_array_layers.unwrap_or(1)
+                )
+            },
+            || {
+                self.queue.write_texture(
+                    location.to_wgpu(&destination.texture),
+                    &data,
+                    wgpu::ImageDataLayout {
+                        offset: layout.offset.unwrap_or(0) as u64,
+                        bytes_per_row: layout.bytes_per_row,
+                        rows_per_image: layout.rows_per_image,
+                    },
+                    (&size).into(),
+                )
+            },
+        )
+    }
+
+    /// Upload large data to a buffer with a single copy submission (extension)
+    ///
+    /// Unlike `writeBuffer`, which goes through wgpu's internal staging belt on every call,
+    /// the data is written straight into `mappedAtCreation` staging buffers (in 64 MiB
+    /// chunks) and copied into `buffer` by one submitted command buffer. Intended for
+    /// multi-hundred-MB uploads; `offset` and the data length must be multiples of 4.
+    #[napi(catch_unwind, js_name = "uploadBuffer")]
+    pub fn upload_buffer(&self, buffer: &crate::GpuBuffer, offset: i64, data: Buffer) -> Result<()> {
+        let offset = offset as u64;
+        let size = data.len() as u64;
+        if offset % wgpu::COPY_BUFFER_ALIGNMENT != 0 || size % wgpu::COPY_BUFFER_ALIGNMENT != 0 {
+            return Err(Error::from_reason(format!(
+                "uploadBuffer offset ({}) and size ({}) must be multiples of 4",
+                offset, size
+            )));
+        }
+        if offset + size > buffer.buffer.size() {
+            return Err(Error::from_reason(format!(
+                "uploadBuffer range {}..{} exceeds buffer size {}",
+                offset,
+                offset + size,
+                buffer.buffer.size()
+            )));
+        }
+        if size == 0 {
+            return Ok(());
+        }
+
+        let commands = self.errors.check(
+            "uploadBuffer",
+            || {
+                format!(
+                    "buffer: {}, bufferOffset: {}, size: {}",
+                    crate::error::describe_label(buffer.label.as_deref()),
+                    offset,
+                    size
+                )
+            },
+            || {
+                let mut encoder = self.device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                    label: Some("uploadBuffer"),
+                });
+                let mut chunk_offset = offset;
+                for chunk in data.chunks(UPLOAD_CHUNK_SIZE) {
+                    let staging = self.device.create_buffer(&wgpu::BufferDescriptor {
+                        label: Some("uploadBuffer staging"),
+                        size: chunk.len() as u64,
+                        usage: wgpu::BufferUsages::MAP_WRITE | wgpu::BufferUsages::COPY_SRC,
+                        mapped_at_creation: true,
+                    });
+                    staging.slice(..).get_mapped_range_mut().copy_from_slice(chunk);
+                    staging.unmap();
+
+                    // wgpu keeps the staging buffer alive until the copy has executed
+                    encoder.copy_buffer_to_buffer(&staging, 0, &buffer.buffer, chunk_offset, chunk.len() as u64);
+                    chunk_offset += chunk.len() as u64;
+                }
+                encoder.finish()
+            },
+        )?;
+        self.queue.submit(Some(commands));
+        Ok(())
+    }
+
+    /// Write the six faces of a cube map in one call (extension)
+    ///
+    /// `faces` are tightly packed images in layer order +X, -X, +Y, -Y, +Z, -Z,
+    /// written to layers `baseArrayLayer..baseArrayLayer + 6` (default 0, use a
+    /// multiple of 6 to fill one cube of a cube array) at `mipLevel`.
+    #[napi(catch_unwind, js_name = "writeCubeFaces")]
+    pub fn write_cube_faces(
+        &self,
+        texture: &crate::GpuTexture,
+        faces: Vec<Buffer>,
+        options: Option<CubeFacesOptions>,
+    ) -> Result<()> {
+        let mip_level = options.as_ref().and_then(|o| o.mip_level).unwrap_or(0);
+        let base_array_layer = options.as_ref().and_then(|o| o.base_array_layer).unwrap_or(0);
+        if faces.len() != 6 {
+            return Err(Error::from_reason(format!(
+                "writeCubeFaces needs 6 faces, got {}",
+                faces.len()
+            )));
+        }
+        let layers = texture.texture.depth_or_array_layers();
+        if base_array_layer + 6 > layers {
+            return Err(Error::from_reason(format!(
+                "writeCubeFaces layers {}..{} exceed the texture's {} array layers",
+                base_array_layer,
+                base_array_layer + 6,
+                layers
+            )));
+        }
+        if mip_level >= texture.texture.mip_level_count() {
+            return Err(Error::from_reason(format!(
+                "writeCubeFaces mipLevel {} exceeds the texture's {} mip levels",
+                mip_level,
+                texture.texture.mip_level_count()
+            )));
+        }
+
+        let format = texture.texture.format();
+        let block_size = format.block_copy_size(None).ok_or_else(|| {
+            Error::from_reason(format!("writeCubeFaces doesn't support {:?} textures", format))
+        })?;
+        let (block_width, block_height) = format.block_dimensions();
+        let size = texture
+            .texture
+            .size()
+            .mip_level_size(mip_level, texture.texture.dimension())
+            .physical_size(format);
+        let bytes_per_row = size.width / block_width * block_size;
+        let rows = size.height / block_height;
+        let face_size = (bytes_per_row * rows) as usize;
+        if let Some((index, face)) = faces.iter().enumerate().find(|(_, face)| face.len() != face_size) {
+            return Err(Error::from_reason(format!(
+                "writeCubeFaces face {} is {} bytes, expected {} ({}x{} {:?})",
+                index,
+                face.len(),
+                face_size,
+                size.width,
+                size.height,
+                format
+            )));
+        }
+
+        self.errors.check(
+            "writeCubeFaces",
+            || {
+                format!(
+                    "texture: {}, mipLevel: {}, baseArrayLayer: {}",
+                    crate::error::describe_label(texture.label.as_deref()),
+                    mip_level,
+                    base_array_layer
+                )
+            },
+            || {
+                for (layer, face) in (base_array_layer..).zip(&faces) {
+                    self.queue.write_texture(
+                        wgpu::ImageCopyTexture {
+                            texture: &texture.texture,
+                            mip_level,
+                            origin: wgpu::Origin3d { x: 0, y: 0, z: layer },
+                            aspect: wgpu::TextureAspect::All,
+                        },
+                        face,
+                        wgpu::ImageDataLayout {
+                            offset: 0,
+                            bytes_per_row: Some(bytes_per_row),
+                            rows_per_image: Some(rows),
+                        },
+                        wgpu::Extent3d {
+                            width: size.width,
+                            height: size.height,
+                            depth_or_array_layers: 1,
+                        },
+                    );
+                }
+            },
+        )
+    }
+
+    /// Resolve once all work submitted so far has finished (WebGPU standard method)
+    #[napi(catch_unwind, js_name = "onSubmittedWorkDone")]
+    pub async fn on_submitted_work_done(&self) -> Result<()> {
+        let _polling = self.poller.track();
+        let (sender, receiver) = futures::channel::oneshot::channel();
+        self.queue.on_submitted_work_done(move || {
+            let _ = sender.send(());
+        });
+
+        receiver
+            .await
+            .map_err(|_| Error::from_reason("Failed to receive submitted work done notification"))
     }
 
     /// Get the label of this queue
-    #[napi(getter)]
+    #[napi(catch_unwind, getter)]
     pub fn label(&self) -> Option<String> {
         None // wgpu doesn't expose queue labels
     }
@@ -0,0 +1,252 @@
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use std::sync::Arc;
+
+use crate::util;
+
+const WORKGROUP_SIZE: u32 = 256;
+const RADIX_BITS: u32 = 4;
+const RADIX: u32 = 1 << RADIX_BITS;
+
+/// Radix sort descriptor
+#[napi(object)]
+pub struct RadixSortDescriptor {
+    pub label: Option<String>,
+}
+
+/// GPU radix sort utility
+///
+/// Sorts u32 keys in ascending order, optionally carrying a u32 value per key.
+/// Each pass handles 4 key bits: a per-block digit histogram, a prefix sum over
+/// the histograms, and a stable scatter into a scratch buffer. Sorting happens
+/// in place; scratch buffers are allocated per call.
+#[napi]
+pub struct GpuRadixSort {
+    device: Arc<wgpu::Device>,
+    queue: Arc<wgpu::Queue>,
+    count_pipeline: wgpu::ComputePipeline,
+    scatter_pipeline: wgpu::ComputePipeline,
+    scan: crate::GpuPrefixSum,
+}
+
+impl GpuRadixSort {
+    pub(crate) fn new(
+        device: Arc<wgpu::Device>,
+        queue: Arc<wgpu::Queue>,
+        descriptor: Option<RadixSortDescriptor>,
+    ) -> Result<Self> {
+        let label = descriptor
+            .and_then(|d| d.label)
+            .unwrap_or_else(|| "radix-sort".to_string());
+
+        let count_pipeline = util::create_compute_pipeline(
+            &device,
+            &format!("{} count", label),
+            include_str!("shaders/radix_count.wgsl"),
+            "main",
+        );
+        let scatter_pipeline = util::create_compute_pipeline(
+            &device,
+            &format!("{} scatter", label),
+            include_str!("shaders/radix_scatter.wgsl"),
+            "main",
+        );
+        let scan = crate::GpuPrefixSum::new(device.clone(), queue.clone(), None)?;
+
+        Ok(Self {
+            device,
+            queue,
+            count_pipeline,
+            scatter_pipeline,
+            scan,
+        })
+    }
+
+    fn pass_count(key_bits: u32) -> u32 {
+        key_bits.div_ceil(RADIX_BITS)
+    }
+
+    /// Record an in-place sort of the first `count` keys (and values)
+    pub(crate) fn record(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        keys: &wgpu::Buffer,
+        values: Option<&wgpu::Buffer>,
+        count: u32,
+        key_bits: u32,
+    ) {
+        if count == 0 {
+            return;
+        }
+
+        let size = count as u64 * 4;
+        let block_count = count.div_ceil(WORKGROUP_SIZE);
+        let histogram_len = RADIX * block_count;
+
+        let keys_scratch = util::create_storage_buffer(&self.device, "radix-sort keys", size);
+        // Placeholders keep the bind group layout identical for key-only sorts
+        let values_source = util::create_storage_buffer(&self.device, "radix-sort values", 4);
+        let values_scratch = util::create_storage_buffer(
+            &self.device,
+            "radix-sort values",
+            if values.is_some() { size } else { 4 },
+        );
+        let histograms = util::create_storage_buffer(
+            &self.device,
+            "radix-sort histograms",
+            histogram_len as u64 * 4,
+        );
+        let offsets = util::create_storage_buffer(
+            &self.device,
+            "radix-sort offsets",
+            histogram_len as u64 * 4,
+        );
+
+        let mut src_keys = keys;
+        let mut dst_keys = &keys_scratch;
+        let mut src_values = values.unwrap_or(&values_source);
+        let mut dst_values = &values_scratch;
+
+        let passes = Self::pass_count(key_bits);
+        for pass in 0..passes {
+            let params = util::create_params_buffer(
+                &self.device,
+                "radix-sort params",
+                &[
+                    count,
+                    pass * RADIX_BITS,
+                    block_count,
+                    values.is_some() as u32,
+                ],
+            );
+
+            let bind_group = util::bind_buffers(
+                &self.device,
+                &self.count_pipeline,
+                &[&params, src_keys, &histograms],
+            );
+            util::dispatch(encoder, &self.count_pipeline, &bind_group, block_count);
+
+            self.scan
+                .record(encoder, &histograms, &offsets, histogram_len, false);
+
+            let bind_group = util::bind_buffers(
+                &self.device,
+                &self.scatter_pipeline,
+                &[
+                    &params, src_keys, src_values, &offsets, dst_keys, dst_values,
+                ],
+            );
+            util::dispatch(encoder, &self.scatter_pipeline, &bind_group, block_count);
+
+            std::mem::swap(&mut src_keys, &mut dst_keys);
+            std::mem::swap(&mut src_values, &mut dst_values);
+        }
+
+        // An odd number of passes leaves the result in the scratch buffers
+        if passes % 2 == 1 {
+            encoder.copy_buffer_to_buffer(&keys_scratch, 0, keys, 0, size);
+            if let Some(values) = values {
+                encoder.copy_buffer_to_buffer(&values_scratch, 0, values, 0, size);
+            }
+        }
+    }
+
+    fn validate(
+        keys: &crate::GpuBuffer,
+        values: Option<&crate::GpuBuffer>,
+        count: u32,
+        key_bits: u32,
+    ) -> Result<()> {
+        if key_bits == 0 || key_bits > 32 {
+            return Err(Error::from_reason(format!(
+                "keyBits must be between 1 and 32, got {}",
+                key_bits
+            )));
+        }
+        let size = count as u64 * 4;
+        let needs_copy_back = Self::pass_count(key_bits) % 2 == 1;
+        util::validate_storage_buffer(&keys.buffer, size, "Radix sort keys")?;
+        if needs_copy_back && !keys.buffer.usage().contains(wgpu::BufferUsages::COPY_DST) {
+            return Err(Error::from_reason(format!(
+                "Radix sort keys buffer needs COPY_DST usage when keyBits ({}) requires an odd number of passes",
+                key_bits
+            )));
+        }
+        if let Some(values) = values {
+            if std::ptr::eq(keys, values) {
+                return Err(Error::from_reason(
+                    "Radix sort keys and values must be different buffers",
+                ));
+            }
+            util::validate_storage_buffer(&values.buffer, size, "Radix sort values")?;
+            if needs_copy_back && !values.buffer.usage().contains(wgpu::BufferUsages::COPY_DST) {
+                return Err(Error::from_reason(format!(
+                    "Radix sort values buffer needs COPY_DST usage when keyBits ({}) requires an odd number of passes",
+                    key_bits
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[napi]
+impl GpuRadixSort {
+    /// Record an in-place sort into a command encoder
+    ///
+    /// Sorts the first `count` u32 keys ascending. If `values` is provided, each
+    /// value moves with its key (stable). `keyBits` (default 32) limits the sort
+    /// to the low bits of each key, saving passes for small key ranges.
+    #[napi(catch_unwind)]
+    pub fn encode(
+        &self,
+        encoder: &mut crate::GpuCommandEncoder,
+        keys: &crate::GpuBuffer,
+        count: u32,
+        values: Option<&crate::GpuBuffer>,
+        key_bits: Option<u32>,
+    ) -> Result<()> {
+        let key_bits = key_bits.unwrap_or(32);
+        Self::validate(keys, values, count, key_bits)?;
+        encoder.with_encoder(|enc| {
+            self.record(
+                enc,
+                &keys.buffer,
+                values.map(|v| &*v.buffer),
+                count,
+                key_bits,
+            );
+            Ok(())
+        })
+    }
+
+    /// Sort immediately
+    ///
+    /// Convenience wrapper that encodes the sort into a fresh command buffer and submits it.
+    #[napi(catch_unwind)]
+    pub fn run(
+        &self,
+        keys: &crate::GpuBuffer,
+        count: u32,
+        values: Option<&crate::GpuBuffer>,
+        key_bits: Option<u32>,
+    ) -> Result<()> {
+        let key_bits = key_bits.unwrap_or(32);
+        Self::validate(keys, values, count, key_bits)?;
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("radix-sort"),
+            });
+        self.record(
+            &mut encoder,
+            &keys.buffer,
+            values.map(|v| &*v.buffer),
+            count,
+            key_bits,
+        );
+        self.queue.submit(Some(encoder.finish()));
+        Ok(())
+    }
+}
@@ -0,0 +1,394 @@
+//! Shader reflection built on naga
+//!
+//! Shader modules keep their WGSL source and parse it with naga on first use,
+//! so modules that are never reflected on cost nothing extra.
+
+use napi::bindgen_prelude::*;
+use std::collections::BTreeMap;
+use std::num::NonZeroU64;
+use std::sync::OnceLock;
+
+/// A validated naga module
+pub(crate) struct ParsedShader {
+    pub(crate) module: naga::Module,
+    pub(crate) info: naga::valid::ModuleInfo,
+}
+
+/// Lazily parsed reflection data for a shader module
+pub(crate) struct ShaderReflection {
+    source: String,
+    parsed: OnceLock<std::result::Result<ParsedShader, String>>,
+}
+
+impl ShaderReflection {
+    pub(crate) fn new(source: String) -> Self {
+        Self {
+            source,
+            parsed: OnceLock::new(),
+        }
+    }
+
+    /// Create reflection data from a module that was already parsed
+    pub(crate) fn with_parsed(source: String, parsed: ParsedShader) -> Self {
+        Self {
+            source,
+            parsed: OnceLock::from(Ok(parsed)),
+        }
+    }
+
+    /// Get the parsed module, parsing and validating it on first use
+    pub(crate) fn parsed(&self) -> Result<&ParsedShader> {
+        self.parsed
+            .get_or_init(|| {
+                let module = crate::shader_tools::parse_wgsl(&self.source).map_err(|e| e.reason)?;
+                let info = crate::shader_tools::validate_module(&module).map_err(|e| e.reason)?;
+                Ok(ParsedShader { module, info })
+            })
+            .as_ref()
+            .map_err(|e| Error::from_reason(format!("Shader reflection unavailable: {}", e)))
+    }
+
+    /// Derive the bind group layout entries a shader expects for `group`
+    ///
+    /// Visibility covers the stages of every entry point that uses a binding;
+    /// bindings no entry point uses are visible to all stages in the module,
+    /// except writable storage, which is only visible to the vertex stage
+    /// when `features` has VERTEX_WRITABLE_STORAGE.
+    pub(crate) fn bind_group_layout_entries(
+        &self,
+        group: u32,
+        features: wgpu::Features,
+    ) -> Result<Vec<wgpu::BindGroupLayoutEntry>> {
+        let ParsedShader { module, info } = self.parsed()?;
+        let all_stages = module
+            .entry_points
+            .iter()
+            .fold(wgpu::ShaderStages::empty(), |stages, ep| stages | stage_flags(ep.stage));
+
+        let mut entries = BTreeMap::new();
+        for (handle, var) in module.global_variables.iter() {
+            let Some(binding) = var.binding.as_ref().filter(|b| b.group == group) else {
+                continue;
+            };
+
+            let mut visibility = wgpu::ShaderStages::empty();
+            for (index, ep) in module.entry_points.iter().enumerate() {
+                if !info.get_entry_point(index)[handle].is_empty() {
+                    visibility |= stage_flags(ep.stage);
+                }
+            }
+            let (ty, count) = binding_type(module, var)?;
+            if visibility.is_empty() {
+                visibility = all_stages;
+                if crate::bind_group::is_writable_storage(&ty)
+                    && !features.contains(wgpu::Features::VERTEX_WRITABLE_STORAGE)
+                {
+                    visibility.remove(wgpu::ShaderStages::VERTEX);
+                }
+            }
+
+            entries.insert(
+                binding.binding,
+                wgpu::BindGroupLayoutEntry {
+                    binding: binding.binding,
+                    visibility,
+                    ty,
+                    count,
+                },
+            );
+        }
+
+        Ok(entries.into_values().collect())
+    }
+
+    /// Memory layout of the WGSL struct named `name`
+    pub(crate) fn struct_layout(&self, name: &str) -> Result<StructLayout> {
+        let ParsedShader { module, .. } = self.parsed()?;
+        let (_, ty) = module
+            .types
+            .iter()
+            .find(|(_, ty)| ty.name.as_deref() == Some(name) && matches!(ty.inner, naga::TypeInner::Struct { .. }))
+            .ok_or_else(|| Error::from_reason(format!("Struct '{}' not found in shader module", name)))?;
+        struct_layout(module, name, &ty.inner)
+    }
+
+    /// `@workgroup_size` of the compute entry point `entry_point`
+    pub(crate) fn workgroup_size(&self, entry_point: &str) -> Result<[u32; 3]> {
+        let ParsedShader { module, .. } = self.parsed()?;
+        module
+            .entry_points
+            .iter()
+            .find(|ep| ep.name == entry_point && ep.stage == naga::ShaderStage::Compute)
+            .map(|ep| ep.workgroup_size)
+            .ok_or_else(|| Error::from_reason(format!("Compute entry point '{}' not found in shader module", entry_point)))
+    }
+}
+
+/// Host-shareable layout of a WGSL struct, as stored in uniform and storage buffers
+pub(crate) struct StructLayout {
+    pub(crate) name: String,
+    pub(crate) size: u32,
+    pub(crate) members: Vec<MemberLayout>,
+}
+
+pub(crate) struct MemberLayout {
+    pub(crate) name: String,
+    pub(crate) offset: u32,
+    pub(crate) ty: ValueLayout,
+}
+
+pub(crate) enum ValueLayout {
+    Scalar(ScalarLayout),
+    Vector {
+        size: u32,
+        scalar: ScalarLayout,
+    },
+    /// Column-major; each column is padded to `column_stride` bytes
+    Matrix {
+        columns: u32,
+        rows: u32,
+        column_stride: u32,
+        scalar: ScalarLayout,
+    },
+    Array {
+        base: Box<ValueLayout>,
+        count: u32,
+        stride: u32,
+    },
+    Struct(StructLayout),
+}
+
+/// 4-byte scalar types that can be written from JavaScript numbers
+#[derive(Clone, Copy)]
+pub(crate) enum ScalarLayout {
+    F32,
+    I32,
+    U32,
+}
+
+fn struct_layout(module: &naga::Module, name: &str, inner: &naga::TypeInner) -> Result<StructLayout> {
+    let naga::TypeInner::Struct { members, span } = inner else {
+        unreachable!("struct_layout called on a non-struct type");
+    };
+
+    let members = members
+        .iter()
+        .map(|member| {
+            let member_name = member.name.clone().unwrap_or_default();
+            let ty = value_layout(module, &format!("{}.{}", name, member_name), member.ty)?;
+            Ok(MemberLayout {
+                name: member_name,
+                offset: member.offset,
+                ty,
+            })
+        })
+        .collect::<Result<_>>()?;
+
+    Ok(StructLayout {
+        name: name.to_string(),
+        size: *span,
+        members,
+    })
+}
+
+fn value_layout(module: &naga::Module, path: &str, ty: naga::Handle<naga::Type>) -> Result<ValueLayout> {
+    let layout = match module.types[ty].inner {
+        naga::TypeInner::Scalar(scalar) | naga::TypeInner::Atomic(scalar) => {
+            ValueLayout::Scalar(scalar_layout(path, scalar)?)
+        }
+        naga::TypeInner::Vector { size, scalar } => ValueLayout::Vector {
+            size: size as u32,
+            scalar: scalar_layout(path, scalar)?,
+        },
+        naga::TypeInner::Matrix { columns, rows, scalar } => ValueLayout::Matrix {
+            columns: columns as u32,
+            rows: rows as u32,
+            // Columns are aligned like vecR: vec3 columns take the space of a vec4
+            column_stride: if rows == naga::VectorSize::Bi { 2 } else { 4 } * scalar.width as u32,
+            scalar: scalar_layout(path, scalar)?,
+        },
+        naga::TypeInner::Array {
+            base,
+            size: naga::ArraySize::Constant(count),
+            stride,
+        } => ValueLayout::Array {
+            base: Box::new(value_layout(module, path, base)?),
+            count: count.get(),
+            stride,
+        },
+        ref inner @ naga::TypeInner::Struct { .. } => {
+            let name = module.types[ty].name.as_deref().unwrap_or(path);
+            ValueLayout::Struct(struct_layout(module, name, inner)?)
+        }
+        _ => {
+            return Err(Error::from_reason(format!(
+                "Field '{}' has a type that can't be written from JavaScript",
+                path
+            )))
+        }
+    };
+
+    Ok(layout)
+}
+
+fn scalar_layout(path: &str, scalar: naga::Scalar) -> Result<ScalarLayout> {
+    match (scalar.kind, scalar.width) {
+        (naga::ScalarKind::Float, 4) => Ok(ScalarLayout::F32),
+        (naga::ScalarKind::Sint, 4) => Ok(ScalarLayout::I32),
+        (naga::ScalarKind::Uint, 4) => Ok(ScalarLayout::U32),
+        _ => Err(Error::from_reason(format!(
+            "Field '{}' has a scalar type that can't be written from JavaScript (only f32, i32 and u32 are supported)",
+            path
+        ))),
+    }
+}
+
+fn stage_flags(stage: naga::ShaderStage) -> wgpu::ShaderStages {
+    match stage {
+        naga::ShaderStage::Vertex => wgpu::ShaderStages::VERTEX,
+        naga::ShaderStage::Fragment => wgpu::ShaderStages::FRAGMENT,
+        naga::ShaderStage::Compute => wgpu::ShaderStages::COMPUTE,
+    }
+}
+
+fn binding_type(
+    module: &naga::Module,
+    var: &naga::GlobalVariable,
+) -> Result<(wgpu::BindingType, Option<std::num::NonZeroU32>)> {
+    let name = var.name.as_deref().unwrap_or("<unnamed>");
+    let (inner, count) = match module.types[var.ty].inner {
+        naga::TypeInner::BindingArray { base, size } => {
+            let count = match size {
+                naga::ArraySize::Constant(count) => Some(count),
+                naga::ArraySize::Dynamic => {
+                    return Err(Error::from_reason(format!(
+                        "Binding '{}' is a runtime-sized binding array, which can't be reflected",
+                        name
+                    )))
+                }
+            };
+            (&module.types[base].inner, count)
+        }
+        ref inner => (inner, None),
+    };
+
+    let ty = match (var.space, inner) {
+        (naga::AddressSpace::Uniform, _) => wgpu::BindingType::Buffer {
+            ty: wgpu::BufferBindingType::Uniform,
+            has_dynamic_offset: false,
+            min_binding_size: NonZeroU64::new(inner.size(module.to_ctx()) as u64),
+        },
+        (naga::AddressSpace::Storage { access }, _) => wgpu::BindingType::Buffer {
+            ty: wgpu::BufferBindingType::Storage {
+                read_only: !access.contains(naga::StorageAccess::STORE),
+            },
+            has_dynamic_offset: false,
+            min_binding_size: NonZeroU64::new(inner.size(module.to_ctx()) as u64),
+        },
+        (naga::AddressSpace::Handle, naga::TypeInner::Sampler { comparison }) => {
+            wgpu::BindingType::Sampler(if *comparison {
+                wgpu::SamplerBindingType::Comparison
+            } else {
+                wgpu::SamplerBindingType::Filtering
+            })
+        }
+        (naga::AddressSpace::Handle, naga::TypeInner::Image { dim, arrayed, class }) => {
+            let view_dimension = view_dimension(*dim, *arrayed);
+            match *class {
+                naga::ImageClass::Sampled { kind, multi } => wgpu::BindingType::Texture {
+                    sample_type: match kind {
+                        naga::ScalarKind::Sint => wgpu::TextureSampleType::Sint,
+                        naga::ScalarKind::Uint => wgpu::TextureSampleType::Uint,
+                        // Multisampled float textures can't be filtered
+                        _ => wgpu::TextureSampleType::Float { filterable: !multi },
+                    },
+                    view_dimension,
+                    multisampled: multi,
+                },
+                naga::ImageClass::Depth { multi } => wgpu::BindingType::Texture {
+                    sample_type: wgpu::TextureSampleType::Depth,
+                    view_dimension,
+                    multisampled: multi,
+                },
+                naga::ImageClass::Storage { format, access } => wgpu::BindingType::StorageTexture {
+                    access: match (
+                        access.contains(naga::StorageAccess::LOAD),
+                        access.contains(naga::StorageAccess::STORE),
+                    ) {
+                        (true, true) => wgpu::StorageTextureAccess::ReadWrite,
+                        (true, false) => wgpu::StorageTextureAccess::ReadOnly,
+                        _ => wgpu::StorageTextureAccess::WriteOnly,
+                    },
+                    format: storage_format(format),
+                    view_dimension,
+                },
+            }
+        }
+        _ => {
+            return Err(Error::from_reason(format!(
+                "Binding '{}' has a type that can't be reflected into a bind group layout",
+                name
+            )))
+        }
+    };
+
+    Ok((ty, count))
+}
+
+fn view_dimension(dim: naga::ImageDimension, arrayed: bool) -> wgpu::TextureViewDimension {
+    match (dim, arrayed) {
+        (naga::ImageDimension::D1, _) => wgpu::TextureViewDimension::D1,
+        (naga::ImageDimension::D2, false) => wgpu::TextureViewDimension::D2,
+        (naga::ImageDimension::D2, true) => wgpu::TextureViewDimension::D2Array,
+        (naga::ImageDimension::D3, _) => wgpu::TextureViewDimension::D3,
+        (naga::ImageDimension::Cube, false) => wgpu::TextureViewDimension::Cube,
+        (naga::ImageDimension::Cube, true) => wgpu::TextureViewDimension::CubeArray,
+    }
+}
+
+fn storage_format(format: naga::StorageFormat) -> wgpu::TextureFormat {
+    use naga::StorageFormat as Sf;
+    use wgpu::TextureFormat as Tf;
+    match format {
+        Sf::R8Unorm => Tf::R8Unorm,
+        Sf::R8Snorm => Tf::R8Snorm,
+        Sf::R8Uint => Tf::R8Uint,
+        Sf::R8Sint => Tf::R8Sint,
+        Sf::R16Uint => Tf::R16Uint,
+        Sf::R16Sint => Tf::R16Sint,
+        Sf::R16Float => Tf::R16Float,
+        Sf::Rg8Unorm => Tf::Rg8Unorm,
+        Sf::Rg8Snorm => Tf::Rg8Snorm,
+        Sf::Rg8Uint => Tf::Rg8Uint,
+        Sf::Rg8Sint => Tf::Rg8Sint,
+        Sf::R32Uint => Tf::R32Uint,
+        Sf::R32Sint => Tf::R32Sint,
+        Sf::R32Float => Tf::R32Float,
+        Sf::Rg16Uint => Tf::Rg16Uint,
+        Sf::Rg16Sint => Tf::Rg16Sint,
+        Sf::Rg16Float => Tf::Rg16Float,
+        Sf::Rgba8Unorm => Tf::Rgba8Unorm,
+        Sf::Rgba8Snorm => Tf::Rgba8Snorm,
+        Sf::Rgba8Uint => Tf::Rgba8Uint,
+        Sf::Rgba8Sint => Tf::Rgba8Sint,
+        Sf::Bgra8Unorm => Tf::Bgra8Unorm,
+        Sf::Rgb10a2Uint => Tf::Rgb10a2Uint,
+        Sf::Rgb10a2Unorm => Tf::Rgb10a2Unorm,
+        Sf::Rg11b10Float => Tf::Rg11b10Float,
+        Sf::Rg32Uint => Tf::Rg32Uint,
+        Sf::Rg32Sint => Tf::Rg32Sint,
+        Sf::Rg32Float => Tf::Rg32Float,
+        Sf::Rgba16Uint => Tf::Rgba16Uint,
+        Sf::Rgba16Sint => Tf::Rgba16Sint,
+        Sf::Rgba16Float => Tf::Rgba16Float,
+        Sf::Rgba32Uint => Tf::Rgba32Uint,
+        Sf::Rgba32Sint => Tf::Rgba32Sint,
+        Sf::Rgba32Float => Tf::Rgba32Float,
+        Sf::R16Unorm => Tf::R16Unorm,
+        Sf::R16Snorm => Tf::R16Snorm,
+        Sf::Rg16Unorm => Tf::Rg16Unorm,
+        Sf::Rg16Snorm => Tf::Rg16Snorm,
+        Sf::Rgba16Unorm => Tf::Rgba16Unorm,
+        Sf::Rgba16Snorm => Tf::Rgba16Snorm,
+    }
+}
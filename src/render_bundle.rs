@@ -1,4 +1,35 @@
+use crate::render_pass::RenderCommand;
+use napi::bindgen_prelude::*;
 use napi_derive::napi;
+use std::sync::Arc;
+
+/// Render bundle encoder descriptor following WebGPU spec
+///
+/// Must match the attachments of the render passes the bundle is executed in.
+#[napi(object)]
+pub struct RenderBundleEncoderDescriptor {
+    pub label: Option<String>,
+    /// Color attachment formats; `null` skips a color location
+    #[napi(js_name = "colorFormats")]
+    pub color_formats: Vec<Option<Either<String, u32>>>,
+    #[napi(js_name = "depthStencilFormat")]
+    pub depth_stencil_format: Option<Either<String, u32>>,
+    /// The bundle doesn't write depth, so it can run in passes with a read-only depth aspect
+    #[napi(js_name = "depthReadOnly")]
+    pub depth_read_only: Option<bool>,
+    /// The bundle doesn't write stencil, so it can run in passes with a read-only stencil aspect
+    #[napi(js_name = "stencilReadOnly")]
+    pub stencil_read_only: Option<bool>,
+    /// Sample count of the pass attachments (default 1)
+    #[napi(js_name = "sampleCount")]
+    pub sample_count: Option<u32>,
+}
+
+/// Render bundle descriptor following WebGPU spec
+#[napi(object)]
+pub struct RenderBundleDescriptor {
+    pub label: Option<String>,
+}
 
 /// Render bundle - pre-recorded render commands that can be reused
 ///
@@ -6,20 +37,221 @@ use napi_derive::napi;
 /// This reduces CPU overhead by avoiding re-recording commands every frame.
 #[napi]
 pub struct GpuRenderBundle {
-    pub(crate) bundle: wgpu::RenderBundle,
+    pub(crate) bundle: Arc<wgpu::RenderBundle>,
 }
 
 impl GpuRenderBundle {
     pub(crate) fn new(bundle: wgpu::RenderBundle) -> Self {
-        Self { bundle }
+        Self { bundle: Arc::new(bundle) }
     }
 }
 
 #[napi]
 impl GpuRenderBundle {
     /// Destroy the render bundle (automatic when dropped)
-    #[napi]
+    #[napi(catch_unwind)]
     pub fn destroy(&self) {
         // wgpu render bundles are automatically cleaned up when dropped
     }
 }
+
+/// Render bundle encoder following WebGPU spec
+///
+/// Commands are recorded here and encoded into a wgpu bundle on `finish()`,
+/// the same way render passes are replayed on `end()`.
+#[napi]
+pub struct GpuRenderBundleEncoder {
+    pub(crate) device: Arc<wgpu::Device>,
+    pub(crate) label: Option<String>,
+    pub(crate) color_formats: Vec<Option<wgpu::TextureFormat>>,
+    pub(crate) depth_stencil: Option<wgpu::RenderBundleDepthStencil>,
+    pub(crate) sample_count: u32,
+    /// `None` once finished
+    pub(crate) commands: Option<Vec<RenderCommand>>,
+    pub(crate) errors: crate::error::ErrorSink,
+}
+
+impl GpuRenderBundleEncoder {
+    pub(crate) fn new(
+        device: Arc<wgpu::Device>,
+        descriptor: RenderBundleEncoderDescriptor,
+        errors: crate::error::ErrorSink,
+    ) -> Result<Self> {
+        let depth_read_only = descriptor.depth_read_only.unwrap_or(false);
+        let stencil_read_only = descriptor.stencil_read_only.unwrap_or(false);
+        let depth_stencil = match descriptor.depth_stencil_format.as_ref() {
+            Some(format) => Some(wgpu::RenderBundleDepthStencil {
+                format: crate::parse::parse_texture_format(format),
+                depth_read_only,
+                stencil_read_only,
+            }),
+            None if depth_read_only || stencil_read_only => {
+                return Err(Error::from_reason(
+                    "depthReadOnly and stencilReadOnly require a depthStencilFormat",
+                ))
+            }
+            None => None,
+        };
+
+        Ok(Self {
+            device,
+            label: descriptor.label,
+            color_formats: descriptor
+                .color_formats
+                .iter()
+                .map(|format| format.as_ref().map(crate::parse::parse_texture_format))
+                .collect(),
+            depth_stencil,
+            sample_count: descriptor.sample_count.unwrap_or(1),
+            commands: Some(Vec::new()),
+            errors,
+        })
+    }
+
+    fn record(&mut self, command: RenderCommand) -> Result<()> {
+        match self.commands.as_mut() {
+            Some(commands) => {
+                commands.push(command);
+                Ok(())
+            }
+            None => Err(Error::from_reason("Render bundle encoder already finished")),
+        }
+    }
+
+    pub(crate) fn record_all(&mut self, commands: Vec<RenderCommand>) -> Result<()> {
+        match self.commands.as_mut() {
+            Some(recorded) => {
+                recorded.extend(commands);
+                Ok(())
+            }
+            None => Err(Error::from_reason("Render bundle encoder already finished")),
+        }
+    }
+}
+
+#[napi]
+impl GpuRenderBundleEncoder {
+    /// Set the pipeline for subsequent draws (WebGPU standard method)
+    #[napi(catch_unwind, js_name = "setPipeline")]
+    pub fn set_pipeline(&mut self, pipeline: &crate::GpuRenderPipeline) -> Result<()> {
+        self.record(RenderCommand::SetPipeline(pipeline.pipeline.clone()))
+    }
+
+    /// Set a bind group (WebGPU standard method)
+    #[napi(catch_unwind, js_name = "setBindGroup")]
+    pub fn set_bind_group(
+        &mut self,
+        index: u32,
+        bind_group: &crate::GpuBindGroup,
+        dynamic_offsets: Option<Vec<u32>>,
+    ) -> Result<()> {
+        self.record(RenderCommand::SetBindGroup(
+            index,
+            bind_group.bind_group.clone(),
+            dynamic_offsets.unwrap_or_default(),
+        ))
+    }
+
+    /// Set a vertex buffer (WebGPU standard method)
+    #[napi(catch_unwind, js_name = "setVertexBuffer")]
+    pub fn set_vertex_buffer(
+        &mut self,
+        slot: u32,
+        buffer: &crate::GpuBuffer,
+        offset: Option<f64>,
+        size: Option<f64>,
+    ) -> Result<()> {
+        let (offset, size) = buffer.binding_range("setVertexBuffer", offset, size)?;
+        self.record(RenderCommand::SetVertexBuffer(slot, buffer.buffer.clone(), offset, size))
+    }
+
+    /// Set the index buffer (WebGPU standard method)
+    #[napi(catch_unwind, js_name = "setIndexBuffer")]
+    pub fn set_index_buffer(
+        &mut self,
+        buffer: &crate::GpuBuffer,
+        index_format: Either<String, u32>,
+        offset: Option<f64>,
+        size: Option<f64>,
+    ) -> Result<()> {
+        let format = crate::parse::parse_index_format(&index_format).ok_or_else(|| {
+            Error::from_reason(format!("Invalid index format: {}", crate::parse::describe_enum(&index_format)))
+        })?;
+        let (offset, size) = buffer.binding_range("setIndexBuffer", offset, size)?;
+        self.record(RenderCommand::SetIndexBuffer(buffer.buffer.clone(), format, offset, size))
+    }
+
+    /// Draw primitives (WebGPU standard method)
+    #[napi(catch_unwind)]
+    pub fn draw(
+        &mut self,
+        vertex_count: u32,
+        instance_count: Option<u32>,
+        first_vertex: Option<u32>,
+        first_instance: Option<u32>,
+    ) -> Result<()> {
+        self.record(RenderCommand::Draw {
+            vertices: first_vertex.unwrap_or(0)..first_vertex.unwrap_or(0) + vertex_count,
+            instances: first_instance.unwrap_or(0)..first_instance.unwrap_or(0) + instance_count.unwrap_or(1),
+        })
+    }
+
+    /// Draw indexed primitives (WebGPU standard method)
+    #[napi(catch_unwind, js_name = "drawIndexed")]
+    pub fn draw_indexed(
+        &mut self,
+        index_count: u32,
+        instance_count: Option<u32>,
+        first_index: Option<u32>,
+        base_vertex: Option<i32>,
+        first_instance: Option<u32>,
+    ) -> Result<()> {
+        self.record(RenderCommand::DrawIndexed {
+            indices: first_index.unwrap_or(0)..first_index.unwrap_or(0) + index_count,
+            base_vertex: base_vertex.unwrap_or(0),
+            instances: first_instance.unwrap_or(0)..first_instance.unwrap_or(0) + instance_count.unwrap_or(1),
+        })
+    }
+
+    /// Draw primitives using parameters from a buffer (WebGPU standard method)
+    #[napi(catch_unwind, js_name = "drawIndirect")]
+    pub fn draw_indirect(&mut self, indirect_buffer: &crate::GpuBuffer, indirect_offset: f64) -> Result<()> {
+        self.record(RenderCommand::DrawIndirect(indirect_buffer.buffer.clone(), indirect_offset as u64))
+    }
+
+    /// Draw indexed primitives using parameters from a buffer (WebGPU standard method)
+    #[napi(catch_unwind, js_name = "drawIndexedIndirect")]
+    pub fn draw_indexed_indirect(&mut self, indirect_buffer: &crate::GpuBuffer, indirect_offset: f64) -> Result<()> {
+        self.record(RenderCommand::DrawIndexedIndirect(indirect_buffer.buffer.clone(), indirect_offset as u64))
+    }
+
+    /// Finish recording and create the render bundle (WebGPU standard method)
+    /// After calling this, the encoder can no longer be used
+    #[napi(catch_unwind)]
+    pub fn finish(&mut self, descriptor: Option<RenderBundleDescriptor>) -> Result<GpuRenderBundle> {
+        let Some(commands) = self.commands.take() else {
+            return Err(Error::from_reason("Render bundle encoder already finished"));
+        };
+        let label = descriptor.and_then(|d| d.label).or_else(|| self.label.clone());
+
+        // Recorded commands are validated when the bundle is finished
+        let bundle = self.errors.check(
+            "finish",
+            || format!("label: {}", crate::error::describe_label(label.as_deref())),
+            || {
+                let mut encoder = self.device.create_render_bundle_encoder(&wgpu::RenderBundleEncoderDescriptor {
+                    label: self.label.as_deref(),
+                    color_formats: &self.color_formats,
+                    depth_stencil: self.depth_stencil,
+                    sample_count: self.sample_count,
+                    multiview: None,
+                });
+                for command in &commands {
+                    crate::render_pass::encode_draw_command(&mut encoder, command);
+                }
+                encoder.finish(&wgpu::RenderBundleDescriptor { label: label.as_deref() })
+            },
+        )?;
+        Ok(GpuRenderBundle::new(bundle))
+    }
+}
@@ -0,0 +1,178 @@
+//! Natively paced render loops
+//!
+//! Timers in JavaScript fire late and drift, and a busy event loop adds jitter
+//! to every frame. `surface.runRenderLoop()` instead paces frames, polls the
+//! device and acquires surface textures on a native thread, then calls into
+//! JavaScript once per frame through a threadsafe function. The next frame is
+//! only acquired after the previous one was presented or dropped.
+
+use crate::surface::SurfaceState;
+use crate::{GpuSurface, GpuSurfaceTexture};
+use napi::bindgen_prelude::*;
+use napi::threadsafe_function::{ErrorStrategy, ThreadSafeCallContext, ThreadsafeFunction, ThreadsafeFunctionCallMode};
+use napi::{JsFunction, JsUnknown, NapiValue};
+use napi_derive::napi;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+use std::time::{Duration, Instant};
+
+/// How often a loop waiting on JavaScript checks whether it was stopped
+const STOP_CHECK_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Render loop options
+#[napi(object)]
+pub struct RenderLoopOptions {
+    /// Target frames per second. By default frames are paced by the present
+    /// mode alone, e.g. to the display refresh rate with "fifo"
+    pub fps: Option<f64>,
+}
+
+/// Timing of a render loop frame
+#[napi(object)]
+pub struct RenderLoopFrame {
+    /// Frame number, counting from 0
+    pub frame: u32,
+    /// Milliseconds since the loop started
+    pub time: f64,
+    /// Milliseconds since the previous frame
+    pub delta: f64,
+}
+
+struct Frame {
+    texture: wgpu::SurfaceTexture,
+    done: mpsc::Sender<()>,
+    timing: RenderLoopFrame,
+}
+
+type FrameCallback = ThreadsafeFunction<Frame, ErrorStrategy::Fatal>;
+
+/// Handle of a running render loop
+#[napi]
+pub struct GpuRenderLoop {
+    running: Arc<AtomicBool>,
+}
+
+#[napi]
+impl GpuRenderLoop {
+    /// Stop the loop; a frame already passed to the callback still completes
+    #[napi(catch_unwind)]
+    pub fn stop(&self) {
+        self.running.store(false, Ordering::Relaxed);
+    }
+
+    /// False once stopped, or after the surface failed to provide a frame
+    #[napi(catch_unwind, getter)]
+    pub fn running(&self) -> bool {
+        self.running.load(Ordering::Relaxed)
+    }
+}
+
+#[napi]
+impl GpuSurface {
+    /// Call `callback` with a texture for every frame until `stop()` (extension)
+    ///
+    /// Present the texture at the end of the callback; the next frame is
+    /// acquired once it has been presented or dropped. The loop keeps the
+    /// process alive while it runs.
+    #[napi(
+        catch_unwind,
+        js_name = "runRenderLoop",
+        ts_args_type = "callback: (texture: GpuSurfaceTexture, frame: RenderLoopFrame) => void, options?: RenderLoopOptions | undefined | null"
+    )]
+    pub fn run_render_loop(&self, callback: JsFunction, options: Option<RenderLoopOptions>) -> Result<GpuRenderLoop> {
+        let interval = match options.and_then(|options| options.fps) {
+            Some(fps) if fps.is_finite() && fps > 0.0 => Some(Duration::from_secs_f64(1.0 / fps)),
+            Some(fps) => return Err(Error::from_reason(format!("fps must be a positive number, got {}", fps))),
+            None => None,
+        };
+        self.state.device()?;
+
+        let callback: FrameCallback = callback.create_threadsafe_function(0, |ctx: ThreadSafeCallContext<Frame>| {
+            let Frame { texture, done, timing } = ctx.value;
+            let texture = GpuSurfaceTexture::new(texture, Some(done))
+                .into_instance(ctx.env)?
+                .as_object(ctx.env)
+                .into_unknown();
+            // SAFETY: the value was just created in this env, on its thread
+            let timing = unsafe {
+                JsUnknown::from_raw_unchecked(ctx.env.raw(), RenderLoopFrame::to_napi_value(ctx.env.raw(), timing)?)
+            };
+            Ok(vec![texture, timing])
+        })?;
+
+        let running = Arc::new(AtomicBool::new(true));
+        let state = self.state.clone();
+        let thread_running = running.clone();
+        std::thread::Builder::new()
+            .name("webgpu-render-loop".to_string())
+            .spawn(move || {
+                // Catch panics too, so `running` still turns false
+                let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                    run(&state, &callback, &thread_running, interval)
+                }));
+                match result {
+                    Ok(Ok(())) => {}
+                    Ok(Err(e)) => log::error!(target: "webgpu", "Render loop stopped: {}", e),
+                    Err(panic) => log::error!(
+                        target: "webgpu",
+                        "Render loop stopped: {}",
+                        crate::error::panic_message(panic.as_ref())
+                    ),
+                }
+                thread_running.store(false, Ordering::Relaxed);
+            })
+            .map_err(|e| Error::from_reason(format!("Failed to start render loop: {}", e)))?;
+
+        Ok(GpuRenderLoop { running })
+    }
+}
+
+fn run(state: &SurfaceState, callback: &FrameCallback, running: &AtomicBool, interval: Option<Duration>) -> Result<()> {
+    let start = Instant::now();
+    let mut deadline = start;
+    let mut previous = start;
+    let mut frame = 0;
+
+    while running.load(Ordering::Relaxed) {
+        if let Some(interval) = interval {
+            let now = Instant::now();
+            if deadline > now {
+                std::thread::sleep(deadline - now);
+            } else if now - deadline > interval {
+                // Fell more than a frame behind; don't try to catch up
+                deadline = now;
+            }
+            deadline += interval;
+        }
+
+        // Runs mapping and submitted-work-done callbacks of the last frame
+        state.device()?.poll(wgpu::Maintain::Poll);
+        let Some(texture) = state.acquire()? else {
+            continue;
+        };
+        if !running.load(Ordering::Relaxed) {
+            break;
+        }
+
+        let now = Instant::now();
+        let (done, frame_done) = mpsc::channel();
+        let timing = RenderLoopFrame {
+            frame,
+            time: (now - start).as_secs_f64() * 1000.0,
+            delta: (now - previous).as_secs_f64() * 1000.0,
+        };
+        if callback.call(Frame { texture, done, timing }, ThreadsafeFunctionCallMode::NonBlocking) != Status::Ok {
+            break;
+        }
+        previous = now;
+        frame += 1;
+
+        // The sender is dropped once the texture is presented or collected
+        while let Err(mpsc::RecvTimeoutError::Timeout) = frame_done.recv_timeout(STOP_CHECK_INTERVAL) {
+            if !running.load(Ordering::Relaxed) {
+                break;
+            }
+        }
+    }
+    Ok(())
+}
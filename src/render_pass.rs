@@ -1,52 +1,191 @@
 use napi::bindgen_prelude::*;
 use napi_derive::napi;
+use std::ops::Range;
+use std::sync::{Arc, Mutex};
+
+/// Color attachment of a render pass, kept until the pass is replayed
+pub(crate) struct ColorTarget {
+    pub(crate) view: Arc<wgpu::TextureView>,
+    pub(crate) resolve_target: Option<Arc<wgpu::TextureView>>,
+    pub(crate) ops: wgpu::Operations<wgpu::Color>,
+    /// View format, size and sample count, for utilities drawing into the pass
+    pub(crate) format: wgpu::TextureFormat,
+    pub(crate) size: (u32, u32),
+    pub(crate) sample_count: u32,
+}
+
+/// Depth/stencil attachment of a render pass, kept until the pass is replayed
+pub(crate) struct DepthStencilTarget {
+    pub(crate) view: Arc<wgpu::TextureView>,
+    pub(crate) format: wgpu::TextureFormat,
+    pub(crate) depth_ops: Option<wgpu::Operations<f32>>,
+    pub(crate) stencil_ops: Option<wgpu::Operations<u32>>,
+}
+
+/// A render pass command, holding the resources it uses until replay
+pub(crate) enum RenderCommand {
+    SetPipeline(Arc<wgpu::RenderPipeline>),
+    SetBindGroup(u32, Arc<wgpu::BindGroup>, Vec<u32>),
+    /// Slot, buffer, offset and size (`None` for the rest of the buffer)
+    SetVertexBuffer(u32, Arc<wgpu::Buffer>, u64, Option<u64>),
+    SetIndexBuffer(Arc<wgpu::Buffer>, wgpu::IndexFormat, u64, Option<u64>),
+    Draw {
+        vertices: Range<u32>,
+        instances: Range<u32>,
+    },
+    DrawIndexed {
+        indices: Range<u32>,
+        base_vertex: i32,
+        instances: Range<u32>,
+    },
+    DrawIndirect(Arc<wgpu::Buffer>, u64),
+    DrawIndexedIndirect(Arc<wgpu::Buffer>, u64),
+    ExecuteBundles(Vec<Arc<wgpu::RenderBundle>>),
+    SetViewport([f32; 6]),
+    SetScissorRect(u32, u32, u32, u32),
+    SetBlendConstant(wgpu::Color),
+    SetStencilReference(u32),
+    PushDebugGroup(String),
+    PopDebugGroup,
+    InsertDebugMarker(String),
+}
+
+/// Offset and size to record for a vertex or index buffer binding of `size`
+/// bytes at `offset` (`None` for the rest of the buffer)
+///
+/// wgpu doesn't bounds-check these bindings and panics on offsets past the
+/// end, nor does it take empty slices, so the range is checked when recorded
+/// and an empty one is bound as the rest of the buffer from its end.
+pub(crate) fn buffer_range(
+    buffer: &wgpu::Buffer,
+    offset: u64,
+    size: Option<u64>,
+) -> std::result::Result<(u64, Option<u64>), String> {
+    let buffer_size = buffer.size();
+    match size {
+        Some(size) if offset.checked_add(size).map_or(true, |end| end > buffer_size) => Err(format!(
+            "range (offset {} + size {}) exceeds buffer size ({})",
+            offset, size, buffer_size
+        )),
+        None if offset > buffer_size => Err(format!(
+            "offset ({}) exceeds buffer size ({})",
+            offset, buffer_size
+        )),
+        Some(0) => Ok((buffer_size, None)),
+        _ => Ok((offset, size)),
+    }
+}
+
+/// Slice of a range checked by `buffer_range`
+fn buffer_slice(buffer: &wgpu::Buffer, offset: u64, size: Option<u64>) -> wgpu::BufferSlice<'_> {
+    match size {
+        Some(size) => buffer.slice(offset..offset + size),
+        None => buffer.slice(offset..),
+    }
+}
+
+/// Encode a command render passes and render bundles have in common
+///
+/// Returns `false` for commands only render passes support.
+pub(crate) fn encode_draw_command<'a>(
+    encoder: &mut impl wgpu::util::RenderEncoder<'a>,
+    command: &'a RenderCommand,
+) -> bool {
+    match command {
+        RenderCommand::SetPipeline(pipeline) => encoder.set_pipeline(pipeline),
+        RenderCommand::SetBindGroup(index, bind_group, offsets) => encoder.set_bind_group(*index, bind_group, offsets),
+        RenderCommand::SetVertexBuffer(slot, buffer, offset, size) => {
+            encoder.set_vertex_buffer(*slot, buffer_slice(buffer, *offset, *size))
+        }
+        RenderCommand::SetIndexBuffer(buffer, format, offset, size) => {
+            encoder.set_index_buffer(buffer_slice(buffer, *offset, *size), *format)
+        }
+        RenderCommand::Draw { vertices, instances } => encoder.draw(vertices.clone(), instances.clone()),
+        RenderCommand::DrawIndexed { indices, base_vertex, instances } => {
+            encoder.draw_indexed(indices.clone(), *base_vertex, instances.clone())
+        }
+        RenderCommand::DrawIndirect(buffer, offset) => encoder.draw_indirect(buffer, *offset),
+        RenderCommand::DrawIndexedIndirect(buffer, offset) => encoder.draw_indexed_indirect(buffer, *offset),
+        _ => return false,
+    }
+    true
+}
 
 /// Render pass encoder following WebGPU spec
 /// Records commands for rendering operations
+///
+/// Commands are recorded here and encoded into the parent command encoder on
+/// `end()`, so the pass never outlives or aliases the encoder.
 #[napi]
 pub struct GpuRenderPassEncoder {
-    // Store as erased pointer to avoid lifetime issues
-    pub(crate) pass: Option<*mut ()>,
+    pub(crate) encoder: Arc<Mutex<crate::device::EncoderState>>,
+    pub(crate) label: Option<String>,
+    pub(crate) color_attachments: Vec<Option<ColorTarget>>,
+    pub(crate) depth_stencil_attachment: Option<DepthStencilTarget>,
+    /// `None` once ended
+    pub(crate) commands: Option<Vec<RenderCommand>>,
+    /// Layouts of the pipeline and bind groups set, to explain a failed `end()`
+    pub(crate) bindings: crate::layout_check::BindingTracker,
+    pub(crate) errors: crate::error::ErrorSink,
+}
+
+impl GpuRenderPassEncoder {
+    fn record(&mut self, command: RenderCommand) -> Result<()> {
+        match self.commands.as_mut() {
+            Some(commands) => {
+                commands.push(command);
+                Ok(())
+            }
+            None => Err(Error::from_reason("Render pass already ended")),
+        }
+    }
+
+    fn record_draw(&mut self, command: RenderCommand) -> Result<()> {
+        self.record(command)?;
+        self.bindings.check("draw");
+        Ok(())
+    }
+
+    pub(crate) fn record_all(&mut self, commands: Vec<RenderCommand>) -> Result<()> {
+        match self.commands.as_mut() {
+            Some(recorded) => {
+                recorded.extend(commands);
+                Ok(())
+            }
+            None => Err(Error::from_reason("Render pass already ended")),
+        }
+    }
 }
 
 #[napi]
 impl GpuRenderPassEncoder {
     /// Set the pipeline for this render pass (WebGPU standard method)
-    #[napi(js_name = "setPipeline")]
+    #[napi(catch_unwind, js_name = "setPipeline")]
     pub fn set_pipeline(&mut self, pipeline: &crate::GpuRenderPipeline) -> Result<()> {
-        if let Some(pass_ptr) = self.pass {
-            unsafe {
-                let pass = &mut *(pass_ptr as *mut wgpu::RenderPass<'_>);
-                pass.set_pipeline(&pipeline.pipeline);
-            }
-            Ok(())
-        } else {
-            Err(Error::from_reason("Render pass already ended"))
-        }
+        self.record(RenderCommand::SetPipeline(pipeline.pipeline.clone()))?;
+        self.bindings.set_pipeline(pipeline.bind_group_layouts.clone());
+        Ok(())
     }
 
     /// Set a bind group for this render pass (WebGPU standard method)
-    #[napi(js_name = "setBindGroup")]
+    #[napi(catch_unwind, js_name = "setBindGroup")]
     pub fn set_bind_group(
         &mut self,
         index: u32,
         bind_group: &crate::GpuBindGroup,
         dynamic_offsets: Option<Vec<u32>>,
     ) -> Result<()> {
-        if let Some(pass_ptr) = self.pass {
-            unsafe {
-                let pass = &mut *(pass_ptr as *mut wgpu::RenderPass<'_>);
-                let offsets = dynamic_offsets.unwrap_or_default();
-                pass.set_bind_group(index, &bind_group.bind_group, &offsets);
-            }
-            Ok(())
-        } else {
-            Err(Error::from_reason("Render pass already ended"))
-        }
+        self.record(RenderCommand::SetBindGroup(
+            index,
+            bind_group.bind_group.clone(),
+            dynamic_offsets.unwrap_or_default(),
+        ))?;
+        self.bindings.set_bind_group(index, bind_group.layout_entries.clone());
+        Ok(())
     }
 
     /// Set the vertex buffer for this render pass (WebGPU standard method)
-    #[napi(js_name = "setVertexBuffer")]
+    #[napi(catch_unwind, js_name = "setVertexBuffer")]
     pub fn set_vertex_buffer(
         &mut self,
         slot: u32,
@@ -54,58 +193,28 @@ impl GpuRenderPassEncoder {
         offset: Option<f64>,
         size: Option<f64>,
     ) -> Result<()> {
-        if let Some(pass_ptr) = self.pass {
-            unsafe {
-                let pass = &mut *(pass_ptr as *mut wgpu::RenderPass<'_>);
-                let buffer_slice = if let (Some(off), Some(sz)) = (offset, size) {
-                    buffer.buffer.slice(off as u64..(off as u64 + sz as u64))
-                } else if let Some(off) = offset {
-                    buffer.buffer.slice(off as u64..)
-                } else {
-                    buffer.buffer.slice(..)
-                };
-                pass.set_vertex_buffer(slot, buffer_slice);
-            }
-            Ok(())
-        } else {
-            Err(Error::from_reason("Render pass already ended"))
-        }
+        let (offset, size) = buffer.binding_range("setVertexBuffer", offset, size)?;
+        self.record(RenderCommand::SetVertexBuffer(slot, buffer.buffer.clone(), offset, size))
     }
 
     /// Set the index buffer for this render pass (WebGPU standard method)
-    #[napi(js_name = "setIndexBuffer")]
+    #[napi(catch_unwind, js_name = "setIndexBuffer")]
     pub fn set_index_buffer(
         &mut self,
         buffer: &crate::GpuBuffer,
-        index_format: String,
+        index_format: Either<String, u32>,
         offset: Option<f64>,
         size: Option<f64>,
     ) -> Result<()> {
-        if let Some(pass_ptr) = self.pass {
-            unsafe {
-                let pass = &mut *(pass_ptr as *mut wgpu::RenderPass<'_>);
-                let format = match index_format.as_str() {
-                    "uint16" => wgpu::IndexFormat::Uint16,
-                    "uint32" => wgpu::IndexFormat::Uint32,
-                    _ => return Err(Error::from_reason(format!("Invalid index format: {}", index_format))),
-                };
-                let buffer_slice = if let (Some(off), Some(sz)) = (offset, size) {
-                    buffer.buffer.slice(off as u64..(off as u64 + sz as u64))
-                } else if let Some(off) = offset {
-                    buffer.buffer.slice(off as u64..)
-                } else {
-                    buffer.buffer.slice(..)
-                };
-                pass.set_index_buffer(buffer_slice, format);
-            }
-            Ok(())
-        } else {
-            Err(Error::from_reason("Render pass already ended"))
-        }
+        let format = crate::parse::parse_index_format(&index_format).ok_or_else(|| {
+            Error::from_reason(format!("Invalid index format: {}", crate::parse::describe_enum(&index_format)))
+        })?;
+        let (offset, size) = buffer.binding_range("setIndexBuffer", offset, size)?;
+        self.record(RenderCommand::SetIndexBuffer(buffer.buffer.clone(), format, offset, size))
     }
 
     /// Draw primitives (WebGPU standard method)
-    #[napi]
+    #[napi(catch_unwind)]
     pub fn draw(
         &mut self,
         vertex_count: u32,
@@ -113,22 +222,14 @@ impl GpuRenderPassEncoder {
         first_vertex: Option<u32>,
         first_instance: Option<u32>,
     ) -> Result<()> {
-        if let Some(pass_ptr) = self.pass {
-            unsafe {
-                let pass = &mut *(pass_ptr as *mut wgpu::RenderPass<'_>);
-                pass.draw(
-                    first_vertex.unwrap_or(0)..first_vertex.unwrap_or(0) + vertex_count,
-                    first_instance.unwrap_or(0)..first_instance.unwrap_or(0) + instance_count.unwrap_or(1),
-                );
-            }
-            Ok(())
-        } else {
-            Err(Error::from_reason("Render pass already ended"))
-        }
+        self.record_draw(RenderCommand::Draw {
+            vertices: first_vertex.unwrap_or(0)..first_vertex.unwrap_or(0) + vertex_count,
+            instances: first_instance.unwrap_or(0)..first_instance.unwrap_or(0) + instance_count.unwrap_or(1),
+        })
     }
 
     /// Draw indexed primitives (WebGPU standard method)
-    #[napi(js_name = "drawIndexed")]
+    #[napi(catch_unwind, js_name = "drawIndexed")]
     pub fn draw_indexed(
         &mut self,
         index_count: u32,
@@ -137,74 +238,43 @@ impl GpuRenderPassEncoder {
         base_vertex: Option<i32>,
         first_instance: Option<u32>,
     ) -> Result<()> {
-        if let Some(pass_ptr) = self.pass {
-            unsafe {
-                let pass = &mut *(pass_ptr as *mut wgpu::RenderPass<'_>);
-                pass.draw_indexed(
-                    first_index.unwrap_or(0)..first_index.unwrap_or(0) + index_count,
-                    base_vertex.unwrap_or(0),
-                    first_instance.unwrap_or(0)..first_instance.unwrap_or(0) + instance_count.unwrap_or(1),
-                );
-            }
-            Ok(())
-        } else {
-            Err(Error::from_reason("Render pass already ended"))
-        }
+        self.record_draw(RenderCommand::DrawIndexed {
+            indices: first_index.unwrap_or(0)..first_index.unwrap_or(0) + index_count,
+            base_vertex: base_vertex.unwrap_or(0),
+            instances: first_instance.unwrap_or(0)..first_instance.unwrap_or(0) + instance_count.unwrap_or(1),
+        })
     }
 
     /// Draw primitives using parameters from a buffer (WebGPU standard method)
-    #[napi(js_name = "drawIndirect")]
+    #[napi(catch_unwind, js_name = "drawIndirect")]
     pub fn draw_indirect(
         &mut self,
         indirect_buffer: &crate::GpuBuffer,
         indirect_offset: f64,
     ) -> Result<()> {
-        if let Some(pass_ptr) = self.pass {
-            unsafe {
-                let pass = &mut *(pass_ptr as *mut wgpu::RenderPass<'_>);
-                pass.draw_indirect(&indirect_buffer.buffer, indirect_offset as u64);
-            }
-            Ok(())
-        } else {
-            Err(Error::from_reason("Render pass already ended"))
-        }
+        self.record_draw(RenderCommand::DrawIndirect(indirect_buffer.buffer.clone(), indirect_offset as u64))
     }
 
     /// Draw indexed primitives using parameters from a buffer (WebGPU standard method)
-    #[napi(js_name = "drawIndexedIndirect")]
+    #[napi(catch_unwind, js_name = "drawIndexedIndirect")]
     pub fn draw_indexed_indirect(
         &mut self,
         indirect_buffer: &crate::GpuBuffer,
         indirect_offset: f64,
     ) -> Result<()> {
-        if let Some(pass_ptr) = self.pass {
-            unsafe {
-                let pass = &mut *(pass_ptr as *mut wgpu::RenderPass<'_>);
-                pass.draw_indexed_indirect(&indirect_buffer.buffer, indirect_offset as u64);
-            }
-            Ok(())
-        } else {
-            Err(Error::from_reason("Render pass already ended"))
-        }
+        self.record_draw(RenderCommand::DrawIndexedIndirect(indirect_buffer.buffer.clone(), indirect_offset as u64))
     }
 
     /// Execute render bundles (WebGPU standard method)
-    #[napi(js_name = "executeBundles")]
+    #[napi(catch_unwind, js_name = "executeBundles")]
     pub fn execute_bundles(&mut self, bundles: Vec<&crate::GpuRenderBundle>) -> Result<()> {
-        if let Some(pass_ptr) = self.pass {
-            unsafe {
-                let pass = &mut *(pass_ptr as *mut wgpu::RenderPass<'_>);
-                let bundle_refs: Vec<&wgpu::RenderBundle> = bundles.iter().map(|b| &b.bundle).collect();
-                pass.execute_bundles(bundle_refs);
-            }
-            Ok(())
-        } else {
-            Err(Error::from_reason("Render pass already ended"))
-        }
+        self.record(RenderCommand::ExecuteBundles(
+            bundles.iter().map(|b| b.bundle.clone()).collect(),
+        ))
     }
 
     /// Set the viewport for this render pass (WebGPU standard method)
-    #[napi(js_name = "setViewport")]
+    #[napi(catch_unwind, js_name = "setViewport")]
     pub fn set_viewport(
         &mut self,
         x: f64,
@@ -214,130 +284,121 @@ impl GpuRenderPassEncoder {
         min_depth: f64,
         max_depth: f64,
     ) -> Result<()> {
-        if let Some(pass_ptr) = self.pass {
-            unsafe {
-                let pass = &mut *(pass_ptr as *mut wgpu::RenderPass<'_>);
-                pass.set_viewport(x as f32, y as f32, width as f32, height as f32, min_depth as f32, max_depth as f32);
-            }
-            Ok(())
-        } else {
-            Err(Error::from_reason("Render pass already ended"))
-        }
+        self.record(RenderCommand::SetViewport([
+            x as f32,
+            y as f32,
+            width as f32,
+            height as f32,
+            min_depth as f32,
+            max_depth as f32,
+        ]))
     }
 
     /// Set the scissor rectangle for this render pass (WebGPU standard method)
-    #[napi(js_name = "setScissorRect")]
+    #[napi(catch_unwind, js_name = "setScissorRect")]
     pub fn set_scissor_rect(&mut self, x: u32, y: u32, width: u32, height: u32) -> Result<()> {
-        if let Some(pass_ptr) = self.pass {
-            unsafe {
-                let pass = &mut *(pass_ptr as *mut wgpu::RenderPass<'_>);
-                pass.set_scissor_rect(x, y, width, height);
-            }
-            Ok(())
-        } else {
-            Err(Error::from_reason("Render pass already ended"))
-        }
+        self.record(RenderCommand::SetScissorRect(x, y, width, height))
     }
 
     /// Set the blend constant for this render pass (WebGPU standard method)
-    #[napi(js_name = "setBlendConstant")]
+    #[napi(catch_unwind, js_name = "setBlendConstant")]
     pub fn set_blend_constant(&mut self, color: Vec<f64>) -> Result<()> {
-        if let Some(pass_ptr) = self.pass {
-            if color.len() < 4 {
-                return Err(Error::from_reason("Blend constant must have 4 components (RGBA)"));
-            }
-            unsafe {
-                let pass = &mut *(pass_ptr as *mut wgpu::RenderPass<'_>);
-                pass.set_blend_constant(wgpu::Color {
-                    r: color[0],
-                    g: color[1],
-                    b: color[2],
-                    a: color[3],
-                });
-            }
-            Ok(())
-        } else {
-            Err(Error::from_reason("Render pass already ended"))
+        if color.len() < 4 {
+            return Err(Error::from_reason("Blend constant must have 4 components (RGBA)"));
         }
+        self.record(RenderCommand::SetBlendConstant(wgpu::Color {
+            r: color[0],
+            g: color[1],
+            b: color[2],
+            a: color[3],
+        }))
     }
 
     /// Set the stencil reference value for this render pass (WebGPU standard method)
-    #[napi(js_name = "setStencilReference")]
+    #[napi(catch_unwind, js_name = "setStencilReference")]
     pub fn set_stencil_reference(&mut self, reference: u32) -> Result<()> {
-        if let Some(pass_ptr) = self.pass {
-            unsafe {
-                let pass = &mut *(pass_ptr as *mut wgpu::RenderPass<'_>);
-                pass.set_stencil_reference(reference);
-            }
-            Ok(())
-        } else {
-            Err(Error::from_reason("Render pass already ended"))
-        }
+        self.record(RenderCommand::SetStencilReference(reference))
     }
 
     /// End the render pass (WebGPU standard method)
     /// After calling this, the pass encoder can no longer be used
-    #[napi]
-    pub fn end(&mut self) {
-        // Take the pass pointer and drop it properly
-        if let Some(pass_ptr) = self.pass.take() {
-            unsafe {
-                // Cast back to the correct type and reconstruct the Box to drop it
-                let _ = Box::from_raw(pass_ptr as *mut wgpu::RenderPass<'static>);
+    #[napi(catch_unwind)]
+    pub fn end(&mut self) -> Result<()> {
+        let Some(commands) = self.commands.take() else {
+            return Ok(());
+        };
+        let mut state = crate::device::lock_encoder(&self.encoder);
+        state.pass_open = false;
+        let Some(encoder) = state.encoder.as_mut() else {
+            return Err(Error::from_reason("Command encoder already finished"));
+        };
+
+        let color_attachments: Vec<Option<wgpu::RenderPassColorAttachment>> = self
+            .color_attachments
+            .iter()
+            .map(|target| {
+                target.as_ref().map(|target| wgpu::RenderPassColorAttachment {
+                    view: &target.view,
+                    resolve_target: target.resolve_target.as_deref(),
+                    ops: target.ops,
+                })
+            })
+            .collect();
+        let depth_stencil_attachment = self.depth_stencil_attachment.as_ref().map(|target| {
+            wgpu::RenderPassDepthStencilAttachment {
+                view: &target.view,
+                depth_ops: target.depth_ops,
+                stencil_ops: target.stencil_ops,
             }
-        }
+        });
+
+        // Recorded commands are validated when the pass is dropped
+        let bindings = &self.bindings;
+        self.errors.check_diagnosed("end", String::new, || bindings.diagnosis(), || {
+            let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: self.label.as_deref(),
+                color_attachments: &color_attachments,
+                depth_stencil_attachment,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+            for command in &commands {
+                if encode_draw_command(&mut pass, command) {
+                    continue;
+                }
+                match command {
+                    RenderCommand::ExecuteBundles(bundles) => pass.execute_bundles(bundles.iter().map(|b| &**b)),
+                    RenderCommand::SetViewport([x, y, width, height, min_depth, max_depth]) => {
+                        pass.set_viewport(*x, *y, *width, *height, *min_depth, *max_depth)
+                    }
+                    RenderCommand::SetScissorRect(x, y, width, height) => pass.set_scissor_rect(*x, *y, *width, *height),
+                    RenderCommand::SetBlendConstant(color) => pass.set_blend_constant(*color),
+                    RenderCommand::SetStencilReference(reference) => pass.set_stencil_reference(*reference),
+                    RenderCommand::PushDebugGroup(label) => pass.push_debug_group(label),
+                    RenderCommand::PopDebugGroup => pass.pop_debug_group(),
+                    RenderCommand::InsertDebugMarker(label) => pass.insert_debug_marker(label),
+                    // Draw state, encoded above
+                    _ => {}
+                }
+            }
+        })
     }
 
     /// Push a debug group (WebGPU standard method)
-    #[napi(js_name = "pushDebugGroup")]
+    #[napi(catch_unwind, js_name = "pushDebugGroup")]
     pub fn push_debug_group(&mut self, label: String) -> Result<()> {
-        if let Some(pass_ptr) = self.pass {
-            unsafe {
-                let pass = &mut *(pass_ptr as *mut wgpu::RenderPass<'_>);
-                pass.push_debug_group(&label);
-            }
-            Ok(())
-        } else {
-            Err(Error::from_reason("Render pass already ended"))
-        }
+        self.record(RenderCommand::PushDebugGroup(label))
     }
 
     /// Pop a debug group (WebGPU standard method)
-    #[napi(js_name = "popDebugGroup")]
+    #[napi(catch_unwind, js_name = "popDebugGroup")]
     pub fn pop_debug_group(&mut self) -> Result<()> {
-        if let Some(pass_ptr) = self.pass {
-            unsafe {
-                let pass = &mut *(pass_ptr as *mut wgpu::RenderPass<'_>);
-                pass.pop_debug_group();
-            }
-            Ok(())
-        } else {
-            Err(Error::from_reason("Render pass already ended"))
-        }
+        self.record(RenderCommand::PopDebugGroup)
     }
 
     /// Insert a debug marker (WebGPU standard method)
-    #[napi(js_name = "insertDebugMarker")]
+    #[napi(catch_unwind, js_name = "insertDebugMarker")]
     pub fn insert_debug_marker(&mut self, label: String) -> Result<()> {
-        if let Some(pass_ptr) = self.pass {
-            unsafe {
-                let pass = &mut *(pass_ptr as *mut wgpu::RenderPass<'_>);
-                pass.insert_debug_marker(&label);
-            }
-            Ok(())
-        } else {
-            Err(Error::from_reason("Render pass already ended"))
-        }
-    }
-}
-
-// Implement Drop to ensure the pass is properly cleaned up if not manually ended
-impl Drop for GpuRenderPassEncoder {
-    fn drop(&mut self) {
-        if let Some(pass_ptr) = self.pass.take() {
-            unsafe {
-                let _ = Box::from_raw(pass_ptr as *mut wgpu::RenderPass<'static>);
-            }
-        }
+        self.record(RenderCommand::InsertDebugMarker(label))
     }
 }
@@ -1,3 +1,5 @@
+use crate::parse::EnumValue;
+use napi::bindgen_prelude::*;
 use napi_derive::napi;
 use std::sync::Arc;
 
@@ -5,15 +7,15 @@ use std::sync::Arc;
 #[napi(object)]
 pub struct SamplerDescriptor {
     pub label: Option<String>,
-    pub address_mode_u: Option<String>,
-    pub address_mode_v: Option<String>,
-    pub address_mode_w: Option<String>,
-    pub mag_filter: Option<String>,
-    pub min_filter: Option<String>,
-    pub mipmap_filter: Option<String>,
+    pub address_mode_u: Option<Either<String, u32>>,
+    pub address_mode_v: Option<Either<String, u32>>,
+    pub address_mode_w: Option<Either<String, u32>>,
+    pub mag_filter: Option<Either<String, u32>>,
+    pub min_filter: Option<Either<String, u32>>,
+    pub mipmap_filter: Option<Either<String, u32>>,
     pub lod_min_clamp: Option<f64>,
     pub lod_max_clamp: Option<f64>,
-    pub compare: Option<String>,
+    pub compare: Option<Either<String, u32>>,
     pub max_anisotropy: Option<u32>,
 }
 
@@ -34,19 +36,49 @@ impl GpuSampler {
     }
 }
 
-pub(crate) fn create_sampler(device: &wgpu::Device, descriptor: &SamplerDescriptor) -> wgpu::Sampler {
-    device.create_sampler(&wgpu::SamplerDescriptor {
+/// Highest anisotropy wgpu passes to the backends; larger values are clamped to it
+const MAX_ANISOTROPY: u32 = 16;
+
+/// Filter mode of an anisotropic sampler
+///
+/// wgpu requires linear min, mag and mipmap filters with `maxAnisotropy > 1`,
+/// so omitted filters default to linear and others are rejected up front.
+fn filter_mode(name: &str, mode: Option<&EnumValue>, anisotropy: u32) -> Result<wgpu::FilterMode> {
+    if anisotropy == 1 {
+        return Ok(crate::parse::parse_filter_mode(mode));
+    }
+    match mode {
+        None => Ok(wgpu::FilterMode::Linear),
+        Some(mode) => match crate::parse::parse_filter_mode(Some(mode)) {
+            wgpu::FilterMode::Linear => Ok(wgpu::FilterMode::Linear),
+            _ => Err(Error::from_reason(format!(
+                "maxAnisotropy {} requires linear filtering, but {} is \"{}\"",
+                anisotropy,
+                name,
+                crate::parse::describe_enum(mode)
+            ))),
+        },
+    }
+}
+
+pub(crate) fn sampler_descriptor(descriptor: &SamplerDescriptor) -> Result<wgpu::SamplerDescriptor<'_>> {
+    let anisotropy = descriptor.max_anisotropy.unwrap_or(1);
+    if anisotropy == 0 {
+        return Err(Error::from_reason("maxAnisotropy must be at least 1"));
+    }
+
+    Ok(wgpu::SamplerDescriptor {
         label: descriptor.label.as_deref(),
         address_mode_u: crate::parse::parse_address_mode(descriptor.address_mode_u.as_ref()),
         address_mode_v: crate::parse::parse_address_mode(descriptor.address_mode_v.as_ref()),
         address_mode_w: crate::parse::parse_address_mode(descriptor.address_mode_w.as_ref()),
-        mag_filter: crate::parse::parse_filter_mode(descriptor.mag_filter.as_ref()),
-        min_filter: crate::parse::parse_filter_mode(descriptor.min_filter.as_ref()),
-        mipmap_filter: crate::parse::parse_filter_mode(descriptor.mipmap_filter.as_ref()),
+        mag_filter: filter_mode("magFilter", descriptor.mag_filter.as_ref(), anisotropy)?,
+        min_filter: filter_mode("minFilter", descriptor.min_filter.as_ref(), anisotropy)?,
+        mipmap_filter: filter_mode("mipmapFilter", descriptor.mipmap_filter.as_ref(), anisotropy)?,
         lod_min_clamp: descriptor.lod_min_clamp.unwrap_or(0.0) as f32,
         lod_max_clamp: descriptor.lod_max_clamp.unwrap_or(32.0) as f32,
         compare: crate::parse::parse_compare_function(descriptor.compare.as_ref()),
-        anisotropy_clamp: descriptor.max_anisotropy.unwrap_or(1) as u16,
+        anisotropy_clamp: anisotropy.min(MAX_ANISOTROPY) as u16,
         border_color: None,
     })
 }
@@ -0,0 +1,167 @@
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use std::sync::Arc;
+
+use crate::util;
+
+const WORKGROUP_SIZE: u32 = 256;
+
+/// Prefix sum descriptor
+#[napi(object)]
+pub struct PrefixSumDescriptor {
+    pub label: Option<String>,
+    /// Element type: "u32" (default) or "f32"
+    #[napi(js_name = "dataType")]
+    pub data_type: Option<String>,
+}
+
+/// Prefix sum (scan) utility
+///
+/// Computes exclusive or inclusive prefix sums over u32/f32 storage buffers.
+/// Large inputs are scanned in 256-element blocks; the block totals are scanned
+/// recursively and added back, so any element count is supported.
+/// Building block for stream compaction, GPU culling, and radix sort.
+#[napi]
+pub struct GpuPrefixSum {
+    device: Arc<wgpu::Device>,
+    queue: Arc<wgpu::Queue>,
+    scan_pipeline: wgpu::ComputePipeline,
+    add_pipeline: wgpu::ComputePipeline,
+}
+
+impl GpuPrefixSum {
+    pub(crate) fn new(
+        device: Arc<wgpu::Device>,
+        queue: Arc<wgpu::Queue>,
+        descriptor: Option<PrefixSumDescriptor>,
+    ) -> Result<Self> {
+        let element_type = match descriptor.as_ref().and_then(|d| d.data_type.as_deref()) {
+            None | Some("u32") => "u32",
+            Some("f32") => "f32",
+            Some(other) => {
+                return Err(Error::from_reason(format!(
+                    "Invalid prefix sum data type: {}. Use 'u32' or 'f32'",
+                    other
+                )))
+            }
+        };
+        let label = descriptor
+            .as_ref()
+            .and_then(|d| d.label.clone())
+            .unwrap_or_else(|| "prefix-sum".to_string());
+
+        let alias = format!("alias Element = {};\n", element_type);
+        let scan_pipeline = util::create_compute_pipeline(
+            &device,
+            &format!("{} scan", label),
+            &(alias.clone() + include_str!("shaders/scan.wgsl")),
+            "main",
+        );
+        let add_pipeline = util::create_compute_pipeline(
+            &device,
+            &format!("{} add", label),
+            &(alias + include_str!("shaders/scan_add.wgsl")),
+            "main",
+        );
+
+        Ok(Self {
+            device,
+            queue,
+            scan_pipeline,
+            add_pipeline,
+        })
+    }
+
+    /// Record the scan of `count` elements from `input` into `output`
+    ///
+    /// `input` and `output` must be different buffers.
+    pub(crate) fn record(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        input: &wgpu::Buffer,
+        output: &wgpu::Buffer,
+        count: u32,
+        inclusive: bool,
+    ) {
+        if count == 0 {
+            return;
+        }
+
+        let block_count = count.div_ceil(WORKGROUP_SIZE);
+        let params = util::create_params_buffer(&self.device, "prefix-sum params", &[count, inclusive as u32]);
+        let block_sums = util::create_storage_buffer(&self.device, "prefix-sum block sums", block_count as u64 * 4);
+
+        let bind_group = util::bind_buffers(
+            &self.device,
+            &self.scan_pipeline,
+            &[&params, input, output, &block_sums],
+        );
+        util::dispatch(encoder, &self.scan_pipeline, &bind_group, block_count);
+
+        if block_count > 1 {
+            // Exclusive scan of the block totals gives each block's starting offset
+            let block_offsets =
+                util::create_storage_buffer(&self.device, "prefix-sum block offsets", block_count as u64 * 4);
+            self.record(encoder, &block_sums, &block_offsets, block_count, false);
+
+            let bind_group = util::bind_buffers(
+                &self.device,
+                &self.add_pipeline,
+                &[&params, &block_offsets, output],
+            );
+            util::dispatch(encoder, &self.add_pipeline, &bind_group, block_count);
+        }
+    }
+
+    fn validate(input: &crate::GpuBuffer, output: &crate::GpuBuffer, count: u32) -> Result<()> {
+        if std::ptr::eq(input, output) {
+            return Err(Error::from_reason("Prefix sum input and output must be different buffers"));
+        }
+        let size = count as u64 * 4;
+        util::validate_storage_buffer(&input.buffer, size, "Prefix sum input")?;
+        util::validate_storage_buffer(&output.buffer, size, "Prefix sum output")
+    }
+}
+
+#[napi]
+impl GpuPrefixSum {
+    /// Record the prefix sum into a command encoder
+    ///
+    /// Scans `count` elements of `input` into `output`. The scan is exclusive
+    /// unless `inclusive` is true. Both buffers need STORAGE usage.
+    #[napi(catch_unwind)]
+    pub fn encode(
+        &self,
+        encoder: &mut crate::GpuCommandEncoder,
+        input: &crate::GpuBuffer,
+        output: &crate::GpuBuffer,
+        count: u32,
+        inclusive: Option<bool>,
+    ) -> Result<()> {
+        Self::validate(input, output, count)?;
+        encoder.with_encoder(|enc| {
+            self.record(enc, &input.buffer, &output.buffer, count, inclusive.unwrap_or(false));
+            Ok(())
+        })
+    }
+
+    /// Run the prefix sum immediately
+    ///
+    /// Convenience wrapper that encodes the scan into a fresh command buffer and submits it.
+    #[napi(catch_unwind)]
+    pub fn run(
+        &self,
+        input: &crate::GpuBuffer,
+        output: &crate::GpuBuffer,
+        count: u32,
+        inclusive: Option<bool>,
+    ) -> Result<()> {
+        Self::validate(input, output, count)?;
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: Some("prefix-sum") });
+        self.record(&mut encoder, &input.buffer, &output.buffer, count, inclusive.unwrap_or(false));
+        self.queue.submit(Some(encoder.finish()));
+        Ok(())
+    }
+}
@@ -0,0 +1,81 @@
+use napi_derive::napi;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Shader module cache statistics
+#[napi(object)]
+pub struct ShaderModuleCacheStats {
+    /// Number of distinct shader sources currently cached
+    pub entries: u32,
+    /// Number of createShaderModule calls served from the cache
+    pub hits: u32,
+    /// Number of createShaderModule calls that compiled a new module
+    pub misses: u32,
+}
+
+/// Device-level shader module de-duplication cache
+///
+/// Maps WGSL source to the compiled module (and its lazily parsed naga
+/// reflection data) so identical code is only compiled and parsed once. Disabled by default; when disabled every call compiles a fresh module.
+#[derive(Default)]
+pub(crate) struct ShaderModuleCache {
+    inner: Mutex<CacheState>,
+}
+
+#[derive(Default)]
+struct CacheState {
+    enabled: bool,
+    modules: HashMap<String, crate::GpuShaderModule>,
+    hits: u32,
+    misses: u32,
+}
+
+impl ShaderModuleCache {
+    pub(crate) fn set_enabled(&self, enabled: bool) {
+        let mut state = self.inner.lock().unwrap();
+        state.enabled = enabled;
+        if !enabled {
+            state.modules.clear();
+        }
+    }
+
+    pub(crate) fn clear(&self) {
+        let mut state = self.inner.lock().unwrap();
+        state.modules.clear();
+        state.hits = 0;
+        state.misses = 0;
+    }
+
+    pub(crate) fn stats(&self) -> ShaderModuleCacheStats {
+        let state = self.inner.lock().unwrap();
+        ShaderModuleCacheStats {
+            entries: state.modules.len() as u32,
+            hits: state.hits,
+            misses: state.misses,
+        }
+    }
+
+    /// Return the cached module for `code`, compiling it with `create` on a miss
+    ///
+    /// The label of the first module created for a given source is kept.
+    pub(crate) fn get_or_create(
+        &self,
+        code: String,
+        create: impl FnOnce(&str) -> napi::Result<crate::GpuShaderModule>,
+    ) -> napi::Result<crate::GpuShaderModule> {
+        let mut state = self.inner.lock().unwrap();
+        if !state.enabled {
+            return create(&code);
+        }
+        if let Some(module) = state.modules.get(&code) {
+            let module = module.clone();
+            state.hits += 1;
+            return Ok(module);
+        }
+        // Modules that fail to compile are not cached
+        let module = create(&code)?;
+        state.misses += 1;
+        state.modules.insert(code, module.clone());
+        Ok(module)
+    }
+}
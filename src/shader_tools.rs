@@ -0,0 +1,156 @@
+//! Offline shader conversion helpers built on naga
+//!
+//! These run entirely on the CPU and don't need an adapter or device.
+
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+
+/// Parse WGSL source, formatting errors with source context
+pub(crate) fn parse_wgsl(code: &str) -> Result<naga::Module> {
+    naga::front::wgsl::parse_str(code)
+        .map_err(|e| Error::from_reason(format!("Failed to parse WGSL: {}", e.emit_to_string(code))))
+}
+
+/// Validate a naga module, returning the info needed by the backends
+pub(crate) fn validate_module(module: &naga::Module) -> Result<naga::valid::ModuleInfo> {
+    naga::valid::Validator::new(
+        naga::valid::ValidationFlags::all(),
+        naga::valid::Capabilities::all(),
+    )
+    .validate(module)
+    .map_err(|e| Error::from_reason(format!("Shader validation failed: {}", e.into_inner())))
+}
+
+/// Convert a SPIR-V binary to WGSL source
+///
+/// The input is the raw SPIR-V byte stream (little-endian words, e.g. the
+/// contents of a `.spv` file). Throws if the module can't be parsed, fails
+/// validation, or uses features WGSL can't express.
+#[napi(catch_unwind, js_name = "spirvToWgsl")]
+pub fn spirv_to_wgsl(spirv: Uint8Array) -> Result<String> {
+    if spirv.len() % 4 != 0 {
+        return Err(Error::from_reason(format!(
+            "SPIR-V byte length must be a multiple of 4, got {}",
+            spirv.len()
+        )));
+    }
+
+    let module = naga::front::spv::parse_u8_slice(&spirv, &naga::front::spv::Options::default())
+        .map_err(|e| Error::from_reason(format!("Failed to parse SPIR-V: {}", e)))?;
+    let info = validate_module(&module)?;
+
+    naga::back::wgsl::write_string(&module, &info, naga::back::wgsl::WriterFlags::empty())
+        .map_err(|e| Error::from_reason(format!("Failed to write WGSL: {}", e)))
+}
+
+/// Options for translateWgsl
+#[napi(object)]
+pub struct TranslateWgslOptions {
+    /// Entry point to translate (GLSL only; defaults to the first entry point)
+    #[napi(js_name = "entryPoint")]
+    pub entry_point: Option<String>,
+    /// GLSL version, e.g. "450" (core) or "310 es" (default)
+    #[napi(js_name = "glslVersion")]
+    pub glsl_version: Option<String>,
+}
+
+fn parse_glsl_version(version: &str) -> Result<naga::back::glsl::Version> {
+    let invalid = || Error::from_reason(format!("Invalid GLSL version: {}. Use e.g. '450' or '310 es'", version));
+    let (number, es) = match version.trim().strip_suffix("es") {
+        Some(number) => (number.trim(), true),
+        None => (version.trim(), false),
+    };
+    let number: u16 = number.parse().map_err(|_| invalid())?;
+    // Unsupported version numbers are rejected by the GLSL writer
+    Ok(if es {
+        naga::back::glsl::Version::new_gles(number)
+    } else {
+        naga::back::glsl::Version::Desktop(number)
+    })
+}
+
+fn write_glsl(
+    module: &naga::Module,
+    info: &naga::valid::ModuleInfo,
+    options: Option<&TranslateWgslOptions>,
+) -> Result<String> {
+    let entry_point = match options.and_then(|o| o.entry_point.as_deref()) {
+        Some(name) => module
+            .entry_points
+            .iter()
+            .find(|ep| ep.name == name)
+            .ok_or_else(|| Error::from_reason(format!("Entry point not found: {}", name)))?,
+        None => module
+            .entry_points
+            .first()
+            .ok_or_else(|| Error::from_reason("Shader has no entry points"))?,
+    };
+
+    let mut glsl_options = naga::back::glsl::Options::default();
+    if let Some(version) = options.and_then(|o| o.glsl_version.as_deref()) {
+        glsl_options.version = parse_glsl_version(version)?;
+    }
+    let pipeline_options = naga::back::glsl::PipelineOptions {
+        shader_stage: entry_point.stage,
+        entry_point: entry_point.name.clone(),
+        multiview: None,
+    };
+
+    let mut output = String::new();
+    naga::back::glsl::Writer::new(
+        &mut output,
+        module,
+        info,
+        &glsl_options,
+        &pipeline_options,
+        naga::proc::BoundsCheckPolicies::default(),
+    )
+    .and_then(|mut writer| writer.write())
+    .map_err(|e| Error::from_reason(format!("Failed to write GLSL: {}", e)))?;
+    Ok(output)
+}
+
+/// Translate WGSL to another shading language
+///
+/// `target` is one of "msl", "hlsl", "glsl" or "spirv". Text targets return
+/// source code; "spirv" returns the binary as a Buffer. GLSL output covers a
+/// single entry point, selected with `options.entryPoint`.
+#[napi(catch_unwind, js_name = "translateWgsl")]
+pub fn translate_wgsl(
+    code: String,
+    target: String,
+    options: Option<TranslateWgslOptions>,
+) -> Result<Either<String, Buffer>> {
+    let module = parse_wgsl(&code)?;
+    let info = validate_module(&module)?;
+
+    match target.as_str() {
+        "msl" => naga::back::msl::write_string(
+            &module,
+            &info,
+            &naga::back::msl::Options::default(),
+            &naga::back::msl::PipelineOptions::default(),
+        )
+        .map(|(output, _)| Either::A(output))
+        .map_err(|e| Error::from_reason(format!("Failed to write MSL: {}", e))),
+        "hlsl" => {
+            let hlsl_options = naga::back::hlsl::Options::default();
+            let mut output = String::new();
+            naga::back::hlsl::Writer::new(&mut output, &hlsl_options)
+                .write(&module, &info)
+                .map_err(|e| Error::from_reason(format!("Failed to write HLSL: {}", e)))?;
+            Ok(Either::A(output))
+        }
+        "glsl" => write_glsl(&module, &info, options.as_ref()).map(Either::A),
+        "spirv" => {
+            let words = naga::back::spv::write_vec(&module, &info, &naga::back::spv::Options::default(), None)
+                .map_err(|e| Error::from_reason(format!("Failed to write SPIR-V: {}", e)))?;
+            let bytes: Vec<u8> = words.iter().flat_map(|word| word.to_le_bytes()).collect();
+            Ok(Either::B(bytes.into()))
+        }
+        other => Err(Error::from_reason(format!(
+            "Invalid translation target: {}. Use 'msl', 'hlsl', 'glsl' or 'spirv'",
+            other
+        ))),
+    }
+}
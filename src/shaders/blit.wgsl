@@ -0,0 +1,11 @@
+// Draws the source texture over the whole render target, converting colors
+// with convert_color
+
+@group(0) @binding(0) var source: texture_2d<f32>;
+@group(0) @binding(1) var source_sampler: sampler;
+@group(0) @binding(2) var<uniform> params: ColorParams;
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    return convert_color(textureSampleLevel(source, source_sampler, in.uv, 0.0), params);
+}
@@ -0,0 +1,31 @@
+// One direction of a separable Gaussian blur; texels past the edge repeat the edge
+
+struct Params {
+    // (1, 0) for the horizontal pass, (0, 1) for the vertical one
+    direction: vec2<u32>,
+    radius: u32,
+    // Weights of the center and each distance up to the radius, packed four per vector
+    weights: array<vec4<f32>, 16>,
+}
+
+@group(0) @binding(0) var source: texture_2d<f32>;
+@group(0) @binding(1) var<uniform> params: Params;
+
+fn weight(distance: u32) -> f32 {
+    return params.weights[distance / 4u][distance % 4u];
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let last = vec2<i32>(textureDimensions(source)) - 1;
+    let center = vec2<i32>(in.position.xy);
+    let step = vec2<i32>(params.direction);
+    var sum = textureLoad(source, center, 0) * weight(0u);
+    for (var i = 1u; i <= params.radius; i++) {
+        let offset = step * i32(i);
+        let before = textureLoad(source, clamp(center - offset, vec2<i32>(0), last), 0);
+        let after = textureLoad(source, clamp(center + offset, vec2<i32>(0), last), 0);
+        sum += (before + after) * weight(i);
+    }
+    return sum;
+}
@@ -0,0 +1,33 @@
+// Channel swizzle and sRGB transfer functions shared by the blit and pixel conversion shaders
+
+struct ColorParams {
+    // Source channel of each output channel
+    swizzle: vec4<u32>,
+    // 0: none, 1: sRGB to linear, 2: linear to sRGB
+    color_space: u32,
+    // Pixel count, for buffer conversions
+    count: u32,
+}
+
+fn srgb_to_linear(c: vec3<f32>) -> vec3<f32> {
+    return select(pow((c + 0.055) / 1.055, vec3<f32>(2.4)), c / 12.92, c <= vec3<f32>(0.04045));
+}
+
+fn linear_to_srgb(c: vec3<f32>) -> vec3<f32> {
+    return select(1.055 * pow(c, vec3<f32>(1.0 / 2.4)) - 0.055, c * 12.92, c <= vec3<f32>(0.0031308));
+}
+
+fn convert_color(color: vec4<f32>, params: ColorParams) -> vec4<f32> {
+    var out = vec4<f32>(
+        color[params.swizzle.x],
+        color[params.swizzle.y],
+        color[params.swizzle.z],
+        color[params.swizzle.w],
+    );
+    if (params.color_space == 1u) {
+        out = vec4<f32>(srgb_to_linear(max(out.rgb, vec3<f32>(0.0))), out.a);
+    } else if (params.color_space == 2u) {
+        out = vec4<f32>(linear_to_srgb(max(out.rgb, vec3<f32>(0.0))), out.a);
+    }
+    return out;
+}
@@ -0,0 +1,19 @@
+// Converts packed RGBA8 pixels with convert_color
+
+const WORKGROUP_SIZE: u32 = 256u;
+
+@group(0) @binding(0) var<uniform> params: ColorParams;
+@group(0) @binding(1) var<storage, read> input: array<u32>;
+@group(0) @binding(2) var<storage, read_write> output: array<u32>;
+
+@compute @workgroup_size(256)
+fn main(
+    @builtin(local_invocation_id) local_id: vec3<u32>,
+    @builtin(workgroup_id) workgroup_id: vec3<u32>,
+    @builtin(num_workgroups) num_workgroups: vec3<u32>,
+) {
+    let index = (workgroup_id.x + workgroup_id.y * num_workgroups.x) * WORKGROUP_SIZE + local_id.x;
+    if (index < params.count) {
+        output[index] = pack4x8unorm(convert_color(unpack4x8unorm(input[index]), params));
+    }
+}
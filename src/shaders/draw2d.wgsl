@@ -0,0 +1,33 @@
+// Colored and textured triangles in pixel coordinates for GpuDraw2D
+//
+// Untextured shapes sample a 1x1 white texture, so one pipeline draws both.
+
+struct Viewport {
+    size: vec2<f32>,
+}
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+    @location(1) color: vec4<f32>,
+}
+
+@group(0) @binding(0) var<uniform> viewport: Viewport;
+@group(0) @binding(1) var image_sampler: sampler;
+@group(0) @binding(2) var image: texture_2d<f32>;
+
+@vertex
+fn vs_main(
+    @location(0) position: vec2<f32>,
+    @location(1) uv: vec2<f32>,
+    @location(2) color: vec4<f32>,
+) -> VertexOutput {
+    // Pixels from the top left to clip space
+    let clip = position / viewport.size * vec2<f32>(2.0, -2.0) + vec2<f32>(-1.0, 1.0);
+    return VertexOutput(vec4<f32>(clip, 0.0, 1.0), uv, color);
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    return textureSample(image, image_sampler, in.uv) * in.color;
+}
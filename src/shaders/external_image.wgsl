@@ -0,0 +1,47 @@
+// Converts decoded RGBA8 images for copyExternalImageToTexture: flips and
+// crops the source, applies convert_color and premultiplies alpha into rows
+// padded for a buffer-to-texture copy
+
+const WORKGROUP_SIZE: u32 = 256u;
+
+struct ImageParams {
+    color: ColorParams,
+    source_width: u32,
+    source_height: u32,
+    // Copy origin in the (possibly flipped) source
+    origin_x: u32,
+    origin_y: u32,
+    // Copy width; the height follows from color.count
+    width: u32,
+    // Output row length in pixels
+    row_pixels: u32,
+    flip_y: u32,
+    premultiply: u32,
+}
+
+@group(0) @binding(0) var<uniform> params: ImageParams;
+@group(0) @binding(1) var<storage, read> input: array<u32>;
+@group(0) @binding(2) var<storage, read_write> output: array<u32>;
+
+@compute @workgroup_size(256)
+fn main(
+    @builtin(local_invocation_id) local_id: vec3<u32>,
+    @builtin(workgroup_id) workgroup_id: vec3<u32>,
+    @builtin(num_workgroups) num_workgroups: vec3<u32>,
+) {
+    let index = (workgroup_id.x + workgroup_id.y * num_workgroups.x) * WORKGROUP_SIZE + local_id.x;
+    if (index >= params.color.count) {
+        return;
+    }
+    let x = index % params.width;
+    let y = index / params.width;
+    var row = params.origin_y + y;
+    if (params.flip_y != 0u) {
+        row = params.source_height - 1u - row;
+    }
+    var color = convert_color(unpack4x8unorm(input[row * params.source_width + params.origin_x + x]), params.color);
+    if (params.premultiply != 0u) {
+        color = vec4<f32>(color.rgb * color.a, color.a);
+    }
+    output[y * params.row_pixels + x] = pack4x8unorm(color);
+}
@@ -0,0 +1,46 @@
+// Per-channel histogram accumulation shared by the texture and buffer shaders
+//
+// Each workgroup counts its pixels into workgroup memory and then adds the
+// non-zero bins to `histogram`, which holds `bins` counters per channel in
+// RGBA order.
+
+struct Params {
+    bins: u32,
+    // Pixel count, for buffer sources
+    count: u32,
+}
+
+const WORKGROUP_SIZE: u32 = 256u;
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var<storage, read_write> histogram: array<atomic<u32>>;
+
+// Four channels of up to 256 bins
+var<workgroup> local_bins: array<atomic<u32>, 1024>;
+
+fn clear_bins(local_index: u32) {
+    for (var i = local_index; i < params.bins * 4u; i += WORKGROUP_SIZE) {
+        atomicStore(&local_bins[i], 0u);
+    }
+    workgroupBarrier();
+}
+
+// Count one color with channels in [0, 1]; values outside go to the end bins
+fn count_color(color: vec4<f32>) {
+    let bins = params.bins;
+    let bin = vec4<u32>(clamp(color * f32(bins), vec4<f32>(0.0), vec4<f32>(f32(bins - 1u))));
+    atomicAdd(&local_bins[bin.r], 1u);
+    atomicAdd(&local_bins[bins + bin.g], 1u);
+    atomicAdd(&local_bins[bins * 2u + bin.b], 1u);
+    atomicAdd(&local_bins[bins * 3u + bin.a], 1u);
+}
+
+fn flush_bins(local_index: u32) {
+    workgroupBarrier();
+    for (var i = local_index; i < params.bins * 4u; i += WORKGROUP_SIZE) {
+        let count = atomicLoad(&local_bins[i]);
+        if (count > 0u) {
+            atomicAdd(&histogram[i], count);
+        }
+    }
+}
@@ -0,0 +1,22 @@
+// Histogram of packed RGBA8 pixels, PIXELS_PER_THREAD per invocation
+
+const PIXELS_PER_THREAD: u32 = 4u;
+
+@group(0) @binding(2) var<storage, read> pixels: array<u32>;
+
+@compute @workgroup_size(256)
+fn main(
+    @builtin(local_invocation_index) local_index: u32,
+    @builtin(workgroup_id) workgroup_id: vec3<u32>,
+    @builtin(num_workgroups) num_workgroups: vec3<u32>,
+) {
+    clear_bins(local_index);
+    let block = workgroup_id.x + workgroup_id.y * num_workgroups.x;
+    for (var i = 0u; i < PIXELS_PER_THREAD; i++) {
+        let index = (block * PIXELS_PER_THREAD + i) * WORKGROUP_SIZE + local_index;
+        if (index < params.count) {
+            count_color(unpack4x8unorm(pixels[index]));
+        }
+    }
+    flush_bins(local_index);
+}
@@ -0,0 +1,16 @@
+// Histogram of mip level 0 of a float texture, one 16x16 tile per workgroup
+
+@group(0) @binding(2) var source: texture_2d<f32>;
+
+@compute @workgroup_size(256)
+fn main(
+    @builtin(local_invocation_index) local_index: u32,
+    @builtin(workgroup_id) workgroup_id: vec3<u32>,
+) {
+    clear_bins(local_index);
+    let pixel = workgroup_id.xy * 16u + vec2<u32>(local_index % 16u, local_index / 16u);
+    if (all(pixel < textureDimensions(source))) {
+        count_color(textureLoad(source, pixel, 0));
+    }
+    flush_bins(local_index);
+}
@@ -0,0 +1,89 @@
+// Tiled matrix multiply used by GpuMatmul: C (m x n) = A (m x k) * B (k x n).
+//
+// All matrices are dense and row-major. `Element` is injected by the host as
+// an alias (f32 or f16, with `enable f16;` when needed); tiles are staged in
+// workgroup memory and accumulated in f32 regardless of the element type.
+// Each workgroup computes a 64x64 block of C, each invocation a 4x4 sub-block.
+
+struct Params {
+    m: u32,
+    n: u32,
+    k: u32,
+}
+
+const TILE: u32 = 64u;
+const TILE_K: u32 = 16u;
+const THREAD_TILE: u32 = 4u;
+const INVOCATIONS: u32 = 256u;
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var<storage, read> a: array<Element>;
+@group(0) @binding(2) var<storage, read> b: array<Element>;
+@group(0) @binding(3) var<storage, read_write> c: array<Element>;
+
+// A tile is TILE rows x TILE_K columns, B tile is TILE_K rows x TILE columns
+var<workgroup> tile_a: array<f32, 1024>;
+var<workgroup> tile_b: array<f32, 1024>;
+
+@compute @workgroup_size(16, 16)
+fn main(
+    @builtin(local_invocation_id) local_id: vec3<u32>,
+    @builtin(workgroup_id) workgroup_id: vec3<u32>,
+) {
+    let block_row = workgroup_id.y * TILE;
+    let block_col = workgroup_id.x * TILE;
+    let local_index = local_id.y * 16u + local_id.x;
+
+    var acc: array<f32, 16>;
+    for (var i = 0u; i < 16u; i++) {
+        acc[i] = 0.0;
+    }
+
+    for (var t = 0u; t < params.k; t += TILE_K) {
+        for (var i = 0u; i < TILE * TILE_K / INVOCATIONS; i++) {
+            let index = local_index + i * INVOCATIONS;
+
+            let a_row = block_row + index / TILE_K;
+            let a_col = t + index % TILE_K;
+            var a_value = 0.0;
+            if (a_row < params.m && a_col < params.k) {
+                a_value = f32(a[a_row * params.k + a_col]);
+            }
+            tile_a[index] = a_value;
+
+            let b_row = t + index / TILE;
+            let b_col = block_col + index % TILE;
+            var b_value = 0.0;
+            if (b_row < params.k && b_col < params.n) {
+                b_value = f32(b[b_row * params.n + b_col]);
+            }
+            tile_b[index] = b_value;
+        }
+        workgroupBarrier();
+
+        for (var kk = 0u; kk < TILE_K; kk++) {
+            var a_regs: array<f32, 4>;
+            var b_regs: array<f32, 4>;
+            for (var i = 0u; i < THREAD_TILE; i++) {
+                a_regs[i] = tile_a[(local_id.y * THREAD_TILE + i) * TILE_K + kk];
+                b_regs[i] = tile_b[kk * TILE + local_id.x * THREAD_TILE + i];
+            }
+            for (var i = 0u; i < THREAD_TILE; i++) {
+                for (var j = 0u; j < THREAD_TILE; j++) {
+                    acc[i * THREAD_TILE + j] += a_regs[i] * b_regs[j];
+                }
+            }
+        }
+        workgroupBarrier();
+    }
+
+    for (var i = 0u; i < THREAD_TILE; i++) {
+        let row = block_row + local_id.y * THREAD_TILE + i;
+        for (var j = 0u; j < THREAD_TILE; j++) {
+            let col = block_col + local_id.x * THREAD_TILE + j;
+            if (row < params.m && col < params.n) {
+                c[row * params.n + col] = Element(acc[i * THREAD_TILE + j]);
+            }
+        }
+    }
+}
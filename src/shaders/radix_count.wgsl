@@ -0,0 +1,45 @@
+// Per-block digit histogram for one GpuRadixSort pass.
+//
+// Histograms are written digit-major (`digit * block_count + block`) so an
+// exclusive scan over the whole array yields stable global scatter offsets.
+
+struct Params {
+    count: u32,
+    shift: u32,
+    block_count: u32,
+    has_values: u32,
+}
+
+const WORKGROUP_SIZE: u32 = 256u;
+const RADIX: u32 = 16u;
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var<storage, read> keys: array<u32>;
+@group(0) @binding(2) var<storage, read_write> histograms: array<u32>;
+
+var<workgroup> counts: array<atomic<u32>, RADIX>;
+
+@compute @workgroup_size(256)
+fn main(
+    @builtin(local_invocation_id) local_id: vec3<u32>,
+    @builtin(workgroup_id) workgroup_id: vec3<u32>,
+    @builtin(num_workgroups) num_workgroups: vec3<u32>,
+) {
+    let block = workgroup_id.x + workgroup_id.y * num_workgroups.x;
+    let index = block * WORKGROUP_SIZE + local_id.x;
+
+    if (local_id.x < RADIX) {
+        atomicStore(&counts[local_id.x], 0u);
+    }
+    workgroupBarrier();
+
+    if (index < params.count) {
+        let digit = (keys[index] >> params.shift) & (RADIX - 1u);
+        atomicAdd(&counts[digit], 1u);
+    }
+    workgroupBarrier();
+
+    if (local_id.x < RADIX && block < params.block_count) {
+        histograms[local_id.x * params.block_count + block] = atomicLoad(&counts[local_id.x]);
+    }
+}
@@ -0,0 +1,58 @@
+// Stable scatter for one GpuRadixSort pass.
+//
+// Each element's destination is its (digit, block) offset from the scanned
+// histograms plus its rank among equal digits earlier in the same block.
+
+struct Params {
+    count: u32,
+    shift: u32,
+    block_count: u32,
+    has_values: u32,
+}
+
+const WORKGROUP_SIZE: u32 = 256u;
+const RADIX: u32 = 16u;
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var<storage, read> keys_in: array<u32>;
+@group(0) @binding(2) var<storage, read> values_in: array<u32>;
+@group(0) @binding(3) var<storage, read> offsets: array<u32>;
+@group(0) @binding(4) var<storage, read_write> keys_out: array<u32>;
+@group(0) @binding(5) var<storage, read_write> values_out: array<u32>;
+
+var<workgroup> digits: array<u32, WORKGROUP_SIZE>;
+
+@compute @workgroup_size(256)
+fn main(
+    @builtin(local_invocation_id) local_id: vec3<u32>,
+    @builtin(workgroup_id) workgroup_id: vec3<u32>,
+    @builtin(num_workgroups) num_workgroups: vec3<u32>,
+) {
+    let block = workgroup_id.x + workgroup_id.y * num_workgroups.x;
+    let index = block * WORKGROUP_SIZE + local_id.x;
+
+    // Out-of-range lanes get a digit no real key can have
+    var digit = RADIX;
+    var key = 0u;
+    if (index < params.count) {
+        key = keys_in[index];
+        digit = (key >> params.shift) & (RADIX - 1u);
+    }
+    digits[local_id.x] = digit;
+    workgroupBarrier();
+
+    if (index < params.count) {
+        var rank = 0u;
+        for (var i = 0u; i < local_id.x; i = i + 1u) {
+            if (digits[i] == digit) {
+                rank = rank + 1u;
+            }
+        }
+
+        let destination = offsets[digit * params.block_count + block] + rank;
+        keys_out[destination] = key;
+        if (params.has_values != 0u) {
+            values_out[destination] = values_in[index];
+        }
+    }
+}
@@ -0,0 +1,63 @@
+// Work-efficient block scan used by GpuPrefixSum.
+//
+// `Element` is injected by the host as an alias (u32 or f32) so the same
+// source serves both element types. Each workgroup scans WORKGROUP_SIZE
+// elements and writes its total to `block_sums`; those totals are scanned
+// recursively and added back by `scan_add.wgsl`.
+
+struct Params {
+    count: u32,
+    inclusive: u32,
+}
+
+const WORKGROUP_SIZE: u32 = 256u;
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var<storage, read> input: array<Element>;
+@group(0) @binding(2) var<storage, read_write> output: array<Element>;
+@group(0) @binding(3) var<storage, read_write> block_sums: array<Element>;
+
+var<workgroup> temp: array<Element, WORKGROUP_SIZE>;
+
+@compute @workgroup_size(256)
+fn main(
+    @builtin(local_invocation_id) local_id: vec3<u32>,
+    @builtin(workgroup_id) workgroup_id: vec3<u32>,
+    @builtin(num_workgroups) num_workgroups: vec3<u32>,
+) {
+    let block = workgroup_id.x + workgroup_id.y * num_workgroups.x;
+    let index = block * WORKGROUP_SIZE + local_id.x;
+
+    var value = Element(0);
+    if (index < params.count) {
+        value = input[index];
+    }
+    temp[local_id.x] = value;
+    workgroupBarrier();
+
+    // Hillis-Steele inclusive scan over the block
+    for (var offset = 1u; offset < WORKGROUP_SIZE; offset = offset * 2u) {
+        var addend = Element(0);
+        if (local_id.x >= offset) {
+            addend = temp[local_id.x - offset];
+        }
+        workgroupBarrier();
+        temp[local_id.x] = temp[local_id.x] + addend;
+        workgroupBarrier();
+    }
+
+    if (index < params.count) {
+        if (params.inclusive != 0u) {
+            output[index] = temp[local_id.x];
+        } else if (local_id.x == 0u) {
+            output[index] = Element(0);
+        } else {
+            output[index] = temp[local_id.x - 1u];
+        }
+    }
+
+    let block_count = (params.count + WORKGROUP_SIZE - 1u) / WORKGROUP_SIZE;
+    if (local_id.x == WORKGROUP_SIZE - 1u && block < block_count) {
+        block_sums[block] = temp[local_id.x];
+    }
+}
@@ -0,0 +1,25 @@
+// Adds the scanned per-block totals back onto every element of the block.
+
+struct Params {
+    count: u32,
+    inclusive: u32,
+}
+
+const WORKGROUP_SIZE: u32 = 256u;
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var<storage, read> block_offsets: array<Element>;
+@group(0) @binding(2) var<storage, read_write> data: array<Element>;
+
+@compute @workgroup_size(256)
+fn main(
+    @builtin(local_invocation_id) local_id: vec3<u32>,
+    @builtin(workgroup_id) workgroup_id: vec3<u32>,
+    @builtin(num_workgroups) num_workgroups: vec3<u32>,
+) {
+    let block = workgroup_id.x + workgroup_id.y * num_workgroups.x;
+    let index = block * WORKGROUP_SIZE + local_id.x;
+    if (index < params.count) {
+        data[index] = data[index] + block_offsets[block];
+    }
+}
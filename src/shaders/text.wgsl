@@ -0,0 +1,35 @@
+// Glyph quads in pixel coordinates for GpuFont
+//
+// The atlas holds glyph coverage in its red channel, which scales the alpha
+// of the text color.
+
+struct Viewport {
+    size: vec2<f32>,
+}
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+    @location(1) color: vec4<f32>,
+}
+
+@group(0) @binding(0) var<uniform> viewport: Viewport;
+@group(0) @binding(1) var atlas_sampler: sampler;
+@group(0) @binding(2) var atlas: texture_2d<f32>;
+
+@vertex
+fn vs_main(
+    @location(0) position: vec2<f32>,
+    @location(1) uv: vec2<f32>,
+    @location(2) color: vec4<f32>,
+) -> VertexOutput {
+    // Pixels from the top left to clip space
+    let clip = position / viewport.size * vec2<f32>(2.0, -2.0) + vec2<f32>(-1.0, 1.0);
+    return VertexOutput(vec4<f32>(clip, 0.0, 1.0), uv, color);
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let coverage = textureSample(atlas, atlas_sampler, in.uv).r;
+    return vec4<f32>(in.color.rgb, in.color.a * coverage);
+}
@@ -0,0 +1,541 @@
+//! Window surfaces for presenting rendered frames
+//!
+//! Surfaces are created from native window handles passed as BigInts. Nothing
+//! here owns the window: it must stay open for as long as the surface exists.
+
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use raw_window_handle::{
+    AppKitDisplayHandle, AppKitWindowHandle, RawDisplayHandle, RawWindowHandle, WaylandDisplayHandle,
+    WaylandWindowHandle, Win32WindowHandle, WindowsDisplayHandle, XcbDisplayHandle, XcbWindowHandle,
+    XlibDisplayHandle, XlibWindowHandle,
+};
+use std::num::{NonZeroIsize, NonZeroU32};
+use std::ptr::NonNull;
+use std::sync::{mpsc, Arc, Mutex};
+
+/// Native window to create a surface for
+#[napi(object)]
+pub struct SurfaceDescriptor {
+    /// "win32", "appkit", "xlib", "xcb" or "wayland"
+    pub platform: String,
+    /// HWND, NSView*, X11 Window, xcb_window_t or wl_surface*
+    pub window: BigInt,
+    /// X11 Display*, xcb_connection_t* or wl_display* (required for "wayland")
+    pub display: Option<BigInt>,
+    /// X11 screen number (default 0)
+    pub screen: Option<i32>,
+    /// HINSTANCE of the module that created the window ("win32", optional)
+    pub hinstance: Option<BigInt>,
+}
+
+/// Surface configuration
+#[napi(object)]
+pub struct SurfaceConfiguration {
+    pub format: Either<String, u32>,
+    pub width: u32,
+    pub height: u32,
+    /// Texture usage bit mask or flag names (default RENDER_ATTACHMENT)
+    pub usage: Option<Either<u32, Vec<String>>>,
+    /// "fifo" (default, vsync), "fifo-relaxed", "immediate", "mailbox",
+    /// "auto-vsync" or "auto-no-vsync"
+    pub present_mode: Option<Either<String, u32>>,
+    /// "auto" (default), "opaque", "premultiplied", "postmultiplied" or "inherit"
+    pub alpha_mode: Option<Either<String, u32>>,
+    /// Extra formats views of the frame may use: the sRGB or non-sRGB
+    /// variant of `format`
+    pub view_formats: Option<Vec<Either<String, u32>>>,
+}
+
+/// What a surface supports on a given adapter
+#[napi(object)]
+pub struct SurfaceCapabilities {
+    /// Supported texture formats, preferred first
+    pub formats: Vec<String>,
+    /// Supported present modes; "fifo" is always included
+    pub present_modes: Vec<String>,
+    /// Supported alpha compositing modes
+    pub alpha_modes: Vec<String>,
+}
+
+fn pointer(value: &BigInt, name: &str) -> Result<NonNull<std::ffi::c_void>> {
+    let (_, address, _) = value.get_u64();
+    NonNull::new(address as usize as *mut std::ffi::c_void)
+        .ok_or_else(|| Error::from_reason(format!("Surface {} handle must not be null", name)))
+}
+
+/// Decode a native window handle Buffer such as Electron's
+/// `BrowserWindow.getNativeWindowHandle()`: the handle's bytes in native byte
+/// order, 8 bytes for pointers and 4 or 8 for X11 window ids
+pub(crate) fn native_window_handle(handle: &[u8]) -> Result<u64> {
+    match handle.len() {
+        8 => Ok(u64::from_ne_bytes(handle.try_into().unwrap())),
+        4 => Ok(u32::from_ne_bytes(handle.try_into().unwrap()) as u64),
+        len => Err(Error::from_reason(format!(
+            "Native window handle must be 4 or 8 bytes, got {}",
+            len
+        ))),
+    }
+}
+
+/// Connection to the default X server, opened through a runtime-loaded libX11
+///
+/// Electron only hands out the X11 window id, while Vulkan surfaces also need
+/// a `Display*`. Window ids are global to the server, so a connection of our
+/// own works. It must outlive the surface created with it.
+#[cfg(target_os = "linux")]
+pub(crate) struct XlibDisplay {
+    library: libloading::Library,
+    display: NonNull<std::ffi::c_void>,
+}
+
+#[cfg(target_os = "linux")]
+impl XlibDisplay {
+    pub(crate) fn open() -> Result<Self> {
+        type XOpenDisplay = unsafe extern "C" fn(*const std::ffi::c_char) -> *mut std::ffi::c_void;
+
+        // SAFETY: libX11 has no library constructors with preconditions
+        let library = unsafe { libloading::Library::new("libX11.so.6") }
+            .or_else(|_| unsafe { libloading::Library::new("libX11.so") })
+            .map_err(|e| Error::from_reason(format!("Failed to load libX11: {}", e)))?;
+        // SAFETY: the signature matches Xlib's XOpenDisplay; null selects $DISPLAY
+        let display = unsafe {
+            let open: libloading::Symbol<XOpenDisplay> = library
+                .get(b"XOpenDisplay\0")
+                .map_err(|e| Error::from_reason(format!("Failed to load XOpenDisplay: {}", e)))?;
+            open(std::ptr::null())
+        };
+        let display = NonNull::new(display)
+            .ok_or_else(|| Error::from_reason("Failed to open X display; is DISPLAY set?"))?;
+        Ok(Self { library, display })
+    }
+
+    pub(crate) fn as_ptr(&self) -> NonNull<std::ffi::c_void> {
+        self.display
+    }
+}
+
+// SAFETY: we only close the connection on drop; everything else goes through
+// the wgpu surface created with it, which is itself Send + Sync
+#[cfg(target_os = "linux")]
+unsafe impl Send for XlibDisplay {}
+#[cfg(target_os = "linux")]
+unsafe impl Sync for XlibDisplay {}
+
+#[cfg(target_os = "linux")]
+impl Drop for XlibDisplay {
+    fn drop(&mut self) {
+        type XCloseDisplay = unsafe extern "C" fn(*mut std::ffi::c_void) -> std::ffi::c_int;
+
+        // SAFETY: the display was opened by XOpenDisplay and is closed once
+        unsafe {
+            if let Ok(close) = self.library.get::<XCloseDisplay>(b"XCloseDisplay\0") {
+                close(self.display.as_ptr());
+            }
+        }
+    }
+}
+
+/// Build raw handles for `descriptor`
+pub(crate) fn surface_target(descriptor: &SurfaceDescriptor) -> Result<wgpu::SurfaceTargetUnsafe> {
+    let window = descriptor.window.get_u64().1;
+    let display = descriptor.display.as_ref().map(|display| pointer(display, "display")).transpose()?;
+    let screen = descriptor.screen.unwrap_or(0);
+
+    let (raw_display_handle, raw_window_handle) = match descriptor.platform.as_str() {
+        "win32" => {
+            let hwnd = NonZeroIsize::new(window as isize)
+                .ok_or_else(|| Error::from_reason("Surface window handle must not be null"))?;
+            let mut handle = Win32WindowHandle::new(hwnd);
+            handle.hinstance = descriptor
+                .hinstance
+                .as_ref()
+                .and_then(|hinstance| NonZeroIsize::new(hinstance.get_u64().1 as isize));
+            (
+                RawDisplayHandle::Windows(WindowsDisplayHandle::new()),
+                RawWindowHandle::Win32(handle),
+            )
+        }
+        "appkit" => (
+            RawDisplayHandle::AppKit(AppKitDisplayHandle::new()),
+            RawWindowHandle::AppKit(AppKitWindowHandle::new(pointer(&descriptor.window, "window")?)),
+        ),
+        "xlib" => (
+            RawDisplayHandle::Xlib(XlibDisplayHandle::new(display, screen)),
+            RawWindowHandle::Xlib(XlibWindowHandle::new(window as std::ffi::c_ulong)),
+        ),
+        "xcb" => {
+            let window = u32::try_from(window)
+                .ok()
+                .and_then(NonZeroU32::new)
+                .ok_or_else(|| Error::from_reason("Surface window handle must be a non-zero 32-bit XCB window id"))?;
+            (
+                RawDisplayHandle::Xcb(XcbDisplayHandle::new(display, screen)),
+                RawWindowHandle::Xcb(XcbWindowHandle::new(window)),
+            )
+        }
+        "wayland" => {
+            let display = display.ok_or_else(|| Error::from_reason("Wayland surfaces require a display handle"))?;
+            (
+                RawDisplayHandle::Wayland(WaylandDisplayHandle::new(display)),
+                RawWindowHandle::Wayland(WaylandWindowHandle::new(pointer(&descriptor.window, "window")?)),
+            )
+        }
+        other => {
+            return Err(Error::from_reason(format!(
+                "Unknown surface platform: {}. Use 'win32', 'appkit', 'xlib', 'xcb' or 'wayland'",
+                other
+            )))
+        }
+    };
+
+    Ok(wgpu::SurfaceTargetUnsafe::RawHandle {
+        raw_display_handle,
+        raw_window_handle,
+    })
+}
+
+/// Surface and configuration, shared with a running render loop
+pub(crate) struct SurfaceState {
+    surface: wgpu::Surface<'static>,
+    config: Mutex<SurfaceConfig>,
+    /// X connection opened for the surface; declared after it so it is
+    /// closed only once the surface is gone
+    #[cfg(target_os = "linux")]
+    _display: Option<XlibDisplay>,
+}
+
+#[derive(Default)]
+struct SurfaceConfig {
+    /// Last configuration and its device, reapplied after `resize()` and when
+    /// the surface becomes outdated or lost
+    current: Option<(Arc<wgpu::Device>, wgpu::SurfaceConfiguration)>,
+    /// Set by `resize()`; the surface is reconfigured before the next frame
+    /// is acquired, so a frame still being rendered is never invalidated
+    resized: bool,
+}
+
+impl SurfaceState {
+    fn lock_config(&self) -> Result<std::sync::MutexGuard<'_, SurfaceConfig>> {
+        self.config
+            .lock()
+            .map_err(|_| Error::from_reason("Surface configuration is poisoned"))
+    }
+
+    fn configured(config: &SurfaceConfig) -> Result<&(Arc<wgpu::Device>, wgpu::SurfaceConfiguration)> {
+        config
+            .current
+            .as_ref()
+            .ok_or_else(|| Error::from_reason("Surface must be configured first"))
+    }
+
+    fn reconfigure(&self, config: &mut SurfaceConfig) -> Result<()> {
+        let (device, current) = Self::configured(config)?;
+        self.surface.configure(device, current);
+        config.resized = false;
+        Ok(())
+    }
+
+    /// Device the surface is configured for
+    pub(crate) fn device(&self) -> Result<Arc<wgpu::Device>> {
+        Ok(Self::configured(&*self.lock_config()?)?.0.clone())
+    }
+
+    /// Acquire the next frame, or `None` if none became available in time
+    ///
+    /// An outdated or lost surface (e.g. after the window was resized) is
+    /// reconfigured and acquired again.
+    pub(crate) fn acquire(&self) -> Result<Option<wgpu::SurfaceTexture>> {
+        {
+            let mut config = self.lock_config()?;
+            Self::configured(&config)?;
+            if config.resized {
+                self.reconfigure(&mut config)?;
+            }
+        }
+
+        let texture = match self.surface.get_current_texture() {
+            Err(wgpu::SurfaceError::Outdated | wgpu::SurfaceError::Lost) => {
+                self.reconfigure(&mut *self.lock_config()?)?;
+                self.surface.get_current_texture()
+            }
+            result => result,
+        };
+
+        match texture {
+            Ok(texture) => Ok(Some(texture)),
+            Err(wgpu::SurfaceError::Timeout) => Ok(None),
+            Err(e) => Err(Error::from_reason(format!("Failed to acquire surface texture: {}", e))),
+        }
+    }
+}
+
+/// Presentable surface of a native window
+#[napi]
+pub struct GpuSurface {
+    pub(crate) state: Arc<SurfaceState>,
+}
+
+impl GpuSurface {
+    pub(crate) fn new(surface: wgpu::Surface<'static>) -> Self {
+        Self {
+            state: Arc::new(SurfaceState {
+                surface,
+                config: Mutex::default(),
+                #[cfg(target_os = "linux")]
+                _display: None,
+            }),
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    pub(crate) fn with_display(surface: wgpu::Surface<'static>, display: XlibDisplay) -> Self {
+        Self {
+            state: Arc::new(SurfaceState {
+                surface,
+                config: Mutex::default(),
+                _display: Some(display),
+            }),
+        }
+    }
+
+    pub(crate) fn surface(&self) -> &wgpu::Surface<'static> {
+        &self.state.surface
+    }
+}
+
+#[napi]
+impl GpuSurface {
+    /// Formats, present modes and alpha modes this surface supports on `adapter`
+    #[napi(catch_unwind, js_name = "getCapabilities")]
+    pub fn get_capabilities(&self, adapter: &crate::GpuAdapter) -> SurfaceCapabilities {
+        let capabilities = self.state.surface.get_capabilities(&adapter.adapter);
+        SurfaceCapabilities {
+            formats: capabilities
+                .formats
+                .into_iter()
+                .map(crate::parse::texture_format_name)
+                .collect(),
+            present_modes: capabilities
+                .present_modes
+                .into_iter()
+                .map(|mode| crate::parse::present_mode_name(mode).to_string())
+                .collect(),
+            alpha_modes: capabilities
+                .alpha_modes
+                .into_iter()
+                .map(|mode| crate::parse::alpha_mode_name(mode).to_string())
+                .collect(),
+        }
+    }
+
+    /// Configure the surface for presenting frames rendered by `device`
+    ///
+    /// Throws if the present or alpha mode isn't supported by the surface on
+    /// the device's adapter; the "auto" modes always succeed.
+    #[napi(catch_unwind)]
+    pub fn configure(&self, device: &crate::GpuDevice, configuration: SurfaceConfiguration) -> Result<()> {
+        if configuration.width == 0 || configuration.height == 0 {
+            return Err(Error::from_reason(format!(
+                "Surface size must be non-zero, got {}x{}",
+                configuration.width, configuration.height
+            )));
+        }
+
+        let present_mode = match &configuration.present_mode {
+            Some(mode) => crate::parse::parse_present_mode(mode).ok_or_else(|| {
+                Error::from_reason(format!(
+                    "Unknown present mode: {}. Use 'fifo', 'fifo-relaxed', 'immediate', 'mailbox', 'auto-vsync' or 'auto-no-vsync'",
+                    crate::parse::describe_enum(mode)
+                ))
+            })?,
+            None => wgpu::PresentMode::Fifo,
+        };
+
+        let alpha_mode = match &configuration.alpha_mode {
+            Some(mode) => crate::parse::parse_alpha_mode(mode).ok_or_else(|| {
+                Error::from_reason(format!(
+                    "Unknown alpha mode: {}. Use 'auto', 'opaque', 'premultiplied', 'postmultiplied' or 'inherit'",
+                    crate::parse::describe_enum(mode)
+                ))
+            })?,
+            None => wgpu::CompositeAlphaMode::Auto,
+        };
+
+        let format = crate::parse::parse_texture_format(&configuration.format);
+        let view_formats = configuration
+            .view_formats
+            .iter()
+            .flatten()
+            .map(|value| {
+                let view_format = crate::parse::parse_texture_format(value);
+                // Views may only reinterpret the sRGB-ness of the frame format
+                if view_format.remove_srgb_suffix() == format.remove_srgb_suffix() {
+                    Ok(view_format)
+                } else {
+                    Err(Error::from_reason(format!(
+                        "View format '{}' is not compatible with surface format '{}'; only its sRGB or non-sRGB variant is allowed",
+                        crate::parse::texture_format_name(view_format),
+                        crate::parse::texture_format_name(format)
+                    )))
+                }
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let usage = configuration
+            .usage
+            .as_ref()
+            .map(crate::parse::parse_texture_usage)
+            .transpose()?
+            .unwrap_or(wgpu::TextureUsages::RENDER_ATTACHMENT);
+
+        let capabilities = self.state.surface.get_capabilities(&device.adapter);
+        if capabilities.formats.is_empty() {
+            return Err(Error::from_reason("Surface is not supported by the device's adapter"));
+        }
+        let auto = matches!(present_mode, wgpu::PresentMode::AutoVsync | wgpu::PresentMode::AutoNoVsync);
+        if !auto && !capabilities.present_modes.contains(&present_mode) {
+            let supported: Vec<&str> = capabilities
+                .present_modes
+                .iter()
+                .map(|mode| crate::parse::present_mode_name(*mode))
+                .collect();
+            return Err(Error::from_reason(format!(
+                "Present mode '{}' is not supported by this surface (supported: {})",
+                crate::parse::present_mode_name(present_mode),
+                supported.join(", ")
+            )));
+        }
+        if alpha_mode != wgpu::CompositeAlphaMode::Auto && !capabilities.alpha_modes.contains(&alpha_mode) {
+            let supported: Vec<&str> = capabilities
+                .alpha_modes
+                .iter()
+                .map(|mode| crate::parse::alpha_mode_name(*mode))
+                .collect();
+            return Err(Error::from_reason(format!(
+                "Alpha mode '{}' is not supported by this surface (supported: {})",
+                crate::parse::alpha_mode_name(alpha_mode),
+                supported.join(", ")
+            )));
+        }
+
+        let mut config = self.state.lock_config()?;
+        config.current = Some((
+            device.device.clone(),
+            wgpu::SurfaceConfiguration {
+                usage,
+                format,
+                width: configuration.width,
+                height: configuration.height,
+                present_mode,
+                desired_maximum_frame_latency: 2,
+                alpha_mode,
+                view_formats,
+            },
+        ));
+        self.state.reconfigure(&mut config)
+    }
+
+    /// Reconfigure the surface for a new window size, keeping the other settings
+    ///
+    /// The new size applies from the next acquired frame. A zero width or
+    /// height (a minimized window) is ignored; keep rendering paused until the
+    /// window has a size again.
+    #[napi(catch_unwind)]
+    pub fn resize(&self, width: u32, height: u32) -> Result<()> {
+        if width == 0 || height == 0 {
+            return Ok(());
+        }
+
+        let mut config = self.state.lock_config()?;
+        let Some((_, current)) = config.current.as_mut() else {
+            return Err(Error::from_reason("Surface must be configured first"));
+        };
+        if current.width != width || current.height != height {
+            current.width = width;
+            current.height = height;
+            config.resized = true;
+        }
+        Ok(())
+    }
+
+    /// Acquire the next frame to render into
+    ///
+    /// An outdated or lost surface (e.g. after the window was resized) is
+    /// reconfigured and acquired again. Returns `null` if no frame became
+    /// available in time; skip rendering that frame. Call `present()` on the
+    /// returned texture once the frame's commands have been submitted.
+    #[napi(catch_unwind, js_name = "getCurrentTexture")]
+    pub fn get_current_texture(&self) -> Result<Option<GpuSurfaceTexture>> {
+        Ok(self.state.acquire()?.map(|texture| GpuSurfaceTexture::new(texture, None)))
+    }
+}
+
+/// Frame acquired from a surface
+#[napi]
+pub struct GpuSurfaceTexture {
+    texture: Option<wgpu::SurfaceTexture>,
+    /// Render loop frame; dropping it tells the loop the frame is done
+    frame: Option<mpsc::Sender<()>>,
+}
+
+impl GpuSurfaceTexture {
+    pub(crate) fn new(texture: wgpu::SurfaceTexture, frame: Option<mpsc::Sender<()>>) -> Self {
+        Self {
+            texture: Some(texture),
+            frame,
+        }
+    }
+
+    fn texture(&self) -> Result<&wgpu::SurfaceTexture> {
+        self.texture
+            .as_ref()
+            .ok_or_else(|| Error::from_reason("Surface texture already presented"))
+    }
+}
+
+#[napi]
+impl GpuSurfaceTexture {
+    /// Create a view of this frame, e.g. for a render pass color attachment
+    #[napi(catch_unwind, ts_args_type = "descriptor?: TextureViewDescriptor | string")]
+    pub fn create_view(
+        &self,
+        descriptor: Option<Either<String, crate::TextureViewDescriptor>>,
+    ) -> Result<crate::GpuTextureView> {
+        Ok(crate::texture::create_texture_view(&self.texture()?.texture, descriptor))
+    }
+
+    /// Get texture width
+    #[napi(catch_unwind)]
+    pub fn width(&self) -> Result<u32> {
+        Ok(self.texture()?.texture.width())
+    }
+
+    /// Get texture height
+    #[napi(catch_unwind)]
+    pub fn height(&self) -> Result<u32> {
+        Ok(self.texture()?.texture.height())
+    }
+
+    /// Whether the surface should be reconfigured to present efficiently
+    #[napi(catch_unwind, getter)]
+    pub fn suboptimal(&self) -> Result<bool> {
+        Ok(self.texture()?.suboptimal)
+    }
+
+    /// Whether `present()` was called
+    #[napi(catch_unwind, getter)]
+    pub fn presented(&self) -> bool {
+        self.texture.is_none()
+    }
+
+    /// Present the frame to the window
+    #[napi(catch_unwind)]
+    pub fn present(&mut self) -> Result<()> {
+        self.texture
+            .take()
+            .ok_or_else(|| Error::from_reason("Surface texture already presented"))?
+            .present();
+        self.frame.take();
+        Ok(())
+    }
+}
@@ -0,0 +1,441 @@
+//! Tensors over storage buffers
+//!
+//! A `GpuTensor` describes typed, shaped data in a storage buffer. Slices and
+//! reshapes share the buffer and only change the shape, strides and element
+//! offset, so several tensors can describe parts of one allocation.
+
+use crate::GpuDevice;
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use wgpu::util::DeviceExt;
+
+/// Tensor descriptor
+#[napi(object)]
+pub struct TensorDescriptor {
+    pub label: Option<String>,
+    /// Size of each dimension. Required by `createTensor`; `fromArray`
+    /// defaults to one dimension holding all the data
+    pub shape: Option<Vec<u32>>,
+    /// "f32" (default), "f16", "i32" or "u8"
+    pub dtype: Option<String>,
+    /// Usage added to STORAGE | COPY_SRC | COPY_DST, e.g. VERTEX or ["VERTEX"]
+    pub usage: Option<Either<u32, Vec<String>>>,
+}
+
+/// Element type of a tensor
+#[derive(Clone, Copy, PartialEq)]
+enum Dtype {
+    F32,
+    F16,
+    I32,
+    U8,
+}
+
+impl Dtype {
+    fn parse(name: Option<&str>) -> Result<Self> {
+        match name {
+            None | Some("f32") => Ok(Self::F32),
+            Some("f16") => Ok(Self::F16),
+            Some("i32") => Ok(Self::I32),
+            Some("u8") => Ok(Self::U8),
+            Some(other) => Err(Error::from_reason(format!(
+                "Unknown tensor dtype: {}. Use 'f32', 'f16', 'i32' or 'u8'",
+                other
+            ))),
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::F32 => "f32",
+            Self::F16 => "f16",
+            Self::I32 => "i32",
+            Self::U8 => "u8",
+        }
+    }
+
+    fn size(self) -> u64 {
+        match self {
+            Self::F32 | Self::I32 => 4,
+            Self::F16 => 2,
+            Self::U8 => 1,
+        }
+    }
+
+    /// Little-endian bytes of `value` converted to this type
+    fn encode(self, value: f64, bytes: &mut Vec<u8>) {
+        match self {
+            Self::F32 => bytes.extend((value as f32).to_le_bytes()),
+            Self::F16 => bytes.extend(crate::half::f32_to_f16(value as f32).to_le_bytes()),
+            Self::I32 => bytes.extend((value as i32).to_le_bytes()),
+            Self::U8 => bytes.push(value as u8),
+        }
+    }
+}
+
+/// Row-major strides of `shape`, in elements
+fn contiguous_strides(shape: &[u32]) -> Vec<u32> {
+    let mut strides = vec![1; shape.len()];
+    for i in (0..shape.len().saturating_sub(1)).rev() {
+        strides[i] = strides[i + 1] * shape[i + 1];
+    }
+    strides
+}
+
+/// Number of elements of `shape`, which must fit a u32
+fn element_count(shape: &[u32]) -> Result<u32> {
+    shape
+        .iter()
+        .try_fold(1u32, |count, &size| count.checked_mul(size))
+        .ok_or_else(|| Error::from_reason(format!("Tensor shape {:?} has too many elements", shape)))
+}
+
+/// Shaped, typed view of a storage buffer
+///
+/// Elements are laid out by `strides` (in elements) starting at `offset`.
+/// Tensors created by `createTensor` and `fromArray` are row-major and
+/// contiguous; `slice()` returns views into the same buffer.
+#[napi]
+pub struct GpuTensor {
+    buffer: crate::GpuBuffer,
+    dtype: Dtype,
+    shape: Vec<u32>,
+    strides: Vec<u32>,
+    offset: u32,
+}
+
+impl GpuTensor {
+    /// Create a contiguous tensor over a new buffer holding `contents`, or zeros
+    fn create(
+        env: &mut Env,
+        method: &str,
+        device: &GpuDevice,
+        descriptor: TensorDescriptor,
+        shape: Vec<u32>,
+        dtype: Dtype,
+        contents: Option<Vec<u8>>,
+    ) -> Result<Self> {
+        let size = (element_count(&shape)? as u64 * dtype.size()).next_multiple_of(4).max(4);
+        let usage = wgpu::BufferUsages::STORAGE
+            | wgpu::BufferUsages::COPY_SRC
+            | wgpu::BufferUsages::COPY_DST
+            | descriptor
+                .usage
+                .as_ref()
+                .map(crate::parse::parse_buffer_usage)
+                .transpose()?
+                .unwrap_or(wgpu::BufferUsages::empty());
+        let label = descriptor.label.as_deref();
+        let buffer = device.errors.check(
+            method,
+            || {
+                format!(
+                    "label: {}, shape: {:?}, dtype: {}",
+                    crate::error::describe_label(label),
+                    shape,
+                    dtype.name()
+                )
+            },
+            || match contents {
+                Some(mut contents) => {
+                    contents.resize(size as usize, 0);
+                    device.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                        label,
+                        contents: &contents,
+                        usage,
+                    })
+                }
+                None => device.device.create_buffer(&wgpu::BufferDescriptor {
+                    label,
+                    size,
+                    usage,
+                    mapped_at_creation: false,
+                }),
+            },
+        )?;
+        let buffer = crate::GpuBuffer::new(
+            buffer,
+            descriptor.label,
+            device.device.clone(),
+            device.queue_internal.clone(),
+            device.poller.clone(),
+        )
+        .track_memory(env)?;
+        Ok(Self {
+            buffer,
+            dtype,
+            strides: contiguous_strides(&shape),
+            shape,
+            offset: 0,
+        })
+    }
+
+    fn is_contiguous(&self) -> bool {
+        let expected = contiguous_strides(&self.shape);
+        self.shape
+            .iter()
+            .zip(self.strides.iter().zip(&expected))
+            .all(|(&size, (stride, expected))| size == 1 || stride == expected)
+    }
+
+    /// Buffer offset of every element, in row-major order of the view
+    fn element_offsets(&self) -> Vec<u64> {
+        let mut offsets = vec![self.offset as u64];
+        for (&size, &stride) in self.shape.iter().zip(&self.strides) {
+            offsets = offsets
+                .iter()
+                .flat_map(|&base| (0..size as u64).map(move |i| base + i * stride as u64))
+                .collect();
+        }
+        offsets
+    }
+
+    /// Typed array of little-endian elements; f16 is widened to f32
+    fn typed_array(&self, bytes: Vec<u8>) -> Either3<Float32Array, Int32Array, Uint8Array> {
+        match self.dtype {
+            Dtype::F32 => Either3::A(Float32Array::new(
+                bytes.chunks_exact(4).map(|b| f32::from_le_bytes(b.try_into().unwrap())).collect(),
+            )),
+            Dtype::F16 => Either3::A(Float32Array::new(crate::half::unpack(&bytes))),
+            Dtype::I32 => Either3::B(Int32Array::new(
+                bytes.chunks_exact(4).map(|b| i32::from_le_bytes(b.try_into().unwrap())).collect(),
+            )),
+            Dtype::U8 => Either3::C(Uint8Array::new(bytes)),
+        }
+    }
+
+    /// View with the same buffer and dtype
+    fn view(&self, shape: Vec<u32>, strides: Vec<u32>, offset: u32) -> Self {
+        Self {
+            buffer: self.buffer.clone(),
+            dtype: self.dtype,
+            shape,
+            strides,
+            offset,
+        }
+    }
+}
+
+#[napi]
+impl GpuDevice {
+    /// Create a zero-filled tensor (extension)
+    #[napi(catch_unwind, js_name = "createTensor")]
+    pub fn create_tensor(&self, mut env: Env, descriptor: TensorDescriptor) -> Result<GpuTensor> {
+        let Some(shape) = descriptor.shape.clone() else {
+            return Err(Error::from_reason("createTensor requires a shape"));
+        };
+        let dtype = Dtype::parse(descriptor.dtype.as_deref())?;
+        GpuTensor::create(&mut env, "createTensor", self, descriptor, shape, dtype, None)
+    }
+}
+
+#[napi]
+impl GpuTensor {
+    /// Create a tensor holding `data` (extension)
+    ///
+    /// `data` is either raw little-endian elements of the dtype or numbers,
+    /// which are converted to it. The `GpuTensor` wrapper accepts typed
+    /// arrays and picks between the two.
+    #[napi(catch_unwind, factory, js_name = "fromArray")]
+    pub fn from_array(
+        mut env: Env,
+        device: &GpuDevice,
+        data: Either<Buffer, Vec<f64>>,
+        descriptor: Option<TensorDescriptor>,
+    ) -> Result<Self> {
+        let descriptor = descriptor.unwrap_or(TensorDescriptor {
+            label: None,
+            shape: None,
+            dtype: None,
+            usage: None,
+        });
+        let dtype = Dtype::parse(descriptor.dtype.as_deref())?;
+        let contents = match data {
+            Either::A(bytes) => {
+                if bytes.len() as u64 % dtype.size() != 0 {
+                    return Err(Error::from_reason(format!(
+                        "Tensor data of {} bytes isn't a whole number of {} elements",
+                        bytes.len(),
+                        dtype.name()
+                    )));
+                }
+                bytes.to_vec()
+            }
+            Either::B(values) => {
+                let mut bytes = Vec::with_capacity(values.len() * dtype.size() as usize);
+                for value in values {
+                    dtype.encode(value, &mut bytes);
+                }
+                bytes
+            }
+        };
+        let count = contents.len() as u64 / dtype.size();
+        let shape = match descriptor.shape.clone() {
+            Some(shape) => {
+                if element_count(&shape)? as u64 != count {
+                    return Err(Error::from_reason(format!(
+                        "Tensor shape {:?} holds {} elements but the data has {}",
+                        shape,
+                        element_count(&shape)?,
+                        count
+                    )));
+                }
+                shape
+            }
+            None => vec![u32::try_from(count).map_err(|_| Error::from_reason("Tensor data has too many elements"))?],
+        };
+        Self::create(&mut env, "fromArray", device, descriptor, shape, dtype, Some(contents))
+    }
+
+    /// Size of each dimension
+    #[napi(catch_unwind, getter)]
+    pub fn shape(&self) -> Vec<u32> {
+        self.shape.clone()
+    }
+
+    /// Elements between neighbors along each dimension
+    #[napi(catch_unwind, getter)]
+    pub fn strides(&self) -> Vec<u32> {
+        self.strides.clone()
+    }
+
+    /// "f32", "f16", "i32" or "u8"
+    #[napi(catch_unwind, getter)]
+    pub fn dtype(&self) -> String {
+        self.dtype.name().to_string()
+    }
+
+    /// Number of elements
+    #[napi(catch_unwind, getter)]
+    pub fn size(&self) -> u32 {
+        self.shape.iter().product()
+    }
+
+    /// Offset of the first element in the buffer, in bytes
+    #[napi(catch_unwind, getter, js_name = "byteOffset")]
+    pub fn byte_offset(&self) -> f64 {
+        (self.offset as u64 * self.dtype.size()) as f64
+    }
+
+    /// Whether the elements are packed in row-major order
+    #[napi(catch_unwind, getter, js_name = "isContiguous")]
+    pub fn contiguous(&self) -> bool {
+        self.is_contiguous()
+    }
+
+    /// The storage buffer holding the elements, shared with other views
+    #[napi(catch_unwind, getter)]
+    pub fn buffer(&self) -> crate::GpuBuffer {
+        self.buffer.clone()
+    }
+
+    /// View of `start..end` along `dimension`, sharing the buffer
+    ///
+    /// `end` defaults to the size of the dimension. Slicing any but the first
+    /// dimension gives a non-contiguous view.
+    #[napi(catch_unwind)]
+    pub fn slice(&self, dimension: u32, start: u32, end: Option<u32>) -> Result<GpuTensor> {
+        let dimension = dimension as usize;
+        let Some(&size) = self.shape.get(dimension) else {
+            return Err(Error::from_reason(format!(
+                "Tensor slice dimension {} is out of range for {} dimensions",
+                dimension,
+                self.shape.len()
+            )));
+        };
+        let end = end.unwrap_or(size);
+        if start > end || end > size {
+            return Err(Error::from_reason(format!(
+                "Tensor slice {}..{} is out of range for dimension {} of size {}",
+                start, end, dimension, size
+            )));
+        }
+        let mut shape = self.shape.clone();
+        shape[dimension] = end - start;
+        Ok(self.view(shape, self.strides.clone(), self.offset + start * self.strides[dimension]))
+    }
+
+    /// View with a new shape of the same number of elements, sharing the buffer
+    ///
+    /// One dimension may be -1 to infer its size. Non-contiguous slices can't
+    /// be reshaped.
+    #[napi(catch_unwind)]
+    pub fn reshape(&self, shape: Vec<i64>) -> Result<GpuTensor> {
+        if !self.is_contiguous() {
+            return Err(Error::from_reason("Cannot reshape a non-contiguous tensor view"));
+        }
+        let count = self.size();
+        let invalid = || Error::from_reason(format!("Cannot reshape {:?} to {:?}", self.shape, shape));
+        let inferred = match shape.iter().filter(|&&size| size == -1).count() {
+            0 => 0,
+            1 => {
+                let known = shape.iter().filter(|&&size| size != -1).product::<i64>();
+                if known <= 0 || count as i64 % known != 0 {
+                    return Err(invalid());
+                }
+                count as i64 / known
+            }
+            _ => return Err(invalid()),
+        };
+        let shape = shape
+            .iter()
+            .map(|&size| u32::try_from(if size == -1 { inferred } else { size }))
+            .collect::<std::result::Result<Vec<u32>, _>>()
+            .map_err(|_| invalid())?;
+        if element_count(&shape)? != count {
+            return Err(invalid());
+        }
+        let strides = contiguous_strides(&shape);
+        Ok(self.view(shape, strides, self.offset))
+    }
+
+    /// Read the elements back in row-major order
+    ///
+    /// Resolves to a Float32Array for f32 and f16, an Int32Array for i32 and
+    /// a Uint8Array for u8 tensors.
+    #[napi(catch_unwind, js_name = "toArray")]
+    pub async fn to_array(&self) -> Result<Either3<Float32Array, Int32Array, Uint8Array>> {
+        let element_size = self.dtype.size();
+        let offsets = self.element_offsets();
+        let (Some(&first), Some(&last)) = (offsets.iter().min(), offsets.iter().max()) else {
+            return Ok(self.typed_array(Vec::new()));
+        };
+        // Copies are 4-byte aligned; the buffer size is a multiple of 4
+        let start = (first * element_size) / 4 * 4;
+        let end = ((last + 1) * element_size).next_multiple_of(4);
+
+        let buffer = &self.buffer;
+        let readback = buffer.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("tensor readback"),
+            size: end - start,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let mut encoder = buffer
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: Some("tensor readback") });
+        encoder.copy_buffer_to_buffer(&buffer.buffer, start, &readback, 0, end - start);
+        buffer.queue.submit(Some(encoder.finish()));
+
+        let slice = readback.slice(..);
+        let _polling = buffer.poller.track();
+        let (sender, receiver) = futures::channel::oneshot::channel();
+        slice.map_async(wgpu::MapMode::Read, move |result| {
+            let _ = sender.send(result);
+        });
+        receiver
+            .await
+            .map_err(|_| Error::from_reason("Failed to receive map result"))?
+            .map_err(|e| Error::from_reason(format!("Failed to read tensor: {:?}", e)))?;
+
+        let mapped = slice.get_mapped_range();
+        let mut bytes = Vec::with_capacity(offsets.len() * element_size as usize);
+        for offset in offsets {
+            let at = (offset * element_size - start) as usize;
+            bytes.extend_from_slice(&mapped[at..at + element_size as usize]);
+        }
+        drop(mapped);
+        readback.unmap();
+        Ok(self.typed_array(bytes))
+    }
+}
@@ -0,0 +1,458 @@
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use std::collections::HashMap;
+use std::sync::Arc;
+use wgpu::util::DeviceExt;
+
+use crate::font::Font;
+use crate::render_pass::RenderCommand;
+use crate::util;
+
+/// Width and height of the glyph atlas texture
+const ATLAS_SIZE: u32 = 1024;
+
+/// Texels left empty around each glyph so filtering doesn't bleed between them
+const GLYPH_PADDING: u32 = 1;
+
+/// Floats per vertex: position, atlas coordinates and color
+const VERTEX_FLOATS: usize = 8;
+
+/// Font descriptor
+#[napi(object)]
+pub struct FontDescriptor {
+    pub label: Option<String>,
+    /// Default text size in pixels per em (default 16)
+    pub size: Option<f64>,
+}
+
+/// Options for `drawText` and `measureText`
+#[napi(object)]
+pub struct TextOptions {
+    /// Size in pixels per em (default: the font's size)
+    pub size: Option<f64>,
+    /// Color as [r, g, b, a?] from 0 to 1 (default white)
+    pub color: Option<Vec<f64>>,
+}
+
+/// Size of laid out text in pixels
+#[napi(object)]
+pub struct TextMetrics {
+    /// Advance of the widest line
+    pub width: f64,
+    /// Line height times the number of lines
+    pub height: f64,
+}
+
+/// Where a rasterized glyph is in the atlas
+#[derive(Clone, Copy)]
+struct AtlasGlyph {
+    left: i32,
+    top: i32,
+    width: u32,
+    height: u32,
+    x: u32,
+    y: u32,
+}
+
+/// Render pipeline compatibility: color formats, sample count and depth format
+type PassKey = (Vec<Option<wgpu::TextureFormat>>, u32, Option<wgpu::TextureFormat>);
+
+fn validate_size(size: f64) -> Result<f32> {
+    if !(1.0..=256.0).contains(&size) {
+        return Err(Error::from_reason(format!(
+            "Font size must be between 1 and 256 pixels, got {}",
+            size
+        )));
+    }
+    Ok(size as f32)
+}
+
+/// TrueType font drawn from a glyph atlas
+///
+/// Glyphs are rasterized on first use at each size and packed into a 1024x1024
+/// `r8unorm` atlas texture.
+#[napi]
+pub struct GpuFont {
+    device: Arc<wgpu::Device>,
+    queue: Arc<wgpu::Queue>,
+    label: String,
+    font: Font,
+    size: f32,
+    module: wgpu::ShaderModule,
+    bind_group_layout: wgpu::BindGroupLayout,
+    layout: wgpu::PipelineLayout,
+    pipelines: HashMap<PassKey, Arc<wgpu::RenderPipeline>>,
+    sampler: wgpu::Sampler,
+    atlas: wgpu::Texture,
+    atlas_view: wgpu::TextureView,
+    /// Glyphs by index and size bits; None for glyphs without ink
+    glyphs: HashMap<(u16, u32), Option<AtlasGlyph>>,
+    /// Shelf packing state: the next free position and the current row's height
+    cursor: (u32, u32),
+    row_height: u32,
+}
+
+impl GpuFont {
+    pub(crate) fn new(
+        device: Arc<wgpu::Device>,
+        queue: Arc<wgpu::Queue>,
+        data: Vec<u8>,
+        descriptor: Option<FontDescriptor>,
+    ) -> Result<Self> {
+        let (label, size) = match descriptor {
+            Some(d) => (d.label, d.size),
+            None => (None, None),
+        };
+        let label = label.unwrap_or_else(|| "font".to_string());
+        let size = validate_size(size.unwrap_or(16.0))?;
+        let font = Font::parse(data)?;
+
+        let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some(&label),
+            source: wgpu::ShaderSource::Wgsl(include_str!("shaders/text.wgsl").into()),
+        });
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some(&label),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::VERTEX,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+            ],
+        });
+        let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some(&label),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some(&label),
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+        let atlas = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some(&format!("{} atlas", label)),
+            size: wgpu::Extent3d {
+                width: ATLAS_SIZE,
+                height: ATLAS_SIZE,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::R8Unorm,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        });
+        let atlas_view = atlas.create_view(&Default::default());
+
+        Ok(Self {
+            device,
+            queue,
+            label,
+            font,
+            size,
+            module,
+            bind_group_layout,
+            layout,
+            pipelines: HashMap::new(),
+            sampler,
+            atlas,
+            atlas_view,
+            glyphs: HashMap::new(),
+            cursor: (0, 0),
+            row_height: 0,
+        })
+    }
+
+    fn text_size(&self, options: Option<&TextOptions>) -> Result<f32> {
+        match options.and_then(|o| o.size) {
+            Some(size) => validate_size(size),
+            None => Ok(self.size),
+        }
+    }
+
+    fn line_height(&self, size: f32) -> f32 {
+        (self.font.ascent - self.font.descent + self.font.line_gap) * size / self.font.units_per_em
+    }
+
+    /// Look up a glyph at `size`, rasterizing and uploading it on first use
+    fn glyph(&mut self, glyph: u16, size: f32) -> Result<Option<AtlasGlyph>> {
+        if let Some(entry) = self.glyphs.get(&(glyph, size.to_bits())) {
+            return Ok(*entry);
+        }
+        let entry = match self.font.rasterize(glyph, size) {
+            None => None,
+            Some(bitmap) => {
+                let (width, height) = (bitmap.width + GLYPH_PADDING, bitmap.height + GLYPH_PADDING);
+                if self.cursor.0 + width > ATLAS_SIZE {
+                    self.cursor = (0, self.cursor.1 + self.row_height);
+                    self.row_height = 0;
+                }
+                if self.cursor.1 + height > ATLAS_SIZE {
+                    return Err(Error::from_reason(format!(
+                        "Glyph atlas of font \"{}\" is full; draw with fewer sizes or create another font",
+                        self.label
+                    )));
+                }
+                let (x, y) = (self.cursor.0 + GLYPH_PADDING, self.cursor.1 + GLYPH_PADDING);
+                self.cursor.0 += width;
+                self.row_height = self.row_height.max(height);
+
+                self.queue.write_texture(
+                    wgpu::ImageCopyTexture {
+                        texture: &self.atlas,
+                        mip_level: 0,
+                        origin: wgpu::Origin3d { x, y, z: 0 },
+                        aspect: wgpu::TextureAspect::All,
+                    },
+                    &bitmap.coverage,
+                    wgpu::ImageDataLayout {
+                        offset: 0,
+                        bytes_per_row: Some(bitmap.width),
+                        rows_per_image: Some(bitmap.height),
+                    },
+                    wgpu::Extent3d {
+                        width: bitmap.width,
+                        height: bitmap.height,
+                        depth_or_array_layers: 1,
+                    },
+                );
+                Some(AtlasGlyph {
+                    left: bitmap.left,
+                    top: bitmap.top,
+                    width: bitmap.width,
+                    height: bitmap.height,
+                    x,
+                    y,
+                })
+            }
+        };
+        self.glyphs.insert((glyph, size.to_bits()), entry);
+        Ok(entry)
+    }
+
+    /// Lay out `text` with its first line's top left at (x, y), returning vertices
+    fn layout(&mut self, text: &str, x: f32, y: f32, size: f32, color: [f32; 4]) -> Result<Vec<f32>> {
+        let scale = size / self.font.units_per_em;
+        let line_height = self.line_height(size);
+        let mut vertices = Vec::new();
+        let mut baseline = (y + self.font.ascent * scale).round();
+        let mut pen = x;
+        for character in text.chars() {
+            if character == '\n' {
+                pen = x;
+                baseline += line_height.round();
+                continue;
+            }
+            let index = self.font.glyph_index(character);
+            if let Some(glyph) = self.glyph(index, size)? {
+                // Whole pixels keep glyphs drawn at their rasterized size sharp
+                let x0 = pen.round() + glyph.left as f32;
+                let y0 = baseline + glyph.top as f32;
+                let (x1, y1) = (x0 + glyph.width as f32, y0 + glyph.height as f32);
+                let atlas = ATLAS_SIZE as f32;
+                let u0 = glyph.x as f32 / atlas;
+                let v0 = glyph.y as f32 / atlas;
+                let u1 = (glyph.x + glyph.width) as f32 / atlas;
+                let v1 = (glyph.y + glyph.height) as f32 / atlas;
+                for (px, py, u, v) in [
+                    (x0, y0, u0, v0),
+                    (x1, y0, u1, v0),
+                    (x1, y1, u1, v1),
+                    (x0, y0, u0, v0),
+                    (x1, y1, u1, v1),
+                    (x0, y1, u0, v1),
+                ] {
+                    vertices.extend([px, py, u, v]);
+                    vertices.extend(color);
+                }
+            }
+            pen += self.font.advance(index) * scale;
+        }
+        Ok(vertices)
+    }
+
+    fn pipeline(&mut self, key: PassKey) -> Arc<wgpu::RenderPipeline> {
+        let (device, module, layout, label) = (&self.device, &self.module, &self.layout, &self.label);
+        self.pipelines
+            .entry(key)
+            .or_insert_with_key(|(formats, sample_count, depth_format)| {
+                // Text goes to the first color attachment; the others are left alone
+                let mut first = true;
+                let targets: Vec<Option<wgpu::ColorTargetState>> = formats
+                    .iter()
+                    .map(|format| {
+                        format.map(|format| {
+                            let write_mask = if first { wgpu::ColorWrites::ALL } else { wgpu::ColorWrites::empty() };
+                            first = false;
+                            wgpu::ColorTargetState {
+                                format,
+                                blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                                write_mask,
+                            }
+                        })
+                    })
+                    .collect();
+                Arc::new(device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                    label: Some(label),
+                    layout: Some(layout),
+                    vertex: wgpu::VertexState {
+                        module,
+                        entry_point: "vs_main",
+                        buffers: &[wgpu::VertexBufferLayout {
+                            array_stride: (VERTEX_FLOATS * 4) as u64,
+                            step_mode: wgpu::VertexStepMode::Vertex,
+                            attributes: &wgpu::vertex_attr_array![0 => Float32x2, 1 => Float32x2, 2 => Float32x4],
+                        }],
+                    },
+                    fragment: Some(wgpu::FragmentState {
+                        module,
+                        entry_point: "fs_main",
+                        targets: &targets,
+                    }),
+                    primitive: wgpu::PrimitiveState::default(),
+                    // Text overlays everything and leaves depth and stencil unchanged
+                    depth_stencil: depth_format.map(|format| wgpu::DepthStencilState {
+                        format,
+                        depth_write_enabled: false,
+                        depth_compare: wgpu::CompareFunction::Always,
+                        stencil: Default::default(),
+                        bias: Default::default(),
+                    }),
+                    multisample: wgpu::MultisampleState {
+                        count: *sample_count,
+                        ..Default::default()
+                    },
+                    multiview: None,
+                }))
+            })
+            .clone()
+    }
+}
+
+#[napi]
+impl GpuFont {
+    /// Default text size in pixels per em
+    #[napi(catch_unwind, getter)]
+    pub fn size(&self) -> f64 {
+        self.size as f64
+    }
+
+    /// Distance between baselines at the default size, in pixels
+    #[napi(catch_unwind, getter, js_name = "lineHeight")]
+    pub fn line_height_getter(&self) -> f64 {
+        self.line_height(self.size) as f64
+    }
+
+    /// Measure `text` without drawing it
+    #[napi(catch_unwind, js_name = "measureText")]
+    pub fn measure_text(&self, text: String, options: Option<TextOptions>) -> Result<TextMetrics> {
+        let size = self.text_size(options.as_ref())?;
+        let scale = size / self.font.units_per_em;
+        let widths: Vec<f32> = text
+            .split('\n')
+            .map(|line| line.chars().map(|c| self.font.advance(self.font.glyph_index(c)) * scale).sum())
+            .collect();
+        Ok(TextMetrics {
+            width: widths.iter().copied().fold(0.0, f32::max) as f64,
+            height: (widths.len() as f32 * self.line_height(size)) as f64,
+        })
+    }
+
+    /// Record drawing `text` into a render pass
+    ///
+    /// (x, y) is the top left of the first line in pixels of the pass's
+    /// attachments, and `\n` starts a new line. Text is alpha blended into
+    /// the first color attachment, ignoring depth. This sets the pass's
+    /// pipeline, bind group 0 and vertex buffer 0, so set them again before
+    /// drawing anything else.
+    #[napi(catch_unwind, js_name = "drawText")]
+    pub fn draw_text(
+        &mut self,
+        pass: &mut crate::GpuRenderPassEncoder,
+        text: String,
+        x: f64,
+        y: f64,
+        options: Option<TextOptions>,
+    ) -> Result<()> {
+        let size = self.text_size(options.as_ref())?;
+        let color = crate::draw2d::parse_color(options.as_ref().and_then(|o| o.color.as_deref()), "drawText color")?;
+        let Some(target) = pass.color_attachments.iter().flatten().next() else {
+            return Err(Error::from_reason("drawText needs a render pass with a color attachment"));
+        };
+        let (width, height) = target.size;
+        let key = (
+            pass.color_attachments.iter().map(|a| a.as_ref().map(|a| a.format)).collect(),
+            target.sample_count,
+            pass.depth_stencil_attachment.as_ref().map(|d| d.format),
+        );
+
+        let vertices = self.layout(&text, x as f32, y as f32, size, color)?;
+        if vertices.is_empty() {
+            return Ok(());
+        }
+        let pipeline = self.pipeline(key);
+        let viewport = util::create_params_buffer(
+            &self.device,
+            &format!("{} viewport", self.label),
+            &[(width as f32).to_bits(), (height as f32).to_bits()],
+        );
+        let vertex_buffer = self.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some(&format!("{} vertices", self.label)),
+            contents: &vertices.iter().flat_map(|v| v.to_le_bytes()).collect::<Vec<u8>>(),
+            usage: wgpu::BufferUsages::VERTEX,
+        });
+        let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some(&self.label),
+            layout: &self.bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: viewport.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Sampler(&self.sampler),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: wgpu::BindingResource::TextureView(&self.atlas_view),
+                },
+            ],
+        });
+        let count = (vertices.len() / VERTEX_FLOATS) as u32;
+        pass.record_all(vec![
+            RenderCommand::SetPipeline(pipeline),
+            RenderCommand::SetBindGroup(0, Arc::new(bind_group), Vec::new()),
+            RenderCommand::SetVertexBuffer(0, Arc::new(vertex_buffer), 0, None),
+            RenderCommand::Draw {
+                vertices: 0..count,
+                instances: 0..1,
+            },
+        ])
+    }
+}
@@ -1,4 +1,6 @@
+use napi::bindgen_prelude::*;
 use napi_derive::napi;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
 /// Texture descriptor
@@ -8,65 +10,535 @@ pub struct TextureDescriptor {
     pub width: u32,
     pub height: u32,
     pub depth: Option<u32>,
-    pub format: String,
-    pub usage: u32,
-    pub dimension: Option<String>,
+    pub format: Either<String, u32>,
+    /// GPUTextureUsage bit mask or flag names, e.g. ["TEXTURE_BINDING", "COPY_DST"]
+    pub usage: Either<u32, Vec<String>>,
+    pub dimension: Option<Either<String, u32>>,
     pub mip_level_count: Option<u32>,
     pub sample_count: Option<u32>,
+    /// Initial contents, uploaded right after creation (extension). Without
+    /// `dataLayout` they are tightly packed and fill mip level 0 or every mip
+    /// level, layer by layer; COPY_DST usage is added implicitly
+    pub data: Option<Buffer>,
+    /// Layout of `data`, which then fills mip level 0 (extension)
+    pub data_layout: Option<crate::TextureDataLayout>,
+}
+
+/// Tightly packed bytes per row and rows of mip level `mip`, with the extent
+/// of one write: a single layer, or the whole depth of a 3D texture
+fn packed_level_layout(descriptor: &wgpu::TextureDescriptor, block_size: u32, mip: u32) -> (wgpu::Extent3d, u32, u32) {
+    let mut size = descriptor.mip_level_size(mip).unwrap_or_default();
+    if descriptor.dimension != wgpu::TextureDimension::D3 {
+        size.depth_or_array_layers = 1;
+    }
+    let (block_width, block_height) = descriptor.format.block_dimensions();
+    let physical = size.physical_size(descriptor.format);
+    (size, physical.width / block_width * block_size, physical.height / block_height)
+}
+
+/// Check the initial `data` of a texture and return how many mip levels it fills
+///
+/// Tightly packed data fills mip level 0 or all mip levels; with a layout it
+/// fills mip level 0 and `writeTexture` rules apply.
+pub(crate) fn initial_data_mip_levels(
+    descriptor: &wgpu::TextureDescriptor,
+    data: &[u8],
+    layout: Option<&crate::TextureDataLayout>,
+) -> Result<u32> {
+    if layout.is_some() {
+        return Ok(1);
+    }
+    let block_size = descriptor.format.block_copy_size(None).ok_or_else(|| {
+        Error::from_reason(format!(
+            "createTexture data isn't supported for {:?} textures",
+            descriptor.format
+        ))
+    })?;
+    let level_bytes = |mip| {
+        let (size, bytes_per_row, rows) = packed_level_layout(descriptor, block_size, mip);
+        bytes_per_row as usize * rows as usize * size.depth_or_array_layers as usize * descriptor.array_layer_count() as usize
+    };
+    let base = level_bytes(0);
+    let full: usize = (0..descriptor.mip_level_count).map(level_bytes).sum();
+    if data.len() == full {
+        Ok(descriptor.mip_level_count)
+    } else if data.len() == base {
+        Ok(1)
+    } else {
+        Err(Error::from_reason(format!(
+            "createTexture data is {} bytes, expected {} for mip level 0 or {} for all {} mip levels",
+            data.len(),
+            base,
+            full,
+            descriptor.mip_level_count
+        )))
+    }
+}
+
+/// Write the initial `data` of a new texture into its first `mip_levels` levels
+///
+/// Tightly packed data goes layer by layer, each with all its mip levels, as
+/// in DDS files.
+pub(crate) fn write_initial_data(
+    queue: &wgpu::Queue,
+    texture: &wgpu::Texture,
+    descriptor: &wgpu::TextureDescriptor,
+    data: &[u8],
+    layout: Option<&crate::TextureDataLayout>,
+    mip_levels: u32,
+) {
+    if let Some(layout) = layout {
+        queue.write_texture(
+            texture.as_image_copy(),
+            data,
+            wgpu::ImageDataLayout {
+                offset: layout.offset.unwrap_or(0) as u64,
+                bytes_per_row: layout.bytes_per_row,
+                rows_per_image: layout.rows_per_image,
+            },
+            descriptor.size,
+        );
+        return;
+    }
+
+    let block_size = descriptor.format.block_copy_size(None).unwrap_or(4);
+    let mut offset = 0;
+    for layer in 0..descriptor.array_layer_count() {
+        for mip in 0..mip_levels {
+            let (size, bytes_per_row, rows) = packed_level_layout(descriptor, block_size, mip);
+            let end = offset + bytes_per_row as usize * rows as usize * size.depth_or_array_layers as usize;
+            queue.write_texture(
+                wgpu::ImageCopyTexture {
+                    texture,
+                    mip_level: mip,
+                    origin: wgpu::Origin3d { x: 0, y: 0, z: layer },
+                    aspect: wgpu::TextureAspect::All,
+                },
+                &data[offset..end],
+                wgpu::ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: Some(bytes_per_row),
+                    rows_per_image: Some(rows),
+                },
+                size,
+            );
+            offset = end;
+        }
+    }
+}
+
+/// Why a `format` texture with `usage` can't be created on a device with
+/// `features`, naming the missing feature, or None when it needs none
+///
+/// Compressed formats need their texture-compression feature (ASTC HDR its
+/// own), and rg11b10ufloat render attachments "rg11b10ufloat-renderable"
+/// unless the adapter's format features (`allowed_usages`) permit them.
+pub(crate) fn format_feature_error(
+    format: wgpu::TextureFormat,
+    usage: wgpu::TextureUsages,
+    allowed_usages: wgpu::TextureUsages,
+    features: wgpu::Features,
+) -> Option<String> {
+    let missing = format.required_features() - features;
+    let missing = if !missing.is_empty() {
+        missing
+    } else if format == wgpu::TextureFormat::Rg11b10Float
+        && usage.contains(wgpu::TextureUsages::RENDER_ATTACHMENT)
+        && !allowed_usages.contains(wgpu::TextureUsages::RENDER_ATTACHMENT)
+    {
+        wgpu::Features::RG11B10UFLOAT_RENDERABLE
+    } else {
+        return None;
+    };
+    Some(format!(
+        "format '{}' needs the '{}' feature",
+        crate::parse::texture_format_name(format),
+        crate::features::feature_names(missing).join("', '")
+    ))
+}
+
+/// Texture view descriptor
+#[napi(object)]
+pub struct TextureViewDescriptor {
+    pub label: Option<String>,
+    pub format: Option<Either<String, u32>>,
+    pub dimension: Option<Either<String, u32>>,
+    /// "all" (default), "depth-only" or "stencil-only"
+    pub aspect: Option<Either<String, u32>>,
+    pub base_mip_level: Option<u32>,
+    pub mip_level_count: Option<u32>,
+    pub base_array_layer: Option<u32>,
+    pub array_layer_count: Option<u32>,
+}
+
+/// Options for `createLayerView`
+#[napi(object)]
+pub struct LayerViewOptions {
+    pub label: Option<String>,
+    pub mip_level: Option<u32>,
+    /// "all" (default), "depth-only" or "stencil-only"
+    pub aspect: Option<Either<String, u32>>,
 }
 
 /// GPU texture - multi-dimensional image data on the GPU
 ///
 /// Textures store image data for rendering and compute operations.
 /// Create views to bind textures to shaders.
-#[napi]
+#[napi(custom_finalize)]
+#[derive(Clone)]
 pub struct GpuTexture {
     pub(crate) texture: Arc<wgpu::Texture>,
+    /// Descriptor label, quoted in validation errors
+    pub(crate) label: Option<String>,
+    pub(crate) device: Arc<wgpu::Device>,
+    pub(crate) queue: Arc<wgpu::Queue>,
+    pub(crate) poller: crate::poller::DevicePoller,
+    /// Set by destroy(), so work submitted outside a JS call can check first
+    pub(crate) destroyed: Arc<AtomicBool>,
+    /// Allocation size reported to the garbage collector
+    pub(crate) external_memory: crate::memory::ExternalMemory,
 }
 
 impl GpuTexture {
-    pub(crate) fn new(texture: wgpu::Texture) -> Self {
+    pub(crate) fn new(
+        texture: wgpu::Texture,
+        label: Option<String>,
+        device: Arc<wgpu::Device>,
+        queue: Arc<wgpu::Queue>,
+        poller: crate::poller::DevicePoller,
+    ) -> Self {
         Self {
             texture: Arc::new(texture),
+            label,
+            device,
+            queue,
+            poller,
+            destroyed: Arc::new(AtomicBool::new(false)),
+            external_memory: Default::default(),
+        }
+    }
+
+    /// Throw if destroy() was called
+    pub(crate) fn ensure_not_destroyed(&self, method: &str) -> Result<()> {
+        if self.destroyed.load(Ordering::Relaxed) {
+            return Err(Error::from_reason(format!(
+                "{}: texture {} is destroyed",
+                method,
+                crate::error::describe_label(self.label.as_deref())
+            )));
         }
+        Ok(())
+    }
+
+    /// Report the texture's size to the garbage collector of `env`
+    pub(crate) fn track_memory(self, env: &mut Env) -> Result<Self> {
+        self.external_memory.report(env, crate::memory::texture_size(&self.texture))?;
+        Ok(self)
+    }
+
+    /// Check that `method` can read the texture back as RGBA8 pixels, and
+    /// return whether its channels are in BGRA order
+    pub(crate) fn rgba8_readback_order(&self, method: &str) -> Result<bool> {
+        let format = self.texture.format();
+        let bgra = match format {
+            wgpu::TextureFormat::Rgba8Unorm | wgpu::TextureFormat::Rgba8UnormSrgb => false,
+            wgpu::TextureFormat::Bgra8Unorm | wgpu::TextureFormat::Bgra8UnormSrgb => true,
+            _ => {
+                return Err(Error::from_reason(format!(
+                    "{}() supports rgba8unorm and bgra8unorm (or -srgb) textures, got {}",
+                    method,
+                    crate::parse::texture_format_name(format)
+                )))
+            }
+        };
+        if self.texture.dimension() != wgpu::TextureDimension::D2 || self.texture.sample_count() != 1 {
+            return Err(Error::from_reason(format!(
+                "{}() requires a 2D texture that isn't multisampled",
+                method
+            )));
+        }
+        if !self.texture.usage().contains(wgpu::TextureUsages::COPY_SRC) {
+            return Err(Error::from_reason(format!("{}() needs a texture with COPY_SRC usage", method)));
+        }
+        Ok(bgra)
+    }
+
+    /// Copy array layer 0 of `mip_level` into `buffer`, with rows padded as
+    /// `rgba8_readback_size()` expects
+    pub(crate) fn encode_rgba8_readback(&self, encoder: &mut wgpu::CommandEncoder, buffer: &wgpu::Buffer, mip_level: u32) {
+        let size = self.texture.size().mip_level_size(mip_level, wgpu::TextureDimension::D2);
+        encoder.copy_texture_to_buffer(
+            wgpu::ImageCopyTexture {
+                texture: &self.texture,
+                mip_level,
+                origin: wgpu::Origin3d::ZERO,
+                aspect: wgpu::TextureAspect::All,
+            },
+            wgpu::ImageCopyBuffer {
+                buffer,
+                layout: wgpu::ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: Some(padded_rgba8_row_bytes(size.width)),
+                    rows_per_image: Some(size.height),
+                },
+            },
+            wgpu::Extent3d { depth_or_array_layers: 1, ..size },
+        );
+    }
+
+    fn check_mip_level(&self, method: &str, level: u32) -> Result<()> {
+        let levels = self.texture.mip_level_count();
+        if level >= levels {
+            return Err(Error::from_reason(format!(
+                "{} mip level {} is out of range for a texture with {} mip levels",
+                method, level, levels
+            )));
+        }
+        Ok(())
     }
 }
 
 #[napi]
 impl GpuTexture {
     /// Create a view of this texture
-    #[napi]
-    pub fn create_view(&self, label: Option<String>) -> GpuTextureView {
-        let view = self.texture.create_view(&wgpu::TextureViewDescriptor {
-            label: label.as_deref(),
-            format: None,
-            dimension: None,
-            aspect: wgpu::TextureAspect::All,
-            base_mip_level: 0,
-            mip_level_count: None,
-            base_array_layer: 0,
-            array_layer_count: None,
-        });
+    ///
+    /// Accepts a view descriptor, or just a label for a default view.
+    #[napi(catch_unwind, ts_args_type = "descriptor?: TextureViewDescriptor | string")]
+    pub fn create_view(&self, descriptor: Option<Either<String, TextureViewDescriptor>>) -> GpuTextureView {
+        create_texture_view(&self.texture, descriptor)
+    }
+
+    /// Create a 2D view of one array layer (extension)
+    ///
+    /// Covers a single mip level (`mipLevel`, default 0), as needed to render
+    /// into one layer of a texture array or cube map.
+    #[napi(catch_unwind, js_name = "createLayerView")]
+    pub fn create_layer_view(&self, layer: u32, options: Option<LayerViewOptions>) -> Result<GpuTextureView> {
+        if self.texture.dimension() != wgpu::TextureDimension::D2 {
+            return Err(Error::from_reason("createLayerView requires a 2D texture"));
+        }
+        let layers = self.texture.depth_or_array_layers();
+        if layer >= layers {
+            return Err(Error::from_reason(format!(
+                "createLayerView layer {} is out of range for a texture with {} array layers",
+                layer, layers
+            )));
+        }
+        let mip_level = options.as_ref().and_then(|o| o.mip_level).unwrap_or(0);
+        self.check_mip_level("createLayerView", mip_level)?;
 
-        GpuTextureView::new(view)
+        Ok(create_texture_view(
+            &self.texture,
+            Some(Either::B(TextureViewDescriptor {
+                label: options.as_ref().and_then(|o| o.label.clone()),
+                format: None,
+                dimension: Some(Either::A("2d".to_string())),
+                aspect: options.and_then(|o| o.aspect),
+                base_mip_level: Some(mip_level),
+                mip_level_count: Some(1),
+                base_array_layer: Some(layer),
+                array_layer_count: Some(1),
+            })),
+        ))
     }
 
-    /// Get texture width
-    #[napi]
+    /// Create a view of one mip level, covering all array layers (extension)
+    #[napi(catch_unwind, js_name = "createMipView")]
+    pub fn create_mip_view(&self, level: u32, label: Option<String>) -> Result<GpuTextureView> {
+        self.check_mip_level("createMipView", level)?;
+        Ok(create_texture_view(
+            &self.texture,
+            Some(Either::B(TextureViewDescriptor {
+                label,
+                format: None,
+                dimension: None,
+                aspect: None,
+                base_mip_level: Some(level),
+                mip_level_count: Some(1),
+                base_array_layer: None,
+                array_layer_count: None,
+            })),
+        ))
+    }
+
+    /// Width of the base mip level in texels
+    #[napi(catch_unwind, getter)]
     pub fn width(&self) -> u32 {
         self.texture.width()
     }
 
-    /// Get texture height
-    #[napi]
+    /// Height of the base mip level in texels
+    #[napi(catch_unwind, getter)]
     pub fn height(&self) -> u32 {
         self.texture.height()
     }
 
+    /// Depth of 3D textures, or the number of array layers
+    #[napi(catch_unwind, getter, js_name = "depthOrArrayLayers")]
+    pub fn depth_or_array_layers(&self) -> u32 {
+        self.texture.depth_or_array_layers()
+    }
+
+    #[napi(catch_unwind, getter, js_name = "mipLevelCount")]
+    pub fn mip_level_count(&self) -> u32 {
+        self.texture.mip_level_count()
+    }
+
+    #[napi(catch_unwind, getter, js_name = "sampleCount")]
+    pub fn sample_count(&self) -> u32 {
+        self.texture.sample_count()
+    }
+
+    /// "1d", "2d" or "3d"
+    #[napi(catch_unwind, getter)]
+    pub fn dimension(&self) -> String {
+        match self.texture.dimension() {
+            wgpu::TextureDimension::D1 => "1d",
+            wgpu::TextureDimension::D2 => "2d",
+            wgpu::TextureDimension::D3 => "3d",
+        }
+        .to_string()
+    }
+
+    /// Texture format name, e.g. "rgba8unorm"
+    #[napi(catch_unwind, getter)]
+    pub fn format(&self) -> String {
+        crate::parse::texture_format_name(self.texture.format())
+    }
+
+    /// Usage flags of the texture
+    #[napi(catch_unwind, getter)]
+    pub fn usage(&self) -> u32 {
+        self.texture.usage().bits()
+    }
+
+    /// Names of the usage flags, e.g. ["COPY_DST", "TEXTURE_BINDING"] (extension)
+    #[napi(catch_unwind, js_name = "usageNames")]
+    pub fn usage_names(&self) -> Vec<String> {
+        crate::parse::texture_usage_names(self.texture.usage())
+    }
+
+    /// Descriptor label, or "" without one
+    #[napi(catch_unwind, getter)]
+    pub fn label(&self) -> String {
+        self.label.clone().unwrap_or_default()
+    }
+
+    /// Read one mip level back as tightly packed RGBA8 pixels (extension)
+    ///
+    /// For 2D rgba8unorm, rgba8unorm-srgb, bgra8unorm and bgra8unorm-srgb
+    /// textures with COPY_SRC usage; BGRA pixels are swizzled to RGBA. Reads
+    /// array layer 0 of `mipLevel` (default 0). Submits the copy immediately
+    /// and resolves once the GPU is done.
+    #[napi(catch_unwind, js_name = "readPixels")]
+    pub async fn read_pixels(&self, mip_level: Option<u32>) -> Result<Buffer> {
+        let bgra = self.rgba8_readback_order("readPixels")?;
+        let mip_level = mip_level.unwrap_or(0);
+        self.check_mip_level("readPixels", mip_level)?;
+
+        let size = self.texture.size().mip_level_size(mip_level, wgpu::TextureDimension::D2);
+        let readback = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("readPixels readback"),
+            size: rgba8_readback_size(size.width, size.height),
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: Some("readPixels") });
+        self.encode_rgba8_readback(&mut encoder, &readback, mip_level);
+        self.queue.submit(Some(encoder.finish()));
+
+        let slice = readback.slice(..);
+        let _polling = self.poller.track();
+        let (sender, receiver) = futures::channel::oneshot::channel();
+        slice.map_async(wgpu::MapMode::Read, move |result| {
+            let _ = sender.send(result);
+        });
+        receiver
+            .await
+            .map_err(|_| Error::from_reason("Failed to receive map result"))?
+            .map_err(|e| Error::from_reason(format!("Failed to read texture: {:?}", e)))?;
+
+        let pixels = unpad_rgba8_rows(&slice.get_mapped_range(), size.width, size.height, bgra);
+        readback.unmap();
+        Ok(pixels.into())
+    }
+
     /// Destroy the texture
-    #[napi]
-    pub fn destroy(&self) {
+    #[napi(catch_unwind)]
+    pub fn destroy(&self, mut env: Env) -> Result<()> {
+        self.destroyed.store(true, Ordering::Relaxed);
         self.texture.destroy();
+        self.external_memory.release(&mut env)
+    }
+}
+
+fn padded_rgba8_row_bytes(width: u32) -> u32 {
+    (width * 4).next_multiple_of(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT)
+}
+
+/// Size of a readback buffer for `width` x `height` RGBA8 pixels
+pub(crate) fn rgba8_readback_size(width: u32, height: u32) -> u64 {
+    padded_rgba8_row_bytes(width) as u64 * height as u64
+}
+
+/// Tightly packed RGBA8 pixels from padded readback rows
+pub(crate) fn unpad_rgba8_rows(mapped: &[u8], width: u32, height: u32, bgra: bool) -> Vec<u8> {
+    let row_bytes = width as usize * 4;
+    let mut pixels = Vec::with_capacity(row_bytes * height as usize);
+    for row in mapped.chunks(padded_rgba8_row_bytes(width) as usize).take(height as usize) {
+        pixels.extend_from_slice(&row[..row_bytes]);
+    }
+    if bgra {
+        for pixel in pixels.chunks_exact_mut(4) {
+            pixel.swap(0, 2);
+        }
+    }
+    pixels
+}
+
+/// Create a view from a view descriptor, or just a label for a default view
+pub(crate) fn create_texture_view(
+    texture: &wgpu::Texture,
+    descriptor: Option<Either<String, TextureViewDescriptor>>,
+) -> GpuTextureView {
+    let (view, format, base_mip_level) = match descriptor {
+        Some(Either::B(descriptor)) => {
+            let format = descriptor.format.as_ref().map(crate::parse::parse_texture_format);
+            let base_mip_level = descriptor.base_mip_level.unwrap_or(0);
+            let view = texture.create_view(&wgpu::TextureViewDescriptor {
+                label: descriptor.label.as_deref(),
+                format,
+                dimension: crate::parse::parse_texture_view_dimension(descriptor.dimension.as_ref()),
+                aspect: crate::parse::parse_texture_aspect(descriptor.aspect.as_ref()),
+                base_mip_level,
+                mip_level_count: descriptor.mip_level_count,
+                base_array_layer: descriptor.base_array_layer.unwrap_or(0),
+                array_layer_count: descriptor.array_layer_count,
+            });
+            (view, format, base_mip_level)
+        }
+        label => {
+            let view = texture.create_view(&wgpu::TextureViewDescriptor {
+                label: match &label {
+                    Some(Either::A(label)) => Some(label.as_str()),
+                    _ => None,
+                },
+                ..Default::default()
+            });
+            (view, None, 0)
+        }
+    };
+
+    GpuTextureView {
+        view: Arc::new(view),
+        format: format.unwrap_or(texture.format()),
+        size: (
+            (texture.width() >> base_mip_level).max(1),
+            (texture.height() >> base_mip_level).max(1),
+        ),
+        sample_count: texture.sample_count(),
     }
 }
 
@@ -76,12 +548,9 @@ impl GpuTexture {
 #[napi]
 pub struct GpuTextureView {
     pub(crate) view: Arc<wgpu::TextureView>,
-}
-
-impl GpuTextureView {
-    pub(crate) fn new(view: wgpu::TextureView) -> Self {
-        Self {
-            view: Arc::new(view),
-        }
-    }
+    /// Format of the view, which may differ from the texture's in sRGB-ness
+    pub(crate) format: wgpu::TextureFormat,
+    /// Width and height of the base mip level
+    pub(crate) size: (u32, u32),
+    pub(crate) sample_count: u32,
 }
@@ -0,0 +1,212 @@
+//! Lightweight GPU timing for one-off measurements
+//!
+//! A timer owns a timestamp query set with two queries per span and the
+//! buffers needed to read them back. Spans are written straight into command
+//! encoders, so anything recorded between `begin` and `end` is measured.
+
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use std::sync::{Arc, Mutex};
+
+const DEFAULT_CAPACITY: u32 = 32;
+/// Two timestamps per span must fit in one query set
+const MAX_CAPACITY: u32 = wgpu::QUERY_SET_MAX_QUERIES / 2;
+
+/// Timer descriptor
+#[napi(object)]
+pub struct TimerDescriptor {
+    pub label: Option<String>,
+    /// Maximum number of spans measured between `results()` calls (default 32)
+    pub capacity: Option<u32>,
+}
+
+/// GPU duration of one labeled span
+#[napi(object)]
+pub struct TimerResult {
+    pub label: String,
+    /// Duration in milliseconds
+    pub ms: f64,
+}
+
+struct Span {
+    label: String,
+    ended: bool,
+}
+
+/// Timestamp query timer
+///
+/// Measures labeled spans of encoded work and reports their GPU durations in
+/// milliseconds. Requires the "timestamp-query" feature.
+#[napi]
+pub struct GpuTimer {
+    device: Arc<wgpu::Device>,
+    queue: Arc<wgpu::Queue>,
+    poller: crate::poller::DevicePoller,
+    query_set: wgpu::QuerySet,
+    resolve_buffer: wgpu::Buffer,
+    readback_buffer: wgpu::Buffer,
+    capacity: u32,
+    spans: Mutex<Vec<Span>>,
+}
+
+impl GpuTimer {
+    pub(crate) fn new(
+        device: Arc<wgpu::Device>,
+        queue: Arc<wgpu::Queue>,
+        poller: crate::poller::DevicePoller,
+        descriptor: Option<TimerDescriptor>,
+    ) -> Result<Self> {
+        if !device.features().contains(wgpu::Features::TIMESTAMP_QUERY) {
+            return Err(Error::from_reason("GpuTimer requires the 'timestamp-query' feature"));
+        }
+        let capacity = descriptor.as_ref().and_then(|d| d.capacity).unwrap_or(DEFAULT_CAPACITY);
+        if capacity == 0 || capacity > MAX_CAPACITY {
+            return Err(Error::from_reason(format!(
+                "Invalid timer capacity: {}. Use 1 to {}",
+                capacity, MAX_CAPACITY
+            )));
+        }
+        let label = descriptor
+            .as_ref()
+            .and_then(|d| d.label.clone())
+            .unwrap_or_else(|| "timer".to_string());
+
+        let query_count = capacity * 2;
+        let size = query_count as u64 * wgpu::QUERY_SIZE as u64;
+        let query_set = device.create_query_set(&wgpu::QuerySetDescriptor {
+            label: Some(&label),
+            ty: wgpu::QueryType::Timestamp,
+            count: query_count,
+        });
+        let resolve_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some(&label),
+            size,
+            usage: wgpu::BufferUsages::QUERY_RESOLVE | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+        let readback_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some(&label),
+            size,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        Ok(Self {
+            device,
+            queue,
+            poller,
+            query_set,
+            resolve_buffer,
+            readback_buffer,
+            capacity,
+            spans: Mutex::new(Vec::new()),
+        })
+    }
+
+    fn lock_spans(&self) -> Result<std::sync::MutexGuard<'_, Vec<Span>>> {
+        self.spans
+            .lock()
+            .map_err(|_| Error::from_reason("Timer state is poisoned"))
+    }
+}
+
+#[napi]
+impl GpuTimer {
+    /// Start a labeled span by writing a timestamp into `encoder`
+    ///
+    /// Returns the span index to pass to `end()`.
+    #[napi(catch_unwind)]
+    pub fn begin(&self, encoder: &crate::GpuCommandEncoder, label: String) -> Result<u32> {
+        let mut spans = self.lock_spans()?;
+        let index = spans.len() as u32;
+        if index >= self.capacity {
+            return Err(Error::from_reason(format!(
+                "Timer capacity of {} spans exceeded; call results() to collect them first",
+                self.capacity
+            )));
+        }
+        encoder.with_encoder(|enc| {
+            enc.write_timestamp(&self.query_set, index * 2);
+            Ok(())
+        })?;
+        spans.push(Span { label, ended: false });
+        Ok(index)
+    }
+
+    /// End the span returned by `begin()` by writing a timestamp into `encoder`
+    #[napi(catch_unwind)]
+    pub fn end(&self, encoder: &crate::GpuCommandEncoder, span: u32) -> Result<()> {
+        let mut spans = self.lock_spans()?;
+        let Some(entry) = spans.get_mut(span as usize) else {
+            return Err(Error::from_reason(format!("Unknown timer span: {}", span)));
+        };
+        if entry.ended {
+            return Err(Error::from_reason(format!("Timer span '{}' already ended", entry.label)));
+        }
+        encoder.with_encoder(|enc| {
+            enc.write_timestamp(&self.query_set, span * 2 + 1);
+            Ok(())
+        })?;
+        entry.ended = true;
+        Ok(())
+    }
+
+    /// Read back the durations of all spans recorded since the last call
+    ///
+    /// Call after submitting the encoders the spans were written to. The spans
+    /// are cleared, so the timer can be reused for the next measurement.
+    #[napi(catch_unwind)]
+    pub async fn results(&self) -> Result<Vec<TimerResult>> {
+        let spans = std::mem::take(&mut *self.lock_spans()?);
+        if let Some(open) = spans.iter().find(|span| !span.ended) {
+            return Err(Error::from_reason(format!("Timer span '{}' was never ended", open.label)));
+        }
+        if spans.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let query_count = spans.len() as u32 * 2;
+        let size = query_count as u64 * wgpu::QUERY_SIZE as u64;
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: Some("timer resolve") });
+        encoder.resolve_query_set(&self.query_set, 0..query_count, &self.resolve_buffer, 0);
+        encoder.copy_buffer_to_buffer(&self.resolve_buffer, 0, &self.readback_buffer, 0, size);
+        self.queue.submit(Some(encoder.finish()));
+
+        let slice = self.readback_buffer.slice(..size);
+        let _polling = self.poller.track();
+        let (sender, receiver) = futures::channel::oneshot::channel();
+        slice.map_async(wgpu::MapMode::Read, move |result| {
+            let _ = sender.send(result);
+        });
+        receiver
+            .await
+            .map_err(|_| Error::from_reason("Failed to receive map result"))?
+            .map_err(|e| Error::from_reason(format!("Failed to read timestamps: {:?}", e)))?;
+
+        let timestamps: Vec<u64> = slice
+            .get_mapped_range()
+            .chunks_exact(8)
+            .map(|bytes| u64::from_le_bytes(bytes.try_into().unwrap()))
+            .collect();
+        self.readback_buffer.unmap();
+
+        // Timestamps count ticks of `period` nanoseconds
+        let period = self.queue.get_timestamp_period() as f64;
+        Ok(spans
+            .into_iter()
+            .zip(timestamps.chunks_exact(2))
+            .map(|(span, pair)| TimerResult {
+                label: span.label,
+                ms: pair[1].saturating_sub(pair[0]) as f64 * period / 1_000_000.0,
+            })
+            .collect())
+    }
+
+    /// Maximum number of spans between `results()` calls
+    #[napi(catch_unwind, getter)]
+    pub fn capacity(&self) -> u32 {
+        self.capacity
+    }
+}
@@ -0,0 +1,362 @@
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use std::sync::Arc;
+
+/// Largest staging allocation used per chunk of a cross-device buffer copy
+const TRANSFER_CHUNK_SIZE: u64 = 64 * 1024 * 1024;
+
+/// Cross-device buffer copy options
+#[napi(object)]
+pub struct CrossDeviceBufferCopyOptions {
+    pub source_offset: Option<i64>,
+    pub destination_offset: Option<i64>,
+    /// Bytes to copy (default: the rest of the source buffer)
+    pub size: Option<i64>,
+}
+
+/// Cross-device texture copy options
+#[napi(object)]
+pub struct CrossDeviceTextureCopyOptions {
+    pub mip_level: Option<u32>,
+}
+
+struct DeviceHandle {
+    device: Arc<wgpu::Device>,
+    queue: Arc<wgpu::Queue>,
+}
+
+enum TransferKind {
+    Buffer {
+        source: Arc<wgpu::Buffer>,
+        destination: Arc<wgpu::Buffer>,
+        source_offset: u64,
+        destination_offset: u64,
+        size: u64,
+    },
+    Texture {
+        source: Arc<wgpu::Texture>,
+        destination: Arc<wgpu::Texture>,
+        mip_level: u32,
+    },
+}
+
+/// Background job backing `copyBufferBetweenDevices` / `copyTextureBetweenDevices`
+///
+/// Runs on the libuv thread pool so blocking on both devices never stalls the JS thread.
+pub struct CrossDeviceTransfer {
+    source: DeviceHandle,
+    destination: DeviceHandle,
+    kind: TransferKind,
+}
+
+impl Task for CrossDeviceTransfer {
+    type Output = ();
+    type JsValue = ();
+
+    fn compute(&mut self) -> Result<()> {
+        // Runs outside any napi callback, where an unwind would abort the process
+        std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| self.transfer())).unwrap_or_else(|panic| {
+            Err(Error::from_reason(format!(
+                "Cross-device copy failed: {}",
+                crate::error::panic_message(panic.as_ref())
+            )))
+        })
+    }
+
+    fn resolve(&mut self, _env: Env, _output: ()) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl CrossDeviceTransfer {
+    fn transfer(&self) -> Result<()> {
+        match &self.kind {
+            TransferKind::Buffer { source, destination, source_offset, destination_offset, size } => {
+                if Arc::ptr_eq(&self.source.device, &self.destination.device) {
+                    let mut encoder = self.source.device.create_command_encoder(&Default::default());
+                    encoder.copy_buffer_to_buffer(source, *source_offset, destination, *destination_offset, *size);
+                    self.source.queue.submit(Some(encoder.finish()));
+                    return Ok(());
+                }
+                self.copy_buffer(source, destination, *source_offset, *destination_offset, *size)
+            }
+            TransferKind::Texture { source, destination, mip_level } => {
+                if Arc::ptr_eq(&self.source.device, &self.destination.device) {
+                    let mut encoder = self.source.device.create_command_encoder(&Default::default());
+                    encoder.copy_texture_to_texture(
+                        texture_copy(source, *mip_level),
+                        texture_copy(destination, *mip_level),
+                        source.size().mip_level_size(*mip_level, source.dimension()),
+                    );
+                    self.source.queue.submit(Some(encoder.finish()));
+                    return Ok(());
+                }
+                self.copy_texture(source, destination, *mip_level)
+            }
+        }
+    }
+
+    /// Stream the range through host memory in bounded chunks
+    ///
+    /// Each chunk is read back into a MAP_READ staging buffer on the source device and
+    /// copied straight into a mapped-at-creation upload buffer on the destination device,
+    /// so the data never passes through an intermediate JS allocation.
+    fn copy_buffer(
+        &self,
+        source: &wgpu::Buffer,
+        destination: &wgpu::Buffer,
+        source_offset: u64,
+        destination_offset: u64,
+        size: u64,
+    ) -> Result<()> {
+        let readback = self.source.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("cross-device readback"),
+            size: size.min(TRANSFER_CHUNK_SIZE),
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let mut copied = 0;
+        while copied < size {
+            let chunk = (size - copied).min(TRANSFER_CHUNK_SIZE);
+
+            let mut encoder = self.source.device.create_command_encoder(&Default::default());
+            encoder.copy_buffer_to_buffer(source, source_offset + copied, &readback, 0, chunk);
+            self.source.queue.submit(Some(encoder.finish()));
+            map_read(&self.source.device, &readback, chunk)?;
+
+            let upload = self.destination.device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some("cross-device upload"),
+                size: chunk,
+                usage: wgpu::BufferUsages::MAP_WRITE | wgpu::BufferUsages::COPY_SRC,
+                mapped_at_creation: true,
+            });
+            upload
+                .slice(..)
+                .get_mapped_range_mut()
+                .copy_from_slice(&readback.slice(..chunk).get_mapped_range());
+            upload.unmap();
+            readback.unmap();
+
+            let mut encoder = self.destination.device.create_command_encoder(&Default::default());
+            encoder.copy_buffer_to_buffer(&upload, 0, destination, destination_offset + copied, chunk);
+            self.destination.queue.submit(Some(encoder.finish()));
+
+            copied += chunk;
+        }
+
+        readback.destroy();
+        Ok(())
+    }
+
+    /// Copy one mip level (all layers) through host memory using padded rows
+    fn copy_texture(&self, source: &wgpu::Texture, destination: &wgpu::Texture, mip_level: u32) -> Result<()> {
+        let format = source.format();
+        let extent = source.size().mip_level_size(mip_level, source.dimension());
+        let block_size = format
+            .block_copy_size(None)
+            .ok_or_else(|| Error::from_reason(format!("Texture format {:?} cannot be copied between devices", format)))?;
+        let (block_width, block_height) = format.block_dimensions();
+        let rows_per_image = extent.height.div_ceil(block_height);
+        let bytes_per_row = (extent.width.div_ceil(block_width) * block_size)
+            .next_multiple_of(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT);
+        let size = bytes_per_row as u64 * rows_per_image as u64 * extent.depth_or_array_layers as u64;
+        let layout = wgpu::ImageDataLayout {
+            offset: 0,
+            bytes_per_row: Some(bytes_per_row),
+            rows_per_image: Some(rows_per_image),
+        };
+
+        let readback = self.source.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("cross-device readback"),
+            size,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let mut encoder = self.source.device.create_command_encoder(&Default::default());
+        encoder.copy_texture_to_buffer(
+            texture_copy(source, mip_level),
+            wgpu::ImageCopyBuffer { buffer: &readback, layout },
+            extent,
+        );
+        self.source.queue.submit(Some(encoder.finish()));
+        map_read(&self.source.device, &readback, size)?;
+
+        let upload = self.destination.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("cross-device upload"),
+            size,
+            usage: wgpu::BufferUsages::MAP_WRITE | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: true,
+        });
+        upload
+            .slice(..)
+            .get_mapped_range_mut()
+            .copy_from_slice(&readback.slice(..).get_mapped_range());
+        upload.unmap();
+        readback.unmap();
+        readback.destroy();
+
+        let mut encoder = self.destination.device.create_command_encoder(&Default::default());
+        encoder.copy_buffer_to_texture(
+            wgpu::ImageCopyBuffer { buffer: &upload, layout },
+            texture_copy(destination, mip_level),
+            extent,
+        );
+        self.destination.queue.submit(Some(encoder.finish()));
+        Ok(())
+    }
+}
+
+fn texture_copy(texture: &wgpu::Texture, mip_level: u32) -> wgpu::ImageCopyTexture<'_> {
+    wgpu::ImageCopyTexture {
+        texture,
+        mip_level,
+        origin: wgpu::Origin3d::ZERO,
+        aspect: wgpu::TextureAspect::All,
+    }
+}
+
+/// Map the first `size` bytes of a readback buffer, blocking until the source device is idle
+fn map_read(device: &wgpu::Device, buffer: &wgpu::Buffer, size: u64) -> Result<()> {
+    let (sender, receiver) = std::sync::mpsc::channel();
+    buffer.slice(..size).map_async(wgpu::MapMode::Read, move |result| {
+        let _ = sender.send(result);
+    });
+    device.poll(wgpu::Maintain::Wait);
+    receiver
+        .recv()
+        .map_err(|_| Error::from_reason("Failed to receive map result"))?
+        .map_err(|e| Error::from_reason(format!("Failed to map staging buffer: {:?}", e)))
+}
+
+fn non_negative(value: Option<i64>, name: &str) -> Result<Option<u64>> {
+    match value {
+        Some(v) if v < 0 => Err(Error::from_reason(format!("{} must not be negative", name))),
+        Some(v) => Ok(Some(v as u64)),
+        None => Ok(None),
+    }
+}
+
+/// Copy a buffer range from one device to another
+///
+/// Works across devices created from different adapters (e.g. two GPUs in one
+/// process). The data is staged through host memory in 64 MiB chunks; when both
+/// buffers live on the same device a plain GPU copy is recorded instead.
+/// The source needs COPY_SRC usage and the destination COPY_DST usage.
+///
+/// Example:
+/// ```js
+/// await copyBufferBetweenDevices(resultsOnGpu0, inputsOnGpu1, { size: 4096 })
+/// ```
+#[napi(catch_unwind, js_name = "copyBufferBetweenDevices", ts_return_type = "Promise<void>")]
+pub fn copy_buffer_between_devices(
+    source: &crate::GpuBuffer,
+    destination: &crate::GpuBuffer,
+    options: Option<CrossDeviceBufferCopyOptions>,
+) -> Result<AsyncTask<CrossDeviceTransfer>> {
+    let (source_offset, destination_offset, size) = match options {
+        Some(o) => (
+            non_negative(o.source_offset, "sourceOffset")?,
+            non_negative(o.destination_offset, "destinationOffset")?,
+            non_negative(o.size, "size")?,
+        ),
+        None => (None, None, None),
+    };
+    let source_offset = source_offset.unwrap_or(0);
+    let destination_offset = destination_offset.unwrap_or(0);
+    source.ensure_submittable("copyBufferBetweenDevices")?;
+    destination.ensure_submittable("copyBufferBetweenDevices")?;
+
+    if source_offset > source.buffer.size() {
+        return Err(Error::from_reason("sourceOffset is past the end of the source buffer"));
+    }
+    let size = size.unwrap_or(source.buffer.size() - source_offset);
+    if source_offset + size > source.buffer.size() {
+        return Err(Error::from_reason(format!(
+            "Copy of {} bytes at offset {} overruns source buffer of {} bytes",
+            size, source_offset, source.buffer.size()
+        )));
+    }
+    if destination_offset + size > destination.buffer.size() {
+        return Err(Error::from_reason(format!(
+            "Copy of {} bytes at offset {} overruns destination buffer of {} bytes",
+            size, destination_offset, destination.buffer.size()
+        )));
+    }
+    if (source_offset | destination_offset | size) % wgpu::COPY_BUFFER_ALIGNMENT != 0 {
+        return Err(Error::from_reason("Offsets and size must be multiples of 4"));
+    }
+    if !source.buffer.usage().contains(wgpu::BufferUsages::COPY_SRC) {
+        return Err(Error::from_reason("Source buffer requires COPY_SRC usage"));
+    }
+    if !destination.buffer.usage().contains(wgpu::BufferUsages::COPY_DST) {
+        return Err(Error::from_reason("Destination buffer requires COPY_DST usage"));
+    }
+
+    Ok(AsyncTask::new(CrossDeviceTransfer {
+        source: DeviceHandle { device: source.device.clone(), queue: source.queue.clone() },
+        destination: DeviceHandle { device: destination.device.clone(), queue: destination.queue.clone() },
+        kind: TransferKind::Buffer {
+            source: source.buffer.clone(),
+            destination: destination.buffer.clone(),
+            source_offset,
+            destination_offset,
+            size,
+        },
+    }))
+}
+
+/// Copy a texture mip level (all layers) from one device to another
+///
+/// Both textures must share format and size. The source needs COPY_SRC usage and
+/// the destination COPY_DST usage; depth/stencil and multisampled textures are not
+/// supported.
+///
+/// Example:
+/// ```js
+/// await copyTextureBetweenDevices(frameOnGpu0, frameOnGpu1)
+/// ```
+#[napi(catch_unwind, js_name = "copyTextureBetweenDevices", ts_return_type = "Promise<void>")]
+pub fn copy_texture_between_devices(
+    source: &crate::GpuTexture,
+    destination: &crate::GpuTexture,
+    options: Option<CrossDeviceTextureCopyOptions>,
+) -> Result<AsyncTask<CrossDeviceTransfer>> {
+    let mip_level = options.and_then(|o| o.mip_level).unwrap_or(0);
+    let (src, dst) = (&source.texture, &destination.texture);
+    source.ensure_not_destroyed("copyTextureBetweenDevices")?;
+    destination.ensure_not_destroyed("copyTextureBetweenDevices")?;
+
+    if src.format() != dst.format() {
+        return Err(Error::from_reason(format!(
+            "Texture formats differ ({:?} vs {:?})",
+            src.format(),
+            dst.format()
+        )));
+    }
+    if mip_level >= src.mip_level_count() || mip_level >= dst.mip_level_count() {
+        return Err(Error::from_reason(format!("Mip level {} out of range", mip_level)));
+    }
+    if src.size().mip_level_size(mip_level, src.dimension()) != dst.size().mip_level_size(mip_level, dst.dimension()) {
+        return Err(Error::from_reason("Texture sizes differ at the requested mip level"));
+    }
+    if src.format().is_depth_stencil_format() || src.sample_count() > 1 {
+        return Err(Error::from_reason("Depth/stencil and multisampled textures cannot be copied between devices"));
+    }
+    if !src.usage().contains(wgpu::TextureUsages::COPY_SRC) {
+        return Err(Error::from_reason("Source texture requires COPY_SRC usage"));
+    }
+    if !dst.usage().contains(wgpu::TextureUsages::COPY_DST) {
+        return Err(Error::from_reason("Destination texture requires COPY_DST usage"));
+    }
+
+    Ok(AsyncTask::new(CrossDeviceTransfer {
+        source: DeviceHandle { device: source.device.clone(), queue: source.queue.clone() },
+        destination: DeviceHandle { device: destination.device.clone(), queue: destination.queue.clone() },
+        kind: TransferKind::Texture {
+            source: src.clone(),
+            destination: dst.clone(),
+            mip_level,
+        },
+    }))
+}
@@ -0,0 +1,203 @@
+//! Uniform struct writer driven by shader reflection
+//!
+//! Values set from JavaScript are checked against the WGSL struct layout
+//! (field names, component counts, integer ranges) and packed with the
+//! shader's offsets, strides and padding.
+
+use crate::reflect::{ScalarLayout, StructLayout, ValueLayout};
+use napi::bindgen_prelude::*;
+use napi::{JsNumber, JsObject, JsString, JsUnknown, ValueType};
+use napi_derive::napi;
+
+/// CPU-side copy of a WGSL struct, packed to match the shader's memory layout
+#[napi]
+pub struct GpuUniformWriter {
+    layout: StructLayout,
+    data: Vec<u8>,
+}
+
+impl GpuUniformWriter {
+    pub(crate) fn new(layout: StructLayout) -> Self {
+        let data = vec![0; layout.size as usize];
+        Self { layout, data }
+    }
+}
+
+#[napi]
+impl GpuUniformWriter {
+    /// Set struct fields from a `{ field: value }` object
+    ///
+    /// Fields that are not mentioned keep their previous value (initially zero).
+    /// Vectors and matrices take arrays or TypedArrays (matrices column-major);
+    /// nested structs take objects.
+    #[napi(catch_unwind)]
+    pub fn set(&mut self, values: JsObject) -> Result<()> {
+        write_struct(&self.layout, &mut self.data, 0, &values, &self.layout.name)
+    }
+
+    /// Write the packed struct to `buffer` at `offset` (default 0)
+    #[napi(catch_unwind)]
+    pub fn upload(&self, queue: &crate::GpuQueue, buffer: &crate::GpuBuffer, offset: Option<i64>) {
+        queue
+            .queue
+            .write_buffer(&buffer.buffer, offset.unwrap_or(0) as u64, &self.data);
+    }
+
+    /// Packed struct bytes
+    #[napi(catch_unwind, js_name = "getData")]
+    pub fn get_data(&self) -> Buffer {
+        self.data.clone().into()
+    }
+
+    /// Struct size in bytes, including trailing padding
+    #[napi(catch_unwind, getter)]
+    pub fn size(&self) -> u32 {
+        self.layout.size
+    }
+}
+
+fn write_struct(layout: &StructLayout, data: &mut [u8], offset: u32, values: &JsObject, path: &str) -> Result<()> {
+    let names = values.get_property_names()?;
+    for i in 0..names.get_array_length()? {
+        let name = names.get_element::<JsString>(i)?.into_utf8()?;
+        let name = name.as_str()?;
+        let member = layout
+            .members
+            .iter()
+            .find(|member| member.name == name)
+            .ok_or_else(|| Error::from_reason(format!("Unknown field '{}' in struct {}", name, layout.name)))?;
+
+        let value = values.get_named_property::<JsUnknown>(name)?;
+        write_value(&member.ty, data, offset + member.offset, value, &format!("{}.{}", path, name))?;
+    }
+    Ok(())
+}
+
+fn write_value(ty: &ValueLayout, data: &mut [u8], offset: u32, value: JsUnknown, path: &str) -> Result<()> {
+    match ty {
+        ValueLayout::Scalar(scalar) => {
+            if value.get_type()? != ValueType::Number {
+                return Err(Error::from_reason(format!("{}: expected a number", path)));
+            }
+            write_scalar(*scalar, data, offset, value.coerce_to_number()?.get_double()?, path)
+        }
+        ValueLayout::Struct(layout) => {
+            if value.get_type()? != ValueType::Object {
+                return Err(Error::from_reason(format!("{}: expected an object for struct {}", path, layout.name)));
+            }
+            write_struct(layout, data, offset, &unsafe { value.cast::<JsObject>() }, path)
+        }
+        ValueLayout::Array { base, count, stride } if matches!(**base, ValueLayout::Struct(_)) => {
+            let elements = array_like(value, path)?;
+            let length = elements.get_named_property::<JsNumber>("length")?.get_uint32()?;
+            if length > *count {
+                return Err(Error::from_reason(format!(
+                    "{}: expected at most {} elements, got {}",
+                    path, count, length
+                )));
+            }
+            for i in 0..length {
+                let element = elements.get_element::<JsUnknown>(i)?;
+                write_value(base, data, offset + i * stride, element, &format!("{}[{}]", path, i))?;
+            }
+            Ok(())
+        }
+        _ => {
+            // Vectors, matrices and arrays of them: flat or nested lists of numbers
+            let mut numbers = Vec::new();
+            collect_numbers(value, &mut numbers, path)?;
+            let expected = components(ty);
+            let valid = match ty {
+                // Arrays may be partially written, one whole element at a time
+                ValueLayout::Array { base, .. } => numbers.len() <= expected && numbers.len() % components(base) == 0,
+                _ => numbers.len() == expected,
+            };
+            if !valid {
+                return Err(Error::from_reason(format!(
+                    "{}: expected {} components, got {}",
+                    path,
+                    expected,
+                    numbers.len()
+                )));
+            }
+            write_flat(ty, data, offset, &numbers, path)
+        }
+    }
+}
+
+/// Number of scalar components in a value without structs
+fn components(ty: &ValueLayout) -> usize {
+    match ty {
+        ValueLayout::Scalar(_) => 1,
+        ValueLayout::Vector { size, .. } => *size as usize,
+        ValueLayout::Matrix { columns, rows, .. } => (columns * rows) as usize,
+        ValueLayout::Array { base, count, .. } => components(base) * *count as usize,
+        ValueLayout::Struct(_) => 0,
+    }
+}
+
+fn write_flat(ty: &ValueLayout, data: &mut [u8], offset: u32, numbers: &[f64], path: &str) -> Result<()> {
+    match ty {
+        ValueLayout::Scalar(scalar) => write_scalar(*scalar, data, offset, numbers[0], path),
+        ValueLayout::Vector { scalar, .. } => numbers
+            .iter()
+            .enumerate()
+            .try_for_each(|(i, n)| write_scalar(*scalar, data, offset + i as u32 * 4, *n, path)),
+        ValueLayout::Matrix {
+            rows,
+            column_stride,
+            scalar,
+            ..
+        } => numbers.iter().enumerate().try_for_each(|(i, n)| {
+            let (column, row) = (i as u32 / rows, i as u32 % rows);
+            write_scalar(*scalar, data, offset + column * column_stride + row * 4, *n, path)
+        }),
+        ValueLayout::Array { base, stride, .. } => numbers
+            .chunks(components(base))
+            .enumerate()
+            .try_for_each(|(i, chunk)| write_flat(base, data, offset + i as u32 * stride, chunk, path)),
+        ValueLayout::Struct(_) => unreachable!("structs are written field by field"),
+    }
+}
+
+fn write_scalar(scalar: ScalarLayout, data: &mut [u8], offset: u32, value: f64, path: &str) -> Result<()> {
+    let bytes = match scalar {
+        ScalarLayout::F32 => (value as f32).to_le_bytes(),
+        ScalarLayout::I32 if value.fract() == 0.0 && value >= i32::MIN as f64 && value <= i32::MAX as f64 => {
+            (value as i32).to_le_bytes()
+        }
+        ScalarLayout::U32 if value.fract() == 0.0 && value >= 0.0 && value <= u32::MAX as f64 => {
+            (value as u32).to_le_bytes()
+        }
+        ScalarLayout::I32 => return Err(Error::from_reason(format!("{}: {} is not a valid i32", path, value))),
+        ScalarLayout::U32 => return Err(Error::from_reason(format!("{}: {} is not a valid u32", path, value))),
+    };
+    let offset = offset as usize;
+    data[offset..offset + 4].copy_from_slice(&bytes);
+    Ok(())
+}
+
+fn array_like(value: JsUnknown, path: &str) -> Result<JsObject> {
+    if value.get_type()? == ValueType::Object {
+        let object = unsafe { value.cast::<JsObject>() };
+        if object.is_array()? || object.is_typedarray()? {
+            return Ok(object);
+        }
+    }
+    Err(Error::from_reason(format!("{}: expected an array or TypedArray", path)))
+}
+
+/// Flatten (possibly nested) arrays of numbers, e.g. `[[1, 0], [0, 1]]` for a mat2x2
+fn collect_numbers(value: JsUnknown, numbers: &mut Vec<f64>, path: &str) -> Result<()> {
+    if value.get_type()? == ValueType::Number {
+        numbers.push(value.coerce_to_number()?.get_double()?);
+        return Ok(());
+    }
+
+    let elements = array_like(value, path)?;
+    let length = elements.get_named_property::<JsNumber>("length")?.get_uint32()?;
+    for i in 0..length {
+        collect_numbers(elements.get_element::<JsUnknown>(i)?, numbers, path)?;
+    }
+    Ok(())
+}
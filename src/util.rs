@@ -0,0 +1,121 @@
+//! Shared helpers for the built-in compute utilities
+//!
+//! These keep the per-utility modules focused on their algorithm rather than
+//! on pipeline and dispatch boilerplate.
+
+use wgpu::util::DeviceExt;
+
+/// Maximum workgroups per dispatch dimension guaranteed by the default limits
+pub(crate) const MAX_WORKGROUPS_PER_DIMENSION: u32 = 65535;
+
+/// Split a linear workgroup count into a 2D grid that respects the per-dimension limit
+///
+/// Shaders recover the linear index as `workgroup_id.x + workgroup_id.y * num_workgroups.x`
+/// and must bounds-check it, since the grid may overshoot.
+pub(crate) fn workgroup_grid(workgroups: u32) -> (u32, u32) {
+    if workgroups <= MAX_WORKGROUPS_PER_DIMENSION {
+        (workgroups.max(1), 1)
+    } else {
+        (
+            MAX_WORKGROUPS_PER_DIMENSION,
+            workgroups.div_ceil(MAX_WORKGROUPS_PER_DIMENSION),
+        )
+    }
+}
+
+/// Create a compute pipeline from WGSL source using an automatic layout
+pub(crate) fn create_compute_pipeline(
+    device: &wgpu::Device,
+    label: &str,
+    source: &str,
+    entry_point: &str,
+) -> wgpu::ComputePipeline {
+    let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: Some(label),
+        source: wgpu::ShaderSource::Wgsl(source.into()),
+    });
+    device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+        label: Some(label),
+        layout: None,
+        module: &module,
+        entry_point,
+    })
+}
+
+/// Create a 16-byte aligned uniform buffer holding the given u32 parameters
+pub(crate) fn create_params_buffer(device: &wgpu::Device, label: &str, params: &[u32]) -> wgpu::Buffer {
+    let mut contents: Vec<u8> = params.iter().flat_map(|v| v.to_le_bytes()).collect();
+    contents.resize(contents.len().div_ceil(16).max(1) * 16, 0);
+    device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+        label: Some(label),
+        contents: &contents,
+        usage: wgpu::BufferUsages::UNIFORM,
+    })
+}
+
+/// Create a scratch storage buffer of at least `size` bytes
+pub(crate) fn create_storage_buffer(device: &wgpu::Device, label: &str, size: u64) -> wgpu::Buffer {
+    device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some(label),
+        size: size.max(4).next_multiple_of(4),
+        usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC | wgpu::BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    })
+}
+
+/// Check that a buffer can be bound as storage and holds at least `min_size` bytes
+pub(crate) fn validate_storage_buffer(buffer: &wgpu::Buffer, min_size: u64, name: &str) -> napi::Result<()> {
+    if !buffer.usage().contains(wgpu::BufferUsages::STORAGE) {
+        return Err(napi::Error::from_reason(format!(
+            "{} buffer must have STORAGE usage",
+            name
+        )));
+    }
+    if buffer.size() < min_size {
+        return Err(napi::Error::from_reason(format!(
+            "{} buffer is too small: {} bytes required, {} available",
+            name,
+            min_size,
+            buffer.size()
+        )));
+    }
+    Ok(())
+}
+
+/// Record a single compute dispatch with one bind group in its own pass
+pub(crate) fn dispatch(
+    encoder: &mut wgpu::CommandEncoder,
+    pipeline: &wgpu::ComputePipeline,
+    bind_group: &wgpu::BindGroup,
+    workgroups: u32,
+) {
+    let (x, y) = workgroup_grid(workgroups);
+    let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+        label: None,
+        timestamp_writes: None,
+    });
+    pass.set_pipeline(pipeline);
+    pass.set_bind_group(0, bind_group, &[]);
+    pass.dispatch_workgroups(x, y, 1);
+}
+
+/// Create a bind group for group 0 of a pipeline from buffers bound in order starting at binding 0
+pub(crate) fn bind_buffers(
+    device: &wgpu::Device,
+    pipeline: &wgpu::ComputePipeline,
+    buffers: &[&wgpu::Buffer],
+) -> wgpu::BindGroup {
+    let entries: Vec<wgpu::BindGroupEntry> = buffers
+        .iter()
+        .enumerate()
+        .map(|(i, buffer)| wgpu::BindGroupEntry {
+            binding: i as u32,
+            resource: buffer.as_entire_binding(),
+        })
+        .collect();
+    device.create_bind_group(&wgpu::BindGroupDescriptor {
+        label: None,
+        layout: &pipeline.get_bind_group_layout(0),
+        entries: &entries,
+    })
+}
@@ -0,0 +1,205 @@
+/**
+ * Adapter Selection Tests
+ *
+ * Tests for choosing specific adapters (fallback/software adapters, backends, ...).
+ * Software and GL adapters aren't present everywhere, so some tests tolerate their absence.
+ */
+
+import { describe, test, expect } from 'bun:test'
+import { Gpu, GPUBufferUsage, GPUShaderStage } from '../webgpu.js'
+
+describe('Adapter Selection: Fallback Adapter', () => {
+  test('should only return software adapters when forced', async () => {
+    const gpu = Gpu()
+    let adapter = null
+    try {
+      adapter = await gpu.requestAdapter({ forceFallbackAdapter: true })
+    } catch (e) {
+      // No software adapter installed on this machine
+      expect(e.message).toContain('fallback')
+      return
+    }
+
+    expect(adapter.isFallbackAdapter).toBe(true)
+    expect(adapter.info.deviceType).toBe('Cpu')
+
+    const device = await adapter.requestDevice()
+    expect(device).toBeDefined()
+    device.destroy()
+  })
+
+  test('should report isFallbackAdapter consistently with device type', async () => {
+    const adapter = await Gpu().requestAdapter()
+    expect(adapter.isFallbackAdapter).toBe(adapter.info.deviceType === 'Cpu')
+  })
+})
+
+describe('Adapter Selection: Enumeration', () => {
+  test('should enumerate structured adapter info', () => {
+    const adapters = Gpu().enumerateAdapters()
+    expect(adapters.length).toBeGreaterThan(0)
+    for (const info of adapters) {
+      expect(typeof info.name).toBe('string')
+      expect(typeof info.vendor).toBe('number')
+      expect(typeof info.device).toBe('number')
+      expect(typeof info.deviceType).toBe('string')
+      expect(typeof info.backend).toBe('string')
+      expect(typeof info.driver).toBe('string')
+      expect(typeof info.driverInfo).toBe('string')
+    }
+  })
+
+  test('should report the Vulkan API version', () => {
+    for (const info of Gpu().enumerateAdapters()) {
+      if (info.backend === 'Vulkan') {
+        expect(info.backendVersion).toMatch(/^1\.\d+\.\d+$/)
+      } else {
+        expect(info.backendVersion).toBeUndefined()
+      }
+    }
+  })
+
+  test('should request an adapter by index', async () => {
+    const gpu = Gpu()
+    const adapters = gpu.enumerateAdapters()
+    const index = adapters.length - 1
+
+    const adapter = gpu.requestAdapterByIndex(index)
+    expect(adapter.info).toEqual(adapters[index])
+
+    const device = await adapter.requestDevice()
+    expect(device).toBeDefined()
+    device.destroy()
+  })
+
+  test('should reject out-of-range indices', () => {
+    const gpu = Gpu()
+    expect(() => gpu.requestAdapterByIndex(gpu.enumerateAdapters().length)).toThrow()
+  })
+})
+
+describe('Adapter Selection: Selector', () => {
+  const DEVICE_TYPES = { DiscreteGpu: 'discrete', IntegratedGpu: 'integrated', VirtualGpu: 'virtual', Cpu: 'cpu', Other: 'other' }
+
+  test('should match vendor and device IDs', async () => {
+    const gpu = Gpu()
+    const [info] = gpu.enumerateAdapters().slice(-1)
+    if (!info) return
+
+    const adapter = await gpu.requestAdapter({ selector: { vendor: info.vendor, device: info.device } })
+    expect(adapter.info.vendor).toBe(info.vendor)
+    expect(adapter.info.device).toBe(info.device)
+  })
+
+  test('should prefer the device type, then the backend', async () => {
+    const gpu = Gpu()
+    for (const info of gpu.enumerateAdapters()) {
+      const deviceType = DEVICE_TYPES[info.deviceType]
+      const backend = info.backend.toLowerCase()
+      const adapter = await gpu.requestAdapter({ selector: { deviceType, backend } })
+
+      expect(adapter.info.deviceType).toBe(info.deviceType)
+      expect(adapter.info.backend).toBe(info.backend)
+    }
+  })
+
+  test('should reject unmatched IDs and unknown device types', async () => {
+    const gpu = Gpu()
+    await expect(gpu.requestAdapter({ selector: { vendor: 0xfffe } })).rejects.toThrow('No adapter matches the selector (vendor 0xfffe)')
+    await expect(gpu.requestAdapter({ selector: { deviceType: 'gpu' } })).rejects.toThrow('Unknown device type: gpu')
+    await expect(gpu.requestAdapter({ selector: { backend: 'opengl' } })).rejects.toThrow('Unknown backend: opengl')
+  })
+})
+
+describe('Adapter Selection: Backends', () => {
+  test('should only enumerate adapters of the requested backends', () => {
+    for (const info of Gpu({ backends: ['gl'] }).enumerateAdapters()) {
+      expect(info.backend).toBe('Gl')
+    }
+  })
+
+  test('should reject unknown or empty backend lists', () => {
+    expect(() => Gpu({ backends: ['opengl'] })).toThrow('Unknown backend: opengl')
+    expect(() => Gpu({ backends: [] })).toThrow('at least one backend')
+  })
+
+  test('should run compute on the GL backend', async () => {
+    const gpu = Gpu({ backends: ['gl'] })
+    if (gpu.enumerateAdapters().length === 0) return // no EGL / OpenGL driver
+
+    const adapter = await gpu.requestAdapter()
+    const downlevel = adapter.getDownlevelCapabilities()
+    expect(typeof downlevel.compliant).toBe('boolean')
+    expect(downlevel.flags.includes('compute-shaders')).toBe(downlevel.computeShaders)
+    if (!downlevel.computeShaders) return
+
+    const device = await adapter.requestDevice()
+    // Device limits are the tier the adapter supports, never more
+    expect(device.limits.maxBufferSize).toBeLessThanOrEqual(Number(adapter.limits.maxBufferSize))
+
+    const module = device.createShaderModule({
+      code: `
+        @group(0) @binding(0) var<storage, read_write> data: array<u32>;
+
+        @compute @workgroup_size(4)
+        fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+          data[id.x] = id.x * 2u;
+        }
+      `
+    })
+    const layout = device.createBindGroupLayout({
+      entries: [{ binding: 0, visibility: GPUShaderStage.COMPUTE, buffer: { type: 'storage' } }]
+    })
+    const pipeline = device.createComputePipeline({
+      layout: device.createPipelineLayout({ bindGroupLayouts: [layout] }),
+      compute: { module, entryPoint: 'main' }
+    })
+    const storage = device.createBuffer({ size: 16, usage: GPUBufferUsage.STORAGE | GPUBufferUsage.COPY_SRC })
+    const readback = device.createBuffer({ size: 16, usage: GPUBufferUsage.MAP_READ | GPUBufferUsage.COPY_DST })
+    const bindGroup = device.createBindGroup({
+      layout,
+      entries: [{ binding: 0, resource: { buffer: storage } }]
+    })
+
+    const encoder = device.createCommandEncoder()
+    const pass = encoder.beginComputePass()
+    pass.setPipeline(pipeline)
+    pass.setBindGroup(0, bindGroup)
+    pass.dispatchWorkgroups(1)
+    pass.end()
+    encoder.copyBufferToBuffer(storage, 0, readback, 0, 16)
+    device.queue.submit([encoder.finish()])
+
+    await readback.mapAsync('READ')
+    expect(Array.from(new Uint32Array(readback.getMappedRange()))).toEqual([0, 2, 4, 6])
+    readback.unmap()
+    device.destroy()
+  })
+})
+
+// Option tests leave out the GL backend: dropping an instance closes the EGL
+// display that GL devices of other instances share
+describe('Adapter Selection: DX12 Shader Compiler', () => {
+  test('should accept fxc and dxc', () => {
+    expect(() => Gpu({ backends: ['dx12'], dx12ShaderCompiler: 'dxc' })).not.toThrow()
+    expect(() => Gpu({ backends: ['dx12'], dx12ShaderCompiler: 'fxc' })).not.toThrow()
+  })
+
+  test('should reject unknown compilers and DLL paths without dxc', () => {
+    expect(() => Gpu({ dx12ShaderCompiler: 'slang' })).toThrow('Unknown DX12 shader compiler: slang')
+    expect(() => Gpu({ dx12ShaderCompiler: 'fxc', dxcPath: 'dxc' })).toThrow("require dx12ShaderCompiler: 'dxc'")
+    expect(() => Gpu({ dxilPath: 'dxil.dll' })).toThrow("require dx12ShaderCompiler: 'dxc'")
+  })
+})
+
+describe('Adapter Selection: Backend Options', () => {
+  test('should accept instance flags and GLES versions', () => {
+    expect(() => Gpu({ backends: ['vulkan'], instanceFlags: ['validation', 'discard-hal-labels'] })).not.toThrow()
+    expect(() => Gpu({ backends: ['vulkan'], instanceFlags: [], glesMinorVersion: 0 })).not.toThrow()
+  })
+
+  test('should reject unknown flags and GLES versions', () => {
+    expect(() => Gpu({ instanceFlags: ['fast'] })).toThrow('Unknown instance flag: fast')
+    expect(() => Gpu({ glesMinorVersion: 3 })).toThrow('Unsupported GLES minor version: 3')
+  })
+})
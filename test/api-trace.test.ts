@@ -0,0 +1,91 @@
+/**
+ * API Trace Tests
+ *
+ * enableApiTrace() records calls into the native binding with their
+ * arguments and resource labels, in a ring buffer, a callback and a file.
+ */
+
+import { describe, test, expect, beforeAll, afterAll } from 'bun:test'
+import { readFileSync, mkdtempSync } from 'fs'
+import { tmpdir } from 'os'
+import { join } from 'path'
+import { Gpu, GPUBufferUsage, enableApiTrace, disableApiTrace, getApiTrace } from '../webgpu.js'
+
+let device
+
+beforeAll(async () => {
+  const adapter = await Gpu().requestAdapter()
+  device = await adapter.requestDevice()
+})
+
+afterAll(() => {
+  disableApiTrace()
+  device?.destroy()
+})
+
+describe('API Trace: Recording', () => {
+  test('should record calls with arguments and labels', () => {
+    enableApiTrace()
+    const buffer = device.createBuffer({ label: 'vertices', size: 64, usage: GPUBufferUsage.COPY_DST })
+    device.queue.writeBuffer(buffer, 0, new Float32Array(16))
+    disableApiTrace()
+
+    const calls = getApiTrace().map((entry) => entry.call)
+    expect(calls).toContain('GpuDevice.createBuffer({ label: "vertices", size: 64, usage: 8 })')
+    expect(calls.some((call) => call.startsWith('GpuQueue.writeBuffer(GpuBuffer("vertices"), 0, Buffer(64)'))).toBe(true)
+  })
+
+  test('should stop recording once disabled', () => {
+    enableApiTrace()
+    device.createBuffer({ size: 16, usage: GPUBufferUsage.COPY_DST })
+    disableApiTrace()
+    const recorded = getApiTrace().length
+    device.createBuffer({ size: 16, usage: GPUBufferUsage.COPY_DST })
+
+    expect(getApiTrace().length).toBe(recorded)
+  })
+
+  test('should keep the last calls up to the capacity', () => {
+    enableApiTrace({ capacity: 3 })
+    for (let size = 4; size <= 20; size += 4) {
+      device.createBuffer({ size, usage: GPUBufferUsage.COPY_DST })
+    }
+    disableApiTrace()
+
+    const entries = getApiTrace()
+    expect(entries.map((entry) => entry.call.match(/size: (\d+)/)[1])).toEqual(['12', '16', '20'])
+    expect(entries[2].sequence).toBe(entries[0].sequence + 2)
+  })
+
+  test('should record errors thrown by calls', () => {
+    enableApiTrace()
+    expect(() => device.createBuffer({ size: 6, usage: GPUBufferUsage.COPY_DST, mappedAtCreation: true })).toThrow()
+    disableApiTrace()
+
+    expect(getApiTrace().at(-1).error).toMatch(/createBuffer.*validation failed/)
+  })
+})
+
+describe('API Trace: Outputs', () => {
+  test('should pass each call to the callback', () => {
+    const calls = []
+    enableApiTrace({ callback: (entry) => calls.push(entry.call) })
+    device.createSampler({ label: 'linear' })
+    disableApiTrace()
+
+    expect(calls).toEqual(['GpuDevice.createSampler({ label: "linear" })'])
+  })
+
+  test('should write calls to a bounded file', () => {
+    const file = join(mkdtempSync(join(tmpdir(), 'webgpu-trace-')), 'trace.log')
+    enableApiTrace({ capacity: 2, file })
+    for (let size = 4; size <= 28; size += 4) {
+      device.createBuffer({ size, usage: GPUBufferUsage.COPY_DST })
+    }
+    disableApiTrace()
+
+    const lines = readFileSync(file, 'utf8').trim().split('\n')
+    expect(lines.length).toBeLessThanOrEqual(4)
+    expect(lines.at(-1)).toMatch(/^#\d+ \S+ GpuDevice\.createBuffer\(\{ size: 28, usage: 8 \}\)$/)
+  })
+})
@@ -0,0 +1,201 @@
+/**
+ * Batched Recording Tests
+ *
+ * dispatchWorkgroupsBatch / drawBatch / drawIndexedBatch record many calls
+ * from one Uint32Array; the results must match the individual calls.
+ * Draws fill one quadrant of a 4x4 target per instance, selected by
+ * instance_index, as in the instancing tests.
+ */
+
+import { describe, test, expect, beforeAll, afterAll } from 'bun:test'
+import { Gpu, GPUBufferUsage, GPUTextureUsage, GPUShaderStage } from '../webgpu.js'
+
+const SIZE = 4
+
+let device
+let pipeline
+
+const SHADER = `
+  var<private> CORNERS: array<vec2<f32>, 6> = array<vec2<f32>, 6>(
+    vec2<f32>(0.0, 0.0), vec2<f32>(1.0, 0.0), vec2<f32>(0.0, 1.0),
+    vec2<f32>(0.0, 1.0), vec2<f32>(1.0, 0.0), vec2<f32>(1.0, 1.0)
+  );
+
+  @vertex
+  fn vs_main(@builtin(vertex_index) v: u32, @builtin(instance_index) i: u32) -> @builtin(position) vec4<f32> {
+    let cell = vec2<f32>(f32(i % 2u), f32(i / 2u));
+    return vec4<f32>(cell + CORNERS[v % 6u] - 1.0, 0.5, 1.0);
+  }
+
+  @fragment
+  fn fs_main() -> @location(0) vec4<f32> {
+    return vec4<f32>(0.0, 1.0, 0.0, 1.0);
+  }
+`
+
+// Pixel sampled from each instance's quadrant (instance 0 is bottom-left)
+const QUADRANT_PIXELS = [[0, 3], [3, 3], [0, 0], [3, 0]]
+
+async function render(record) {
+  const target = device.createTexture({
+    width: SIZE,
+    height: SIZE,
+    format: 'rgba8unorm',
+    usage: GPUTextureUsage.RENDER_ATTACHMENT | GPUTextureUsage.COPY_SRC
+  })
+  const readback = device.createBuffer({
+    size: 256 * SIZE,
+    usage: GPUBufferUsage.COPY_DST | GPUBufferUsage.MAP_READ
+  })
+
+  const encoder = device.createCommandEncoder()
+  const pass = encoder.beginRenderPass({
+    colorAttachments: [{
+      view: target.createView(),
+      loadOp: 'clear',
+      storeOp: 'store',
+      clearValue: { r: 0, g: 0, b: 0, a: 1 }
+    }]
+  })
+  record(pass)
+  pass.end()
+  encoder.copyTextureToBuffer(target, 0, 0, 0, 0, readback, 0, 256, SIZE, SIZE, SIZE, 1)
+  device.queue.submit([encoder.finish()])
+
+  await readback.mapAsync('READ')
+  const pixels = new Uint8Array(readback.getMappedRange())
+  // Which instances (quadrants) were drawn
+  const drawn = QUADRANT_PIXELS.map(([x, y]) => pixels[y * 256 + x * 4 + 1] === 255)
+  readback.unmap()
+  return drawn
+}
+
+beforeAll(async () => {
+  const adapter = await Gpu().requestAdapter()
+  device = await adapter.requestDevice()
+  const module = device.createShaderModule({ code: SHADER })
+  pipeline = device.createRenderPipeline({
+    layout: null,
+    vertex: { module, entryPoint: 'vs_main' },
+    fragment: { module, entryPoint: 'fs_main', targets: [{ format: 'rgba8unorm' }] }
+  })
+})
+
+afterAll(() => {
+  device?.destroy()
+})
+
+describe('Batched Recording: Draws', () => {
+  test('should record each draw in the batch', async () => {
+    const drawn = await render((pass) => {
+      pass.setPipeline(pipeline)
+      pass.drawBatch(new Uint32Array([
+        6, 1, 0, 0,
+        6, 1, 6, 3
+      ]))
+    })
+    expect(drawn).toEqual([true, false, false, true])
+  })
+
+  test('should read baseVertex as signed in indexed draws', async () => {
+    const indices = device.createBufferInit({
+      data: new Uint16Array([6, 7, 8, 9, 10, 11]),
+      usage: GPUBufferUsage.INDEX
+    })
+    const drawn = await render((pass) => {
+      pass.setPipeline(pipeline)
+      pass.setIndexBuffer(indices, 'uint16')
+      pass.drawIndexedBatch(new Uint32Array([
+        6, 1, 0, -6 >>> 0, 1,
+        6, 1, 0, 0, 2
+      ]))
+    })
+    expect(drawn).toEqual([false, true, true, false])
+  })
+
+  test('should record batches into render bundles', async () => {
+    const bundleEncoder = device.createRenderBundleEncoder({ colorFormats: ['rgba8unorm'] })
+    bundleEncoder.setPipeline(pipeline)
+    bundleEncoder.drawBatch(new Uint32Array([6, 2, 0, 2]))
+    const bundle = bundleEncoder.finish()
+
+    expect(await render((pass) => pass.executeBundles([bundle]))).toEqual([false, false, true, true])
+  })
+})
+
+describe('Batched Recording: Dispatches', () => {
+  test('should set per-dispatch dynamic offsets', async () => {
+    // Each dispatch writes its workgroup count into its own 256-byte slice
+    const module = device.createShaderModule({
+      code: `
+        @group(0) @binding(0) var<storage, read_write> slice: array<u32, 4>;
+
+        @compute @workgroup_size(1)
+        fn main(@builtin(workgroup_id) id: vec3<u32>, @builtin(num_workgroups) count: vec3<u32>) {
+          slice[id.x] = count.x;
+        }
+      `
+    })
+    const layout = device.createBindGroupLayout({
+      entries: [{
+        binding: 0,
+        visibility: GPUShaderStage.COMPUTE,
+        buffer: { type: 'storage', hasDynamicOffset: true }
+      }]
+    })
+    const computePipeline = device.createComputePipeline({
+      layout: device.createPipelineLayout({ bindGroupLayouts: [layout] }),
+      compute: { module, entryPoint: 'main' }
+    })
+    const storage = device.createBuffer({ size: 768, usage: GPUBufferUsage.STORAGE | GPUBufferUsage.COPY_SRC })
+    const readback = device.createBuffer({ size: 768, usage: GPUBufferUsage.MAP_READ | GPUBufferUsage.COPY_DST })
+    const bindGroup = device.createBindGroup({
+      layout,
+      entries: [{ binding: 0, resource: { buffer: storage, size: 16 } }]
+    })
+
+    const encoder = device.createCommandEncoder()
+    const pass = encoder.beginComputePass()
+    pass.setPipeline(computePipeline)
+    pass.dispatchWorkgroupsBatch(
+      new Uint32Array([1, 1, 1, 2, 1, 1, 3, 1, 1]),
+      0,
+      bindGroup,
+      new Uint32Array([0, 256, 512])
+    )
+    pass.end()
+    encoder.copyBufferToBuffer(storage, 0, readback, 0, 768)
+    device.queue.submit([encoder.finish()])
+
+    await readback.mapAsync('READ')
+    const words = new Uint32Array(readback.getMappedRange())
+    const slices = [0, 1, 2].map((i) => Array.from(words.slice(i * 64, i * 64 + 4)))
+    readback.unmap()
+    expect(slices).toEqual([[1, 0, 0, 0], [2, 2, 0, 0], [3, 3, 3, 0]])
+  })
+})
+
+describe('Batched Recording: Validation', () => {
+  test('should reject partial items', () => {
+    const pass = device.createCommandEncoder().beginComputePass()
+    expect(() => pass.dispatchWorkgroupsBatch(new Uint32Array([1, 1]))).toThrow('multiple of 3')
+    pass.end()
+  })
+
+  test('should reject dynamic offsets that do not split evenly', () => {
+    const layout = device.createBindGroupLayout({
+      entries: [{ binding: 0, visibility: GPUShaderStage.COMPUTE, buffer: { type: 'storage', hasDynamicOffset: true } }]
+    })
+    const storage = device.createBuffer({ size: 512, usage: GPUBufferUsage.STORAGE })
+    const bindGroup = device.createBindGroup({
+      layout,
+      entries: [{ binding: 0, resource: { buffer: storage, size: 16 } }]
+    })
+    const pass = device.createCommandEncoder().beginComputePass()
+    expect(() => pass.dispatchWorkgroupsBatch(new Uint32Array([1, 1, 1, 1, 1, 1]), 0, bindGroup, new Uint32Array([0, 256, 0])))
+      .toThrow('multiple of the batch size 2')
+    expect(() => pass.dispatchWorkgroupsBatch(new Uint32Array([1, 1, 1]), null, null, new Uint32Array([0])))
+      .toThrow('require a bindGroup')
+    pass.end()
+  })
+})
@@ -0,0 +1,120 @@
+/**
+ * Buffer Binding Array Tests
+ *
+ * Layout entries with `count` declare binding arrays, and createBindGroup()
+ * takes an array of buffer bindings for them. Kernels run only on adapters
+ * with the 'wgpu-buffer-binding-array' and
+ * 'wgpu-storage-resource-binding-array' features.
+ */
+
+import { describe, test, expect, beforeAll, afterAll } from 'bun:test'
+import { Gpu, GPUBufferUsage, GPUShaderStage } from '../webgpu.js'
+
+const FEATURES = ['wgpu-buffer-binding-array', 'wgpu-storage-resource-binding-array']
+
+let device
+let supported
+
+// Each invocation copies the first word of one input buffer
+const SHADER = `
+  struct Word { value: u32 }
+  @group(0) @binding(0) var<storage, read> inputs: binding_array<Word, 4>;
+  @group(0) @binding(1) var<storage, read_write> output: array<u32, 4>;
+
+  @compute @workgroup_size(1)
+  fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    output[id.x] = inputs[id.x].value;
+  }
+`
+
+beforeAll(async () => {
+  const adapter = await Gpu().requestAdapter()
+  supported = FEATURES.every((feature) => adapter.features.includes(feature))
+  device = await adapter.requestDevice({ requiredFeatures: supported ? FEATURES : [] })
+})
+
+afterAll(() => {
+  device?.destroy()
+})
+
+function createLayout(count) {
+  return device.createBindGroupLayout({
+    entries: [
+      { binding: 0, visibility: GPUShaderStage.COMPUTE, buffer: { type: 'read-only-storage' }, count },
+      { binding: 1, visibility: GPUShaderStage.COMPUTE, buffer: { type: 'storage' } }
+    ]
+  })
+}
+
+describe('Buffer Binding Arrays: Kernels', () => {
+  test('should index across the buffers of the array', async () => {
+    if (!supported) return
+
+    const inputs = [10, 20, 30, 40].map((value) =>
+      device.createBufferInit({ data: new Uint32Array([value, 0, 0, 0]), usage: GPUBufferUsage.STORAGE })
+    )
+    const output = device.createBuffer({ size: 16, usage: GPUBufferUsage.STORAGE | GPUBufferUsage.COPY_SRC })
+    const layout = createLayout(4)
+    const pipeline = device.createComputePipeline({
+      layout: device.createPipelineLayout({ bindGroupLayouts: [layout] }),
+      compute: { module: device.createShaderModule({ code: SHADER }), entryPoint: 'main' }
+    })
+    const bindGroup = device.createBindGroup({
+      layout,
+      entries: [
+        { binding: 0, resource: inputs.map((buffer) => ({ buffer, size: 4 })) },
+        { binding: 1, resource: { buffer: output } }
+      ]
+    })
+
+    const encoder = device.createCommandEncoder()
+    const pass = encoder.beginComputePass()
+    pass.setPipeline(pipeline)
+    pass.setBindGroup(0, bindGroup)
+    pass.dispatchWorkgroups(4)
+    pass.end()
+    device.queue.submit([encoder.finish()])
+
+    expect(Array.from(new Uint32Array(output.readSync()))).toEqual([10, 20, 30, 40])
+  })
+})
+
+describe('Buffer Binding Arrays: Validation', () => {
+  test('should require the feature for array layouts', () => {
+    if (supported) return
+
+    expect(() => createLayout(4)).toThrow(/createBindGroupLayout.*validation failed[\s\S]*BUFFER_BINDING_ARRAY/)
+  })
+
+  test('should require the feature for array bind groups', () => {
+    if (supported) return
+    const layout = createLayout(undefined)
+    const buffer = device.createBuffer({ size: 16, usage: GPUBufferUsage.STORAGE })
+
+    expect(() =>
+      device.createBindGroup({
+        layout,
+        entries: [
+          { binding: 0, resource: [{ buffer }, { buffer }] },
+          { binding: 1, resource: { buffer } }
+        ]
+      })
+    ).toThrow(/binding 0 is a buffer array, which needs the 'wgpu-buffer-binding-array' feature/)
+  })
+
+  test('should reject arrays for bindings that are not arrays', () => {
+    if (!supported) return
+    const layout = createLayout(undefined)
+    const buffer = device.createBuffer({ size: 16, usage: GPUBufferUsage.STORAGE })
+
+    expect(() =>
+      device.createBindGroup({
+        layout,
+        entries: [
+          { binding: 0, resource: [{ buffer }, { buffer }] },
+          { binding: 1, resource: { buffer } }
+        ]
+      })
+    ).toThrow(/createBindGroup.*0 \(buffer-array\).*validation failed/)
+  })
+})
@@ -0,0 +1,81 @@
+/**
+ * createBufferInit Tests
+ *
+ * Buffers are created with their contents in one call; the data is read back
+ * through a copy to check what landed on the GPU.
+ */
+
+import { describe, test, expect, beforeAll, afterAll } from 'bun:test'
+import { Gpu, GPUBufferUsage } from '../webgpu.js'
+
+let device
+
+beforeAll(async () => {
+  const adapter = await Gpu().requestAdapter()
+  device = await adapter.requestDevice()
+})
+
+afterAll(() => {
+  device?.destroy()
+})
+
+async function readBack(buffer, size) {
+  const staging = device.createBuffer({ size, usage: GPUBufferUsage.MAP_READ | GPUBufferUsage.COPY_DST })
+  const encoder = device.createCommandEncoder()
+  encoder.copyBufferToBuffer(buffer, 0, staging, 0, size)
+  device.queue.submit([encoder.finish()])
+  await staging.mapAsync('READ')
+  const bytes = new Uint8Array(staging.getMappedRange().slice(0))
+  staging.unmap()
+  return bytes
+}
+
+describe('createBufferInit: Contents', () => {
+  test('should create a buffer from a TypedArray', async () => {
+    const data = new Float32Array([1.5, -2, 3.25, 4])
+    const buffer = device.createBufferInit({ usage: GPUBufferUsage.STORAGE | GPUBufferUsage.COPY_SRC, data })
+
+    expect(buffer.size).toBe(16)
+    expect(Array.from(new Float32Array((await readBack(buffer, 16)).buffer))).toEqual([1.5, -2, 3.25, 4])
+  })
+
+  test('should only copy the view range of a subarray', async () => {
+    const data = new Uint32Array([1, 2, 3, 4, 5, 6]).subarray(2, 4)
+    const buffer = device.createBufferInit({ usage: GPUBufferUsage.COPY_SRC, data })
+
+    expect(Array.from(new Uint32Array((await readBack(buffer, 8)).buffer))).toEqual([3, 4])
+  })
+
+  test('should pad the size to a multiple of 4 bytes', async () => {
+    const buffer = device.createBufferInit({ usage: GPUBufferUsage.COPY_SRC, data: new Uint8Array([7, 8, 9]) })
+
+    expect(buffer.size).toBe(4)
+    expect(Array.from(await readBack(buffer, 4))).toEqual([7, 8, 9, 0])
+  })
+
+  test('should accept an ArrayBuffer and leave the buffer unmapped', () => {
+    const buffer = device.createBufferInit({ usage: GPUBufferUsage.VERTEX, data: new ArrayBuffer(32) })
+
+    expect(buffer.size).toBe(32)
+    expect(buffer.mapState).toBe('unmapped')
+  })
+})
+
+describe('createBufferInit: Validation', () => {
+  test('should reject data beyond maxBufferSize', async () => {
+    const adapter = await Gpu().requestAdapter()
+    const small = await adapter.requestDevice({ requiredLimits: { maxBufferSize: 1024 } })
+    const data = new Uint8Array(2048)
+
+    expect(() => small.createBufferInit({ label: 'big', usage: GPUBufferUsage.STORAGE, data }))
+      .toThrow("createBufferInit(label: 'big', size: 2048, usage: 0x80): Buffer size 2048 is greater than the maximum buffer size (1024)")
+
+    small.pushErrorScope('validation')
+    small.createBufferInit({ usage: GPUBufferUsage.STORAGE, data })
+    expect((await small.popErrorScope()).message).toContain('maximum buffer size')
+
+    // The scope stack is still balanced
+    expect(small.createBufferInit({ usage: GPUBufferUsage.STORAGE, data: new Uint8Array(16) }).size).toBe(16)
+    small.destroy()
+  })
+})
@@ -0,0 +1,121 @@
+/**
+ * Chunked Buffer Tests
+ *
+ * device.createChunkedBuffer() splits a dataset across storage buffers no
+ * larger than a binding, and dispatch() runs a pipeline over every chunk.
+ */
+
+import { describe, test, expect, beforeAll, afterAll } from 'bun:test'
+import { Gpu, GPUBufferUsage } from '../webgpu.js'
+
+let device
+
+// Scales each element and adds its index in the whole dataset
+const SCALE = `
+  struct ChunkInfo { index: u32, elementCount: u32, elementOffset: vec2<u32> }
+
+  @group(0) @binding(0) var<storage, read_write> data: array<u32>;
+  @group(0) @binding(1) var<uniform> info: ChunkInfo;
+  @group(1) @binding(0) var<uniform> scale: u32;
+
+  @compute @workgroup_size(8)
+  fn main(
+    @builtin(workgroup_id) workgroup: vec3<u32>,
+    @builtin(num_workgroups) workgroups: vec3<u32>,
+    @builtin(local_invocation_index) local: u32
+  ) {
+    let i = (workgroup.x + workgroup.y * workgroups.x) * 8u + local;
+    if (i >= info.elementCount) {
+      return;
+    }
+    data[i] = data[i] * scale + info.elementOffset.x + i;
+  }
+`
+
+beforeAll(async () => {
+  const adapter = await Gpu().requestAdapter()
+  device = await adapter.requestDevice()
+})
+
+afterAll(() => {
+  device?.destroy()
+})
+
+describe('Chunked Buffer: Layout', () => {
+  test('should split the dataset into chunks of whole elements', () => {
+    const chunked = device.createChunkedBuffer({ size: 200, elementSize: 8, maxChunkSize: 60 })
+
+    // 56 bytes, the most whole elements that fit 60
+    expect(chunked.chunkCount).toBe(4)
+    expect(chunked.chunks.map(({ byteOffset, byteSize, elementOffset, elementCount }) => [byteOffset, byteSize, elementOffset, elementCount])).toEqual([
+      [0, 56, 0, 7],
+      [56, 56, 7, 7],
+      [112, 56, 14, 7],
+      [168, 32, 21, 4]
+    ])
+  })
+
+  test('should default to chunks as large as a storage binding', () => {
+    const chunked = device.createChunkedBuffer({ size: 1024 })
+    expect(chunked.chunkCount).toBe(1)
+    expect(chunked.chunks[0].buffer.size).toBe(1024)
+  })
+
+  test('should reject sizes it cannot split', () => {
+    expect(() => device.createChunkedBuffer({ size: 30, elementSize: 8 })).toThrow(/positive multiple of elementSize \(8\), got 30/)
+    expect(() => device.createChunkedBuffer({ size: 32, elementSize: 6 })).toThrow(/elementSize must be a non-zero multiple of 4/)
+    const limit = Math.min(device.limits.maxStorageBufferBindingSize, device.limits.maxBufferSize)
+    expect(() => device.createChunkedBuffer({ size: 32, maxChunkSize: limit + 4 })).toThrow(/maxChunkSize must be between 1 and/)
+  })
+})
+
+describe('Chunked Buffer: Data', () => {
+  test('should write and read ranges across chunk boundaries', async () => {
+    const chunked = device.createChunkedBuffer({ size: 64, maxChunkSize: 24 })
+    const values = Uint32Array.from({ length: 16 }, (_, i) => i + 1)
+    chunked.write(0, values)
+    chunked.write(20, new Uint32Array([100, 101]))
+
+    const all = await chunked.read()
+    expect(Array.from(new Uint32Array(all.buffer, all.byteOffset, 16))).toEqual([1, 2, 3, 4, 5, 100, 101, 8, 9, 10, 11, 12, 13, 14, 15, 16])
+    const part = await chunked.read(16, 16)
+    expect(Array.from(new Uint32Array(part.buffer, part.byteOffset, 4))).toEqual([5, 100, 101, 8])
+  })
+
+  test('should reject unaligned and out of range accesses', async () => {
+    const chunked = device.createChunkedBuffer({ size: 64 })
+    expect(() => chunked.write(2, new Uint8Array(4))).toThrow(/write: range \[2, 6\) must be 4-byte aligned/)
+    await expect(chunked.read(60, 8)).rejects.toThrow(/read: range \[60, 68\) must be 4-byte aligned and within the dataset \(64 bytes\)/)
+  })
+})
+
+describe('Chunked Buffer: Dispatch', () => {
+  test('should run a pipeline over every chunk', async () => {
+    const pipeline = device.createComputePipeline({
+      layout: 'auto',
+      compute: { module: device.createShaderModule({ code: SCALE }), entryPoint: 'main' }
+    })
+    // 50 elements in chunks of 16, 16, 16 and 2
+    const chunked = device.createChunkedBuffer({ label: 'dataset', size: 200, maxChunkSize: 64 })
+    chunked.write(0, new Uint32Array(50).fill(1))
+    const scale = device.createBuffer({ size: 16, usage: GPUBufferUsage.UNIFORM | GPUBufferUsage.COPY_DST })
+    device.queue.writeBuffer(scale, 0, new Uint32Array([3, 0, 0, 0]))
+
+    const bindGroups = chunked.createBindGroups({ layout: pipeline.getBindGroupLayout(0), binding: 0, infoBinding: 1 })
+    const shared = device.createBindGroup({ layout: pipeline.getBindGroupLayout(1), entries: [{ binding: 0, resource: { buffer: scale } }] })
+    expect(bindGroups.length).toBe(4)
+    chunked.dispatch({ pipeline, bindGroups, sharedBindGroups: [null, shared] })
+
+    const result = await chunked.read()
+    expect(Array.from(new Uint32Array(result.buffer, result.byteOffset, 50))).toEqual(Array.from({ length: 50 }, (_, i) => 3 + i))
+  })
+
+  test('should need one bind group per chunk', () => {
+    const pipeline = device.createComputePipeline({
+      layout: 'auto',
+      compute: { module: device.createShaderModule({ code: SCALE }), entryPoint: 'main' }
+    })
+    const chunked = device.createChunkedBuffer({ size: 64, maxChunkSize: 32 })
+    expect(() => chunked.dispatch({ pipeline, bindGroups: [] })).toThrow(/one bind group per chunk \(2\), got 0/)
+  })
+})
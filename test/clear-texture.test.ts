@@ -0,0 +1,99 @@
+/**
+ * Clear Texture Tests
+ *
+ * encoder.clearTexture() zeroes texture subresources on devices with the
+ * 'wgpu-clear-texture' feature, and names the feature otherwise.
+ */
+
+import { describe, test, expect, beforeAll, afterAll } from 'bun:test'
+import { Gpu, GPUBufferUsage, GPUTextureUsage } from '../webgpu.js'
+
+let adapter
+let device
+let supported
+
+beforeAll(async () => {
+  adapter = await Gpu().requestAdapter()
+  supported = adapter.features.includes('wgpu-clear-texture')
+  device = await adapter.requestDevice({ requiredFeatures: supported ? ['wgpu-clear-texture'] : [] })
+})
+
+afterAll(() => {
+  device?.destroy()
+})
+
+// 4x4 rgba8unorm layers filled with 0xff
+function createFilledTexture(layers) {
+  return device.createTexture({
+    size: [4, 4, layers],
+    format: 'rgba8unorm',
+    usage: GPUTextureUsage.COPY_SRC | GPUTextureUsage.COPY_DST,
+    data: new Uint8Array(4 * 4 * 4 * layers).fill(0xff)
+  })
+}
+
+// The first byte of each layer
+function readLayers(texture, layers) {
+  const buffer = device.createBuffer({ size: 256 * 4 * layers, usage: GPUBufferUsage.COPY_DST | GPUBufferUsage.COPY_SRC })
+  const encoder = device.createCommandEncoder()
+  encoder.copyTextureToBuffer(
+    { texture },
+    { buffer, bytesPerRow: 256, rowsPerImage: 4 },
+    { width: 4, height: 4, depthOrArrayLayers: layers }
+  )
+  device.queue.submit([encoder.finish()])
+  const bytes = new Uint8Array(buffer.readSync())
+  return Array.from({ length: layers }, (_, layer) => bytes[layer * 256 * 4])
+}
+
+describe('Clear Texture', () => {
+  test('should clear a whole texture', () => {
+    if (!supported) return
+    const texture = createFilledTexture(2)
+
+    const encoder = device.createCommandEncoder()
+    encoder.clearTexture(texture)
+    device.queue.submit([encoder.finish()])
+
+    expect(readLayers(texture, 2)).toEqual([0, 0])
+  })
+
+  test('should clear only the requested array layers', () => {
+    if (!supported) return
+    const texture = createFilledTexture(3)
+
+    const encoder = device.createCommandEncoder()
+    encoder.clearTexture(texture, { baseArrayLayer: 1, arrayLayerCount: 1 })
+    device.queue.submit([encoder.finish()])
+
+    expect(readLayers(texture, 3)).toEqual([255, 0, 255])
+  })
+
+  test('should reject ranges outside the texture', () => {
+    if (!supported) return
+    const texture = device.createTexture({
+      label: 'target',
+      size: [4, 4, 2],
+      format: 'rgba8unorm',
+      usage: GPUTextureUsage.COPY_DST
+    })
+    const encoder = device.createCommandEncoder()
+
+    expect(() => encoder.clearTexture(texture, { baseMipLevel: 3 })).toThrow(
+      "clearTexture: base mip level 3 is outside texture 'target', which has 1 mip levels"
+    )
+    expect(() => encoder.clearTexture(texture, { baseArrayLayer: 1, arrayLayerCount: 2 })).toThrow(
+      "clearTexture: array layers 1..3 are outside texture 'target', which has 2"
+    )
+  })
+
+  test('should name the feature clearing needs', async () => {
+    const plain = await adapter.requestDevice()
+    const texture = plain.createTexture({ size: [4, 4], format: 'rgba8unorm', usage: GPUTextureUsage.COPY_DST })
+
+    expect(() => plain.createCommandEncoder().clearTexture(texture)).toThrow(
+      "clearTexture: needs the 'wgpu-clear-texture' feature"
+    )
+    plain.destroy()
+  })
+})
@@ -0,0 +1,112 @@
+/**
+ * Color Conversion Tests
+ *
+ * blitTexture({ swizzle, colorSpace }) and convertPixels() swap channels and
+ * apply sRGB transfer functions, between textures and packed RGBA8 buffers.
+ */
+
+import { describe, test, expect, beforeAll, afterAll } from 'bun:test'
+import { Gpu, GPUBufferUsage, GPUTextureUsage } from '../webgpu.js'
+
+let device
+
+beforeAll(async () => {
+  const adapter = await Gpu().requestAdapter()
+  device = await adapter.requestDevice()
+})
+
+afterAll(() => {
+  device?.destroy()
+})
+
+function createPixelBuffer(pixels) {
+  const buffer = device.createBuffer({
+    size: Math.max(pixels.length, 4),
+    usage: GPUBufferUsage.STORAGE | GPUBufferUsage.COPY_SRC | GPUBufferUsage.COPY_DST
+  })
+  device.queue.writeBuffer(buffer, 0, pixels)
+  return buffer
+}
+
+async function convert(pixels, options) {
+  const input = createPixelBuffer(pixels)
+  const output = createPixelBuffer(new Uint8Array(pixels.length))
+  const readback = device.createBuffer({ size: pixels.length, usage: GPUBufferUsage.COPY_DST | GPUBufferUsage.MAP_READ })
+
+  const encoder = device.createCommandEncoder()
+  device.convertPixels(encoder, input, output, pixels.length / 4, options)
+  encoder.copyBufferToBuffer(output, 0, readback, 0, pixels.length)
+  device.queue.submit([encoder.finish()])
+
+  await readback.mapAsync('READ')
+  const bytes = Array.from(new Uint8Array(readback.getMappedRange()))
+  readback.unmap()
+  return bytes
+}
+
+describe('Color Conversion: Buffers', () => {
+  test('should swap RGBA and BGRA', async () => {
+    expect(await convert(new Uint8Array([1, 2, 3, 4, 10, 20, 30, 40]), { swizzle: 'bgra' }))
+      .toEqual([3, 2, 1, 4, 30, 20, 10, 40])
+  })
+
+  test('should convert between sRGB and linear', async () => {
+    const [linear] = await convert(new Uint8Array([188, 188, 188, 255]), { colorSpace: 'srgb-to-linear' })
+    expect(Math.abs(linear - 128)).toBeLessThanOrEqual(1)
+
+    const [srgb, , , alpha] = await convert(new Uint8Array([128, 128, 128, 100]), { colorSpace: 'linear-to-srgb' })
+    expect(Math.abs(srgb - 188)).toBeLessThanOrEqual(1)
+    expect(alpha).toBe(100)
+  })
+
+  test('should convert more pixels than one workgroup', async () => {
+    const pixels = new Uint8Array(1000 * 4)
+    for (let i = 0; i < 1000; i++) pixels.set([i & 255, 0, 255, 7], i * 4)
+    const converted = await convert(pixels, { swizzle: 'bgra' })
+    expect(converted.slice(999 * 4)).toEqual([255, 0, 999 & 255, 7])
+  })
+
+  test('should validate options and buffers', () => {
+    const buffer = createPixelBuffer(new Uint8Array(16))
+    const other = createPixelBuffer(new Uint8Array(16))
+    const encoder = device.createCommandEncoder()
+    expect(() => device.convertPixels(encoder, buffer, other, 4, { swizzle: 'rgb' })).toThrow('Invalid swizzle: rgb')
+    expect(() => device.convertPixels(encoder, buffer, other, 4, { colorSpace: 'p3' })).toThrow('Unknown color space conversion: p3')
+    expect(() => device.convertPixels(encoder, buffer, buffer, 4)).toThrow('must be different buffers')
+    expect(() => device.convertPixels(encoder, buffer, other, 5)).toThrow('too small')
+  })
+})
+
+describe('Color Conversion: Textures', () => {
+  test('should swizzle and convert while blitting', async () => {
+    const source = device.createTexture({
+      width: 1,
+      height: 1,
+      format: 'rgba8unorm',
+      usage: GPUTextureUsage.TEXTURE_BINDING | GPUTextureUsage.COPY_DST
+    })
+    const upload = device.createBuffer({ size: 4, usage: GPUBufferUsage.COPY_SRC | GPUBufferUsage.COPY_DST })
+    device.queue.writeBuffer(upload, 0, new Uint8Array([0, 64, 188, 255]))
+    const destination = device.createTexture({
+      width: 1,
+      height: 1,
+      format: 'rgba8unorm',
+      usage: GPUTextureUsage.RENDER_ATTACHMENT | GPUTextureUsage.COPY_SRC
+    })
+    const readback = device.createBuffer({ size: 4, usage: GPUBufferUsage.COPY_DST | GPUBufferUsage.MAP_READ })
+
+    const encoder = device.createCommandEncoder()
+    encoder.copyBufferToTexture({ buffer: upload, bytesPerRow: 256 }, { texture: source }, { width: 1, height: 1 })
+    device.blitTexture(encoder, source, destination, { swizzle: 'bgra', colorSpace: 'srgb-to-linear' })
+    encoder.copyTextureToBuffer({ texture: destination }, { buffer: readback, bytesPerRow: 256 }, { width: 1, height: 1 })
+    device.queue.submit([encoder.finish()])
+
+    await readback.mapAsync('READ')
+    const [r, g, b, a] = Array.from(new Uint8Array(readback.getMappedRange()))
+    readback.unmap()
+    expect(Math.abs(r - 128)).toBeLessThanOrEqual(1)
+    expect(Math.abs(g - 13)).toBeLessThanOrEqual(1)
+    expect(b).toBe(0)
+    expect(a).toBe(255)
+  })
+})
@@ -0,0 +1,89 @@
+/**
+ * Compressed Format Tests
+ *
+ * createTexture() accepts BC, ETC2 and ASTC formats on devices with their
+ * texture-compression feature, and names the feature otherwise.
+ */
+
+import { describe, test, expect, beforeAll, afterAll } from 'bun:test'
+import { Gpu, GPUTextureUsage } from '../webgpu.js'
+
+const FORMATS = {
+  'texture-compression-bc': ['bc1-rgba-unorm', 'bc4-r-snorm', 'bc6h-rgb-ufloat', 'bc7-rgba-unorm-srgb'],
+  'texture-compression-etc2': ['etc2-rgb8unorm', 'etc2-rgba8unorm-srgb', 'eac-rg11snorm'],
+  'texture-compression-astc': ['astc-4x4-unorm', 'astc-12x12-unorm-srgb'],
+  'wgpu-texture-compression-astc-hdr': ['astc-4x4-hdr', 'astc-8x5-hdr']
+}
+
+let adapter
+let device
+let plain
+let supported
+
+beforeAll(async () => {
+  adapter = await Gpu().requestAdapter()
+  supported = Object.keys(FORMATS).filter((feature) => adapter.features.includes(feature))
+  device = await adapter.requestDevice({ requiredFeatures: supported })
+  plain = await adapter.requestDevice()
+})
+
+afterAll(() => {
+  device?.destroy()
+  plain?.destroy()
+})
+
+function createTexture(target, format, usage = GPUTextureUsage.TEXTURE_BINDING | GPUTextureUsage.COPY_DST) {
+  return target.createTexture({ label: format, size: [12, 12], format, usage })
+}
+
+describe('Compressed Formats: Creation', () => {
+  test('should create textures of the formats the device has features for', () => {
+    for (const feature of supported) {
+      for (const format of FORMATS[feature]) {
+        expect(createTexture(device, format).format).toBe(format)
+      }
+    }
+  })
+
+  test('should upload compressed blocks', () => {
+    if (!supported.includes('texture-compression-bc')) return
+    const texture = createTexture(device, 'bc1-rgba-unorm')
+
+    // 3x3 blocks of 8 bytes
+    device.queue.writeTexture({ texture }, new Uint8Array(72), { bytesPerRow: 24, rowsPerImage: 3 }, [12, 12])
+  })
+})
+
+describe('Compressed Formats: Validation', () => {
+  test('should name the feature each format needs', () => {
+    for (const [feature, formats] of Object.entries(FORMATS)) {
+      for (const format of formats) {
+        expect(() => createTexture(plain, format)).toThrow(`format '${format}' needs the '${feature}' feature`)
+      }
+    }
+  })
+
+  test('should name the feature for rg11b10ufloat render attachments', () => {
+    if (adapter.getTextureFormatFeatures('rg11b10ufloat').renderable) return
+
+    createTexture(plain, 'rg11b10ufloat')
+    expect(() => createTexture(plain, 'rg11b10ufloat', GPUTextureUsage.RENDER_ATTACHMENT)).toThrow(
+      "format 'rg11b10ufloat' needs the 'rg11b10ufloat-renderable' feature"
+    )
+  })
+
+  test('should reject unknown formats', () => {
+    expect(() => createTexture(plain, 'bc8-rgba-unorm')).toThrow('Unknown texture format: bc8-rgba-unorm')
+  })
+
+  test('should name the feature on null devices', async () => {
+    const nullDevice = await (await Gpu({ backends: ['null'] }).requestAdapter()).requestDevice()
+
+    expect(() => createTexture(nullDevice, 'astc-4x4-hdr')).toThrow(
+      "Format 'astc-4x4-hdr' needs the 'wgpu-texture-compression-astc-hdr' feature"
+    )
+    expect(() => createTexture(nullDevice, 'rg11b10ufloat', GPUTextureUsage.RENDER_ATTACHMENT)).toThrow(
+      "Format 'rg11b10ufloat' needs the 'rg11b10ufloat-renderable' feature"
+    )
+  })
+})
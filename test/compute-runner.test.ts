@@ -0,0 +1,100 @@
+/**
+ * Compute Runner Tests
+ *
+ * device.createComputeRunner() keeps a pipeline, its bind groups and a
+ * parameter buffer; run() writes the parameters and dispatches in one call.
+ */
+
+import { describe, test, expect, beforeAll, afterAll } from 'bun:test'
+import { Gpu, GPUBufferUsage } from '../webgpu.js'
+
+let device
+
+const ACCUMULATE = `
+  @group(0) @binding(0) var<storage, read_write> data: array<u32>;
+  @group(0) @binding(1) var<uniform> params: vec4<u32>;
+
+  @compute @workgroup_size(1)
+  fn main(@builtin(workgroup_id) id: vec3<u32>) {
+    data[id.x + id.y * 4u] += params.x;
+  }
+`
+
+beforeAll(async () => {
+  const adapter = await Gpu().requestAdapter()
+  device = await adapter.requestDevice()
+})
+
+afterAll(() => {
+  device?.destroy()
+})
+
+function createRunner(descriptor = {}) {
+  const pipeline = device.createComputePipeline({
+    layout: 'auto',
+    compute: { module: device.createShaderModule({ code: ACCUMULATE }), entryPoint: 'main' }
+  })
+  const data = device.createBuffer({ size: 64, usage: GPUBufferUsage.STORAGE | GPUBufferUsage.COPY_SRC })
+  const params = device.createBuffer({ label: 'params', size: 16, usage: GPUBufferUsage.UNIFORM | GPUBufferUsage.COPY_DST })
+  const bindGroup = device.createBindGroup({
+    layout: pipeline.getBindGroupLayout(0),
+    entries: [
+      { binding: 0, resource: { buffer: data } },
+      { binding: 1, resource: { buffer: params } }
+    ]
+  })
+  const runner = device.createComputeRunner({ pipeline, bindGroups: [bindGroup], params, ...descriptor })
+  return { runner, data }
+}
+
+describe('Compute Runner: run', () => {
+  test('should write the parameters of each run before its dispatch', () => {
+    const { runner, data } = createRunner()
+    for (let i = 1; i <= 10; i++) {
+      runner.run(new Uint32Array([i, 0, 0, 0]), 1)
+    }
+
+    expect(new Uint32Array(data.readSync().buffer)[0]).toBe(55)
+  })
+
+  test('should dispatch workgroup counts in up to three dimensions', () => {
+    const { runner, data } = createRunner()
+    runner.run(new Uint32Array([1, 0, 0, 0]), [4, 2])
+    // Without params, the buffer keeps the last ones
+    runner.run(null, [2])
+
+    expect(Array.from(new Uint32Array(data.readSync().buffer, 0, 8))).toEqual([2, 2, 1, 1, 1, 1, 1, 1])
+  })
+
+  test('should write parameters at paramsOffset', () => {
+    const { runner, data } = createRunner({ paramsOffset: 4 })
+    // Lands in params.y, so params.x stays 0
+    runner.run(new Uint32Array([7]), 1)
+
+    expect(new Uint32Array(data.readSync().buffer)[0]).toBe(0)
+  })
+})
+
+describe('Compute Runner: Errors', () => {
+  test('should reject parameters that do not fit the buffer', () => {
+    const { runner } = createRunner()
+    expect(() => runner.run(new Uint32Array(8), 1)).toThrow(/32 bytes of params at offset 0 don't fit buffer 'params' \(16 bytes\)/)
+  })
+
+  test('should report validation errors of the dispatch', () => {
+    const { runner } = createRunner({ label: 'simulate' })
+    expect(() => runner.run(null, device.limits.maxComputeWorkgroupsPerDimension + 1)).toThrow(
+      /^run\(label: 'simulate', workgroups: \[\d+, 1, 1\]\): /
+    )
+  })
+
+  test('should require COPY_DST usage for the params buffer', () => {
+    const params = device.createBuffer({ size: 16, usage: GPUBufferUsage.UNIFORM })
+    const pipeline = device.createComputePipeline({
+      layout: 'auto',
+      compute: { module: device.createShaderModule({ code: ACCUMULATE }), entryPoint: 'main' }
+    })
+
+    expect(() => device.createComputeRunner({ pipeline, params })).toThrow(/needs COPY_DST usage/)
+  })
+})
@@ -0,0 +1,157 @@
+/**
+ * Built-in Compute Utility Tests
+ *
+ * Tests for the native compute helpers exposed on GpuDevice
+ * (prefix sum, ...). Results are verified against CPU references.
+ */
+
+import { describe, test, expect, beforeAll, afterAll } from 'bun:test'
+import { Gpu, GPUBufferUsage } from '../webgpu.js'
+
+let device
+
+async function readBuffer(buffer, size) {
+  const staging = device.createBuffer({
+    size,
+    usage: GPUBufferUsage.MAP_READ | GPUBufferUsage.COPY_DST
+  })
+  const encoder = device.createCommandEncoder()
+  encoder.copyBufferToBuffer(buffer, 0, staging, 0, size)
+  device.queue.submit([encoder.finish()])
+  await staging.mapAsync('READ')
+  const data = staging.getMappedRange().slice(0)
+  staging.unmap()
+  staging.destroy()
+  return data
+}
+
+function storageBuffer(data) {
+  const buffer = device.createBuffer({
+    size: data.byteLength,
+    usage: GPUBufferUsage.STORAGE | GPUBufferUsage.COPY_SRC | GPUBufferUsage.COPY_DST
+  })
+  device.queue.writeBuffer(buffer, 0, Buffer.from(data.buffer))
+  return buffer
+}
+
+beforeAll(async () => {
+  const gpu = Gpu()
+  const adapter = await gpu.requestAdapter()
+  device = await adapter.requestDevice()
+})
+
+afterAll(() => {
+  device?.destroy()
+})
+
+describe('Compute Utilities: Prefix Sum', () => {
+  test('should compute exclusive u32 scan across many blocks', async () => {
+    const count = 100000
+    const input = new Uint32Array(count)
+    for (let i = 0; i < count; i++) input[i] = i % 7
+
+    const src = storageBuffer(input)
+    const dst = storageBuffer(new Uint32Array(count))
+
+    const scan = device.createPrefixSum()
+    scan.run(src, dst, count)
+
+    const result = new Uint32Array(await readBuffer(dst, count * 4))
+    let sum = 0
+    let mismatches = 0
+    for (let i = 0; i < count; i++) {
+      if (result[i] !== sum) mismatches++
+      sum += input[i]
+    }
+    expect(mismatches).toBe(0)
+  })
+
+  test('should compute inclusive f32 scan through an encoder', async () => {
+    const input = new Float32Array([1.5, 2, 0.5, 4, 1])
+    const src = storageBuffer(input)
+    const dst = storageBuffer(new Float32Array(input.length))
+
+    const scan = device.createPrefixSum({ dataType: 'f32' })
+    const encoder = device.createCommandEncoder()
+    scan.encode(encoder, src, dst, input.length, true)
+    device.queue.submit([encoder.finish()])
+
+    const result = new Float32Array(await readBuffer(dst, input.byteLength))
+    expect(Array.from(result)).toEqual([1.5, 3.5, 4, 8, 9])
+  })
+
+  test('should reject invalid configurations', () => {
+    expect(() => device.createPrefixSum({ dataType: 'f64' })).toThrow('Invalid prefix sum data type')
+
+    const src = storageBuffer(new Uint32Array(4))
+    const scan = device.createPrefixSum()
+    expect(() => scan.run(src, src, 4)).toThrow('different buffers')
+  })
+})
+
+describe('Compute Utilities: Radix Sort', () => {
+  test('should sort u32 keys with values', async () => {
+    const count = 5000
+    const keys = new Uint32Array(count)
+    const values = new Uint32Array(count)
+    for (let i = 0; i < count; i++) {
+      keys[i] = (Math.random() * 0xffffffff) >>> 0
+      values[i] = i
+    }
+
+    const keyBuffer = storageBuffer(keys)
+    const valueBuffer = storageBuffer(values)
+    const sort = device.createRadixSort()
+    sort.run(keyBuffer, count, valueBuffer)
+
+    const sortedKeys = new Uint32Array(await readBuffer(keyBuffer, count * 4))
+    const sortedValues = new Uint32Array(await readBuffer(valueBuffer, count * 4))
+    const expected = Array.from(keys).sort((a, b) => a - b)
+    expect(Array.from(sortedKeys)).toEqual(expected)
+    for (let i = 0; i < count; i++) {
+      expect(keys[sortedValues[i]]).toBe(sortedKeys[i])
+    }
+  })
+
+  test('should stably sort low key bits with an odd pass count', async () => {
+    const keys = new Uint32Array([0x105, 0x203, 0x305, 0x401, 0x503])
+    const keyBuffer = storageBuffer(keys)
+    const sort = device.createRadixSort()
+    const encoder = device.createCommandEncoder()
+    sort.encode(encoder, keyBuffer, keys.length, null, 4)
+    device.queue.submit([encoder.finish()])
+
+    const sorted = new Uint32Array(await readBuffer(keyBuffer, keys.byteLength))
+    expect(Array.from(sorted)).toEqual([0x401, 0x203, 0x503, 0x105, 0x305])
+  })
+})
+
+describe('Compute Utilities: Matmul', () => {
+  test('should multiply f32 matrices with partial tiles', async () => {
+    const m = 70, n = 45, k = 33
+    const a = new Float32Array(m * k).map(() => Math.random() * 2 - 1)
+    const b = new Float32Array(k * n).map(() => Math.random() * 2 - 1)
+
+    const matmul = device.createMatmul()
+    const output = matmul.matmul(storageBuffer(a), storageBuffer(b), { m, n, k })
+    const result = new Float32Array(await readBuffer(output, m * n * 4))
+
+    for (let row = 0; row < m; row++) {
+      for (let col = 0; col < n; col++) {
+        let expected = 0
+        for (let i = 0; i < k; i++) {
+          expected += a[row * k + i] * b[i * n + col]
+        }
+        expect(Math.abs(result[row * n + col] - expected)).toBeLessThan(1e-4)
+      }
+    }
+  })
+
+  test('should reject undersized buffers', () => {
+    const matmul = device.createMatmul()
+    const a = storageBuffer(new Float32Array(4))
+    const b = storageBuffer(new Float32Array(4))
+    const output = storageBuffer(new Float32Array(4))
+    expect(() => matmul.run(a, b, output, { m: 4, n: 4, k: 4 })).toThrow()
+  })
+})
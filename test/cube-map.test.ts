@@ -0,0 +1,117 @@
+/**
+ * Cube Map Tests
+ *
+ * Uploads six solid-color faces with writeCubeFaces, binds the texture through
+ * a 'cube' view and samples one direction per face in a compute shader.
+ */
+
+import { describe, test, expect, beforeAll, afterAll } from 'bun:test'
+import { Gpu, GPUBufferUsage, GPUTextureUsage } from '../webgpu.js'
+
+const SIZE = 4
+
+let device
+
+beforeAll(async () => {
+  const adapter = await Gpu().requestAdapter()
+  device = await adapter.requestDevice()
+})
+
+afterAll(() => {
+  device?.destroy()
+})
+
+function createCubeTexture(layers = 6) {
+  return device.createTexture({
+    width: SIZE,
+    height: SIZE,
+    depth: layers,
+    format: 'rgba8unorm',
+    usage: GPUTextureUsage.TEXTURE_BINDING | GPUTextureUsage.COPY_DST
+  })
+}
+
+// Face i is filled with red = (i + 1) * 40
+function solidFaces() {
+  return Array.from({ length: 6 }, (_, i) => new Uint8Array(SIZE * SIZE * 4).map((_, j) => j % 4 === 0 ? (i + 1) * 40 : 255))
+}
+
+describe('Cube Maps: Sampling', () => {
+  test('should sample each face through a cube view', async () => {
+    const texture = createCubeTexture()
+    device.queue.writeCubeFaces(texture, solidFaces())
+
+    const module = device.createShaderModule({
+      code: `
+        @group(0) @binding(0) var cube: texture_cube<f32>;
+        @group(0) @binding(1) var cubeSampler: sampler;
+        @group(0) @binding(2) var<storage, read_write> result: array<u32, 6>;
+
+        @compute @workgroup_size(6)
+        fn main(@builtin(local_invocation_index) i: u32) {
+          var directions = array<vec3f, 6>(
+            vec3f(1.0, 0.0, 0.0), vec3f(-1.0, 0.0, 0.0),
+            vec3f(0.0, 1.0, 0.0), vec3f(0.0, -1.0, 0.0),
+            vec3f(0.0, 0.0, 1.0), vec3f(0.0, 0.0, -1.0),
+          );
+          let color = textureSampleLevel(cube, cubeSampler, directions[i], 0.0);
+          result[i] = u32(round(color.r * 255.0));
+        }
+      `
+    })
+    const layout = device.createBindGroupLayout({
+      entries: [
+        { binding: 0, visibility: 4, texture: { viewDimension: 'cube' } },
+        { binding: 1, visibility: 4, sampler: {} },
+        { binding: 2, visibility: 4, buffer: { type: 'storage' } }
+      ]
+    })
+    const pipeline = device.createComputePipeline({
+      layout: device.createPipelineLayout({ bindGroupLayouts: [layout] }),
+      compute: { module, entryPoint: 'main' }
+    })
+    const result = device.createBuffer({ size: 24, usage: GPUBufferUsage.STORAGE | GPUBufferUsage.COPY_SRC })
+    const readback = device.createBuffer({ size: 24, usage: GPUBufferUsage.MAP_READ | GPUBufferUsage.COPY_DST })
+    const bindGroup = device.createBindGroup({
+      layout,
+      entries: [
+        { binding: 0, resource: texture.createView({ dimension: 'cube' }) },
+        { binding: 1, resource: device.createSampler({}) },
+        { binding: 2, resource: { buffer: result } }
+      ]
+    })
+
+    const encoder = device.createCommandEncoder()
+    const pass = encoder.beginComputePass()
+    pass.setPipeline(pipeline)
+    pass.setBindGroup(0, bindGroup)
+    pass.dispatchWorkgroups(1)
+    pass.end()
+    encoder.copyBufferToBuffer(result, 0, readback, 0, 24)
+    device.queue.submit([encoder.finish()])
+
+    await readback.mapAsync('READ')
+    expect(Array.from(new Uint32Array(readback.getMappedRange()))).toEqual([40, 80, 120, 160, 200, 240])
+    readback.unmap()
+  })
+})
+
+describe('Cube Maps: writeCubeFaces Validation', () => {
+  test('should require six faces', () => {
+    expect(() => device.queue.writeCubeFaces(createCubeTexture(), solidFaces().slice(0, 5))).toThrow('needs 6 faces')
+  })
+
+  test('should reject faces of the wrong size', () => {
+    const faces = solidFaces()
+    faces[3] = new Uint8Array(8)
+    expect(() => device.queue.writeCubeFaces(createCubeTexture(), faces)).toThrow('face 3 is 8 bytes')
+  })
+
+  test('should reject layers outside the texture', () => {
+    expect(() => device.queue.writeCubeFaces(createCubeTexture(6), solidFaces(), { baseArrayLayer: 6 })).toThrow('exceed')
+  })
+
+  test('should write the second cube of a cube array', () => {
+    expect(() => device.queue.writeCubeFaces(createCubeTexture(12), solidFaces(), { baseArrayLayer: 6 })).not.toThrow()
+  })
+})
@@ -0,0 +1,223 @@
+/**
+ * Numeric Descriptor Enum Tests
+ *
+ * Descriptor fields accept numeric constants (GPUTextureFormat.RGBA8UNORM, ...)
+ * in place of strings; the results must match the string descriptors.
+ */
+
+import { describe, test, expect, beforeAll, afterAll } from 'bun:test'
+import {
+  Gpu,
+  GPUBufferUsage,
+  GPUTextureUsage,
+  GPUShaderStage,
+  GPUTextureFormat,
+  GPUTextureViewDimension,
+  GPUPrimitiveTopology,
+  GPUCullMode,
+  GPUFrontFace,
+  GPUBlendFactor,
+  GPUBlendOperation,
+  GPULoadOp,
+  GPUStoreOp,
+  GPUAddressMode,
+  GPUFilterMode,
+  GPUBufferBindingType,
+  GPUIndexFormat
+} from '../webgpu.js'
+
+const SIZE = 4
+
+let device
+
+const SHADER = `
+  @vertex
+  fn vs_main(@builtin(vertex_index) i: u32) -> @builtin(position) vec4<f32> {
+    let uv = vec2<f32>(f32((i << 1u) & 2u), f32(i & 2u));
+    return vec4<f32>(uv * 2.0 - 1.0, 0.5, 1.0);
+  }
+
+  @fragment
+  fn fs_main() -> @location(0) vec4<f32> {
+    return vec4<f32>(0.0, 1.0, 0.0, 0.5);
+  }
+`
+
+beforeAll(async () => {
+  const adapter = await Gpu().requestAdapter()
+  device = await adapter.requestDevice()
+})
+
+afterAll(() => {
+  device?.destroy()
+})
+
+async function renderPixel(enums) {
+  const target = device.createTexture({
+    width: SIZE,
+    height: SIZE,
+    format: enums.format,
+    usage: GPUTextureUsage.RENDER_ATTACHMENT | GPUTextureUsage.COPY_SRC
+  })
+  const module = device.createShaderModule({ code: SHADER })
+  const pipeline = device.createRenderPipeline({
+    layout: null,
+    vertex: { module, entryPoint: 'vs_main' },
+    fragment: {
+      module,
+      entryPoint: 'fs_main',
+      targets: [{
+        format: enums.format,
+        blend: {
+          color: { srcFactor: enums.srcAlpha, dstFactor: enums.oneMinusSrcAlpha, operation: enums.add },
+          alpha: { srcFactor: enums.one, dstFactor: enums.zero, operation: enums.add }
+        }
+      }]
+    },
+    primitive: { topology: enums.triangleList, cullMode: enums.back, frontFace: enums.ccw }
+  })
+
+  const encoder = device.createCommandEncoder()
+  const pass = encoder.beginRenderPass({
+    colorAttachments: [{
+      view: target.createView(),
+      loadOp: enums.clear,
+      storeOp: enums.store,
+      clearValue: { r: 1, g: 0, b: 0, a: 1 }
+    }]
+  })
+  pass.setPipeline(pipeline)
+  pass.draw(3)
+  pass.end()
+
+  const buffer = device.createBuffer({ size: 256 * SIZE, usage: GPUBufferUsage.COPY_DST | GPUBufferUsage.MAP_READ })
+  encoder.copyTextureToBuffer(target, 0, 0, 0, 0, buffer, 0, 256, SIZE, SIZE, SIZE, 1)
+  device.queue.submit([encoder.finish()])
+  await buffer.mapAsync('READ')
+  const pixel = Array.from(new Uint8Array(buffer.getMappedRange()).slice(0, 4))
+  buffer.unmap()
+  return pixel
+}
+
+describe('Descriptor Enums: Constants', () => {
+  test('should number each enum in WebGPU order', () => {
+    expect(GPUPrimitiveTopology.POINT_LIST).toBe(0)
+    expect(GPUPrimitiveTopology.TRIANGLE_STRIP).toBe(4)
+    expect(GPULoadOp.CLEAR).toBe(1)
+    expect(GPUIndexFormat.UINT32).toBe(1)
+  })
+
+  test('should name dimensions and ASTC formats', () => {
+    expect(typeof GPUTextureViewDimension.D2_ARRAY).toBe('number')
+    expect(typeof GPUTextureFormat.RGBA8UNORM_SRGB).toBe('number')
+    expect(GPUTextureFormat.ASTC_12X12_HDR).toBe(Object.keys(GPUTextureFormat).length - 1)
+  })
+
+  test('should be frozen', () => {
+    expect(Object.isFrozen(GPUTextureFormat)).toBe(true)
+  })
+})
+
+describe('Descriptor Enums: Descriptors', () => {
+  test('should render the same with numeric and string enums', async () => {
+    const numeric = await renderPixel({
+      format: GPUTextureFormat.RGBA8UNORM,
+      srcAlpha: GPUBlendFactor.SRC_ALPHA,
+      oneMinusSrcAlpha: GPUBlendFactor.ONE_MINUS_SRC_ALPHA,
+      one: GPUBlendFactor.ONE,
+      zero: GPUBlendFactor.ZERO,
+      add: GPUBlendOperation.ADD,
+      triangleList: GPUPrimitiveTopology.TRIANGLE_LIST,
+      back: GPUCullMode.BACK,
+      ccw: GPUFrontFace.CCW,
+      clear: GPULoadOp.CLEAR,
+      store: GPUStoreOp.STORE
+    })
+    const strings = await renderPixel({
+      format: 'rgba8unorm',
+      srcAlpha: 'src-alpha',
+      oneMinusSrcAlpha: 'one-minus-src-alpha',
+      one: 'one',
+      zero: 'zero',
+      add: 'add',
+      triangleList: 'triangle-list',
+      back: 'back',
+      ccw: 'ccw',
+      clear: 'clear',
+      store: 'store'
+    })
+    // Green at half alpha over the red clear color
+    expect(numeric).toEqual(strings)
+    expect(numeric[0]).toBeGreaterThan(100)
+    expect(numeric[1]).toBeGreaterThan(100)
+  })
+
+  test('should bind a storage buffer with a numeric binding type', async () => {
+    const module = device.createShaderModule({
+      code: `
+        @group(0) @binding(0) var<storage, read_write> data: array<u32>;
+
+        @compute @workgroup_size(4)
+        fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+          data[id.x] = id.x + 1u;
+        }
+      `
+    })
+    const layout = device.createBindGroupLayout({
+      entries: [{ binding: 0, visibility: GPUShaderStage.COMPUTE, buffer: { type: GPUBufferBindingType.STORAGE } }]
+    })
+    const pipeline = device.createComputePipeline({
+      layout: device.createPipelineLayout({ bindGroupLayouts: [layout] }),
+      compute: { module, entryPoint: 'main' }
+    })
+    const storage = device.createBuffer({ size: 16, usage: GPUBufferUsage.STORAGE | GPUBufferUsage.COPY_SRC })
+    const readback = device.createBuffer({ size: 16, usage: GPUBufferUsage.MAP_READ | GPUBufferUsage.COPY_DST })
+    const bindGroup = device.createBindGroup({ layout, entries: [{ binding: 0, resource: { buffer: storage } }] })
+
+    const encoder = device.createCommandEncoder()
+    const pass = encoder.beginComputePass()
+    pass.setPipeline(pipeline)
+    pass.setBindGroup(0, bindGroup)
+    pass.dispatchWorkgroups(1)
+    pass.end()
+    encoder.copyBufferToBuffer(storage, 0, readback, 0, 16)
+    device.queue.submit([encoder.finish()])
+
+    await readback.mapAsync('READ')
+    expect(Array.from(new Uint32Array(readback.getMappedRange()))).toEqual([1, 2, 3, 4])
+    readback.unmap()
+  })
+
+  test('should accept numeric view, sampler and index format enums', () => {
+    const texture = device.createTexture({
+      width: SIZE,
+      height: SIZE,
+      depth: 2,
+      format: GPUTextureFormat.RGBA8UNORM,
+      usage: GPUTextureUsage.TEXTURE_BINDING
+    })
+    expect(texture.createView({ dimension: GPUTextureViewDimension.D2_ARRAY })).toBeDefined()
+    expect(device.createSampler({
+      addressModeU: GPUAddressMode.REPEAT,
+      magFilter: GPUFilterMode.LINEAR,
+      minFilter: GPUFilterMode.LINEAR
+    })).toBeDefined()
+
+    const indices = device.createBuffer({ size: 12, usage: GPUBufferUsage.INDEX })
+    const pass = device.createCommandEncoder().beginRenderPass({
+      colorAttachments: [{
+        view: device.createTexture({
+          width: SIZE,
+          height: SIZE,
+          format: 'rgba8unorm',
+          usage: GPUTextureUsage.RENDER_ATTACHMENT
+        }).createView(),
+        loadOp: GPULoadOp.LOAD,
+        storeOp: GPUStoreOp.STORE
+      }]
+    })
+    pass.setIndexBuffer(indices, GPUIndexFormat.UINT16)
+    expect(() => pass.setIndexBuffer(indices, 7)).toThrow('Invalid index format: 7')
+    pass.end()
+  })
+})
@@ -0,0 +1,110 @@
+/**
+ * Device Event Tests
+ *
+ * GpuDevice is an EventEmitter for GPU timeline events. Submissions report
+ * 'submit' and, once the GPU is done, 'submissionComplete' with the same
+ * submission number; errors outside error scopes are 'uncapturedError'.
+ * Destroying a device loses it: 'deviceLost' fires once, device.lost
+ * resolves, and later calls throw.
+ */
+
+import { describe, test, expect, beforeAll, afterAll } from 'bun:test'
+import { Gpu, GPUBufferUsage } from '../webgpu.js'
+
+let device
+
+beforeAll(async () => {
+  const adapter = await Gpu().requestAdapter()
+  device = await adapter.requestDevice()
+})
+
+afterAll(() => {
+  device?.destroy()
+})
+
+function nextEvent(type) {
+  return new Promise((resolve) => device.once(type, resolve))
+}
+
+describe('Device Events: Submissions', () => {
+  test('should report submissions and their completion', async () => {
+    const submitted = nextEvent('submit')
+    const completed = nextEvent('submissionComplete')
+
+    const encoder = device.createCommandEncoder()
+    device.queue.submit([encoder.finish()])
+
+    const submit = await submitted
+    expect(submit.type).toBe('submit')
+    expect(submit.commandBuffers).toBe(1)
+    expect((await completed).submission).toBe(submit.submission)
+  })
+
+  test('should number submissions in order', async () => {
+    const submissions = []
+    const listener = (event) => submissions.push(event.submission)
+    device.on('submit', listener)
+    device.queue.submit([device.createCommandEncoder().finish()])
+    device.queue.submit([device.createCommandEncoder().finish()])
+    await device.queue.onSubmittedWorkDone()
+    await new Promise((resolve) => setTimeout(resolve, 50))
+    device.off('submit', listener)
+
+    expect(submissions.length).toBe(2)
+    expect(submissions[1]).toBe(submissions[0] + 1)
+  })
+
+  test('should stop reporting once the listeners are removed', async () => {
+    const submissions = []
+    const listener = (event) => submissions.push(event)
+    device.on('submit', listener)
+    device.off('submit', listener)
+    device.queue.submit([device.createCommandEncoder().finish()])
+    await device.queue.onSubmittedWorkDone()
+    await new Promise((resolve) => setTimeout(resolve, 50))
+
+    expect(submissions).toEqual([])
+  })
+})
+
+describe('Device Events: Errors', () => {
+  test('should emit uncaptured errors', async () => {
+    const uncaptured = nextEvent('uncapturedError')
+    const buffer = device.createBuffer({ size: 16, usage: GPUBufferUsage.MAP_READ | GPUBufferUsage.COPY_DST })
+    buffer.destroy()
+    await buffer.mapAsync('READ').catch(() => {})
+
+    const event = await uncaptured
+    expect(event.message).toContain('destroyed')
+  })
+})
+
+describe('Device Events: Loss', () => {
+  test('should report destruction once and resolve lost', async () => {
+    const other = await (await Gpu().requestAdapter()).requestDevice()
+    const events = []
+    other.on('deviceLost', (event) => events.push(event))
+    const lost = other.lost
+    other.destroy()
+    other.destroy()
+
+    expect(await lost).toEqual({ reason: 'destroyed', message: 'Device was destroyed' })
+    await new Promise((resolve) => setTimeout(resolve, 50))
+    expect(events.map((event) => event.reason)).toEqual(['destroyed'])
+  })
+
+  test('should reject calls on a destroyed device', async () => {
+    const other = await (await Gpu().requestAdapter()).requestDevice()
+    const encoder = other.createCommandEncoder()
+    const commands = encoder.finish()
+    other.destroy()
+
+    expect(() => other.createBuffer({ size: 16, usage: GPUBufferUsage.COPY_DST })).toThrow('createBuffer: device was destroyed')
+    expect(() => other.createCommandEncoder()).toThrow(/device was destroyed/)
+    expect(() => other.queue.submit([commands])).toThrow('submit: device was destroyed')
+    expect(() => other.createPrefixSum()).toThrow('createPrefixSum: device was destroyed')
+    expect(() => other.createDraw2D()).toThrow('createDraw2D: device was destroyed')
+    expect(() => other.createFont(Buffer.alloc(0))).toThrow('createFont: device was destroyed')
+    expect(await other.lost).toEqual({ reason: 'destroyed', message: 'Device was destroyed' })
+  })
+})
@@ -0,0 +1,108 @@
+/**
+ * Device Polling Tests
+ *
+ * Async APIs are serviced by the per-device background poll thread,
+ * so none of these tests call device.poll().
+ */
+
+import { describe, test, expect, beforeAll, afterAll } from 'bun:test'
+import { Gpu, GPUBufferUsage } from '../webgpu.js'
+
+let device
+
+beforeAll(async () => {
+  const adapter = await Gpu().requestAdapter()
+  device = await adapter.requestDevice()
+})
+
+afterAll(() => {
+  device?.destroy()
+})
+
+function submitCopy(source, destination, size) {
+  const encoder = device.createCommandEncoder()
+  encoder.copyBufferToBuffer(source, 0, destination, 0, size)
+  device.queue.submit([encoder.finish()])
+}
+
+describe('Device Polling: Background Thread', () => {
+  test('should resolve mapAsync after a submitted copy', async () => {
+    const source = device.createBuffer({ size: 16, usage: GPUBufferUsage.COPY_SRC | GPUBufferUsage.COPY_DST })
+    const staging = device.createBuffer({ size: 16, usage: GPUBufferUsage.MAP_READ | GPUBufferUsage.COPY_DST })
+    device.queue.writeBuffer(source, 0, Buffer.from(new Uint32Array([4, 3, 2, 1]).buffer))
+    submitCopy(source, staging, 16)
+
+    await staging.mapAsync('READ')
+    expect(Array.from(new Uint32Array(staging.getMappedRange()))).toEqual([4, 3, 2, 1])
+    staging.unmap()
+  })
+
+  test('should resolve concurrent mappings', async () => {
+    const source = device.createBuffer({ size: 64, usage: GPUBufferUsage.COPY_SRC | GPUBufferUsage.COPY_DST })
+    const stagings = []
+    for (let i = 0; i < 4; i++) {
+      const staging = device.createBuffer({ size: 64, usage: GPUBufferUsage.MAP_READ | GPUBufferUsage.COPY_DST })
+      submitCopy(source, staging, 64)
+      stagings.push(staging)
+    }
+
+    await Promise.all(stagings.map((staging) => staging.mapAsync('READ')))
+    for (const staging of stagings) {
+      expect(staging.mapState).toBe('mapped')
+    }
+  })
+
+  test('should resolve onSubmittedWorkDone', async () => {
+    const source = device.createBuffer({ size: 16, usage: GPUBufferUsage.COPY_SRC })
+    const destination = device.createBuffer({ size: 16, usage: GPUBufferUsage.COPY_DST })
+    submitCopy(source, destination, 16)
+
+    await device.queue.onSubmittedWorkDone()
+    // Nothing submitted since: resolves immediately
+    await device.queue.onSubmittedWorkDone()
+  })
+})
+
+describe('Device Polling: Wait Idle', () => {
+  test('should report an empty queue once idle', async () => {
+    const source = device.createBuffer({ size: 16, usage: GPUBufferUsage.COPY_SRC })
+    const destination = device.createBuffer({ size: 16, usage: GPUBufferUsage.COPY_DST })
+    submitCopy(source, destination, 16)
+
+    await device.waitIdle()
+    expect(device.poll()).toBe(true)
+    expect(device.poll(true)).toBe(true)
+  })
+
+  test('should flush pending writeBuffer data', async () => {
+    const buffer = device.createBuffer({ size: 16, usage: GPUBufferUsage.COPY_DST | GPUBufferUsage.MAP_READ })
+    device.queue.writeBuffer(buffer, 0, Buffer.from(new Uint32Array([9, 8, 7, 6]).buffer))
+
+    await device.waitIdle()
+    await buffer.mapAsync('READ')
+    expect(Array.from(new Uint32Array(buffer.getMappedRange()))).toEqual([9, 8, 7, 6])
+    buffer.unmap()
+  })
+})
+
+describe('Device Polling: Auto Maintenance', () => {
+  test('should keep polling after each submit when enabled', async () => {
+    device.setAutoMaintenance(true)
+    try {
+      const source = device.createBuffer({ size: 16, usage: GPUBufferUsage.COPY_SRC | GPUBufferUsage.COPY_DST })
+      const staging = device.createBuffer({ size: 16, usage: GPUBufferUsage.MAP_READ | GPUBufferUsage.COPY_DST })
+      device.queue.writeBuffer(source, 0, Buffer.from(new Uint32Array([5, 6, 7, 8]).buffer))
+      for (let i = 0; i < 8; i++) submitCopy(source, staging, 16)
+
+      await staging.mapAsync('READ')
+      expect(Array.from(new Uint32Array(staging.getMappedRange()))).toEqual([5, 6, 7, 8])
+      staging.unmap()
+
+      // Every submission is retired without an explicit poll(true)
+      await device.queue.onSubmittedWorkDone()
+      expect(device.poll()).toBe(true)
+    } finally {
+      device.setAutoMaintenance(false)
+    }
+  })
+})
@@ -0,0 +1,114 @@
+/**
+ * Device Recovery Tests
+ *
+ * adapter.requestRecoverableDevice() returns a GpuDeviceRecovery that
+ * replaces a lost device and runs the registered creation callbacks again.
+ * A real loss can't be caused on demand, so recovery is started with
+ * recover(), which destroys the current device first.
+ */
+
+import { describe, test, expect, beforeAll, afterAll } from 'bun:test'
+import { Gpu, GPUBufferUsage } from '../webgpu.js'
+
+let adapter
+let recovery
+
+const SHADER = `
+  @group(0) @binding(0) var<storage, read_write> data: array<u32>;
+
+  @compute @workgroup_size(1)
+  fn main() {
+    data[0] = data[0] * 2u;
+  }
+`
+
+beforeAll(async () => {
+  adapter = await Gpu().requestAdapter()
+  recovery = await adapter.requestRecoverableDevice()
+})
+
+afterAll(() => {
+  recovery?.destroy()
+})
+
+describe('Device Recovery: Resources', () => {
+  test('should recreate registered resources on the new device', async () => {
+    const first = recovery.device
+    const buffer = recovery.register((device) =>
+      device.createBufferInit({ data: new Uint32Array([21]), usage: GPUBufferUsage.STORAGE | GPUBufferUsage.COPY_SRC })
+    )
+    const shader = recovery.register((device) => device.createShaderModule({ code: SHADER }))
+    const pipeline = recovery.register((device) =>
+      device.createComputePipeline({ layout: 'auto', compute: { module: shader.value, entryPoint: 'main' } })
+    )
+    const recovered = new Promise((resolve) => recovery.once('recovered', resolve))
+
+    const device = await recovery.recover()
+    const event = await recovered
+
+    expect(device).not.toBe(first)
+    expect(recovery.device).toBe(device)
+    expect(event.device).toBe(device)
+    expect(event.lost.reason).toBe('destroyed')
+    expect(event.attempt).toBe(1)
+
+    const bindGroup = device.createBindGroup({
+      layout: pipeline.value.getBindGroupLayout(0),
+      entries: [{ binding: 0, resource: { buffer: buffer.value } }]
+    })
+    const encoder = device.createCommandEncoder()
+    const pass = encoder.beginComputePass()
+    pass.setPipeline(pipeline.value)
+    pass.setBindGroup(0, bindGroup)
+    pass.dispatchWorkgroups(1)
+    pass.end()
+    device.queue.submit([encoder.finish()])
+    expect(new Uint32Array(buffer.value.readSync())[0]).toBe(42)
+
+    recovery.unregister(pipeline)
+    recovery.unregister(shader)
+    recovery.unregister(buffer)
+  })
+
+  test('should stop recreating unregistered resources', async () => {
+    let runs = 0
+    const resource = recovery.register(() => ++runs)
+    await recovery.recover()
+    recovery.unregister(resource)
+    await recovery.recover()
+
+    expect(runs).toBe(2)
+    expect(resource.value).toBe(2)
+  })
+})
+
+describe('Device Recovery: Failures', () => {
+  test('should report failed recoveries', async () => {
+    const failing = await adapter.requestRecoverableDevice({}, { maxAttempts: 2 })
+    let attempts = 0
+    failing.register(() => {
+      if (attempts++ > 0) throw new Error('replay failed')
+    })
+    const failed = new Promise((resolve) => failing.once('recoveryFailed', resolve))
+
+    await expect(failing.recover()).rejects.toThrow(/replay failed/)
+    expect((await failed).error.message).toBe('replay failed')
+    expect(attempts).toBe(3)
+  })
+
+  test('should not recover devices that were destroyed', async () => {
+    const destroyed = await adapter.requestRecoverableDevice()
+    const device = destroyed.device
+    let recovered = false
+    destroyed.on('recovered', () => {
+      recovered = true
+    })
+
+    device.destroy()
+    await device.lost
+    await new Promise((resolve) => setTimeout(resolve, 50))
+
+    expect(recovered).toBe(false)
+    expect(destroyed.device).toBe(device)
+  })
+})
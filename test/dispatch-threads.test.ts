@@ -0,0 +1,111 @@
+/**
+ * dispatchThreads Tests
+ *
+ * Each invocation bumps an atomic counter, so the counter reads back the
+ * number of invocations the rounded-up workgroup counts produced.
+ */
+
+import { describe, test, expect, beforeAll, afterAll } from 'bun:test'
+import { Gpu, GPUBufferUsage } from '../webgpu.js'
+
+let device
+
+beforeAll(async () => {
+  const adapter = await Gpu().requestAdapter()
+  device = await adapter.requestDevice()
+})
+
+afterAll(() => {
+  device?.destroy()
+})
+
+async function countInvocations(workgroupSize, dispatch) {
+  const module = device.createShaderModule({
+    code: `
+      @group(0) @binding(0) var<storage, read_write> counter: atomic<u32>;
+
+      @compute @workgroup_size(${workgroupSize.join(', ')})
+      fn main() {
+        atomicAdd(&counter, 1u);
+      }
+    `
+  })
+  const layout = device.createBindGroupLayout({
+    entries: [{ binding: 0, visibility: 4, buffer: { type: 'storage' } }]
+  })
+  const pipeline = device.createComputePipeline({
+    layout: device.createPipelineLayout({ bindGroupLayouts: [layout] }),
+    compute: { module, entryPoint: 'main' }
+  })
+  const counter = device.createBuffer({ size: 4, usage: GPUBufferUsage.STORAGE | GPUBufferUsage.COPY_SRC })
+  const readback = device.createBuffer({ size: 4, usage: GPUBufferUsage.MAP_READ | GPUBufferUsage.COPY_DST })
+  const bindGroup = device.createBindGroup({
+    layout,
+    entries: [{ binding: 0, resource: { buffer: counter } }]
+  })
+
+  const encoder = device.createCommandEncoder()
+  const pass = encoder.beginComputePass()
+  pass.setPipeline(pipeline)
+  pass.setBindGroup(0, bindGroup)
+  dispatch(pass, pipeline)
+  pass.end()
+  encoder.copyBufferToBuffer(counter, 0, readback, 0, 4)
+  device.queue.submit([encoder.finish()])
+
+  await readback.mapAsync('READ')
+  const count = new Uint32Array(readback.getMappedRange())[0]
+  readback.unmap()
+  return count
+}
+
+describe('dispatchThreads: Workgroup Counts', () => {
+  test('should round a 1D thread count up to whole workgroups', async () => {
+    expect(await countInvocations([64], (pass) => pass.dispatchThreads(100, 1, 1, 64))).toBe(128)
+  })
+
+  test('should round each dimension separately', async () => {
+    // 2x1 workgroups of 8x8
+    expect(await countInvocations([8, 8], (pass) => pass.dispatchThreads(10, 3, 1, [8, 8]))).toBe(128)
+  })
+
+  test('should not over-dispatch exact multiples', async () => {
+    expect(await countInvocations([4, 2, 2], (pass) => pass.dispatchThreads(8, 4, 2, [4, 2, 2]))).toBe(64)
+  })
+})
+
+describe('dispatchThreads: Workgroup Size Introspection', () => {
+  test('should report the entry point workgroup size', () => {
+    const module = device.createShaderModule({
+      code: `
+        @compute @workgroup_size(64)
+        fn wide() {}
+
+        @compute @workgroup_size(8, 4, 2)
+        fn cube() {}
+      `
+    })
+    const layout = device.createPipelineLayout({ bindGroupLayouts: [] })
+    const wide = device.createComputePipeline({ layout, compute: { module, entryPoint: 'wide' } })
+    const cube = device.createComputePipeline({ layout, compute: { module, entryPoint: 'cube' } })
+
+    expect(wide.getWorkgroupSize()).toEqual([64, 1, 1])
+    expect(cube.getWorkgroupSize()).toEqual([8, 4, 2])
+  })
+
+  test('should size dispatches from the pipeline', async () => {
+    const count = await countInvocations([8, 8], (pass, pipeline) => {
+      pass.dispatchThreads(10, 3, 1, pipeline.getWorkgroupSize())
+    })
+    expect(count).toBe(128)
+  })
+})
+
+describe('dispatchThreads: Validation', () => {
+  test('should reject zero workgroup sizes', () => {
+    const pass = device.createCommandEncoder().beginComputePass()
+    expect(() => pass.dispatchThreads(16, 1, 1, [8, 0])).toThrow('non-zero')
+    expect(() => pass.dispatchThreads(16, 1, 1, [1, 1, 1, 1])).toThrow('1 to 3 dimensions')
+    pass.end()
+  })
+})
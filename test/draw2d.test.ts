@@ -0,0 +1,163 @@
+/**
+ * 2D Drawing Tests
+ *
+ * device.createDraw2D() queues rectangles, lines, quads and sprites in pixel
+ * coordinates and draws them into a texture view in one render pass.
+ */
+
+import { describe, test, expect, beforeAll, afterAll } from 'bun:test'
+import { Gpu, GPUBufferUsage, GPUTextureUsage } from '../webgpu.js'
+
+let device
+
+beforeAll(async () => {
+  const adapter = await Gpu().requestAdapter()
+  device = await adapter.requestDevice()
+})
+
+afterAll(() => {
+  device?.destroy()
+})
+
+const SIZE = 8
+
+function createTarget() {
+  return device.createTexture({
+    width: SIZE,
+    height: SIZE,
+    format: 'rgba8unorm',
+    usage: GPUTextureUsage.RENDER_ATTACHMENT | GPUTextureUsage.COPY_SRC,
+  })
+}
+
+function createSprite(width, height, pixels) {
+  const texture = device.createTexture({
+    width,
+    height,
+    format: 'rgba8unorm',
+    usage: GPUTextureUsage.TEXTURE_BINDING | GPUTextureUsage.COPY_DST,
+  })
+  const upload = device.createBuffer({ size: 256 * height, usage: GPUBufferUsage.COPY_SRC | GPUBufferUsage.COPY_DST })
+  for (let row = 0; row < height; row++) {
+    device.queue.writeBuffer(upload, row * 256, pixels.subarray(row * width * 4, (row + 1) * width * 4))
+  }
+  const encoder = device.createCommandEncoder()
+  encoder.copyBufferToTexture({ buffer: upload, bytesPerRow: 256 }, { texture }, { width, height })
+  device.queue.submit([encoder.finish()])
+  return texture
+}
+
+// Draw the queued shapes over a black target and return its RGBA pixels by row
+async function drawAndRead(draw) {
+  const target = createTarget()
+  const readback = device.createBuffer({ size: 256 * SIZE, usage: GPUBufferUsage.COPY_DST | GPUBufferUsage.MAP_READ })
+  const encoder = device.createCommandEncoder()
+  draw.encode(encoder, target.createView(), { clear: [0, 0, 0, 1] })
+  encoder.copyTextureToBuffer({ texture: target }, { buffer: readback, bytesPerRow: 256 }, { width: SIZE, height: SIZE })
+  device.queue.submit([encoder.finish()])
+
+  await readback.mapAsync('READ')
+  const bytes = new Uint8Array(readback.getMappedRange())
+  const pixel = (x, y) => Array.from(bytes.subarray(y * 256 + x * 4, y * 256 + x * 4 + 4))
+  const rows = []
+  for (let y = 0; y < SIZE; y++) {
+    const row = []
+    for (let x = 0; x < SIZE; x++) row.push(pixel(x, y))
+    rows.push(row)
+  }
+  readback.unmap()
+  return rows
+}
+
+describe('Draw2D: Shapes', () => {
+  test('should fill rectangles in pixel coordinates', async () => {
+    const draw = device.createDraw2D()
+    draw.rect(2, 1, 4, 3, [1, 0, 0])
+    const pixels = await drawAndRead(draw)
+
+    expect(pixels[1][2]).toEqual([255, 0, 0, 255])
+    expect(pixels[3][5]).toEqual([255, 0, 0, 255])
+    expect(pixels[0][2]).toEqual([0, 0, 0, 255])
+    expect(pixels[4][5]).toEqual([0, 0, 0, 255])
+    expect(pixels[2][1]).toEqual([0, 0, 0, 255])
+    expect(pixels[2][6]).toEqual([0, 0, 0, 255])
+  })
+
+  test('should blend shapes in call order', async () => {
+    const draw = device.createDraw2D()
+    draw.rect(0, 0, 8, 8, [0, 0, 1]).rect(0, 0, 4, 8, [1, 1, 1, 0.5])
+    const pixels = await drawAndRead(draw)
+
+    expect(pixels[0][6]).toEqual([0, 0, 255, 255])
+    const [r, g, b] = pixels[0][1]
+    expect(Math.abs(r - 128)).toBeLessThanOrEqual(1)
+    expect(Math.abs(g - 128)).toBeLessThanOrEqual(1)
+    expect(b).toBe(255)
+  })
+
+  test('should draw lines and quads', async () => {
+    const draw = device.createDraw2D()
+    draw.line(0, 4, 8, 4, [0, 1, 0], 2)
+    draw.quad([6, 0, 8, 0, 8, 2, 6, 2], [0, 0, 1])
+    const pixels = await drawAndRead(draw)
+
+    expect(pixels[3][0]).toEqual([0, 255, 0, 255])
+    expect(pixels[4][7]).toEqual([0, 255, 0, 255])
+    expect(pixels[2][0]).toEqual([0, 0, 0, 255])
+    expect(pixels[5][0]).toEqual([0, 0, 0, 255])
+    expect(pixels[1][7]).toEqual([0, 0, 255, 255])
+  })
+
+  test('should apply and restore transforms', async () => {
+    const draw = device.createDraw2D()
+    draw.save().translate(4, 4).scale(2).rect(0, 0, 1, 1, [1, 0, 0]).restore()
+    draw.rect(0, 0, 1, 1, [0, 1, 0])
+    const pixels = await drawAndRead(draw)
+
+    expect(pixels[4][4]).toEqual([255, 0, 0, 255])
+    expect(pixels[5][5]).toEqual([255, 0, 0, 255])
+    expect(pixels[6][6]).toEqual([0, 0, 0, 255])
+    expect(pixels[0][0]).toEqual([0, 255, 0, 255])
+  })
+
+  test('should empty the queue after encoding', async () => {
+    const draw = device.createDraw2D()
+    draw.rect(0, 0, 8, 8)
+    await drawAndRead(draw)
+    const pixels = await drawAndRead(draw)
+
+    expect(pixels[4][4]).toEqual([0, 0, 0, 255])
+  })
+})
+
+describe('Draw2D: Sprites', () => {
+  test('should draw regions of a texture with a tint', async () => {
+    // 2x1 sprite: red texel, then white texel
+    const sprite = createSprite(2, 1, new Uint8Array([255, 0, 0, 255, 255, 255, 255, 255]))
+    const draw = device.createDraw2D()
+    draw.sprite(sprite, 0, 0, 4, 4, { filter: 'nearest' })
+    draw.sprite(sprite, 4, 4, 4, 4, { source: [1, 0, 1, 1], color: [0, 1, 0] })
+    const pixels = await drawAndRead(draw)
+
+    expect(pixels[0][0]).toEqual([255, 0, 0, 255])
+    expect(pixels[3][3]).toEqual([255, 255, 255, 255])
+    expect(pixels[6][6]).toEqual([0, 255, 0, 255])
+  })
+
+  test('should reject sprites that cannot be sampled', () => {
+    const draw = device.createDraw2D()
+    const target = createTarget()
+
+    expect(() => draw.sprite(target, 0, 0, 1, 1)).toThrow(/TEXTURE_BINDING/)
+  })
+})
+
+describe('Draw2D: Validation', () => {
+  test('should reject malformed colors and points', () => {
+    const draw = device.createDraw2D()
+
+    expect(() => draw.rect(0, 0, 1, 1, [1, 0])).toThrow(/3 or 4 components/)
+    expect(() => draw.quad([0, 0, 1, 1])).toThrow(/8 coordinates/)
+    expect(() => draw.sprite(createSprite(1, 1, new Uint8Array(4)), 0, 0, 1, 1, { filter: 'cubic' })).toThrow(/unknown filter/)
+  })
+})
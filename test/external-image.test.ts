@@ -0,0 +1,117 @@
+/**
+ * copyExternalImageToTexture Tests
+ *
+ * Decoded RGBA8 images are copied into textures with flipY, premultiplied
+ * alpha and color space conversions, and read back to check the texels.
+ */
+
+import { describe, test, expect, beforeAll, afterAll } from 'bun:test'
+import { Gpu, GPUBufferUsage, GPUTextureUsage } from '../webgpu.js'
+
+let device
+
+beforeAll(async () => {
+  const adapter = await Gpu().requestAdapter()
+  device = await adapter.requestDevice()
+})
+
+afterAll(() => {
+  device?.destroy()
+})
+
+// 2x2 image: red, green / blue, half-transparent white
+const image = {
+  data: new Uint8ClampedArray([255, 0, 0, 255, 0, 255, 0, 255, 0, 0, 255, 255, 255, 255, 255, 128]),
+  width: 2,
+  height: 2,
+}
+
+function createTarget(format = 'rgba8unorm', width = 2, height = 2) {
+  return device.createTexture({
+    size: [width, height],
+    format,
+    usage: GPUTextureUsage.COPY_DST | GPUTextureUsage.COPY_SRC,
+  })
+}
+
+// Read back a 2x2 texture as four [r, g, b, a] texels in row order
+async function readTexels(texture) {
+  const readback = device.createBuffer({ size: 512, usage: GPUBufferUsage.COPY_DST | GPUBufferUsage.MAP_READ })
+  const encoder = device.createCommandEncoder()
+  encoder.copyTextureToBuffer({ texture }, { buffer: readback, bytesPerRow: 256 }, [2, 2])
+  device.queue.submit([encoder.finish()])
+  await readback.mapAsync('READ')
+  const bytes = new Uint8Array(readback.getMappedRange())
+  const texels = [0, 4, 256, 260].map((offset) => Array.from(bytes.subarray(offset, offset + 4)))
+  readback.unmap()
+  return texels
+}
+
+describe('copyExternalImageToTexture: Copies', () => {
+  test('should copy pixels unchanged by default', async () => {
+    const texture = createTarget()
+    device.queue.copyExternalImageToTexture({ source: image }, { texture }, [2, 2])
+
+    expect(await readTexels(texture)).toEqual([[255, 0, 0, 255], [0, 255, 0, 255], [0, 0, 255, 255], [255, 255, 255, 128]])
+  })
+
+  test('should flip rows with flipY', async () => {
+    const texture = createTarget()
+    device.queue.copyExternalImageToTexture({ source: image, flipY: true }, { texture }, [2, 2])
+
+    expect(await readTexels(texture)).toEqual([[0, 0, 255, 255], [255, 255, 255, 128], [255, 0, 0, 255], [0, 255, 0, 255]])
+  })
+
+  test('should copy a region between origins', async () => {
+    const texture = createTarget()
+    device.queue.copyExternalImageToTexture({ source: image, origin: [0, 1] }, { texture, origin: [1, 0] }, [1, 1])
+
+    const texels = await readTexels(texture)
+    expect(texels[1]).toEqual([0, 0, 255, 255])
+    expect(texels[0]).toEqual([0, 0, 0, 0])
+    expect(texels[3]).toEqual([0, 0, 0, 0])
+  })
+
+  test('should swizzle into bgra8unorm textures', async () => {
+    const texture = createTarget('bgra8unorm')
+    device.queue.copyExternalImageToTexture({ source: image }, { texture }, [2, 2])
+
+    expect((await readTexels(texture))[0]).toEqual([0, 0, 255, 255])
+  })
+})
+
+describe('copyExternalImageToTexture: Conversions', () => {
+  test('should premultiply alpha', async () => {
+    const texture = createTarget()
+    device.queue.copyExternalImageToTexture({ source: image }, { texture, premultipliedAlpha: true }, [2, 2])
+
+    expect((await readTexels(texture))[3]).toEqual([128, 128, 128, 128])
+  })
+
+  test('should decode sRGB values into srgb-linear', async () => {
+    const gray = { data: new Uint8Array([188, 188, 188, 255]), width: 1, height: 1 }
+    const texture = createTarget()
+    device.queue.copyExternalImageToTexture({ source: gray }, { texture, colorSpace: 'srgb-linear' }, [1, 1])
+
+    // sRGB 188 is about 0.5 in linear light
+    const [r, , , a] = (await readTexels(texture))[0]
+    expect(Math.abs(r - 128)).toBeLessThanOrEqual(1)
+    expect(a).toBe(255)
+  })
+})
+
+describe('copyExternalImageToTexture: Validation', () => {
+  test('should reject mismatched data and out-of-range copies', () => {
+    const texture = createTarget()
+    const short = { data: new Uint8Array(12), width: 2, height: 2 }
+
+    expect(() => device.queue.copyExternalImageToTexture({ source: short }, { texture }, [2, 2])).toThrow(/expected 16/)
+    expect(() => device.queue.copyExternalImageToTexture({ source: image, origin: [1, 1] }, { texture }, [2, 2])).toThrow(/exceeds the 2x2 source/)
+  })
+
+  test('should reject unsupported formats and color spaces', () => {
+    expect(() => device.queue.copyExternalImageToTexture({ source: image }, { texture: createTarget('r8unorm') }, [2, 2])).toThrow(/doesn't support/)
+    expect(() => device.queue.copyExternalImageToTexture({ source: image }, { texture: createTarget('rgba8unorm-srgb'), colorSpace: 'srgb-linear' }, [2, 2])).toThrow(/non-sRGB/)
+    expect(() => device.queue.copyExternalImageToTexture({ source: image }, { texture: createTarget(), colorSpace: 'p3' }, [2, 2])).toThrow(/Unknown color space/)
+  })
+})
@@ -0,0 +1,79 @@
+/**
+ * Fault Injection Tests
+ *
+ * device.enableFaultInjection() makes create calls fail on demand: every nth
+ * allocation as out of memory, or the next create call as a validation error.
+ * Each enableFaultInjection() call replaces the previous options.
+ */
+
+import { describe, test, expect, beforeAll, afterAll } from 'bun:test'
+import { Gpu, GPUBufferUsage, GPUTextureUsage, GPUValidationError, GPUOutOfMemoryError } from '../webgpu.js'
+
+let device
+
+beforeAll(async () => {
+  const adapter = await Gpu().requestAdapter()
+  device = await adapter.requestDevice()
+})
+
+afterAll(() => {
+  device?.destroy()
+})
+
+function createBuffer() {
+  return device.createBuffer({ size: 16, usage: GPUBufferUsage.STORAGE })
+}
+
+describe('Fault Injection: oomEveryN', () => {
+  test('should fail every nth allocation as out of memory', () => {
+    device.enableFaultInjection({ oomEveryN: 2 })
+
+    expect(createBuffer()).toBeDefined()
+    expect(() => createBuffer()).toThrow(/createBuffer\(.*\): out of memory\n  Injected by enableFaultInjection/)
+    expect(createBuffer()).toBeDefined()
+    expect(() => device.createTexture({ size: [4, 4], format: 'rgba8unorm', usage: GPUTextureUsage.COPY_DST })).toThrow(/createTexture.*out of memory/)
+  })
+
+  test('should not count calls that do not allocate', () => {
+    device.enableFaultInjection({ oomEveryN: 1 })
+
+    expect(device.createSampler({})).toBeDefined()
+    expect(device.createCommandEncoder()).toBeDefined()
+    expect(() => createBuffer()).toThrow(/out of memory/)
+  })
+
+  test('should emit oomWarning', async () => {
+    const warning = new Promise((resolve) => device.once('oomWarning', resolve))
+    device.enableFaultInjection({ oomEveryN: 1 })
+
+    expect(() => createBuffer()).toThrow(/out of memory/)
+    expect((await warning).message).toMatch(/createBuffer.*out of memory/)
+  })
+})
+
+describe('Fault Injection: failNextCreate', () => {
+  test('should fail only the next create call', () => {
+    device.enableFaultInjection({ failNextCreate: true })
+
+    expect(() => device.createSampler({ label: 'first' })).toThrow(/createSampler\(label: 'first'\): validation failed/)
+    expect(device.createSampler({ label: 'second' })).toBeDefined()
+  })
+
+  test('should leave failures to error scopes and stop once disabled', async () => {
+    device.enableFaultInjection({ failNextCreate: true, oomEveryN: 1 })
+    device.pushErrorScope('out-of-memory')
+    device.pushErrorScope('validation')
+    expect(createBuffer()).toBeDefined()
+    expect(createBuffer()).toBeDefined()
+
+    const validation = await device.popErrorScope()
+    expect(validation).toBeInstanceOf(GPUValidationError)
+    expect(validation.message).toMatch(/^createBuffer\(.*\): validation failed\n  Injected by enableFaultInjection/)
+    const outOfMemory = await device.popErrorScope()
+    expect(outOfMemory).toBeInstanceOf(GPUOutOfMemoryError)
+    expect(outOfMemory.message).toMatch(/^createBuffer\(.*\): out of memory/)
+
+    device.disableFaultInjection()
+    expect(createBuffer()).toBeDefined()
+  })
+})
@@ -0,0 +1,56 @@
+/**
+ * Feature Name Tests
+ *
+ * Every wgpu feature has a name: the WebGPU name for standard features and a
+ * "wgpu-" prefix for native-only ones. Adapter features, device features,
+ * requiredFeatures and getTextureFormatInfo all use the same names.
+ */
+
+import { describe, test, expect, beforeAll } from 'bun:test'
+import { Gpu, getTextureFormatInfo } from '../webgpu.js'
+
+const READ_WRITE_STORAGE_TEXTURES = 'readonly-and-readwrite-storage-textures'
+
+let adapter
+
+beforeAll(async () => {
+  adapter = await Gpu().requestAdapter()
+})
+
+describe('Feature Names: Reporting', () => {
+  test('should report kebab-case names', () => {
+    for (const name of adapter.features) {
+      expect(name).toMatch(/^[a-z0-9]+(-[a-z0-9]+)*$/)
+    }
+  })
+
+  test('should grant every feature the adapter reports', async () => {
+    const features = adapter.features.filter((name) => name !== READ_WRITE_STORAGE_TEXTURES)
+    const device = await adapter.requestDevice({ requiredFeatures: adapter.features })
+    for (const name of features) {
+      expect(device.features.has(name)).toBe(true)
+    }
+    device.destroy()
+  })
+
+  test('should name format features consistently', () => {
+    expect(getTextureFormatInfo('bc1-rgba-unorm').requiredFeature).toBe('texture-compression-bc')
+    expect(getTextureFormatInfo('r16unorm').requiredFeature).toBe('wgpu-texture-format-16bit-norm')
+  })
+})
+
+describe('Feature Names: Validation', () => {
+  test('should reject unknown features', async () => {
+    await expect(adapter.requestDevice({ requiredFeatures: ['teleportation'] })).rejects.toThrow('Unknown feature: teleportation')
+  })
+
+  test('should list features the adapter lacks', async () => {
+    const missing = ['wgpu-ray-query', 'wgpu-shader-f64', 'texture-compression-astc']
+      .filter((name) => !adapter.features.includes(name))
+    if (missing.length === 0) {
+      return
+    }
+    await expect(adapter.requestDevice({ requiredFeatures: missing }))
+      .rejects.toThrow(`Adapter doesn't support the required features: ${missing.join(', ')}`)
+  })
+})
@@ -0,0 +1,15 @@
+// Worker for test/worker-handles.test.ts: fills a shared buffer on a shared device
+const { parentPort, workerData } = require('worker_threads')
+const { deviceFromHandle, bufferFromHandle } = require(workerData.webgpu)
+
+async function main() {
+  const device = deviceFromHandle(workerData.device)
+  const buffer = bufferFromHandle(workerData.buffer)
+
+  device.queue.writeBuffer(buffer, 0, new Uint32Array([10, 20, 30, 40]))
+  device.queue.submit([])
+  await device.queue.onSubmittedWorkDone()
+  parentPort.postMessage('done')
+}
+
+main().catch((error) => parentPort.postMessage(`error: ${error.message}`))
@@ -0,0 +1,5 @@
+#include "constants.wgsl"
+
+fn scale(value: f32) -> f32 {
+    return value * SCALE;
+}
@@ -0,0 +1,126 @@
+/**
+ * Frame Capture Tests
+ *
+ * device.createFrameCapture() reads textures back through rotating staging
+ * buffers and hands the frames out in order, as RGBA or yuv420p bytes.
+ */
+
+import { describe, test, expect, beforeAll, afterAll } from 'bun:test'
+import { Writable } from 'stream'
+import { pipeline } from 'stream/promises'
+import { Gpu, GPUTextureUsage } from '../webgpu.js'
+
+let device
+
+beforeAll(async () => {
+  const adapter = await Gpu().requestAdapter()
+  device = await adapter.requestDevice()
+})
+
+afterAll(() => {
+  device?.destroy()
+})
+
+// 2x2 texture filled with one color
+function solidTexture(color, format = 'rgba8unorm') {
+  return device.createTexture({
+    size: [2, 2],
+    format,
+    usage: GPUTextureUsage.COPY_SRC | GPUTextureUsage.TEXTURE_BINDING,
+    data: new Uint8Array([...color, ...color, ...color, ...color])
+  })
+}
+
+describe('Frame Capture: Frames', () => {
+  test('should return frames in capture order with their timestamps', async () => {
+    const capture = device.createFrameCapture({ bufferCount: 2 })
+    // More frames than staging buffers
+    for (let i = 0; i < 5; i++) {
+      expect(await capture.capture(solidTexture([i * 10, 0, 0, 255]), i * 40)).toBe(i)
+    }
+    capture.end()
+
+    const frames = []
+    for await (const frame of capture) frames.push(frame)
+    expect(frames.map((frame) => [frame.index, frame.timestamp, frame.data[0]])).toEqual([
+      [0, 0, 0],
+      [1, 40, 10],
+      [2, 80, 20],
+      [3, 120, 30],
+      [4, 160, 40]
+    ])
+    expect(frames[0].data.length).toBe(16)
+    expect(await capture.next()).toBeNull()
+  })
+
+  test('should swizzle BGRA textures to RGBA', async () => {
+    const capture = device.createFrameCapture()
+    await capture.capture(solidTexture([10, 20, 30, 40], 'bgra8unorm'))
+
+    const frame = await capture.next()
+    expect(Array.from(frame.data.subarray(0, 4))).toEqual([30, 20, 10, 40])
+    expect(frame.timestamp).toBeGreaterThanOrEqual(0)
+  })
+
+  test('should convert frames to yuv420p planes', async () => {
+    const capture = device.createFrameCapture({ pixelFormat: 'yuv420p' })
+    await capture.capture(solidTexture([255, 255, 255, 255]))
+    await capture.capture(solidTexture([0, 0, 0, 255]))
+
+    // Four Y bytes, then one U and one V byte
+    expect(Array.from((await capture.next()).data)).toEqual([235, 235, 235, 235, 128, 128])
+    expect(Array.from((await capture.next()).data)).toEqual([16, 16, 16, 16, 128, 128])
+  })
+
+  test('should wait in next() for the next capture', async () => {
+    const capture = device.createFrameCapture()
+    const next = capture.next()
+    await capture.capture(solidTexture([1, 2, 3, 4]))
+
+    expect((await next).index).toBe(0)
+    const ended = capture.next()
+    capture.end()
+    expect(await ended).toBeNull()
+  })
+})
+
+describe('Frame Capture: Streams', () => {
+  test('should stream frame bytes while capture() waits for the consumer', async () => {
+    const capture = device.createFrameCapture({ maxQueuedFrames: 1 })
+    const chunks = []
+    const consumer = pipeline(
+      capture.stream(),
+      new Writable({
+        write(chunk, encoding, callback) {
+          chunks.push(chunk[0])
+          callback()
+        }
+      })
+    )
+
+    for (let i = 0; i < 4; i++) {
+      await capture.capture(solidTexture([i, 0, 0, 255]))
+      expect(capture.queuedFrames).toBeLessThanOrEqual(1)
+    }
+    capture.end()
+    await consumer
+
+    expect(chunks).toEqual([0, 1, 2, 3])
+  })
+})
+
+describe('Frame Capture: Errors', () => {
+  test('should reject unknown pixel formats and textures it cannot read', async () => {
+    expect(() => device.createFrameCapture({ pixelFormat: 'nv12' })).toThrow(/'rgba' or 'yuv420p', got 'nv12'/)
+
+    const capture = device.createFrameCapture()
+    const texture = device.createTexture({ size: [2, 2], format: 'r32float', usage: GPUTextureUsage.COPY_SRC })
+    await expect(capture.capture(texture)).rejects.toThrow(/capture\(\) supports .* got r32float/)
+  })
+
+  test('should reject captures after end()', async () => {
+    const capture = device.createFrameCapture()
+    capture.end()
+    await expect(capture.capture(solidTexture([0, 0, 0, 0]))).rejects.toThrow(/the capture was ended/)
+  })
+})
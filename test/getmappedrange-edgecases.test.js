@@ -149,7 +149,7 @@ async function testGetMappedRangeEdgeCases() {
     })
 
     // Initial state should be unmapped
-    let state = buffer4.mapState()
+    let state = buffer4.mapState
     if (state === 'unmapped') {
         console.log('   ✅ Initial state: unmapped')
     } else {
@@ -159,7 +159,7 @@ async function testGetMappedRangeEdgeCases() {
 
     // Map async and check state transitions
     const mapPromise = buffer4.mapAsync('READ')
-    state = buffer4.mapState()
+    state = buffer4.mapState
     if (state === 'pending') {
         console.log('   ✅ State during mapAsync: pending')
     } else {
@@ -167,7 +167,7 @@ async function testGetMappedRangeEdgeCases() {
     }
 
     await mapPromise
-    state = buffer4.mapState()
+    state = buffer4.mapState
     if (state === 'mapped') {
         console.log('   ✅ State after mapAsync: mapped')
     } else {
@@ -177,7 +177,7 @@ async function testGetMappedRangeEdgeCases() {
 
     // Unmap and check state
     buffer4.unmap()
-    state = buffer4.mapState()
+    state = buffer4.mapState
     if (state === 'unmapped') {
         console.log('   ✅ State after unmap: unmapped\n')
     } else {
@@ -191,7 +191,7 @@ async function testGetMappedRangeEdgeCases() {
         usage: GPUBufferUsage.COPY_DST,
         mappedAtCreation: true
     })
-    state = buffer4b.mapState()
+    state = buffer4b.mapState
     if (state === 'mapped') {
         console.log('   ✅ mappedAtCreation buffer starts in mapped state\n')
     } else {
@@ -0,0 +1,73 @@
+/**
+ * GpuTimer Tests
+ *
+ * Timing needs the timestamp-query feature; without it createTimer must fail
+ * with a clear error instead of producing bogus durations.
+ */
+
+import { describe, test, expect, beforeAll, afterAll } from 'bun:test'
+import { Gpu, GPUBufferUsage } from '../webgpu.js'
+
+let device
+let supported
+
+beforeAll(async () => {
+  const adapter = await Gpu().requestAdapter()
+  device = await adapter.requestDevice()
+  supported = device.features.has('timestamp-query')
+})
+
+afterAll(() => {
+  device?.destroy()
+})
+
+function recordCopy(encoder) {
+  const source = device.createBuffer({ size: 1024, usage: GPUBufferUsage.COPY_SRC })
+  const destination = device.createBuffer({ size: 1024, usage: GPUBufferUsage.COPY_DST })
+  encoder.copyBufferToBuffer(source, 0, destination, 0, 1024)
+}
+
+describe('GpuTimer: Creation', () => {
+  test('should require the timestamp-query feature', () => {
+    if (supported) {
+      expect(device.createTimer().capacity).toBe(32)
+    } else {
+      expect(() => device.createTimer()).toThrow('timestamp-query')
+    }
+  })
+
+  test('should reject invalid capacities', () => {
+    if (!supported) return
+    expect(() => device.createTimer({ capacity: 0 })).toThrow('Invalid timer capacity')
+  })
+})
+
+describe('GpuTimer: Measurement', () => {
+  test('should report labeled durations in milliseconds', async () => {
+    if (!supported) return
+    const timer = device.createTimer({ capacity: 2 })
+    const encoder = device.createCommandEncoder()
+
+    expect(timer.wrap(encoder, 'copy', recordCopy)).toBeUndefined()
+    const span = timer.begin(encoder, 'second')
+    recordCopy(encoder)
+    timer.end(encoder, span)
+    device.queue.submit([encoder.finish()])
+
+    const results = await timer.results()
+    expect(results.map((r) => r.label)).toEqual(['copy', 'second'])
+    for (const { ms } of results) {
+      expect(ms).toBeGreaterThanOrEqual(0)
+    }
+    expect(await timer.results()).toEqual([])
+  })
+
+  test('should reject spans beyond capacity and unended spans', async () => {
+    if (!supported) return
+    const timer = device.createTimer({ capacity: 1 })
+    const encoder = device.createCommandEncoder()
+    timer.begin(encoder, 'open')
+    expect(() => timer.begin(encoder, 'extra')).toThrow('capacity')
+    await expect(timer.results()).rejects.toThrow('never ended')
+  })
+})
@@ -0,0 +1,58 @@
+/**
+ * Half-Precision Helper Tests
+ *
+ * packF16 converts floats to little-endian f16 bytes and unpackF16 back,
+ * rounding to nearest even like the GPU does.
+ */
+
+import { describe, test, expect } from 'bun:test'
+import { packF16, unpackF16 } from '../webgpu.js'
+
+function bits(buffer) {
+  return Array.from(new Uint16Array(buffer.buffer, buffer.byteOffset, buffer.byteLength / 2))
+}
+
+describe('Half-Precision Helpers: packF16', () => {
+  test('should encode special and boundary values', () => {
+    const values = new Float32Array([0, -0, 1, -2, 65504, 65520, Infinity, -Infinity, 2 ** -24, 2 ** -14, 1e-9])
+    expect(bits(packF16(values))).toEqual([
+      0x0000, 0x8000, 0x3c00, 0xc000, 0x7bff, 0x7c00, 0x7c00, 0xfc00, 0x0001, 0x0400, 0x0000,
+    ])
+    expect(bits(packF16([NaN]))[0] & 0x7c00).toBe(0x7c00)
+  })
+
+  test('should round to nearest even', () => {
+    // 1 + 2^-11 is halfway between 1 and the next f16; 1 + 3 * 2^-11 rounds up
+    expect(bits(packF16([1 + 2 ** -11, 1 + 3 * 2 ** -11]))).toEqual([0x3c00, 0x3c02])
+  })
+
+  test('should convert the same with and without SIMD', () => {
+    // 19 values: two groups of eight and a scalar tail
+    const values = Float32Array.from({ length: 19 }, (_, i) => (i - 9) * 0.37)
+    const packed = bits(packF16(values))
+    for (let i = 0; i < values.length; i++) {
+      expect(packed[i]).toBe(bits(packF16([values[i]]))[0])
+    }
+  })
+})
+
+describe('Half-Precision Helpers: unpackF16', () => {
+  test('should round-trip values exactly representable in f16', () => {
+    const values = Float32Array.from({ length: 37 }, (_, i) => (i - 18) / 4)
+    expect(Array.from(unpackF16(packF16(values)))).toEqual(Array.from(values))
+  })
+
+  test('should decode subnormals, infinities and NaN', () => {
+    const decoded = unpackF16(new Uint16Array([0x0001, 0x03ff, 0x7c00, 0xfc00, 0x7e00, 0x8000]))
+    expect(decoded[0]).toBe(2 ** -24)
+    expect(decoded[1]).toBe(1023 * 2 ** -24)
+    expect(decoded[2]).toBe(Infinity)
+    expect(decoded[3]).toBe(-Infinity)
+    expect(Number.isNaN(decoded[4])).toBe(true)
+    expect(Object.is(decoded[5], -0)).toBe(true)
+  })
+
+  test('should reject odd byte lengths', () => {
+    expect(() => unpackF16(new Uint8Array(3))).toThrow('multiple of 2')
+  })
+})
@@ -0,0 +1,126 @@
+/**
+ * Histogram Tests
+ *
+ * device.createHistogram({ bins }) counts the channels of a texture or of
+ * packed RGBA8 pixels into `bins` equal ranges of [0, 1].
+ */
+
+import { describe, test, expect, beforeAll, afterAll } from 'bun:test'
+import { Gpu, GPUBufferUsage, GPUTextureUsage } from '../webgpu.js'
+
+let device
+
+beforeAll(async () => {
+  const adapter = await Gpu().requestAdapter()
+  device = await adapter.requestDevice()
+})
+
+afterAll(() => {
+  device?.destroy()
+})
+
+function createPixelBuffer(pixels) {
+  const buffer = device.createBuffer({ size: pixels.byteLength, usage: GPUBufferUsage.STORAGE | GPUBufferUsage.COPY_DST })
+  device.queue.writeBuffer(buffer, 0, pixels)
+  return buffer
+}
+
+function createTexture(width, height, pixels) {
+  const texture = device.createTexture({
+    width,
+    height,
+    format: 'rgba8unorm',
+    usage: GPUTextureUsage.TEXTURE_BINDING | GPUTextureUsage.COPY_DST,
+  })
+  const upload = device.createBuffer({ size: 256 * height, usage: GPUBufferUsage.COPY_SRC | GPUBufferUsage.COPY_DST })
+  for (let row = 0; row < height; row++) {
+    device.queue.writeBuffer(upload, row * 256, pixels.subarray(row * width * 4, (row + 1) * width * 4))
+  }
+  const encoder = device.createCommandEncoder()
+  encoder.copyBufferToTexture({ buffer: upload, bytesPerRow: 256 }, { texture }, { width, height })
+  device.queue.submit([encoder.finish()])
+  return texture
+}
+
+describe('Histogram: Buffers', () => {
+  test('should count each channel of packed pixels', async () => {
+    const count = 5000
+    const pixels = new Uint8Array(count * 4)
+    for (let i = 0; i < count; i++) pixels.set([i % 256, 255, 0, 128], i * 4)
+
+    const histogram = device.createHistogram()
+    const result = await histogram.computeBuffer(createPixelBuffer(pixels), count)
+
+    expect(histogram.bins).toBe(256)
+    expect(result.red.length).toBe(256)
+    expect(result.red[0]).toBe(Math.ceil(count / 256))
+    expect(result.red[255]).toBe(Math.floor(count / 256))
+    expect(result.red.reduce((sum, n) => sum + n, 0)).toBe(count)
+    expect(result.green[255]).toBe(count)
+    expect(result.blue[0]).toBe(count)
+    expect(result.alpha[128]).toBe(count)
+  })
+
+  test('should group values into fewer bins', async () => {
+    const pixels = new Uint8Array([0, 0, 0, 0, 63, 64, 191, 192, 255, 255, 255, 255])
+    const result = await device.createHistogram({ bins: 4 }).computeBuffer(createPixelBuffer(pixels), 3)
+
+    expect(result.red).toEqual([2, 0, 0, 1])
+    expect(result.green).toEqual([1, 1, 0, 1])
+    expect(result.blue).toEqual([1, 0, 1, 1])
+    expect(result.alpha).toEqual([1, 0, 0, 2])
+  })
+
+  test('should encode into an output buffer', async () => {
+    const histogram = device.createHistogram({ bins: 2 })
+    const output = device.createBuffer({
+      size: histogram.outputSize,
+      usage: GPUBufferUsage.STORAGE | GPUBufferUsage.COPY_SRC | GPUBufferUsage.COPY_DST,
+    })
+    const readback = device.createBuffer({ size: histogram.outputSize, usage: GPUBufferUsage.COPY_DST | GPUBufferUsage.MAP_READ })
+    const pixels = createPixelBuffer(new Uint8Array([255, 0, 255, 0, 255, 255, 0, 0]))
+
+    // Encoding twice still yields one histogram, since the output is cleared
+    const encoder = device.createCommandEncoder()
+    histogram.encodeBuffer(encoder, pixels, 2, output)
+    histogram.encodeBuffer(encoder, pixels, 2, output)
+    encoder.copyBufferToBuffer(output, 0, readback, 0, histogram.outputSize)
+    device.queue.submit([encoder.finish()])
+
+    await readback.mapAsync('READ')
+    expect(Array.from(new Uint32Array(readback.getMappedRange()))).toEqual([0, 2, 1, 1, 1, 1, 2, 0])
+    readback.unmap()
+  })
+})
+
+describe('Histogram: Textures', () => {
+  test('should count every texel of a texture', async () => {
+    const width = 20
+    const height = 18
+    const pixels = new Uint8Array(width * height * 4)
+    for (let i = 0; i < width * height; i++) pixels.set([i < 100 ? 0 : 255, 0, 0, 255], i * 4)
+
+    const result = await device.createHistogram({ bins: 16 }).compute(createTexture(width, height, pixels))
+
+    expect(result.red[0]).toBe(100)
+    expect(result.red[15]).toBe(width * height - 100)
+    expect(result.alpha[15]).toBe(width * height)
+  })
+})
+
+describe('Histogram: Validation', () => {
+  test('should reject invalid bin counts', () => {
+    expect(() => device.createHistogram({ bins: 0 })).toThrow('between 1 and 256')
+    expect(() => device.createHistogram({ bins: 512 })).toThrow('between 1 and 256')
+  })
+
+  test('should reject buffers that are too small or lack usage', () => {
+    const histogram = device.createHistogram()
+    const pixels = createPixelBuffer(new Uint8Array(16))
+    const output = device.createBuffer({ size: histogram.outputSize, usage: GPUBufferUsage.STORAGE })
+    const encoder = device.createCommandEncoder()
+
+    expect(() => histogram.encodeBuffer(encoder, pixels, 5, output)).toThrow('too small')
+    expect(() => histogram.encodeBuffer(encoder, pixels, 4, output)).toThrow('COPY_DST')
+  })
+})
@@ -0,0 +1,84 @@
+/**
+ * Indirect Argument Tests
+ *
+ * pack*IndirectArgs() and unpack*IndirectArgs() convert indirect argument
+ * structs to and from their buffer layouts.
+ */
+
+import { describe, test, expect, beforeAll, afterAll } from 'bun:test'
+import {
+  Gpu,
+  GPUBufferUsage,
+  packDrawIndirectArgs,
+  packDrawIndexedIndirectArgs,
+  packDispatchIndirectArgs,
+  unpackDrawIndirectArgs,
+  unpackDrawIndexedIndirectArgs,
+  unpackDispatchIndirectArgs
+} from '../webgpu.js'
+
+let device
+
+beforeAll(async () => {
+  const adapter = await Gpu().requestAdapter()
+  device = await adapter.requestDevice()
+})
+
+afterAll(() => {
+  device?.destroy()
+})
+
+describe('Indirect Args: Packing', () => {
+  test('should pack structs with their defaults', () => {
+    const draw = packDrawIndirectArgs({ vertexCount: 6 })
+    expect(Array.from(new Uint32Array(draw.buffer, draw.byteOffset, 4))).toEqual([6, 1, 0, 0])
+
+    const indexed = packDrawIndexedIndirectArgs([{ indexCount: 3, baseVertex: -2 }, { indexCount: 9, firstIndex: 3 }])
+    expect(indexed.length).toBe(40)
+    expect(Array.from(new Int32Array(indexed.buffer, indexed.byteOffset, 10))).toEqual([3, 1, 0, -2, 0, 9, 1, 3, 0, 0])
+  })
+
+  test('should pack at an offset and stride into a destination', () => {
+    const destination = new Uint32Array(12).fill(99)
+    const result = packDispatchIndirectArgs([{ x: 4 }, { x: 5, y: 6, z: 7 }], { offset: 8, stride: 16 }, destination)
+
+    expect(result).toBe(destination)
+    expect(Array.from(destination)).toEqual([99, 99, 4, 1, 1, 99, 5, 6, 7, 99, 99, 99])
+  })
+
+  test('should reject destinations that are too small and bad strides', () => {
+    expect(() => packDrawIndirectArgs([{ vertexCount: 1 }, { vertexCount: 2 }], {}, new Uint8Array(20))).toThrow(
+      /2 DrawIndirectArgs structs need 32 bytes, the destination has 20/
+    )
+    expect(() => packDrawIndirectArgs({ vertexCount: 1 }, { stride: 12 })).toThrow(/stride at least 16/)
+  })
+})
+
+describe('Indirect Args: Unpacking', () => {
+  test('should unpack every struct that fits', () => {
+    const data = packDrawIndexedIndirectArgs([{ indexCount: 3, baseVertex: -1 }, { indexCount: 6, instanceCount: 2 }])
+    expect(unpackDrawIndexedIndirectArgs(data)).toEqual([
+      { indexCount: 3, instanceCount: 1, firstIndex: 0, baseVertex: -1, firstInstance: 0 },
+      { indexCount: 6, instanceCount: 2, firstIndex: 0, baseVertex: 0, firstInstance: 0 }
+    ])
+  })
+
+  test('should unpack a count of structs at an offset and stride', () => {
+    const data = new Uint32Array([0, 7, 1, 1, 0, 8, 2, 3, 0, 9, 9, 9])
+    expect(unpackDispatchIndirectArgs(data, { offset: 4, stride: 16, count: 2 })).toEqual([
+      { x: 7, y: 1, z: 1 },
+      { x: 8, y: 2, z: 3 }
+    ])
+    expect(() => unpackDispatchIndirectArgs(data, { stride: 16, count: 4 })).toThrow(/hold 3 DispatchIndirectArgs structs/)
+  })
+
+  test('should round-trip through a GPU buffer', () => {
+    const args = [{ vertexCount: 3, instanceCount: 4, firstVertex: 5, firstInstance: 6 }]
+    const buffer = device.createBufferInit({
+      data: packDrawIndirectArgs(args),
+      usage: GPUBufferUsage.INDIRECT | GPUBufferUsage.COPY_SRC
+    })
+
+    expect(unpackDrawIndirectArgs(buffer.readSync())).toEqual(args)
+  })
+})
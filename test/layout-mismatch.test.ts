@@ -0,0 +1,150 @@
+/**
+ * Layout Mismatch Diagnostics Tests
+ *
+ * Bind groups that don't fit their layout, and bind groups that don't fit the
+ * pipeline's layout, fail with a per-binding diff of the layout entries
+ * against what was provided.
+ */
+
+import { describe, test, expect, beforeAll, afterAll } from 'bun:test'
+import { Gpu, GPUBufferUsage, GPUShaderStage } from '../webgpu.js'
+
+let device
+let layout
+
+const SHADER = `
+  @group(0) @binding(0) var<storage, read_write> data: array<u32>;
+  @group(0) @binding(1) var<uniform> params: vec4<u32>;
+
+  @compute @workgroup_size(1)
+  fn main() {
+    data[0] = params.x;
+  }
+`
+
+beforeAll(async () => {
+  const adapter = await Gpu().requestAdapter()
+  device = await adapter.requestDevice()
+  layout = device.createBindGroupLayout({
+    entries: [
+      { binding: 0, visibility: GPUShaderStage.COMPUTE, buffer: { type: 'storage', minBindingSize: 64 } },
+      { binding: 1, visibility: GPUShaderStage.COMPUTE, buffer: { type: 'uniform' } }
+    ]
+  })
+})
+
+afterAll(() => {
+  device?.destroy()
+})
+
+function createBuffer(label, size, usage) {
+  return device.createBuffer({ label, size, usage })
+}
+
+function dispatch(pipeline, bindGroup) {
+  const pass = device.createCommandEncoder().beginComputePass()
+  pass.setPipeline(pipeline)
+  pass.setBindGroup(0, bindGroup)
+  pass.dispatchWorkgroups(1)
+  pass.end()
+}
+
+describe('Layout Mismatch: createBindGroup', () => {
+  test('should list each binding that does not fit the layout', () => {
+    const params = createBuffer('params', 16, GPUBufferUsage.UNIFORM)
+    let message = ''
+    try {
+      device.createBindGroup({ layout, entries: [{ binding: 0, resource: { buffer: params } }] })
+    } catch (error) {
+      message = error.message
+    }
+
+    expect(message).toBe(
+      [
+        'createBindGroup(label: (none), entries: [0 (buffer)]): entries don\'t match the bind group layout',
+        '  binding 0:',
+        '  - layout:   storage buffer, at least 64 bytes, visible to COMPUTE',
+        "  + provided: buffer 'params', 16 bytes, usage UNIFORM",
+        '  binding 1:',
+        '  - layout:   uniform buffer, visible to COMPUTE',
+        '  + provided: nothing'
+      ].join('\n')
+    )
+  })
+
+  test('should report bindings the layout does not have', () => {
+    const data = createBuffer('data', 64, GPUBufferUsage.STORAGE)
+    const params = createBuffer('params', 16, GPUBufferUsage.UNIFORM)
+
+    expect(() =>
+      device.createBindGroup({
+        layout,
+        entries: [
+          { binding: 0, resource: { buffer: data } },
+          { binding: 1, resource: { buffer: params } },
+          { binding: 2, resource: device.createSampler({}) }
+        ]
+      })
+    ).toThrow(/  binding 2:\n  - layout:   nothing\n  \+ provided: sampler$/)
+  })
+
+  test('should keep the wgpu message when every binding fits', () => {
+    // The offset isn't aligned to minStorageBufferOffsetAlignment
+    const data = createBuffer('data', 512, GPUBufferUsage.STORAGE)
+    const params = createBuffer('params', 16, GPUBufferUsage.UNIFORM)
+
+    expect(() =>
+      device.createBindGroup({
+        layout,
+        entries: [
+          { binding: 0, resource: { buffer: data, offset: 4 } },
+          { binding: 1, resource: { buffer: params } }
+        ]
+      })
+    ).toThrow(/createBindGroup.*: validation failed/)
+  })
+})
+
+describe('Layout Mismatch: passes', () => {
+  test('should diff the bind group layout against the pipeline layout', () => {
+    const pipeline = device.createComputePipeline({
+      layout: device.createPipelineLayout({ bindGroupLayouts: [layout] }),
+      compute: { module: device.createShaderModule({ code: SHADER }), entryPoint: 'main' }
+    })
+    const other = device.createBindGroupLayout({
+      entries: [{ binding: 0, visibility: GPUShaderStage.COMPUTE, buffer: { type: 'read-only-storage' } }]
+    })
+    const bindGroup = device.createBindGroup({
+      layout: other,
+      entries: [{ binding: 0, resource: { buffer: createBuffer('data', 64, GPUBufferUsage.STORAGE) } }]
+    })
+
+    expect(() => dispatch(pipeline, bindGroup)).toThrow(
+      [
+        "end: bind group 0 doesn't match the pipeline layout at dispatch 1 of the pass",
+        '  binding 0:',
+        '  - pipeline:   storage buffer, at least 64 bytes, visible to COMPUTE',
+        '  + bind group: read-only-storage buffer, visible to COMPUTE',
+        '  binding 1:',
+        '  - pipeline:   uniform buffer, visible to COMPUTE',
+        '  + bind group: nothing'
+      ].join('\n')
+    )
+  })
+
+  test('should accept bind groups whose layout has the same entries', () => {
+    const pipeline = device.createComputePipeline({
+      layout: device.createPipelineLayout({ bindGroupLayouts: [layout] }),
+      compute: { module: device.createShaderModule({ code: SHADER }), entryPoint: 'main' }
+    })
+    const bindGroup = device.createBindGroup({
+      layout: pipeline.getBindGroupLayout(0),
+      entries: [
+        { binding: 0, resource: { buffer: createBuffer('data', 64, GPUBufferUsage.STORAGE) } },
+        { binding: 1, resource: { buffer: createBuffer('params', 16, GPUBufferUsage.UNIFORM) } }
+      ]
+    })
+
+    expect(() => dispatch(pipeline, bindGroup)).not.toThrow()
+  })
+})
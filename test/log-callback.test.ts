@@ -0,0 +1,89 @@
+/**
+ * Log Callback Tests
+ *
+ * wgpu and naga log records are forwarded to the setLogCallback callback.
+ */
+
+import { describe, test, expect, afterAll } from 'bun:test'
+import { Gpu, GPUBufferUsage, setLogCallback } from '../webgpu.js'
+
+const LEVELS = ['error', 'warn', 'info', 'debug', 'trace']
+
+// Records are queued from any thread and delivered on a later tick
+function settle() {
+  return new Promise((resolve) => setTimeout(resolve, 50))
+}
+
+afterAll(() => {
+  setLogCallback('off')
+})
+
+describe('Log Callback: Forwarding', () => {
+  test('should forward wgpu records at the requested level', async () => {
+    const records = []
+    setLogCallback('trace', (record) => records.push(record))
+
+    const adapter = await Gpu().requestAdapter()
+    const device = await adapter.requestDevice()
+    device.createBuffer({ label: 'logged', size: 16, usage: GPUBufferUsage.STORAGE })
+    device.destroy()
+    await settle()
+
+    expect(records.length).toBeGreaterThan(0)
+    for (const record of records) {
+      expect(LEVELS).toContain(record.level)
+      expect(typeof record.target).toBe('string')
+      expect(typeof record.message).toBe('string')
+    }
+    expect(records.some((record) => record.target.startsWith('wgpu'))).toBe(true)
+  })
+
+  test('should filter out records below the level', async () => {
+    const records = []
+    setLogCallback('warn', (record) => records.push(record))
+
+    const adapter = await Gpu().requestAdapter()
+    const device = await adapter.requestDevice()
+    device.destroy()
+    await settle()
+
+    expect(records.every((record) => record.level === 'error' || record.level === 'warn')).toBe(true)
+  })
+
+  test('should stop forwarding when turned off', async () => {
+    const records = []
+    setLogCallback('trace', (record) => records.push(record))
+    setLogCallback('off')
+
+    const adapter = await Gpu().requestAdapter()
+    const device = await adapter.requestDevice()
+    device.destroy()
+    await settle()
+
+    expect(records).toEqual([])
+  })
+})
+
+describe('Log Callback: Package Warnings', () => {
+  test('should forward warnings about resubmitted command buffers', async () => {
+    const records = []
+    setLogCallback('warn', (record) => records.push(record))
+
+    const adapter = await Gpu().requestAdapter()
+    const device = await adapter.requestDevice()
+    const commandBuffer = device.createCommandEncoder().finish()
+    device.queue.submit([commandBuffer]).submit([commandBuffer])
+    device.destroy()
+    await settle()
+
+    expect(records.filter((record) => record.target === 'webgpu').map((record) => record.message)).toEqual([
+      'submit: skipped 1 of 1 command buffers that were already submitted',
+    ])
+  })
+})
+
+describe('Log Callback: Validation', () => {
+  test('should reject unknown levels', () => {
+    expect(() => setLogCallback('verbose', () => {})).toThrow('Invalid log level')
+  })
+})
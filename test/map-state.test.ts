@@ -0,0 +1,131 @@
+/**
+ * Map State Tests
+ *
+ * mapAsync() follows the WebGPU mapping state machine: it rejects while a
+ * map is pending or active, unmap() and destroy() abort a pending map, and
+ * getMappedRange() stays within the range that was mapped.
+ */
+
+import { describe, test, expect, beforeAll, afterAll } from 'bun:test'
+import { Gpu, GPUBufferUsage, GPUMapMode } from '../webgpu.js'
+
+let device
+
+beforeAll(async () => {
+  const adapter = await Gpu().requestAdapter()
+  device = await adapter.requestDevice()
+})
+
+afterAll(() => {
+  device?.destroy()
+})
+
+function readback(label = 'readback', size = 64) {
+  return device.createBuffer({ label, size, usage: GPUBufferUsage.MAP_READ | GPUBufferUsage.COPY_DST })
+}
+
+describe('Map State: Transitions', () => {
+  test('should be pending as soon as mapAsync() returns', async () => {
+    const buffer = readback()
+    const mapping = buffer.mapAsync(GPUMapMode.READ)
+
+    expect(buffer.mapState).toBe('pending')
+    await mapping
+    expect(buffer.mapState).toBe('mapped')
+    buffer.unmap()
+    expect(buffer.mapState).toBe('unmapped')
+  })
+
+  test('should reject mapAsync() while pending or mapped', async () => {
+    const buffer = readback('busy')
+    const mapping = buffer.mapAsync(GPUMapMode.READ)
+
+    await expect(buffer.mapAsync(GPUMapMode.READ)).rejects.toThrow("mapAsync: buffer 'busy' is already pending")
+    await mapping
+    await expect(buffer.mapAsync(GPUMapMode.READ)).rejects.toThrow("mapAsync: buffer 'busy' is already mapped")
+    buffer.unmap()
+  })
+
+  test('should treat unmap() of an unmapped buffer as a no-op', () => {
+    const buffer = readback()
+    buffer.unmap()
+    expect(buffer.mapState).toBe('unmapped')
+  })
+})
+
+describe('Map State: Aborting', () => {
+  test('should abort a pending map on unmap()', async () => {
+    const buffer = readback()
+    const mapping = buffer.mapAsync(GPUMapMode.READ)
+    buffer.unmap()
+
+    expect(buffer.mapState).toBe('unmapped')
+    const error = await mapping.catch((error) => error)
+    expect(error).toBeInstanceOf(DOMException)
+    expect(error.name).toBe('AbortError')
+
+    // The buffer can be mapped again
+    await buffer.mapAsync(GPUMapMode.READ)
+    expect(buffer.mapState).toBe('mapped')
+    buffer.unmap()
+  })
+
+  test('should abort a pending map on destroy()', async () => {
+    const buffer = readback()
+    const mapping = buffer.mapAsync(GPUMapMode.READ)
+    buffer.destroy()
+
+    await expect(mapping).rejects.toThrow(/aborted by unmap\(\) or destroy\(\)/)
+    expect(buffer.mapState).toBe('unmapped')
+  })
+})
+
+describe('Map State: Mapped Ranges', () => {
+  test('should only return ranges within the mapped range', async () => {
+    const buffer = readback()
+    await buffer.mapAsync(GPUMapMode.READ, 16, 32)
+
+    expect(buffer.getMappedRange(16, 32).byteLength).toBe(32)
+    expect(() => buffer.getMappedRange(8, 8)).toThrow('getMappedRange() range [8, 16) is outside the mapped range [16, 48)')
+    // The default size runs to the end of the buffer, past the mapped range
+    expect(() => buffer.getMappedRange(40)).toThrow(/range \[40, 64\) is outside/)
+    buffer.unmap()
+  })
+
+  test('should write back ranges of a partial write map', async () => {
+    const upload = device.createBuffer({ size: 32, usage: GPUBufferUsage.MAP_WRITE | GPUBufferUsage.COPY_SRC })
+    await upload.mapAsync(GPUMapMode.WRITE, 16)
+    new Uint32Array(upload.getMappedRange(16, 8)).set([7, 9])
+    upload.unmap()
+
+    const buffer = readback('result', 32)
+    const encoder = device.createCommandEncoder()
+    encoder.copyBufferToBuffer(upload, 0, buffer, 0, 32)
+    device.queue.submit([encoder.finish()])
+    await buffer.mapAsync(GPUMapMode.READ)
+    expect(Array.from(new Uint32Array(buffer.getMappedRange()))).toEqual([0, 0, 0, 0, 7, 9, 0, 0])
+    buffer.unmap()
+  })
+
+  test('should check the mapped range is aligned and in bounds', async () => {
+    const buffer = readback()
+    await expect(buffer.mapAsync(GPUMapMode.READ, 4)).rejects.toThrow(/offset \(4\) must be a multiple of 8/)
+    await expect(buffer.mapAsync(GPUMapMode.READ, 32, 64)).rejects.toThrow(/exceeds buffer size \(64\)/)
+    expect(buffer.mapState).toBe('unmapped')
+  })
+})
+
+describe('Map State: Null Backend', () => {
+  test('should follow the same transitions', async () => {
+    const nullDevice = await (await Gpu({ backends: ['null'] }).requestAdapter()).requestDevice()
+    const buffer = nullDevice.createBuffer({ size: 64, usage: GPUBufferUsage.MAP_READ | GPUBufferUsage.COPY_DST })
+
+    const aborted = buffer.mapAsync(GPUMapMode.READ)
+    expect(buffer.mapState).toBe('pending')
+    buffer.unmap()
+    expect((await aborted.catch((error) => error)).name).toBe('AbortError')
+
+    await buffer.mapAsync(GPUMapMode.READ, 0, 16)
+    expect(() => buffer.getMappedRange(16, 8)).toThrow(/outside the mapped range \[0, 16\)/)
+  })
+})
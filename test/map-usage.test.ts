@@ -0,0 +1,72 @@
+/**
+ * Map Usage Tests
+ *
+ * createBuffer() names the usage flags that can't be combined with MAP_READ
+ * or MAP_WRITE, and mapAsync() the flag a map mode needs.
+ */
+
+import { describe, test, expect, beforeAll, afterAll } from 'bun:test'
+import { Gpu, GPUBufferUsage, GPUMapMode } from '../webgpu.js'
+
+let device
+
+beforeAll(async () => {
+  const adapter = await Gpu().requestAdapter()
+  device = await adapter.requestDevice()
+})
+
+afterAll(() => {
+  device?.destroy()
+})
+
+describe('Map Usage: createBuffer', () => {
+  test('should name the flags combined with MAP_READ', () => {
+    expect(() =>
+      device.createBuffer({
+        label: 'readback',
+        size: 16,
+        usage: GPUBufferUsage.MAP_READ | GPUBufferUsage.COPY_DST | GPUBufferUsage.STORAGE | GPUBufferUsage.UNIFORM
+      })
+    ).toThrow(/^createBuffer\(label: 'readback', .*\): MAP_READ can only be combined with COPY_DST, not UNIFORM \| STORAGE/)
+  })
+
+  test('should name the flags combined with MAP_WRITE', () => {
+    expect(() => device.createBuffer({ size: 16, usage: ['MAP_WRITE', 'COPY_DST'] })).toThrow(
+      /MAP_WRITE can only be combined with COPY_SRC, not COPY_DST/
+    )
+    expect(() =>
+      device.createBufferInit({ data: new Uint8Array(4), usage: GPUBufferUsage.MAP_WRITE | GPUBufferUsage.VERTEX })
+    ).toThrow(/^createBufferInit\(.*\): MAP_WRITE can only be combined with COPY_SRC, not VERTEX/)
+  })
+
+  test('should reject MAP_READ with MAP_WRITE', () => {
+    expect(() => device.createBuffer({ size: 16, usage: GPUBufferUsage.MAP_READ | GPUBufferUsage.MAP_WRITE })).toThrow(
+      /MAP_READ and MAP_WRITE can't be combined/
+    )
+  })
+
+  test('should accept valid map usages', () => {
+    device.createBuffer({ size: 16, usage: GPUBufferUsage.MAP_READ | GPUBufferUsage.COPY_DST })
+    device.createBuffer({ size: 16, usage: GPUBufferUsage.MAP_WRITE | GPUBufferUsage.COPY_SRC, mappedAtCreation: true }).unmap()
+  })
+})
+
+describe('Map Usage: mapAsync', () => {
+  test('should name the flag the map mode needs', async () => {
+    const buffer = device.createBuffer({ label: 'staging', size: 16, usage: GPUBufferUsage.MAP_READ | GPUBufferUsage.COPY_DST })
+
+    await expect(buffer.mapAsync(GPUMapMode.WRITE)).rejects.toThrow(
+      "mapAsync: buffer 'staging' can't be mapped for WRITE, its usage (MAP_READ | COPY_DST) is missing MAP_WRITE"
+    )
+    // The failed call leaves the buffer mappable
+    expect(buffer.mapState).toBe('unmapped')
+    await buffer.mapAsync(GPUMapMode.READ)
+    expect(buffer.mapState).toBe('mapped')
+    buffer.unmap()
+  })
+
+  test('should reject buffers without any map usage', async () => {
+    const buffer = device.createBuffer({ size: 16, usage: GPUBufferUsage.STORAGE })
+    await expect(buffer.mapAsync('READ')).rejects.toThrow(/can't be mapped for READ, its usage \(STORAGE\) is missing MAP_READ/)
+  })
+})
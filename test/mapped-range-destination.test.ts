@@ -0,0 +1,74 @@
+/**
+ * Mapped Range Destination Tests
+ *
+ * getMappedRange() and mapRead() can copy results straight into a
+ * caller-provided SharedArrayBuffer region instead of returning a new copy.
+ */
+
+import { describe, test, expect, beforeAll, afterAll } from 'bun:test'
+import { Gpu, GPUBufferUsage } from '../webgpu.js'
+
+let device
+
+beforeAll(async () => {
+  const adapter = await Gpu().requestAdapter()
+  device = await adapter.requestDevice()
+})
+
+afterAll(() => {
+  device?.destroy()
+})
+
+async function createReadback(values) {
+  const source = device.createBufferInit({ usage: GPUBufferUsage.COPY_SRC, data: new Uint32Array(values) })
+  const readback = device.createBuffer({ size: values.length * 4, usage: GPUBufferUsage.MAP_READ | GPUBufferUsage.COPY_DST })
+  const encoder = device.createCommandEncoder()
+  encoder.copyBufferToBuffer(source, 0, readback, 0, values.length * 4)
+  device.queue.submit([encoder.finish()])
+  return readback
+}
+
+describe('Mapped Range Destination: getMappedRange', () => {
+  test('should copy into a SharedArrayBuffer', async () => {
+    const readback = await createReadback([1, 2, 3, 4])
+    const shared = new SharedArrayBuffer(16)
+
+    await readback.mapAsync('READ')
+    expect(readback.getMappedRange(0, 16, shared)).toBe(shared)
+    readback.unmap()
+
+    expect(Array.from(new Uint32Array(shared))).toEqual([1, 2, 3, 4])
+  })
+
+  test('should copy a subrange into a view of a larger region', async () => {
+    const readback = await createReadback([1, 2, 3, 4])
+    const shared = new SharedArrayBuffer(32)
+
+    await readback.mapAsync('READ')
+    readback.getMappedRange(8, 8, new Uint32Array(shared, 16, 2))
+    // Copies don't create mapped views, so they may overlap
+    readback.getMappedRange(0, 16, new Uint32Array(shared, 0, 4))
+    readback.unmap()
+
+    expect(Array.from(new Uint32Array(shared))).toEqual([1, 2, 3, 4, 3, 4, 0, 0])
+  })
+
+  test('should reject destinations smaller than the range', async () => {
+    const readback = await createReadback([1, 2, 3, 4])
+
+    await readback.mapAsync('READ')
+    expect(() => readback.getMappedRange(0, 16, new SharedArrayBuffer(8))).toThrow('smaller than the range')
+    readback.unmap()
+  })
+})
+
+describe('Mapped Range Destination: mapRead', () => {
+  test('should read the whole buffer into the destination and unmap', async () => {
+    const readback = await createReadback([5, 6, 7, 8])
+    const shared = new SharedArrayBuffer(16)
+
+    expect(await readback.mapRead(shared)).toBe(shared)
+    expect(readback.mapState).toBe('unmapped')
+    expect(Array.from(new Uint32Array(shared))).toEqual([5, 6, 7, 8])
+  })
+})
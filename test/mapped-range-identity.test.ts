@@ -0,0 +1,79 @@
+/**
+ * Mapped Range Identity Tests
+ *
+ * Repeated getMappedRange() calls over the same range return the same
+ * ArrayBuffer, and every range is written back at its own offset on unmap().
+ */
+
+import { describe, test, expect, beforeAll, afterAll } from 'bun:test'
+import { Gpu, GPUBufferUsage } from '../webgpu.js'
+
+let device
+
+beforeAll(async () => {
+  const adapter = await Gpu().requestAdapter()
+  device = await adapter.requestDevice()
+})
+
+afterAll(() => {
+  device?.destroy()
+})
+
+async function readBack(buffer) {
+  const staging = device.createBuffer({ size: buffer.size, usage: GPUBufferUsage.MAP_READ | GPUBufferUsage.COPY_DST })
+  const encoder = device.createCommandEncoder()
+  encoder.copyBufferToBuffer(buffer, 0, staging, 0, buffer.size)
+  device.queue.submit([encoder.finish()])
+  await staging.mapAsync('READ')
+  const values = Array.from(new Uint32Array(staging.getMappedRange().slice(0)))
+  staging.unmap()
+  return values
+}
+
+describe('Mapped Range Identity: getMappedRange', () => {
+  test('should return the same ArrayBuffer for the same range', () => {
+    const buffer = device.createBuffer({ size: 32, usage: GPUBufferUsage.COPY_SRC, mappedAtCreation: true })
+
+    expect(buffer.getMappedRange()).toBe(buffer.getMappedRange(0, 32))
+    buffer.unmap()
+  })
+
+  test('should keep early views valid across repeated calls', async () => {
+    const buffer = device.createBuffer({ size: 16, usage: GPUBufferUsage.COPY_SRC, mappedAtCreation: true })
+    const early = new Uint32Array(buffer.getMappedRange())
+    early[0] = 1
+    new Uint32Array(buffer.getMappedRange())[1] = 2
+    early[2] = 3
+    buffer.unmap()
+
+    expect(await readBack(buffer)).toEqual([1, 2, 3, 0])
+  })
+
+  test('should write back each range at its offset', async () => {
+    const buffer = device.createBuffer({ size: 16, usage: GPUBufferUsage.COPY_SRC, mappedAtCreation: true })
+    new Uint32Array(buffer.getMappedRange(8, 8)).set([3, 4])
+    new Uint32Array(buffer.getMappedRange(0, 8)).set([1, 2])
+    buffer.unmap()
+
+    expect(await readBack(buffer)).toEqual([1, 2, 3, 4])
+  })
+
+  test('should still reject overlapping ranges', () => {
+    const buffer = device.createBuffer({ size: 32, usage: GPUBufferUsage.COPY_SRC, mappedAtCreation: true })
+    buffer.getMappedRange(0, 16)
+
+    expect(() => buffer.getMappedRange(8, 16)).toThrow(/overlaps/)
+    expect(() => buffer.getMappedRange(0, 8)).toThrow(/overlaps/)
+    buffer.unmap()
+  })
+
+  test('should start over after unmapping', async () => {
+    const buffer = device.createBuffer({ size: 16, usage: GPUBufferUsage.MAP_WRITE | GPUBufferUsage.COPY_SRC, mappedAtCreation: true })
+    const first = buffer.getMappedRange()
+    buffer.unmap()
+
+    await buffer.mapAsync('WRITE')
+    expect(buffer.getMappedRange()).not.toBe(first)
+    buffer.unmap()
+  })
+})
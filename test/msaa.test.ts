@@ -0,0 +1,138 @@
+/**
+ * MSAA Tests
+ *
+ * Color attachments with `sampleCount` render into a cached multisampled
+ * target and resolve into their view; createMultisampleTexture() creates a
+ * matching multisampled texture. A triangle edge crossing the first column
+ * of a 4x4 target shows whether the edge pixels were antialiased.
+ */
+
+import { describe, test, expect, beforeAll, afterAll } from 'bun:test'
+import { Gpu, GPUBufferUsage, GPUTextureUsage } from '../webgpu.js'
+
+const SIZE = 4
+
+let device
+
+// Green left of x = 0.4 pixels: half of the standard 4x sample positions of
+// the first column, but not its pixel centers
+const SHADER = `
+  @vertex
+  fn vs_main(@builtin(vertex_index) i: u32) -> @builtin(position) vec4<f32> {
+    var positions = array<vec2<f32>, 3>(vec2<f32>(-0.8, -3.0), vec2<f32>(-0.8, 3.0), vec2<f32>(-5.0, 0.0));
+    return vec4<f32>(positions[i], 0.5, 1.0);
+  }
+
+  @fragment
+  fn fs_main() -> @location(0) vec4<f32> {
+    return vec4<f32>(0.0, 1.0, 0.0, 1.0);
+  }
+`
+
+function createTarget() {
+  return device.createTexture({
+    size: [SIZE, SIZE],
+    format: 'rgba8unorm',
+    usage: GPUTextureUsage.RENDER_ATTACHMENT | GPUTextureUsage.COPY_SRC
+  })
+}
+
+function createPipeline(sampleCount) {
+  const module = device.createShaderModule({ code: SHADER })
+  return device.createRenderPipeline({
+    layout: null,
+    vertex: { module, entryPoint: 'vs_main' },
+    fragment: { module, entryPoint: 'fs_main', targets: [{ format: 'rgba8unorm' }] },
+    primitive: { topology: 'triangle-list' },
+    multisample: { count: sampleCount }
+  })
+}
+
+function draw(attachment, sampleCount = 4) {
+  const encoder = device.createCommandEncoder()
+  const pass = encoder.beginRenderPass({
+    colorAttachments: [{ loadOp: 'clear', storeOp: 'discard', clearValue: { r: 0, g: 0, b: 0, a: 1 }, ...attachment }]
+  })
+  pass.setPipeline(createPipeline(sampleCount))
+  pass.draw(3)
+  pass.end()
+  device.queue.submit([encoder.finish()])
+}
+
+// Green channel of the first two pixels of the top row
+async function readGreen(texture) {
+  const buffer = device.createBuffer({ size: 256 * SIZE, usage: GPUBufferUsage.COPY_DST | GPUBufferUsage.MAP_READ })
+  const encoder = device.createCommandEncoder()
+  encoder.copyTextureToBuffer({ texture }, { buffer, bytesPerRow: 256 }, [SIZE, SIZE])
+  device.queue.submit([encoder.finish()])
+  await buffer.mapAsync('READ')
+  const bytes = new Uint8Array(buffer.getMappedRange())
+  const green = [bytes[1], bytes[5]]
+  buffer.unmap()
+  return green
+}
+
+beforeAll(async () => {
+  const adapter = await Gpu().requestAdapter()
+  device = await adapter.requestDevice()
+})
+
+afterAll(() => {
+  device?.destroy()
+})
+
+describe('MSAA: sampleCount attachments', () => {
+  test('should antialias edges and resolve into the view', async () => {
+    const aliased = createTarget()
+    draw({ view: aliased.createView(), storeOp: 'store' }, 1)
+    const resolved = createTarget()
+    draw({ view: resolved.createView(), sampleCount: 4 })
+
+    expect(await readGreen(aliased)).toEqual([0, 0])
+    const [edge, outside] = await readGreen(resolved)
+    expect(edge).toBeGreaterThan(64)
+    expect(edge).toBeLessThan(192)
+    expect(outside).toBe(0)
+  })
+
+  test('should reuse the cached target across passes and sizes', async () => {
+    const first = createTarget()
+    const second = createTarget()
+    draw({ view: first.createView(), sampleCount: 4 })
+    draw({ view: second.createView(), sampleCount: 4 })
+
+    const larger = device.createTexture({ size: [8, 8], format: 'rgba8unorm', usage: GPUTextureUsage.RENDER_ATTACHMENT })
+    draw({ view: larger.createView(), sampleCount: 4 })
+
+    expect(await readGreen(second)).toEqual(await readGreen(first))
+  })
+
+  test('should reject invalid combinations', () => {
+    const target = createTarget()
+    const msaa = device.createMultisampleTexture(target)
+
+    expect(() => draw({ view: target.createView(), resolveTarget: createTarget().createView(), sampleCount: 4 })).toThrow(/resolveTarget/)
+    expect(() => draw({ view: msaa.createView(), sampleCount: 4 })).toThrow(/single-sampled/)
+  })
+})
+
+describe('MSAA: createMultisampleTexture', () => {
+  test('should match the target size and format', () => {
+    const target = device.createTexture({ size: [6, 3], format: 'bgra8unorm', usage: GPUTextureUsage.RENDER_ATTACHMENT })
+    const msaa = device.createMultisampleTexture(target)
+
+    expect([msaa.width, msaa.height, msaa.format, msaa.sampleCount]).toEqual([6, 3, 'bgra8unorm', 4])
+    expect(msaa.usageNames()).toEqual(['RENDER_ATTACHMENT'])
+    expect(device.createMultisampleTexture(target.createView(), 1).sampleCount).toBe(1)
+  })
+
+  test('should resolve through resolveTarget', async () => {
+    const target = createTarget()
+    const msaa = device.createMultisampleTexture(target, 4, 'msaa')
+    draw({ view: msaa.createView(), resolveTarget: target.createView() })
+
+    const [edge] = await readGreen(target)
+    expect(edge).toBeGreaterThan(64)
+    expect(edge).toBeLessThan(192)
+  })
+})
@@ -0,0 +1,153 @@
+/**
+ * Multi-Device Tests
+ *
+ * Tests for running several devices in one process and moving data between them.
+ * On dual-GPU machines the two power preferences select different adapters;
+ * elsewhere both devices come from the same adapter.
+ */
+
+import { describe, test, expect, beforeAll, afterAll } from 'bun:test'
+import { Gpu, GPUBufferUsage, GPUTextureUsage, copyBufferBetweenDevices, copyTextureBetweenDevices } from '../webgpu.js'
+
+let deviceA
+let deviceB
+
+async function readBuffer(device, buffer, size) {
+  const staging = device.createBuffer({
+    size,
+    usage: GPUBufferUsage.MAP_READ | GPUBufferUsage.COPY_DST
+  })
+  const encoder = device.createCommandEncoder()
+  encoder.copyBufferToBuffer(buffer, 0, staging, 0, size)
+  device.queue.submit([encoder.finish()])
+  await staging.mapAsync('READ')
+  const data = staging.getMappedRange().slice(0)
+  staging.unmap()
+  staging.destroy()
+  return data
+}
+
+function copyBuffer(device, data) {
+  const buffer = device.createBuffer({
+    size: data.byteLength,
+    usage: GPUBufferUsage.COPY_SRC | GPUBufferUsage.COPY_DST
+  })
+  device.queue.writeBuffer(buffer, 0, Buffer.from(data.buffer))
+  return buffer
+}
+
+beforeAll(async () => {
+  const gpu = Gpu()
+  const adapterA = await gpu.requestAdapter({ powerPreference: 'high-performance' })
+  const adapterB = await gpu.requestAdapter({ powerPreference: 'low-power' })
+  deviceA = await adapterA.requestDevice()
+  deviceB = await adapterB.requestDevice()
+})
+
+afterAll(() => {
+  deviceA?.destroy()
+  deviceB?.destroy()
+})
+
+describe('Multi-Device: Buffer Transfer', () => {
+  test('should copy a buffer range between devices', async () => {
+    const data = new Uint32Array(1024).map((_, i) => i * 3)
+    const source = copyBuffer(deviceA, data)
+    const destination = copyBuffer(deviceB, new Uint32Array(1024))
+
+    await copyBufferBetweenDevices(source, destination, { sourceOffset: 16, destinationOffset: 32, size: 64 })
+
+    const result = new Uint32Array(await readBuffer(deviceB, destination, data.byteLength))
+    expect(result[7]).toBe(0)
+    expect(Array.from(result.slice(8, 24))).toEqual(Array.from(data.slice(4, 20)))
+    expect(result[24]).toBe(0)
+  })
+
+  test('should copy within the same device', async () => {
+    const data = new Uint32Array([1, 2, 3, 4])
+    const source = copyBuffer(deviceA, data)
+    const destination = copyBuffer(deviceA, new Uint32Array(4))
+
+    await copyBufferBetweenDevices(source, destination)
+
+    const result = new Uint32Array(await readBuffer(deviceA, destination, data.byteLength))
+    expect(Array.from(result)).toEqual([1, 2, 3, 4])
+  })
+
+  test('should reject invalid copies', () => {
+    const source = copyBuffer(deviceA, new Uint32Array(4))
+    const destination = copyBuffer(deviceB, new Uint32Array(2))
+    expect(() => copyBufferBetweenDevices(source, destination)).toThrow('overruns destination')
+    expect(() => copyBufferBetweenDevices(source, source, { size: 6 })).toThrow('multiples of 4')
+
+    const noCopySrc = deviceA.createBuffer({ size: 16, usage: GPUBufferUsage.STORAGE })
+    expect(() => copyBufferBetweenDevices(noCopySrc, source)).toThrow('COPY_SRC')
+  })
+
+  test('should reject destroyed and mapped buffers', async () => {
+    const source = copyBuffer(deviceA, new Uint32Array(4))
+    const destination = copyBuffer(deviceB, new Uint32Array(4))
+    const mapped = deviceB.createBuffer({ label: 'mapped', size: 16, usage: GPUBufferUsage.COPY_DST, mappedAtCreation: true })
+    expect(() => copyBufferBetweenDevices(source, mapped)).toThrow("copyBufferBetweenDevices: buffer 'mapped' is mapped; unmap it first")
+
+    source.destroy()
+    expect(() => copyBufferBetweenDevices(source, destination)).toThrow('is destroyed')
+  })
+})
+
+describe('Multi-Device: Texture Transfer', () => {
+  test('should copy an rgba8 texture with unaligned rows', async () => {
+    const width = 5, height = 3
+    const descriptor = {
+      width,
+      height,
+      format: 'rgba8unorm',
+      usage: GPUTextureUsage.COPY_SRC | GPUTextureUsage.COPY_DST
+    }
+    const source = deviceA.createTexture(descriptor)
+    const destination = deviceB.createTexture(descriptor)
+
+    // Rows are padded to 256 bytes for buffer <-> texture copies
+    const pixels = new Uint8Array(256 * height)
+    for (let y = 0; y < height; y++) {
+      for (let x = 0; x < width * 4; x++) pixels[y * 256 + x] = y * 40 + x
+    }
+    const upload = copyBuffer(deviceA, pixels)
+    let encoder = deviceA.createCommandEncoder()
+    encoder.copyBufferToTexture(upload, 0, 256, height, source, 0, 0, 0, 0, width, height, 1)
+    deviceA.queue.submit([encoder.finish()])
+
+    await copyTextureBetweenDevices(source, destination)
+
+    const readback = deviceB.createBuffer({
+      size: pixels.byteLength,
+      usage: GPUBufferUsage.COPY_SRC | GPUBufferUsage.COPY_DST
+    })
+    encoder = deviceB.createCommandEncoder()
+    encoder.copyTextureToBuffer(destination, 0, 0, 0, 0, readback, 0, 256, height, width, height, 1)
+    deviceB.queue.submit([encoder.finish()])
+
+    const result = new Uint8Array(await readBuffer(deviceB, readback, pixels.byteLength))
+    for (let y = 0; y < height; y++) {
+      expect(Array.from(result.slice(y * 256, y * 256 + width * 4)))
+        .toEqual(Array.from(pixels.slice(y * 256, y * 256 + width * 4)))
+    }
+  })
+
+  test('should reject mismatched textures', () => {
+    const usage = GPUTextureUsage.COPY_SRC | GPUTextureUsage.COPY_DST
+    const a = deviceA.createTexture({ width: 4, height: 4, format: 'rgba8unorm', usage })
+    const b = deviceB.createTexture({ width: 4, height: 4, format: 'rgba16float', usage })
+    const c = deviceB.createTexture({ width: 8, height: 4, format: 'rgba8unorm', usage })
+    expect(() => copyTextureBetweenDevices(a, b)).toThrow('formats differ')
+    expect(() => copyTextureBetweenDevices(a, c)).toThrow('sizes differ')
+  })
+
+  test('should reject destroyed textures', () => {
+    const usage = GPUTextureUsage.COPY_SRC | GPUTextureUsage.COPY_DST
+    const a = deviceA.createTexture({ label: 'gone', width: 4, height: 4, format: 'rgba8unorm', usage })
+    const b = deviceB.createTexture({ width: 4, height: 4, format: 'rgba8unorm', usage })
+    a.destroy()
+    expect(() => copyTextureBetweenDevices(a, b)).toThrow("copyTextureBetweenDevices: texture 'gone' is destroyed")
+  })
+})
@@ -0,0 +1,140 @@
+/**
+ * navigator.gpu Compatibility Tests
+ *
+ * installNavigatorGpu() exposes the package as globalThis.navigator.gpu. The
+ * objects it hands out follow the shapes of @webgpu/types: getters rather
+ * than methods, standard descriptor fields, and the browser event and promise
+ * members WebGPU renderers rely on.
+ */
+
+import { describe, test, expect, beforeAll, afterAll } from 'bun:test'
+import { installNavigatorGpu } from '../webgpu.js'
+
+let adapter
+let device
+
+beforeAll(async () => {
+  installNavigatorGpu()
+  adapter = await globalThis.navigator.gpu.requestAdapter()
+  device = await adapter.requestDevice()
+})
+
+afterAll(() => {
+  device?.destroy()
+})
+
+describe('navigator.gpu: Globals', () => {
+  test('should install navigator.gpu and the constant globals', () => {
+    const gpu = globalThis.navigator.gpu
+
+    expect(installNavigatorGpu()).toBe(gpu)
+    expect(gpu.getPreferredCanvasFormat()).toBe('bgra8unorm')
+    expect(gpu.wgslLanguageFeatures.size).toBe(0)
+    expect(globalThis.GPUBufferUsage.MAP_READ).toBe(1)
+    expect(globalThis.GPUMapMode.READ).toBe(1)
+    expect(globalThis.GPUColorWrite.ALL).toBe(0xf)
+  })
+
+  test('should expose adapter info and iterable features', async () => {
+    const features = [...device.features]
+
+    expect(await adapter.requestAdapterInfo()).toEqual(adapter.info)
+    expect(features.length).toBe(device.features.size)
+    expect(features.every((name) => device.features.has(name))).toBe(true)
+  })
+})
+
+describe('navigator.gpu: Resources', () => {
+  test('should expose buffer attributes as getters', async () => {
+    const buffer = device.createBuffer({ label: 'staging', size: 16, usage: GPUBufferUsage.MAP_READ | GPUBufferUsage.COPY_DST })
+
+    expect(buffer.size).toBe(16)
+    expect(buffer.usage).toBe(GPUBufferUsage.MAP_READ | GPUBufferUsage.COPY_DST)
+    expect(buffer.label).toBe('staging')
+    expect(buffer.mapState).toBe('unmapped')
+
+    await buffer.mapAsync(GPUMapMode.READ)
+    expect(buffer.mapState).toBe('mapped')
+    buffer.unmap()
+  })
+
+  test('should create textures from a standard size', () => {
+    const texture = device.createTexture({
+      size: [8, 4, 2],
+      format: 'rgba8unorm',
+      usage: GPUTextureUsage.TEXTURE_BINDING | GPUTextureUsage.COPY_DST,
+    })
+
+    expect([texture.width, texture.height, texture.depthOrArrayLayers]).toEqual([8, 4, 2])
+    expect(texture.format).toBe('rgba8unorm')
+    expect(texture.dimension).toBe('2d')
+    expect(texture.mipLevelCount).toBe(1)
+    expect(texture.sampleCount).toBe(1)
+    expect(texture.usage).toBe(GPUTextureUsage.TEXTURE_BINDING | GPUTextureUsage.COPY_DST)
+  })
+
+  test('should write textures with a standard data layout', async () => {
+    const texture = device.createTexture({
+      size: { width: 2, height: 2 },
+      format: 'rgba8unorm',
+      usage: GPUTextureUsage.COPY_SRC | GPUTextureUsage.COPY_DST,
+    })
+    const texels = new Uint8Array(16).map((_, i) => i + 1)
+    device.queue.writeTexture({ texture, origin: [1, 1] }, texels.subarray(8), { bytesPerRow: 8 }, [1, 1])
+
+    const readback = device.createBuffer({ size: 512, usage: GPUBufferUsage.COPY_DST | GPUBufferUsage.MAP_READ })
+    const encoder = device.createCommandEncoder()
+    encoder.copyTextureToBuffer({ texture }, { buffer: readback, bytesPerRow: 256 }, { width: 2, height: 2 })
+    device.queue.submit([encoder.finish()])
+    await readback.mapAsync(GPUMapMode.READ)
+    const bytes = new Uint8Array(readback.getMappedRange())
+
+    expect(Array.from(bytes.subarray(260, 264))).toEqual([9, 10, 11, 12])
+    expect(Array.from(bytes.subarray(0, 4))).toEqual([0, 0, 0, 0])
+    readback.unmap()
+  })
+})
+
+describe('navigator.gpu: Pipelines', () => {
+  test('should create pipelines asynchronously with an auto layout', async () => {
+    const module = device.createShaderModule({
+      code: `
+        @group(0) @binding(0) var<storage, read_write> data: array<u32>;
+        @compute @workgroup_size(1) fn main() { data[0] = 7u; }
+      `,
+    })
+    const pipeline = await device.createComputePipelineAsync({ layout: 'auto', compute: { module, entryPoint: 'main' } })
+
+    expect(pipeline.getBindGroupLayout(0)).toBeDefined()
+  })
+})
+
+describe('navigator.gpu: Device Events', () => {
+  test('should dispatch uncaptured errors to onuncapturederror', async () => {
+    const errors = []
+    const received = new Promise((resolve) => {
+      device.onuncapturederror = (event) => {
+        errors.push(event)
+        resolve()
+      }
+    })
+    const buffer = device.createBuffer({ size: 16, usage: GPUBufferUsage.MAP_READ | GPUBufferUsage.COPY_DST })
+    buffer.destroy()
+    await buffer.mapAsync('READ').catch(() => {})
+    await received
+    device.onuncapturederror = null
+
+    expect(errors[0].type).toBe('uncapturederror')
+    expect(errors[0].error.message).toContain('destroyed')
+    expect(device.listenerCount('uncapturedError')).toBe(0)
+  })
+
+  test('should resolve lost when the device is destroyed', async () => {
+    const other = await adapter.requestDevice()
+    const lost = other.lost
+    other.destroy()
+
+    expect((await lost).reason).toBe('destroyed')
+    expect(await other.lost).toEqual(await lost)
+  })
+})
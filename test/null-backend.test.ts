@@ -0,0 +1,174 @@
+/**
+ * Null Backend Tests
+ *
+ * Gpu({ backends: ['null'] }) never loads a driver: its devices check calls
+ * against their limits like real ones, but run nothing and read back zeros.
+ */
+
+import { describe, test, expect, beforeAll, afterAll } from 'bun:test'
+import { Gpu, GPUBufferUsage, GPUMapMode, GPUShaderStage, GPUValidationError } from '../webgpu.js'
+
+let adapter
+let device
+
+beforeAll(async () => {
+  adapter = await Gpu({ backends: ['null'] }).requestAdapter()
+  device = await adapter.requestDevice()
+})
+
+afterAll(() => {
+  device?.destroy()
+})
+
+describe('Null Backend: Adapter', () => {
+  test('should report the null adapter with default limits', () => {
+    expect(adapter.info.name).toBe('Null Adapter')
+    expect(adapter.info.backend).toBe('Null')
+    expect(adapter.features).toEqual([])
+    expect(adapter.limits.maxBindGroups).toBe(4)
+    expect(device.limits.maxComputeWorkgroupsPerDimension).toBe(65535)
+  })
+
+  test('should reject limits above the defaults', async () => {
+    await expect(adapter.requestDevice({ requiredLimits: { maxBindGroups: 8 } })).rejects.toThrow(/maxBindGroups/)
+  })
+
+  test('should not combine with other backends', () => {
+    expect(() => Gpu({ backends: ['null', 'gl'] })).toThrow(/can't be combined/)
+  })
+})
+
+describe('Null Backend: Validation', () => {
+  test('should reject buffers beyond maxBufferSize', () => {
+    expect(() => device.createBuffer({ label: 'huge', size: 2 ** 40, usage: GPUBufferUsage.STORAGE })).toThrow(
+      /^createBuffer\(label: 'huge'\): validation failed\n  Buffer size \d+ is greater than the maximum buffer size/
+    )
+  })
+
+  test('should capture errors in error scopes', async () => {
+    device.pushErrorScope('validation')
+    device.createBuffer({ size: 16, usage: GPUBufferUsage.MAP_READ | GPUBufferUsage.STORAGE })
+    const error = await device.popErrorScope()
+    expect(error).toBeInstanceOf(GPUValidationError)
+    expect(error.message).toMatch(/MAP_READ can only be combined with COPY_DST/)
+
+    device.pushErrorScope('validation')
+    device.createBuffer({ size: 16, usage: GPUBufferUsage.STORAGE })
+    expect(await device.popErrorScope()).toBeNull()
+  })
+
+  test('should reject misaligned bind group offsets', () => {
+    const layout = device.createBindGroupLayout({
+      entries: [{ binding: 0, visibility: GPUShaderStage.COMPUTE, buffer: { type: 'storage' } }]
+    })
+    const buffer = device.createBuffer({ size: 1024, usage: GPUBufferUsage.STORAGE })
+
+    expect(() =>
+      device.createBindGroup({ layout, entries: [{ binding: 0, resource: { buffer, offset: 4 } }] })
+    ).toThrow(/offset 4 is not a multiple of minStorageBufferOffsetAlignment \(256\)/)
+  })
+
+  test('should report dispatches beyond the limit when the pass ends', () => {
+    const pass = device.createCommandEncoder().beginComputePass()
+    pass.dispatchWorkgroups(70000)
+
+    expect(() => pass.end()).toThrow(/^end: validation failed\n  Dispatch of 70000x1x1 workgroups exceeds/)
+  })
+})
+
+describe('Null Backend: Buffer Misuse', () => {
+  // Each case misuses a fresh 64-byte buffer labeled 'b'
+  const CASES = {
+    'readSync out of bounds': [GPUBufferUsage.MAP_READ, (buffer) => buffer.readSync(32, 64)],
+    'readSync offset past the end': [GPUBufferUsage.MAP_READ, (buffer) => buffer.readSync(128)],
+    'readSync misaligned': [GPUBufferUsage.MAP_READ, (buffer) => buffer.readSync(4, 8)],
+    'readSync without read usage': [GPUBufferUsage.UNIFORM, (buffer) => buffer.readSync()],
+    'readSync while mapped': [GPUBufferUsage.MAP_READ, async (buffer) => {
+      await buffer.mapAsync(GPUMapMode.READ)
+      buffer.readSync()
+    }],
+    'readSync after destroy': [GPUBufferUsage.COPY_SRC, (buffer) => {
+      buffer.destroy()
+      buffer.readSync()
+    }],
+    'mapAsync misaligned': [GPUBufferUsage.MAP_READ, (buffer) => buffer.mapAsync(GPUMapMode.READ, 4)],
+    'mapAsync out of bounds': [GPUBufferUsage.MAP_READ, (buffer) => buffer.mapAsync(GPUMapMode.READ, 0, 128)],
+    'mapAsync after destroy': [GPUBufferUsage.MAP_READ, async (buffer) => {
+      buffer.destroy()
+      await buffer.mapAsync(GPUMapMode.READ)
+    }],
+    'getMappedRange misaligned offset': [GPUBufferUsage.MAP_READ, async (buffer) => {
+      await buffer.mapAsync(GPUMapMode.READ)
+      buffer.getMappedRange(4, 8)
+    }],
+    'getMappedRange misaligned size': [GPUBufferUsage.MAP_READ, async (buffer) => {
+      await buffer.mapAsync(GPUMapMode.READ)
+      buffer.getMappedRange(0, 6)
+    }],
+    'getMappedRange out of bounds': [GPUBufferUsage.MAP_READ, async (buffer) => {
+      await buffer.mapAsync(GPUMapMode.READ)
+      buffer.getMappedRange(0, 128)
+    }],
+    'getMappedRange overlapping': [GPUBufferUsage.MAP_READ, async (buffer) => {
+      await buffer.mapAsync(GPUMapMode.READ)
+      buffer.getMappedRange(0, 32)
+      buffer.getMappedRange(16, 32)
+    }],
+    'getMappedRange unmapped': [GPUBufferUsage.MAP_READ, (buffer) => buffer.getMappedRange()],
+    'writeMapped mapped for reading': [GPUBufferUsage.MAP_READ, async (buffer) => {
+      await buffer.mapAsync(GPUMapMode.READ)
+      buffer.writeMapped(0, new Uint8Array(4))
+    }]
+  }
+
+  async function errorOf(target, usage, misuse) {
+    try {
+      await misuse(target.createBuffer({ label: 'b', size: 64, usage }))
+    } catch (error) {
+      return error.message
+    }
+    return null
+  }
+
+  test('should reject the same misuse as native buffers, with the same errors', async () => {
+    const native = await (await Gpu().requestAdapter()).requestDevice()
+    for (const [name, [usage, misuse]] of Object.entries(CASES)) {
+      const expected = await errorOf(native, usage, misuse)
+      expect(expected, name).not.toBeNull()
+      expect(await errorOf(device, usage, misuse), name).toBe(expected)
+    }
+    native.destroy()
+  })
+})
+
+describe('Null Backend: Execution', () => {
+  test('should read back zeros', async () => {
+    const buffer = device.createBuffer({ size: 16, usage: GPUBufferUsage.MAP_READ | GPUBufferUsage.COPY_DST })
+    await buffer.mapAsync(GPUMapMode.READ)
+    expect(Array.from(new Uint32Array(buffer.getMappedRange()))).toEqual([0, 0, 0, 0])
+    buffer.unmap()
+
+    const storage = device.createBufferInit({ data: new Uint32Array([1, 2]), usage: GPUBufferUsage.STORAGE | GPUBufferUsage.COPY_SRC })
+    expect(Array.from(new Uint32Array(storage.readSync().buffer))).toEqual([0, 0])
+  })
+
+  test('should accept calls it has no checks for', async () => {
+    const module = device.createShaderModule({ code: 'not wgsl' })
+    const pipeline = device.createComputePipeline({ layout: 'auto', compute: { module, entryPoint: 'main' } })
+    const buffer = device.createBuffer({ size: 16, usage: GPUBufferUsage.STORAGE })
+    const bindGroup = device.createBindGroup({
+      layout: pipeline.getBindGroupLayout(0),
+      entries: [{ binding: 0, resource: { buffer } }]
+    })
+
+    const encoder = device.createCommandEncoder()
+    const pass = encoder.beginComputePass()
+    pass.setPipeline(pipeline)
+    pass.setBindGroup(0, bindGroup)
+    pass.dispatchWorkgroups(1)
+    pass.end()
+    encoder.writeTimestamp(device.createQuerySet({ type: 'timestamp', count: 2 }), 0)
+    device.queue.submit([encoder.finish()])
+    await device.queue.onSubmittedWorkDone()
+  })
+})
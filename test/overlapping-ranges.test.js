@@ -15,7 +15,7 @@ async function testOverlappingRanges() {
     const device = await adapter.requestDevice()
 
     // Test 1: Exact duplicate range (same offset and size)
-    console.log('📝 Test 1: Calling getMappedRange() twice with same range should fail')
+    console.log('📝 Test 1: Calling getMappedRange() twice with same range returns the same ArrayBuffer')
     const buffer1 = device.createBuffer({
         size: 64,
         usage: GPUBufferUsage.COPY_DST | GPUBufferUsage.MAP_READ,
@@ -25,18 +25,12 @@ async function testOverlappingRanges() {
     const range1a = buffer1.getMappedRange(0, 32)
     console.log('   ✅ First getMappedRange(0, 32) succeeded')
 
-    try {
-        buffer1.getMappedRange(0, 32)
-        console.log('   ❌ Should have thrown error for duplicate range\n')
+    const range1b = buffer1.getMappedRange(0, 32)
+    if (range1b === range1a) {
+        console.log('   ✅ Correctly returns the same ArrayBuffer\n')
+    } else {
+        console.log('   ❌ Should have returned the same ArrayBuffer\n')
         process.exit(1)
-    } catch (err) {
-        if (err.message.includes('overlaps')) {
-            console.log('   ✅ Correctly rejects duplicate range')
-            console.log('      Error:', err.message, '\n')
-        } else {
-            console.log('   ❌ Wrong error:', err.message, '\n')
-            process.exit(1)
-        }
     }
 
     buffer1.unmap()
@@ -0,0 +1,48 @@
+/**
+ * Panic Safety Tests
+ *
+ * wgpu panics on some misuse (e.g. submitting work that uses a destroyed
+ * buffer). Panics are thrown as JavaScript errors instead of aborting.
+ */
+
+import { describe, test, expect, beforeAll, afterAll } from 'bun:test'
+import { Gpu, GPUBufferUsage } from '../webgpu.js'
+
+let device
+
+beforeAll(async () => {
+  const adapter = await Gpu().requestAdapter()
+  device = await adapter.requestDevice()
+})
+
+afterAll(() => {
+  device?.destroy()
+})
+
+function recordCopy(source, destination, size) {
+  const encoder = device.createCommandEncoder()
+  encoder.copyBufferToBuffer(source, 0, destination, 0, size)
+  return encoder.finish()
+}
+
+describe('Panic Safety: Queue', () => {
+  test('should throw when submitting work that uses a destroyed buffer', () => {
+    const source = device.createBuffer({ size: 16, usage: GPUBufferUsage.COPY_SRC })
+    const destination = device.createBuffer({ size: 16, usage: GPUBufferUsage.COPY_DST })
+    const commands = recordCopy(source, destination, 16)
+    source.destroy()
+
+    expect(() => device.queue.submit([commands])).toThrow('Queue::submit')
+  })
+
+  test('should keep the device usable after a panic', async () => {
+    const source = device.createBuffer({ size: 16, usage: GPUBufferUsage.COPY_SRC | GPUBufferUsage.COPY_DST })
+    const staging = device.createBuffer({ size: 16, usage: GPUBufferUsage.MAP_READ | GPUBufferUsage.COPY_DST })
+    device.queue.writeBuffer(source, 0, new Uint32Array([1, 2, 3, 4]))
+    device.queue.submit([recordCopy(source, staging, 16)])
+
+    await staging.mapAsync('READ')
+    expect(Array.from(new Uint32Array(staging.getMappedRange()))).toEqual([1, 2, 3, 4])
+    staging.unmap()
+  })
+})
@@ -0,0 +1,131 @@
+/**
+ * Pass Encoder Safety Tests
+ *
+ * Pass encoders record into their command encoder when they end, so calling
+ * things in the wrong order throws instead of touching a dead pass.
+ */
+
+import { describe, test, expect, beforeAll, afterAll } from 'bun:test'
+import { Gpu, GPUBufferUsage, GPUTextureUsage } from '../webgpu.js'
+
+let device
+
+beforeAll(async () => {
+  const adapter = await Gpu().requestAdapter()
+  device = await adapter.requestDevice()
+})
+
+afterAll(() => {
+  device?.destroy()
+})
+
+function beginRenderPass(encoder) {
+  const texture = device.createTexture({
+    width: 4,
+    height: 4,
+    format: 'rgba8unorm',
+    usage: GPUTextureUsage.RENDER_ATTACHMENT
+  })
+  return encoder.beginRenderPass({
+    colorAttachments: [{ view: texture.createView(), loadOp: 'clear', storeOp: 'store' }]
+  })
+}
+
+describe('Pass Encoder Safety: Encoder Locking', () => {
+  test('should throw when finishing with a pass still open', () => {
+    const encoder = device.createCommandEncoder()
+    const pass = beginRenderPass(encoder)
+    expect(() => encoder.finish()).toThrow('pass is open')
+
+    pass.end()
+    device.queue.submit([encoder.finish()])
+  })
+
+  test('should throw when recording encoder commands with a pass open', () => {
+    const buffer = device.createBuffer({ size: 16, usage: GPUBufferUsage.COPY_SRC | GPUBufferUsage.COPY_DST })
+    const other = device.createBuffer({ size: 16, usage: GPUBufferUsage.COPY_SRC | GPUBufferUsage.COPY_DST })
+    const encoder = device.createCommandEncoder()
+    const pass = encoder.beginComputePass()
+    expect(() => encoder.copyBufferToBuffer(buffer, 0, other, 0, 16)).toThrow('locked by an open pass')
+
+    pass.end()
+    encoder.copyBufferToBuffer(buffer, 0, other, 0, 16)
+    device.queue.submit([encoder.finish()])
+  })
+
+  test('should throw when beginning a second pass before the first ends', () => {
+    const encoder = device.createCommandEncoder()
+    const pass = encoder.beginComputePass()
+    expect(() => beginRenderPass(encoder)).toThrow('locked by an open pass')
+    expect(() => encoder.beginComputePass()).toThrow('locked by an open pass')
+
+    pass.end()
+    encoder.beginComputePass().end()
+    device.queue.submit([encoder.finish()])
+  })
+
+  test('should throw when beginning a pass on a finished encoder', () => {
+    const encoder = device.createCommandEncoder()
+    encoder.finish()
+    expect(() => encoder.beginComputePass()).toThrow('already finished')
+  })
+})
+
+describe('Pass Encoder Safety: Ended Passes', () => {
+  test('should throw when recording into an ended pass', () => {
+    const encoder = device.createCommandEncoder()
+    const compute = encoder.beginComputePass()
+    compute.end()
+    expect(() => compute.dispatchWorkgroups(1)).toThrow('Compute pass already ended')
+
+    const render = beginRenderPass(encoder)
+    render.end()
+    expect(() => render.draw(3)).toThrow('Render pass already ended')
+    encoder.finish()
+  })
+
+  test('should ignore a second end', () => {
+    const encoder = device.createCommandEncoder()
+    const pass = encoder.beginComputePass()
+    pass.end()
+    pass.end()
+    device.queue.submit([encoder.finish()])
+  })
+})
+
+describe('Pass Encoder Safety: Buffer Ranges', () => {
+  function createVertexBuffer() {
+    return device.createBuffer({ label: 'vertices', size: 32, usage: GPUBufferUsage.VERTEX | GPUBufferUsage.INDEX })
+  }
+
+  test('should throw when a vertex or index range is out of bounds', () => {
+    const buffer = createVertexBuffer()
+    const encoder = device.createCommandEncoder()
+    const pass = beginRenderPass(encoder)
+
+    expect(() => pass.setVertexBuffer(0, buffer, 16, 32)).toThrow(
+      "setVertexBuffer(buffer: 'vertices'): range (offset 16 + size 32) exceeds buffer size (32)"
+    )
+    expect(() => pass.setIndexBuffer(buffer, 'uint16', 48)).toThrow(
+      "setIndexBuffer(buffer: 'vertices'): offset (48) exceeds buffer size (32)"
+    )
+    pass.end()
+    device.queue.submit([encoder.finish()])
+  })
+
+  test('should bind empty ranges', () => {
+    const buffer = createVertexBuffer()
+    const encoder = device.createCommandEncoder()
+    const pass = beginRenderPass(encoder)
+    pass.setVertexBuffer(0, buffer, 8, 0)
+    pass.setVertexBuffer(1, buffer, 32)
+    pass.setIndexBuffer(buffer, 'uint32', 0, 0)
+    pass.end()
+    device.queue.submit([encoder.finish()])
+
+    const bundleEncoder = device.createRenderBundleEncoder({ colorFormats: ['rgba8unorm'] })
+    bundleEncoder.setVertexBuffer(0, buffer, 16, 0)
+    expect(() => bundleEncoder.setVertexBuffer(0, buffer, 40)).toThrow('offset (40) exceeds buffer size (32)')
+    bundleEncoder.finish()
+  })
+})
@@ -0,0 +1,60 @@
+/**
+ * Int8 Quantization Tests
+ *
+ * quantizeInt8 maps floats to round(x / scale) + zeroPoint as int8 bytes and
+ * dequantizeInt8 maps them back, per tensor or per channel.
+ */
+
+import { describe, test, expect } from 'bun:test'
+import { quantizeInt8, dequantizeInt8 } from '../webgpu.js'
+
+function int8(buffer) {
+  return Array.from(new Int8Array(buffer.buffer, buffer.byteOffset, buffer.byteLength))
+}
+
+describe('Int8 Quantization: Per Tensor', () => {
+  test('should round, offset and saturate', () => {
+    const values = new Float32Array([0, 0.5, 1.5, 2.5, -1, 100, -100, NaN])
+    expect(int8(quantizeInt8(values, { scale: 0.5, zeroPoint: 10 }))).toEqual([10, 11, 13, 15, 8, 127, -128, 10])
+  })
+
+  test('should dequantize back within half a step', () => {
+    const values = Float32Array.from({ length: 50 }, (_, i) => Math.sin(i) * 3)
+    const params = { scale: 3 / 127 }
+    const restored = dequantizeInt8(quantizeInt8(values, params), params)
+    for (let i = 0; i < values.length; i++) {
+      expect(Math.abs(restored[i] - values[i])).toBeLessThanOrEqual(params.scale / 2 + 1e-6)
+    }
+  })
+
+  test('should accept typed arrays and plain arrays', () => {
+    expect(Array.from(dequantizeInt8(new Int8Array([-2, 0, 4]), { scale: 0.25, zeroPoint: -2 }))).toEqual([0, 0.5, 1.5])
+    expect(int8(quantizeInt8([1, 2], { scale: 1 }))).toEqual([1, 2])
+  })
+})
+
+describe('Int8 Quantization: Per Channel', () => {
+  test('should apply one scale per block of values', () => {
+    // Two rows of three values, one scale per row
+    const params = { scale: [1, 0.1], zeroPoint: [0, 5] }
+    const quantized = quantizeInt8(new Float32Array([1, 2, 3, 0.1, 0.2, 0.3]), params)
+    expect(int8(quantized)).toEqual([1, 2, 3, 6, 7, 8])
+
+    const restored = dequantizeInt8(quantized, params)
+    expect(restored[0]).toBe(1)
+    expect(Math.abs(restored[5] - 0.3)).toBeLessThanOrEqual(1e-6)
+  })
+
+  test('should apply scales to interleaved channels', () => {
+    const quantized = quantizeInt8(new Float32Array([1, 1, 2, 2]), { scale: [1, 0.5], interleaved: true })
+    expect(int8(quantized)).toEqual([1, 2, 2, 4])
+  })
+
+  test('should reject invalid parameters', () => {
+    const values = new Float32Array(6)
+    expect(() => quantizeInt8(values, { scale: 0 })).toThrow('positive')
+    expect(() => quantizeInt8(values, { scale: [1, 2, 3, 4] })).toThrow("can't be split into 4 channels")
+    expect(() => quantizeInt8(values, { scale: [1, 2], zeroPoint: [0] })).toThrow('1 zero points given for 2 scales')
+    expect(() => dequantizeInt8(new Int8Array(2), { scale: 1, zeroPoint: 200 })).toThrow('outside the int8 range')
+  })
+})
@@ -0,0 +1,118 @@
+/**
+ * Queue writeBuffer Tests
+ *
+ * Tests for spec-compliant data handling in queue.writeBuffer():
+ * ArrayBufferView ranges and dataOffset/size in elements, plus the uploadBuffer extension.
+ */
+
+import { describe, test, expect, beforeAll, afterAll } from 'bun:test'
+import { Gpu, GPUBufferUsage } from '../webgpu.js'
+
+let device
+
+beforeAll(async () => {
+  const adapter = await Gpu().requestAdapter()
+  device = await adapter.requestDevice()
+})
+
+afterAll(() => {
+  device?.destroy()
+})
+
+async function writeAndRead(write) {
+  const buffer = device.createBuffer({ size: 16, usage: GPUBufferUsage.COPY_DST | GPUBufferUsage.MAP_READ })
+  write(buffer)
+  // Flushes pending writes, which are otherwise only submitted with the next queue.submit()
+  await device.waitIdle()
+  await buffer.mapAsync('READ')
+  const values = Array.from(new Uint32Array(buffer.getMappedRange()))
+  buffer.unmap()
+  return values
+}
+
+describe('Queue writeBuffer: Data Sources', () => {
+  test('should accept TypedArrays directly', async () => {
+    const values = await writeAndRead((buffer) => {
+      device.queue.writeBuffer(buffer, 0, new Uint32Array([1, 2, 3, 4]))
+    })
+    expect(values).toEqual([1, 2, 3, 4])
+  })
+
+  test('should accept ArrayBuffers', async () => {
+    const values = await writeAndRead((buffer) => {
+      device.queue.writeBuffer(buffer, 0, new Uint32Array([5, 6, 7, 8]).buffer)
+    })
+    expect(values).toEqual([5, 6, 7, 8])
+  })
+
+  test('should honor the byteOffset of a subarray', async () => {
+    const source = new Uint32Array([0, 0, 9, 10, 11, 12])
+    const values = await writeAndRead((buffer) => {
+      device.queue.writeBuffer(buffer, 0, source.subarray(2))
+    })
+    expect(values).toEqual([9, 10, 11, 12])
+  })
+
+  test('should accept DataViews', async () => {
+    const source = new Uint32Array([0, 13, 14, 0])
+    const values = await writeAndRead((buffer) => {
+      device.queue.writeBuffer(buffer, 4, new DataView(source.buffer, 4, 8))
+    })
+    expect(values).toEqual([0, 13, 14, 0])
+  })
+})
+
+describe('Queue writeBuffer: dataOffset and size', () => {
+  test('should count TypedArray offsets in elements', async () => {
+    const values = await writeAndRead((buffer) => {
+      device.queue.writeBuffer(buffer, 8, new Uint32Array([1, 2, 3, 4]), 1, 2)
+    })
+    expect(values).toEqual([0, 0, 2, 3])
+  })
+
+  test('should count ArrayBuffer offsets in bytes', async () => {
+    const values = await writeAndRead((buffer) => {
+      device.queue.writeBuffer(buffer, 0, new Uint32Array([1, 2, 3, 4]).buffer, 8)
+    })
+    expect(values).toEqual([3, 4, 0, 0])
+  })
+
+  test('should reject ranges outside the data', () => {
+    const buffer = device.createBuffer({ size: 16, usage: GPUBufferUsage.COPY_DST })
+    expect(() => device.queue.writeBuffer(buffer, 0, new Uint32Array(4), 2, 4)).toThrow(RangeError)
+  })
+
+  test('should reject sizes that are not a multiple of 4 bytes', () => {
+    const buffer = device.createBuffer({ size: 16, usage: GPUBufferUsage.COPY_DST })
+    expect(() => device.queue.writeBuffer(buffer, 0, new Uint8Array(6))).toThrow(RangeError)
+  })
+})
+
+describe('Queue uploadBuffer', () => {
+  test('should upload data at an offset', async () => {
+    const values = await writeAndRead((buffer) => {
+      device.queue.uploadBuffer(buffer, 4, new Uint32Array([0, 21, 22, 23]).subarray(1))
+    })
+    expect(values).toEqual([0, 21, 22, 23])
+  })
+
+  test('should upload more than one staging chunk', async () => {
+    // Just over 64 MiB, so the upload is split across two staging buffers
+    const size = 64 * 1024 * 1024 + 16
+    const data = new Uint32Array(size / 4)
+    data[0] = 1
+    data[data.length - 1] = 2
+    const buffer = device.createBuffer({ size, usage: GPUBufferUsage.COPY_DST | GPUBufferUsage.MAP_READ })
+    device.queue.uploadBuffer(buffer, 0, data)
+
+    await buffer.mapAsync('READ')
+    const result = new Uint32Array(buffer.getMappedRange())
+    expect([result[0], result[result.length - 1]]).toEqual([1, 2])
+    buffer.unmap()
+  })
+
+  test('should reject ranges outside the buffer', () => {
+    const buffer = device.createBuffer({ size: 16, usage: GPUBufferUsage.COPY_DST })
+    expect(() => device.queue.uploadBuffer(buffer, 8, new Uint32Array(4))).toThrow('exceeds buffer size')
+  })
+})
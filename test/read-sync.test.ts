@@ -0,0 +1,61 @@
+/**
+ * readSync Tests
+ *
+ * buffer.readSync() reads small ranges back in one blocking call, mapping
+ * MAP_READ buffers directly and copying COPY_SRC buffers through staging.
+ */
+
+import { describe, test, expect, beforeAll, afterAll } from 'bun:test'
+import { Gpu, GPUBufferUsage } from '../webgpu.js'
+
+let device
+
+beforeAll(async () => {
+  const adapter = await Gpu().requestAdapter()
+  device = await adapter.requestDevice()
+})
+
+afterAll(() => {
+  device?.destroy()
+})
+
+const toUint32 = (bytes) => Array.from(new Uint32Array(bytes.buffer, bytes.byteOffset, bytes.byteLength / 4))
+
+describe('readSync: Reads', () => {
+  test('should read a storage buffer through a staging copy', () => {
+    const buffer = device.createBufferInit({ usage: GPUBufferUsage.STORAGE | GPUBufferUsage.COPY_SRC, data: new Uint32Array([1, 2, 3, 4]) })
+
+    expect(toUint32(buffer.readSync())).toEqual([1, 2, 3, 4])
+    expect(toUint32(buffer.readSync(8, 8))).toEqual([3, 4])
+  })
+
+  test('should read a MAP_READ buffer after GPU work', () => {
+    const source = device.createBufferInit({ usage: GPUBufferUsage.COPY_SRC, data: new Uint32Array([5, 6, 7, 8]) })
+    const readback = device.createBuffer({ size: 16, usage: GPUBufferUsage.MAP_READ | GPUBufferUsage.COPY_DST })
+    const encoder = device.createCommandEncoder()
+    encoder.copyBufferToBuffer(source, 0, readback, 0, 16)
+    device.queue.submit([encoder.finish()])
+
+    expect(toUint32(readback.readSync(8))).toEqual([7, 8])
+    expect(readback.mapState).toBe('unmapped')
+  })
+})
+
+describe('readSync: Validation', () => {
+  test('should reject unreadable and mapped buffers', () => {
+    const uniform = device.createBuffer({ size: 16, usage: GPUBufferUsage.UNIFORM })
+    const mapped = device.createBuffer({ size: 16, usage: GPUBufferUsage.COPY_SRC, mappedAtCreation: true })
+
+    expect(() => uniform.readSync()).toThrow(/MAP_READ or COPY_SRC/)
+    expect(() => mapped.readSync()).toThrow(/must be unmapped/)
+    mapped.unmap()
+  })
+
+  test('should reject misaligned, out-of-range and oversized reads', () => {
+    const buffer = device.createBuffer({ size: 8 * 1024 * 1024, usage: GPUBufferUsage.COPY_SRC })
+
+    expect(() => buffer.readSync(4, 4)).toThrow(/multiple of 8/)
+    expect(() => buffer.readSync(8 * 1024 * 1024, 8)).toThrow(/exceeds buffer size/)
+    expect(() => buffer.readSync()).toThrow(/at most 4194304 bytes/)
+  })
+})
@@ -0,0 +1,182 @@
+/**
+ * Render Bundle Tests
+ *
+ * Bundles are recorded with createRenderBundleEncoder and executed in passes
+ * whose attachments (color formats, depth-stencil, sample count) match.
+ */
+
+import { describe, test, expect, beforeAll, afterAll } from 'bun:test'
+import { Gpu, GPUBufferUsage, GPUTextureUsage } from '../webgpu.js'
+
+const SIZE = 4
+
+let device
+
+// Fullscreen triangle at z = 0.5 in a solid color
+const SHADER = `
+  @vertex
+  fn vs_main(@builtin(vertex_index) i: u32) -> @builtin(position) vec4<f32> {
+    let uv = vec2<f32>(f32((i << 1u) & 2u), f32(i & 2u));
+    return vec4<f32>(uv * 2.0 - 1.0, 0.5, 1.0);
+  }
+
+  @fragment
+  fn fs_main() -> @location(0) vec4<f32> {
+    return vec4<f32>(0.0, 1.0, 0.0, 1.0);
+  }
+`
+
+function createTexture(format, sampleCount = 1) {
+  return device.createTexture({
+    width: SIZE,
+    height: SIZE,
+    format,
+    sampleCount,
+    usage: sampleCount > 1
+      ? GPUTextureUsage.RENDER_ATTACHMENT
+      : GPUTextureUsage.RENDER_ATTACHMENT | GPUTextureUsage.COPY_SRC
+  })
+}
+
+function createPipeline(options = {}) {
+  const module = device.createShaderModule({ code: SHADER })
+  return device.createRenderPipeline({
+    layout: null,
+    vertex: { module, entryPoint: 'vs_main' },
+    fragment: { module, entryPoint: 'fs_main', targets: [{ format: 'rgba8unorm' }] },
+    primitive: { topology: 'triangle-list' },
+    depthStencil: options.depthStencil,
+    multisample: options.sampleCount ? { count: options.sampleCount } : undefined
+  })
+}
+
+function recordBundle(pipeline, descriptor) {
+  const bundleEncoder = device.createRenderBundleEncoder({ colorFormats: ['rgba8unorm'], ...descriptor })
+  bundleEncoder.setPipeline(pipeline)
+  bundleEncoder.draw(3)
+  return bundleEncoder.finish()
+}
+
+async function readPixel(texture) {
+  const buffer = device.createBuffer({
+    size: 256 * SIZE,
+    usage: GPUBufferUsage.COPY_DST | GPUBufferUsage.MAP_READ
+  })
+  const encoder = device.createCommandEncoder()
+  encoder.copyTextureToBuffer(texture, 0, 0, 0, 0, buffer, 0, 256, SIZE, SIZE, SIZE, 1)
+  device.queue.submit([encoder.finish()])
+  await buffer.mapAsync('READ')
+  const pixel = Array.from(new Uint8Array(buffer.getMappedRange()).slice(0, 4))
+  buffer.unmap()
+  return pixel
+}
+
+function colorAttachment(view, resolveTarget) {
+  return {
+    view,
+    resolveTarget,
+    loadOp: 'clear',
+    storeOp: 'store',
+    clearValue: { r: 0, g: 0, b: 1, a: 1 }
+  }
+}
+
+beforeAll(async () => {
+  const adapter = await Gpu().requestAdapter()
+  device = await adapter.requestDevice()
+})
+
+afterAll(() => {
+  device?.destroy()
+})
+
+describe('Render Bundle: Color', () => {
+  test('should replay recorded draws', async () => {
+    const target = createTexture('rgba8unorm')
+    const bundle = recordBundle(createPipeline())
+
+    const encoder = device.createCommandEncoder()
+    const pass = encoder.beginRenderPass({ colorAttachments: [colorAttachment(target.createView())] })
+    pass.executeBundles([bundle])
+    pass.end()
+    device.queue.submit([encoder.finish()])
+
+    expect(await readPixel(target)).toEqual([0, 255, 0, 255])
+  })
+
+  test('should not be reusable after finish', () => {
+    const bundleEncoder = device.createRenderBundleEncoder({ colorFormats: ['rgba8unorm'] })
+    bundleEncoder.finish()
+    expect(() => bundleEncoder.draw(3)).toThrow('already finished')
+  })
+})
+
+describe('Render Bundle: Depth-Stencil', () => {
+  function renderWithDepth(bundle, depthClearValue, depthReadOnly = false) {
+    const target = createTexture('rgba8unorm')
+    const depth = createTexture('depth32float')
+
+    const encoder = device.createCommandEncoder()
+    if (depthReadOnly) {
+      const prepass = encoder.beginRenderPass({
+        depthStencilAttachment: { view: depth.createView(), depthLoadOp: 'clear', depthStoreOp: 'store', depthClearValue }
+      })
+      prepass.end()
+    }
+    const pass = encoder.beginRenderPass({
+      colorAttachments: [colorAttachment(target.createView())],
+      depthStencilAttachment: depthReadOnly
+        ? { view: depth.createView(), depthReadOnly: true }
+        : { view: depth.createView(), depthLoadOp: 'clear', depthStoreOp: 'store', depthClearValue }
+    })
+    pass.executeBundles([bundle])
+    pass.end()
+    device.queue.submit([encoder.finish()])
+    return target
+  }
+
+  test('should depth test in a pass with a depth attachment', async () => {
+    const bundle = recordBundle(
+      createPipeline({ depthStencil: { format: 'depth32float', depthWriteEnabled: true, depthCompare: 'less' } }),
+      { depthStencilFormat: 'depth32float' }
+    )
+    expect(await readPixel(renderWithDepth(bundle, 1.0))).toEqual([0, 255, 0, 255])
+    expect(await readPixel(renderWithDepth(bundle, 0.25))).toEqual([0, 0, 255, 255])
+  })
+
+  test('should execute read-only bundles in read-only depth passes', async () => {
+    const bundle = recordBundle(
+      createPipeline({ depthStencil: { format: 'depth32float', depthWriteEnabled: false, depthCompare: 'greater' } }),
+      { depthStencilFormat: 'depth32float', depthReadOnly: true, stencilReadOnly: true }
+    )
+    expect(await readPixel(renderWithDepth(bundle, 0.25, true))).toEqual([0, 255, 0, 255])
+  })
+
+  test('should reject read-only flags without a depth-stencil format', () => {
+    expect(() => device.createRenderBundleEncoder({ colorFormats: ['rgba8unorm'], depthReadOnly: true }))
+      .toThrow('depthStencilFormat')
+  })
+
+  test('should reject bundles without depth in passes with depth', () => {
+    const bundle = recordBundle(createPipeline())
+    expect(() => renderWithDepth(bundle, 1.0)).toThrow('end')
+  })
+})
+
+describe('Render Bundle: Multisampling', () => {
+  test('should execute in a multisampled pass', async () => {
+    const msaa = createTexture('rgba8unorm', 4)
+    const resolve = createTexture('rgba8unorm')
+    const bundle = recordBundle(createPipeline({ sampleCount: 4 }), { sampleCount: 4 })
+
+    const encoder = device.createCommandEncoder()
+    const pass = encoder.beginRenderPass({
+      colorAttachments: [colorAttachment(msaa.createView(), resolve.createView())]
+    })
+    pass.executeBundles([bundle])
+    pass.end()
+    device.queue.submit([encoder.finish()])
+
+    expect(await readPixel(resolve)).toEqual([0, 255, 0, 255])
+  })
+})
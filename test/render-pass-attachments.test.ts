@@ -0,0 +1,376 @@
+/**
+ * Render Pass Attachment Tests
+ *
+ * Tests for attachment options on beginRenderPass (store ops, read-only depth, stencil, depth-only passes, ...).
+ * Results are read back from a small 4x4 target.
+ */
+
+import { describe, test, expect, beforeAll, afterAll } from 'bun:test'
+import { Gpu, GPUBufferUsage, GPUTextureUsage } from '../webgpu.js'
+
+const SIZE = 4
+
+let device
+
+// Fullscreen triangle in a solid color
+const SHADER = `
+  @vertex
+  fn vs_main(@builtin(vertex_index) i: u32) -> @builtin(position) vec4<f32> {
+    let uv = vec2<f32>(f32((i << 1u) & 2u), f32(i & 2u));
+    return vec4<f32>(uv * 2.0 - 1.0, 0.5, 1.0);
+  }
+
+  @fragment
+  fn fs_main() -> @location(0) vec4<f32> {
+    return vec4<f32>(0.0, 1.0, 0.0, 1.0);
+  }
+`
+
+function createTarget(format, sampleCount = 1) {
+  return device.createTexture({
+    width: SIZE,
+    height: SIZE,
+    format,
+    sampleCount,
+    // Multisampled textures can only be render attachments
+    usage: sampleCount > 1
+      ? GPUTextureUsage.RENDER_ATTACHMENT
+      : GPUTextureUsage.RENDER_ATTACHMENT | GPUTextureUsage.COPY_SRC
+  })
+}
+
+function createPipeline(options = {}) {
+  const module = device.createShaderModule({ code: SHADER })
+  return device.createRenderPipeline({
+    layout: null,
+    vertex: { module, entryPoint: 'vs_main' },
+    fragment: { module, entryPoint: 'fs_main', targets: [{ format: 'rgba8unorm' }] },
+    primitive: { topology: 'triangle-list' },
+    depthStencil: options.depthStencil,
+    multisample: options.sampleCount ? { count: options.sampleCount } : undefined
+  })
+}
+
+async function readPixel(texture) {
+  const buffer = device.createBuffer({
+    size: 256 * SIZE,
+    usage: GPUBufferUsage.COPY_DST | GPUBufferUsage.MAP_READ
+  })
+  const encoder = device.createCommandEncoder()
+  encoder.copyTextureToBuffer(texture, 0, 0, 0, 0, buffer, 0, 256, SIZE, SIZE, SIZE, 1)
+  device.queue.submit([encoder.finish()])
+  await buffer.mapAsync('READ')
+  const pixel = Array.from(new Uint8Array(buffer.getMappedRange()).slice(0, 4))
+  buffer.unmap()
+  return pixel
+}
+
+beforeAll(async () => {
+  const adapter = await Gpu().requestAdapter()
+  device = await adapter.requestDevice()
+})
+
+afterAll(() => {
+  device?.destroy()
+})
+
+describe('Render Pass Attachments: Store Ops', () => {
+  test('should resolve MSAA color while discarding the multisampled attachment', async () => {
+    const msaa = createTarget('rgba8unorm', 4)
+    const resolve = createTarget('rgba8unorm')
+    const depth = device.createTexture({
+      width: SIZE,
+      height: SIZE,
+      format: 'depth32float',
+      sampleCount: 4,
+      usage: GPUTextureUsage.RENDER_ATTACHMENT
+    })
+    const pipeline = createPipeline({
+      sampleCount: 4,
+      depthStencil: { format: 'depth32float', depthWriteEnabled: true, depthCompare: 'less' }
+    })
+
+    const encoder = device.createCommandEncoder()
+    const pass = encoder.beginRenderPass({
+      colorAttachments: [{
+        view: msaa.createView(),
+        resolveTarget: resolve.createView(),
+        loadOp: 'clear',
+        storeOp: 'discard',
+        clearValue: { r: 1, g: 0, b: 0, a: 1 }
+      }],
+      depthStencilAttachment: {
+        view: depth.createView(),
+        depthLoadOp: 'clear',
+        depthStoreOp: 'discard',
+        depthClearValue: 1.0
+      }
+    })
+    pass.setPipeline(pipeline)
+    pass.draw(3)
+    pass.end()
+    device.queue.submit([encoder.finish()])
+
+    expect(await readPixel(resolve)).toEqual([0, 255, 0, 255])
+  })
+
+  test('should store color when storeOp is store', async () => {
+    const target = createTarget('rgba8unorm')
+    const encoder = device.createCommandEncoder()
+    const pass = encoder.beginRenderPass({
+      colorAttachments: [{
+        view: target.createView(),
+        loadOp: 'clear',
+        storeOp: 'store',
+        clearValue: { r: 0, g: 0, b: 1, a: 1 }
+      }]
+    })
+    pass.end()
+    device.queue.submit([encoder.finish()])
+
+    expect(await readPixel(target)).toEqual([0, 0, 255, 255])
+  })
+})
+
+describe('Render Pass Attachments: Read-Only Depth', () => {
+  // Depth cleared to 0.25 in a prepass, then tested (never written) at z = 0.5
+  function renderWithReadOnlyDepth(depthCompare) {
+    const depth = device.createTexture({
+      width: SIZE,
+      height: SIZE,
+      format: 'depth32float',
+      usage: GPUTextureUsage.RENDER_ATTACHMENT | GPUTextureUsage.TEXTURE_BINDING
+    })
+    const target = createTarget('rgba8unorm')
+    const pipeline = createPipeline({
+      depthStencil: { format: 'depth32float', depthWriteEnabled: false, depthCompare }
+    })
+
+    const encoder = device.createCommandEncoder()
+    const prepass = encoder.beginRenderPass({
+      colorAttachments: [],
+      depthStencilAttachment: {
+        view: depth.createView(),
+        depthLoadOp: 'clear',
+        depthStoreOp: 'store',
+        depthClearValue: 0.25
+      }
+    })
+    prepass.end()
+
+    const pass = encoder.beginRenderPass({
+      colorAttachments: [{
+        view: target.createView(),
+        loadOp: 'clear',
+        storeOp: 'store',
+        clearValue: { r: 0, g: 0, b: 1, a: 1 }
+      }],
+      depthStencilAttachment: { view: depth.createView(), depthReadOnly: true }
+    })
+    pass.setPipeline(pipeline)
+    pass.draw(3)
+    pass.end()
+    device.queue.submit([encoder.finish()])
+    return target
+  }
+
+  test('should depth test against a read-only attachment', async () => {
+    expect(await readPixel(renderWithReadOnlyDepth('greater'))).toEqual([0, 255, 0, 255])
+    expect(await readPixel(renderWithReadOnlyDepth('less'))).toEqual([0, 0, 255, 255])
+  })
+
+  test('should reject load/store ops on a read-only aspect', () => {
+    const depth = device.createTexture({
+      width: SIZE,
+      height: SIZE,
+      format: 'depth32float',
+      usage: GPUTextureUsage.RENDER_ATTACHMENT
+    })
+    const encoder = device.createCommandEncoder()
+    expect(() => encoder.beginRenderPass({
+      colorAttachments: [],
+      depthStencilAttachment: { view: depth.createView(), depthReadOnly: true, depthLoadOp: 'clear' }
+    })).toThrow('depthReadOnly')
+  })
+})
+
+describe('Render Pass Attachments: Stencil', () => {
+  function stencilPipeline(compare, passOp) {
+    const face = { compare, passOp }
+    return createPipeline({
+      depthStencil: {
+        format: 'depth24plus-stencil8',
+        depthWriteEnabled: false,
+        depthCompare: 'always',
+        stencilFront: face,
+        stencilBack: face
+      }
+    })
+  }
+
+  function renderStencil(stencilClearValue, draws) {
+    const stencil = device.createTexture({
+      width: SIZE,
+      height: SIZE,
+      format: 'depth24plus-stencil8',
+      usage: GPUTextureUsage.RENDER_ATTACHMENT
+    })
+    const target = createTarget('rgba8unorm')
+
+    const encoder = device.createCommandEncoder()
+    const pass = encoder.beginRenderPass({
+      colorAttachments: [{
+        view: target.createView(),
+        loadOp: 'clear',
+        storeOp: 'store',
+        clearValue: { r: 0, g: 0, b: 1, a: 1 }
+      }],
+      depthStencilAttachment: {
+        view: stencil.createView(),
+        depthLoadOp: 'clear',
+        depthStoreOp: 'discard',
+        depthClearValue: 1.0,
+        stencilLoadOp: 'clear',
+        stencilStoreOp: 'store',
+        stencilClearValue
+      }
+    })
+    for (const { pipeline, reference } of draws) {
+      pass.setPipeline(pipeline)
+      pass.setStencilReference(reference)
+      pass.draw(3)
+    }
+    pass.end()
+    device.queue.submit([encoder.finish()])
+    return target
+  }
+
+  test('should compare against the stencil clear value', async () => {
+    const equal = stencilPipeline('equal', 'keep')
+    expect(await readPixel(renderStencil(1, [{ pipeline: equal, reference: 1 }]))).toEqual([0, 255, 0, 255])
+    expect(await readPixel(renderStencil(1, [{ pipeline: equal, reference: 0 }]))).toEqual([0, 0, 255, 255])
+  })
+
+  test('should write the stencil reference with passOp replace', async () => {
+    const write = stencilPipeline('never', 'replace')
+    const writeAlways = stencilPipeline('always', 'replace')
+    const equal = stencilPipeline('equal', 'keep')
+
+    // compare 'never' fails, so nothing is written and the masked draw is rejected
+    expect(await readPixel(renderStencil(0, [
+      { pipeline: write, reference: 5 },
+      { pipeline: equal, reference: 5 }
+    ]))).toEqual([0, 0, 255, 255])
+
+    expect(await readPixel(renderStencil(0, [
+      { pipeline: writeAlways, reference: 5 },
+      { pipeline: equal, reference: 5 }
+    ]))).toEqual([0, 255, 0, 255])
+  })
+})
+
+describe('Render Pass Attachments: Depth-Only', () => {
+  // Vertex-only pipeline, as used for shadow maps and depth prepasses
+  function depthOnlyPipeline(depthStencil = {}) {
+    const module = device.createShaderModule({ code: SHADER })
+    return device.createRenderPipeline({
+      layout: null,
+      vertex: { module, entryPoint: 'vs_main' },
+      depthStencil: { format: 'depth32float', depthWriteEnabled: true, depthCompare: 'less', ...depthStencil }
+    })
+  }
+
+  // Depth-only pass, then a color pass that only draws where z = 0.5 is in front of it
+  async function renderDepth(pipeline) {
+    const depth = device.createTexture({
+      width: SIZE,
+      height: SIZE,
+      format: 'depth32float',
+      usage: GPUTextureUsage.RENDER_ATTACHMENT
+    })
+    const target = createTarget('rgba8unorm')
+    const probe = createPipeline({
+      depthStencil: { format: 'depth32float', depthWriteEnabled: false, depthCompare: 'less' }
+    })
+
+    const encoder = device.createCommandEncoder()
+    const depthPass = encoder.beginRenderPass({
+      depthStencilAttachment: {
+        view: depth.createView(),
+        depthLoadOp: 'clear',
+        depthStoreOp: 'store',
+        depthClearValue: 1.0
+      }
+    })
+    depthPass.setPipeline(pipeline)
+    depthPass.draw(3)
+    depthPass.end()
+
+    const colorPass = encoder.beginRenderPass({
+      colorAttachments: [{
+        view: target.createView(),
+        loadOp: 'clear',
+        storeOp: 'store',
+        clearValue: { r: 0, g: 0, b: 1, a: 1 }
+      }],
+      depthStencilAttachment: { view: depth.createView(), depthReadOnly: true }
+    })
+    colorPass.setPipeline(probe)
+    colorPass.draw(3)
+    colorPass.end()
+    device.queue.submit([encoder.finish()])
+    return readPixel(target)
+  }
+
+  test('should render depth without color attachments', async () => {
+    // Depth 0.5 was written, so the probe at 0.5 is rejected
+    expect(await renderDepth(depthOnlyPipeline())).toEqual([0, 0, 255, 255])
+  })
+
+  test('should apply constant depth bias', async () => {
+    // Biased depth lies behind 0.5, so the probe passes
+    expect(await renderDepth(depthOnlyPipeline({ depthBias: 1 << 16 }))).toEqual([0, 255, 0, 255])
+  })
+})
+
+describe('Render Pass Attachments: Sparse Color', () => {
+  // Only writes location 1; location 0 is a hole in both pipeline and pass
+  const SPARSE_SHADER = `
+    @vertex
+    fn vs_main(@builtin(vertex_index) i: u32) -> @builtin(position) vec4<f32> {
+      let uv = vec2<f32>(f32((i << 1u) & 2u), f32(i & 2u));
+      return vec4<f32>(uv * 2.0 - 1.0, 0.5, 1.0);
+    }
+
+    @fragment
+    fn fs_main() -> @location(1) vec4<f32> {
+      return vec4<f32>(1.0, 1.0, 0.0, 1.0);
+    }
+  `
+
+  test('should skip null color attachments', async () => {
+    const module = device.createShaderModule({ code: SPARSE_SHADER })
+    const pipeline = device.createRenderPipeline({
+      layout: null,
+      vertex: { module, entryPoint: 'vs_main' },
+      fragment: { module, entryPoint: 'fs_main', targets: [null, { format: 'rgba8unorm' }] }
+    })
+    const target = createTarget('rgba8unorm')
+
+    const encoder = device.createCommandEncoder()
+    const pass = encoder.beginRenderPass({
+      colorAttachments: [null, {
+        view: target.createView(),
+        loadOp: 'clear',
+        storeOp: 'store',
+        clearValue: { r: 0, g: 0, b: 1, a: 1 }
+      }]
+    })
+    pass.setPipeline(pipeline)
+    pass.draw(3)
+    pass.end()
+    device.queue.submit([encoder.finish()])
+
+    expect(await readPixel(target)).toEqual([255, 255, 0, 255])
+  })
+})
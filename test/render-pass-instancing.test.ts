@@ -0,0 +1,115 @@
+/**
+ * Render Pass Instancing Tests
+ *
+ * Tests for instanceCount / firstVertex / firstInstance on draw and drawIndexed.
+ * Each instance fills one quadrant of a 4x4 target, selected by instance_index.
+ */
+
+import { describe, test, expect, beforeAll, afterAll } from 'bun:test'
+import { Gpu, GPUBufferUsage, GPUTextureUsage } from '../webgpu.js'
+
+const SIZE = 4
+
+let device
+let pipeline
+
+const SHADER = `
+  var<private> CORNERS: array<vec2<f32>, 6> = array<vec2<f32>, 6>(
+    vec2<f32>(0.0, 0.0), vec2<f32>(1.0, 0.0), vec2<f32>(0.0, 1.0),
+    vec2<f32>(0.0, 1.0), vec2<f32>(1.0, 0.0), vec2<f32>(1.0, 1.0)
+  );
+
+  @vertex
+  fn vs_main(@builtin(vertex_index) v: u32, @builtin(instance_index) i: u32) -> @builtin(position) vec4<f32> {
+    let cell = vec2<f32>(f32(i % 2u), f32(i / 2u));
+    return vec4<f32>(cell + CORNERS[v % 6u] - 1.0, 0.5, 1.0);
+  }
+
+  @fragment
+  fn fs_main() -> @location(0) vec4<f32> {
+    return vec4<f32>(0.0, 1.0, 0.0, 1.0);
+  }
+`
+
+// Pixel sampled from each instance's quadrant (instance 0 is bottom-left)
+const QUADRANT_PIXELS = [[0, 3], [3, 3], [0, 0], [3, 0]]
+
+async function render(record) {
+  const target = device.createTexture({
+    width: SIZE,
+    height: SIZE,
+    format: 'rgba8unorm',
+    usage: GPUTextureUsage.RENDER_ATTACHMENT | GPUTextureUsage.COPY_SRC
+  })
+  const readback = device.createBuffer({
+    size: 256 * SIZE,
+    usage: GPUBufferUsage.COPY_DST | GPUBufferUsage.MAP_READ
+  })
+
+  const encoder = device.createCommandEncoder()
+  const pass = encoder.beginRenderPass({
+    colorAttachments: [{
+      view: target.createView(),
+      loadOp: 'clear',
+      storeOp: 'store',
+      clearValue: { r: 0, g: 0, b: 0, a: 1 }
+    }]
+  })
+  pass.setPipeline(pipeline)
+  record(pass)
+  pass.end()
+  encoder.copyTextureToBuffer(target, 0, 0, 0, 0, readback, 0, 256, SIZE, SIZE, SIZE, 1)
+  device.queue.submit([encoder.finish()])
+
+  await readback.mapAsync('READ')
+  const pixels = new Uint8Array(readback.getMappedRange())
+  // Which instances (quadrants) were drawn
+  const drawn = QUADRANT_PIXELS.map(([x, y]) => pixels[y * 256 + x * 4 + 1] === 255)
+  readback.unmap()
+  return drawn
+}
+
+beforeAll(async () => {
+  const adapter = await Gpu().requestAdapter()
+  device = await adapter.requestDevice()
+  const module = device.createShaderModule({ code: SHADER })
+  pipeline = device.createRenderPipeline({
+    layout: null,
+    vertex: { module, entryPoint: 'vs_main' },
+    fragment: { module, entryPoint: 'fs_main', targets: [{ format: 'rgba8unorm' }] }
+  })
+})
+
+afterAll(() => {
+  device?.destroy()
+})
+
+describe('Render Pass Instancing: draw', () => {
+  test('should draw a single instance by default', async () => {
+    expect(await render((pass) => pass.draw(6))).toEqual([true, false, false, false])
+  })
+
+  test('should honor instanceCount and firstInstance', async () => {
+    expect(await render((pass) => pass.draw(6, 2, 0, 1))).toEqual([false, true, true, false])
+  })
+
+  test('should honor firstVertex', async () => {
+    expect(await render((pass) => pass.draw(6, 1, 6, 3))).toEqual([false, false, false, true])
+  })
+})
+
+describe('Render Pass Instancing: drawIndexed', () => {
+  test('should honor instanceCount, baseVertex and firstInstance', async () => {
+    const indices = device.createBuffer({
+      size: 12,
+      usage: GPUBufferUsage.INDEX | GPUBufferUsage.COPY_DST
+    })
+    device.queue.writeBuffer(indices, 0, Buffer.from(new Uint16Array([0, 1, 2, 3, 4, 5]).buffer))
+
+    const drawn = await render((pass) => {
+      pass.setIndexBuffer(indices, 'uint16')
+      pass.drawIndexed(6, 2, 0, 6, 2)
+    })
+    expect(drawn).toEqual([false, false, true, true])
+  })
+})
@@ -0,0 +1,74 @@
+/**
+ * Required Limits Tests
+ *
+ * requestDevice({ requiredLimits }) takes limit values or a preset
+ * ("default", "downlevel", "adapterMaximum") and rejects limits the adapter
+ * can't provide. Adapters differ, so expectations are relative to adapter.limits.
+ */
+
+import { describe, test, expect, beforeAll } from 'bun:test'
+import { Gpu } from '../webgpu.js'
+
+let adapter
+
+beforeAll(async () => {
+  adapter = await Gpu().requestAdapter()
+})
+
+describe('Required Limits: Values', () => {
+  test('should create the device with the requested limits', async () => {
+    const device = await adapter.requestDevice({ requiredLimits: { maxBindGroups: 2 } })
+    expect(device.limits.maxBindGroups).toBe(2)
+    device.destroy()
+  })
+
+  test('should list every exceeded limit', async () => {
+    const supported = adapter.limits
+    const request = adapter.requestDevice({
+      requiredLimits: {
+        maxBufferSize: supported.maxBufferSize + 1,
+        maxTextureDimension2D: supported.maxTextureDimension2D * 2
+      }
+    })
+    const error = await request.catch((e) => e)
+    expect(error.message).toContain(`maxBufferSize (requested ${supported.maxBufferSize + 1}, adapter supports ${supported.maxBufferSize})`)
+    expect(error.message).toContain('maxTextureDimension2D')
+  })
+
+  test('should reject unknown limits and invalid values', async () => {
+    await expect(adapter.requestDevice({ requiredLimits: { maxTeapots: 1 } })).rejects.toThrow('Unknown limit: maxTeapots')
+    await expect(adapter.requestDevice({ requiredLimits: { maxBindGroups: 1.5 } })).rejects.toThrow('non-negative integer')
+  })
+})
+
+describe('Required Limits: Adapter Limits', () => {
+  test('should report every device limit', async () => {
+    const device = await adapter.requestDevice()
+    expect(Object.keys(adapter.limits).sort()).toEqual(Object.keys(device.limits).sort())
+    expect(adapter.limits.maxComputeInvocationsPerWorkgroup).toBeGreaterThanOrEqual(device.limits.maxComputeInvocationsPerWorkgroup)
+    device.destroy()
+  })
+})
+
+describe('Required Limits: Presets', () => {
+  test('should grant the adapter maximum', async () => {
+    const device = await adapter.requestDevice({ requiredLimits: 'adapterMaximum' })
+    expect(device.limits.maxBufferSize).toBe(adapter.limits.maxBufferSize)
+    expect(device.limits.maxTextureDimension2D).toBe(adapter.limits.maxTextureDimension2D)
+    device.destroy()
+  })
+
+  test('should apply values on top of a preset', async () => {
+    const device = await adapter.requestDevice({
+      limitsPreset: 'adapterMaximum',
+      requiredLimits: { maxBindGroups: 1 }
+    })
+    expect(device.limits.maxBindGroups).toBe(1)
+    expect(device.limits.maxTextureDimension2D).toBe(adapter.limits.maxTextureDimension2D)
+    device.destroy()
+  })
+
+  test('should reject unknown presets', async () => {
+    await expect(adapter.requestDevice({ requiredLimits: 'huge' })).rejects.toThrow('Unknown limits preset: huge')
+  })
+})
@@ -0,0 +1,70 @@
+/**
+ * Explicit Resource Management Tests
+ *
+ * Buffers, textures, command encoders and devices implement Symbol.dispose and
+ * Symbol.asyncDispose, so `using` / `await using` release them at scope exit.
+ */
+
+import { describe, test, expect, beforeAll, afterAll } from 'bun:test'
+import { Gpu, GPUBufferUsage, GPUTextureUsage } from '../webgpu.js'
+
+let adapter
+let device
+
+beforeAll(async () => {
+  adapter = await Gpu().requestAdapter()
+  device = await adapter.requestDevice()
+})
+
+afterAll(() => {
+  device?.destroy()
+})
+
+describe('Resource Management: Buffers and Textures', () => {
+  test('should destroy a buffer on dispose', async () => {
+    const buffer = device.createBuffer({ size: 16, usage: GPUBufferUsage.MAP_READ | GPUBufferUsage.COPY_DST })
+    buffer[Symbol.dispose]()
+    await expect(buffer.mapAsync('READ')).rejects.toThrow()
+  })
+
+  test('should destroy a buffer on async dispose', async () => {
+    const buffer = device.createBuffer({ size: 16, usage: GPUBufferUsage.MAP_READ | GPUBufferUsage.COPY_DST })
+    await buffer[Symbol.asyncDispose]()
+    await expect(buffer.mapAsync('READ')).rejects.toThrow()
+  })
+
+  test('should allow disposing a destroyed texture again', () => {
+    const texture = device.createTexture({
+      width: 64,
+      height: 64,
+      format: 'rgba8unorm',
+      mipLevelCount: 7,
+      usage: GPUTextureUsage.TEXTURE_BINDING
+    })
+    texture.destroy()
+    expect(() => texture[Symbol.dispose]()).not.toThrow()
+  })
+})
+
+describe('Resource Management: Command Encoders', () => {
+  test('should discard an unfinished encoder on dispose', () => {
+    const source = device.createBuffer({ size: 16, usage: GPUBufferUsage.COPY_SRC })
+    const destination = device.createBuffer({ size: 16, usage: GPUBufferUsage.COPY_DST })
+    const encoder = device.createCommandEncoder()
+    encoder.copyBufferToBuffer(source, 0, destination, 0, 16)
+    encoder[Symbol.dispose]()
+
+    expect(() => encoder.copyBufferToBuffer(source, 0, destination, 0, 16)).toThrow('already finished')
+    // Discarding twice is a no-op
+    expect(() => encoder.discard()).not.toThrow()
+  })
+})
+
+describe('Resource Management: Devices', () => {
+  test('should wait for submitted work on async dispose', async () => {
+    const scoped = await adapter.requestDevice()
+    const buffer = scoped.createBuffer({ size: 16, usage: GPUBufferUsage.COPY_DST })
+    scoped.queue.writeBuffer(buffer, 0, Buffer.alloc(16))
+    await scoped[Symbol.asyncDispose]()
+  })
+})
@@ -0,0 +1,48 @@
+/**
+ * Sampler Anisotropy Tests
+ *
+ * maxAnisotropy > 1 needs linear min/mag/mipmap filters: omitted filters
+ * default to linear, nearest ones are rejected before reaching wgpu, and
+ * values above the backend maximum are clamped.
+ */
+
+import { describe, test, expect, beforeAll, afterAll } from 'bun:test'
+import { Gpu } from '../webgpu.js'
+
+let device
+
+beforeAll(async () => {
+  const adapter = await Gpu().requestAdapter()
+  device = await adapter.requestDevice()
+})
+
+afterAll(() => {
+  device?.destroy()
+})
+
+describe('Sampler Anisotropy: Filters', () => {
+  test('should default filters to linear', () => {
+    expect(device.createSampler({ maxAnisotropy: 8 })).toBeDefined()
+  })
+
+  test('should reject nearest filters', () => {
+    expect(() => device.createSampler({ maxAnisotropy: 4, magFilter: 'linear', minFilter: 'nearest' }))
+      .toThrow('maxAnisotropy 4 requires linear filtering, but minFilter is "nearest"')
+    expect(() => device.createSampler({ maxAnisotropy: 4, mipmapFilter: 'nearest' }))
+      .toThrow('mipmapFilter is "nearest"')
+  })
+
+  test('should keep nearest filters without anisotropy', () => {
+    expect(device.createSampler({ maxAnisotropy: 1, minFilter: 'nearest' })).toBeDefined()
+  })
+})
+
+describe('Sampler Anisotropy: Range', () => {
+  test('should clamp values above the maximum', () => {
+    expect(device.createSampler({ maxAnisotropy: 65536 })).toBeDefined()
+  })
+
+  test('should reject zero', () => {
+    expect(() => device.createSampler({ maxAnisotropy: 0 })).toThrow('at least 1')
+  })
+})
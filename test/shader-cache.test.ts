@@ -0,0 +1,71 @@
+/**
+ * Shader Module Cache Tests
+ *
+ * Tests for the opt-in device-level cache that de-duplicates
+ * shader modules created from identical WGSL source.
+ */
+
+import { describe, test, expect, beforeAll, afterAll } from 'bun:test'
+import { Gpu } from '../webgpu.js'
+
+const code = `
+  @compute @workgroup_size(1)
+  fn main() {}
+`
+
+describe('Shader Module Cache', () => {
+  let device
+
+  beforeAll(async () => {
+    const gpu = Gpu()
+    const adapter = await gpu.requestAdapter()
+    device = await adapter.requestDevice()
+  })
+
+  afterAll(() => {
+    device?.destroy()
+  })
+
+  test('should be disabled by default', () => {
+    device.createShaderModule({ code })
+    device.createShaderModule({ code })
+
+    const stats = device.getShaderModuleCacheStats()
+    expect(stats.entries).toBe(0)
+    expect(stats.hits).toBe(0)
+    expect(stats.misses).toBe(0)
+  })
+
+  test('should reuse modules for identical source', () => {
+    device.setShaderModuleCacheEnabled(true)
+
+    const first = device.createShaderModule({ label: 'first', code })
+    const second = device.createShaderModule({ label: 'second', code })
+    device.createShaderModule({ code: code + '\n// variant' })
+
+    const stats = device.getShaderModuleCacheStats()
+    expect(stats.entries).toBe(2)
+    expect(stats.hits).toBe(1)
+    expect(stats.misses).toBe(2)
+
+    // Cached modules remain usable for pipeline creation
+    for (const module of [first, second]) {
+      const pipeline = device.createComputePipeline({
+        compute: { module, entryPoint: 'main' }
+      })
+      expect(pipeline).toBeDefined()
+    }
+  })
+
+  test('should drop entries when cleared or disabled', () => {
+    device.setShaderModuleCacheEnabled(true)
+    device.createShaderModule({ code })
+
+    device.clearShaderModuleCache()
+    expect(device.getShaderModuleCacheStats()).toEqual({ entries: 0, hits: 0, misses: 0 })
+
+    device.createShaderModule({ code })
+    device.setShaderModuleCacheEnabled(false)
+    expect(device.getShaderModuleCacheStats().entries).toBe(0)
+  })
+})
@@ -0,0 +1,219 @@
+/**
+ * Shader Reflection Tests
+ *
+ * Tests for APIs that derive pipeline state from WGSL via naga reflection.
+ */
+
+import { describe, test, expect, beforeAll, afterAll } from 'bun:test'
+import { Gpu, GPUBufferUsage } from '../webgpu.js'
+
+describe('Shader Reflection: createBindGroupLayoutFromShader', () => {
+  let device
+
+  beforeAll(async () => {
+    const gpu = Gpu()
+    const adapter = await gpu.requestAdapter()
+    device = await adapter.requestDevice()
+  })
+
+  afterAll(() => {
+    device?.destroy()
+  })
+
+  test('should generate a layout usable for dispatch', async () => {
+    const module = device.createShaderModule({
+      code: `
+        struct Params { factor: f32, count: u32 }
+
+        @group(0) @binding(0) var<uniform> params: Params;
+        @group(0) @binding(1) var<storage, read> input: array<f32>;
+        @group(0) @binding(2) var<storage, read_write> output: array<f32>;
+
+        @compute @workgroup_size(4)
+        fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+          if (id.x < params.count) {
+            output[id.x] = input[id.x] * params.factor;
+          }
+        }
+      `
+    })
+
+    const layout = device.createBindGroupLayoutFromShader(module, 0, 'reflected')
+    const pipeline = device.createComputePipeline({
+      layout: device.createPipelineLayout({ bindGroupLayouts: [layout] }),
+      compute: { module, entryPoint: 'main' }
+    })
+
+    const params = device.createBuffer({ size: 8, usage: GPUBufferUsage.UNIFORM | GPUBufferUsage.COPY_DST })
+    device.queue.writeBuffer(params, 0, Buffer.from(new Float32Array([3, 0]).buffer))
+    device.queue.writeBuffer(params, 4, Buffer.from(new Uint32Array([4]).buffer))
+    const input = device.createBuffer({ size: 16, usage: GPUBufferUsage.STORAGE | GPUBufferUsage.COPY_DST })
+    device.queue.writeBuffer(input, 0, Buffer.from(new Float32Array([1, 2, 3, 4]).buffer))
+    const output = device.createBuffer({ size: 16, usage: GPUBufferUsage.STORAGE | GPUBufferUsage.COPY_SRC })
+    const staging = device.createBuffer({ size: 16, usage: GPUBufferUsage.MAP_READ | GPUBufferUsage.COPY_DST })
+
+    const bindGroup = device.createBindGroup({
+      layout,
+      entries: [
+        { binding: 0, resource: { buffer: params } },
+        { binding: 1, resource: { buffer: input } },
+        { binding: 2, resource: { buffer: output } }
+      ]
+    })
+
+    const encoder = device.createCommandEncoder()
+    const pass = encoder.beginComputePass()
+    pass.setPipeline(pipeline)
+    pass.setBindGroup(0, bindGroup)
+    pass.dispatchWorkgroups(1)
+    pass.end()
+    encoder.copyBufferToBuffer(output, 0, staging, 0, 16)
+    device.queue.submit([encoder.finish()])
+
+    await staging.mapAsync('READ')
+    expect(Array.from(new Float32Array(staging.getMappedRange()))).toEqual([3, 6, 9, 12])
+    staging.unmap()
+  })
+
+  test('should reflect textures, samplers and per-stage visibility', () => {
+    const module = device.createShaderModule({
+      code: `
+        @group(1) @binding(0) var color: texture_2d<f32>;
+        @group(1) @binding(1) var color_sampler: sampler;
+        @group(1) @binding(2) var shadow: texture_depth_2d;
+        @group(1) @binding(3) var shadow_sampler: sampler_comparison;
+        @group(1) @binding(4) var<uniform> offset: vec4<f32>;
+
+        @vertex
+        fn vs_main(@builtin(vertex_index) index: u32) -> @builtin(position) vec4<f32> {
+          return vec4<f32>(f32(index), 0.0, 0.0, 1.0) + offset;
+        }
+
+        @fragment
+        fn fs_main(@builtin(position) position: vec4<f32>) -> @location(0) vec4<f32> {
+          let depth = textureSampleCompare(shadow, shadow_sampler, position.xy, 0.5);
+          return textureSample(color, color_sampler, position.xy) * depth;
+        }
+      `
+    })
+
+    // Groups without bindings produce an empty layout
+    const layouts = [0, 1].map((group) => device.createBindGroupLayoutFromShader(module, group))
+    const pipeline = device.createRenderPipeline({
+      layout: device.createPipelineLayout({ bindGroupLayouts: layouts }),
+      vertex: { module, entryPoint: 'vs_main' },
+      fragment: { module, entryPoint: 'fs_main', targets: [{ format: 'rgba8unorm' }] }
+    })
+    expect(pipeline).toBeDefined()
+  })
+})
+
+describe('Shader Reflection: createUniformWriter', () => {
+  let device
+
+  const SHADER = `
+    struct Light {
+      color: vec3<f32>,
+      intensity: f32,
+    }
+
+    struct Uniforms {
+      transform: mat3x3<f32>,
+      tint: vec4<f32>,
+      count: u32,
+      offset: i32,
+      lights: array<Light, 2>,
+      weights: array<vec4<f32>, 2>,
+    }
+
+    @group(0) @binding(0) var<uniform> uniforms: Uniforms;
+    @group(0) @binding(1) var<storage, read_write> output: array<f32>;
+
+    @compute @workgroup_size(1)
+    fn main() {
+      output[0] = uniforms.transform[2][1];
+      output[1] = uniforms.tint.w;
+      output[2] = f32(uniforms.count) + f32(uniforms.offset);
+      output[3] = uniforms.lights[1].color.z * uniforms.lights[1].intensity;
+      output[4] = uniforms.weights[1].y;
+    }
+  `
+
+  beforeAll(async () => {
+    const gpu = Gpu()
+    const adapter = await gpu.requestAdapter()
+    device = await adapter.requestDevice()
+  })
+
+  afterAll(() => {
+    device?.destroy()
+  })
+
+  test('should pack fields with the shader layout', () => {
+    const module = device.createShaderModule({ code: SHADER })
+    const writer = device.createUniformWriter(module, 'Uniforms')
+    // mat3x3 (48) + vec4 (16) + u32 + i32 (8, padded to 16) + 2 lights (2 x 16) + 2 vec4 (32)
+    expect(writer.size).toBe(144)
+
+    writer.set({ tint: [1, 2, 3, 4], count: 7 })
+    const floats = new Float32Array(writer.getData().buffer.slice(0))
+    expect(Array.from(floats.slice(12, 16))).toEqual([1, 2, 3, 4])
+    expect(new Uint32Array(floats.buffer)[16]).toBe(7)
+  })
+
+  test('should upload values the shader can read', async () => {
+    const module = device.createShaderModule({ code: SHADER })
+    const writer = device.createUniformWriter(module, 'Uniforms')
+    writer.set({
+      // Column-major; vec3 columns are padded to 16 bytes
+      transform: [[1, 0, 0], [0, 1, 0], [0, 5, 1]],
+      tint: new Float32Array([0, 0, 0, 0.5]),
+      count: 3,
+      offset: -1,
+      lights: [{ color: [1, 1, 1], intensity: 1 }, { color: [0, 0, 2], intensity: 3 }],
+      weights: [0, 0, 0, 0, 0, 9, 0, 0]
+    })
+
+    const uniforms = device.createBuffer({ size: writer.size, usage: GPUBufferUsage.UNIFORM | GPUBufferUsage.COPY_DST })
+    writer.upload(device.queue, uniforms)
+
+    const output = device.createBuffer({ size: 20, usage: GPUBufferUsage.STORAGE | GPUBufferUsage.COPY_SRC })
+    const staging = device.createBuffer({ size: 20, usage: GPUBufferUsage.MAP_READ | GPUBufferUsage.COPY_DST })
+    const layout = device.createBindGroupLayoutFromShader(module, 0)
+    const pipeline = device.createComputePipeline({
+      layout: device.createPipelineLayout({ bindGroupLayouts: [layout] }),
+      compute: { module, entryPoint: 'main' }
+    })
+    const bindGroup = device.createBindGroup({
+      layout,
+      entries: [
+        { binding: 0, resource: { buffer: uniforms } },
+        { binding: 1, resource: { buffer: output } }
+      ]
+    })
+
+    const encoder = device.createCommandEncoder()
+    const pass = encoder.beginComputePass()
+    pass.setPipeline(pipeline)
+    pass.setBindGroup(0, bindGroup)
+    pass.dispatchWorkgroups(1)
+    pass.end()
+    encoder.copyBufferToBuffer(output, 0, staging, 0, 20)
+    device.queue.submit([encoder.finish()])
+
+    await staging.mapAsync('READ')
+    expect(Array.from(new Float32Array(staging.getMappedRange()))).toEqual([5, 0.5, 2, 6, 9])
+    staging.unmap()
+  })
+
+  test('should reject values that do not match the struct', () => {
+    const module = device.createShaderModule({ code: SHADER })
+    const writer = device.createUniformWriter(module, 'Uniforms')
+
+    expect(() => writer.set({ colour: [1, 1, 1, 1] })).toThrow("Unknown field 'colour'")
+    expect(() => writer.set({ tint: [1, 2, 3] })).toThrow('Uniforms.tint: expected 4 components, got 3')
+    expect(() => writer.set({ count: -1 })).toThrow('not a valid u32')
+    expect(() => writer.set({ lights: [{ color: 'red' }] })).toThrow('Uniforms.lights[0].color')
+    expect(() => device.createUniformWriter(module, 'Missing')).toThrow("Struct 'Missing' not found")
+  })
+})
@@ -0,0 +1,163 @@
+/**
+ * Shader Tools Tests
+ *
+ * Tests for the offline shader conversion helpers (naga-based).
+ */
+
+import { describe, test, expect } from 'bun:test'
+import { readFileSync } from 'fs'
+import { join } from 'path'
+import { Gpu, spirvToWgsl, translateWgsl, preprocessWgsl } from '../webgpu.js'
+
+// Compute shader doubling every element of a storage buffer at @group(0) @binding(0)
+const spirv = readFileSync(join(__dirname, 'fixtures', 'double.spv'))
+
+describe('Shader Tools: spirvToWgsl', () => {
+  test('should convert SPIR-V to WGSL', () => {
+    const wgsl = spirvToWgsl(spirv)
+    expect(wgsl).toContain('@compute @workgroup_size(64, 1, 1)')
+    expect(wgsl).toContain('@group(0) @binding(0)')
+  })
+
+  test('should accept word arrays', () => {
+    const words = new Uint32Array(spirv.buffer, spirv.byteOffset, spirv.byteLength / 4)
+    expect(spirvToWgsl(words)).toBe(spirvToWgsl(spirv))
+  })
+
+  test('should produce WGSL accepted by createShaderModule', async () => {
+    const adapter = await Gpu().requestAdapter()
+    const device = await adapter.requestDevice()
+    const module = device.createShaderModule({ code: spirvToWgsl(spirv) })
+    const pipeline = device.createComputePipeline({
+      compute: { module, entryPoint: 'main' }
+    })
+    expect(pipeline).toBeDefined()
+    device.destroy()
+  })
+
+  test('should reject invalid input', () => {
+    expect(() => spirvToWgsl(new Uint8Array(3))).toThrow()
+    expect(() => spirvToWgsl(new Uint8Array(16))).toThrow()
+  })
+})
+
+const wgsl = `
+  @group(0) @binding(0) var<storage, read_write> data: array<f32>;
+
+  @compute @workgroup_size(64)
+  fn scale(@builtin(global_invocation_id) id: vec3<u32>) {
+    data[id.x] = data[id.x] * 2.0;
+  }
+
+  @vertex
+  fn vs_main(@builtin(vertex_index) index: u32) -> @builtin(position) vec4<f32> {
+    return vec4<f32>(f32(index), 0.0, 0.0, 1.0);
+  }
+`
+
+describe('Shader Tools: translateWgsl', () => {
+  test('should translate to MSL and HLSL', () => {
+    expect(translateWgsl(wgsl, 'msl')).toContain('kernel void scale(')
+    expect(translateWgsl(wgsl, 'hlsl')).toContain('[numthreads(64, 1, 1)]')
+  })
+
+  test('should translate a selected entry point to GLSL', () => {
+    const compute = translateWgsl(wgsl, 'glsl', { entryPoint: 'scale', glslVersion: '450' })
+    expect(compute).toContain('#version 450 core')
+    expect(compute).toContain('local_size_x = 64')
+
+    const vertex = translateWgsl(wgsl, 'glsl', { entryPoint: 'vs_main' })
+    expect(vertex).toContain('#version 310 es')
+    expect(vertex).toContain('gl_Position')
+  })
+
+  test('should round-trip through SPIR-V', () => {
+    const spirvOut = translateWgsl(wgsl, 'spirv')
+    expect(new Uint32Array(spirvOut.buffer, spirvOut.byteOffset, 1)[0]).toBe(0x07230203)
+    expect(spirvToWgsl(spirvOut)).toContain('fn scale(')
+  })
+
+  test('should report errors', () => {
+    expect(() => translateWgsl('fn broken( {', 'msl')).toThrow()
+    expect(() => translateWgsl(wgsl, 'dxil')).toThrow()
+    expect(() => translateWgsl(wgsl, 'glsl', { entryPoint: 'missing' })).toThrow()
+    expect(() => translateWgsl(wgsl, 'glsl', { glslVersion: 'latest' })).toThrow()
+  })
+})
+
+describe('Shader Tools: WGSL preprocessor', () => {
+  test('should expand defines and conditionals', () => {
+    const code = preprocessWgsl(
+      [
+        '#define COUNT 4u',
+        '#ifdef USE_F16',
+        'alias Real = f16;',
+        '#else',
+        'alias Real = f32;',
+        '#endif',
+        'const N: u32 = COUNT;',
+        'const M = 1u + WIDTH;'
+      ].join('\n'),
+      { defines: { WIDTH: 8 } }
+    )
+    expect(code).toBe('alias Real = f32;\nconst N: u32 = 4u;\nconst M = 1u + 8;\n')
+  })
+
+  test('should resolve includes once through a callback', () => {
+    const files = {
+      'a.wgsl': '#include "b.wgsl"\nconst A = B;',
+      'b.wgsl': 'const B = 1;'
+    }
+    const requests = []
+    const code = preprocessWgsl('#include "a.wgsl"\n#include "b.wgsl"\nconst C = A;', {
+      resolveInclude: (path, from) => {
+        requests.push([path, from])
+        return files[path]
+      }
+    })
+    expect(code).toBe('const B = 1;\nconst A = B;\nconst C = A;\n')
+    expect(requests[0]).toEqual(['a.wgsl', '<shader>'])
+  })
+
+  test('should resolve includes from search paths', () => {
+    const code = preprocessWgsl('#include "common.wgsl"', {
+      includePaths: [join(__dirname, 'fixtures', 'wgsl')]
+    })
+    expect(code).toContain('const SCALE: f32 = 2.0;')
+    expect(code).toContain('fn scale(value: f32)')
+  })
+
+  test('should reject malformed directives', () => {
+    expect(() => preprocessWgsl('#include "missing.wgsl"')).toThrow()
+    expect(() => preprocessWgsl('#ifdef A\nconst x = 1;')).toThrow()
+    expect(() => preprocessWgsl('#pragma once')).toThrow()
+  })
+
+  test('should preprocess in createShaderModule and remap errors', async () => {
+    const adapter = await Gpu().requestAdapter()
+    const device = await adapter.requestDevice()
+    const resolveInclude = (path) => path === 'broken.wgsl'
+      ? 'const OK = 1u;\nfn broken() -> u32 { return missing_value; }'
+      : null
+
+    const module = device.createShaderModule({
+      code: '#include "common.wgsl"\n@compute @workgroup_size(WG) fn main() { _ = scale(1.0); }',
+      defines: { WG: 64 },
+      includePaths: [join(__dirname, 'fixtures', 'wgsl')]
+    })
+    expect(module).toBeDefined()
+
+    let message = ''
+    try {
+      device.createShaderModule({
+        label: 'main.wgsl',
+        code: '#include "broken.wgsl"\n@compute @workgroup_size(1) fn main() {}',
+        resolveInclude
+      })
+    } catch (e) {
+      message = e.message
+    }
+    expect(message).toContain('broken.wgsl:2:')
+    device.destroy()
+  })
+})
@@ -0,0 +1,99 @@
+/**
+ * Storage Texture Access Tests
+ *
+ * Read-write storage textures let a kernel update an image in place. They
+ * need adapter support, advertised as the
+ * 'readonly-and-readwrite-storage-textures' adapter feature.
+ */
+
+import { describe, test, expect, beforeAll, afterAll } from 'bun:test'
+import { Gpu, GPUBufferUsage, GPUTextureUsage } from '../webgpu.js'
+
+const SIZE = 4
+
+let device
+let supported
+
+beforeAll(async () => {
+  const adapter = await Gpu().requestAdapter()
+  supported = adapter.features.includes('readonly-and-readwrite-storage-textures')
+  device = await adapter.requestDevice()
+})
+
+afterAll(() => {
+  device?.destroy()
+})
+
+describe('Storage Textures: Read-Write Access', () => {
+  test('should double every texel in place', async () => {
+    if (!supported) return
+
+    const texture = device.createTexture({
+      width: SIZE,
+      height: SIZE,
+      format: 'r32float',
+      usage: GPUTextureUsage.STORAGE_BINDING | GPUTextureUsage.COPY_SRC | GPUTextureUsage.COPY_DST
+    })
+    const upload = device.createBuffer({ size: 256 * SIZE, usage: GPUBufferUsage.COPY_SRC | GPUBufferUsage.COPY_DST })
+    const values = new Float32Array(64 * SIZE)
+    for (let y = 0; y < SIZE; y++) {
+      for (let x = 0; x < SIZE; x++) values[y * 64 + x] = y * SIZE + x
+    }
+    device.queue.writeBuffer(upload, 0, values)
+    const readback = device.createBuffer({ size: 256 * SIZE, usage: GPUBufferUsage.COPY_DST | GPUBufferUsage.MAP_READ })
+
+    const module = device.createShaderModule({
+      code: `
+        @group(0) @binding(0) var image: texture_storage_2d<r32float, read_write>;
+
+        @compute @workgroup_size(${SIZE}, ${SIZE})
+        fn main(@builtin(global_invocation_id) id: vec3u) {
+          let value = textureLoad(image, id.xy);
+          textureStore(image, id.xy, value * 2.0);
+        }
+      `
+    })
+    const layout = device.createBindGroupLayout({
+      entries: [{ binding: 0, visibility: 4, storageTexture: { access: 'read-write', format: 'r32float' } }]
+    })
+    const pipeline = device.createComputePipeline({
+      layout: device.createPipelineLayout({ bindGroupLayouts: [layout] }),
+      compute: { module, entryPoint: 'main' }
+    })
+    const bindGroup = device.createBindGroup({
+      layout,
+      entries: [{ binding: 0, resource: texture.createView() }]
+    })
+
+    const encoder = device.createCommandEncoder()
+    encoder.copyBufferToTexture({ buffer: upload, bytesPerRow: 256 }, { texture }, { width: SIZE, height: SIZE })
+    const pass = encoder.beginComputePass()
+    pass.setPipeline(pipeline)
+    pass.setBindGroup(0, bindGroup)
+    pass.dispatchWorkgroups(1)
+    pass.end()
+    encoder.copyTextureToBuffer({ texture }, { buffer: readback, bytesPerRow: 256 }, { width: SIZE, height: SIZE })
+    device.queue.submit([encoder.finish()])
+
+    await readback.mapAsync('READ')
+    const result = new Float32Array(readback.getMappedRange())
+    for (let y = 0; y < SIZE; y++) {
+      for (let x = 0; x < SIZE; x++) expect(result[y * 64 + x]).toBe((y * SIZE + x) * 2)
+    }
+    readback.unmap()
+  })
+})
+
+describe('Storage Textures: Access Validation', () => {
+  test('should reject read-write access for formats without read-write support', () => {
+    expect(() => device.createBindGroupLayout({
+      entries: [{ binding: 0, visibility: 4, storageTexture: { access: 'read-write', format: 'rgba8unorm-srgb' } }]
+    })).toThrow('can only be written')
+  })
+
+  test('should still accept write-only access', () => {
+    expect(() => device.createBindGroupLayout({
+      entries: [{ binding: 0, visibility: 4, storageTexture: { access: 'write-only', format: 'rgba8unorm' } }]
+    })).not.toThrow()
+  })
+})
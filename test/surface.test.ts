@@ -0,0 +1,34 @@
+/**
+ * Surface Tests
+ *
+ * Presenting needs a real window, so these cover descriptor validation only.
+ */
+
+import { describe, test, expect } from 'bun:test'
+import { Gpu } from '../webgpu.js'
+
+describe('Surface: createSurface', () => {
+  test('should reject unknown platforms', () => {
+    expect(() => Gpu().createSurface({ platform: 'android', window: 1n })).toThrow('Unknown surface platform')
+  })
+
+  test('should reject null window handles', () => {
+    expect(() => Gpu().createSurface({ platform: 'win32', window: 0n })).toThrow('must not be null')
+    expect(() => Gpu().createSurface({ platform: 'appkit', window: 0n })).toThrow('must not be null')
+  })
+
+  test('should require a display for wayland', () => {
+    expect(() => Gpu().createSurface({ platform: 'wayland', window: 1n })).toThrow('require a display handle')
+  })
+
+  test('should reject XCB window ids wider than 32 bits', () => {
+    expect(() => Gpu().createSurface({ platform: 'xcb', window: 1n << 40n })).toThrow('32-bit XCB window id')
+  })
+})
+
+describe('Surface: createSurfaceFromNativeWindowHandle', () => {
+  test('should reject handles of the wrong size', () => {
+    expect(() => Gpu().createSurfaceFromNativeWindowHandle(Buffer.alloc(2))).toThrow('4 or 8 bytes, got 2')
+    expect(() => Gpu().createSurfaceFromNativeWindowHandle(Buffer.alloc(16))).toThrow('4 or 8 bytes, got 16')
+  })
+})
@@ -0,0 +1,103 @@
+/**
+ * Tensor Tests
+ *
+ * GpuTensor gives storage buffers a shape and dtype. fromArray/toArray move
+ * data without byte offsets, and slice/reshape return views of the same
+ * buffer.
+ */
+
+import { describe, test, expect, beforeAll, afterAll } from 'bun:test'
+import { Gpu, GpuTensor } from '../webgpu.js'
+
+let device
+
+beforeAll(async () => {
+  const adapter = await Gpu().requestAdapter()
+  device = await adapter.requestDevice()
+})
+
+afterAll(() => {
+  device?.destroy()
+})
+
+describe('Tensors: Creation', () => {
+  test('should round-trip typed arrays', async () => {
+    const tensor = GpuTensor.fromArray(device, new Float32Array([1, 2, 3, 4, 5, 6]), { shape: [2, 3] })
+
+    expect(tensor.shape).toEqual([2, 3])
+    expect(tensor.strides).toEqual([3, 1])
+    expect(tensor.dtype).toBe('f32')
+    expect(tensor.size).toBe(6)
+    expect(Array.from(await tensor.toArray())).toEqual([1, 2, 3, 4, 5, 6])
+  })
+
+  test('should infer integer dtypes and convert plain arrays', async () => {
+    const ints = GpuTensor.fromArray(device, new Int32Array([-1, 2, -3]))
+    expect(ints.dtype).toBe('i32')
+    expect(ints.shape).toEqual([3])
+    expect(Array.from(await ints.toArray())).toEqual([-1, 2, -3])
+
+    const bytes = GpuTensor.fromArray(device, [1, 2, 3, 255, 7], { dtype: 'u8' })
+    const result = await bytes.toArray()
+    expect(result instanceof Uint8Array).toBe(true)
+    expect(Array.from(result)).toEqual([1, 2, 3, 255, 7])
+  })
+
+  test('should convert f16 data to and from numbers', async () => {
+    const tensor = GpuTensor.fromArray(device, [0.5, -2, 65504, 1 / 3], { dtype: 'f16' })
+    const values = await tensor.toArray()
+
+    expect(tensor.buffer.size).toBe(8)
+    expect(values[0]).toBe(0.5)
+    expect(values[1]).toBe(-2)
+    expect(values[2]).toBe(65504)
+    expect(Math.abs(values[3] - 1 / 3)).toBeLessThanOrEqual(0.0005)
+  })
+
+  test('should create zero-filled tensors', async () => {
+    const tensor = device.createTensor({ shape: [2, 2], dtype: 'i32' })
+    expect(Array.from(await tensor.toArray())).toEqual([0, 0, 0, 0])
+  })
+})
+
+describe('Tensors: Views', () => {
+  const data = Array.from({ length: 12 }, (_, i) => i)
+
+  test('should slice rows and columns', async () => {
+    const tensor = GpuTensor.fromArray(device, data, { shape: [3, 4] })
+
+    const rows = tensor.slice(0, 1, 3)
+    expect(rows.shape).toEqual([2, 4])
+    expect(rows.byteOffset).toBe(16)
+    expect(rows.isContiguous).toBe(true)
+    expect(Array.from(await rows.toArray())).toEqual([4, 5, 6, 7, 8, 9, 10, 11])
+
+    const columns = tensor.slice(1, 1, 3)
+    expect(columns.shape).toEqual([3, 2])
+    expect(columns.isContiguous).toBe(false)
+    expect(Array.from(await columns.toArray())).toEqual([1, 2, 5, 6, 9, 10])
+  })
+
+  test('should reshape and share the buffer', async () => {
+    const tensor = GpuTensor.fromArray(device, data, { shape: [3, 4] })
+    const reshaped = tensor.reshape([2, -1, 2])
+
+    expect(reshaped.shape).toEqual([2, 3, 2])
+    expect(reshaped.strides).toEqual([6, 2, 1])
+    expect(Array.from(await reshaped.slice(1, 2).toArray())).toEqual([4, 5, 10, 11])
+
+    device.queue.writeBuffer(tensor.buffer, 0, new Float32Array([42]))
+    expect((await reshaped.toArray())[0]).toBe(42)
+  })
+
+  test('should reject invalid shapes and views', () => {
+    const tensor = GpuTensor.fromArray(device, data, { shape: [3, 4] })
+
+    expect(() => GpuTensor.fromArray(device, data, { shape: [5, 2] })).toThrow('holds 10 elements')
+    expect(() => tensor.reshape([5, -1])).toThrow('Cannot reshape')
+    expect(() => tensor.slice(1, 1, 3).reshape([6])).toThrow('non-contiguous')
+    expect(() => tensor.slice(2, 0)).toThrow('out of range')
+    expect(() => tensor.slice(0, 2, 5)).toThrow('out of range')
+    expect(() => device.createTensor({ shape: [2], dtype: 'f64' })).toThrow('Unknown tensor dtype')
+  })
+})
@@ -0,0 +1,167 @@
+/**
+ * Text Rendering Tests
+ *
+ * device.createFont(ttf) rasterizes glyphs into an atlas and font.drawText()
+ * records them into a render pass. The tests build a tiny TrueType font whose
+ * "A" is a square, so the expected pixels are exact.
+ */
+
+import { describe, test, expect, beforeAll, afterAll } from 'bun:test'
+import { Gpu, GPUBufferUsage, GPUTextureUsage } from '../webgpu.js'
+
+let device
+
+beforeAll(async () => {
+  const adapter = await Gpu().requestAdapter()
+  device = await adapter.requestDevice()
+})
+
+afterAll(() => {
+  device?.destroy()
+})
+
+const u16 = (value) => [(value >> 8) & 0xff, value & 0xff]
+const u32 = (value) => [...u16(value >>> 16), ...u16(value & 0xffff)]
+
+/**
+ * Build a TrueType font with 1000 units per em, ascent 800 and descent -200:
+ * glyph 1 ("A") is a 500-unit square with a 600-unit advance, and glyph 2
+ * (" ") is empty with a 300-unit advance.
+ */
+function buildFont() {
+  const head = new Array(54).fill(0)
+  head.splice(18, 2, ...u16(1000))
+  const hhea = new Array(36).fill(0)
+  hhea.splice(4, 4, ...u16(800), ...u16(-200 & 0xffff))
+  hhea.splice(34, 2, ...u16(3))
+  const maxp = [...u32(0x5000), ...u16(3)]
+  const hmtx = [...u16(500), 0, 0, ...u16(600), 0, 0, ...u16(300), 0, 0]
+  // One contour of four on-curve points given as i16 deltas
+  const square = [
+    ...u16(1), ...u16(0), ...u16(0), ...u16(500), ...u16(500),
+    ...u16(3), ...u16(0), 1, 1, 1, 1,
+    ...u16(0), ...u16(500), ...u16(0), ...u16(-500 & 0xffff),
+    ...u16(0), ...u16(0), ...u16(500), ...u16(0),
+  ]
+  const loca = [...u16(0), ...u16(0), ...u16(square.length / 2), ...u16(square.length / 2)]
+  // Segments for " ", "A" and the 0xffff terminator, mapped by delta
+  const cmap = [
+    ...u16(0), ...u16(1), ...u16(3), ...u16(1), ...u32(12),
+    ...u16(4), ...u16(40), ...u16(0), ...u16(6), 0, 0, 0, 0, 0, 0,
+    ...u16(0x20), ...u16(0x41), ...u16(0xffff), 0, 0,
+    ...u16(0x20), ...u16(0x41), ...u16(0xffff),
+    ...u16((2 - 0x20) & 0xffff), ...u16((1 - 0x41) & 0xffff), ...u16(1),
+    0, 0, 0, 0, 0, 0,
+  ]
+  const tables = { cmap, glyf: square, head, hhea, hmtx, loca, maxp }
+
+  const tags = Object.keys(tables)
+  const bytes = [...u32(0x10000), ...u16(tags.length), 0, 0, 0, 0, 0, 0]
+  let offset = 12 + tags.length * 16
+  const data = []
+  for (const tag of tags) {
+    bytes.push(...Buffer.from(tag), 0, 0, 0, 0, ...u32(offset), ...u32(tables[tag].length))
+    data.push(...tables[tag])
+    while (data.length % 4) data.push(0)
+    offset = 12 + tags.length * 16 + data.length
+  }
+  return new Uint8Array([...bytes, ...data])
+}
+
+const SIZE = 16
+
+// Draw into a black target, optionally with a depth attachment, and return its red channel by row
+async function drawAndReadRed(draw, { depth = false } = {}) {
+  const target = device.createTexture({
+    width: SIZE,
+    height: SIZE,
+    format: 'rgba8unorm',
+    usage: GPUTextureUsage.RENDER_ATTACHMENT | GPUTextureUsage.COPY_SRC,
+  })
+  const depthTexture = depth && device.createTexture({
+    width: SIZE,
+    height: SIZE,
+    format: 'depth24plus',
+    usage: GPUTextureUsage.RENDER_ATTACHMENT,
+  })
+  const readback = device.createBuffer({ size: 256 * SIZE, usage: GPUBufferUsage.COPY_DST | GPUBufferUsage.MAP_READ })
+  const encoder = device.createCommandEncoder()
+  const pass = encoder.beginRenderPass({
+    colorAttachments: [{ view: target.createView(), loadOp: 'clear', storeOp: 'store', clearValue: { r: 0, g: 0, b: 0, a: 1 } }],
+    depthStencilAttachment: depth ? { view: depthTexture.createView(), depthLoadOp: 'clear', depthStoreOp: 'store', depthClearValue: 1 } : undefined,
+  })
+  draw(pass)
+  pass.end()
+  encoder.copyTextureToBuffer({ texture: target }, { buffer: readback, bytesPerRow: 256 }, { width: SIZE, height: SIZE })
+  device.queue.submit([encoder.finish()])
+
+  await readback.mapAsync('READ')
+  const bytes = new Uint8Array(readback.getMappedRange())
+  const rows = []
+  for (let y = 0; y < SIZE; y++) {
+    const row = []
+    for (let x = 0; x < SIZE; x++) row.push(bytes[y * 256 + x * 4])
+    rows.push(row)
+  }
+  readback.unmap()
+  return rows
+}
+
+describe('Text: Layout', () => {
+  test('should measure advances and line height', () => {
+    const font = device.createFont(buildFont(), { size: 10 })
+
+    expect(font.size).toBe(10)
+    expect(font.lineHeight).toBe(10)
+    expect(font.measureText('AA A')).toEqual({ width: 21, height: 10 })
+    expect(font.measureText('AA\nA', { size: 20 })).toEqual({ width: 24, height: 40 })
+  })
+})
+
+describe('Text: Drawing', () => {
+  test('should draw glyphs on the baseline', async () => {
+    const font = device.createFont(buildFont(), { size: 10 })
+    // Baseline at y = 8: each "A" covers 5x5 pixels above it, 6 pixels apart
+    const red = await drawAndReadRed((pass) => font.drawText(pass, 'AA', 0, 0))
+
+    expect(red[3].slice(0, 12)).toEqual([255, 255, 255, 255, 255, 0, 255, 255, 255, 255, 255, 0])
+    expect(red[7][0]).toBe(255)
+    expect(red[2][0]).toBe(0)
+    expect(red[8][0]).toBe(0)
+  })
+
+  test('should start new lines and apply colors', async () => {
+    const font = device.createFont(buildFont(), { size: 5 })
+    const red = await drawAndReadRed((pass) => {
+      font.drawText(pass, 'A\n A', 1, 1)
+      font.drawText(pass, 'A', 10, 1, { color: [0, 1, 0] })
+    })
+
+    // Baselines at y = 5 and 10; the space advances 1.5 pixels
+    expect(red[3][1]).toBe(255)
+    expect(red[8][3]).toBe(255)
+    expect(red[8][1]).toBe(0)
+    expect(red[3][10]).toBe(0)
+  })
+
+  test('should draw into passes with a depth attachment', async () => {
+    const font = device.createFont(buildFont(), { size: 10 })
+    const red = await drawAndReadRed((pass) => font.drawText(pass, 'A', 0, 0), { depth: true })
+
+    expect(red[5][2]).toBe(255)
+  })
+})
+
+describe('Text: Validation', () => {
+  test('should reject data that is not a TrueType font', () => {
+    expect(() => device.createFont(new Uint8Array(16))).toThrow(/TrueType/)
+    expect(() => device.createFont(Buffer.from('OTTO\0\0\0\0'))).toThrow(/CFF/)
+    expect(() => device.createFont(buildFont().subarray(0, 130))).toThrow(/truncated/)
+  })
+
+  test('should reject out-of-range sizes', () => {
+    expect(() => device.createFont(buildFont(), { size: 0 })).toThrow(/between 1 and 256/)
+    const font = device.createFont(buildFont())
+    expect(() => font.measureText('A', { size: 1000 })).toThrow(/between 1 and 256/)
+  })
+})
@@ -0,0 +1,145 @@
+/**
+ * Texture Binding Array Tests
+ *
+ * createBindGroup() takes arrays of texture views and samplers for layout
+ * entries with `count`, and leaves elements unbound only with the
+ * 'wgpu-partially-bound-binding-array' feature. Kernels run only on
+ * adapters with the features.
+ */
+
+import { describe, test, expect, beforeAll, afterAll } from 'bun:test'
+import { Gpu, GPUBufferUsage, GPUShaderStage, GPUTextureUsage } from '../webgpu.js'
+
+const PARTIAL = [
+  'wgpu-texture-binding-array',
+  'wgpu-partially-bound-binding-array',
+  'wgpu-sampled-texture-and-storage-buffer-array-non-uniform-indexing'
+]
+
+let adapter
+let device
+let arrays
+let partial
+
+// Each invocation loads the texel of one bound texture, indexed per invocation
+const SHADER = `
+  @group(0) @binding(0) var textures: binding_array<texture_2d<f32>, 4>;
+  @group(0) @binding(1) var<storage, read_write> output: array<f32, 2>;
+
+  @compute @workgroup_size(1)
+  fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    output[id.x] = textureLoad(textures[id.x], vec2<i32>(0, 0), 0).r;
+  }
+`
+
+beforeAll(async () => {
+  adapter = await Gpu().requestAdapter()
+  arrays = adapter.features.includes('wgpu-texture-binding-array')
+  partial = PARTIAL.every((feature) => adapter.features.includes(feature))
+  device = await adapter.requestDevice({ requiredFeatures: partial ? PARTIAL : arrays ? ['wgpu-texture-binding-array'] : [] })
+})
+
+afterAll(() => {
+  device?.destroy()
+})
+
+function createLayout(count) {
+  return device.createBindGroupLayout({
+    entries: [
+      { binding: 0, visibility: GPUShaderStage.COMPUTE, texture: { sampleType: 'unfilterable-float' }, count },
+      { binding: 1, visibility: GPUShaderStage.COMPUTE, buffer: { type: 'storage' } }
+    ]
+  })
+}
+
+function createView(value) {
+  return device
+    .createTexture({
+      size: [1, 1],
+      format: 'r32float',
+      usage: GPUTextureUsage.TEXTURE_BINDING,
+      data: new Float32Array([value])
+    })
+    .createView()
+}
+
+describe('Texture Binding Arrays: Kernels', () => {
+  test('should sample a partially bound array', async () => {
+    if (!partial) return
+
+    const output = device.createBuffer({ size: 8, usage: GPUBufferUsage.STORAGE | GPUBufferUsage.COPY_SRC })
+    const layout = createLayout(4)
+    const pipeline = device.createComputePipeline({
+      layout: device.createPipelineLayout({ bindGroupLayouts: [layout] }),
+      compute: { module: device.createShaderModule({ code: SHADER }), entryPoint: 'main' }
+    })
+    const bindGroup = device.createBindGroup({
+      layout,
+      entries: [
+        { binding: 0, resource: [createView(1.5), createView(2.5)] },
+        { binding: 1, resource: { buffer: output } }
+      ]
+    })
+
+    const encoder = device.createCommandEncoder()
+    const pass = encoder.beginComputePass()
+    pass.setPipeline(pipeline)
+    pass.setBindGroup(0, bindGroup)
+    pass.dispatchWorkgroups(2)
+    pass.end()
+    device.queue.submit([encoder.finish()])
+
+    expect(Array.from(new Float32Array(output.readSync()))).toEqual([1.5, 2.5])
+  })
+})
+
+describe('Texture Binding Arrays: Validation', () => {
+  test('should know the descriptor indexing feature names', async () => {
+    if (partial) return
+    const missing = PARTIAL.filter((feature) => !adapter.features.includes(feature))
+
+    await expect(adapter.requestDevice({ requiredFeatures: missing })).rejects.toThrow(
+      `Adapter doesn't support the required features: ${missing.join(', ')}`
+    )
+  })
+
+  test('should require the feature for texture and sampler arrays', () => {
+    if (arrays) return
+    const layout = device.createBindGroupLayout({
+      entries: [
+        { binding: 0, visibility: GPUShaderStage.FRAGMENT, texture: {} },
+        { binding: 1, visibility: GPUShaderStage.FRAGMENT, sampler: {} }
+      ]
+    })
+    const view = createView(0)
+    const sampler = device.createSampler({})
+
+    expect(() => device.createBindGroup({ layout, entries: [{ binding: 0, resource: [view, view] }] })).toThrow(
+      /binding 0 is a texture array, which needs the 'wgpu-texture-binding-array' feature/
+    )
+    expect(() =>
+      device.createBindGroup({
+        layout,
+        entries: [
+          { binding: 0, resource: view },
+          { binding: 1, resource: [sampler] }
+        ]
+      })
+    ).toThrow(/binding 1 is a sampler array, which needs the 'wgpu-texture-binding-array' feature/)
+  })
+
+  test('should name the feature that leaves array elements unbound', () => {
+    if (!arrays || partial) return
+    const output = device.createBuffer({ size: 8, usage: GPUBufferUsage.STORAGE })
+
+    expect(() =>
+      device.createBindGroup({
+        layout: createLayout(4),
+        entries: [
+          { binding: 0, resource: [createView(1), createView(2)] },
+          { binding: 1, resource: { buffer: output } }
+        ]
+      })
+    ).toThrow(/binding 0 binds 2 of the 4 elements of its array; .* needs the 'wgpu-partially-bound-binding-array' feature/)
+  })
+})
@@ -0,0 +1,154 @@
+/**
+ * Texture Blit Tests
+ *
+ * device.blitTexture() draws one texture into another, stretching it over the
+ * destination, so it rescales and converts between float/normalized formats.
+ */
+
+import { describe, test, expect, beforeAll, afterAll } from 'bun:test'
+import { Gpu, GPUBufferUsage, GPUTextureUsage } from '../webgpu.js'
+
+let device
+
+beforeAll(async () => {
+  const adapter = await Gpu().requestAdapter()
+  device = await adapter.requestDevice()
+})
+
+afterAll(() => {
+  device?.destroy()
+})
+
+function createSource(width, height, format, data) {
+  const texture = device.createTexture({
+    width,
+    height,
+    format,
+    usage: GPUTextureUsage.TEXTURE_BINDING | GPUTextureUsage.COPY_DST
+  })
+  const bytesPerTexel = data.byteLength / (width * height)
+  const upload = device.createBuffer({ size: 256 * height, usage: GPUBufferUsage.COPY_SRC | GPUBufferUsage.COPY_DST })
+  for (let row = 0; row < height; row++) {
+    const rowBytes = width * bytesPerTexel
+    device.queue.writeBuffer(upload, row * 256, new Uint8Array(data.buffer, data.byteOffset + row * rowBytes, rowBytes))
+  }
+  const encoder = device.createCommandEncoder()
+  encoder.copyBufferToTexture({ buffer: upload, bytesPerRow: 256 }, { texture }, { width, height })
+  device.queue.submit([encoder.finish()])
+  return texture
+}
+
+function createDestination(width, height, format = 'rgba8unorm') {
+  return device.createTexture({
+    width,
+    height,
+    format,
+    usage: GPUTextureUsage.RENDER_ATTACHMENT | GPUTextureUsage.COPY_SRC
+  })
+}
+
+async function blitAndRead(source, destination, width, height, options) {
+  const readback = device.createBuffer({ size: 256 * height, usage: GPUBufferUsage.COPY_DST | GPUBufferUsage.MAP_READ })
+  const encoder = device.createCommandEncoder()
+  device.blitTexture(encoder, source, destination, options)
+  encoder.copyTextureToBuffer({ texture: destination }, { buffer: readback, bytesPerRow: 256 }, { width, height })
+  device.queue.submit([encoder.finish()])
+
+  await readback.mapAsync('READ')
+  const bytes = new Uint8Array(readback.getMappedRange())
+  const texels = []
+  for (let row = 0; row < height; row++) {
+    texels.push(...bytes.slice(row * 256, row * 256 + width * 4))
+  }
+  readback.unmap()
+  return texels
+}
+
+describe('Texture Blit: Rescaling', () => {
+  test('should downscale a texture', async () => {
+    const pixels = new Uint8Array(4 * 4 * 4)
+    for (let i = 0; i < 16; i++) pixels.set([200, 100, 50, 255], i * 4)
+    const source = createSource(4, 4, 'rgba8unorm', pixels)
+
+    const texels = await blitAndRead(source, createDestination(2, 2), 2, 2)
+    expect(texels).toEqual([200, 100, 50, 255, 200, 100, 50, 255, 200, 100, 50, 255, 200, 100, 50, 255])
+  })
+
+  test('should filter linearly by default and on request pick the nearest texel', async () => {
+    const source = createSource(2, 1, 'rgba8unorm', new Uint8Array([0, 0, 0, 255, 254, 254, 254, 255]))
+
+    const [linear] = await blitAndRead(source, createDestination(1, 1), 1, 1)
+    expect(Math.abs(linear - 127)).toBeLessThanOrEqual(1)
+
+    const [nearest] = await blitAndRead(source, createDestination(1, 1), 1, 1, { filter: 'nearest' })
+    expect([0, 254]).toContain(nearest)
+  })
+
+  test('should write the requested destination mip level', async () => {
+    const source = createSource(1, 1, 'rgba8unorm', new Uint8Array([10, 20, 30, 40]))
+    const destination = device.createTexture({
+      width: 4,
+      height: 4,
+      format: 'rgba8unorm',
+      mipLevelCount: 3,
+      usage: GPUTextureUsage.RENDER_ATTACHMENT | GPUTextureUsage.COPY_SRC
+    })
+    const readback = device.createBuffer({ size: 4, usage: GPUBufferUsage.COPY_DST | GPUBufferUsage.MAP_READ })
+
+    const encoder = device.createCommandEncoder()
+    device.blitTexture(encoder, source, destination, { destinationMipLevel: 2 })
+    encoder.copyTextureToBuffer({ texture: destination, mipLevel: 2 }, { buffer: readback, bytesPerRow: 256 }, { width: 1, height: 1 })
+    device.queue.submit([encoder.finish()])
+
+    await readback.mapAsync('READ')
+    expect(Array.from(new Uint8Array(readback.getMappedRange()))).toEqual([10, 20, 30, 40])
+    readback.unmap()
+  })
+})
+
+describe('Texture Blit: Format Conversion', () => {
+  test('should convert rgba16float to rgba8unorm', async () => {
+    // 1.0, 0.5, 0.0, 1.0 as half floats
+    const source = createSource(1, 1, 'rgba16float', new Uint16Array([0x3c00, 0x3800, 0x0000, 0x3c00]))
+
+    const texels = await blitAndRead(source, createDestination(1, 1), 1, 1)
+    expect(texels[0]).toBe(255)
+    expect(Math.abs(texels[1] - 128)).toBeLessThanOrEqual(1)
+    expect(texels[2]).toBe(0)
+    expect(texels[3]).toBe(255)
+  })
+
+  test('should encode sRGB destinations', async () => {
+    const source = createSource(1, 1, 'rgba16float', new Uint16Array([0x3800, 0x3800, 0x3800, 0x3c00]))
+
+    const [srgb] = await blitAndRead(source, createDestination(1, 1, 'rgba8unorm-srgb'), 1, 1)
+    // linear 0.5 is 188 in sRGB
+    expect(Math.abs(srgb - 188)).toBeLessThanOrEqual(1)
+  })
+})
+
+describe('Texture Blit: Validation', () => {
+  test('should require TEXTURE_BINDING and RENDER_ATTACHMENT usage', () => {
+    const source = createSource(1, 1, 'rgba8unorm', new Uint8Array(4))
+    const encoder = device.createCommandEncoder()
+    expect(() => device.blitTexture(encoder, createDestination(1, 1), createDestination(1, 1))).toThrow('source needs TEXTURE_BINDING usage')
+    expect(() => device.blitTexture(encoder, source, source)).toThrow('destination needs RENDER_ATTACHMENT usage')
+  })
+
+  test('should reject integer formats and unknown filters', () => {
+    const source = createSource(1, 1, 'rgba8uint', new Uint8Array(4))
+    const encoder = device.createCommandEncoder()
+    expect(() => device.blitTexture(encoder, source, createDestination(1, 1))).toThrow("rgba8uint can't be sampled as float")
+    const color = createSource(1, 1, 'rgba8unorm', new Uint8Array(4))
+    expect(() => device.blitTexture(encoder, color, createDestination(1, 1), { filter: 'cubic' })).toThrow('unknown filter cubic')
+  })
+
+  test('should require nearest filtering for unfilterable sources', async () => {
+    const source = createSource(1, 1, 'r32float', new Float32Array([0.25]))
+    const encoder = device.createCommandEncoder()
+    expect(() => device.blitTexture(encoder, source, createDestination(1, 1))).toThrow("use filter: 'nearest'")
+
+    const [red] = await blitAndRead(source, createDestination(1, 1), 1, 1, { filter: 'nearest' })
+    expect(Math.abs(red - 64)).toBeLessThanOrEqual(1)
+  })
+})
@@ -0,0 +1,100 @@
+/**
+ * Gaussian Blur Tests
+ *
+ * device.createBlur({ radius, sigma }) blurs textures in a horizontal and a
+ * vertical pass; edge texels repeat, so uniform images stay unchanged.
+ */
+
+import { describe, test, expect, beforeAll, afterAll } from 'bun:test'
+import { Gpu, GPUBufferUsage, GPUTextureUsage } from '../webgpu.js'
+
+let device
+
+beforeAll(async () => {
+  const adapter = await Gpu().requestAdapter()
+  device = await adapter.requestDevice()
+})
+
+afterAll(() => {
+  device?.destroy()
+})
+
+const SIZE = 8
+
+function createTexture(usage, format = 'rgba8unorm') {
+  return device.createTexture({ width: SIZE, height: SIZE, format, usage })
+}
+
+function createSource(pixels) {
+  const texture = createTexture(GPUTextureUsage.TEXTURE_BINDING | GPUTextureUsage.COPY_DST)
+  const upload = device.createBuffer({ size: 256 * SIZE, usage: GPUBufferUsage.COPY_SRC | GPUBufferUsage.COPY_DST })
+  for (let row = 0; row < SIZE; row++) {
+    device.queue.writeBuffer(upload, row * 256, pixels.subarray(row * SIZE * 4, (row + 1) * SIZE * 4))
+  }
+  const encoder = device.createCommandEncoder()
+  encoder.copyBufferToTexture({ buffer: upload, bytesPerRow: 256 }, { texture }, { width: SIZE, height: SIZE })
+  device.queue.submit([encoder.finish()])
+  return texture
+}
+
+async function blurAndReadRed(blur, source) {
+  const destination = createTexture(GPUTextureUsage.RENDER_ATTACHMENT | GPUTextureUsage.COPY_SRC)
+  const readback = device.createBuffer({ size: 256 * SIZE, usage: GPUBufferUsage.COPY_DST | GPUBufferUsage.MAP_READ })
+  const encoder = device.createCommandEncoder()
+  blur.encode(encoder, source, destination)
+  encoder.copyTextureToBuffer({ texture: destination }, { buffer: readback, bytesPerRow: 256 }, { width: SIZE, height: SIZE })
+  device.queue.submit([encoder.finish()])
+
+  await readback.mapAsync('READ')
+  const bytes = new Uint8Array(readback.getMappedRange())
+  const red = []
+  for (let y = 0; y < SIZE; y++) {
+    for (let x = 0; x < SIZE; x++) red.push(bytes[y * 256 + x * 4])
+  }
+  readback.unmap()
+  return red
+}
+
+describe('Gaussian Blur: Output', () => {
+  test('should keep uniform images unchanged', async () => {
+    const pixels = new Uint8Array(SIZE * SIZE * 4).fill(200)
+    const red = await blurAndReadRed(device.createBlur({ radius: 3 }), createSource(pixels))
+    for (const value of red) expect(Math.abs(value - 200)).toBeLessThanOrEqual(1)
+  })
+
+  test('should spread a point symmetrically and preserve its energy', async () => {
+    const pixels = new Uint8Array(SIZE * SIZE * 4)
+    pixels[(4 * SIZE + 4) * 4] = 255
+    const red = await blurAndReadRed(device.createBlur({ radius: 2, sigma: 1 }), createSource(pixels))
+
+    const at = (x, y) => red[y * SIZE + x]
+    expect(at(4, 4)).toBeLessThan(255)
+    expect(at(4, 4)).toBeGreaterThan(at(5, 4))
+    expect(at(3, 4)).toBe(at(5, 4))
+    expect(at(4, 3)).toBe(at(4, 5))
+    expect(at(7, 4)).toBe(0)
+    const total = red.reduce((sum, value) => sum + value, 0)
+    expect(Math.abs(total - 255)).toBeLessThanOrEqual(20)
+  })
+})
+
+describe('Gaussian Blur: Parameters', () => {
+  test('should derive the radius from sigma and sigma from the radius', () => {
+    expect(device.createBlur({ sigma: 2 }).radius).toBe(6)
+    expect(device.createBlur({ radius: 8 }).sigma).toBe(4)
+    const blur = device.createBlur()
+    expect(blur.radius).toBe(4)
+    expect(blur.sigma).toBe(2)
+  })
+
+  test('should reject invalid parameters and textures', () => {
+    expect(() => device.createBlur({ radius: 64 })).toThrow('Blur radius 64 is too large')
+    expect(() => device.createBlur({ sigma: 0 })).toThrow('Blur sigma must be positive')
+
+    const blur = device.createBlur()
+    const source = createTexture(GPUTextureUsage.TEXTURE_BINDING)
+    const small = device.createTexture({ width: 4, height: 4, format: 'rgba8unorm', usage: GPUTextureUsage.RENDER_ATTACHMENT })
+    expect(() => blur.run(source, small)).toThrow('must be the same size')
+    expect(() => blur.run(source, source)).toThrow('needs RENDER_ATTACHMENT usage')
+  })
+})
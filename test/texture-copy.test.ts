@@ -0,0 +1,164 @@
+/**
+ * Texture Copy Tests
+ *
+ * Tests for spec-shaped (GPUImageCopyBuffer / GPUImageCopyTexture / GPUExtent3D)
+ * copyBufferToTexture, copyTextureToBuffer and copyTextureToTexture calls,
+ * and depth/stencil aspect selection.
+ */
+
+import { describe, test, expect, beforeAll, afterAll } from 'bun:test'
+import { Gpu, GPUBufferUsage, GPUTextureUsage } from '../webgpu.js'
+
+let device
+let backend
+
+beforeAll(async () => {
+  const adapter = await Gpu().requestAdapter()
+  backend = adapter.info.backend
+  device = await adapter.requestDevice()
+})
+
+afterAll(() => {
+  device?.destroy()
+})
+
+function createTexture(size) {
+  return device.createTexture({
+    width: size,
+    height: size,
+    format: 'rgba8unorm',
+    usage: GPUTextureUsage.COPY_SRC | GPUTextureUsage.COPY_DST
+  })
+}
+
+async function readBuffer(buffer) {
+  await buffer.mapAsync('READ')
+  const bytes = Array.from(new Uint8Array(buffer.getMappedRange()))
+  buffer.unmap()
+  return bytes
+}
+
+describe('Texture Copy: Dictionary Signatures', () => {
+  test('should round-trip a texel through buffer and texture', async () => {
+    const texture = createTexture(4)
+    const upload = device.createBuffer({ size: 256 + 4, usage: GPUBufferUsage.COPY_SRC | GPUBufferUsage.COPY_DST })
+    device.queue.writeBuffer(upload, 256, new Uint8Array([10, 20, 30, 40]))
+    const readback = device.createBuffer({ size: 4, usage: GPUBufferUsage.COPY_DST | GPUBufferUsage.MAP_READ })
+
+    const encoder = device.createCommandEncoder()
+    encoder.copyBufferToTexture(
+      { buffer: upload, offset: 256, bytesPerRow: 256 },
+      { texture, origin: { x: 2, y: 1 } },
+      { width: 1, height: 1 }
+    )
+    encoder.copyTextureToBuffer(
+      { texture, mipLevel: 0, origin: [2, 1, 0] },
+      { buffer: readback, bytesPerRow: 256 },
+      [1, 1, 1]
+    )
+    device.queue.submit([encoder.finish()])
+
+    expect(await readBuffer(readback)).toEqual([10, 20, 30, 40])
+  })
+
+  test('should keep accepting positional arguments', async () => {
+    const texture = createTexture(4)
+    const upload = device.createBuffer({ size: 4, usage: GPUBufferUsage.COPY_SRC | GPUBufferUsage.COPY_DST })
+    device.queue.writeBuffer(upload, 0, new Uint8Array([1, 2, 3, 4]))
+    const readback = device.createBuffer({ size: 4, usage: GPUBufferUsage.COPY_DST | GPUBufferUsage.MAP_READ })
+
+    const encoder = device.createCommandEncoder()
+    encoder.copyBufferToTexture(upload, 0, 256, null, texture, 0, 3, 3, 0, 1, 1, 1)
+    encoder.copyTextureToBuffer(texture, 0, 3, 3, 0, readback, 0, 256, null, 1, 1, 1)
+    device.queue.submit([encoder.finish()])
+
+    expect(await readBuffer(readback)).toEqual([1, 2, 3, 4])
+  })
+})
+
+describe('Texture Copy: copyTextureToTexture', () => {
+  test('should copy a region between textures', async () => {
+    const source = createTexture(4)
+    const destination = createTexture(4)
+    const upload = device.createBuffer({ size: 4, usage: GPUBufferUsage.COPY_SRC | GPUBufferUsage.COPY_DST })
+    device.queue.writeBuffer(upload, 0, new Uint8Array([5, 6, 7, 8]))
+    const readback = device.createBuffer({ size: 4, usage: GPUBufferUsage.COPY_DST | GPUBufferUsage.MAP_READ })
+
+    const encoder = device.createCommandEncoder()
+    encoder.copyBufferToTexture({ buffer: upload, bytesPerRow: 256 }, { texture: source }, [1, 1])
+    encoder.copyTextureToTexture(
+      { texture: source, origin: { x: 0, y: 0 } },
+      { texture: destination, origin: { x: 1, y: 2 } },
+      { width: 1, height: 1 }
+    )
+    encoder.copyTextureToBuffer({ texture: destination, origin: { x: 1, y: 2 } }, { buffer: readback, bytesPerRow: 256 }, [1])
+    device.queue.submit([encoder.finish()])
+
+    expect(await readBuffer(readback)).toEqual([5, 6, 7, 8])
+  })
+})
+
+describe('Texture Copy: Aspects', () => {
+  function createDepthStencil() {
+    return device.createTexture({
+      width: 4,
+      height: 4,
+      format: 'depth24plus-stencil8',
+      usage: GPUTextureUsage.RENDER_ATTACHMENT | GPUTextureUsage.TEXTURE_BINDING | GPUTextureUsage.COPY_SRC
+    })
+  }
+
+  test('should read back the stencil aspect', async () => {
+    // GL lacks the DEPTH_TEXTURE_AND_BUFFER_COPIES downlevel flag
+    if (backend === 'Gl') return
+
+    const texture = createDepthStencil()
+    const readback = device.createBuffer({ size: 256 * 4, usage: GPUBufferUsage.COPY_DST | GPUBufferUsage.MAP_READ })
+
+    const encoder = device.createCommandEncoder()
+    const pass = encoder.beginRenderPass({
+      depthStencilAttachment: {
+        view: texture.createView(),
+        depthLoadOp: 'clear',
+        depthStoreOp: 'store',
+        depthClearValue: 1.0,
+        stencilLoadOp: 'clear',
+        stencilStoreOp: 'store',
+        stencilClearValue: 7
+      }
+    })
+    pass.end()
+    encoder.copyTextureToBuffer(
+      { texture, aspect: 'stencil-only' },
+      { buffer: readback, bytesPerRow: 256 },
+      [4, 4]
+    )
+    device.queue.submit([encoder.finish()])
+
+    expect((await readBuffer(readback)).slice(0, 4)).toEqual([7, 7, 7, 7])
+  })
+
+  test('should bind the depth aspect of a depth-stencil texture', async () => {
+    const module = device.createShaderModule({
+      code: `
+        @group(0) @binding(0) var depth: texture_depth_2d;
+
+        @fragment
+        fn fs_main(@builtin(position) position: vec4<f32>) -> @location(0) vec4<f32> {
+          return vec4<f32>(textureLoad(depth, vec2<i32>(position.xy), 0));
+        }
+      `
+    })
+    const layout = device.createBindGroupLayoutFromShader(module, 0)
+    const texture = createDepthStencil()
+
+    device.pushErrorScope('validation')
+    device.createBindGroup({ layout, entries: [{ binding: 0, resource: texture.createView({ aspect: 'depth-only' }) }] })
+    expect(await device.popErrorScope()).toBeNull()
+
+    // Combined depth-stencil views can't be sampled
+    device.pushErrorScope('validation')
+    device.createBindGroup({ layout, entries: [{ binding: 0, resource: texture.createView() }] })
+    expect(await device.popErrorScope()).not.toBeNull()
+  })
+})
@@ -0,0 +1,91 @@
+/**
+ * Texture Format Info Tests
+ *
+ * getTextureFormatInfo is a pure lookup; no device is needed.
+ * adapter.getTextureFormatFeatures reports what a particular adapter supports.
+ */
+
+import { describe, test, expect } from 'bun:test'
+import { Gpu, GPUTextureUsage, getTextureFormatInfo } from '../webgpu.js'
+
+describe('Texture Format Info: Block Sizes', () => {
+  test('should describe an uncompressed color format', () => {
+    const info = getTextureFormatInfo('rgba8unorm')
+    expect(info.blockWidth).toBe(1)
+    expect(info.blockHeight).toBe(1)
+    expect(info.bytesPerBlock).toBe(4)
+    expect(info.bytesPerTexel).toBe(4)
+    expect(info.aspects).toEqual(['color'])
+    expect(info.compressed).toBe(false)
+    expect(info.sampleType).toBe('float')
+    expect(info.filterable).toBe(true)
+    expect(info.renderable).toBe(true)
+    expect(info.storage).toBe(true)
+  })
+
+  test('should describe packed formats', () => {
+    expect(getTextureFormatInfo('rgb10a2unorm').bytesPerTexel).toBe(4)
+    expect(getTextureFormatInfo('rg11b10ufloat').bytesPerTexel).toBe(4)
+    expect(getTextureFormatInfo('rgb9e5ufloat').renderable).toBe(false)
+  })
+
+  test('should describe block-compressed formats', () => {
+    const bc1 = getTextureFormatInfo('bc1-rgba-unorm-srgb')
+    expect([bc1.blockWidth, bc1.blockHeight, bc1.bytesPerBlock]).toEqual([4, 4, 8])
+    expect(bc1.bytesPerTexel).toBeUndefined()
+    expect(bc1.compressed).toBe(true)
+    expect(bc1.srgb).toBe(true)
+    expect(bc1.requiredFeature).toBe('texture-compression-bc')
+
+    const astc = getTextureFormatInfo('astc-10x6-unorm')
+    expect([astc.blockWidth, astc.blockHeight, astc.bytesPerBlock]).toEqual([10, 6, 16])
+    expect(astc.format).toBe('astc-10x6-unorm')
+    expect(astc.requiredFeature).toBe('texture-compression-astc')
+  })
+})
+
+describe('Texture Format Info: Capabilities', () => {
+  test('should describe depth and stencil aspects', () => {
+    const info = getTextureFormatInfo('depth24plus-stencil8')
+    expect(info.aspects).toEqual(['depth', 'stencil'])
+    expect(info.bytesPerBlock).toBeUndefined()
+    expect(info.sampleType).toBe('depth')
+    expect(info.filterable).toBe(false)
+    expect(getTextureFormatInfo('depth32float').bytesPerBlock).toBe(4)
+  })
+
+  test('should report unfilterable and integer formats', () => {
+    expect(getTextureFormatInfo('r32float').sampleType).toBe('unfilterable-float')
+    expect(getTextureFormatInfo('r32float').filterable).toBe(false)
+    expect(getTextureFormatInfo('rg16uint').sampleType).toBe('uint')
+    expect(getTextureFormatInfo('rgba8unorm-srgb').storage).toBe(false)
+  })
+
+  test('should reject unknown formats', () => {
+    expect(() => getTextureFormatInfo('rgba9unorm')).toThrow('Unknown texture format')
+    expect(() => getTextureFormatInfo('astc-3x3-unorm')).toThrow('Unknown texture format')
+  })
+})
+
+describe('Texture Format Info: Adapter Features', () => {
+  test('should report what the adapter supports', async () => {
+    const adapter = await Gpu().requestAdapter()
+    const features = adapter.getTextureFormatFeatures('rgba8unorm')
+    expect(features.format).toBe('rgba8unorm')
+    expect(features.supported).toBe(true)
+    expect(features.renderable).toBe(true)
+    expect(features.usage & GPUTextureUsage.TEXTURE_BINDING).toBe(GPUTextureUsage.TEXTURE_BINDING)
+    expect(features.filterable).toBe(true)
+    expect(features.blendable).toBe(true)
+    expect(features.sampleCounts).toContain(1)
+    expect(features.sampleCounts).toContain(4)
+  })
+
+  test('should report unfilterable and unblendable formats', async () => {
+    const adapter = await Gpu().requestAdapter()
+    const features = adapter.getTextureFormatFeatures('r32uint')
+    expect(features.filterable).toBe(false)
+    expect(features.blendable).toBe(false)
+    expect(() => adapter.getTextureFormatFeatures('rgba9000')).toThrow('Unknown texture format: rgba9000')
+  })
+})
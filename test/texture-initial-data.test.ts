@@ -0,0 +1,83 @@
+/**
+ * Texture Initial Data Tests
+ *
+ * createTexture({ data }) uploads the contents right after creation; the
+ * texels are read back through a copy to check where they landed.
+ */
+
+import { describe, test, expect, beforeAll, afterAll } from 'bun:test'
+import { Gpu, GPUBufferUsage, GPUTextureUsage } from '../webgpu.js'
+
+let device
+
+beforeAll(async () => {
+  const adapter = await Gpu().requestAdapter()
+  device = await adapter.requestDevice()
+})
+
+afterAll(() => {
+  device?.destroy()
+})
+
+// Read the first `width` RGBA8 texels of row 0 of a layer and mip level
+async function readRow(texture, width, { mipLevel = 0, layer = 0 } = {}) {
+  const readback = device.createBuffer({ size: 256, usage: GPUBufferUsage.COPY_DST | GPUBufferUsage.MAP_READ })
+  const encoder = device.createCommandEncoder()
+  encoder.copyTextureToBuffer({ texture, mipLevel, origin: [0, 0, layer] }, { buffer: readback, bytesPerRow: 256 }, [width, 1])
+  device.queue.submit([encoder.finish()])
+  await readback.mapAsync('READ')
+  const bytes = Array.from(new Uint8Array(readback.getMappedRange()).subarray(0, width * 4))
+  readback.unmap()
+  return bytes
+}
+
+describe('Texture Initial Data: Uploads', () => {
+  test('should upload tightly packed mip level 0', async () => {
+    const data = new Uint8Array(2 * 2 * 4).map((_, i) => i)
+    const texture = device.createTexture({ size: [2, 2], format: 'rgba8unorm', usage: GPUTextureUsage.COPY_SRC, data })
+
+    expect(await readRow(texture, 2)).toEqual([0, 1, 2, 3, 4, 5, 6, 7])
+    expect(texture.usage & GPUTextureUsage.COPY_DST).toBe(GPUTextureUsage.COPY_DST)
+  })
+
+  test('should upload every mip level of every layer', async () => {
+    // Per layer: a 2x2 mip level 0 filled with 10 * layer + 1, then a 1x1 level filled with 10 * layer + 2
+    const data = new Uint8Array([
+      ...new Array(16).fill(1), ...new Array(4).fill(2),
+      ...new Array(16).fill(11), ...new Array(4).fill(12),
+    ])
+    const texture = device.createTexture({
+      size: [2, 2, 2],
+      format: 'rgba8unorm',
+      mipLevelCount: 2,
+      usage: GPUTextureUsage.COPY_SRC,
+      data,
+    })
+
+    expect(await readRow(texture, 1, { layer: 1 })).toEqual([11, 11, 11, 11])
+    expect(await readRow(texture, 1, { mipLevel: 1 })).toEqual([2, 2, 2, 2])
+    expect(await readRow(texture, 1, { mipLevel: 1, layer: 1 })).toEqual([12, 12, 12, 12])
+  })
+
+  test('should upload padded rows with a data layout', async () => {
+    const data = new Uint8Array(4 + 2 * 12)
+    data.set([1, 2, 3, 4, 5, 6, 7, 8], 4)
+    const texture = device.createTexture({
+      width: 2,
+      height: 2,
+      format: 'rgba8unorm',
+      usage: GPUTextureUsage.COPY_SRC,
+      data,
+      dataLayout: { offset: 4, bytesPerRow: 12 },
+    })
+
+    expect(await readRow(texture, 2)).toEqual([1, 2, 3, 4, 5, 6, 7, 8])
+  })
+})
+
+describe('Texture Initial Data: Validation', () => {
+  test('should reject data of the wrong length', () => {
+    expect(() => device.createTexture({ size: [2, 2], format: 'rgba8unorm', usage: GPUTextureUsage.COPY_SRC, data: new Uint8Array(12) }))
+      .toThrow(/expected 16 for mip level 0/)
+  })
+})
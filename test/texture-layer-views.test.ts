@@ -0,0 +1,98 @@
+/**
+ * Layer and Mip View Tests
+ *
+ * Clears single layers / mip levels through createLayerView and createMipView
+ * render targets and reads the texels back.
+ */
+
+import { describe, test, expect, beforeAll, afterAll } from 'bun:test'
+import { Gpu, GPUBufferUsage, GPUTextureUsage } from '../webgpu.js'
+
+let device
+
+beforeAll(async () => {
+  const adapter = await Gpu().requestAdapter()
+  device = await adapter.requestDevice()
+})
+
+afterAll(() => {
+  device?.destroy()
+})
+
+function createTexture(options) {
+  return device.createTexture({
+    width: 4,
+    height: 4,
+    format: 'rgba8unorm',
+    usage: GPUTextureUsage.RENDER_ATTACHMENT | GPUTextureUsage.COPY_SRC,
+    ...options
+  })
+}
+
+function clear(encoder, view, r) {
+  encoder.beginRenderPass({
+    colorAttachments: [{ view, loadOp: 'clear', storeOp: 'store', clearValue: { r, g: 0, b: 0, a: 1 } }]
+  }).end()
+}
+
+async function readRed(texture, mipLevel, layer) {
+  const readback = device.createBuffer({ size: 4, usage: GPUBufferUsage.COPY_DST | GPUBufferUsage.MAP_READ })
+  const encoder = device.createCommandEncoder()
+  encoder.copyTextureToBuffer(
+    { texture, mipLevel, origin: [0, 0, layer] },
+    { buffer: readback, bytesPerRow: 256 },
+    [1, 1, 1]
+  )
+  device.queue.submit([encoder.finish()])
+  await readback.mapAsync('READ')
+  const red = new Uint8Array(readback.getMappedRange())[0]
+  readback.unmap()
+  return red
+}
+
+describe('Texture Views: createLayerView', () => {
+  test('should render into a single array layer', async () => {
+    const texture = createTexture({ depth: 3 })
+    const encoder = device.createCommandEncoder()
+    clear(encoder, texture.createLayerView(0), 0)
+    clear(encoder, texture.createLayerView(1), 1)
+    clear(encoder, texture.createLayerView(2), 0)
+    device.queue.submit([encoder.finish()])
+
+    expect(await readRed(texture, 0, 0)).toBe(0)
+    expect(await readRed(texture, 0, 1)).toBe(255)
+    expect(await readRed(texture, 0, 2)).toBe(0)
+  })
+
+  test('should select the mip level of a layer', async () => {
+    const texture = createTexture({ depth: 2, mipLevelCount: 2 })
+    const encoder = device.createCommandEncoder()
+    clear(encoder, texture.createLayerView(1, { mipLevel: 1 }), 1)
+    device.queue.submit([encoder.finish()])
+
+    expect(await readRed(texture, 1, 1)).toBe(255)
+  })
+
+  test('should reject out-of-range layers and mip levels', () => {
+    const texture = createTexture({ depth: 2 })
+    expect(() => texture.createLayerView(2)).toThrow('out of range')
+    expect(() => texture.createLayerView(0, { mipLevel: 1 })).toThrow('mip level 1 is out of range')
+  })
+})
+
+describe('Texture Views: createMipView', () => {
+  test('should render into a single mip level', async () => {
+    const texture = createTexture({ mipLevelCount: 3 })
+    const encoder = device.createCommandEncoder()
+    clear(encoder, texture.createMipView(0), 0)
+    clear(encoder, texture.createMipView(2), 1)
+    device.queue.submit([encoder.finish()])
+
+    expect(await readRed(texture, 0, 0)).toBe(0)
+    expect(await readRed(texture, 2, 0)).toBe(255)
+  })
+
+  test('should reject out-of-range mip levels', () => {
+    expect(() => createTexture({}).createMipView(1)).toThrow('out of range')
+  })
+})
@@ -0,0 +1,138 @@
+/**
+ * Texture Snapshot Tests
+ *
+ * texture.readPixels() reads RGBA8 and BGRA8 textures back, and
+ * expectTextureMatches() compares them with reference PNG files.
+ */
+
+import { describe, test, expect, beforeAll, afterAll } from 'bun:test'
+import { existsSync, mkdtempSync, readFileSync, writeFileSync } from 'fs'
+import { tmpdir } from 'os'
+import { join } from 'path'
+import { deflateSync } from 'zlib'
+import { Gpu, GPUTextureUsage, expectTextureMatches } from '../webgpu.js'
+
+let device
+let directory
+
+beforeAll(async () => {
+  const adapter = await Gpu().requestAdapter()
+  device = await adapter.requestDevice()
+  directory = mkdtempSync(join(tmpdir(), 'webgpu-snapshot-'))
+})
+
+afterAll(() => {
+  device?.destroy()
+})
+
+// 2x2 texture: red, green, blue and white
+const PIXELS = [255, 0, 0, 255, 0, 255, 0, 255, 0, 0, 255, 255, 255, 255, 255, 255]
+
+function createTexture(pixels, format = 'rgba8unorm') {
+  return device.createTexture({
+    size: [2, 2],
+    format,
+    usage: GPUTextureUsage.COPY_SRC | GPUTextureUsage.TEXTURE_BINDING,
+    data: new Uint8Array(pixels)
+  })
+}
+
+// PNG with the given IHDR color type and one filter byte per row
+function handmadePng(width, height, colorType, rows) {
+  const chunk = (type, data) => {
+    const length = Buffer.alloc(4)
+    length.writeUInt32BE(data.length)
+    // The decoder doesn't check CRCs
+    return Buffer.concat([length, Buffer.from(type, 'latin1'), data, Buffer.alloc(4)])
+  }
+  const header = Buffer.alloc(13)
+  header.writeUInt32BE(width, 0)
+  header.writeUInt32BE(height, 4)
+  header.set([8, colorType, 0, 0, 0], 8)
+  return Buffer.concat([
+    Buffer.from([0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a]),
+    chunk('IHDR', header),
+    chunk('IDAT', deflateSync(Buffer.from(rows.flat()))),
+    chunk('IEND', Buffer.alloc(0))
+  ])
+}
+
+describe('Texture Snapshot: readPixels', () => {
+  test('should read RGBA8 pixels', async () => {
+    const pixels = await createTexture(PIXELS).readPixels()
+    expect(Array.from(pixels)).toEqual(PIXELS)
+  })
+
+  test('should swizzle BGRA8 pixels to RGBA', async () => {
+    const pixels = await createTexture([10, 20, 30, 40, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0], 'bgra8unorm').readPixels()
+    expect(Array.from(pixels.subarray(0, 4))).toEqual([30, 20, 10, 40])
+  })
+
+  test('should reject other formats', async () => {
+    const texture = device.createTexture({ size: [2, 2], format: 'r32float', usage: GPUTextureUsage.COPY_SRC })
+    await expect(texture.readPixels()).rejects.toThrow(/got r32float/)
+  })
+})
+
+describe('Texture Snapshot: expectTextureMatches', () => {
+  test('should record a missing reference and then match it', async () => {
+    const reference = join(directory, 'record.png')
+    await expectTextureMatches(createTexture(PIXELS), reference)
+
+    expect(existsSync(reference)).toBe(true)
+    expect(await expectTextureMatches(createTexture(PIXELS), reference)).toEqual({ diffPixels: 0, diffPercent: 0 })
+  })
+
+  test('should write the actual and diff images on failure', async () => {
+    const reference = join(directory, 'mismatch.png')
+    await expectTextureMatches(createTexture(PIXELS), reference)
+    const changed = [...PIXELS]
+    changed[0] = 200
+
+    await expect(expectTextureMatches(createTexture(changed), reference)).rejects.toThrow(
+      /1 of 4 pixels \(25\.00%\) differ by more than 0/
+    )
+    expect(existsSync(join(directory, 'mismatch.actual.png'))).toBe(true)
+
+    // The diff image marks the changed pixel in red
+    const diffReference = join(directory, 'diff-copy.png')
+    writeFileSync(diffReference, readFileSync(join(directory, 'mismatch.diff.png')))
+    const red = [255, 0, 0, 255, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]
+    const result = await expectTextureMatches(createTexture(red), diffReference, { maxDiffPercent: 75 })
+    expect(result.diffPixels).toBe(3)
+  })
+
+  test('should allow differences within the tolerances', async () => {
+    const reference = join(directory, 'tolerance.png')
+    await expectTextureMatches(createTexture(PIXELS), reference)
+    const changed = [...PIXELS]
+    changed[0] = 253
+    changed[4] = 100
+
+    expect(await expectTextureMatches(createTexture(changed), reference, { perChannelTolerance: 2, maxDiffPercent: 25 })).toEqual({
+      diffPixels: 1,
+      diffPercent: 25
+    })
+  })
+
+  test('should reject references of another size', async () => {
+    const reference = join(directory, 'size.png')
+    writeFileSync(reference, handmadePng(1, 1, 6, [[0, 0, 0, 0, 255]]))
+
+    await expect(expectTextureMatches(createTexture(PIXELS), reference)).rejects.toThrow(/Texture is 2x2 but .* is 1x1/)
+  })
+
+  test('should read filtered RGB references', async () => {
+    const reference = join(directory, 'filtered.png')
+    // Sub filter on the first row, Up filter on the second
+    writeFileSync(
+      reference,
+      handmadePng(2, 2, 2, [
+        [1, 255, 0, 0, 1, 255, 0],
+        [2, 1, 0, 255, 255, 0, 255]
+      ])
+    )
+
+    expect((await expectTextureMatches(createTexture(PIXELS), reference)).diffPixels).toBe(0)
+  })
+})
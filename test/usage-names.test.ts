@@ -0,0 +1,55 @@
+/**
+ * Usage Flag Name Tests
+ *
+ * buffer.usageNames() and texture.usageNames() decode usage bit masks, and
+ * descriptors accept arrays of flag names in place of the masks.
+ */
+
+import { describe, test, expect, beforeAll, afterAll } from 'bun:test'
+import { Gpu, GPUBufferUsage, GPUTextureUsage } from '../webgpu.js'
+
+let device
+
+beforeAll(async () => {
+  const adapter = await Gpu().requestAdapter()
+  device = await adapter.requestDevice()
+})
+
+afterAll(() => {
+  device?.destroy()
+})
+
+describe('Usage Names: Decoding', () => {
+  test('should decode buffer usage in bit order', () => {
+    const buffer = device.createBuffer({ size: 16, usage: GPUBufferUsage.STORAGE | GPUBufferUsage.COPY_DST })
+
+    expect(buffer.usageNames()).toEqual(['COPY_DST', 'STORAGE'])
+  })
+
+  test('should decode texture usage in bit order', () => {
+    const texture = device.createTexture({
+      size: [4, 4],
+      format: 'rgba8unorm',
+      usage: GPUTextureUsage.RENDER_ATTACHMENT | GPUTextureUsage.TEXTURE_BINDING,
+    })
+
+    expect(texture.usageNames()).toEqual(['TEXTURE_BINDING', 'RENDER_ATTACHMENT'])
+  })
+})
+
+describe('Usage Names: Descriptors', () => {
+  test('should accept flag names for buffers and textures', () => {
+    const buffer = device.createBuffer({ size: 16, usage: ['MAP_READ', 'COPY_DST'] })
+    const init = device.createBufferInit({ usage: ['VERTEX'], data: new Float32Array(4) })
+    const texture = device.createTexture({ size: [4, 4], format: 'rgba8unorm', usage: ['COPY_DST', 'TEXTURE_BINDING'] })
+
+    expect(buffer.usage).toBe(GPUBufferUsage.MAP_READ | GPUBufferUsage.COPY_DST)
+    expect(init.usage).toBe(GPUBufferUsage.VERTEX)
+    expect(texture.usage).toBe(GPUTextureUsage.COPY_DST | GPUTextureUsage.TEXTURE_BINDING)
+  })
+
+  test('should reject unknown flag names', () => {
+    expect(() => device.createBuffer({ size: 16, usage: ['STORAGE', 'SAMPLED'] })).toThrow(/Unknown buffer usage: SAMPLED/)
+    expect(() => device.createTexture({ size: [4, 4], format: 'rgba8unorm', usage: ['STORAGE'] })).toThrow(/Unknown texture usage: STORAGE/)
+  })
+})
@@ -0,0 +1,119 @@
+/**
+ * Validation Error Tests
+ *
+ * wgpu validation errors are thrown from the call that caused them, naming
+ * the method, resource labels and descriptor values.
+ */
+
+import { describe, test, expect, beforeAll, afterAll } from 'bun:test'
+import { Gpu, GPUBufferUsage, GPUTextureUsage, GPUValidationError } from '../webgpu.js'
+
+let device
+
+beforeAll(async () => {
+  const adapter = await Gpu().requestAdapter()
+  device = await adapter.requestDevice()
+})
+
+afterAll(() => {
+  device?.destroy()
+})
+
+function thrownMessage(fn) {
+  try {
+    fn()
+  } catch (error) {
+    return error.message
+  }
+  return null
+}
+
+describe('Validation Errors: Resource Creation', () => {
+  test('should name the method, label and descriptor values', () => {
+    const message = thrownMessage(() => device.createBuffer({
+      label: 'vertices',
+      size: 6,
+      usage: GPUBufferUsage.VERTEX,
+      mappedAtCreation: true
+    }))
+
+    expect(message).toContain("createBuffer(label: 'vertices', size: 6")
+    expect(message).toContain('mappedAtCreation: true): validation failed')
+    expect(message).toContain('COPY_BUFFER_ALIGNMENT')
+  })
+
+  test('should include the shader parse error', () => {
+    const message = thrownMessage(() => device.createShaderModule({ label: 'broken', code: 'fn main( {' }))
+
+    expect(message).toContain("createShaderModule(label: 'broken'): validation failed")
+    expect(message).toContain('expected identifier')
+  })
+})
+
+describe('Validation Errors: Commands', () => {
+  test('should name the buffers of a failed copy', () => {
+    const source = device.createBuffer({ label: 'src', size: 16, usage: GPUBufferUsage.COPY_SRC })
+    const destination = device.createBuffer({ label: 'dst', size: 8, usage: GPUBufferUsage.COPY_DST })
+    const encoder = device.createCommandEncoder()
+
+    const message = thrownMessage(() => encoder.copyBufferToBuffer(source, 0, destination, 0, 16))
+    expect(message).toContain("copyBufferToBuffer(source: 'src', sourceOffset: 0, destination: 'dst'")
+    expect(message).toContain('overrunning the bounds')
+  })
+
+  test('should throw pass errors from end()', () => {
+    const target = device.createTexture({
+      width: 4,
+      height: 4,
+      format: 'rgba8unorm',
+      usage: GPUTextureUsage.RENDER_ATTACHMENT
+    })
+    const encoder = device.createCommandEncoder()
+    const pass = encoder.beginRenderPass({
+      colorAttachments: [{ view: target.createView(), loadOp: 'clear', storeOp: 'store' }]
+    })
+    // No pipeline set
+    pass.draw(3)
+
+    expect(thrownMessage(() => pass.end())).toContain('end: validation failed')
+  })
+
+  test('should name the buffer of a failed writeBuffer', () => {
+    const buffer = device.createBuffer({ label: 'readback', size: 8, usage: GPUBufferUsage.MAP_READ })
+
+    const message = thrownMessage(() => device.queue.writeBuffer(buffer, 0, new Uint32Array(2)))
+    expect(message).toContain("writeBuffer(buffer: 'readback', bufferOffset: 0, size: 8)")
+    expect(message).toContain('COPY_DST')
+  })
+})
+
+describe('Validation Errors: Error Scopes', () => {
+  test('should leave errors to an open error scope', async () => {
+    device.pushErrorScope('validation')
+    const message = thrownMessage(() => device.createBuffer({
+      size: 6,
+      usage: GPUBufferUsage.VERTEX,
+      mappedAtCreation: true
+    }))
+    const error = await device.popErrorScope()
+
+    expect(message).toBeNull()
+    expect(error).toBeInstanceOf(GPUValidationError)
+    expect(error.message).toContain('COPY_BUFFER_ALIGNMENT')
+  })
+
+  test('should reject popping with no scope open', async () => {
+    await expect(device.popErrorScope()).rejects.toThrow('no error scope to pop')
+
+    device.pushErrorScope('validation')
+    expect(await device.popErrorScope()).toBeNull()
+  })
+
+  test('should throw again once the scope is popped', () => {
+    expect(thrownMessage(() => device.createBuffer({
+      size: 6,
+      usage: GPUBufferUsage.VERTEX,
+      mappedAtCreation: true
+    }))).toContain('createBuffer')
+  })
+})
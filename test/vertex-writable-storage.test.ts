@@ -0,0 +1,115 @@
+/**
+ * Vertex-Writable Storage Tests
+ *
+ * Vertex shaders can write storage buffers on devices with the
+ * 'wgpu-vertex-writable-storage' feature; layouts that need it name the
+ * feature otherwise.
+ */
+
+import { describe, test, expect, beforeAll, afterAll } from 'bun:test'
+import { Gpu, GPUBufferUsage, GPUShaderStage, GPUTextureUsage } from '../webgpu.js'
+
+let adapter
+let device
+let supported
+
+// Each vertex records its index
+const SHADER = `
+  @group(0) @binding(0) var<storage, read_write> seen: array<u32, 4>;
+
+  @vertex
+  fn vs_main(@builtin(vertex_index) index: u32) -> @builtin(position) vec4<f32> {
+    seen[index] = index + 10u;
+    return vec4<f32>(0.0, 0.0, 0.0, 1.0);
+  }
+
+  @fragment
+  fn fs_main() -> @location(0) vec4<f32> {
+    return vec4<f32>(1.0);
+  }
+`
+
+// The storage buffer isn't used by any entry point
+const UNUSED = `
+  @group(0) @binding(0) var<storage, read_write> unused: array<u32>;
+
+  @vertex
+  fn vs_main() -> @builtin(position) vec4<f32> {
+    return vec4<f32>(0.0);
+  }
+`
+
+beforeAll(async () => {
+  adapter = await Gpu().requestAdapter()
+  supported = adapter.features.includes('wgpu-vertex-writable-storage')
+  device = await adapter.requestDevice({ requiredFeatures: supported ? ['wgpu-vertex-writable-storage'] : [] })
+})
+
+afterAll(() => {
+  device?.destroy()
+})
+
+function vertexStorageLayout(target = device) {
+  return target.createBindGroupLayout({
+    entries: [{ binding: 0, visibility: GPUShaderStage.VERTEX, buffer: { type: 'storage' } }]
+  })
+}
+
+describe('Vertex-Writable Storage: Shaders', () => {
+  test('should let vertex shaders write storage buffers', async () => {
+    if (!supported) return
+
+    const layout = vertexStorageLayout()
+    const module = device.createShaderModule({ code: SHADER })
+    const pipeline = device.createRenderPipeline({
+      layout: device.createPipelineLayout({ bindGroupLayouts: [layout] }),
+      vertex: { module, entryPoint: 'vs_main' },
+      fragment: { module, entryPoint: 'fs_main', targets: [{ format: 'rgba8unorm' }] },
+      primitive: { topology: 'point-list' }
+    })
+    const seen = device.createBuffer({ size: 16, usage: GPUBufferUsage.STORAGE | GPUBufferUsage.COPY_SRC })
+    const target = device.createTexture({ size: [1, 1], format: 'rgba8unorm', usage: GPUTextureUsage.RENDER_ATTACHMENT })
+
+    const encoder = device.createCommandEncoder()
+    const pass = encoder.beginRenderPass({
+      colorAttachments: [{ view: target.createView(), loadOp: 'clear', storeOp: 'store' }]
+    })
+    pass.setPipeline(pipeline)
+    pass.setBindGroup(0, device.createBindGroup({ layout, entries: [{ binding: 0, resource: { buffer: seen } }] }))
+    pass.draw(4)
+    pass.end()
+    device.queue.submit([encoder.finish()])
+
+    expect(Array.from(new Uint32Array(seen.readSync().buffer, 0, 4))).toEqual([10, 11, 12, 13])
+  })
+
+  test('should keep unused writable storage out of the vertex stage without the feature', async () => {
+    const plain = await adapter.requestDevice()
+    const module = plain.createShaderModule({ code: UNUSED })
+
+    plain.pushErrorScope('validation')
+    plain.createBindGroupLayoutFromShader(module, 0)
+    expect(await plain.popErrorScope()).toBeNull()
+    plain.destroy()
+  })
+})
+
+describe('Vertex-Writable Storage: Validation', () => {
+  test('should name the feature writable vertex storage needs', async () => {
+    const plain = await adapter.requestDevice()
+
+    expect(() => vertexStorageLayout(plain)).toThrow(
+      "createBindGroupLayout: binding 0 is writable storage visible to VERTEX, which needs the 'wgpu-vertex-writable-storage' feature"
+    )
+    // Read-only storage needs no feature
+    plain.createBindGroupLayout({
+      entries: [{ binding: 0, visibility: GPUShaderStage.VERTEX, buffer: { type: 'read-only-storage' } }]
+    })
+    plain.destroy()
+  })
+
+  test('should reject writable vertex storage on null devices', async () => {
+    const nullDevice = await (await Gpu({ backends: ['null'] }).requestAdapter()).requestDevice()
+    expect(() => vertexStorageLayout(nullDevice)).toThrow(/Binding 0 is writable storage visible to VERTEX/)
+  })
+})
@@ -8,7 +8,7 @@
  */
 
 import { describe, test, expect, beforeAll, afterAll } from 'bun:test'
-import { Gpu, GPUBufferUsage, GPUMapMode, GPUTextureUsage } from '../webgpu.js'
+import { Gpu, GPUBufferUsage, GPUMapMode, GPUShaderStage, GPUTextureUsage, GPUError } from '../webgpu.js'
 
 describe('WebGPU Standard: GPU Instance', () => {
   let gpu: ReturnType<typeof Gpu>
@@ -118,7 +118,7 @@ describe('WebGPU Standard: GPUDevice', () => {
     device.pushErrorScope('validation')
     const error = await device.popErrorScope()
     // No error expected for just pushing/popping
-    expect(error === null || typeof error === 'string').toBe(true)
+    expect(error === null || error instanceof GPUError).toBe(true)
   })
 })
 
@@ -144,7 +144,7 @@ describe('WebGPU Standard: GPUBuffer Creation', () => {
     })
 
     expect(buffer).toBeDefined()
-    expect(buffer.size()).toBe(256)
+    expect(buffer.size).toBe(256)
     buffer.destroy()
   })
 
@@ -324,8 +324,8 @@ describe('WebGPU Standard: Textures', () => {
     })
 
     expect(texture).toBeDefined()
-    expect(texture.width()).toBe(256)
-    expect(texture.height()).toBe(256)
+    expect(texture.width).toBe(256)
+    expect(texture.height).toBe(256)
     texture.destroy()
   })
 
@@ -423,6 +423,16 @@ describe('WebGPU Standard: Command Encoding', () => {
     srcBuffer.destroy()
     dstBuffer.destroy()
   })
+
+  test('should submit arrays or single command buffers and chain', () => {
+    const first = device.createCommandEncoder().finish()
+    const second = device.createCommandEncoder().finish()
+
+    expect(device.queue.submit([first])).toBe(device.queue)
+    // The already submitted buffer is skipped with a warning
+    expect(() => device.queue.submit(second).submit([first])).not.toThrow()
+    expect(() => device.queue.submit([42])).toThrow()
+  })
 })
 
 describe('WebGPU Standard: Bind Groups', () => {
@@ -444,7 +454,7 @@ describe('WebGPU Standard: Bind Groups', () => {
       entries: [
         {
           binding: 0,
-          visibility: 1 | 2, // VERTEX | FRAGMENT
+          visibility: GPUShaderStage.VERTEX | GPUShaderStage.FRAGMENT,
           buffer: {
             type: 'uniform'
           }
@@ -585,4 +595,10 @@ describe('WebGPU Standard: Constants', () => {
       expect(typeof GPUTextureUsage[flag as keyof typeof GPUTextureUsage]).toBe('number')
     })
   })
+
+  test('should have shader stage constants', () => {
+    expect(GPUShaderStage.VERTEX).toBe(1)
+    expect(GPUShaderStage.FRAGMENT).toBe(2)
+    expect(GPUShaderStage.COMPUTE).toBe(4)
+  })
 })
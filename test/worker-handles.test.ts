@@ -0,0 +1,85 @@
+/**
+ * Worker Handle Tests
+ *
+ * Devices and resources are registered with toHandle() and rehydrated from
+ * the token, in this thread and in a worker thread.
+ */
+
+import { describe, test, expect, beforeAll, afterAll } from 'bun:test'
+import { Worker } from 'worker_threads'
+import path from 'path'
+import {
+  Gpu,
+  GPUBufferUsage,
+  GPUTextureUsage,
+  deviceFromHandle,
+  bufferFromHandle,
+  textureFromHandle,
+  releaseHandle
+} from '../webgpu.js'
+
+let device
+
+beforeAll(async () => {
+  const adapter = await Gpu().requestAdapter()
+  device = await adapter.requestDevice()
+})
+
+afterAll(() => {
+  device?.destroy()
+})
+
+describe('Worker Handles: Registry', () => {
+  test('should rehydrate a texture from its handle', () => {
+    const texture = device.createTexture({
+      width: 8,
+      height: 4,
+      format: 'rgba8unorm',
+      usage: GPUTextureUsage.TEXTURE_BINDING
+    })
+    const handle = texture.toHandle()
+
+    expect(textureFromHandle(handle).width).toBe(8)
+    expect(releaseHandle(handle)).toBe(true)
+    expect(releaseHandle(handle)).toBe(false)
+  })
+
+  test('should reject released handles and handles of another kind', () => {
+    const buffer = device.createBuffer({ size: 16, usage: GPUBufferUsage.COPY_DST })
+    const handle = buffer.toHandle()
+
+    expect(() => deviceFromHandle(handle)).toThrow('not a device')
+    releaseHandle(handle)
+    expect(() => bufferFromHandle(handle)).toThrow('Unknown or released handle')
+  })
+})
+
+describe('Worker Handles: worker_threads', () => {
+  test('should write a shared buffer from a worker', async () => {
+    const buffer = device.createBuffer({
+      size: 16,
+      usage: GPUBufferUsage.COPY_DST | GPUBufferUsage.COPY_SRC
+    })
+    const readback = device.createBuffer({ size: 16, usage: GPUBufferUsage.MAP_READ | GPUBufferUsage.COPY_DST })
+    const handles = { device: device.toHandle(), buffer: buffer.toHandle() }
+
+    const worker = new Worker(path.join(__dirname, 'fixtures/handle-worker.js'), {
+      workerData: { ...handles, webgpu: path.resolve(__dirname, '..', 'webgpu.js') }
+    })
+    const message = await new Promise((resolve, reject) => {
+      worker.once('message', resolve)
+      worker.once('error', reject)
+    })
+    await worker.terminate()
+    releaseHandle(handles.device)
+    releaseHandle(handles.buffer)
+    expect(message).toBe('done')
+
+    const encoder = device.createCommandEncoder()
+    encoder.copyBufferToBuffer(buffer, 0, readback, 0, 16)
+    device.queue.submit([encoder.finish()])
+    await readback.mapAsync('READ')
+    expect(Array.from(new Uint32Array(readback.getMappedRange()))).toEqual([10, 20, 30, 40])
+    readback.unmap()
+  })
+})
@@ -0,0 +1,82 @@
+/**
+ * writeMapped Tests
+ *
+ * buffer.writeMapped() copies data straight into the mapped memory of a
+ * buffer mapped for writing; the contents are read back through a copy.
+ */
+
+import { describe, test, expect, beforeAll, afterAll } from 'bun:test'
+import { Gpu, GPUBufferUsage } from '../webgpu.js'
+
+let device
+
+beforeAll(async () => {
+  const adapter = await Gpu().requestAdapter()
+  device = await adapter.requestDevice()
+})
+
+afterAll(() => {
+  device?.destroy()
+})
+
+async function readBack(buffer) {
+  const staging = device.createBuffer({ size: buffer.size, usage: GPUBufferUsage.MAP_READ | GPUBufferUsage.COPY_DST })
+  const encoder = device.createCommandEncoder()
+  encoder.copyBufferToBuffer(buffer, 0, staging, 0, buffer.size)
+  device.queue.submit([encoder.finish()])
+  await staging.mapAsync('READ')
+  const values = Array.from(new Float32Array(staging.getMappedRange().slice(0)))
+  staging.unmap()
+  return values
+}
+
+describe('writeMapped: Writes', () => {
+  test('should write into a buffer mapped at creation', async () => {
+    const buffer = device.createBuffer({ size: 16, usage: GPUBufferUsage.COPY_SRC, mappedAtCreation: true })
+    buffer.writeMapped(0, new Float32Array([1, 2]))
+    buffer.writeMapped(8, new Float32Array([5, 6, 3, 4]).subarray(2))
+    buffer.unmap()
+
+    expect(await readBack(buffer)).toEqual([1, 2, 3, 4])
+  })
+
+  test('should write into a buffer mapped with mapAsync(WRITE)', async () => {
+    const buffer = device.createBuffer({ size: 16, usage: GPUBufferUsage.MAP_WRITE | GPUBufferUsage.COPY_SRC })
+    await buffer.mapAsync('WRITE')
+    buffer.writeMapped(0, new Float32Array([1, 2, 3, 4]).buffer)
+    buffer.unmap()
+
+    expect(await readBack(buffer)).toEqual([1, 2, 3, 4])
+  })
+
+  test('should combine with getMappedRange() ranges', async () => {
+    const buffer = device.createBuffer({ size: 16, usage: GPUBufferUsage.COPY_SRC, mappedAtCreation: true })
+    new Float32Array(buffer.getMappedRange(0, 8)).set([1, 2])
+    buffer.writeMapped(8, new Float32Array([3, 4]))
+    buffer.unmap()
+
+    expect(await readBack(buffer)).toEqual([1, 2, 3, 4])
+  })
+})
+
+describe('writeMapped: Validation', () => {
+  test('should reject buffers that are not mapped for writing', async () => {
+    const buffer = device.createBuffer({ size: 16, usage: GPUBufferUsage.MAP_READ | GPUBufferUsage.COPY_DST })
+
+    expect(() => buffer.writeMapped(0, new Float32Array(4))).toThrow(/must be mapped/)
+    await buffer.mapAsync('READ')
+    expect(() => buffer.writeMapped(0, new Float32Array(4))).toThrow(/mapped for writing/)
+    buffer.unmap()
+  })
+
+  test('should reject misaligned, out-of-range and overlapping writes', () => {
+    const buffer = device.createBuffer({ size: 16, usage: GPUBufferUsage.COPY_SRC, mappedAtCreation: true })
+    buffer.getMappedRange(0, 8)
+
+    expect(() => buffer.writeMapped(12, new Float32Array(1))).toThrow(/multiple of 8/)
+    expect(() => buffer.writeMapped(8, new Uint8Array(6))).toThrow(/multiple of 4/)
+    expect(() => buffer.writeMapped(8, new Float32Array(4))).toThrow(/exceeds buffer size/)
+    expect(() => buffer.writeMapped(0, new Float32Array(4))).toThrow(/overlaps/)
+    buffer.unmap()
+  })
+})
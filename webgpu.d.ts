@@ -5,13 +5,35 @@
  * The wrapper provides 100% WebGPU standard-compliant API signatures.
  */
 
+/// <reference lib="esnext.disposable" />
+
 // Import native types
 import * as Native from './index'
+import { EventEmitter } from 'events'
+import { Readable } from 'stream'
+
+// Buffers and textures support `using` / `await using` (destroy on dispose)
+// Supported features iterate like a Set of feature names
+declare module './index' {
+    interface GpuBuffer {
+        [Symbol.dispose](): void
+        [Symbol.asyncDispose](): Promise<void>
+    }
+    interface GpuTexture {
+        [Symbol.dispose](): void
+        [Symbol.asyncDispose](): Promise<void>
+    }
+    interface GpuSupportedFeatures {
+        [Symbol.iterator](): IterableIterator<string>
+        keys(): IterableIterator<string>
+        entries(): IterableIterator<[string, string]>
+        forEach(callback: (value: string, key: string, features: GpuSupportedFeatures) => void, thisArg?: unknown): void
+    }
+}
 
 // Re-export native types that don't need wrapping
 export {
     GpuShaderModule,
-    GpuCommandEncoder,
     GpuCommandBuffer,
     GpuBuffer,
     GpuTexture,
@@ -24,21 +46,52 @@ export {
     GpuSampler,
     GpuQuerySet,
     GpuRenderBundle,
-    GpuQueue,
     GpuSupportedFeatures,
+    TextureDataLayout,
     GpuComputePassEncoder,
     GpuRenderPassEncoder,
+    GpuSurfaceTexture,
+    SurfaceDescriptor,
+    SurfaceCapabilities,
+    GpuRenderLoop,
+    RenderLoopOptions,
+    RenderLoopFrame,
     BufferDescriptor,
     ShaderModuleDescriptor,
     TextureDescriptor,
     SamplerDescriptor,
     QuerySetDescriptor,
+    RenderBundleEncoderDescriptor,
+    RenderBundleDescriptor,
     CommandEncoderDescriptor,
     BindGroupLayoutDescriptor,
     ComputePassDescriptor,
     RenderPassDescriptor,
     AdapterInfo,
-    AdapterLimits,
+    AdapterSelector,
+    GpuSupportedLimits,
+    PrefixSumDescriptor,
+    RadixSortDescriptor,
+    MatmulDescriptor,
+    MatmulDimensions,
+    BlitOptions,
+    ColorConversionOptions,
+    BlurDescriptor,
+    HistogramDescriptor,
+    HistogramResult,
+    Draw2DDescriptor,
+    SpriteOptions,
+    Draw2DEncodeOptions,
+    FontDescriptor,
+    TextOptions,
+    TextMetrics,
+    TensorDescriptor,
+    QuantizationParams,
+    TimerDescriptor,
+    TimerResult,
+    TranslateWgslOptions,
+    CrossDeviceBufferCopyOptions,
+    CrossDeviceTextureCopyOptions,
 } from './index'
 
 // WebGPU Standard Constants (UPPER_SNAKE_CASE)
@@ -68,9 +121,78 @@ export interface GPUTextureUsageFlags {
     readonly RENDER_ATTACHMENT: number
 }
 
+export interface GPUShaderStageFlags {
+    readonly VERTEX: number
+    readonly FRAGMENT: number
+    readonly COMPUTE: number
+}
+
+export interface GPUColorWriteFlags {
+    readonly RED: number
+    readonly GREEN: number
+    readonly BLUE: number
+    readonly ALPHA: number
+    readonly ALL: number
+}
+
+/** Opcodes for `executeCommandStream()` (extension) */
+export interface GPUCommandOpcodes {
+    readonly SET_PIPELINE: number
+    readonly SET_BIND_GROUP: number
+    readonly SET_VERTEX_BUFFER: number
+    readonly SET_INDEX_BUFFER: number
+    readonly DRAW: number
+    readonly DRAW_INDEXED: number
+    readonly DRAW_INDIRECT: number
+    readonly DRAW_INDEXED_INDIRECT: number
+    readonly DISPATCH: number
+    readonly DISPATCH_INDIRECT: number
+    /** Buffer size argument for the rest of the buffer */
+    readonly WHOLE_SIZE: number
+}
+
 export const GPUBufferUsage: GPUBufferUsageFlags
 export const GPUMapMode: GPUMapModeFlags
 export const GPUTextureUsage: GPUTextureUsageFlags
+export const GPUShaderStage: GPUShaderStageFlags
+export const GPUColorWrite: GPUColorWriteFlags
+export const GPUCommandOpcode: GPUCommandOpcodes
+
+/** Numeric descriptor enum values, accepted wherever the WebGPU string is */
+export type GPUEnumConstants = Readonly<Record<string, number>>
+
+export const GPUTextureFormat: GPUEnumConstants
+export const GPUTextureDimension: GPUEnumConstants
+export const GPUTextureViewDimension: GPUEnumConstants
+export const GPUTextureAspect: GPUEnumConstants
+export const GPUVertexFormat: GPUEnumConstants
+export const GPUVertexStepMode: GPUEnumConstants
+export const GPUPrimitiveTopology: GPUEnumConstants
+export const GPUIndexFormat: GPUEnumConstants
+export const GPUFrontFace: GPUEnumConstants
+export const GPUCullMode: GPUEnumConstants
+export const GPUCompareFunction: GPUEnumConstants
+export const GPUStencilOperation: GPUEnumConstants
+export const GPUBlendFactor: GPUEnumConstants
+export const GPUBlendOperation: GPUEnumConstants
+export const GPULoadOp: GPUEnumConstants
+export const GPUStoreOp: GPUEnumConstants
+export const GPUAddressMode: GPUEnumConstants
+export const GPUFilterMode: GPUEnumConstants
+export const GPUBufferBindingType: GPUEnumConstants
+export const GPUSamplerBindingType: GPUEnumConstants
+export const GPUTextureSampleType: GPUEnumConstants
+export const GPUStorageTextureAccess: GPUEnumConstants
+export const GPUQueryType: GPUEnumConstants
+export const GPUPresentMode: GPUEnumConstants
+export const GPUCanvasAlphaMode: GPUEnumConstants
+
+/** An error captured by an error scope (WebGPU standard) */
+export class GPUError {
+    readonly message: string
+}
+export class GPUValidationError extends GPUError {}
+export class GPUOutOfMemoryError extends GPUError {}
 
 // Legacy exports (backwards compatibility)
 export const bufferUsage: GPUBufferUsageFlags
@@ -82,15 +204,52 @@ export const textureUsage: GPUTextureUsageFlags
 export interface GPURequestAdapterOptions {
     powerPreference?: 'low-power' | 'high-performance'
     forceFallbackAdapter?: boolean
+    // Only consider adapters that can present to this surface
+    compatibleSurface?: GpuSurface
+    // Extension: choose from enumerateAdapters() by device type, backend and PCI IDs
+    selector?: Native.AdapterSelector
 }
 
+export type GPULimitsPreset = 'default' | 'downlevel' | 'adapterMaximum'
+
 export interface GPUDeviceDescriptor {
     label?: string
     requiredFeatures?: string[]
-    requiredLimits?: Record<string, number>
+    // Limit values by name, or a preset for all of them
+    requiredLimits?: Record<string, number> | GPULimitsPreset
+    // Extension: preset that object requiredLimits apply on top of
+    limitsPreset?: GPULimitsPreset
+}
+
+// Usage flags may be given by name instead of as a bit mask (extension)
+export type GPUBufferUsageName = 'MAP_READ' | 'MAP_WRITE' | 'COPY_SRC' | 'COPY_DST' | 'INDEX' | 'VERTEX' | 'UNIFORM' | 'STORAGE' | 'INDIRECT' | 'QUERY_RESOLVE'
+export type GPUTextureUsageName = 'COPY_SRC' | 'COPY_DST' | 'TEXTURE_BINDING' | 'STORAGE_BINDING' | 'RENDER_ATTACHMENT'
+
+export interface GPUBufferInitDescriptor {
+    label?: string
+    usage: number | GPUBufferUsageName[]
+    data: ArrayBuffer | ArrayBufferView
 }
 
 // Bind Group - WebGPU Standard
+export type GPUIncludeResolver = (path: string, fromFile: string) => string | null | undefined
+
+export interface GPUShaderModuleDescriptor {
+    label?: string
+    code: string
+    // Preprocessor extensions (#include, #define, #ifdef)
+    defines?: Record<string, string | number | boolean>
+    includePaths?: string[]
+    resolveInclude?: GPUIncludeResolver
+}
+
+export interface WgslPreprocessOptions {
+    label?: string
+    defines?: Record<string, string | number | boolean>
+    includePaths?: string[]
+    resolveInclude?: GPUIncludeResolver
+}
+
 export interface GPUBindingResource {
     buffer?: Native.GpuBuffer
     offset?: number
@@ -107,6 +266,10 @@ export type GPUBindingResourceType =
     | { buffer: Native.GpuBuffer; offset?: number; size?: number }
     | Native.GpuTextureView
     | Native.GpuSampler
+    // Extension: binding arrays, for layout entries with `count`
+    | GPUBufferBinding[]
+    | Native.GpuTextureView[]
+    | Native.GpuSampler[]
 
 export interface GPUBindGroupEntry {
     binding: number
@@ -156,49 +319,513 @@ export interface GPURenderPipelineDescriptor {
     }
 }
 
+// Copies - WebGPU Standard
+export type GPUOrigin3D = { x?: number, y?: number, z?: number } | number[]
+export type GPUExtent3D = { width: number, height?: number, depthOrArrayLayers?: number } | number[]
+
+export interface GPUImageCopyBuffer {
+    buffer: Native.GpuBuffer
+    offset?: number
+    bytesPerRow: number
+    rowsPerImage?: number
+}
+
+export interface GPUImageCopyTexture {
+    texture: Native.GpuTexture
+    mipLevel?: number
+    origin?: GPUOrigin3D
+    aspect?: 'all' | 'depth-only' | 'stencil-only' | number
+}
+
+// Decoded image data: unpremultiplied RGBA8 pixels with the top row first,
+// e.g. an ImageData or the raw output of an image decoder
+export interface GPUExternalImage {
+    data: ArrayBuffer | ArrayBufferView
+    width: number
+    height: number
+}
+
+export interface GPUCopyExternalImageSourceInfo {
+    source: GPUExternalImage
+    origin?: { x?: number, y?: number } | number[]
+    flipY?: boolean
+}
+
+export interface GPUCopyExternalImageDestInfo extends GPUImageCopyTexture {
+    premultipliedAlpha?: boolean
+    colorSpace?: 'srgb' | 'srgb-linear'
+}
+
 // GPU Classes - WebGPU Standard
 
+export declare class GpuCommandEncoder {
+    writeTimestamp(querySet: Native.GpuQuerySet, queryIndex: number): void
+    resolveQuerySet(querySet: Native.GpuQuerySet, firstQuery: number, queryCount: number, destination: Native.GpuBuffer, destinationOffset: number): void
+    copyBufferToBuffer(source: Native.GpuBuffer, sourceOffset: number, destination: Native.GpuBuffer, destinationOffset: number, size: number): void
+    copyBufferToTexture(source: GPUImageCopyBuffer, destination: GPUImageCopyTexture, copySize: GPUExtent3D): void
+    copyBufferToTexture(source: Native.GpuBuffer, sourceOffset: number, bytesPerRow: number, rowsPerImage: number | undefined | null, destination: Native.GpuTexture, mipLevel: number | undefined | null, originX: number | undefined | null, originY: number | undefined | null, originZ: number | undefined | null, width: number, height: number, depth?: number | null): void
+    copyTextureToBuffer(source: GPUImageCopyTexture, destination: GPUImageCopyBuffer, copySize: GPUExtent3D): void
+    copyTextureToBuffer(source: Native.GpuTexture, mipLevel: number | undefined | null, originX: number | undefined | null, originY: number | undefined | null, originZ: number | undefined | null, destination: Native.GpuBuffer, destinationOffset: number, bytesPerRow: number, rowsPerImage: number | undefined | null, width: number, height: number, depth?: number | null): void
+    copyTextureToTexture(source: GPUImageCopyTexture, destination: GPUImageCopyTexture, copySize: GPUExtent3D): void
+    clearTexture(texture: Native.GpuTexture, range?: Native.ImageSubresourceRange): void
+    beginComputePass(descriptor?: Native.ComputePassDescriptor): any
+    beginRenderPass(descriptor: any): any  // TODO: Add proper GPURenderPassDescriptor types
+    finish(): Native.GpuCommandBuffer
+    discard(): void
+    [Symbol.dispose](): void
+    [Symbol.asyncDispose](): Promise<void>
+}
+
 export declare class GpuAdapter {
     readonly features: Native.GpuSupportedFeatures
-    readonly limits: any
+    readonly limits: Native.GpuSupportedLimits
     readonly info: Native.AdapterInfo
     readonly isFallbackAdapter: boolean
 
+    getDownlevelCapabilities(): Native.DownlevelCapabilities
+    getTextureFormatFeatures(format: string): Native.TextureFormatFeatures
+    requestAdapterInfo(): Promise<Native.AdapterInfo>
     requestDevice(descriptor?: GPUDeviceDescriptor): Promise<GpuDevice>
+    requestRecoverableDevice(descriptor?: GPUDeviceDescriptor, options?: GpuDeviceRecoveryOptions): Promise<GpuDeviceRecovery>
+}
+
+// Extension: device recovery
+export interface GpuDeviceRecoveryOptions {
+    // Device requests to try before giving up (default 3)
+    maxAttempts?: number
+}
+
+export interface GpuRecoveredResource<T> {
+    // Result of the latest run of the creation callback
+    readonly value: T
+}
+
+// 'recovered' listeners receive a GpuRecoveredEvent, 'recoveryFailed' ones { error }
+export type GpuDeviceRecoveryEventType = 'recovered' | 'recoveryFailed'
+
+export interface GpuRecoveredEvent {
+    device: GpuDevice
+    // How the previous device was lost
+    lost: GPUDeviceLostInfo
+    attempt: number
+}
+
+export declare class GpuDeviceRecovery extends EventEmitter {
+    readonly device: GpuDevice
+    register<T>(create: (device: GpuDevice) => T): GpuRecoveredResource<T>
+    unregister(resource: GpuRecoveredResource<unknown>): void
+    recover(): Promise<GpuDevice>
+    destroy(): void
 }
 
-export declare class GpuDevice {
-    readonly queue: Native.GpuQueue
+export declare class GpuQueue {
+    submit(commandBuffers: Native.GpuCommandBuffer[] | Native.GpuCommandBuffer): this
+    writeBuffer(buffer: Native.GpuBuffer, bufferOffset: number, data: ArrayBuffer | ArrayBufferView, dataOffset?: number, size?: number): void
+    uploadBuffer(buffer: Native.GpuBuffer, bufferOffset: number, data: ArrayBuffer | ArrayBufferView): void
+    writeCubeFaces(texture: Native.GpuTexture, faces: Array<ArrayBuffer | ArrayBufferView>, options?: Native.CubeFacesOptions): void
+    writeTexture(destination: GPUImageCopyTexture, data: ArrayBuffer | ArrayBufferView, dataLayout: Native.TextureDataLayout, size: GPUExtent3D): void
+    copyExternalImageToTexture(source: GPUCopyExternalImageSourceInfo, destination: GPUCopyExternalImageDestInfo, copySize: GPUExtent3D): void
+    onSubmittedWorkDone(): Promise<void>
+}
+
+// Extension: GpuDevice events. 'submit' and 'submissionComplete' listeners
+// receive a GpuSubmitEvent, the others a Native.DeviceEvent
+export type GpuDeviceEventType = 'submit' | 'submissionComplete' | 'deviceLost' | 'uncapturedError' | 'oomWarning'
+
+export interface GPUDeviceLostInfo {
+    reason: 'unknown' | 'destroyed'
+    message: string
+}
+
+export interface GPUUncapturedErrorEvent {
+    type: 'uncapturederror'
+    error: { message: string }
+}
+
+export type GPUUncapturedErrorListener = ((event: GPUUncapturedErrorEvent) => void) | { handleEvent(event: GPUUncapturedErrorEvent): void }
+
+// createTexture() takes the standard `size` or flat width / height / depth
+// fields, and initial `data` as any ArrayBuffer or view
+type GPUTextureDescriptorFields = Omit<Native.TextureDescriptor, 'data'> & { data?: ArrayBuffer | ArrayBufferView }
+export type GPUTextureDescriptor = GPUTextureDescriptorFields | (Omit<GPUTextureDescriptorFields, 'width' | 'height' | 'depth'> & {
+    size: GPUExtent3D
+    viewFormats?: (string | number)[]
+})
+
+export interface GpuSubmitEvent {
+    type: 'submit' | 'submissionComplete'
+    // Counts queue.submit() calls made while listeners were registered, from 1
+    submission: number
+    // Only for 'submit'
+    commandBuffers?: number
+}
+
+export declare class GpuDevice extends EventEmitter {
+    readonly queue: GpuQueue
     readonly features: Native.GpuSupportedFeatures
-    readonly limits: any
+    readonly limits: Native.GpuSupportedLimits
     readonly label: string | null
+    readonly lost: Promise<GPUDeviceLostInfo>
+    onuncapturederror: ((event: GPUUncapturedErrorEvent) => void) | null
+
+    // WebGPU standard events; only 'uncapturederror' is dispatched
+    addEventListener(type: 'uncapturederror', listener: GPUUncapturedErrorListener): void
+    removeEventListener(type: 'uncapturederror', listener: GPUUncapturedErrorListener): void
+
+    // Worker thread sharing
+    toHandle(): string
 
     // Error scopes
     pushErrorScope(filter: 'validation' | 'out-of-memory' | 'internal'): void
-    popErrorScope(): Promise<string | null>
+    popErrorScope(): Promise<GPUError | null>
+    enableFaultInjection(options: Native.FaultInjectionOptions): void
+    disableFaultInjection(): void
+
+    // Device maintenance
+    poll(forceWait?: boolean): boolean
+    setAutoMaintenance(enabled: boolean): void
+    waitIdle(): Promise<void>
 
     // Resource creation
     createBuffer(descriptor: Native.BufferDescriptor): Native.GpuBuffer
-    createTexture(descriptor: Native.TextureDescriptor): Native.GpuTexture
+    createBufferInit(descriptor: GPUBufferInitDescriptor): Native.GpuBuffer
+    createTexture(descriptor: GPUTextureDescriptor): Native.GpuTexture
+    createMultisampleTexture(target: Native.GpuTexture | Native.GpuSurfaceTexture | Native.GpuTextureView, sampleCount?: number, label?: string): Native.GpuTexture
     createSampler(descriptor: Native.SamplerDescriptor): Native.GpuSampler
     createBindGroupLayout(descriptor: Native.BindGroupLayoutDescriptor): Native.GpuBindGroupLayout
-    createShaderModule(descriptor: Native.ShaderModuleDescriptor): Native.GpuShaderModule
+    createBindGroupLayoutFromShader(module: Native.GpuShaderModule, group: number, label?: string): Native.GpuBindGroupLayout
+    createUniformWriter(module: Native.GpuShaderModule, structName: string): GpuUniformWriter
+    createShaderModule(descriptor: GPUShaderModuleDescriptor): Native.GpuShaderModule
     createQuerySet(descriptor: Native.QuerySetDescriptor): Native.GpuQuerySet
-    createCommandEncoder(descriptor?: Native.CommandEncoderDescriptor): Native.GpuCommandEncoder
+    createRenderBundleEncoder(descriptor: Native.RenderBundleEncoderDescriptor): GpuRenderBundleEncoder
+    createCommandEncoder(descriptor?: Native.CommandEncoderDescriptor): GpuCommandEncoder
+
+    // Shader module cache
+    setShaderModuleCacheEnabled(enabled: boolean): void
+    clearShaderModuleCache(): void
+    getShaderModuleCacheStats(): Native.ShaderModuleCacheStats
 
     // WebGPU Standard API
     createBindGroup(descriptor: GPUBindGroupDescriptor): Native.GpuBindGroup
     createPipelineLayout(descriptor: GPUPipelineLayoutDescriptor): Native.GpuPipelineLayout
     createComputePipeline(descriptor: GPUComputePipelineDescriptor): Native.GpuComputePipeline
     createRenderPipeline(descriptor: GPURenderPipelineDescriptor): Native.GpuRenderPipeline
+    createComputePipelineAsync(descriptor: GPUComputePipelineDescriptor): Promise<Native.GpuComputePipeline>
+    createRenderPipelineAsync(descriptor: GPURenderPipelineDescriptor): Promise<Native.GpuRenderPipeline>
+
+    // Compute utilities
+    createPrefixSum(descriptor?: Native.PrefixSumDescriptor): GpuPrefixSum
+    createRadixSort(descriptor?: Native.RadixSortDescriptor): GpuRadixSort
+    createMatmul(descriptor?: Native.MatmulDescriptor): GpuMatmul
+    createComputeRunner(descriptor: ComputeRunnerDescriptor): GpuComputeRunner
+    createChunkedBuffer(descriptor: Native.ChunkedBufferDescriptor): GpuChunkedBuffer
+
+    // Texture utilities
+    createBlur(descriptor?: Native.BlurDescriptor): GpuBlur
+    createHistogram(descriptor?: Native.HistogramDescriptor): GpuHistogram
+    createDraw2D(descriptor?: Native.Draw2DDescriptor): GpuDraw2D
+    createFrameCapture(descriptor?: FrameCaptureDescriptor): GpuFrameCapture
+
+    // Text
+    createFont(data: ArrayBuffer | ArrayBufferView, descriptor?: Native.FontDescriptor): GpuFont
+
+    // Tensors
+    createTensor(descriptor: Native.TensorDescriptor): GpuTensor
+    blitTexture(encoder: GpuCommandEncoder, source: Native.GpuTexture, destination: Native.GpuTexture, options?: Native.BlitOptions): void
+    convertPixels(encoder: GpuCommandEncoder, input: Native.GpuBuffer, output: Native.GpuBuffer, count: number, options?: Native.ColorConversionOptions): void
+
+    // Profiling
+    createTimer(descriptor?: Native.TimerDescriptor): GpuTimer
+
+    // Explicit resource management; async disposal waits for submitted work
+    [Symbol.dispose](): void
+    [Symbol.asyncDispose](): Promise<void>
+}
+
+export declare class GpuRenderBundleEncoder {
+    setPipeline(pipeline: Native.GpuRenderPipeline): void
+    setVertexBuffer(slot: number, buffer: Native.GpuBuffer, offset?: number, size?: number): void
+    setIndexBuffer(buffer: Native.GpuBuffer, indexFormat: 'uint16' | 'uint32' | number, offset?: number, size?: number): void
+    setBindGroup(index: number, bindGroup: Native.GpuBindGroup, dynamicOffsets?: number[]): void
+    draw(vertexCount: number, instanceCount?: number, firstVertex?: number, firstInstance?: number): void
+    drawIndexed(indexCount: number, instanceCount?: number, firstIndex?: number, baseVertex?: number, firstInstance?: number): void
+    drawBatch(draws: Uint32Array, bindGroupIndex?: number, bindGroup?: Native.GpuBindGroup, dynamicOffsets?: Uint32Array): void
+    drawIndexedBatch(draws: Uint32Array, bindGroupIndex?: number, bindGroup?: Native.GpuBindGroup, dynamicOffsets?: Uint32Array): void
+    executeCommandStream(stream: Uint32Array, pipelines: Native.GpuRenderPipeline[], bindGroups?: Native.GpuBindGroup[], buffers?: Native.GpuBuffer[]): void
+    drawIndirect(indirectBuffer: Native.GpuBuffer, indirectOffset: number): void
+    drawIndexedIndirect(indirectBuffer: Native.GpuBuffer, indirectOffset: number): void
+    finish(descriptor?: Native.RenderBundleDescriptor): Native.GpuRenderBundle
+}
+
+export declare class GpuPrefixSum {
+    encode(encoder: GpuCommandEncoder, input: Native.GpuBuffer, output: Native.GpuBuffer, count: number, inclusive?: boolean): void
+    run(input: Native.GpuBuffer, output: Native.GpuBuffer, count: number, inclusive?: boolean): void
+}
+
+export declare class GpuRadixSort {
+    encode(encoder: GpuCommandEncoder, keys: Native.GpuBuffer, count: number, values?: Native.GpuBuffer | null, keyBits?: number): void
+    run(keys: Native.GpuBuffer, count: number, values?: Native.GpuBuffer | null, keyBits?: number): void
+}
+
+export declare class GpuUniformWriter {
+    readonly size: number
+    set(values: Record<string, unknown>): this
+    upload(queue: GpuQueue, buffer: Native.GpuBuffer, offset?: number): void
+    getData(): Buffer
+}
+
+export declare class GpuMatmul {
+    encode(encoder: GpuCommandEncoder, a: Native.GpuBuffer, b: Native.GpuBuffer, output: Native.GpuBuffer, dims: Native.MatmulDimensions): void
+    run(a: Native.GpuBuffer, b: Native.GpuBuffer, output: Native.GpuBuffer, dims: Native.MatmulDimensions): void
+    matmul(a: Native.GpuBuffer, b: Native.GpuBuffer, dims: Native.MatmulDimensions): Native.GpuBuffer
+}
+
+export interface ComputeRunnerDescriptor extends Native.ComputeRunnerDescriptor {
+    pipeline: Native.GpuComputePipeline
+    // Set at indices 0, 1, ... before each dispatch
+    bindGroups?: Native.GpuBindGroup[]
+    // Buffer run() writes its parameters into; needs COPY_DST usage
+    params?: Native.GpuBuffer
+}
+
+export declare class GpuComputeRunner {
+    run(params: ArrayBuffer | ArrayBufferView | null | undefined, workgroups: number | number[]): void
+}
+
+export interface ChunkedBindGroupsDescriptor {
+    label?: string
+    layout: Native.GpuBindGroupLayout
+    // Binding of the chunk's storage buffer
+    binding: number
+    // Binding of the chunk's ChunkInfo uniform, if the shader reads it
+    infoBinding?: number
+    // Other entries, the same for every chunk
+    entries?: GPUBindGroupEntry[]
+}
+
+export interface ChunkDispatchDescriptor {
+    label?: string
+    pipeline: Native.GpuComputePipeline
+    // One per chunk, from createBindGroups()
+    bindGroups: Native.GpuBindGroup[]
+    // Index the per-chunk bind groups are set at (default 0)
+    group?: number
+    // Set at their array indices for every chunk
+    sharedBindGroups?: (Native.GpuBindGroup | null | undefined)[]
+    // Invocations per workgroup (default: the pipeline's @workgroup_size)
+    workgroupSize?: number
+}
+
+export interface GpuBufferChunk extends Native.BufferChunk {
+    buffer: Native.GpuBuffer
+}
+
+export declare class GpuChunkedBuffer {
+    readonly label: string
+    readonly size: number
+    readonly elementSize: number
+    readonly chunkCount: number
+    readonly chunks: GpuBufferChunk[]
+    readonly infoBuffer: Native.GpuBuffer
+    readonly infoStride: number
+    buffer(index: number): Native.GpuBuffer
+    write(offset: number, data: ArrayBuffer | ArrayBufferView): void
+    read(offset?: number, size?: number): Promise<Buffer>
+    createBindGroups(descriptor: ChunkedBindGroupsDescriptor): Native.GpuBindGroup[]
+    dispatch(descriptor: ChunkDispatchDescriptor): void
+}
+
+export interface FrameCaptureDescriptor extends Native.FrameCaptureDescriptor {
+    pixelFormat?: 'rgba' | 'yuv420p'
+    // capture() waits while this many frames wait for next() (default Infinity)
+    maxQueuedFrames?: number
+}
+
+export declare class GpuFrameCapture implements AsyncIterable<Native.CapturedFrame> {
+    readonly label: string
+    readonly queuedFrames: number
+    capture(texture: Native.GpuTexture, timestamp?: number): Promise<number>
+    next(): Promise<Native.CapturedFrame | null>
+    end(): void
+    [Symbol.asyncIterator](): AsyncIterator<Native.CapturedFrame>
+    stream(): Readable
+}
+
+export declare class GpuBlur {
+    readonly radius: number
+    readonly sigma: number
+    encode(encoder: GpuCommandEncoder, source: Native.GpuTexture, destination: Native.GpuTexture): void
+    run(source: Native.GpuTexture, destination: Native.GpuTexture): void
+}
+
+export declare class GpuHistogram {
+    readonly bins: number
+    readonly outputSize: number
+    encode(encoder: GpuCommandEncoder, source: Native.GpuTexture, output: Native.GpuBuffer): void
+    encodeBuffer(encoder: GpuCommandEncoder, pixels: Native.GpuBuffer, count: number, output: Native.GpuBuffer): void
+    compute(source: Native.GpuTexture): Promise<Native.HistogramResult>
+    computeBuffer(pixels: Native.GpuBuffer, count: number): Promise<Native.HistogramResult>
+}
+
+export declare class GpuDraw2D {
+    rect(x: number, y: number, width: number, height: number, color?: number[]): this
+    line(x0: number, y0: number, x1: number, y1: number, color?: number[], width?: number): this
+    quad(points: ArrayLike<number>, color?: number[]): this
+    sprite(texture: Native.GpuTexture, x: number, y: number, width: number, height: number, options?: Native.SpriteOptions): this
+    translate(x: number, y: number): this
+    scale(x: number, y?: number): this
+    rotate(radians: number): this
+    setTransform(a: number, b: number, c: number, d: number, e: number, f: number): this
+    resetTransform(): this
+    save(): this
+    restore(): this
+    encode(encoder: GpuCommandEncoder, target: Native.GpuTextureView, options?: Native.Draw2DEncodeOptions): void
+    run(target: Native.GpuTextureView, options?: Native.Draw2DEncodeOptions): void
+}
+
+export declare class GpuFont {
+    readonly size: number
+    readonly lineHeight: number
+    measureText(text: string, options?: Native.TextOptions): Native.TextMetrics
+    drawText(pass: Native.GpuRenderPassEncoder, text: string, x: number, y: number, options?: Native.TextOptions): void
+}
+
+export type GPUTensorDtype = 'f32' | 'f16' | 'i32' | 'u8'
+
+export declare class GpuTensor {
+    static fromArray(device: GpuDevice, data: ArrayLike<number>, descriptor?: Native.TensorDescriptor): GpuTensor
+    readonly shape: number[]
+    readonly strides: number[]
+    readonly dtype: GPUTensorDtype
+    readonly size: number
+    readonly byteOffset: number
+    readonly isContiguous: boolean
+    readonly buffer: Native.GpuBuffer
+    slice(dimension: number, start: number, end?: number): GpuTensor
+    reshape(shape: number[]): GpuTensor
+    toArray(): Promise<Float32Array | Int32Array | Uint8Array>
+}
+
+export declare class GpuTimer {
+    readonly capacity: number
+    begin(encoder: GpuCommandEncoder, label: string): number
+    end(encoder: GpuCommandEncoder, span: number): void
+    wrap<T>(encoder: GpuCommandEncoder, label: string, fn: (encoder: GpuCommandEncoder) => T): T
+    results(): Promise<Native.TimerResult[]>
+}
+
+export interface GPUSurfaceConfiguration {
+    device: GpuDevice
+    format: string | number
+    width: number
+    height: number
+    usage?: number | GPUTextureUsageName[]
+    presentMode?: 'fifo' | 'fifo-relaxed' | 'immediate' | 'mailbox' | 'auto-vsync' | 'auto-no-vsync' | number
+    alphaMode?: 'auto' | 'opaque' | 'premultiplied' | 'postmultiplied' | 'inherit' | number
+    viewFormats?: (string | number)[]
+}
+
+export declare class GpuSurface {
+    getCapabilities(adapter: GpuAdapter): Native.SurfaceCapabilities
+    configure(configuration: GPUSurfaceConfiguration): void
+    resize(width: number, height: number): void
+    getCurrentTexture(): Native.GpuSurfaceTexture | null
+    runRenderLoop(callback: (texture: Native.GpuSurfaceTexture, frame: Native.RenderLoopFrame) => void, options?: Native.RenderLoopOptions): Native.GpuRenderLoop
 }
 
 export declare class Gpu {
+    readonly wgslLanguageFeatures: ReadonlySet<string>
     requestAdapter(options?: GPURequestAdapterOptions): Promise<GpuAdapter | null>
+    getPreferredCanvasFormat(): 'bgra8unorm'
+    enumerateAdapters(): Native.AdapterInfo[]
+    requestAdapterByIndex(index: number): GpuAdapter
+    createSurface(descriptor: Native.SurfaceDescriptor): GpuSurface
+    createSurfaceFromNativeWindowHandle(handle: Buffer): GpuSurface
 }
 
 // Main exports
-export function Gpu(): Gpu
+export function Gpu(options?: Native.GpuOptions): Gpu
+
+// Sets globalThis.navigator.gpu (and the GPU* constant globals) unless already present
+export function installNavigatorGpu(options?: Native.GpuOptions): Gpu
+
+// Shader tools
+export function spirvToWgsl(spirv: ArrayBuffer | ArrayBufferView): string
+export function translateWgsl(code: string, target: 'spirv', options?: Native.TranslateWgslOptions): Buffer
+export function translateWgsl(code: string, target: 'msl' | 'hlsl' | 'glsl', options?: Native.TranslateWgslOptions): string
+export function preprocessWgsl(code: string, options?: WgslPreprocessOptions): string
+
+// Half-precision helpers
+export function packF16(values: Float32Array | ArrayLike<number>): Buffer
+export function unpackF16(data: ArrayBuffer | ArrayBufferView): Float32Array
+
+// Int8 quantization helpers
+export function quantizeInt8(values: Float32Array | ArrayLike<number>, params: Native.QuantizationParams): Buffer
+export function dequantizeInt8(data: ArrayBuffer | ArrayBufferView, params: Native.QuantizationParams): Float32Array
+
+// Indirect argument structs: packed into a new Buffer, or into `destination`, which is returned
+export function packDrawIndirectArgs<T extends ArrayBuffer | ArrayBufferView = Buffer>(args: Native.DrawIndirectArgs | Native.DrawIndirectArgs[], layout?: Native.IndirectArgsLayout, destination?: T): T
+export function packDrawIndexedIndirectArgs<T extends ArrayBuffer | ArrayBufferView = Buffer>(args: Native.DrawIndexedIndirectArgs | Native.DrawIndexedIndirectArgs[], layout?: Native.IndirectArgsLayout, destination?: T): T
+export function packDispatchIndirectArgs<T extends ArrayBuffer | ArrayBufferView = Buffer>(args: Native.DispatchIndirectArgs | Native.DispatchIndirectArgs[], layout?: Native.IndirectArgsLayout, destination?: T): T
+export function unpackDrawIndirectArgs(data: ArrayBuffer | ArrayBufferView, layout?: Native.IndirectArgsLayout): Required<Native.DrawIndirectArgs>[]
+export function unpackDrawIndexedIndirectArgs(data: ArrayBuffer | ArrayBufferView, layout?: Native.IndirectArgsLayout): Required<Native.DrawIndexedIndirectArgs>[]
+export function unpackDispatchIndirectArgs(data: ArrayBuffer | ArrayBufferView, layout?: Native.IndirectArgsLayout): Required<Native.DispatchIndirectArgs>[]
+
+// Texture helpers
+export function getTextureFormatInfo(format: string): Native.TextureFormatInfo
+
+export interface TextureMatchOptions {
+    // Percentage of pixels that may differ (default 0)
+    maxDiffPercent?: number
+    // Largest difference of a channel that still matches (default 0)
+    perChannelTolerance?: number
+    // Where the diff image is written on failure (default <reference>.diff.png)
+    diffPath?: string
+    // Write the reference from the texture instead of comparing
+    update?: boolean
+}
+
+export interface TextureMatchResult {
+    diffPixels: number
+    diffPercent: number
+}
+
+export function expectTextureMatches(texture: Native.GpuTexture, referencePath: string, options?: TextureMatchOptions): Promise<TextureMatchResult>
+
+// Multi-device helpers
+export function copyBufferBetweenDevices(source: Native.GpuBuffer, destination: Native.GpuBuffer, options?: Native.CrossDeviceBufferCopyOptions): Promise<void>
+export function copyTextureBetweenDevices(source: Native.GpuTexture, destination: Native.GpuTexture, options?: Native.CrossDeviceTextureCopyOptions): Promise<void>
+
+// Worker thread sharing
+export function deviceFromHandle(handle: string): GpuDevice
+export function bufferFromHandle(handle: string): Native.GpuBuffer
+export function textureFromHandle(handle: string): Native.GpuTexture
+export function releaseHandle(handle: string): boolean
+
+// Diagnostics
+export interface ApiTraceEntry {
+    // Counts traced calls from 1
+    sequence: number
+    // Milliseconds since the epoch
+    timestamp: number
+    // e.g. 'GpuDevice.createBuffer({ label: "vertices", size: 64, usage: 40 })'
+    call: string
+    // Message of the error the call threw
+    error?: string
+}
+
+export interface ApiTraceOptions {
+    // Number of calls kept (default 1000)
+    capacity?: number
+    // Written as each call starts, kept to at most twice the capacity
+    file?: string
+    callback?: (entry: ApiTraceEntry) => void
+}
+
+export function enableApiTrace(options?: ApiTraceOptions): void
+export function disableApiTrace(): void
+export function getApiTrace(): ApiTraceEntry[]
+export function setLogCallback(level: 'off' | 'error' | 'warn' | 'info' | 'debug' | 'trace', callback?: ((record: Native.LogRecord) => void) | null): void
 
 // Native bindings (advanced users)
 export { Native as native }
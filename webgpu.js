@@ -13,8 +13,18 @@
  * Performance: Zero overhead - simple object transformation at call time
  */
 
+const { EventEmitter } = require('events')
+const fs = require('fs')
+const { Readable } = require('stream')
+const nodePath = require('path')
+const zlib = require('zlib')
 const native = require('./index.js')
 
+// Explicit resource management (`using` / `await using`). Runtimes without
+// the well-known symbols get the same fallback TypeScript's helpers use
+const disposeSymbol = Symbol.dispose || Symbol.for('Symbol.dispose')
+const asyncDisposeSymbol = Symbol.asyncDispose || Symbol.for('Symbol.asyncDispose')
+
 /**
  * WebGPU-standard GpuBuffer wrapper
  *
@@ -26,7 +36,8 @@ const native = require('./index.js')
 class GpuBuffer {
     constructor(nativeBuffer) {
         this._native = nativeBuffer
-        this._mappedRange = null
+        // { offset, size, data } of every range returned since the buffer was mapped
+        this._mappedRanges = []
     }
 
     /**
@@ -34,37 +45,70 @@ class GpuBuffer {
      *
      * Returns an ArrayBuffer representing the mapped GPU memory (WebGPU standard).
      * Modifications to TypedArray views of this ArrayBuffer will be flushed to GPU when unmap() is called.
+     * Repeated calls over the same range return the same ArrayBuffer, so views
+     * created earlier stay valid; other ranges must not overlap it.
      *
      * Standard usage:
      *   const arrayBuffer = buffer.getMappedRange()
      *   const view = new Float32Array(arrayBuffer)
      *   view[0] = 1.0  // Modifications are tracked
      *
+     * Extension: with `destination` (an ArrayBuffer, SharedArrayBuffer or view
+     * of one), the range is copied straight into it and `destination` is
+     * returned. No mapped view is created, so nothing is written back on unmap().
+     *
      * @param {number} [offset] - Byte offset into the buffer (must be multiple of 8)
      * @param {number} [size] - Number of bytes to return (must be multiple of 4)
+     * @param {ArrayBuffer|SharedArrayBuffer|ArrayBufferView} [destination] - Where to copy the range
      */
-    getMappedRange(offset, size) {
-        // Native returns a Node.js Buffer (Uint8Array subclass)
-        // Store it for unmap()
-        this._mappedRange = this._native.getMappedRange(offset, size)
+    getMappedRange(offset = 0, size, destination) {
+        if (destination) {
+            this._native.readMappedRange(toUint8Array(destination), offset, size)
+            return destination
+        }
+
+        const rangeSize = size ?? Math.max(this.size - offset, 0)
+        const existing = this._mappedRanges.find((range) => range.offset === offset && range.size === rangeSize)
+        if (existing) {
+            return existing.data.buffer
+        }
+
+        // Native returns a Node.js Buffer (Uint8Array subclass) and rejects overlapping ranges.
+        // Keep it for unmap(), which writes it back at its offset
+        const data = this._native.getMappedRange(offset, rangeSize)
+        this._mappedRanges.push({ offset, size: rangeSize, data })
 
         // Return the underlying ArrayBuffer (WebGPU standard)
         // The Buffer always uses the full ArrayBuffer (byteOffset=0, byteLength=buffer.byteLength)
         // so we can safely return buffer.buffer
-        return this._mappedRange.buffer
+        return data.buffer
+    }
+
+    /**
+     * Extension: write `data` straight into the mapped memory at `offset`
+     *
+     * Valid while mapped for writing (mapAsync(WRITE) or mappedAtCreation).
+     * The bytes go directly to the mapping instead of into a copy that unmap()
+     * writes back, so large uploads aren't buffered twice.
+     *
+     * @param {number} offset - Byte offset into the buffer (must be multiple of 8)
+     * @param {ArrayBuffer|ArrayBufferView} data - Bytes to write (length must be multiple of 4)
+     */
+    writeMapped(offset, data) {
+        return this._native.writeMapped(offset, toUint8Array(data))
     }
 
     /**
      * Unmap the buffer and flush changes to GPU
      *
      * Standard WebGPU API - takes no arguments.
-     * Internally passes the stored mapped range back to native implementation.
+     * Internally passes the stored mapped ranges back to native implementation.
      */
     unmap() {
-        if (this._mappedRange) {
-            // Pass modified buffer back to native unmap
-            this._native.unmap(this._mappedRange)
-            this._mappedRange = null
+        if (this._mappedRanges.length > 0) {
+            // Pass modified ranges back to native unmap
+            this._native.unmap(this._mappedRanges.map(({ offset, data }) => ({ offset, data })))
+            this._mappedRanges = []
         } else {
             // No mapped range stored, just unmap
             this._native.unmap()
@@ -72,31 +116,74 @@ class GpuBuffer {
     }
 
     // Pass-through methods
-    mapAsync(mode) {
-        return this._native.mapAsync(mode)
+    // Accepts GPUMapMode flags or 'READ' / 'WRITE', and maps `size` bytes from
+    // `offset`. Rejects with an AbortError DOMException when unmap() or
+    // destroy() is called before the map completes, as in browsers
+    async mapAsync(mode, offset, size) {
+        try {
+            await this._native.mapAsync(mode, offset, size)
+        } catch (error) {
+            if (error.code === 'Cancelled') throw new DOMException(error.message, 'AbortError')
+            throw error
+        }
     }
 
     // Non-standard convenience method for examples
     // WebGPU standard: use mapAsync('READ') + getMappedRange()
-    async mapRead() {
+    // With `destination`, the contents are copied into it and the buffer is unmapped again
+    async mapRead(destination) {
         await this._native.mapAsync('READ')
+        if (destination) {
+            this._native.readMappedRange(toUint8Array(destination))
+            this._native.unmap()
+            return destination
+        }
         return this._native.getMappedRange()
     }
 
+    // Extension: blocking readback of up to 4 MiB for debugging and scripts.
+    // Waits for all submitted GPU work; prefer mapAsync() elsewhere
+    readSync(offset, size) {
+        return this._native.readSync(offset, size)
+    }
+
     destroy() {
+        this._mappedRanges = []
         return this._native.destroy()
     }
 
-    size() {
-        return this._native.size()
+    get size() {
+        return this._native.size
+    }
+
+    get usage() {
+        return this._native.usage
+    }
+
+    // Extension: usage flag names, e.g. ['COPY_DST', 'STORAGE']
+    usageNames() {
+        return this._native.usageNames()
+    }
+
+    get mapState() {
+        return this._native.mapState
+    }
+
+    get label() {
+        return this._native.label
+    }
+
+    // Extension: token for bufferFromHandle() in a worker thread
+    toHandle() {
+        return this._native.toHandle()
     }
 
-    usage() {
-        return this._native.usage()
+    [disposeSymbol]() {
+        this.destroy()
     }
 
-    mapState() {
-        return this._native.mapState()
+    async [asyncDisposeSymbol]() {
+        this.destroy()
     }
 }
 
@@ -106,25 +193,137 @@ class GpuBuffer {
  * Unwraps GpuBuffer objects before passing to native methods.
  */
 class GpuQueue {
-    constructor(nativeQueue) {
+    constructor(nativeQueue, device) {
         this._native = nativeQueue
+        // Emits the 'submit' / 'submissionComplete' device events
+        this._device = device
+        this._submissions = 0
     }
 
+    // WebGPU standard: submit() takes an array of command buffers. A single
+    // command buffer is accepted too, already submitted ones are skipped with
+    // a warning, and the queue is returned for chaining
     submit(commandBuffers) {
-        // WebGPU standard: submit() takes array of command buffers
-        // Support both array and single command buffer for backward compatibility
         const bufferArray = Array.isArray(commandBuffers) ? commandBuffers : [commandBuffers]
-        return this._native.submit(bufferArray)
+        this._native.submit(bufferArray)
+
+        const device = this._device
+        if (device && (device.listenerCount('submit') > 0 || device.listenerCount('submissionComplete') > 0)) {
+            const submission = ++this._submissions
+            device.emit('submit', { type: 'submit', submission, commandBuffers: bufferArray.length })
+            if (device.listenerCount('submissionComplete') > 0) {
+                // Keeps the process alive until the GPU is done, unlike native events
+                this._native.onSubmittedWorkDone().then(
+                    () => device.emit('submissionComplete', { type: 'submissionComplete', submission }),
+                    () => {}
+                )
+            }
+        }
+        return this
     }
 
-    writeBuffer(buffer, offset, data) {
+    /**
+     * Write data to a buffer (WebGPU standard API)
+     *
+     * `data` may be an ArrayBuffer or any ArrayBufferView. `dataOffset` and `size`
+     * count elements for TypedArrays and bytes otherwise; only the view's own
+     * range (byteOffset/byteLength) is ever read.
+     */
+    writeBuffer(buffer, bufferOffset, data, dataOffset = 0, size) {
         // Unwrap GpuBuffer if needed
         const nativeBuffer = buffer._native || buffer
-        return this._native.writeBuffer(nativeBuffer, offset, data)
+
+        let arrayBuffer, byteOffset, byteLength, elementSize
+        if (ArrayBuffer.isView(data)) {
+            arrayBuffer = data.buffer
+            byteOffset = data.byteOffset
+            byteLength = data.byteLength
+            elementSize = data.BYTES_PER_ELEMENT || 1
+        } else if (data instanceof ArrayBuffer || (typeof SharedArrayBuffer !== 'undefined' && data instanceof SharedArrayBuffer)) {
+            arrayBuffer = data
+            byteOffset = 0
+            byteLength = data.byteLength
+            elementSize = 1
+        } else {
+            throw new TypeError('writeBuffer: data must be an ArrayBuffer or ArrayBufferView')
+        }
+
+        const length = byteLength / elementSize
+        const count = size === undefined ? length - dataOffset : size
+        if (dataOffset < 0 || count < 0 || dataOffset + count > length) {
+            throw new RangeError(`writeBuffer: dataOffset ${dataOffset} + size ${count} exceeds data length ${length}`)
+        }
+        if ((count * elementSize) % 4 !== 0) {
+            throw new RangeError(`writeBuffer: write size ${count * elementSize} bytes is not a multiple of 4`)
+        }
+
+        // Zero-copy view over exactly the requested bytes
+        const bytes = Buffer.from(arrayBuffer, byteOffset + dataOffset * elementSize, count * elementSize)
+        return this._native.writeBuffer(nativeBuffer, bufferOffset, bytes)
+    }
+
+    // Extension: large uploads through mappedAtCreation staging and a single copy
+    uploadBuffer(buffer, bufferOffset, data) {
+        const nativeBuffer = buffer._native || buffer
+        const bytes = ArrayBuffer.isView(data)
+            ? Buffer.from(data.buffer, data.byteOffset, data.byteLength)
+            : Buffer.from(data)
+        return this._native.uploadBuffer(nativeBuffer, bufferOffset, bytes)
     }
 
+    // Extension: upload all six cube map faces (+X, -X, +Y, -Y, +Z, -Z)
+    writeCubeFaces(texture, faces, options) {
+        const bytes = faces.map((face) => ArrayBuffer.isView(face)
+            ? Buffer.from(face.buffer, face.byteOffset, face.byteLength)
+            : Buffer.from(face))
+        return this._native.writeCubeFaces(texture, bytes, options)
+    }
+
+    /**
+     * Write data to a texture (WebGPU standard API)
+     *
+     * writeTexture(
+     *   { texture, mipLevel, origin: { x, y, z }, aspect },
+     *   data,
+     *   { offset, bytesPerRow, rowsPerImage },
+     *   { width, height, depthOrArrayLayers }
+     * )
+     */
     writeTexture(destination, data, dataLayout, size) {
-        return this._native.writeTexture(destination, data, dataLayout, size)
+        const bytes = ArrayBuffer.isView(data)
+            ? Buffer.from(data.buffer, data.byteOffset, data.byteLength)
+            : Buffer.from(data)
+        return this._native.writeTexture(
+            destination.texture,
+            { mipLevel: destination.mipLevel, origin: normalizeOrigin3D(destination.origin), aspect: destination.aspect },
+            bytes,
+            { offset: dataLayout.offset, bytesPerRow: dataLayout.bytesPerRow, rowsPerImage: dataLayout.rowsPerImage },
+            normalizeExtent3D(size)
+        )
+    }
+
+    /**
+     * Copy a decoded image into a texture (WebGPU standard API)
+     *
+     * `source.source` is `{ data, width, height }` with unpremultiplied RGBA8
+     * pixels, e.g. an ImageData or the raw output of an image decoder.
+     * `source.flipY`, `destination.premultipliedAlpha` and
+     * `destination.colorSpace` ('srgb' or 'srgb-linear') are applied on the
+     * GPU during the upload.
+     */
+    copyExternalImageToTexture(source, destination, copySize) {
+        const image = source.source
+        const data = ArrayBuffer.isView(image.data)
+            ? Buffer.from(image.data.buffer, image.data.byteOffset, image.data.byteLength)
+            : Buffer.from(image.data)
+        const { x, y } = normalizeOrigin3D(source.origin)
+        return this._native.copyExternalImageToTexture(
+            { data, width: image.width, height: image.height, origin: { x, y }, flipY: source.flipY },
+            destination.texture,
+            { mipLevel: destination.mipLevel, origin: normalizeOrigin3D(destination.origin), aspect: destination.aspect },
+            { premultipliedAlpha: destination.premultipliedAlpha, colorSpace: destination.colorSpace },
+            normalizeExtent3D(copySize)
+        )
     }
 
     onSubmittedWorkDone() {
@@ -150,6 +349,22 @@ class GpuComputePass {
         return this._native.dispatchWorkgroups(x, y, z)
     }
 
+    // Extension: ceil-divided workgroup counts for x * y * z invocations
+    dispatchThreads(x, y, z, workgroupSize) {
+        return this._native.dispatchThreads(x, y, z, workgroupSize)
+    }
+
+    // Extension: one dispatch per [x, y, z] triple in a Uint32Array
+    dispatchWorkgroupsBatch(counts, bindGroupIndex, bindGroup, dynamicOffsets) {
+        return this._native.dispatchWorkgroupsBatch(counts, bindGroupIndex, bindGroup, dynamicOffsets)
+    }
+
+    // Extension: replay a binary command stream in one call
+    executeCommandStream(stream, pipelines, bindGroups, buffers) {
+        const nativeBuffers = buffers && buffers.map((buffer) => buffer._native || buffer)
+        return this._native.executeCommandStream(stream, pipelines, bindGroups, nativeBuffers)
+    }
+
     dispatchWorkgroupsIndirect(indirectBuffer, indirectOffset) {
         // Unwrap GpuBuffer if needed
         const nativeBuffer = indirectBuffer._native || indirectBuffer
@@ -215,6 +430,21 @@ class GpuRenderPass {
         return this._native.drawIndexed(indexCount, instanceCount, firstIndex, baseVertex, firstInstance)
     }
 
+    // Extension: many draws packed into a Uint32Array, recorded in one call
+    drawBatch(draws, bindGroupIndex, bindGroup, dynamicOffsets) {
+        return this._native.drawBatch(draws, bindGroupIndex, bindGroup, dynamicOffsets)
+    }
+
+    drawIndexedBatch(draws, bindGroupIndex, bindGroup, dynamicOffsets) {
+        return this._native.drawIndexedBatch(draws, bindGroupIndex, bindGroup, dynamicOffsets)
+    }
+
+    // Extension: replay a binary command stream in one call
+    executeCommandStream(stream, pipelines, bindGroups, buffers) {
+        const nativeBuffers = buffers && buffers.map((buffer) => buffer._native || buffer)
+        return this._native.executeCommandStream(stream, pipelines, bindGroups, nativeBuffers)
+    }
+
     drawIndirect(indirectBuffer, indirectOffset) {
         // Unwrap GpuBuffer if needed
         const nativeBuffer = indirectBuffer._native || indirectBuffer
@@ -264,6 +494,68 @@ class GpuRenderPass {
     }
 }
 
+/**
+ * WebGPU-standard GpuRenderBundleEncoder wrapper
+ *
+ * Unwraps GpuBuffer objects before passing to native methods.
+ */
+class GpuRenderBundleEncoder {
+    constructor(nativeEncoder) {
+        this._native = nativeEncoder
+    }
+
+    setPipeline(pipeline) {
+        return this._native.setPipeline(pipeline)
+    }
+
+    setVertexBuffer(slot, buffer, offset, size) {
+        return this._native.setVertexBuffer(slot, buffer._native || buffer, offset, size)
+    }
+
+    setIndexBuffer(buffer, indexFormat, offset, size) {
+        return this._native.setIndexBuffer(buffer._native || buffer, indexFormat, offset, size)
+    }
+
+    setBindGroup(index, bindGroup, dynamicOffsets) {
+        return this._native.setBindGroup(index, bindGroup, dynamicOffsets)
+    }
+
+    draw(vertexCount, instanceCount, firstVertex, firstInstance) {
+        return this._native.draw(vertexCount, instanceCount, firstVertex, firstInstance)
+    }
+
+    drawIndexed(indexCount, instanceCount, firstIndex, baseVertex, firstInstance) {
+        return this._native.drawIndexed(indexCount, instanceCount, firstIndex, baseVertex, firstInstance)
+    }
+
+    // Extension: many draws packed into a Uint32Array, recorded in one call
+    drawBatch(draws, bindGroupIndex, bindGroup, dynamicOffsets) {
+        return this._native.drawBatch(draws, bindGroupIndex, bindGroup, dynamicOffsets)
+    }
+
+    drawIndexedBatch(draws, bindGroupIndex, bindGroup, dynamicOffsets) {
+        return this._native.drawIndexedBatch(draws, bindGroupIndex, bindGroup, dynamicOffsets)
+    }
+
+    // Extension: replay a binary command stream in one call
+    executeCommandStream(stream, pipelines, bindGroups, buffers) {
+        const nativeBuffers = buffers && buffers.map((buffer) => buffer._native || buffer)
+        return this._native.executeCommandStream(stream, pipelines, bindGroups, nativeBuffers)
+    }
+
+    drawIndirect(indirectBuffer, indirectOffset) {
+        return this._native.drawIndirect(indirectBuffer._native || indirectBuffer, indirectOffset)
+    }
+
+    drawIndexedIndirect(indirectBuffer, indirectOffset) {
+        return this._native.drawIndexedIndirect(indirectBuffer._native || indirectBuffer, indirectOffset)
+    }
+
+    finish(descriptor) {
+        return this._native.finish(descriptor)
+    }
+}
+
 /**
  * WebGPU-standard GpuCommandEncoder wrapper
  */
@@ -290,18 +582,93 @@ class GpuCommandEncoder {
         return this._native.copyBufferToBuffer(srcBuffer, sourceOffset, dstBuffer, destinationOffset, size)
     }
 
+    /**
+     * Copy buffer data into a texture
+     *
+     * Standard signature:
+     * copyBufferToTexture(
+     *   { buffer, offset, bytesPerRow, rowsPerImage },
+     *   { texture, mipLevel, origin: { x, y, z }, aspect },
+     *   { width, height, depthOrArrayLayers }
+     * )
+     *
+     * The positional form (source, sourceOffset, bytesPerRow, ...) is still accepted.
+     */
     copyBufferToTexture(source, sourceOffset, bytesPerRow, rowsPerImage, destination, mipLevel, originX, originY, originZ, width, height, depth) {
+        if (sourceOffset && sourceOffset.texture) {
+            const [buffer, texture, size] = [source, sourceOffset, bytesPerRow]
+            const origin = normalizeOrigin3D(texture.origin)
+            const extent = normalizeExtent3D(size)
+            return this._native.copyBufferToTexture(
+                buffer.buffer._native || buffer.buffer, buffer.offset || 0, buffer.bytesPerRow, buffer.rowsPerImage,
+                texture.texture._native || texture.texture, texture.mipLevel, origin.x, origin.y, origin.z,
+                extent.width, extent.height, extent.depthOrArrayLayers, texture.aspect
+            )
+        }
+
         // Unwrap GpuBuffer if needed
         const srcBuffer = source._native || source
         return this._native.copyBufferToTexture(srcBuffer, sourceOffset, bytesPerRow, rowsPerImage, destination, mipLevel, originX, originY, originZ, width, height, depth)
     }
 
+    /**
+     * Copy texture data into a buffer
+     *
+     * Standard signature:
+     * copyTextureToBuffer(
+     *   { texture, mipLevel, origin: { x, y, z }, aspect },
+     *   { buffer, offset, bytesPerRow, rowsPerImage },
+     *   { width, height, depthOrArrayLayers }
+     * )
+     *
+     * The positional form (source, mipLevel, originX, ...) is still accepted.
+     */
     copyTextureToBuffer(source, mipLevel, originX, originY, originZ, destination, destinationOffset, bytesPerRow, rowsPerImage, width, height, depth) {
+        if (source && source.texture) {
+            const [texture, buffer, size] = [source, mipLevel, originX]
+            const origin = normalizeOrigin3D(texture.origin)
+            const extent = normalizeExtent3D(size)
+            return this._native.copyTextureToBuffer(
+                texture.texture._native || texture.texture, texture.mipLevel, origin.x, origin.y, origin.z,
+                buffer.buffer._native || buffer.buffer, buffer.offset || 0, buffer.bytesPerRow, buffer.rowsPerImage,
+                extent.width, extent.height, extent.depthOrArrayLayers, texture.aspect
+            )
+        }
+
         // Unwrap GpuBuffer if needed
         const dstBuffer = destination._native || destination
         return this._native.copyTextureToBuffer(source, mipLevel, originX, originY, originZ, dstBuffer, destinationOffset, bytesPerRow, rowsPerImage, width, height, depth)
     }
 
+    /**
+     * Copy data between textures
+     *
+     * Standard signature:
+     * copyTextureToTexture(
+     *   { texture, mipLevel, origin: { x, y, z }, aspect },
+     *   { texture, mipLevel, origin: { x, y, z }, aspect },
+     *   { width, height, depthOrArrayLayers }
+     * )
+     */
+    copyTextureToTexture(source, destination, size) {
+        return this._native.copyTextureToTexture(
+            source.texture._native || source.texture,
+            { mipLevel: source.mipLevel, origin: normalizeOrigin3D(source.origin), aspect: source.aspect },
+            destination.texture._native || destination.texture,
+            { mipLevel: destination.mipLevel, origin: normalizeOrigin3D(destination.origin), aspect: destination.aspect },
+            normalizeExtent3D(size)
+        )
+    }
+
+    /**
+     * Clear texture subresources to zero (needs the 'wgpu-clear-texture' feature)
+     *
+     * clearTexture(texture, { aspect, baseMipLevel, mipLevelCount, baseArrayLayer, arrayLayerCount })
+     */
+    clearTexture(texture, range) {
+        return this._native.clearTexture(texture._native || texture, range)
+    }
+
     beginComputePass(descriptor) {
         const nativePass = this._native.beginComputePass(descriptor)
         return new GpuComputePass(nativePass)
@@ -326,7 +693,16 @@ class GpuCommandEncoder {
         const colorResolveViews = []
         const colorAttachments = []
 
-        for (const attachment of descriptor.colorAttachments) {
+        // Depth-only passes (shadow maps, depth prepass) may omit color attachments
+        for (const attachment of descriptor.colorAttachments || []) {
+            // Sparse attachments: null skips this color location
+            if (!attachment) {
+                colorViews.push(null)
+                colorResolveViews.push(null)
+                colorAttachments.push(null)
+                continue
+            }
+
             colorViews.push(attachment.view)
             colorResolveViews.push(attachment.resolveTarget || null)
 
@@ -334,7 +710,8 @@ class GpuCommandEncoder {
             colorAttachments.push({
                 clearValue: attachment.clearValue,
                 loadOp: attachment.loadOp,
-                storeOp: attachment.storeOp
+                storeOp: attachment.storeOp,
+                sampleCount: attachment.sampleCount
             })
         }
 
@@ -346,7 +723,9 @@ class GpuCommandEncoder {
             depthStoreOp: descriptor.depthStencilAttachment.depthStoreOp,
             stencilClearValue: descriptor.depthStencilAttachment.stencilClearValue,
             stencilLoadOp: descriptor.depthStencilAttachment.stencilLoadOp,
-            stencilStoreOp: descriptor.depthStencilAttachment.stencilStoreOp
+            stencilStoreOp: descriptor.depthStencilAttachment.stencilStoreOp,
+            depthReadOnly: descriptor.depthStencilAttachment.depthReadOnly,
+            stencilReadOnly: descriptor.depthStencilAttachment.stencilReadOnly
         } : undefined
 
         // Call flattened native API
@@ -366,302 +745,2584 @@ class GpuCommandEncoder {
     finish() {
         return this._native.finish()
     }
+
+    // Extension: drop an unfinished encoder and its commands
+    discard() {
+        return this._native.discard()
+    }
+
+    [disposeSymbol]() {
+        this.discard()
+    }
+
+    async [asyncDisposeSymbol]() {
+        this.discard()
+    }
 }
 
 /**
- * WebGPU-standard GpuDevice wrapper
+ * GpuPrefixSum wrapper
+ *
+ * Unwraps GpuCommandEncoder and GpuBuffer objects before passing to native methods.
  */
-class GpuDevice {
-    constructor(nativeDevice) {
-        this._native = nativeDevice
+class GpuPrefixSum {
+    constructor(nativePrefixSum) {
+        this._native = nativePrefixSum
     }
 
-    // Getters - wrap queue to handle GpuBuffer unwrapping
-    get queue() {
-        if (!this._queue) {
-            this._queue = new GpuQueue(this._native.queue)
-        }
-        return this._queue
+    encode(encoder, input, output, count, inclusive) {
+        return this._native.encode(encoder._native || encoder, input._native || input, output._native || output, count, inclusive)
     }
 
-    get features() {
-        return this._native.features
+    run(input, output, count, inclusive) {
+        return this._native.run(input._native || input, output._native || output, count, inclusive)
     }
+}
 
-    get limits() {
-        return this._native.limits
+/**
+ * GpuRadixSort wrapper
+ *
+ * Unwraps GpuCommandEncoder and GpuBuffer objects before passing to native methods.
+ */
+class GpuRadixSort {
+    constructor(nativeRadixSort) {
+        this._native = nativeRadixSort
     }
 
-    get label() {
-        return this._native.label
+    encode(encoder, keys, count, values, keyBits) {
+        return this._native.encode(encoder._native || encoder, keys._native || keys, count, values ? (values._native || values) : null, keyBits)
     }
 
-    // Error scopes - pass through
-    pushErrorScope(filter) {
-        return this._native.pushErrorScope(filter)
+    run(keys, count, values, keyBits) {
+        return this._native.run(keys._native || keys, count, values ? (values._native || values) : null, keyBits)
     }
+}
 
-    popErrorScope() {
-        return this._native.popErrorScope()
+/**
+ * GpuBlur wrapper
+ *
+ * Unwraps GpuCommandEncoder objects before passing to native methods.
+ */
+class GpuBlur {
+    constructor(nativeBlur) {
+        this._native = nativeBlur
     }
 
-    // Destroy device (WebGPU standard method)
-    destroy() {
-        return this._native.destroy()
+    get radius() {
+        return this._native.radius
     }
 
-    // Non-standard helper method for examples (poll device for completion)
-    // WebGPU standard: use device.queue.onSubmittedWorkDone() instead
-    poll(forceWait) {
-        return this._native.poll(forceWait)
+    get sigma() {
+        return this._native.sigma
     }
 
-    // Simple pass-through methods
-    createBuffer(descriptor) {
-        const nativeBuffer = this._native.createBuffer(descriptor)
-        return new GpuBuffer(nativeBuffer)
+    encode(encoder, source, destination) {
+        return this._native.encode(encoder._native || encoder, source, destination)
     }
 
-    createShaderModule(descriptor) {
-        return this._native.createShaderModule(descriptor)
+    run(source, destination) {
+        return this._native.run(source, destination)
     }
+}
 
-    createTexture(descriptor) {
-        return this._native.createTexture(descriptor)
+/**
+ * GpuHistogram wrapper
+ *
+ * Unwraps GpuCommandEncoder and GpuBuffer objects before passing to native methods.
+ */
+class GpuHistogram {
+    constructor(nativeHistogram) {
+        this._native = nativeHistogram
     }
 
-    createSampler(descriptor) {
-        return this._native.createSampler(descriptor)
+    get bins() {
+        return this._native.bins
     }
 
-    createQuerySet(descriptor) {
-        return this._native.createQuerySet(descriptor)
+    get outputSize() {
+        return this._native.outputSize
     }
 
-    createCommandEncoder(descriptor) {
-        const nativeEncoder = this._native.createCommandEncoder(descriptor)
-        return new GpuCommandEncoder(nativeEncoder)
+    encode(encoder, source, output) {
+        return this._native.encode(encoder._native || encoder, source, output._native || output)
     }
 
-    createBindGroupLayout(descriptor) {
-        return this._native.createBindGroupLayout(descriptor)
+    encodeBuffer(encoder, pixels, count, output) {
+        return this._native.encodeBuffer(encoder._native || encoder, pixels._native || pixels, count, output._native || output)
     }
 
-    /**
-     * Create bind group (WebGPU standard API)
-     *
-     * Standard signature:
-     * createBindGroup({
-     *   layout: GpuBindGroupLayout,
-     *   entries: [
-     *     { binding: 0, resource: { buffer: GpuBuffer } },
-     *     { binding: 1, resource: GpuTextureView },
-     *     { binding: 2, resource: GpuSampler }
-     *   ]
-     * })
-     *
-     * Transforms to flattened format internally
-     */
-    createBindGroup(descriptor) {
-        const entries = []
-        const buffers = []
-        const textures = []
-        const samplers = []
+    compute(source) {
+        return this._native.compute(source)
+    }
 
-        for (const entry of descriptor.entries) {
-            const resource = entry.resource
+    computeBuffer(pixels, count) {
+        return this._native.computeBuffer(pixels._native || pixels, count)
+    }
+}
 
-            // Texture view binding (check first - resource is the view directly)
-            if (resource.constructor?.name === 'GpuTextureView') {
-                entries.push({
-                    binding: entry.binding,
-                    resourceType: 'texture'
-                })
-                textures.push(resource)
-            }
-            // Sampler binding (check second)
-            else if (resource.constructor?.name === 'GpuSampler') {
-                entries.push({
-                    binding: entry.binding,
-                    resourceType: 'sampler'
-                })
-                samplers.push(resource)
-            }
-            // Buffer binding (resource is an object with .buffer property)
-            else if (resource.buffer) {
-                const entry_obj = {
-                    binding: entry.binding,
-                    resourceType: 'buffer'
-                }
-                // Only add offset/size if provided (napi-rs Option<i64> requires undefined, not null)
-                if (resource.offset !== undefined) entry_obj.offset = resource.offset
-                if (resource.size !== undefined) entry_obj.size = resource.size
-                entries.push(entry_obj)
-                // Unwrap GpuBuffer if needed
-                const nativeBuffer = resource.buffer._native || resource.buffer
-                buffers.push(nativeBuffer)
-            }
-            else {
+/**
+ * GpuDraw2D wrapper
+ *
+ * Returns the wrapper from drawing and transform calls so they can be chained,
+ * and unwraps GpuCommandEncoder objects before passing to native methods.
+ */
+class GpuDraw2D {
+    constructor(nativeDraw2D) {
+        this._native = nativeDraw2D
+    }
+
+    rect(x, y, width, height, color) {
+        this._native.rect(x, y, width, height, color)
+        return this
+    }
+
+    line(x0, y0, x1, y1, color, width) {
+        this._native.line(x0, y0, x1, y1, color, width)
+        return this
+    }
+
+    quad(points, color) {
+        this._native.quad(Array.from(points), color)
+        return this
+    }
+
+    sprite(texture, x, y, width, height, options) {
+        this._native.sprite(texture, x, y, width, height, options)
+        return this
+    }
+
+    translate(x, y) {
+        this._native.translate(x, y)
+        return this
+    }
+
+    scale(x, y) {
+        this._native.scale(x, y)
+        return this
+    }
+
+    rotate(radians) {
+        this._native.rotate(radians)
+        return this
+    }
+
+    setTransform(a, b, c, d, e, f) {
+        this._native.setTransform(a, b, c, d, e, f)
+        return this
+    }
+
+    resetTransform() {
+        this._native.resetTransform()
+        return this
+    }
+
+    save() {
+        this._native.save()
+        return this
+    }
+
+    restore() {
+        this._native.restore()
+        return this
+    }
+
+    encode(encoder, target, options) {
+        return this._native.encode(encoder._native || encoder, target, options)
+    }
+
+    run(target, options) {
+        return this._native.run(target, options)
+    }
+}
+
+/**
+ * GpuFont wrapper
+ *
+ * Unwraps GpuRenderPass objects before passing to native methods.
+ */
+class GpuFont {
+    constructor(nativeFont) {
+        this._native = nativeFont
+    }
+
+    get size() {
+        return this._native.size
+    }
+
+    get lineHeight() {
+        return this._native.lineHeight
+    }
+
+    measureText(text, options) {
+        return this._native.measureText(text, options)
+    }
+
+    drawText(pass, text, x, y, options) {
+        return this._native.drawText(pass._native || pass, text, x, y, options)
+    }
+}
+
+// Typed arrays `GpuTensor.fromArray` passes as raw elements of each dtype
+const TENSOR_ARRAY_TYPES = { f32: Float32Array, f16: Uint16Array, i32: Int32Array, u8: Uint8Array }
+
+/**
+ * GpuTensor wrapper
+ *
+ * Converts array data for fromArray() and wraps views and the backing buffer.
+ */
+class GpuTensor {
+    constructor(nativeTensor) {
+        this._native = nativeTensor
+    }
+
+    /**
+     * Create a tensor holding `data`
+     *
+     * Typed arrays of the dtype (Uint16Array holding half-float bits for
+     * "f16") are copied as is; other arrays are converted element by element.
+     * The dtype defaults to that of an Int32Array or Uint8Array, else "f32".
+     */
+    static fromArray(device, data, descriptor = {}) {
+        const dtype = descriptor.dtype || (data instanceof Int32Array ? 'i32' : data instanceof Uint8Array ? 'u8' : 'f32')
+        const ArrayType = TENSOR_ARRAY_TYPES[dtype]
+        const values = ArrayType && data instanceof ArrayType
+            ? Buffer.from(data.buffer, data.byteOffset, data.byteLength)
+            : Array.from(data)
+        return new GpuTensor(native.GpuTensor.fromArray(device._native || device, values, { ...descriptor, dtype }))
+    }
+
+    get shape() {
+        return this._native.shape
+    }
+
+    get strides() {
+        return this._native.strides
+    }
+
+    get dtype() {
+        return this._native.dtype
+    }
+
+    get size() {
+        return this._native.size
+    }
+
+    get byteOffset() {
+        return this._native.byteOffset
+    }
+
+    get isContiguous() {
+        return this._native.isContiguous
+    }
+
+    get buffer() {
+        return new GpuBuffer(this._native.buffer)
+    }
+
+    slice(dimension, start, end) {
+        return new GpuTensor(this._native.slice(dimension, start, end))
+    }
+
+    reshape(shape) {
+        return new GpuTensor(this._native.reshape(shape))
+    }
+
+    toArray() {
+        return this._native.toArray()
+    }
+}
+
+/**
+ * GpuUniformWriter wrapper
+ *
+ * Unwraps GpuQueue and GpuBuffer objects before passing to native methods.
+ */
+class GpuUniformWriter {
+    constructor(nativeWriter) {
+        this._native = nativeWriter
+    }
+
+    get size() {
+        return this._native.size
+    }
+
+    set(values) {
+        this._native.set(values)
+        return this
+    }
+
+    upload(queue, buffer, offset) {
+        return this._native.upload(queue._native || queue, buffer._native || buffer, offset)
+    }
+
+    getData() {
+        return this._native.getData()
+    }
+}
+
+/**
+ * GpuMatmul wrapper
+ *
+ * Unwraps GpuCommandEncoder and GpuBuffer objects before passing to native methods.
+ */
+class GpuMatmul {
+    constructor(nativeMatmul) {
+        this._native = nativeMatmul
+    }
+
+    encode(encoder, a, b, output, dims) {
+        return this._native.encode(encoder._native || encoder, a._native || a, b._native || b, output._native || output, dims)
+    }
+
+    run(a, b, output, dims) {
+        return this._native.run(a._native || a, b._native || b, output._native || output, dims)
+    }
+
+    matmul(a, b, dims) {
+        return new GpuBuffer(this._native.matmul(a._native || a, b._native || b, dims))
+    }
+}
+
+/**
+ * GpuComputeRunner wrapper
+ *
+ * Passes typed array parameters to native code without copying them.
+ */
+class GpuComputeRunner {
+    constructor(nativeRunner) {
+        this._native = nativeRunner
+    }
+
+    // One native call: writes `params` (any ArrayBuffer or view), then
+    // dispatches `workgroups` (a count or [x, y?, z?]) and submits
+    run(params, workgroups) {
+        const bytes = params == null
+            ? null
+            : ArrayBuffer.isView(params)
+                ? Buffer.from(params.buffer, params.byteOffset, params.byteLength)
+                : Buffer.from(params)
+        return this._native.run(bytes, workgroups)
+    }
+}
+
+/**
+ * GpuFrameCapture wrapper
+ *
+ * Adds async iteration over the frames and a Readable stream of their bytes,
+ * and holds capture() back while maxQueuedFrames frames wait to be taken.
+ */
+class GpuFrameCapture {
+    constructor(nativeCapture, maxQueuedFrames) {
+        this._native = nativeCapture
+        this._maxQueuedFrames = maxQueuedFrames
+        // Resolvers of capture() calls waiting for next() to take a frame
+        this._waiting = []
+    }
+
+    get label() {
+        return this._native.label
+    }
+
+    get queuedFrames() {
+        return this._native.queuedFrames
+    }
+
+    _wake() {
+        for (const resolve of this._waiting.splice(0)) resolve()
+    }
+
+    // Waiting only ends when something takes frames, so a consumer such as
+    // stream().pipe(ffmpeg.stdin) must run alongside the render loop
+    async capture(texture, timestamp) {
+        while (this._native.queuedFrames >= this._maxQueuedFrames) {
+            await new Promise((resolve) => this._waiting.push(resolve))
+        }
+        return this._native.capture(texture, timestamp)
+    }
+
+    async next() {
+        const frame = await this._native.next()
+        this._wake()
+        return frame
+    }
+
+    end() {
+        this._native.end()
+        this._wake()
+    }
+
+    async *[Symbol.asyncIterator]() {
+        for (let frame = await this.next(); frame; frame = await this.next()) {
+            yield frame
+        }
+    }
+
+    // Bytes of every frame in order, ending after end(); e.g. for ffmpeg's stdin
+    stream() {
+        const capture = this
+        return Readable.from((async function* () {
+            for await (const frame of capture) yield frame.data
+        })(), { objectMode: false })
+    }
+}
+
+/**
+ * GpuChunkedBuffer wrapper
+ *
+ * Wraps the chunk buffers and builds a bind group per chunk from the same
+ * entries, so a pipeline written for one binding runs over every chunk.
+ */
+class GpuChunkedBuffer {
+    constructor(nativeChunked, device) {
+        this._native = nativeChunked
+        this._device = device
+    }
+
+    get label() {
+        return this._native.label
+    }
+
+    get size() {
+        return this._native.size
+    }
+
+    get elementSize() {
+        return this._native.elementSize
+    }
+
+    get chunkCount() {
+        return this._native.chunkCount
+    }
+
+    // { index, byteOffset, byteSize, elementOffset, elementCount, buffer } per chunk
+    get chunks() {
+        return this._native.chunks.map((chunk) => ({ ...chunk, buffer: this.buffer(chunk.index) }))
+    }
+
+    get infoBuffer() {
+        return new GpuBuffer(this._native.infoBuffer)
+    }
+
+    get infoStride() {
+        return this._native.infoStride
+    }
+
+    buffer(index) {
+        return new GpuBuffer(this._native.buffer(index))
+    }
+
+    write(offset, data) {
+        const bytes = ArrayBuffer.isView(data)
+            ? Buffer.from(data.buffer, data.byteOffset, data.byteLength)
+            : Buffer.from(data)
+        return this._native.write(offset, bytes)
+    }
+
+    read(offset, size) {
+        return this._native.read(offset, size)
+    }
+
+    // { layout, binding, infoBinding?, entries?, label? }: entries are the
+    // same for every chunk; the chunk's buffer is bound at `binding` and its
+    // ChunkInfo, if asked for, at `infoBinding`
+    createBindGroups(descriptor) {
+        const { layout, binding, infoBinding, entries = [], label } = descriptor
+        return this._native.chunks.map((chunk) => {
+            const chunkEntries = [...entries, { binding, resource: { buffer: this._native.buffer(chunk.index) } }]
+            if (infoBinding !== undefined) {
+                chunkEntries.push({
+                    binding: infoBinding,
+                    resource: { buffer: this._native.infoBuffer, offset: chunk.index * this.infoStride, size: 16 }
+                })
+            }
+            return this._device.createBindGroup({ label, layout, entries: chunkEntries })
+        })
+    }
+
+    // { pipeline, bindGroups, group?, sharedBindGroups?, workgroupSize?, label? }:
+    // bindGroups has one group per chunk, set at `group`; sharedBindGroups
+    // are set at their array indices for every chunk
+    dispatch(descriptor) {
+        const { pipeline, bindGroups, sharedBindGroups = [], ...rest } = descriptor
+        const shared = []
+        const sharedGroups = []
+        sharedBindGroups.forEach((bindGroup, index) => {
+            if (bindGroup) {
+                shared.push(bindGroup)
+                sharedGroups.push(index)
+            }
+        })
+        return this._native.dispatch(pipeline, bindGroups, shared, { ...rest, sharedGroups })
+    }
+}
+
+/**
+ * GpuTimer wrapper
+ *
+ * Unwraps GpuCommandEncoder objects and adds wrap() for timing a block of encoding.
+ */
+class GpuTimer {
+    constructor(nativeTimer) {
+        this._native = nativeTimer
+    }
+
+    get capacity() {
+        return this._native.capacity
+    }
+
+    begin(encoder, label) {
+        return this._native.begin(encoder._native || encoder, label)
+    }
+
+    end(encoder, span) {
+        return this._native.end(encoder._native || encoder, span)
+    }
+
+    // Time everything fn(encoder) records into encoder; returns fn's result
+    wrap(encoder, label, fn) {
+        const span = this.begin(encoder, label)
+        try {
+            return fn(encoder)
+        } finally {
+            this.end(encoder, span)
+        }
+    }
+
+    results() {
+        return this._native.results()
+    }
+}
+
+// layout: 'auto' derives the layout from the shaders, as does omitting it
+function pipelineLayout(layout) {
+    return layout === 'auto' ? null : layout
+}
+
+// WebGPU standard error classes, resolved by popErrorScope()
+class GPUError {
+    constructor(message) {
+        this.message = message
+    }
+}
+
+class GPUValidationError extends GPUError {}
+
+class GPUOutOfMemoryError extends GPUError {}
+
+// Turn a native { type, message } scope error into its GPUError class
+function scopeError(error) {
+    if (!error) return null
+    return error.type === 'out-of-memory' ? new GPUOutOfMemoryError(error.message) : new GPUValidationError(error.message)
+}
+
+// Events GpuDevice re-emits from the native device; its queue emits
+// 'submit' and 'submissionComplete'
+const DEVICE_EVENTS = ['deviceLost', 'uncapturedError', 'oomWarning']
+
+/**
+ * WebGPU-standard GpuDevice wrapper
+ *
+ * Also an EventEmitter for GPU timeline events (extension): 'submit',
+ * 'submissionComplete', 'deviceLost', 'uncapturedError' and 'oomWarning'.
+ * The native device only reports errors and loss while one of their
+ * listeners is registered.
+ */
+class GpuDevice extends EventEmitter {
+    constructor(nativeDevice) {
+        super()
+        this._native = nativeDevice
+        this._forwardingEvents = false
+        this.on('newListener', (type) => {
+            if (DEVICE_EVENTS.includes(type) && !this._forwardingEvents) {
+                this._native.setEventCallback((event) => this._emitDeviceEvent(event))
+                this._forwardingEvents = true
+            }
+        })
+        this.on('removeListener', () => {
+            if (this._forwardingEvents && DEVICE_EVENTS.every((type) => this.listenerCount(type) === 0)) {
+                this._native.setEventCallback(null)
+                this._forwardingEvents = false
+            }
+        })
+    }
+
+    _emitDeviceEvent(event) {
+        // Errors nobody listens for still get reported, as without listeners
+        if (!this.emit(event.type, event) && event.type === 'uncapturedError') {
+            console.error(event.message)
+        }
+    }
+
+    // Getters - wrap queue to handle GpuBuffer unwrapping
+    get queue() {
+        if (!this._queue) {
+            this._queue = new GpuQueue(this._native.queue, this)
+        }
+        return this._queue
+    }
+
+    get features() {
+        return this._native.features
+    }
+
+    get limits() {
+        return this._native.limits
+    }
+
+    get label() {
+        return this._native.label
+    }
+
+    // WebGPU standard: resolves with { reason, message } when the device is
+    // lost, including through destroy()
+    get lost() {
+        if (!this._lost) {
+            this._lost = new Promise((resolve) => {
+                this._resolveLost = resolve
+                const lost = this._native.lostInfo
+                if (lost) return resolve(lost)
+                this.once('deviceLost', (event) => resolve({ reason: event.reason, message: event.message }))
+            })
+        }
+        return this._lost
+    }
+
+    // WebGPU standard: GPUUncapturedErrorEvent listeners, delivered through
+    // the 'uncapturedError' event as { type: 'uncapturederror', error: { message } }
+    addEventListener(type, listener) {
+        if (type !== 'uncapturederror' || this._errorListeners?.has(listener)) return
+        const forward = (event) => {
+            const errorEvent = { type, error: { message: event.message } }
+            typeof listener === 'function' ? listener(errorEvent) : listener.handleEvent(errorEvent)
+        }
+        this._errorListeners = this._errorListeners || new Map()
+        this._errorListeners.set(listener, forward)
+        this.on('uncapturedError', forward)
+    }
+
+    removeEventListener(type, listener) {
+        const forward = type === 'uncapturederror' && this._errorListeners?.get(listener)
+        if (!forward) return
+        this._errorListeners.delete(listener)
+        this.off('uncapturedError', forward)
+    }
+
+    get onuncapturederror() {
+        return this._onuncapturederror || null
+    }
+
+    set onuncapturederror(handler) {
+        if (this._onuncapturederror) this.removeEventListener('uncapturederror', this._onuncapturederror)
+        this._onuncapturederror = typeof handler === 'function' ? handler : null
+        if (this._onuncapturederror) this.addEventListener('uncapturederror', this._onuncapturederror)
+    }
+
+    // Error scopes - pass through
+    pushErrorScope(filter) {
+        return this._native.pushErrorScope(filter)
+    }
+
+    async popErrorScope() {
+        return scopeError(this._native.popErrorScope())
+    }
+
+    // Extension: make create calls fail on demand in tests
+    enableFaultInjection(options) {
+        return this._native.enableFaultInjection(options)
+    }
+
+    disableFaultInjection() {
+        return this._native.disableFaultInjection()
+    }
+
+    // Destroy device (WebGPU standard method)
+    destroy() {
+        this._native.destroy()
+        // Resolved here too: the event callback doesn't keep the process alive
+        this._resolveLost?.(this._native.lostInfo)
+    }
+
+    [disposeSymbol]() {
+        this.destroy()
+    }
+
+    // Waits for submitted work before destroying the device
+    async [asyncDisposeSymbol]() {
+        await this.queue.onSubmittedWorkDone()
+        this.destroy()
+    }
+
+    // Non-standard helper method for examples (poll device for completion)
+    // WebGPU standard: use device.queue.onSubmittedWorkDone() instead
+    poll(forceWait) {
+        return this._native.poll(forceWait)
+    }
+
+    // Extension: keep the device polled after each queue.submit() until it completes
+    setAutoMaintenance(enabled) {
+        return this._native.setAutoMaintenance(enabled)
+    }
+
+    // Extension: resolves once all submitted work (and pending writeBuffer data) is done
+    waitIdle() {
+        return this._native.waitIdle()
+    }
+
+    // Simple pass-through methods
+    // Extension: token for deviceFromHandle() in a worker thread
+    toHandle() {
+        return this._native.toHandle()
+    }
+
+    createBuffer(descriptor) {
+        const nativeBuffer = this._native.createBuffer(descriptor)
+        return new GpuBuffer(nativeBuffer)
+    }
+
+    // Extension: create a buffer holding `data` (like wgpu's create_buffer_init)
+    createBufferInit(descriptor) {
+        const { data } = descriptor
+        const bytes = ArrayBuffer.isView(data)
+            ? Buffer.from(data.buffer, data.byteOffset, data.byteLength)
+            : Buffer.from(data)
+        return new GpuBuffer(this._native.createBufferInit({ label: descriptor.label, usage: descriptor.usage }, bytes))
+    }
+
+    /**
+     * Create shader module
+     *
+     * Extensions: `defines`, `includePaths` and `resolveInclude(path, fromFile)`
+     * drive the WGSL preprocessor (#include, #define, #ifdef).
+     */
+    createShaderModule(descriptor) {
+        return this._native.createShaderModule(
+            {
+                label: descriptor.label,
+                code: descriptor.code,
+                defines: normalizeDefines(descriptor.defines),
+                includePaths: descriptor.includePaths
+            },
+            descriptor.resolveInclude
+        )
+    }
+
+    // Accepts the standard `size` ([w, h, d] or { width, height, depthOrArrayLayers })
+    // as well as flat width / height / depth fields
+    createTexture(descriptor) {
+        // Extension: initial `data` may be any ArrayBuffer or view
+        const { data } = descriptor
+        if (data !== undefined && !Buffer.isBuffer(data)) {
+            descriptor = {
+                ...descriptor,
+                data: ArrayBuffer.isView(data) ? Buffer.from(data.buffer, data.byteOffset, data.byteLength) : Buffer.from(data)
+            }
+        }
+        if (descriptor.size === undefined) {
+            return this._native.createTexture(descriptor)
+        }
+        const { size, viewFormats, ...rest } = descriptor
+        const extent = normalizeExtent3D(size)
+        return this._native.createTexture({
+            ...rest,
+            width: extent.width,
+            height: extent.height,
+            depth: extent.depthOrArrayLayers
+        })
+    }
+
+    // Extension: `target` may be a texture, surface texture or view to match
+    createMultisampleTexture(target, sampleCount, label) {
+        const view = typeof target.createView === 'function' ? target.createView() : target
+        return this._native.createMultisampleTexture(view, sampleCount, label)
+    }
+
+    createSampler(descriptor) {
+        return this._native.createSampler(descriptor)
+    }
+
+    createQuerySet(descriptor) {
+        return this._native.createQuerySet(descriptor)
+    }
+
+    createRenderBundleEncoder(descriptor) {
+        return new GpuRenderBundleEncoder(this._native.createRenderBundleEncoder(descriptor))
+    }
+
+    createCommandEncoder(descriptor) {
+        const nativeEncoder = this._native.createCommandEncoder(descriptor)
+        return new GpuCommandEncoder(nativeEncoder)
+    }
+
+    createBindGroupLayout(descriptor) {
+        return this._native.createBindGroupLayout(descriptor)
+    }
+
+    createBindGroupLayoutFromShader(module, group, label) {
+        return this._native.createBindGroupLayoutFromShader(module, group, label)
+    }
+
+    createUniformWriter(module, structName) {
+        return new GpuUniformWriter(this._native.createUniformWriter(module, structName))
+    }
+
+    // Shader module cache
+    setShaderModuleCacheEnabled(enabled) {
+        return this._native.setShaderModuleCacheEnabled(enabled)
+    }
+
+    clearShaderModuleCache() {
+        return this._native.clearShaderModuleCache()
+    }
+
+    getShaderModuleCacheStats() {
+        return this._native.getShaderModuleCacheStats()
+    }
+
+    // Compute utilities
+    createPrefixSum(descriptor) {
+        return new GpuPrefixSum(this._native.createPrefixSum(descriptor))
+    }
+
+    createRadixSort(descriptor) {
+        return new GpuRadixSort(this._native.createRadixSort(descriptor))
+    }
+
+    createMatmul(descriptor) {
+        return new GpuMatmul(this._native.createMatmul(descriptor))
+    }
+
+    // Texture utilities
+    createBlur(descriptor) {
+        return new GpuBlur(this._native.createBlur(descriptor))
+    }
+
+    createHistogram(descriptor) {
+        return new GpuHistogram(this._native.createHistogram(descriptor))
+    }
+
+    createDraw2D(descriptor) {
+        return new GpuDraw2D(this._native.createDraw2D(descriptor))
+    }
+
+    // Text
+    createFont(data, descriptor) {
+        const bytes = ArrayBuffer.isView(data)
+            ? Buffer.from(data.buffer, data.byteOffset, data.byteLength)
+            : Buffer.from(data)
+        return new GpuFont(this._native.createFont(bytes, descriptor))
+    }
+
+    // Tensors
+    createTensor(descriptor) {
+        return new GpuTensor(this._native.createTensor(descriptor))
+    }
+
+    blitTexture(encoder, source, destination, options) {
+        return this._native.blitTexture(encoder._native || encoder, source, destination, options)
+    }
+
+    convertPixels(encoder, input, output, count, options) {
+        return this._native.convertPixels(encoder._native || encoder, input._native || input, output._native || output, count, options)
+    }
+
+    createTimer(descriptor) {
+        return new GpuTimer(this._native.createTimer(descriptor))
+    }
+
+    // Extension: { pipeline, bindGroups?, params?, paramsOffset?, label? }; the
+    // runner's run(params, workgroups) replaces writeBuffer + encode + submit
+    createComputeRunner(descriptor) {
+        const { pipeline, bindGroups = [], params, ...rest } = descriptor
+        return new GpuComputeRunner(
+            this._native.createComputeRunner(rest, pipeline, bindGroups, params ? (params._native || params) : null)
+        )
+    }
+
+    // Extension: { pixelFormat?, bufferCount?, maxQueuedFrames?, label? };
+    // capture(texture) every frame, then take them with next(), for await or stream()
+    createFrameCapture(descriptor = {}) {
+        const { maxQueuedFrames = Infinity, ...rest } = descriptor
+        return new GpuFrameCapture(this._native.createFrameCapture(rest), maxQueuedFrames)
+    }
+
+    // Extension: { size, elementSize?, maxChunkSize?, usage?, label? }; stores
+    // datasets larger than a storage binding in chunks that each fit one
+    createChunkedBuffer(descriptor) {
+        return new GpuChunkedBuffer(this._native.createChunkedBuffer(descriptor), this)
+    }
+
+    /**
+     * Create bind group (WebGPU standard API)
+     *
+     * Standard signature:
+     * createBindGroup({
+     *   layout: GpuBindGroupLayout,
+     *   entries: [
+     *     { binding: 0, resource: { buffer: GpuBuffer } },
+     *     { binding: 1, resource: GpuTextureView },
+     *     { binding: 2, resource: GpuSampler }
+     *   ]
+     * })
+     *
+     * Transforms to flattened format internally
+     */
+    createBindGroup(descriptor) {
+        const entries = []
+        const buffers = []
+        const textures = []
+        const samplers = []
+
+        for (const entry of descriptor.entries) {
+            const resource = entry.resource
+
+            // Texture view binding (check first - resource is the view directly)
+            if (resource.constructor?.name === 'GpuTextureView') {
+                entries.push({
+                    binding: entry.binding,
+                    resourceType: 'texture'
+                })
+                textures.push(resource)
+            }
+            // Sampler binding (check second)
+            else if (resource.constructor?.name === 'GpuSampler') {
+                entries.push({
+                    binding: entry.binding,
+                    resourceType: 'sampler'
+                })
+                samplers.push(resource)
+            }
+            // Extension: texture view and sampler binding arrays
+            else if (Array.isArray(resource) && resource[0]?.constructor?.name === 'GpuTextureView') {
+                entries.push({ binding: entry.binding, resourceType: 'texture-array', count: resource.length })
+                textures.push(...resource)
+            }
+            else if (Array.isArray(resource) && resource[0]?.constructor?.name === 'GpuSampler') {
+                entries.push({ binding: entry.binding, resourceType: 'sampler-array', count: resource.length })
+                samplers.push(...resource)
+            }
+            // Extension: buffer binding array, one { buffer, offset?, size? } per element
+            else if (Array.isArray(resource)) {
+                entries.push({
+                    binding: entry.binding,
+                    resourceType: 'buffer-array',
+                    count: resource.length,
+                    offsets: resource.map(element => element.offset || 0),
+                    sizes: resource.map(element => element.size ?? null)
+                })
+                for (const element of resource) {
+                    buffers.push(element.buffer._native || element.buffer)
+                }
+            }
+            // Buffer binding (resource is an object with .buffer property)
+            else if (resource.buffer) {
+                const entry_obj = {
+                    binding: entry.binding,
+                    resourceType: 'buffer'
+                }
+                // Only add offset/size if provided (napi-rs Option<i64> requires undefined, not null)
+                if (resource.offset !== undefined) entry_obj.offset = resource.offset
+                if (resource.size !== undefined) entry_obj.size = resource.size
+                entries.push(entry_obj)
+                // Unwrap GpuBuffer if needed
+                const nativeBuffer = resource.buffer._native || resource.buffer
+                buffers.push(nativeBuffer)
+            }
+            else {
                 throw new Error(`Invalid bind group resource at binding ${entry.binding}: ${resource?.constructor?.name || typeof resource}`)
             }
-        }
+        }
+
+        // Call flattened native API
+        return this._native.createBindGroup(
+            { label: descriptor.label },
+            descriptor.layout,
+            entries,
+            buffers.length > 0 ? buffers : null,
+            textures.length > 0 ? textures : null,
+            samplers.length > 0 ? samplers : null
+        )
+    }
+
+    /**
+     * Create pipeline layout (WebGPU standard API)
+     *
+     * Standard signature:
+     * createPipelineLayout({
+     *   bindGroupLayouts: [layout1, layout2]
+     * })
+     */
+    createPipelineLayout(descriptor) {
+        return this._native.createPipelineLayout(
+            { label: descriptor.label },
+            descriptor.bindGroupLayouts || []
+        )
+    }
+
+    /**
+     * Create compute pipeline (WebGPU standard API)
+     *
+     * Standard signature:
+     * createComputePipeline({
+     *   layout: pipelineLayout,
+     *   compute: {
+     *     module: shaderModule,
+     *     entryPoint: 'main'
+     *   }
+     * })
+     */
+    createComputePipeline(descriptor) {
+        return this._native.createComputePipeline(
+            {
+                label: descriptor.label,
+                entryPoint: descriptor.compute.entryPoint
+            },
+            pipelineLayout(descriptor.layout),
+            descriptor.compute.module
+        )
+    }
+
+    // WebGPU standard: pipelines are created synchronously, so these resolve immediately
+    async createComputePipelineAsync(descriptor) {
+        return this.createComputePipeline(descriptor)
+    }
+
+    async createRenderPipelineAsync(descriptor) {
+        return this.createRenderPipeline(descriptor)
+    }
+
+    /**
+     * Create render pipeline (WebGPU standard API)
+     *
+     * Standard signature:
+     * createRenderPipeline({
+     *   layout: pipelineLayout,
+     *   vertex: {
+     *     module: vertexShader,
+     *     entryPoint: 'vs_main',
+     *     buffers: [...]
+     *   },
+     *   fragment: {
+     *     module: fragmentShader,
+     *     entryPoint: 'fs_main',
+     *     targets: [...]
+     *   },
+     *   primitive: {...},
+     *   depthStencil: {...},
+     *   multisample: {...}
+     * })
+     */
+    createRenderPipeline(descriptor) {
+        const pipelineDescriptor = {
+            label: descriptor.label,
+            vertex: {
+                entryPoint: descriptor.vertex.entryPoint,
+                buffers: descriptor.vertex.buffers
+            },
+            primitive: descriptor.primitive,
+            depthStencil: descriptor.depthStencil,
+            multisample: descriptor.multisample
+        }
+
+        // Fragment is optional
+        if (descriptor.fragment) {
+            pipelineDescriptor.fragment = {
+                entryPoint: descriptor.fragment.entryPoint,
+                targets: descriptor.fragment.targets
+            }
+        }
+
+        return this._native.createRenderPipeline(
+            pipelineDescriptor,
+            pipelineLayout(descriptor.layout),
+            descriptor.vertex.module,
+            descriptor.fragment?.module || null
+        )
+    }
+}
+
+/**
+ * Device recovery manager (extension)
+ *
+ * Holds the current device of an adapter. When the device is lost for any
+ * reason but destroy(), a new one is requested with the same descriptor,
+ * the registered creation callbacks run again on it in registration order,
+ * and 'recovered' is emitted with { device, lost, attempt }. After
+ * `maxAttempts` failed requests, 'recoveryFailed' is emitted with { error }.
+ */
+class GpuDeviceRecovery extends EventEmitter {
+    constructor(adapter, descriptor, device, options = {}) {
+        super()
+        this._adapter = adapter
+        this._descriptor = descriptor
+        this._maxAttempts = options.maxAttempts ?? 3
+        this._resources = []
+        this._recovering = null
+        this._watch(device)
+    }
+
+    // The current device; replaced on recovery
+    get device() {
+        return this._device
+    }
+
+    _watch(device) {
+        this._device = device
+        device.lost.then((lost) => {
+            if (this._device === device && lost.reason !== 'destroyed') {
+                // Failures are reported through 'recoveryFailed'
+                this.recover().catch(() => {})
+            }
+        })
+    }
+
+    // Run `create(device)` now and again after every recovery. The returned
+    // handle's `value` holds the latest result
+    register(create) {
+        const resource = { value: create(this._device), _create: create }
+        this._resources.push(resource)
+        return resource
+    }
+
+    unregister(resource) {
+        const index = this._resources.indexOf(resource)
+        if (index !== -1) this._resources.splice(index, 1)
+    }
+
+    // Replace the device now, destroying the current one if it isn't lost yet
+    recover() {
+        if (!this._recovering) {
+            this._recovering = this._recover().finally(() => {
+                this._recovering = null
+            })
+        }
+        return this._recovering
+    }
+
+    async _recover() {
+        const previous = this._device
+        if (!previous._native.lostInfo) previous.destroy()
+        const lost = previous._native.lostInfo
+        let error
+        for (let attempt = 1; attempt <= this._maxAttempts; attempt++) {
+            let device
+            try {
+                device = await this._adapter.requestDevice(this._descriptor)
+                // In order, so callbacks can use the values of earlier ones
+                for (const resource of this._resources) {
+                    resource.value = resource._create(device)
+                }
+                this._watch(device)
+                this.emit('recovered', { device, lost, attempt })
+                return device
+            } catch (e) {
+                device?.destroy()
+                error = e
+            }
+        }
+        this.emit('recoveryFailed', { error })
+        throw error
+    }
+
+    // Stop recovering and destroy the current device
+    destroy() {
+        const device = this._device
+        this._device = null
+        device.destroy()
+    }
+}
+
+/**
+ * WebGPU-standard GpuAdapter wrapper
+ */
+class GpuAdapter {
+    constructor(nativeAdapter) {
+        this._native = nativeAdapter
+    }
+
+    // WebGPU standard: features is a property, not a method
+    get features() {
+        return this._native.getFeatures()
+    }
+
+    // WebGPU standard: limits is a property, not a method
+    get limits() {
+        return this._native.getLimits()
+    }
+
+    // WebGPU standard: info is a property, not a method
+    get info() {
+        return this._native.getInfo()
+    }
+
+    // WebGPU standard: isFallbackAdapter is a property
+    // True for software (CPU) adapters such as lavapipe, WARP or SwiftShader
+    get isFallbackAdapter() {
+        return this._native.isFallbackAdapter
+    }
+
+    // Extension: { compliant, computeShaders, flags, shaderModel } for adapters below full WebGPU (e.g. GL)
+    getDownlevelCapabilities() {
+        return this._native.getDownlevelCapabilities()
+    }
+
+    // Extension: usages, sample counts, filtering and blending this adapter supports for a format
+    getTextureFormatFeatures(format) {
+        return this._native.getTextureFormatFeatures(format)
+    }
+
+    // Deprecated WebGPU method, kept for libraries that still call it
+    async requestAdapterInfo() {
+        return this.info
+    }
+
+    async requestDevice(descriptor = {}) {
+        const nativeDevice = await this._native.requestDevice(descriptor)
+        return new GpuDevice(nativeDevice)
+    }
+
+    // Extension: a GpuDeviceRecovery that replaces its device when it's lost
+    async requestRecoverableDevice(descriptor = {}, options) {
+        const device = await this.requestDevice(descriptor)
+        return new GpuDeviceRecovery(this, descriptor, device, options)
+    }
+}
+
+/**
+ * Window surface wrapper (GPUCanvasContext equivalent)
+ *
+ * Unwraps GpuAdapter/GpuDevice objects before passing to native methods.
+ */
+class GpuSurface {
+    constructor(nativeSurface) {
+        this._native = nativeSurface
+    }
+
+    // Extension: { formats, presentModes, alphaModes } supported on the given adapter
+    getCapabilities(adapter) {
+        return this._native.getCapabilities(adapter._native || adapter)
+    }
+
+    // Like GPUCanvasContext.configure(), plus the surface size and presentMode
+    configure(configuration) {
+        const { device, ...rest } = configuration
+        this._native.configure(device._native || device, rest)
+    }
+
+    // Extension: reconfigure for a new window size (0x0 while minimized is ignored)
+    resize(width, height) {
+        this._native.resize(width, height)
+    }
+
+    // Returns a GpuSurfaceTexture, or null if no frame was available in time;
+    // call present() on it after submitting the frame
+    getCurrentTexture() {
+        return this._native.getCurrentTexture()
+    }
+
+    // Extension: frames paced natively; each texture is presented after the
+    // callback returns, unless the callback already presented it
+    runRenderLoop(callback, options) {
+        return this._native.runRenderLoop((texture, frame) => {
+            try {
+                callback(texture, frame)
+            } finally {
+                if (!texture.presented) texture.present()
+            }
+        }, options)
+    }
+}
+
+/**
+ * WebGPU-standard Gpu wrapper (navigator.gpu equivalent)
+ */
+class Gpu {
+    constructor(nativeGpu) {
+        this._native = nativeGpu
+    }
+
+    async requestAdapter(options = {}) {
+        // Extract options from object (WebGPU standard)
+        // Native binding expects positional parameters, not object
+        const powerPreference = options.powerPreference || null
+        const forceFallbackAdapter = options.forceFallbackAdapter || false
+        const surface = options.compatibleSurface
+        const compatibleSurface = surface ? (surface._native || surface) : null
+        // Extension: { deviceType, backend, vendor, device } selection policy
+        const selector = options.selector || null
+        const nativeAdapter = await this._native.requestAdapter(powerPreference, forceFallbackAdapter, compatibleSurface, selector)
+        if (!nativeAdapter) return null
+        return new GpuAdapter(nativeAdapter)
+    }
+
+    // WebGPU standard: there is no canvas, so this is the format surfaces usually prefer
+    getPreferredCanvasFormat() {
+        return 'bgra8unorm'
+    }
+
+    // WebGPU standard: no optional WGSL language features are reported
+    get wgslLanguageFeatures() {
+        return new Set()
+    }
+
+    // Extension: structured adapter list ({ name, vendor, device, deviceType, backend })
+    enumerateAdapters() {
+        return this._native.enumerateAdapters()
+    }
+
+    // Extension: pick an exact adapter by its position in enumerateAdapters()
+    requestAdapterByIndex(index) {
+        return new GpuAdapter(this._native.requestAdapterByIndex(index))
+    }
+
+    // Extension: surface for a native window ({ platform, window, display?, screen?, hinstance? })
+    createSurface(descriptor) {
+        return new GpuSurface(this._native.createSurface(descriptor))
+    }
+
+    // Extension: surface for a native window handle Buffer, e.g. Electron's win.getNativeWindowHandle()
+    createSurfaceFromNativeWindowHandle(handle) {
+        return new GpuSurface(this._native.createSurfaceFromNativeWindowHandle(handle))
+    }
+}
+
+/**
+ * Null backend (extension)
+ *
+ * Gpu({ backends: ['null'] }) has a single adapter that never touches a GPU
+ * driver, so unit tests of GPU-using code run anywhere and deterministically.
+ * Its devices check calls against their limits and fail like real devices
+ * (thrown, or captured by an error scope), but run nothing: buffer readback
+ * returns zeros. Methods without a check accept any arguments and return
+ * inert objects that do the same.
+ *
+ * The objects stand in for the native ones, so the wrappers above work on
+ * them unchanged. Their classes are named like the native classes, which
+ * createBindGroup() and the API trace tell resources apart by.
+ */
+
+// Any unknown method returns an inert object; `then` stays undefined so
+// awaiting one doesn't hang
+function nullObject(target = {}) {
+    return new Proxy(target, {
+        get(object, property, receiver) {
+            if (property in object || typeof property === 'symbol' || property === 'then' || property === 'toJSON') {
+                return Reflect.get(object, property, receiver)
+            }
+            return () => nullObject()
+        }
+    })
+}
+
+function nullClass(name, base) {
+    Object.defineProperty(base, 'name', { value: name })
+    return base
+}
+
+function nullLabel(label) {
+    return label === undefined || label === null ? '(none)' : `'${label}'`
+}
+
+function nullFlags(flags, table) {
+    return Array.isArray(flags) ? flags.reduce((bits, name) => bits | (table[name] ?? 0), 0) : flags
+}
+
+function nullFlagNames(flags, table) {
+    return Object.keys(table).filter((name) => flags & table[name])
+}
+
+function nullMapMode(mode) {
+    if (mode === 'READ' || mode === 'WRITE') return mode
+    return mode & GPUMapMode.READ ? 'READ' : 'WRITE'
+}
+
+// Largest buffer binding size and offset alignment for a layout buffer type
+function nullBufferBindingLimits(limits, type) {
+    return type === 'storage' || type === 'read-only-storage'
+        ? { maxSize: limits.maxStorageBufferBindingSize, alignment: limits.minStorageBufferOffsetAlignment, name: 'Storage' }
+        : { maxSize: limits.maxUniformBufferBindingSize, alignment: limits.minUniformBufferOffsetAlignment, name: 'Uniform' }
+}
+
+const NullGpu = nullClass('Gpu', class {
+    constructor() {
+        return nullObject(this)
+    }
+
+    async requestAdapter() {
+        return new NullAdapter()
+    }
+
+    enumerateAdapters() {
+        return [new NullAdapter().getInfo()]
+    }
+
+    requestAdapterByIndex(index) {
+        if (index !== 0) throw new Error(`No adapter at index ${index}; the null backend has 1 adapter`)
+        return new NullAdapter()
+    }
+
+    createSurface() {
+        throw new Error('The null backend has no surfaces')
+    }
+
+    createSurfaceFromNativeWindowHandle() {
+        throw new Error('The null backend has no surfaces')
+    }
+})
+
+const NullAdapter = nullClass('GpuAdapter', class {
+    constructor() {
+        this.isFallbackAdapter = false
+        return nullObject(this)
+    }
+
+    getFeatures() {
+        return []
+    }
+
+    getLimits() {
+        return native.nullDeviceLimits()
+    }
+
+    getInfo() {
+        return { name: 'Null Adapter', vendor: 0, device: 0, deviceType: 'Cpu', backend: 'Null', driver: '', driverInfo: '' }
+    }
+
+    async requestDevice(descriptor = {}) {
+        const features = descriptor.requiredFeatures || []
+        if (features.length > 0) {
+            throw new Error(`Adapter doesn't support the required features: ${features.join(', ')}`)
+        }
+        return new NullDevice(descriptor)
+    }
+})
+
+const NullDevice = nullClass('GpuDevice', class {
+    constructor(descriptor) {
+        this.label = descriptor.label || ''
+        this.limits = native.nullDeviceLimits(descriptor)
+        this.features = new Set()
+        this.lostInfo = null
+        this.queue = new NullQueue(this)
+        this._scopes = []
+        this._callback = null
+        return nullObject(this)
+    }
+
+    // Throw `reason` from `method`, or leave it to the innermost validation scope
+    _fail(method, details, reason) {
+        const message = `${details ? `${method}(${details})` : method}: validation failed\n  ${reason}`
+        if (this._scopes.length === 0) throw new Error(message)
+        const scope = this._scopes.findLast((scope) => scope.filter === 'validation')
+        if (scope) {
+            scope.error ??= { type: 'validation', message }
+        } else {
+            this._emit({ type: 'uncapturedError', message })
+        }
+    }
+
+    // Like _fail(), for a check that returned a reason or null
+    _check(method, label, reason) {
+        if (this.lostInfo) throw new Error(`${method}: device was destroyed`)
+        if (reason) this._fail(method, `label: ${nullLabel(label)}`, reason)
+    }
+
+    _emit(event) {
+        // Delivered asynchronously, like native events
+        if (this._callback) {
+            queueMicrotask(() => this._callback?.(event))
+        } else if (event.type === 'uncapturedError') {
+            console.error(event.message)
+        }
+    }
+
+    setEventCallback(callback) {
+        this._callback = callback
+    }
+
+    pushErrorScope(filter) {
+        if (filter !== 'validation' && filter !== 'out-of-memory') {
+            throw new Error(`Invalid error filter: ${filter}`)
+        }
+        this._scopes.push({ filter, error: null })
+    }
+
+    popErrorScope() {
+        const scope = this._scopes.pop()
+        if (!scope) throw new Error('popErrorScope: no error scope to pop')
+        return scope.error
+    }
+
+    destroy() {
+        if (this.lostInfo) return
+        this.lostInfo = { reason: 'destroyed', message: 'Device was destroyed' }
+        this._emit({ type: 'deviceLost', ...this.lostInfo })
+    }
+
+    poll() {
+        return true
+    }
+
+    async waitIdle() {}
+
+    toHandle() {
+        throw new Error("toHandle: null devices can't be shared with workers")
+    }
+
+    createBuffer(descriptor) {
+        const usage = nullFlags(descriptor.usage, GPUBufferUsage)
+        this._check('createBuffer', descriptor.label, this._bufferError(descriptor.size, usage, descriptor.mappedAtCreation))
+        return new NullBuffer(this, descriptor.label, descriptor.size, usage, descriptor.mappedAtCreation)
+    }
+
+    createBufferInit(descriptor, data) {
+        const usage = nullFlags(descriptor.usage, GPUBufferUsage)
+        const size = Math.ceil(data.length / 4) * 4
+        this._check('createBufferInit', descriptor.label, this._bufferError(size, usage, false))
+        return new NullBuffer(this, descriptor.label, size, usage, false)
+    }
+
+    _bufferError(size, usage, mappedAtCreation) {
+        if (size > this.limits.maxBufferSize) {
+            return `Buffer size ${size} is greater than the maximum buffer size (${this.limits.maxBufferSize})`
+        }
+        if (!usage) return 'Buffer usage must not be empty'
+        if (usage & GPUBufferUsage.MAP_READ && usage & GPUBufferUsage.MAP_WRITE) {
+            return "MAP_READ and MAP_WRITE can't be combined"
+        }
+        for (const [map, copy] of [['MAP_READ', 'COPY_DST'], ['MAP_WRITE', 'COPY_SRC']]) {
+            const conflicting = usage & ~(GPUBufferUsage[map] | GPUBufferUsage[copy])
+            if (usage & GPUBufferUsage[map] && conflicting) {
+                return `${map} can only be combined with ${copy}, not ${nullFlagNames(conflicting, GPUBufferUsage).join(' | ')} ` +
+                    "(without the 'wgpu-mappable-primary-buffers' feature)"
+            }
+        }
+        if (mappedAtCreation && size % 4 !== 0) {
+            return `Buffers that are mapped at creation must have a size that is a multiple of 4, got ${size}`
+        }
+        return null
+    }
+
+    createTexture(descriptor) {
+        const texture = new NullTexture(descriptor)
+        // Throws for unknown formats, like native createTexture()
+        const info = typeof texture.format === 'string' ? native.getTextureFormatInfo(texture.format) : null
+        this._check('createTexture', descriptor.label, this._formatError(info, texture.usage) ?? this._textureError(texture))
+        return texture
+    }
+
+    // Null devices have no features, so formats that need one can't be created
+    _formatError(info, usage) {
+        if (info?.requiredFeature) {
+            return `Format '${info.format}' needs the '${info.requiredFeature}' feature`
+        }
+        if (info?.format === 'rg11b10ufloat' && usage & GPUTextureUsage.RENDER_ATTACHMENT) {
+            return "Format 'rg11b10ufloat' needs the 'rg11b10ufloat-renderable' feature"
+        }
+        return null
+    }
+
+    _textureError({ width, height, depthOrArrayLayers, dimension, mipLevelCount, sampleCount, usage }) {
+        const limits = this.limits
+        if (!width || !height || !depthOrArrayLayers) {
+            return `Texture size ${width}x${height}x${depthOrArrayLayers} must not be empty`
+        }
+        const [max, maxLayers, name] = {
+            '1d': [[limits.maxTextureDimension1D, 1], 1, 'maxTextureDimension1D'],
+            '2d': [[limits.maxTextureDimension2D, limits.maxTextureDimension2D], limits.maxTextureArrayLayers, 'maxTextureDimension2D'],
+            '3d': [[limits.maxTextureDimension3D, limits.maxTextureDimension3D], limits.maxTextureDimension3D, 'maxTextureDimension3D']
+        }[dimension]
+        if (width > max[0] || height > max[1] || depthOrArrayLayers > maxLayers) {
+            return `Texture size ${width}x${height}x${depthOrArrayLayers} exceeds the ${dimension} limits (${name} ${max[0]}, ${maxLayers} layers)`
+        }
+        const extent = Math.max(width, height, dimension === '3d' ? depthOrArrayLayers : 1)
+        const maxMips = Math.floor(Math.log2(extent)) + 1
+        if (mipLevelCount < 1 || mipLevelCount > maxMips) {
+            return `mipLevelCount ${mipLevelCount} must be between 1 and ${maxMips}`
+        }
+        if (sampleCount !== 1 && sampleCount !== 4) return `sampleCount ${sampleCount} must be 1 or 4`
+        if (!usage) return 'Texture usage must not be empty'
+        return null
+    }
+
+    createMultisampleTexture(view, sampleCount = 4, label) {
+        const { texture } = view
+        return this.createTexture({
+            label,
+            width: texture.width,
+            height: texture.height,
+            format: texture.format,
+            sampleCount,
+            usage: GPUTextureUsage.RENDER_ATTACHMENT
+        })
+    }
+
+    createSampler(descriptor = {}) {
+        return nullResource('GpuSampler', descriptor.label)
+    }
+
+    createBindGroupLayout(descriptor) {
+        const entries = descriptor.entries || []
+        this._check('createBindGroupLayout', descriptor.label, this._layoutError(entries))
+        return new NullBindGroupLayout(descriptor.label, entries)
+    }
+
+    _layoutError(entries) {
+        const limits = this.limits
+        const tooHigh = entries.find((entry) => entry.binding >= limits.maxBindingsPerBindGroup)
+        if (tooHigh) {
+            return `Binding ${tooHigh.binding} is not less than maxBindingsPerBindGroup (${limits.maxBindingsPerBindGroup})`
+        }
+        // Null devices have no features, so vertex shaders can't write storage
+        const vertexWritable = entries.find((entry) =>
+            entry.visibility & GPUShaderStage.VERTEX &&
+            (entry.buffer?.type === 'storage' || (entry.storageTexture && entry.storageTexture.access !== 'read-only'))
+        )
+        if (vertexWritable) {
+            return `Binding ${vertexWritable.binding} is writable storage visible to VERTEX, which needs the 'wgpu-vertex-writable-storage' feature`
+        }
+        const kinds = [
+            ['uniform buffers', 'maxUniformBuffersPerShaderStage', (entry) => entry.buffer && nullBufferBindingLimits(limits, entry.buffer.type).name === 'Uniform'],
+            ['storage buffers', 'maxStorageBuffersPerShaderStage', (entry) => entry.buffer && nullBufferBindingLimits(limits, entry.buffer.type).name === 'Storage'],
+            ['samplers', 'maxSamplersPerShaderStage', (entry) => entry.sampler],
+            ['sampled textures', 'maxSampledTexturesPerShaderStage', (entry) => entry.texture],
+            ['storage textures', 'maxStorageTexturesPerShaderStage', (entry) => entry.storageTexture]
+        ]
+        for (const [stage, bit] of Object.entries(GPUShaderStage)) {
+            for (const [kind, limit, matches] of kinds) {
+                const count = entries
+                    .filter((entry) => entry.visibility & bit && matches(entry))
+                    .reduce((total, entry) => total + (entry.count || 1), 0)
+                if (count > limits[limit]) {
+                    return `${count} ${kind} are visible to ${stage}, more than ${limit} (${limits[limit]})`
+                }
+            }
+        }
+        return null
+    }
+
+    createPipelineLayout(descriptor, bindGroupLayouts) {
+        const reason = bindGroupLayouts.length > this.limits.maxBindGroups
+            ? `${bindGroupLayouts.length} bind group layouts are more than maxBindGroups (${this.limits.maxBindGroups})`
+            : null
+        this._check('createPipelineLayout', descriptor.label, reason)
+        return new NullPipelineLayout(descriptor.label, bindGroupLayouts)
+    }
+
+    createBindGroup(descriptor, layout, entries, buffers) {
+        this._check('createBindGroup', descriptor.label, this._bindGroupError(layout, entries, buffers || []))
+        return nullResource('GpuBindGroup', descriptor.label)
+    }
+
+    _bindGroupError(layout, entries, buffers) {
+        let next = 0
+        for (const entry of entries) {
+            if (entry.resourceType !== 'buffer') {
+                next += entry.resourceType === 'buffer-array' ? entry.count : 0
+                continue
+            }
+            const buffer = buffers[next++]
+            const type = layout.entries?.find((layoutEntry) => layoutEntry.binding === entry.binding)?.buffer?.type
+            const { maxSize, alignment, name } = nullBufferBindingLimits(this.limits, type)
+            const offset = entry.offset || 0
+            const size = entry.size ?? buffer.size - offset
+            if (offset % alignment !== 0) {
+                return `Binding ${entry.binding}: offset ${offset} is not a multiple of min${name}BufferOffsetAlignment (${alignment})`
+            }
+            if (offset + size > buffer.size) {
+                return `Binding ${entry.binding}: range ${offset}..${offset + size} is out of bounds of buffer ${nullLabel(buffer.label)} (${buffer.size} bytes)`
+            }
+            if (size > maxSize) {
+                return `Binding ${entry.binding}: size ${size} is greater than max${name}BufferBindingSize (${maxSize})`
+            }
+        }
+        return null
+    }
+
+    createComputePipeline(descriptor, layout) {
+        this._check('createComputePipeline', descriptor.label, null)
+        return new NullComputePipeline(descriptor.label, layout)
+    }
+
+    createRenderPipeline(descriptor, layout) {
+        const buffers = descriptor.vertex.buffers || []
+        const reason = buffers.length > this.limits.maxVertexBuffers
+            ? `${buffers.length} vertex buffers are more than maxVertexBuffers (${this.limits.maxVertexBuffers})`
+            : null
+        this._check('createRenderPipeline', descriptor.label, reason)
+        return new NullRenderPipeline(descriptor.label, layout)
+    }
+
+    createCommandEncoder(descriptor = {}) {
+        this._check('createCommandEncoder', descriptor.label, null)
+        return new NullCommandEncoder(this)
+    }
+})
+
+const NullQueue = nullClass('GpuQueue', class {
+    constructor(device) {
+        this._device = device
+        return nullObject(this)
+    }
+
+    submit() {
+        if (this._device.lostInfo) throw new Error('submit: device was destroyed')
+    }
+
+    writeBuffer(buffer, bufferOffset, data) {
+        const reason = !(buffer.usage & GPUBufferUsage.COPY_DST)
+            ? `Buffer ${nullLabel(buffer.label)} usage is missing COPY_DST`
+            : bufferOffset % 4 !== 0
+                ? `Buffer offset ${bufferOffset} is not a multiple of 4`
+                : bufferOffset + data.length > buffer.size
+                    ? `Write of ${data.length} bytes at ${bufferOffset} is out of bounds of buffer ${nullLabel(buffer.label)} (${buffer.size} bytes)`
+                    : null
+        if (reason) this._device._fail('writeBuffer', `buffer: ${nullLabel(buffer.label)}`, reason)
+    }
+
+    uploadBuffer(buffer, bufferOffset, data) {
+        this.writeBuffer(buffer, bufferOffset, data)
+    }
+
+    async onSubmittedWorkDone() {}
+})
+
+// Most bytes readSync() reads, like native readSync()
+const NULL_READ_SYNC_LIMIT = 4 * 1024 * 1024
+
+// Mirrors the checks and messages of the native GpuBuffer
+const NullBuffer = nullClass('GpuBuffer', class {
+    constructor(device, label, size, usage, mappedAtCreation) {
+        this._device = device
+        this.label = label || ''
+        this.size = size
+        this.usage = usage
+        this.mapState = mappedAtCreation ? 'mapped' : 'unmapped'
+        // Mode and range of the current or pending map
+        this._mapped = mappedAtCreation ? { mode: 'WRITE', offset: 0, size } : null
+        // getMappedRange() ranges, which may not overlap
+        this._ranges = []
+        this._destroyed = false
+        return nullObject(this)
+    }
+
+    usageNames() {
+        return nullFlagNames(this.usage, GPUBufferUsage)
+    }
+
+    _ensureNotDestroyed(method) {
+        if (this._destroyed) throw new Error(`${method}: buffer ${nullLabel(this.label)} is destroyed`)
+    }
+
+    async mapAsync(mode, offset = 0, size) {
+        const name = nullMapMode(mode)
+        const flag = `MAP_${name}`
+        if (!(this.usage & GPUBufferUsage[flag])) {
+            throw new Error(
+                `mapAsync: buffer ${nullLabel(this.label)} can't be mapped for ${name}, ` +
+                `its usage (${this.usageNames().join(' | ')}) is missing ${flag}`
+            )
+        }
+        size ??= Math.max(this.size - offset, 0)
+        if (offset % 8 !== 0 || size % 4 !== 0) {
+            throw new Error(`mapAsync offset (${offset}) must be a multiple of 8 and size (${size}) a multiple of 4`)
+        }
+        if (offset + size > this.size) {
+            throw new Error(`mapAsync range (offset ${offset} + size ${size}) exceeds buffer size (${this.size})`)
+        }
+        if (this.mapState !== 'unmapped') {
+            throw new Error(`mapAsync: buffer ${nullLabel(this.label)} is already ${this.mapState}`)
+        }
+        this._ensureNotDestroyed('mapAsync')
+        const request = { mode: name, offset, size }
+        this._mapped = request
+        this.mapState = 'pending'
+        await null
+        if (this._mapped !== request) {
+            throw new DOMException('mapAsync: the map was aborted by unmap() or destroy() before it completed', 'AbortError')
+        }
+        this.mapState = 'mapped'
+    }
+
+    _range(method, offset = 0, size) {
+        if (this.mapState !== 'mapped') {
+            throw new Error(`Buffer must be mapped before calling ${method}(). Current state: ${this.mapState}`)
+        }
+        const rangeSize = size ?? Math.max(this.size - offset, 0)
+        if (offset % 8 !== 0) throw new Error(`Offset (${offset}) must be a multiple of 8`)
+        if (rangeSize % 4 !== 0) throw new Error(`Size (${rangeSize}) must be a multiple of 4`)
+        if (offset + rangeSize > this.size) {
+            throw new Error(`Range (offset ${offset} + size ${rangeSize}) exceeds buffer size (${this.size})`)
+        }
+        const { offset: start, size: length } = this._mapped
+        if (offset < start || offset + rangeSize > start + length) {
+            throw new Error(
+                `${method}() range [${offset}, ${offset + rangeSize}) is outside the mapped range [${start}, ${start + length})`
+            )
+        }
+        return rangeSize
+    }
+
+    _overlap(offset, size) {
+        return this._ranges.find((range) => offset < range.offset + range.size && offset + size > range.offset)
+    }
+
+    // Readback is always zeros
+    getMappedRange(offset = 0, size) {
+        const rangeSize = this._range('getMappedRange', offset, size)
+        const overlap = this._overlap(offset, rangeSize)
+        if (overlap) {
+            throw new Error(
+                `getMappedRange() range [${offset}, ${offset + rangeSize}) overlaps with existing range ` +
+                `[${overlap.offset}, ${overlap.offset + overlap.size})`
+            )
+        }
+        this._ranges.push({ offset, size: rangeSize })
+        return Buffer.alloc(rangeSize)
+    }
+
+    readMappedRange(destination, offset, size) {
+        const rangeSize = this._range('readMappedRange', offset, size)
+        if (destination.length < rangeSize) {
+            throw new Error(`Destination (${destination.length} bytes) is smaller than the range (${rangeSize} bytes)`)
+        }
+        destination.fill(0, 0, rangeSize)
+        return rangeSize
+    }
+
+    writeMapped(offset, data) {
+        const size = this._range('writeMapped', offset, data.length)
+        if (this._mapped.mode !== 'WRITE') {
+            throw new Error('writeMapped() needs a buffer mapped for writing (mapAsync(WRITE) or mappedAtCreation)')
+        }
+        const overlap = this._overlap(offset, size)
+        if (overlap) {
+            throw new Error(
+                `writeMapped() range [${offset}, ${offset + size}) overlaps with getMappedRange() range ` +
+                `[${overlap.offset}, ${overlap.offset + overlap.size})`
+            )
+        }
+    }
+
+    unmap() {
+        this.mapState = 'unmapped'
+        this._mapped = null
+        this._ranges = []
+    }
+
+    readSync(offset = 0, size) {
+        if (this.mapState !== 'unmapped') {
+            throw new Error(`Buffer must be unmapped before calling readSync(). Current state: ${this.mapState}`)
+        }
+        this._ensureNotDestroyed('readSync')
+        size ??= Math.max(this.size - offset, 0)
+        if (offset % 8 !== 0 || size % 4 !== 0) {
+            throw new Error(`readSync() offset (${offset}) must be a multiple of 8 and size (${size}) a multiple of 4`)
+        }
+        if (offset + size > this.size) {
+            throw new Error(`Range (offset ${offset} + size ${size}) exceeds buffer size (${this.size})`)
+        }
+        if (size > NULL_READ_SYNC_LIMIT) {
+            throw new Error(`readSync() reads at most ${NULL_READ_SYNC_LIMIT} bytes, got ${size}; use mapAsync() for large buffers`)
+        }
+        if (size > 0 && !(this.usage & (GPUBufferUsage.MAP_READ | GPUBufferUsage.COPY_SRC))) {
+            throw new Error('readSync() needs a buffer with MAP_READ or COPY_SRC usage')
+        }
+        return Buffer.alloc(size)
+    }
+
+    destroy() {
+        this.unmap()
+        this._destroyed = true
+    }
+})
+
+const NullTexture = nullClass('GpuTexture', class {
+    constructor(descriptor) {
+        this.label = descriptor.label || ''
+        this.width = descriptor.width
+        this.height = descriptor.height ?? 1
+        this.depthOrArrayLayers = descriptor.depth ?? 1
+        this.dimension = typeof descriptor.dimension === 'number'
+            ? ['1d', '2d', '3d'][descriptor.dimension]
+            : descriptor.dimension || '2d'
+        this.format = descriptor.format
+        this.mipLevelCount = descriptor.mipLevelCount ?? 1
+        this.sampleCount = descriptor.sampleCount ?? 1
+        this.usage = nullFlags(descriptor.usage, GPUTextureUsage)
+        return nullObject(this)
+    }
+
+    createView(descriptor) {
+        return new NullTextureView(this, descriptor?.label)
+    }
+
+    createLayerView(layer, options) {
+        return new NullTextureView(this, options?.label)
+    }
+
+    createMipView(level, label) {
+        return new NullTextureView(this, label)
+    }
+
+    usageNames() {
+        return nullFlagNames(this.usage, GPUTextureUsage)
+    }
+
+    async readPixels(mipLevel = 0) {
+        return Buffer.alloc(Math.max(this.width >> mipLevel, 1) * Math.max(this.height >> mipLevel, 1) * 4)
+    }
+
+    destroy() {}
+})
+
+const NullTextureView = nullClass('GpuTextureView', class {
+    constructor(texture, label) {
+        this.texture = texture
+        this.label = label || ''
+        return nullObject(this)
+    }
+})
+
+// Classes of resources without checks of their own, by name
+const nullResourceClasses = new Map()
+
+function nullResource(name, label) {
+    if (!nullResourceClasses.has(name)) {
+        nullResourceClasses.set(name, nullClass(name, class {
+            constructor(label) {
+                this.label = label || ''
+                return nullObject(this)
+            }
+        }))
+    }
+    return new (nullResourceClasses.get(name))(label)
+}
 
-        // Call flattened native API
-        return this._native.createBindGroup(
-            { label: descriptor.label },
-            descriptor.layout,
-            entries,
-            buffers.length > 0 ? buffers : null,
-            textures.length > 0 ? textures : null,
-            samplers.length > 0 ? samplers : null
-        )
+const NullBindGroupLayout = nullClass('GpuBindGroupLayout', class {
+    constructor(label, entries) {
+        this.label = label || ''
+        this.entries = entries
+        return nullObject(this)
     }
+})
 
-    /**
-     * Create pipeline layout (WebGPU standard API)
-     *
-     * Standard signature:
-     * createPipelineLayout({
-     *   bindGroupLayouts: [layout1, layout2]
-     * })
-     */
-    createPipelineLayout(descriptor) {
-        return this._native.createPipelineLayout(
-            { label: descriptor.label },
-            descriptor.bindGroupLayouts || []
-        )
+const NullPipelineLayout = nullClass('GpuPipelineLayout', class {
+    constructor(label, bindGroupLayouts) {
+        this.label = label || ''
+        this.bindGroupLayouts = bindGroupLayouts
+        return nullObject(this)
     }
+})
 
-    /**
-     * Create compute pipeline (WebGPU standard API)
-     *
-     * Standard signature:
-     * createComputePipeline({
-     *   layout: pipelineLayout,
-     *   compute: {
-     *     module: shaderModule,
-     *     entryPoint: 'main'
-     *   }
-     * })
-     */
-    createComputePipeline(descriptor) {
-        return this._native.createComputePipeline(
-            {
-                label: descriptor.label,
-                entryPoint: descriptor.compute.entryPoint
-            },
-            descriptor.layout,
-            descriptor.compute.module
-        )
+class NullPipeline {
+    constructor(label, layout) {
+        this.label = label || ''
+        this._layout = layout
+        return nullObject(this)
     }
 
-    /**
-     * Create render pipeline (WebGPU standard API)
-     *
-     * Standard signature:
-     * createRenderPipeline({
-     *   layout: pipelineLayout,
-     *   vertex: {
-     *     module: vertexShader,
-     *     entryPoint: 'vs_main',
-     *     buffers: [...]
-     *   },
-     *   fragment: {
-     *     module: fragmentShader,
-     *     entryPoint: 'fs_main',
-     *     targets: [...]
-     *   },
-     *   primitive: {...},
-     *   depthStencil: {...},
-     *   multisample: {...}
-     * })
-     */
-    createRenderPipeline(descriptor) {
-        const pipelineDescriptor = {
-            label: descriptor.label,
-            vertex: {
-                entryPoint: descriptor.vertex.entryPoint,
-                buffers: descriptor.vertex.buffers
-            },
-            primitive: descriptor.primitive,
-            depthStencil: descriptor.depthStencil,
-            multisample: descriptor.multisample
+    // Layouts derived for layout: 'auto' have no entries to check against
+    getBindGroupLayout(index) {
+        return this._layout?.bindGroupLayouts[index] ?? new NullBindGroupLayout('', null)
+    }
+}
+
+const NullComputePipeline = nullClass('GpuComputePipeline', class extends NullPipeline {})
+const NullRenderPipeline = nullClass('GpuRenderPipeline', class extends NullPipeline {})
+
+const NullCommandEncoder = nullClass('GpuCommandEncoder', class {
+    constructor(device) {
+        this._device = device
+        return nullObject(this)
+    }
+
+    beginComputePass() {
+        return new NullComputePass(this._device)
+    }
+
+    copyBufferToBuffer(source, sourceOffset, destination, destinationOffset, size) {
+        const label = (buffer) => `buffer ${nullLabel(buffer.label)}`
+        const reason = !(source.usage & GPUBufferUsage.COPY_SRC)
+            ? `Source ${label(source)} usage is missing COPY_SRC`
+            : !(destination.usage & GPUBufferUsage.COPY_DST)
+                ? `Destination ${label(destination)} usage is missing COPY_DST`
+                : [sourceOffset, destinationOffset, size].some((value) => value % 4 !== 0)
+                    ? 'Copy offsets and size must be multiples of 4'
+                    : sourceOffset + size > source.size
+                        ? `Copy of ${size} bytes at ${sourceOffset} is out of bounds of source ${label(source)} (${source.size} bytes)`
+                        : destinationOffset + size > destination.size
+                            ? `Copy of ${size} bytes at ${destinationOffset} is out of bounds of destination ${label(destination)} (${destination.size} bytes)`
+                            : null
+        if (reason) this._device._fail('copyBufferToBuffer', '', reason)
+    }
+
+    finish() {
+        return nullResource('GpuCommandBuffer')
+    }
+})
+
+const NullComputePass = nullClass('GpuComputePass', class {
+    constructor(device) {
+        this._device = device
+        this._error = null
+        return nullObject(this)
+    }
+
+    dispatchWorkgroups(x, y, z) {
+        y ??= 1
+        z ??= 1
+        const max = this._device.limits.maxComputeWorkgroupsPerDimension
+        if (!this._error && Math.max(x, y, z) > max) {
+            this._error = `Dispatch of ${x}x${y}x${z} workgroups exceeds maxComputeWorkgroupsPerDimension (${max})`
         }
+    }
 
-        // Fragment is optional
-        if (descriptor.fragment) {
-            pipelineDescriptor.fragment = {
-                entryPoint: descriptor.fragment.entryPoint,
-                targets: descriptor.fragment.targets
+    dispatchThreads(x, y, z, workgroupSize) {
+        const [sizeX, sizeY = 1, sizeZ = 1] = [].concat(workgroupSize)
+        this.dispatchWorkgroups(Math.ceil(x / sizeX), Math.ceil((y ?? 1) / sizeY), Math.ceil((z ?? 1) / sizeZ))
+    }
+
+    // Errors recorded in the pass surface here, like native passes
+    end() {
+        if (this._error) this._device._fail('end', '', this._error)
+    }
+})
+
+// Factory function to create wrapped Gpu instance
+// options: { backends?: ['vulkan' | 'metal' | 'dx12' | 'gl'] or ['null'], dx12ShaderCompiler?: 'fxc' | 'dxc', dxilPath?, dxcPath?,
+//            instanceFlags?: ['debug' | 'validation' | ...], glesMinorVersion?: 0 | 1 | 2 }
+function createGpu(options) {
+    if (options?.backends?.includes('null')) {
+        if (options.backends.length > 1) {
+            throw new Error("The 'null' backend can't be combined with other backends")
+        }
+        return new Gpu(new NullGpu())
+    }
+    const nativeGpu = native.Gpu.create(options)
+    return new Gpu(nativeGpu)
+}
+
+// Install a Gpu as globalThis.navigator.gpu, with the GPU* constants as globals,
+// so code written for browsers (e.g. three.js or Babylon.js WebGPU backends) runs unchanged.
+// Existing navigator.gpu and constants are kept; returns navigator.gpu
+function installNavigatorGpu(options) {
+    if (typeof globalThis.navigator !== 'object' || globalThis.navigator === null) {
+        Object.defineProperty(globalThis, 'navigator', { value: {}, writable: true, configurable: true })
+    }
+    if (!globalThis.navigator.gpu) {
+        Object.defineProperty(globalThis.navigator, 'gpu', { value: createGpu(options), writable: true, configurable: true })
+    }
+    const constants = { GPUBufferUsage, GPUMapMode, GPUTextureUsage, GPUShaderStage, GPUColorWrite }
+    for (const [name, value] of Object.entries(constants)) {
+        if (!(name in globalThis)) globalThis[name] = value
+    }
+    return globalThis.navigator.gpu
+}
+
+// Shader tools
+// Accept any ArrayBuffer or typed array view; native expects a Uint8Array
+function toUint8Array(data) {
+    if (data instanceof Uint8Array) return data
+    if (ArrayBuffer.isView(data)) return new Uint8Array(data.buffer, data.byteOffset, data.byteLength)
+    return new Uint8Array(data)
+}
+
+function spirvToWgsl(spirv) {
+    return native.spirvToWgsl(toUint8Array(spirv))
+}
+
+// Preprocessor define values may be numbers or booleans; native expects strings
+function normalizeDefines(defines) {
+    if (!defines) return defines
+    const result = {}
+    for (const [name, value] of Object.entries(defines)) {
+        result[name] = String(value)
+    }
+    return result
+}
+
+// GPUOrigin3D: { x, y, z } or [x, y, z], missing components default to 0
+function normalizeOrigin3D(origin = {}) {
+    if (Array.isArray(origin)) {
+        return { x: origin[0] || 0, y: origin[1] || 0, z: origin[2] || 0 }
+    }
+    return { x: origin.x || 0, y: origin.y || 0, z: origin.z || 0 }
+}
+
+// GPUExtent3D: { width, height, depthOrArrayLayers } or [width, height, depthOrArrayLayers]
+function normalizeExtent3D(size) {
+    if (Array.isArray(size)) {
+        return { width: size[0], height: size[1] ?? 1, depthOrArrayLayers: size[2] ?? 1 }
+    }
+    return { width: size.width, height: size.height ?? 1, depthOrArrayLayers: size.depthOrArrayLayers ?? 1 }
+}
+
+function preprocessWgsl(code, options = {}) {
+    return native.preprocessWgsl(
+        code,
+        {
+            label: options.label,
+            defines: normalizeDefines(options.defines),
+            includePaths: options.includePaths
+        },
+        options.resolveInclude
+    )
+}
+
+// Worker thread sharing
+function deviceFromHandle(handle) {
+    return new GpuDevice(native.deviceFromHandle(handle))
+}
+
+function bufferFromHandle(handle) {
+    return new GpuBuffer(native.bufferFromHandle(handle))
+}
+
+// Multi-device helpers
+function copyBufferBetweenDevices(source, destination, options) {
+    return native.copyBufferBetweenDevices(source._native || source, destination._native || destination, options)
+}
+
+function copyTextureBetweenDevices(source, destination, options) {
+    return native.copyTextureBetweenDevices(source._native || source, destination._native || destination, options)
+}
+
+// Half-precision helpers
+function packF16(values) {
+    return native.packF16(values instanceof Float32Array ? values : Float32Array.from(values))
+}
+
+function unpackF16(data) {
+    const bytes = ArrayBuffer.isView(data)
+        ? Buffer.from(data.buffer, data.byteOffset, data.byteLength)
+        : Buffer.from(data)
+    return native.unpackF16(bytes)
+}
+
+// Int8 quantization helpers
+function quantizeInt8(values, params) {
+    return native.quantizeInt8(values instanceof Float32Array ? values : Float32Array.from(values), params)
+}
+
+function dequantizeInt8(data, params) {
+    const bytes = ArrayBuffer.isView(data)
+        ? Buffer.from(data.buffer, data.byteOffset, data.byteLength)
+        : Buffer.from(data)
+    return native.dequantizeInt8(bytes, params)
+}
+
+// Indirect argument helpers: one struct or an array of them, packed into a
+// new Buffer or written into `destination` (any ArrayBuffer or view) in place
+function toBuffer(data) {
+    return ArrayBuffer.isView(data)
+        ? Buffer.from(data.buffer, data.byteOffset, data.byteLength)
+        : Buffer.from(data)
+}
+
+function packIndirectArgs(pack, args, layout, destination) {
+    const packed = pack(Array.isArray(args) ? args : [args], layout, destination ? toBuffer(destination) : null)
+    return destination || packed
+}
+
+function packDrawIndirectArgs(args, layout, destination) {
+    return packIndirectArgs(native.packDrawIndirectArgs, args, layout, destination)
+}
+
+function packDrawIndexedIndirectArgs(args, layout, destination) {
+    return packIndirectArgs(native.packDrawIndexedIndirectArgs, args, layout, destination)
+}
+
+function packDispatchIndirectArgs(args, layout, destination) {
+    return packIndirectArgs(native.packDispatchIndirectArgs, args, layout, destination)
+}
+
+function unpackDrawIndirectArgs(data, layout) {
+    return native.unpackDrawIndirectArgs(toBuffer(data), layout)
+}
+
+function unpackDrawIndexedIndirectArgs(data, layout) {
+    return native.unpackDrawIndexedIndirectArgs(toBuffer(data), layout)
+}
+
+function unpackDispatchIndirectArgs(data, layout) {
+    return native.unpackDispatchIndirectArgs(toBuffer(data), layout)
+}
+
+// Image snapshots (extension): RGBA8 PNG files, read and written with zlib
+
+const PNG_SIGNATURE = Buffer.from([0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a])
+// Channels per pixel of each PNG color type
+const PNG_CHANNELS = { 0: 1, 2: 3, 3: 1, 4: 2, 6: 4 }
+
+let crcTable = null
+
+function crc32(bytes) {
+    if (!crcTable) {
+        crcTable = new Int32Array(256).map((_, n) => {
+            for (let bit = 0; bit < 8; bit++) n = n & 1 ? 0xedb88320 ^ (n >>> 1) : n >>> 1
+            return n
+        })
+    }
+    let crc = -1
+    for (const byte of bytes) crc = crcTable[(crc ^ byte) & 0xff] ^ (crc >>> 8)
+    return (crc ^ -1) >>> 0
+}
+
+function pngChunk(type, data) {
+    const chunk = Buffer.alloc(data.length + 12)
+    chunk.writeUInt32BE(data.length, 0)
+    chunk.write(type, 4, 'latin1')
+    data.copy(chunk, 8)
+    chunk.writeUInt32BE(crc32(chunk.subarray(4, chunk.length - 4)), chunk.length - 4)
+    return chunk
+}
+
+function encodePng(width, height, pixels) {
+    const header = Buffer.alloc(13)
+    header.writeUInt32BE(width, 0)
+    header.writeUInt32BE(height, 4)
+    header.set([8, 6, 0, 0, 0], 8)
+    // Every row unfiltered
+    const rows = Buffer.alloc((width * 4 + 1) * height)
+    for (let y = 0; y < height; y++) {
+        rows.set(pixels.subarray(y * width * 4, (y + 1) * width * 4), y * (width * 4 + 1) + 1)
+    }
+    return Buffer.concat([
+        PNG_SIGNATURE,
+        pngChunk('IHDR', header),
+        pngChunk('IDAT', zlib.deflateSync(rows)),
+        pngChunk('IEND', Buffer.alloc(0))
+    ])
+}
+
+function paeth(a, b, c) {
+    const p = a + b - c
+    const pa = Math.abs(p - a)
+    const pb = Math.abs(p - b)
+    const pc = Math.abs(p - c)
+    return pa <= pb && pa <= pc ? a : pb <= pc ? b : c
+}
+
+// Decode an 8 or 16 bit, non-interlaced PNG to { width, height, pixels } with RGBA8 pixels
+function decodePng(file, path) {
+    const fail = (reason) => {
+        throw new Error(`Can't read ${path}: ${reason}`)
+    }
+    if (!file.subarray(0, 8).equals(PNG_SIGNATURE)) fail('not a PNG file')
+
+    let header = null
+    let palette = null
+    let transparency = null
+    const data = []
+    for (let offset = 8; offset + 8 <= file.length;) {
+        const length = file.readUInt32BE(offset)
+        const type = file.toString('latin1', offset + 4, offset + 8)
+        const body = file.subarray(offset + 8, offset + 8 + length)
+        if (type === 'IHDR') {
+            header = {
+                width: body.readUInt32BE(0),
+                height: body.readUInt32BE(4),
+                bitDepth: body[8],
+                colorType: body[9],
+                interlace: body[12]
             }
+        } else if (type === 'PLTE') {
+            palette = body
+        } else if (type === 'tRNS') {
+            transparency = body
+        } else if (type === 'IDAT') {
+            data.push(body)
+        } else if (type === 'IEND') {
+            break
+        }
+        offset += length + 12
+    }
+    if (!header) fail('no IHDR chunk')
+    const { width, height, bitDepth, colorType, interlace } = header
+    const channels = PNG_CHANNELS[colorType]
+    if (!channels || (bitDepth !== 8 && bitDepth !== 16) || (colorType === 3 && bitDepth !== 8) || interlace !== 0) {
+        fail(`unsupported PNG (color type ${colorType}, bit depth ${bitDepth}${interlace ? ', interlaced' : ''})`)
+    }
+
+    const bytesPerPixel = (channels * bitDepth) / 8
+    const stride = width * bytesPerPixel
+    const rows = zlib.inflateSync(Buffer.concat(data))
+    if (rows.length < (stride + 1) * height) fail('truncated image data')
+    const raw = Buffer.alloc(stride * height)
+    for (let y = 0; y < height; y++) {
+        const filter = rows[y * (stride + 1)]
+        const source = y * (stride + 1) + 1
+        const row = y * stride
+        for (let x = 0; x < stride; x++) {
+            const left = x >= bytesPerPixel ? raw[row + x - bytesPerPixel] : 0
+            const up = y > 0 ? raw[row + x - stride] : 0
+            const upLeft = y > 0 && x >= bytesPerPixel ? raw[row + x - stride - bytesPerPixel] : 0
+            const predicted = [0, left, up, (left + up) >> 1, paeth(left, up, upLeft)][filter]
+            if (predicted === undefined) fail(`invalid row filter ${filter}`)
+            raw[row + x] = rows[source + x] + predicted
         }
+    }
 
-        return this._native.createRenderPipeline(
-            pipelineDescriptor,
-            descriptor.layout,
-            descriptor.vertex.module,
-            descriptor.fragment?.module || null
-        )
+    // Most significant byte of each 16-bit sample
+    const sample = (index) => raw[index * (bitDepth / 8)]
+    const pixels = Buffer.alloc(width * height * 4)
+    for (let i = 0; i < width * height; i++) {
+        let rgba
+        if (colorType === 3) {
+            const entry = raw[i]
+            rgba = [palette[entry * 3], palette[entry * 3 + 1], palette[entry * 3 + 2], transparency?.[entry] ?? 255]
+        } else if (channels <= 2) {
+            const gray = sample(i * channels)
+            rgba = [gray, gray, gray, channels === 2 ? sample(i * channels + 1) : 255]
+        } else {
+            rgba = [sample(i * channels), sample(i * channels + 1), sample(i * channels + 2), channels === 4 ? sample(i * channels + 3) : 255]
+        }
+        pixels.set(rgba, i * 4)
     }
+    return { width, height, pixels }
+}
+
+function writePng(path, width, height, pixels) {
+    fs.mkdirSync(nodePath.dirname(path), { recursive: true })
+    fs.writeFileSync(path, encodePng(width, height, pixels))
 }
 
 /**
- * WebGPU-standard GpuAdapter wrapper
+ * Compare a texture against a reference PNG (extension)
+ *
+ * Reads mip level 0 back with texture.readPixels(), so the texture needs
+ * COPY_SRC usage and an rgba8unorm or bgra8unorm (or -srgb) format. A pixel
+ * differs when any channel differs by more than `perChannelTolerance`
+ * (default 0); the comparison fails when more than `maxDiffPercent` percent
+ * (default 0) of the pixels differ.
+ *
+ * On failure the rendered image is written next to the reference as
+ * `<name>.actual.png` and the differing pixels, in red over a faded copy of
+ * the reference, as `<name>.diff.png` (or to `diffPath`), then an error is
+ * thrown. A missing reference, or any reference with `update: true`, is
+ * written from the texture instead. Resolves to { diffPixels, diffPercent }.
  */
-class GpuAdapter {
-    constructor(nativeAdapter) {
-        this._native = nativeAdapter
+async function expectTextureMatches(texture, referencePath, options = {}) {
+    const { maxDiffPercent = 0, perChannelTolerance = 0, update = false } = options
+    const { width, height } = texture
+    const actual = await texture.readPixels()
+    if (update || !fs.existsSync(referencePath)) {
+        writePng(referencePath, width, height, actual)
+        return { diffPixels: 0, diffPercent: 0 }
+    }
+
+    const base = referencePath.replace(/\.png$/i, '')
+    const actualPath = `${base}.actual.png`
+    const reference = decodePng(fs.readFileSync(referencePath), referencePath)
+    if (reference.width !== width || reference.height !== height) {
+        writePng(actualPath, width, height, actual)
+        throw new Error(
+            `Texture is ${width}x${height} but ${referencePath} is ${reference.width}x${reference.height}; ` +
+            `the texture was written to ${actualPath}`
+        )
     }
 
-    // WebGPU standard: features is a property, not a method
-    get features() {
-        return this._native.getFeatures()
+    const diff = Buffer.alloc(actual.length)
+    let diffPixels = 0
+    for (let i = 0; i < actual.length; i += 4) {
+        let differs = false
+        for (let channel = 0; channel < 4; channel++) {
+            if (Math.abs(actual[i + channel] - reference.pixels[i + channel]) > perChannelTolerance) differs = true
+        }
+        if (differs) {
+            diffPixels++
+            diff.set([255, 0, 0, 255], i)
+        } else {
+            const [r, g, b] = reference.pixels.subarray(i, i + 3)
+            const faded = Math.round(255 - (255 - (0.299 * r + 0.587 * g + 0.114 * b)) * 0.25)
+            diff.set([faded, faded, faded, 255], i)
+        }
     }
-
-    // WebGPU standard: limits is a property, not a method
-    get limits() {
-        return this._native.getLimits()
+    const diffPercent = (diffPixels / (width * height)) * 100
+    if (diffPercent > maxDiffPercent) {
+        const diffPath = options.diffPath ?? `${base}.diff.png`
+        writePng(actualPath, width, height, actual)
+        writePng(diffPath, width, height, diff)
+        throw new Error(
+            `Texture doesn't match ${referencePath}: ${diffPixels} of ${width * height} pixels ` +
+            `(${diffPercent.toFixed(2)}%) differ by more than ${perChannelTolerance}, at most ${maxDiffPercent}% may; ` +
+            `the texture was written to ${actualPath} and the differences to ${diffPath}`
+        )
     }
+    return { diffPixels, diffPercent }
+}
 
-    // WebGPU standard: info is a property, not a method
-    get info() {
-        return this._native.getInfo()
+// API call tracing (extension): while a trace is enabled, the methods of
+// the native classes are wrapped to record each call, and the originals are
+// restored when it's disabled
+const apiTrace = {
+    capacity: 0,
+    callback: null,
+    entries: [],
+    sequence: 0,
+    // [prototype, name, original] of each wrapped method
+    originals: [],
+    // Set while recording, so calls made by the recorder aren't traced
+    recording: false,
+    fd: null,
+    filePosition: 0,
+    fileLines: 0
+}
+
+function describeTraceValue(value, depth = 0) {
+    if (value === null || value === undefined) return String(value)
+    if (typeof value === 'string') return JSON.stringify(value.length > 64 ? `${value.slice(0, 64)}...` : value)
+    if (typeof value === 'function') return 'function'
+    if (typeof value !== 'object') return String(value)
+    if (ArrayBuffer.isView(value)) return `${value.constructor.name}(${value.length ?? value.byteLength})`
+    if (value instanceof ArrayBuffer) return `ArrayBuffer(${value.byteLength})`
+
+    // Resources by class and label; wrappers by their native object
+    const object = value._native || value
+    const name = object.constructor?.name
+    if (name?.startsWith('Gpu')) {
+        let label = ''
+        try {
+            label = object.label || ''
+        } catch {
+            // Destroyed objects may not have a label any more
+        }
+        return label ? `${name}(${JSON.stringify(label)})` : name
     }
 
-    // WebGPU standard: isFallbackAdapter is a property
-    // Note: This is always false in wgpu as it doesn't support fallback adapters
-    get isFallbackAdapter() {
-        return false
+    if (depth >= 2) return Array.isArray(value) ? '[...]' : '{...}'
+    if (Array.isArray(value)) {
+        const items = value.slice(0, 8).map((item) => describeTraceValue(item, depth + 1))
+        if (value.length > 8) items.push(`...${value.length - 8} more`)
+        return `[${items.join(', ')}]`
     }
+    const fields = Object.entries(value)
+        .filter(([, field]) => field !== undefined)
+        .map(([key, field]) => `${key}: ${describeTraceValue(field, depth + 1)}`)
+    return `{ ${fields.join(', ')} }`
+}
 
-    async requestDevice(descriptor = {}) {
-        const nativeDevice = await this._native.requestDevice(descriptor)
-        return new GpuDevice(nativeDevice)
+// Append lines to the trace file, rewriting it with just the buffered
+// entries once it holds twice the capacity
+function writeTraceLines(lines) {
+    if (apiTrace.fd === null) return
+    if (apiTrace.fileLines + lines.length > apiTrace.capacity * 2) {
+        fs.ftruncateSync(apiTrace.fd, 0)
+        apiTrace.filePosition = 0
+        apiTrace.fileLines = 0
+        lines = apiTrace.entries.map(formatTraceEntry)
+    }
+    const text = lines.map((line) => `${line}\n`).join('')
+    apiTrace.filePosition += fs.writeSync(apiTrace.fd, text, apiTrace.filePosition)
+    apiTrace.fileLines += lines.length
+}
+
+function formatTraceEntry(entry) {
+    const line = `#${entry.sequence} ${new Date(entry.timestamp).toISOString()} ${entry.call}`
+    return entry.error === undefined ? line : `${line}\n  threw: ${entry.error}`
+}
+
+function recordApiCall(receiver, method, args) {
+    const entry = {
+        sequence: ++apiTrace.sequence,
+        timestamp: Date.now(),
+        call: `${describeTraceValue(receiver)}.${method}(${args.map((arg) => describeTraceValue(arg)).join(', ')})`
+    }
+    apiTrace.entries.push(entry)
+    if (apiTrace.entries.length > apiTrace.capacity) apiTrace.entries.shift()
+    // Written before the call runs, so a crash inside it is still on file
+    writeTraceLines([formatTraceEntry(entry)])
+    apiTrace.callback?.(entry)
+    return entry
+}
+
+function traceMethod(method, original) {
+    return function (...args) {
+        if (apiTrace.recording) return original.apply(this, args)
+        apiTrace.recording = true
+        let entry
+        try {
+            entry = recordApiCall(this, method, args)
+        } finally {
+            apiTrace.recording = false
+        }
+        try {
+            return original.apply(this, args)
+        } catch (error) {
+            entry.error = error.message
+            writeTraceLines([`  threw: ${error.message}`])
+            throw error
+        }
     }
 }
 
 /**
- * WebGPU-standard Gpu wrapper (navigator.gpu equivalent)
+ * Record calls into the native binding (extension)
+ *
+ * Keeps the last `capacity` calls (default 1000) with their arguments and
+ * resource labels for getApiTrace(), passes each to `callback`, and writes
+ * them to `file`, which is kept to at most twice the capacity.
  */
-class Gpu {
-    constructor(nativeGpu) {
-        this._native = nativeGpu
+function enableApiTrace(options = {}) {
+    disableApiTrace()
+    apiTrace.capacity = options.capacity ?? 1000
+    apiTrace.callback = options.callback || null
+    apiTrace.entries = []
+    if (options.file) {
+        apiTrace.fd = fs.openSync(options.file, 'w')
+        apiTrace.filePosition = 0
+        apiTrace.fileLines = 0
+    }
+
+    for (const [className, nativeClass] of Object.entries(native)) {
+        if (!className.startsWith('Gpu') || typeof nativeClass !== 'function' || !nativeClass.prototype) continue
+        const prototype = nativeClass.prototype
+        for (const name of Object.getOwnPropertyNames(prototype)) {
+            const descriptor = Object.getOwnPropertyDescriptor(prototype, name)
+            if (name === 'constructor' || typeof descriptor.value !== 'function') continue
+            apiTrace.originals.push([prototype, name, descriptor.value])
+            prototype[name] = traceMethod(name, descriptor.value)
+        }
     }
+}
 
-    async requestAdapter(options = {}) {
-        // Extract powerPreference from options object (WebGPU standard)
-        // Native binding expects string parameter, not object
-        const powerPreference = options.powerPreference || null
-        const nativeAdapter = await this._native.requestAdapter(powerPreference)
-        if (!nativeAdapter) return null
-        return new GpuAdapter(nativeAdapter)
+// Stop recording; the recorded calls stay available
+function disableApiTrace() {
+    for (const [prototype, name, original] of apiTrace.originals) {
+        prototype[name] = original
+    }
+    apiTrace.originals = []
+    apiTrace.callback = null
+    if (apiTrace.fd !== null) {
+        fs.closeSync(apiTrace.fd)
+        apiTrace.fd = null
     }
 }
 
-// Factory function to create wrapped Gpu instance
-function createGpu() {
-    const nativeGpu = native.Gpu.create()
-    return new Gpu(nativeGpu)
+// The recorded calls, oldest first: { sequence, timestamp, call, error? }
+function getApiTrace() {
+    return apiTrace.entries.slice()
+}
+
+// Native classes returned without a wrapper
+native.GpuTexture.prototype[disposeSymbol] = function () {
+    this.destroy()
+}
+native.GpuTexture.prototype[asyncDisposeSymbol] = async function () {
+    this.destroy()
+}
+
+// GPUSupportedFeatures is a setlike: iterate it like a Set of feature names
+const featuresPrototype = native.GpuSupportedFeatures.prototype
+featuresPrototype[Symbol.iterator] = function () {
+    return this.values()[Symbol.iterator]()
+}
+featuresPrototype.keys = featuresPrototype[Symbol.iterator]
+featuresPrototype.entries = function () {
+    return this.values().map((name) => [name, name])[Symbol.iterator]()
+}
+featuresPrototype.forEach = function (callback, thisArg) {
+    for (const name of this.values()) callback.call(thisArg, name, name, this)
 }
 
 // WebGPU standard constants (UPPER_SNAKE_CASE)
@@ -670,6 +3331,8 @@ function createGpu() {
 const nativeBufferUsage = native.bufferUsage()
 const nativeMapMode = native.mapMode()
 const nativeTextureUsage = native.textureUsage()
+const nativeShaderStage = native.shaderStage()
+const nativeCommandOpcode = native.commandOpcode()
 
 const GPUBufferUsage = {
     MAP_READ: nativeBufferUsage.mapRead,
@@ -697,6 +3360,41 @@ const GPUTextureUsage = {
     RENDER_ATTACHMENT: nativeTextureUsage.renderAttachment
 }
 
+const GPUShaderStage = {
+    VERTEX: nativeShaderStage.vertex,
+    FRAGMENT: nativeShaderStage.fragment,
+    COMPUTE: nativeShaderStage.compute
+}
+
+const GPUColorWrite = {
+    RED: 0x1,
+    GREEN: 0x2,
+    BLUE: 0x4,
+    ALPHA: 0x8,
+    ALL: 0xf
+}
+
+// Extension: opcodes for pass.executeCommandStream()
+const GPUCommandOpcode = {
+    SET_PIPELINE: nativeCommandOpcode.setPipeline,
+    SET_BIND_GROUP: nativeCommandOpcode.setBindGroup,
+    SET_VERTEX_BUFFER: nativeCommandOpcode.setVertexBuffer,
+    SET_INDEX_BUFFER: nativeCommandOpcode.setIndexBuffer,
+    DRAW: nativeCommandOpcode.draw,
+    DRAW_INDEXED: nativeCommandOpcode.drawIndexed,
+    DRAW_INDIRECT: nativeCommandOpcode.drawIndirect,
+    DRAW_INDEXED_INDIRECT: nativeCommandOpcode.drawIndexedIndirect,
+    DISPATCH: nativeCommandOpcode.dispatch,
+    DISPATCH_INDIRECT: nativeCommandOpcode.dispatchIndirect,
+    WHOLE_SIZE: nativeCommandOpcode.wholeSize
+}
+
+// Numeric descriptor enums, e.g. GPUTextureFormat.RGBA8UNORM. Descriptor
+// fields accept these in place of strings and skip native string matching
+const descriptorEnums = Object.fromEntries(
+    Object.entries(native.descriptorEnums()).map(([name, values]) => [name, Object.freeze(values)])
+)
+
 // Export WebGPU standard API
 module.exports = {
     // Main entry point - factory function (WebGPU standard: navigator.gpu)
@@ -721,9 +3419,63 @@ module.exports = {
     GpuSupportedFeatures: native.GpuSupportedFeatures,
     GpuComputePassEncoder: native.GpuComputePassEncoder,
     GpuRenderPassEncoder: native.GpuRenderPassEncoder,
+    GpuSurfaceTexture: native.GpuSurfaceTexture,
+    GpuRenderLoop: native.GpuRenderLoop,
 
     // Export WebGPU-standard constants (UPPER_SNAKE_CASE)
     GPUBufferUsage,
     GPUMapMode,
-    GPUTextureUsage
+    GPUTextureUsage,
+    GPUShaderStage,
+    GPUColorWrite,
+    GPUCommandOpcode,
+    ...descriptorEnums,
+
+    // WebGPU-standard error classes
+    GPUError,
+    GPUValidationError,
+    GPUOutOfMemoryError,
+
+    // Browser compatibility: globalThis.navigator.gpu
+    installNavigatorGpu,
+
+    // Shader tools
+    spirvToWgsl,
+    translateWgsl: native.translateWgsl,
+    preprocessWgsl,
+
+    // Tensors
+    GpuTensor,
+    packF16,
+    unpackF16,
+    quantizeInt8,
+    dequantizeInt8,
+
+    // Indirect argument structs
+    packDrawIndirectArgs,
+    packDrawIndexedIndirectArgs,
+    packDispatchIndirectArgs,
+    unpackDrawIndirectArgs,
+    unpackDrawIndexedIndirectArgs,
+    unpackDispatchIndirectArgs,
+
+    // Texture helpers
+    getTextureFormatInfo: native.getTextureFormatInfo,
+    expectTextureMatches,
+
+    // Multi-device helpers
+    copyBufferBetweenDevices,
+    copyTextureBetweenDevices,
+
+    // Worker thread sharing
+    deviceFromHandle,
+    bufferFromHandle,
+    textureFromHandle: native.textureFromHandle,
+    releaseHandle: native.releaseHandle,
+
+    // Diagnostics
+    setLogCallback: native.setLogCallback,
+    enableApiTrace,
+    disableApiTrace,
+    getApiTrace
 }